@@ -5,6 +5,7 @@ Handles communication with Rust via stdin/stdout.
 import json
 import sys
 import io
+import threading
 from typing import Any, Dict
 
 # Ensure UTF-8 encoding for stdin/stdout on Windows
@@ -13,6 +14,9 @@ if sys.platform == 'win32':
     sys.stdin = io.TextIOWrapper(sys.stdin.buffer, encoding='utf-8')
     sys.stdout = io.TextIOWrapper(sys.stdout.buffer, encoding='utf-8', line_buffering=True)
 
+# Held while a line is written, so lines from worker threads don't interleave
+write_lock = threading.Lock()
+
 
 def read_input() -> Dict[str, Any]:
     """
@@ -57,7 +61,9 @@ def write_progress(percent: int, stage: str = "") -> None:
         "percent": max(0, min(100, percent)),
         "stage": stage
     }
-    print(json.dumps(output, ensure_ascii=False), flush=True)
+    line = json.dumps(output, ensure_ascii=False)
+    with write_lock:
+        print(line, flush=True)
 
 
 def write_error(message: str) -> None:
@@ -69,7 +75,9 @@ def write_error(message: str) -> None:
         "type": "error",
         "message": message
     }
-    print(json.dumps(output, ensure_ascii=False), flush=True)
+    line = json.dumps(output, ensure_ascii=False)
+    with write_lock:
+        print(line, flush=True)
 
 
 def write_log(message: str, level: str = "info") -> None:
@@ -86,4 +94,6 @@ def write_log(message: str, level: str = "info") -> None:
         "level": level,
         "message": message
     }
-    print(json.dumps(output, ensure_ascii=False), flush=True)
+    line = json.dumps(output, ensure_ascii=False)
+    with write_lock:
+        print(line, flush=True)
@@ -0,0 +1,139 @@
+"""
+Base class for long-running workers.
+Instead of one input and one result, requests arrive as JSON lines on stdin:
+    {"id": 1, "action": "open_tunnel", "params": {...}}
+and each is answered with a result or error line carrying the same id. Progress
+and output lines written while a request runs carry its id too. Events the
+worker raises on its own are written as {"type": "event", "name", "data"}.
+The worker runs until stdin closes or the process is killed.
+"""
+import json
+import sys
+import threading
+import traceback
+from typing import Any, Dict, Optional
+
+# Reconfigures stdin/stdout for UTF-8 on Windows
+from . import json_io
+
+# Id of the request the current thread is handling
+_request = threading.local()
+
+
+class Cancelled(Exception):
+    """Raised inside a handler whose request was cancelled."""
+
+
+def _write(message: Dict[str, Any]) -> None:
+    """Write one line; handler threads write concurrently, so lines are serialized."""
+    line = json.dumps(message, ensure_ascii=False)
+    with json_io.write_lock:
+        sys.stdout.write(line + "\n")
+        sys.stdout.flush()
+
+
+def current_request_id() -> Optional[int]:
+    return getattr(_request, "id", None)
+
+
+def write_event(name: str, data: Optional[Dict[str, Any]] = None) -> None:
+    _write({"type": "event", "name": name, "data": data or {}})
+
+
+def write_log(message: str, level: str = "info") -> None:
+    _write({"type": "log", "level": level, "message": message})
+
+
+def write_output(line: str, stream: str = "stdout") -> None:
+    """Output of the current request's command; stream is "stdout" or "stderr"."""
+    _write({"type": "log", "id": current_request_id(), "level": stream, "message": line})
+
+
+def write_progress(percent: int, stage: str) -> None:
+    _write({"type": "progress", "id": current_request_id(), "percent": percent, "stage": stage})
+
+
+class PersistentWorker:
+    """
+    Subclasses implement handle_<action>(params) -> dict for each action.
+    Handlers run one at a time on the main thread, except actions listed in
+    CONCURRENT_ACTIONS, which each get a thread so a slow one doesn't hold up
+    the rest. Background work belongs in threads that report through
+    write_event().
+    """
+
+    CONCURRENT_ACTIONS: frozenset = frozenset()
+
+    def __init__(self):
+        self._cancelled = set()
+        self._cancel_lock = threading.Lock()
+
+    def shutdown(self) -> None:
+        """Release resources when stdin closes."""
+        pass
+
+    def is_cancelled(self) -> bool:
+        with self._cancel_lock:
+            return current_request_id() in self._cancelled
+
+    def check_cancelled(self) -> None:
+        """Raise Cancelled when the current request was cancelled."""
+        if self.is_cancelled():
+            raise Cancelled()
+
+    def handle_cancel(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        """Mark a running request cancelled; its handler stops at its next check."""
+        with self._cancel_lock:
+            self._cancelled.add(params.get("request_id"))
+        return {}
+
+    def serve(self) -> int:
+        write_log("Persistent worker started")
+        try:
+            for line in sys.stdin:
+                if not line.strip():
+                    continue
+                self._handle_line(line)
+        finally:
+            self.shutdown()
+            write_log("Persistent worker stopped")
+        return 0
+
+    def _handle_line(self, line: str) -> None:
+        try:
+            request = json.loads(line)
+        except ValueError as e:
+            _write({"type": "error", "id": None, "message": f"Invalid request: {e}"})
+            return
+
+        request_id = request.get("id")
+        action = request.get("action", "")
+        params = request.get("params") or {}
+        if action in self.CONCURRENT_ACTIONS:
+            threading.Thread(target=self._run, args=(request_id, action, params), daemon=True).start()
+        else:
+            self._run(request_id, action, params)
+
+    def _run(self, request_id: Optional[int], action: str, params: Dict[str, Any]) -> None:
+        _request.id = request_id
+        try:
+            handler = getattr(self, f"handle_{action}", None)
+            if handler is None:
+                raise ValueError(f"Unknown action: {action}")
+            result = handler(params)
+            _write({"type": "result", "id": request_id, "data": result or {}})
+        except Cancelled:
+            _write({"type": "error", "id": request_id, "message": "Cancelled"})
+        except ValueError as e:
+            _write({"type": "error", "id": request_id, "message": str(e)})
+        except Exception as e:
+            _write({"type": "error", "id": request_id, "message": f"{type(e).__name__}: {e}"})
+            write_log(traceback.format_exc(), level="error")
+        finally:
+            with self._cancel_lock:
+                self._cancelled.discard(request_id)
+            _request.id = None
+
+
+def run_persistent_worker(worker_class: type) -> None:
+    sys.exit(worker_class().serve())
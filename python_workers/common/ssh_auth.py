@@ -0,0 +1,124 @@
+"""
+SSH authentication shared by the SSH workers.
+The Rust side sends either a password, or a private key path with an optional passphrase,
+depending on the auth method selected in the server config, along with the host key it
+trusts for the server ("host_key": {key_type, fingerprint}, or null before one is trusted).
+"""
+import base64
+import hashlib
+import os
+from typing import Any, Dict, Optional
+
+import paramiko
+
+# Key types tried in order when loading a private key file
+KEY_CLASSES = (paramiko.Ed25519Key, paramiko.ECDSAKey, paramiko.RSAKey)
+
+
+def validate_auth_fields(input_data: Dict[str, Any]) -> None:
+    """Require a password or a private key."""
+    if not input_data.get("password") and not input_data.get("key_path"):
+        raise ValueError("Missing SSH credentials: provide 'password' or 'key_path'")
+
+
+def load_private_key(key_path: str, passphrase: Optional[str] = None) -> paramiko.PKey:
+    """Load an OpenSSH or PEM private key of any supported type."""
+    if not os.path.exists(key_path):
+        raise ValueError(f"SSH key file not found: {key_path}")
+
+    for key_class in KEY_CLASSES:
+        try:
+            return key_class.from_private_key_file(key_path, password=passphrase or None)
+        except paramiko.PasswordRequiredException:
+            raise ValueError("SSH key is encrypted. Provide its passphrase.")
+        except (paramiko.SSHException, ValueError):
+            continue
+
+    raise ValueError(f"Unsupported SSH key or wrong passphrase: {key_path}")
+
+
+def auth_kwargs(input_data: Dict[str, Any]) -> Dict[str, Any]:
+    """Keyword arguments for SSHClient.connect() from the worker input."""
+    kwargs: Dict[str, Any] = {"allow_agent": False, "look_for_keys": False}
+    key_path = input_data.get("key_path")
+    if key_path:
+        kwargs["pkey"] = load_private_key(key_path, input_data.get("key_passphrase"))
+    else:
+        kwargs["password"] = input_data["password"]
+    return kwargs
+
+
+def fingerprint(key: paramiko.PKey) -> str:
+    """SHA256 fingerprint as printed by ssh-keygen -l."""
+    digest = hashlib.sha256(key.asbytes()).digest()
+    return "SHA256:" + base64.b64encode(digest).decode().rstrip("=")
+
+
+class HostKeyError(ValueError):
+    """
+    The server's host key isn't the trusted one. The message wording is parsed by
+    server_monitor::known_hosts on the Rust side, so keep the two in step.
+    """
+
+
+class PinnedHostKeyPolicy(paramiko.MissingHostKeyPolicy):
+    """Accepts only the host key Atlas trusts for the server; no key is loaded into the client, so every one is checked here."""
+
+    def __init__(self, trusted: Optional[Dict[str, Any]]):
+        self.trusted = trusted
+
+    def missing_host_key(self, client, hostname, key) -> None:
+        key_type = key.get_name()
+        actual = fingerprint(key)
+        if not self.trusted:
+            raise HostKeyError(f"Unknown host key {key_type} {actual}; verify it in Atlas before connecting")
+        if actual != self.trusted.get("fingerprint"):
+            raise HostKeyError(
+                f"HOST KEY CHANGED: server presented {key_type} {actual}, expected {self.trusted.get('fingerprint')}"
+            )
+
+
+def host_key_policy(input_data: Dict[str, Any]) -> paramiko.MissingHostKeyPolicy:
+    """Policy that checks the server against the "host_key" of the worker input."""
+    return PinnedHostKeyPolicy(input_data.get("host_key"))
+
+
+def public_key_line(key: paramiko.PKey, comment: str) -> str:
+    """The authorized_keys line for a key."""
+    return f"{key.get_name()} {key.get_base64()} {comment}".strip()
+
+
+def generate_keypair(key_path: str, comment: str = "atlas", overwrite: bool = False) -> Dict[str, Any]:
+    """
+    Create an unencrypted ed25519 keypair at key_path (public key at key_path + ".pub").
+    An existing key is kept and returned unless overwrite is set.
+    """
+    if os.path.exists(key_path) and not overwrite:
+        key = load_private_key(key_path)
+    else:
+        from cryptography.hazmat.primitives import serialization
+        from cryptography.hazmat.primitives.asymmetric import ed25519
+
+        private_bytes = ed25519.Ed25519PrivateKey.generate().private_bytes(
+            encoding=serialization.Encoding.PEM,
+            format=serialization.PrivateFormat.OpenSSH,
+            encryption_algorithm=serialization.NoEncryption(),
+        )
+
+        if os.path.exists(key_path):
+            os.remove(key_path)
+        # Readable by the owner only, like ssh-keygen does
+        fd = os.open(key_path, os.O_WRONLY | os.O_CREAT | os.O_EXCL, 0o600)
+        with os.fdopen(fd, "wb") as f:
+            f.write(private_bytes)
+        key = load_private_key(key_path)
+
+    public_key = public_key_line(key, comment)
+    with open(key_path + ".pub", "w", encoding="utf-8") as f:
+        f.write(public_key + "\n")
+
+    return {
+        "public_key": public_key,
+        "key_path": key_path,
+        "fingerprint": fingerprint(key),
+    }
@@ -1,10 +1,12 @@
 import json
 import re
 from pathlib import Path
-from typing import Callable, Optional
+from typing import Any, Callable, Dict, Optional
 
 import paramiko
 
+from common.ssh_auth import HostKeyError
+
 REMOTE_BASE = "/root/discord-musicbot"
 REMOTE_MUSIC_DIR = f"{REMOTE_BASE}/music"
 REMOTE_INDEX = f"{REMOTE_MUSIC_DIR}/index.json"
@@ -43,26 +45,33 @@ class VPSSyncManager:
 
         self._remote_dir_cache: dict = {}
 
-    def connect(self, host: str, port: int, username: str, password: str) -> tuple:
-        """Connect to VPS via SSH. Returns (success, error_message)."""
+    def connect(
+        self, host: str, port: int, username: str, auth: Dict[str, Any], policy: paramiko.MissingHostKeyPolicy
+    ) -> tuple:
+        """
+        Connect to VPS via SSH with the kwargs from auth_kwargs() and the policy from
+        host_key_policy(). Returns (success, error_message).
+        """
         try:
             self.client = paramiko.SSHClient()
-            self.client.set_missing_host_key_policy(paramiko.AutoAddPolicy())
+            self.client.set_missing_host_key_policy(policy)
 
             self.client.connect(
                 hostname=host,
                 port=port,
                 username=username,
-                password=password,
                 timeout=10,
-                banner_timeout=10
+                banner_timeout=10,
+                **auth
             )
 
             self.sftp = self.client.open_sftp()
             return True, ""
 
+        except HostKeyError as e:
+            return False, str(e)
         except paramiko.AuthenticationException:
-            return False, "Authentication failed - check password"
+            return False, "Authentication failed - check password or SSH key"
         except paramiko.SSHException as e:
             return False, f"SSH error: {e}"
         except TimeoutError:
@@ -19,20 +19,24 @@ class PlaylistUploaderWorker(WorkerBase):
         action = input_data["action"]
 
         if action == "sync_from_server":
-            required = ["host", "port", "username", "password"]
+            required = ["host", "port", "username"]
             for field in required:
                 if field not in input_data:
                     raise ValueError(f"Missing required field: {field}")
+            from common.ssh_auth import validate_auth_fields
+            validate_auth_fields(input_data)
 
         elif action == "download_playlist":
             if "url" not in input_data:
                 raise ValueError("Missing required field: url")
 
         elif action == "upload_to_server":
-            required = ["host", "port", "username", "password", "track_ids"]
+            required = ["host", "port", "username", "track_ids"]
             for field in required:
                 if field not in input_data:
                     raise ValueError(f"Missing required field: {field}")
+            from common.ssh_auth import validate_auth_fields
+            validate_auth_fields(input_data)
 
     def _log(self, msg: str):
         write_log(msg)
@@ -44,6 +48,7 @@ class PlaylistUploaderWorker(WorkerBase):
 
     def sync_from_server(self, input_data: Dict[str, Any]) -> Dict[str, Any]:
         """Sync index.json, playlist.js, and playlists from server."""
+        from common.ssh_auth import auth_kwargs, host_key_policy
         from playlist_uploader.vps_sync import VPSSyncManager
 
         music_dir = Path(input_data["music_dir"])
@@ -60,7 +65,8 @@ class PlaylistUploaderWorker(WorkerBase):
             host=input_data["host"],
             port=input_data["port"],
             username=input_data["username"],
-            password=input_data["password"]
+            auth=auth_kwargs(input_data),
+            policy=host_key_policy(input_data)
         )
 
         if not success:
@@ -120,6 +126,7 @@ class PlaylistUploaderWorker(WorkerBase):
 
     def upload_to_server(self, input_data: Dict[str, Any]) -> Dict[str, Any]:
         """Upload tracks to server and restart bot."""
+        from common.ssh_auth import auth_kwargs, host_key_policy
         from playlist_uploader.vps_sync import VPSSyncManager, update_local_playlist_js
 
         music_dir = Path(input_data["music_dir"])
@@ -140,7 +147,8 @@ class PlaylistUploaderWorker(WorkerBase):
             host=input_data["host"],
             port=input_data["port"],
             username=input_data["username"],
-            password=input_data["password"]
+            auth=auth_kwargs(input_data),
+            policy=host_key_policy(input_data)
         )
 
         if not success:
@@ -0,0 +1,265 @@
+"""
+SSH Pool Worker for Atlas
+Keeps one authenticated SSH session per server profile open and runs ssh_worker
+actions (commands, system status, SFTP) on it, so they don't each pay for a key
+exchange. Sessions send protocol keepalives, are re-established in the
+background when they drop, and close after idle_timeout seconds without use.
+
+Requests on one profile share its session: connecting is serialized, after
+which each request opens its own channel on the session. A request with
+"close_when_done" (the metrics sampler's, unless told to keep the session)
+closes the session it opened once nothing else is using it.
+
+Runs until Atlas closes stdin; see common.persistent_worker for the protocol.
+
+Actions:
+    run     {profile, idle_timeout, <ssh_worker input>}
+    state   {profile}
+    close   {profile}
+    cancel  {request_id}
+"""
+import json
+import sys
+import threading
+import time
+from datetime import datetime, timezone
+from typing import Any, Dict, Optional
+
+try:
+    import paramiko
+except ImportError:
+    print(json.dumps({
+        "type": "error",
+        "message": "paramiko package not installed. Run: pip install paramiko"
+    }), flush=True)
+    sys.exit(1)
+
+from common.persistent_worker import (
+    PersistentWorker,
+    run_persistent_worker,
+    write_event,
+    write_log,
+    write_output,
+    write_progress,
+)
+from common.ssh_auth import auth_kwargs, host_key_policy
+from ssh_worker import SSHWorker
+
+# Protocol keepalive, so idle sessions aren't dropped by NAT or the server
+KEEPALIVE_SECONDS = 30
+
+# How often sessions are checked for drops and idleness
+MONITOR_INTERVAL_SECONDS = 5
+
+# Used when a request doesn't say
+DEFAULT_IDLE_TIMEOUT_SECONDS = 300
+
+# Input fields that identify a session; a request with different ones reconnects
+CONNECTION_FIELDS = (
+    "host", "port", "username", "auth_method", "password", "key_path", "key_passphrase", "host_key"
+)
+
+
+class PooledConnection(SSHWorker):
+    """An ssh_worker session whose output and progress belong to the request using it."""
+
+    def __init__(self, pool: "SSHPoolWorker", profile: str):
+        super().__init__()
+        self.pool = pool
+        self.profile = profile
+        self.params: Optional[Dict[str, Any]] = None
+        self.state = "disconnected"
+        self.connected_since: Optional[str] = None
+        self.idle_timeout = DEFAULT_IDLE_TIMEOUT_SECONDS
+        self.active = 0
+        self.last_used = time.monotonic()
+        # Held while connecting and while the request count changes
+        self.lock = threading.Lock()
+
+    def report_output(self, line: str, stream: str) -> None:
+        write_output(line, stream)
+
+    def report_progress(self, percent: int, stage: str) -> None:
+        # Uploads are stopped from their progress callback
+        self.pool.check_cancelled()
+        write_progress(percent, stage)
+
+    def check_cancelled(self) -> None:
+        self.pool.check_cancelled()
+
+    def set_state(self, state: str, message: Optional[str] = None) -> None:
+        if state == self.state:
+            return
+        self.state = state
+        data = {"profile": self.profile, "state": state}
+        if message:
+            data["message"] = message
+        write_event("connection_state", data)
+
+    def is_alive(self) -> bool:
+        transport = self.client.get_transport() if self.client else None
+        return transport is not None and transport.is_active()
+
+    def _ensure_connected(self, params: Dict[str, Any]) -> bool:
+        """
+        Connect unless the session is up with the same credentials; call with the lock held.
+        Returns whether a new session was opened.
+        """
+        fields = {field: params.get(field) for field in CONNECTION_FIELDS}
+        if fields == self.params and self.is_alive():
+            return False
+
+        # Same credentials but a dead session means it dropped
+        self.set_state("reconnecting" if fields == self.params else "connecting")
+        self.disconnect()
+        self.client = None
+        try:
+            self.connect(
+                params["host"], params["port"], params["username"], auth_kwargs(params), host_key_policy(params)
+            )
+        except Exception as e:
+            self.client = None
+            self.connected_since = None
+            self.set_state("disconnected", str(e))
+            raise
+        self.client.get_transport().set_keepalive(KEEPALIVE_SECONDS)
+        self.params = fields
+        self.connected_since = datetime.now(timezone.utc).isoformat()
+        self.set_state("connected")
+        return True
+
+    def _close_session(self) -> None:
+        """Disconnect and forget the credentials; call with the lock held."""
+        self.disconnect()
+        self.client = None
+        self.params = None
+        self.connected_since = None
+        self.set_state("disconnected")
+
+    def acquire(self, params: Dict[str, Any]) -> bool:
+        """Count a request against the session, connecting it first when needed; returns whether it connected."""
+        with self.lock:
+            self.idle_timeout = int(params.get("idle_timeout") or DEFAULT_IDLE_TIMEOUT_SECONDS)
+            opened = self._ensure_connected(params)
+            self.active += 1
+            return opened
+
+    def release(self, close: bool = False) -> None:
+        """Uncount a request; with close, the session is closed too unless another request is using it."""
+        with self.lock:
+            self.active -= 1
+            self.last_used = time.monotonic()
+            if close and not self.active and self.params is not None:
+                write_log(f"Closing SSH session to {self.profile} after a one-off request")
+                self._close_session()
+
+    def close(self) -> None:
+        with self.lock:
+            self._close_session()
+
+    def maintain(self) -> None:
+        """Close the session when idle, or bring it back when it dropped while in use recently."""
+        with self.lock:
+            if self.params is None or self.active:
+                return
+            if time.monotonic() - self.last_used >= self.idle_timeout:
+                write_log(f"Closing idle SSH session to {self.profile}")
+                self._close_session()
+                return
+            if not self.is_alive():
+                write_log(f"SSH session to {self.profile} dropped, reconnecting", level="warning")
+                try:
+                    self._ensure_connected(self.params)
+                except Exception as e:
+                    # Retried on the next check until the session goes idle
+                    write_log(f"Reconnecting to {self.profile} failed: {e}", level="warning")
+
+    def info(self) -> Dict[str, Any]:
+        # Read without the lock, which is held for as long as connecting takes
+        state = self.state
+        if state == "connected" and not self.is_alive():
+            state = "disconnected"
+        return {
+            "profile": self.profile,
+            "state": state,
+            "connected_since": self.connected_since if state == "connected" else None,
+            "active_requests": self.active,
+            "idle_seconds": int(time.monotonic() - self.last_used) if not self.active else 0,
+            "idle_timeout_seconds": self.idle_timeout,
+        }
+
+
+class SSHPoolWorker(PersistentWorker):
+
+    # Each request runs on its own thread and channel
+    CONCURRENT_ACTIONS = frozenset({"run"})
+
+    def __init__(self):
+        super().__init__()
+        self.connections: Dict[str, PooledConnection] = {}
+        self.connections_lock = threading.Lock()
+        self.stopping = threading.Event()
+        threading.Thread(target=self._monitor_loop, daemon=True).start()
+
+    def _connection(self, profile: str) -> PooledConnection:
+        with self.connections_lock:
+            connection = self.connections.get(profile)
+            if connection is None:
+                connection = PooledConnection(self, profile)
+                self.connections[profile] = connection
+            return connection
+
+    def _monitor_loop(self) -> None:
+        while not self.stopping.wait(MONITOR_INTERVAL_SECONDS):
+            with self.connections_lock:
+                connections = list(self.connections.values())
+            for connection in connections:
+                connection.maintain()
+
+    # Actions
+
+    def handle_run(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        profile = params.get("profile")
+        if not profile:
+            raise ValueError("Missing required field: profile")
+        connection = self._connection(profile)
+        connection.validate_input(params)
+
+        opened = connection.acquire(params)
+        try:
+            return connection.run_action(params)
+        except (EOFError, OSError, paramiko.SSHException) as e:
+            if connection.is_alive():
+                raise
+            # Not retried, since the command may have run; the monitor reconnects
+            raise ValueError(f"SSH connection to {profile} was lost: {e}")
+        finally:
+            # Only a session this request opened is closed, never one something else relies on
+            connection.release(close=opened and bool(params.get("close_when_done")))
+
+    def handle_state(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        profile = params.get("profile", "")
+        with self.connections_lock:
+            connection = self.connections.get(profile)
+        if connection is None:
+            return {"profile": profile, "state": "disconnected"}
+        return connection.info()
+
+    def handle_close(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        with self.connections_lock:
+            connection = self.connections.pop(params.get("profile", ""), None)
+        if connection is not None:
+            connection.close()
+        return {}
+
+    def shutdown(self) -> None:
+        self.stopping.set()
+        with self.connections_lock:
+            connections = list(self.connections.values())
+            self.connections.clear()
+        for connection in connections:
+            connection.disconnect()
+
+
+if __name__ == "__main__":
+    run_persistent_worker(SSHPoolWorker)
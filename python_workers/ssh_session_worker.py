@@ -0,0 +1,275 @@
+"""
+SSH Session Worker for Atlas
+Keeps one SSH session to the server open and forwards local ports through it
+(like `ssh -L`). A lost session is re-established in the background while the
+local listeners stay bound, so tunnels resume on their own.
+
+Runs until Atlas closes stdin; see common.persistent_worker for the protocol.
+
+Actions:
+    connect       {host, port, username, password | key_path [, key_passphrase]}
+    open_tunnel   {id, local_port, remote_host, remote_port}
+    close_tunnel  {id}
+    list_tunnels  {}
+"""
+import json
+import select
+import socket
+import sys
+import threading
+from typing import Any, Dict, Optional
+
+try:
+    import paramiko
+except ImportError:
+    print(json.dumps({
+        "type": "error",
+        "message": "paramiko package not installed. Run: pip install paramiko"
+    }), flush=True)
+    sys.exit(1)
+
+from common.persistent_worker import PersistentWorker, run_persistent_worker, write_event, write_log
+from common.ssh_auth import HostKeyError, auth_kwargs, host_key_policy, validate_auth_fields
+
+# Protocol keepalive, so idle sessions aren't dropped by NAT or the server
+KEEPALIVE_SECONDS = 30
+
+# How often the session is checked
+MONITOR_INTERVAL_SECONDS = 5
+
+# Waits between reconnect attempts; the last one repeats
+RECONNECT_DELAYS_SECONDS = [2, 5, 10, 30, 60]
+
+BUFFER_SIZE = 32 * 1024
+
+
+class Tunnel:
+    """A local listener forwarded to remote_host:remote_port as seen from the server."""
+
+    def __init__(self, tunnel_id: str, local_port: int, remote_host: str, remote_port: int):
+        self.id = tunnel_id
+        self.local_port = local_port
+        self.remote_host = remote_host
+        self.remote_port = remote_port
+        self.listener: Optional[socket.socket] = None
+        self.closed = threading.Event()
+        self.lock = threading.Lock()
+        self.bytes_sent = 0
+        self.bytes_received = 0
+        self.connections = 0
+        self.active_connections = 0
+
+    def stats(self) -> Dict[str, Any]:
+        with self.lock:
+            return {
+                "id": self.id,
+                "local_port": self.local_port,
+                "remote_host": self.remote_host,
+                "remote_port": self.remote_port,
+                "bytes_sent": self.bytes_sent,
+                "bytes_received": self.bytes_received,
+                "connections": self.connections,
+                "active_connections": self.active_connections,
+            }
+
+
+class SSHSessionWorker(PersistentWorker):
+
+    def __init__(self):
+        super().__init__()
+        self.connection: Optional[Dict[str, Any]] = None
+        self.client: Optional[paramiko.SSHClient] = None
+        self.state = "disconnected"
+        self.tunnels: Dict[str, Tunnel] = {}
+        self.client_lock = threading.RLock()
+        self.stopping = threading.Event()
+        self.monitor: Optional[threading.Thread] = None
+
+    # Session
+
+    def _connect(self) -> None:
+        params = self.connection
+        client = paramiko.SSHClient()
+        client.set_missing_host_key_policy(host_key_policy(params))
+        client.connect(
+            hostname=params["host"],
+            port=params["port"],
+            username=params["username"],
+            timeout=30,
+            **auth_kwargs(params)
+        )
+        client.get_transport().set_keepalive(KEEPALIVE_SECONDS)
+
+        with self.client_lock:
+            self._close_client()
+            self.client = client
+            self.state = "connected"
+        write_log(f"SSH session established to {params['username']}@{params['host']}:{params['port']}")
+
+    def _close_client(self) -> None:
+        with self.client_lock:
+            if self.client:
+                self.client.close()
+                self.client = None
+
+    def _transport(self) -> Optional[paramiko.Transport]:
+        with self.client_lock:
+            transport = self.client.get_transport() if self.client else None
+        return transport if transport and transport.is_active() else None
+
+    def _monitor_loop(self) -> None:
+        """Re-establish the session when it drops, while tunnels need it."""
+        while not self.stopping.wait(MONITOR_INTERVAL_SECONDS):
+            if self._transport() is not None:
+                continue
+            if not self.tunnels:
+                self.state = "disconnected"
+                continue
+
+            self.state = "reconnecting"
+            write_log("SSH session lost, reconnecting", level="warning")
+            write_event("connection_lost", {"tunnels": len(self.tunnels)})
+
+            attempt = 0
+            while not self.stopping.is_set() and self.tunnels:
+                try:
+                    self._connect()
+                    write_event("reconnected", {"attempts": attempt + 1})
+                    break
+                except Exception as e:
+                    delay = RECONNECT_DELAYS_SECONDS[min(attempt, len(RECONNECT_DELAYS_SECONDS) - 1)]
+                    attempt += 1
+                    write_log(f"Reconnect attempt {attempt} failed: {e}", level="warning")
+                    write_event("reconnect_failed", {"attempt": attempt, "message": str(e), "retry_in": delay})
+                    self.stopping.wait(delay)
+
+    # Forwarding
+
+    def _accept_loop(self, tunnel: Tunnel) -> None:
+        while not tunnel.closed.is_set():
+            try:
+                sock, address = tunnel.listener.accept()
+            except OSError:
+                break
+
+            transport = self._transport()
+            if transport is None:
+                # Refused until the session is back
+                sock.close()
+                continue
+            try:
+                channel = transport.open_channel(
+                    "direct-tcpip", (tunnel.remote_host, tunnel.remote_port), address
+                )
+            except Exception as e:
+                write_log(f"Tunnel {tunnel.local_port}: failed to open channel: {e}", level="warning")
+                sock.close()
+                continue
+
+            threading.Thread(target=self._pump, args=(tunnel, sock, channel), daemon=True).start()
+
+    def _pump(self, tunnel: Tunnel, sock: socket.socket, channel: paramiko.Channel) -> None:
+        with tunnel.lock:
+            tunnel.connections += 1
+            tunnel.active_connections += 1
+        try:
+            while not tunnel.closed.is_set():
+                readable, _, _ = select.select([sock, channel], [], [], 1.0)
+                if sock in readable:
+                    data = sock.recv(BUFFER_SIZE)
+                    if not data:
+                        break
+                    channel.sendall(data)
+                    with tunnel.lock:
+                        tunnel.bytes_sent += len(data)
+                if channel in readable:
+                    data = channel.recv(BUFFER_SIZE)
+                    if not data:
+                        break
+                    sock.sendall(data)
+                    with tunnel.lock:
+                        tunnel.bytes_received += len(data)
+        except (OSError, EOFError, paramiko.SSHException):
+            pass
+        finally:
+            channel.close()
+            sock.close()
+            with tunnel.lock:
+                tunnel.active_connections -= 1
+
+    # Actions
+
+    def handle_connect(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        for field in ["host", "port", "username"]:
+            if field not in params:
+                raise ValueError(f"Missing required field: {field}")
+        validate_auth_fields(params)
+
+        self.connection = params
+        if self._transport() is None:
+            try:
+                self._connect()
+            except HostKeyError:
+                raise
+            except paramiko.AuthenticationException:
+                raise ValueError("SSH authentication failed")
+            except Exception as e:
+                raise ValueError(f"Failed to connect: {e}")
+
+        if self.monitor is None:
+            self.monitor = threading.Thread(target=self._monitor_loop, daemon=True)
+            self.monitor.start()
+        return {"state": self.state}
+
+    def handle_open_tunnel(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        tunnel_id = params["id"]
+        local_port = int(params["local_port"])
+        if self._transport() is None:
+            raise ValueError("Not connected to the server")
+        if tunnel_id in self.tunnels:
+            raise ValueError(f"Tunnel {tunnel_id} already exists")
+
+        listener = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+        # No SO_REUSEADDR, so binding fails while anything else holds the port
+        try:
+            listener.bind(("127.0.0.1", local_port))
+            listener.listen(16)
+        except OSError as e:
+            listener.close()
+            raise ValueError(f"Local port {local_port} is already in use ({e})")
+
+        tunnel = Tunnel(tunnel_id, local_port, params["remote_host"], int(params["remote_port"]))
+        tunnel.listener = listener
+        self.tunnels[tunnel_id] = tunnel
+        threading.Thread(target=self._accept_loop, args=(tunnel,), daemon=True).start()
+
+        write_log(f"Forwarding 127.0.0.1:{local_port} -> {tunnel.remote_host}:{tunnel.remote_port}")
+        return tunnel.stats()
+
+    def handle_close_tunnel(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        tunnel = self.tunnels.pop(params["id"], None)
+        if tunnel is None:
+            raise ValueError(f"No tunnel {params['id']}")
+        tunnel.closed.set()
+        tunnel.listener.close()
+        write_log(f"Closed tunnel on local port {tunnel.local_port}")
+        return {"closed": tunnel.id}
+
+    def handle_list_tunnels(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        state = "connected" if self._transport() is not None else self.state
+        return {
+            "state": state,
+            "tunnels": [tunnel.stats() for tunnel in self.tunnels.values()],
+        }
+
+    def shutdown(self) -> None:
+        self.stopping.set()
+        for tunnel in self.tunnels.values():
+            tunnel.closed.set()
+            tunnel.listener.close()
+        self.tunnels.clear()
+        self._close_client()
+
+
+if __name__ == "__main__":
+    run_persistent_worker(SSHSessionWorker)
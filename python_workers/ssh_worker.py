@@ -7,7 +7,11 @@ import json
 import sys
 import re
 import os
-from typing import Any, Dict
+import errno
+import posixpath
+import stat
+from datetime import datetime, timezone
+from typing import Any, Dict, Optional
 
 from common.worker_base import WorkerBase, run_worker
 from common.json_io import write_log, write_progress
@@ -23,6 +27,46 @@ except ImportError:
     }), flush=True)
     sys.exit(1)
 
+from common.ssh_auth import (
+    HostKeyError,
+    auth_kwargs,
+    fingerprint,
+    generate_keypair,
+    host_key_policy,
+    validate_auth_fields,
+)
+
+# SFTP failures reported as typed errors instead of raised, by errno
+SFTP_ERROR_KINDS = {
+    errno.EACCES: "permission_denied",
+    errno.EPERM: "permission_denied",
+    errno.ENOENT: "not_found",
+    errno.EEXIST: "already_exists",
+    errno.ENOTEMPTY: "not_empty",
+}
+
+
+# Tail of a partial download compared with the remote file before resuming it
+RESUME_CHECK_BYTES = 64 * 1024
+
+DOWNLOAD_CHUNK_BYTES = 32 * 1024
+
+
+def sftp_error(kind: str, path: str, message: Optional[str] = None) -> Dict[str, Any]:
+    """Result for a failed SFTP action; the Rust side turns it into an SftpError."""
+    error: Dict[str, Any] = {"kind": kind, "path": path}
+    if message is not None:
+        error["message"] = message
+    return {"sftp_error": error}
+
+
+def sftp_error_from(e: OSError, path: str) -> Dict[str, Any]:
+    kind = SFTP_ERROR_KINDS.get(e.errno)
+    path = getattr(e, "filename", None) or path
+    if kind:
+        return sftp_error(kind, path)
+    return {"sftp_error": {"kind": "failed", "message": f"SFTP error on {path}: {e}"}}
+
 
 
 
@@ -30,6 +74,10 @@ class SSHWorker(WorkerBase):
     """
     Worker for executing SSH commands on remote servers.
 
+    Every connecting action takes either "password", or "key_path" with an
+    optional "key_passphrase", for authentication, and "host_key", the host key
+    trusted for the server (see common.ssh_auth).
+
     Input format for command execution:
     {
         "host": "server_ip",
@@ -49,6 +97,9 @@ class SSHWorker(WorkerBase):
         "action": "system_status"
     }
 
+    "action": "test_connection" takes the same fields and also reports the
+    server's host key.
+
     Input format for file upload:
     {
         "host": "server_ip",
@@ -59,6 +110,33 @@ class SSHWorker(WorkerBase):
         "local_path": "/path/to/local/file",
         "remote_path": "/path/to/remote/file"
     }
+
+    Input format for SFTP browsing ("path" may be null for the home directory
+    when listing):
+    {
+        ...connection fields,
+        "action": "sftp_list" | "sftp_stat" | "sftp_delete" | "sftp_mkdir",
+        "path": "/remote/path"
+    }
+
+    Input format for SFTP download:
+    {
+        ...connection fields,
+        "action": "sftp_download",
+        "remote_path": "/remote/file",
+        "local_path": "/local/file",
+        "partial_path": "/local/file.part",
+        "progress_threshold": 1048576,
+        "resume": false
+    }
+
+    Input format for keypair generation (no connection):
+    {
+        "action": "generate_keypair",
+        "key_path": "/path/to/private/key",
+        "overwrite": false,
+        "comment": "atlas"
+    }
     """
 
     def __init__(self):
@@ -67,20 +145,28 @@ class SSHWorker(WorkerBase):
 
     def validate_input(self, input_data: Dict[str, Any]) -> None:
         """Validate SSH connection parameters."""
-        required_fields = ["host", "port", "username", "password"]
+        if input_data.get("action") == "generate_keypair":
+            if not input_data.get("key_path"):
+                raise ValueError("Missing required field: key_path")
+            return
+
+        required_fields = ["host", "port", "username"]
 
         for field in required_fields:
             if field not in input_data:
                 raise ValueError(f"Missing required field: {field}")
+        validate_auth_fields(input_data)
 
         # Either command or action must be provided
         if "command" not in input_data and "action" not in input_data:
             raise ValueError("Either 'command' or 'action' must be provided")
 
-    def connect(self, host: str, port: int, username: str, password: str) -> None:
-        """Establish SSH connection."""
+    def connect(
+        self, host: str, port: int, username: str, auth: Dict[str, Any], policy: paramiko.MissingHostKeyPolicy
+    ) -> None:
+        """Establish SSH connection with the kwargs from auth_kwargs() and the policy from host_key_policy()."""
         self.client = paramiko.SSHClient()
-        self.client.set_missing_host_key_policy(paramiko.AutoAddPolicy())
+        self.client.set_missing_host_key_policy(policy)
 
         write_log(f"Connecting to {username}@{host}:{port}")
 
@@ -89,13 +175,17 @@ class SSHWorker(WorkerBase):
                 hostname=host,
                 port=port,
                 username=username,
-                password=password,
                 timeout=30,
-                allow_agent=False,
-                look_for_keys=False
+                **auth
             )
             write_log("SSH connection established")
+        except HostKeyError:
+            raise
         except paramiko.AuthenticationException:
+            if "pkey" in auth:
+                raise ValueError(
+                    "SSH key authentication failed. Check that the public key is in the server's authorized_keys."
+                )
             raise ValueError("SSH authentication failed. Check username and password.")
         except paramiko.SSHException as e:
             raise ValueError(f"SSH connection error: {e}")
@@ -108,6 +198,16 @@ class SSHWorker(WorkerBase):
             self.client.close()
             write_log("SSH connection closed")
 
+    def report_output(self, line: str, stream: str) -> None:
+        """Stream a line of command output; stream is "stdout" or "stderr"."""
+        write_log(line, level=stream)
+
+    def report_progress(self, percent: int, stage: str) -> None:
+        write_progress(percent, stage)
+
+    def check_cancelled(self) -> None:
+        """Raise when the request using the session was cancelled; a one-shot worker can't be."""
+
     def execute_command(self, command: str) -> Dict[str, Any]:
         """
         Execute a shell command and stream output.
@@ -125,13 +225,13 @@ class SSHWorker(WorkerBase):
         for line in stdout:
             line_text = line.rstrip('\n\r')
             output_lines.append(line_text)
-            write_log(line_text, level="stdout")
+            self.report_output(line_text, "stdout")
 
         # Stream stderr
         for line in stderr:
             line_text = line.rstrip('\n\r')
             error_lines.append(line_text)
-            write_log(line_text, level="stderr")
+            self.report_output(line_text, "stderr")
 
         # Get exit code
         exit_code = stdout.channel.recv_exit_status()
@@ -142,6 +242,14 @@ class SSHWorker(WorkerBase):
             "exit_code": exit_code
         }
 
+    def test_connection(self) -> Dict[str, Any]:
+        """Run a trivial command and report the host key the server presented."""
+        result = self.execute_command("echo 'Connection successful'")
+        key = self.client.get_transport().get_remote_server_key()
+        result["key_type"] = key.get_name()
+        result["fingerprint"] = fingerprint(key)
+        return result
+
     def get_system_status(self) -> Dict[str, Any]:
         write_log("Fetching system status")
 
@@ -246,7 +354,7 @@ class SSHWorker(WorkerBase):
                 # Only log every 10%
                 if percent >= last_percent[0] + 10 or percent == 100:
                     last_percent[0] = percent
-                    write_progress(percent, f"Uploading {file_name}: {percent}%")
+                    self.report_progress(percent, f"Uploading {file_name}: {percent}%")
                     write_log(f"Upload progress: {transferred / (1024 * 1024):.2f} MB / {total / (1024 * 1024):.2f} MB ({percent}%)")
 
             # Upload the file
@@ -274,35 +382,227 @@ class SSHWorker(WorkerBase):
         except Exception as e:
             raise ValueError(f"SFTP upload failed: {e}")
 
+    def sftp_list(self, path: Optional[str]) -> Dict[str, Any]:
+        """List a remote directory, directories first."""
+        sftp = self.client.open_sftp()
+        try:
+            target = sftp.normalize(path or ".")
+            attrs = sftp.listdir_attr(target)
+
+            entries = []
+            for attr in attrs:
+                full_path = posixpath.join(target, attr.filename)
+                mode = attr.st_mode or 0
+                is_symlink = stat.S_ISLNK(mode)
+                is_dir = stat.S_ISDIR(mode)
+                if is_symlink:
+                    # Follow links so linked directories can be browsed into
+                    try:
+                        is_dir = stat.S_ISDIR(sftp.stat(full_path).st_mode or 0)
+                    except IOError:
+                        pass
+
+                entries.append({
+                    "name": attr.filename,
+                    "path": full_path,
+                    "size": attr.st_size or 0,
+                    "modified": (
+                        datetime.fromtimestamp(attr.st_mtime, tz=timezone.utc).isoformat()
+                        if attr.st_mtime else None
+                    ),
+                    "permissions": stat.filemode(mode)[1:],
+                    "is_dir": is_dir,
+                    "is_symlink": is_symlink,
+                })
+
+            entries.sort(key=lambda e: (not e["is_dir"], e["name"].lower()))
+            write_log(f"Listed {len(entries)} entries in {target}")
+            return {"path": target, "entries": entries}
+        except IOError as e:
+            return sftp_error_from(e, path or "~")
+        finally:
+            sftp.close()
+
+    def sftp_stat(self, path: str) -> Dict[str, Any]:
+        """Size and type of a remote path."""
+        sftp = self.client.open_sftp()
+        try:
+            attrs = sftp.stat(path)
+            return {"path": path, "size": attrs.st_size or 0, "is_dir": stat.S_ISDIR(attrs.st_mode or 0)}
+        except IOError as e:
+            return sftp_error_from(e, path)
+        finally:
+            sftp.close()
+
+    def resume_offset(self, remote: "paramiko.SFTPFile", partial_path: str, size: int) -> int:
+        """
+        Bytes of partial_path that can be kept, or 0 to start over.
+        The tail of the partial file is compared with the remote file, so a
+        partial left from an older version of the file isn't continued.
+        """
+        if not os.path.exists(partial_path):
+            return 0
+        offset = os.path.getsize(partial_path)
+        if offset == 0 or offset > size:
+            return 0
+
+        overlap = min(RESUME_CHECK_BYTES, offset)
+        with open(partial_path, "rb") as local:
+            local.seek(offset - overlap)
+            local_tail = local.read(overlap)
+        remote.seek(offset - overlap)
+        if remote.read(overlap) != local_tail:
+            write_log(f"Partial download {partial_path} doesn't match the remote file, starting over")
+            return 0
+        return offset
+
+    def sftp_download(
+        self, remote_path: str, local_path: str, partial_path: str, threshold: int, resume: bool = False
+    ) -> Dict[str, Any]:
+        """
+        Download into partial_path, then move it to local_path.
+        With resume, an existing partial_path is continued rather than replaced
+        and is kept when the download fails or is cancelled.
+        """
+        sftp = self.client.open_sftp()
+        try:
+            attrs = sftp.stat(remote_path)
+            if stat.S_ISDIR(attrs.st_mode or 0):
+                return sftp_error("invalid_path", remote_path, "is a directory")
+
+            size = attrs.st_size or 0
+            file_name = posixpath.basename(remote_path)
+            last_percent = -1
+
+            with sftp.open(remote_path, "rb") as remote:
+                offset = self.resume_offset(remote, partial_path, size) if resume else 0
+                if offset:
+                    write_log(f"Resuming SFTP download of {remote_path} at {offset} of {size} bytes")
+                else:
+                    write_log(f"Starting SFTP download: {remote_path} -> {local_path} ({size} bytes)")
+
+                remote.seek(offset)
+                remote.prefetch(size)
+                transferred = offset
+                with open(partial_path, "ab" if offset else "wb") as local:
+                    while True:
+                        # Every chunk, so a cancelled download of any size stops writing right away
+                        self.check_cancelled()
+                        data = remote.read(DOWNLOAD_CHUNK_BYTES)
+                        if not data:
+                            break
+                        local.write(data)
+                        transferred += len(data)
+
+                        percent = int((transferred / size) * 100) if size else 100
+                        if size >= threshold and (percent >= last_percent + 5 or percent == 100):
+                            last_percent = percent
+                            self.report_progress(percent, f"Downloading {file_name}: {percent}%")
+
+            if transferred != size:
+                raise IOError(f"Download of {remote_path} stopped at {transferred} of {size} bytes")
+            # Atlas has already given up on a download cancelled after its last chunk
+            self.check_cancelled()
+            os.replace(partial_path, local_path)
+
+            write_log(f"Download complete: {local_path}")
+            return {"size": size, "resumed_from": offset}
+        except (IOError, OSError) as e:
+            return sftp_error_from(e, remote_path)
+        except Exception:
+            # Stopped by check_cancelled; a resumable partial file is kept for next time
+            if not resume and os.path.exists(partial_path):
+                os.remove(partial_path)
+            raise
+        finally:
+            sftp.close()
+
+    def sftp_delete(self, path: str) -> Dict[str, Any]:
+        """Delete a file or an empty directory."""
+        sftp = self.client.open_sftp()
+        try:
+            if stat.S_ISDIR(sftp.lstat(path).st_mode or 0):
+                # Servers report a non-empty rmdir as a generic failure, so check first
+                if sftp.listdir(path):
+                    return sftp_error("not_empty", path)
+                sftp.rmdir(path)
+            else:
+                sftp.remove(path)
+            write_log(f"Deleted {path}")
+            return {"deleted": path}
+        except IOError as e:
+            return sftp_error_from(e, path)
+        finally:
+            sftp.close()
+
+    def sftp_mkdir(self, path: str) -> Dict[str, Any]:
+        """Create a directory; its parent must exist."""
+        sftp = self.client.open_sftp()
+        try:
+            try:
+                sftp.stat(path)
+                return sftp_error("already_exists", path)
+            except IOError as e:
+                if e.errno != errno.ENOENT:
+                    raise
+            sftp.mkdir(path)
+            write_log(f"Created directory {path}")
+            return {"created": path}
+        except IOError as e:
+            return sftp_error_from(e, path)
+        finally:
+            sftp.close()
+
+    def run_action(self, input_data: Dict[str, Any]) -> Dict[str, Any]:
+        """Run the requested action on the connected client; commands are the default."""
+        action = input_data.get("action")
+
+        if action == "system_status":
+            return self.get_system_status()
+        elif action == "test_connection":
+            return self.test_connection()
+        elif action == "upload_file":
+            local_path = input_data.get("local_path")
+            remote_path = input_data.get("remote_path")
+            if not local_path or not remote_path:
+                raise ValueError("upload_file action requires 'local_path' and 'remote_path'")
+            return self.upload_file(local_path, remote_path)
+        elif action == "sftp_list":
+            return self.sftp_list(input_data.get("path"))
+        elif action == "sftp_stat":
+            return self.sftp_stat(input_data["path"])
+        elif action == "sftp_download":
+            return self.sftp_download(
+                input_data["remote_path"],
+                input_data["local_path"],
+                input_data["partial_path"],
+                int(input_data.get("progress_threshold", 0)),
+                bool(input_data.get("resume", False)),
+            )
+        elif action == "sftp_delete":
+            return self.sftp_delete(input_data["path"])
+        elif action == "sftp_mkdir":
+            return self.sftp_mkdir(input_data["path"])
+        return self.execute_command(input_data["command"])
+
     def process(self, input_data: Dict[str, Any]) -> Dict[str, Any]:
         """Process the SSH request."""
+        if input_data.get("action") == "generate_keypair":
+            return generate_keypair(
+                input_data["key_path"],
+                comment=input_data.get("comment", "atlas"),
+                overwrite=bool(input_data.get("overwrite", False)),
+            )
+
         host = input_data["host"]
         port = input_data["port"]
         username = input_data["username"]
-        password = input_data["password"]
 
         try:
             # Connect to the server
-            self.connect(host, port, username, password)
-
-            # Check what action to perform
-            action = input_data.get("action")
-
-            if action == "system_status":
-                result = self.get_system_status()
-            elif action == "upload_file":
-                local_path = input_data.get("local_path")
-                remote_path = input_data.get("remote_path")
-                if not local_path or not remote_path:
-                    raise ValueError("upload_file action requires 'local_path' and 'remote_path'")
-                result = self.upload_file(local_path, remote_path)
-            else:
-                # Execute the command
-                command = input_data["command"]
-                result = self.execute_command(command)
-
-            return result
+            self.connect(host, port, username, auth_kwargs(input_data), host_key_policy(input_data))
 
+            return self.run_action(input_data)
         finally:
             # Always disconnect
             self.disconnect()
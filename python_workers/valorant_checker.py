@@ -3,9 +3,10 @@
 Valorant store checker worker.
 Uses captured Riot cookies to authenticate and fetch real store data.
 """
+import json
 import os
 import sys
-from datetime import datetime
+from datetime import datetime, timedelta
 from typing import Any, Dict, List, Optional
 import urllib.parse
 
@@ -51,7 +52,17 @@ class ValorantCheckerWorker(WorkerBase):
         "date": "2024-01-15",
         "items": [...],
         "checked_at": "2024-01-15T08:00:00Z",
-        "is_real_data": true/false
+        "is_real_data": true/false,
+        "expires_at": "2024-01-16T00:00:00Z" | null,
+        "player_name": "name#tag" | null,
+        "refreshed_cookies": null | {"ssid": "...", ...},  # cookies the reauth renewed
+        "bundles": [{"uuid", "name", "image_url", "items": [...], "base_price",
+                     "bundle_price", "duration_remaining_seconds", "expires_at"}, ...],
+        "night_market": null | {
+            "expires_at": "2024-01-29T08:00:00Z",
+            "items": [{"name", "image_url", "item_type", "uuid",
+                       "original_price", "discount_percent", "final_price"}, ...]
+        }
     }
     """
 
@@ -66,9 +77,31 @@ class ValorantCheckerWorker(WorkerBase):
         "pbe": "pbe"
     }
 
+    # VP currency UUID
+    VP_UUID = "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741"
+
+    # Store item type UUID -> (valorant-api.com endpoint, item_type, name field)
+    ITEM_TYPES = {
+        "e7c63390-eda7-46e0-bb7a-a6abdacd2433": ("weapons/skinlevels", "skin", "displayName"),
+        "3ad1b2b2-acdb-4524-852f-954a76ddae0a": ("weapons/skinchromas", "skin_chroma", "displayName"),
+        "dd3bf334-87f3-40bd-b043-682a57a8dc3a": ("buddies/levels", "buddy", "displayName"),
+        "d5f120f8-ff8c-4aac-92ea-f2b5acbe9475": ("sprays", "spray", "displayName"),
+        "3f296c07-64c3-494c-923b-fe692a4fa1bd": ("playercards", "player_card", "displayName"),
+        "de7caa6b-adf7-4588-bbd1-143831e786c6": ("playertitles", "player_title", "titleText"),
+    }
+
     # Base64 encoded client platform (standard value for PC)
     CLIENT_PLATFORM = "ew0KCSJwbGF0Zm9ybVR5cGUiOiAiUEMiLA0KCSJwbGF0Zm9ybU9TIjogIldpbmRvd3MiLA0KCSJwbGF0Zm9ybU9TVmVyc2lvbiI6ICIxMC4wLjE5MDQyLjEuMjU2LjY0Yml0IiwNCgkicGxhdGZvcm1DaGlwc2V0IjogIlVua25vd24iDQp9"
 
+    def __init__(self):
+        super().__init__()
+        # valorant-api.com lookups for this run, keyed by (endpoint, uuid)
+        self._content_cache: Dict[tuple, Optional[Dict[str, Any]]] = {}
+        # The app's skin catalog (skin/level/chroma UUID -> entry), when it passed one
+        self._catalog: Dict[str, Dict[str, Any]] = {}
+        # Auth cookies as they stand after cookie reauth
+        self._session_cookies: Dict[str, str] = {}
+
     def validate_input(self, input_data: Dict[str, Any]) -> None:
         if not HAS_REQUESTS:
             write_log("Warning: requests library not installed. Using mock data.")
@@ -141,6 +174,7 @@ class ValorantCheckerWorker(WorkerBase):
 
                         if "access_token" in token_params:
                             write_log("Cookie reauth successful - got fresh tokens")
+                            self._session_cookies = self._read_session_cookies(session)
                             return {
                                 "access_token": urllib.parse.unquote(token_params.get("access_token", "")),
                                 "id_token": urllib.parse.unquote(token_params.get("id_token", "")),
@@ -172,6 +206,19 @@ class ValorantCheckerWorker(WorkerBase):
             write_log(f"Cookie reauth request failed: {e}")
             return None
 
+    def _read_session_cookies(self, session: Any) -> Dict[str, str]:
+        """Auth cookies held by the session, including any the reauth response renewed."""
+        cookies = {}
+        for name in ("tdid", "clid", "csid", "ssid"):
+            try:
+                value = session.cookies.get(name)
+            except Exception:
+                # Same cookie set for several domains
+                value = None
+            if value:
+                cookies[name] = value
+        return cookies
+
     def _cookie_reauth_fallback(self, cookies: Dict[str, Optional[str]]) -> Optional[Dict[str, str]]:
         """
         Fallback cookie reauth using requests (without TLS fingerprinting).
@@ -340,8 +387,23 @@ class ValorantCheckerWorker(WorkerBase):
         cleaned = re.sub(r'\s+Level\s+\d+$', '', name, flags=re.IGNORECASE)
         return cleaned.strip()
 
+    def _load_catalog(self, path: Optional[str]) -> None:
+        """Load the app's skin catalog so skins resolve without per-item requests."""
+        if not path:
+            return
+        try:
+            with open(path, "r", encoding="utf-8") as f:
+                self._catalog = json.load(f).get("skins", {})
+            write_log(f"Loaded skin catalog with {len(self._catalog)} entries")
+        except (OSError, ValueError) as e:
+            write_log(f"Could not load skin catalog: {e}")
+
     def _get_skin_info(self, skin_uuid: str) -> Optional[Dict[str, Any]]:
-        """Get skin info from valorant-api.com (public API)."""
+        """Get skin info from the app's catalog, or valorant-api.com (public API)."""
+        entry = self._catalog.get(skin_uuid.lower())
+        if entry:
+            return {"displayName": entry.get("name"), "displayIcon": entry.get("image_url")}
+
         # Try skin levels first (most common for store items)
         url = f"https://valorant-api.com/v1/weapons/skinlevels/{skin_uuid}"
         try:
@@ -404,15 +466,12 @@ class ValorantCheckerWorker(WorkerBase):
             single_offers = skins_panel.get("SingleItemOffers", [])
             write_log(f"Trying SingleItemOffers: found {len(single_offers)}")
 
-        # VP currency UUID
-        VP_UUID = "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741"
-
         for idx, offer in enumerate(single_offers):
             write_log(f"Processing offer {idx + 1}: keys = {list(offer.keys())}")
 
             # Get cost
             cost_info = offer.get("Cost", {})
-            vp_cost = cost_info.get(VP_UUID, 0)
+            vp_cost = cost_info.get(self.VP_UUID, 0)
 
             # Get the offer ID which might be the skin UUID directly
             offer_id = offer.get("OfferID", "")
@@ -455,9 +514,130 @@ class ValorantCheckerWorker(WorkerBase):
         write_log(f"Total items parsed: {len(items)}")
         return items
 
+    def _get_content(self, endpoint: str, uuid: str) -> Optional[Dict[str, Any]]:
+        """Look up a content item on valorant-api.com, cached for the run."""
+        key = (endpoint, uuid)
+        if key not in self._content_cache:
+            result = None
+            try:
+                response = requests.get(f"https://valorant-api.com/v1/{endpoint}/{uuid}", timeout=10)
+                if response.status_code == 200:
+                    result = response.json().get("data")
+            except Exception as e:
+                write_log(f"Content lookup failed for {endpoint}/{uuid}: {e}")
+            self._content_cache[key] = result
+        return self._content_cache[key]
+
+    def _resolve_bundle_item(self, item_type_id: str, item_uuid: str) -> Dict[str, Any]:
+        """Name, type and image of a bundle item, whatever kind of item it is."""
+        if item_type_id == "e7c63390-eda7-46e0-bb7a-a6abdacd2433":
+            info = self._get_skin_info(item_uuid)
+            return {
+                "name": info.get("displayName") if info else f"Skin {item_uuid[:8]}",
+                "item_type": "skin",
+                "image_url": info.get("displayIcon") if info else None,
+            }
+
+        endpoint, item_type, name_field = self.ITEM_TYPES.get(item_type_id, (None, "unknown", None))
+        info = self._get_content(endpoint, item_uuid) if endpoint else None
+        name = info.get(name_field) if info else None
+        return {
+            "name": name or f"{item_type.replace('_', ' ').title()} {item_uuid[:8]}",
+            "item_type": item_type,
+            "image_url": (info.get("displayIcon") or info.get("largeArt")) if info else None,
+        }
+
+    def _parse_featured_bundles(self, storefront: Dict[str, Any], now: datetime) -> List[Dict[str, Any]]:
+        """Parse the FeaturedBundle section; some weeks run more than one bundle."""
+        featured = storefront.get("FeaturedBundle", {})
+        raw_bundles = featured.get("Bundles") or ([featured["Bundle"]] if featured.get("Bundle") else [])
+        write_log(f"Found {len(raw_bundles)} featured bundle(s)")
+
+        bundles = []
+        for raw in raw_bundles:
+            bundle_uuid = raw.get("DataAssetID", "")
+            bundle_info = self._get_content("bundles", bundle_uuid) if bundle_uuid else None
+
+            items = []
+            for entry in raw.get("Items", []):
+                item = entry.get("Item", {})
+                item_uuid = item.get("ItemID", "")
+                if not item_uuid:
+                    continue
+                resolved = self._resolve_bundle_item(item.get("ItemTypeID", ""), item_uuid)
+                items.append({
+                    **resolved,
+                    "uuid": item_uuid,
+                    "amount": item.get("Amount", 1),
+                    "base_price": entry.get("BasePrice", 0),
+                    "discounted_price": entry.get("DiscountedPrice", entry.get("BasePrice", 0)),
+                })
+
+            remaining_seconds = raw.get("DurationRemainingInSeconds", 0)
+            # Bundles with no display name fall back to their most expensive item's name
+            name = bundle_info.get("displayName") if bundle_info else None
+            if not name and items:
+                name = max(items, key=lambda i: i["base_price"])["name"] + " Bundle"
+
+            bundles.append({
+                "uuid": bundle_uuid,
+                "name": name or f"Bundle {bundle_uuid[:8]}",
+                "image_url": bundle_info.get("displayIcon") if bundle_info else None,
+                "items": items,
+                "base_price": raw.get("TotalBaseCost", {}).get(self.VP_UUID, 0),
+                "bundle_price": raw.get("TotalDiscountedCost", {}).get(
+                    self.VP_UUID, raw.get("TotalBaseCost", {}).get(self.VP_UUID, 0)),
+                "duration_remaining_seconds": remaining_seconds,
+                "expires_at": (now + timedelta(seconds=remaining_seconds)).isoformat() + "Z",
+            })
+
+        return bundles
+
+    def _parse_bonus_store(self, storefront: Dict[str, Any], now: datetime) -> Optional[Dict[str, Any]]:
+        """Parse the Night Market (BonusStore) section, if one is running."""
+        bonus_store = storefront.get("BonusStore")
+        if not bonus_store:
+            return None
+
+        offers = bonus_store.get("BonusStoreOffers", [])
+        remaining_seconds = bonus_store.get("BonusStoreRemainingDurationInSeconds", 0)
+        write_log(f"Night Market: {len(offers)} offers, ends in {remaining_seconds}s")
+        if not offers or remaining_seconds <= 0:
+            return None
+
+        items = []
+        for bonus_offer in offers:
+            offer = bonus_offer.get("Offer", {})
+            original_price = offer.get("Cost", {}).get(self.VP_UUID, 0)
+            final_price = bonus_offer.get("DiscountCosts", {}).get(self.VP_UUID, original_price)
+            discount_percent = int(bonus_offer.get("DiscountPercent", 0))
+
+            rewards = offer.get("Rewards", [])
+            item_uuid = rewards[0].get("ItemID", "") if rewards else offer.get("OfferID", "")
+            if not item_uuid:
+                continue
+
+            skin_info = self._get_skin_info(item_uuid)
+            items.append({
+                "name": skin_info.get("displayName", "Unknown Skin") if skin_info else f"Skin {item_uuid[:8]}",
+                "image_url": skin_info.get("displayIcon") if skin_info else None,
+                "item_type": "skin",
+                "uuid": item_uuid,
+                "original_price": original_price,
+                "discount_percent": discount_percent,
+                "final_price": final_price
+            })
+
+        expires_at = now + timedelta(seconds=remaining_seconds)
+        return {
+            "expires_at": expires_at.isoformat() + "Z",
+            "items": items
+        }
+
     def process(self, input_data: Dict[str, Any]) -> Dict[str, Any]:
         region = input_data.get("region", "ap")
         cookies = input_data.get("cookies")
+        self._load_catalog(input_data.get("content_cache_path"))
 
         shard = self.REGION_SHARD_MAP.get(region, "ap")
 
@@ -465,6 +645,10 @@ class ValorantCheckerWorker(WorkerBase):
         write_progress(0, "Initializing...")
 
         items = []
+        night_market = None
+        bundles = []
+        expires_at = None
+        player_name = None
         use_mock = True
 
         # Try real API if we have cookies
@@ -496,6 +680,11 @@ class ValorantCheckerWorker(WorkerBase):
 
                     # IMPORTANT: Always use PUUID from fresh userinfo, NOT from stored cookies
                     # Using old cookie PUUID could return wrong account's store
+                    if player_info:
+                        acct = player_info.get("acct", {})
+                        if acct.get("game_name") and acct.get("tag_line"):
+                            player_name = f"{acct['game_name']}#{acct['tag_line']}"
+
                     if player_info and player_info.get("sub"):
                         puuid = player_info["sub"]
                         write_log(f"Using PUUID from userinfo: {puuid[:8]}...")
@@ -517,6 +706,12 @@ class ValorantCheckerWorker(WorkerBase):
                         if storefront:
                             write_progress(80, "Parsing store data...")
                             items = self._parse_storefront(storefront)
+                            night_market = self._parse_bonus_store(storefront, datetime.utcnow())
+                            bundles = self._parse_featured_bundles(storefront, datetime.utcnow())
+                            reset_seconds = storefront.get("SkinsPanelLayout", {}).get(
+                                "SingleItemOffersRemainingDurationInSeconds", 0)
+                            if reset_seconds:
+                                expires_at = datetime.utcnow() + timedelta(seconds=reset_seconds)
                             use_mock = False
                             write_log(f"Got {len(items)} items from real store")
                         else:
@@ -538,12 +733,22 @@ class ValorantCheckerWorker(WorkerBase):
 
         write_progress(100, "Store check complete!")
 
+        refreshed_cookies = {
+            name: value for name, value in self._session_cookies.items()
+            if cookies and value != cookies.get(name)
+        }
+
         now = datetime.utcnow()
         return {
             "date": now.strftime("%Y-%m-%d"),
             "items": items,
             "checked_at": now.isoformat() + "Z",
-            "is_real_data": not use_mock
+            "is_real_data": not use_mock,
+            "expires_at": expires_at.isoformat() + "Z" if expires_at else None,
+            "player_name": player_name,
+            "refreshed_cookies": refreshed_cookies or None,
+            "bundles": bundles,
+            "night_market": night_market
         }
 
 
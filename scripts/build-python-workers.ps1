@@ -23,6 +23,8 @@ $Workers = @(
     "yt_dlp_worker.py",
     "valorant_checker.py",
     "ssh_worker.py",
+    "ssh_session_worker.py",
+    "ssh_pool_worker.py",
     "playlist_uploader_worker.py",
     "audio_separator.py",
     "audio_event_detector.py",
@@ -132,6 +134,18 @@ $WorkerConfig = @{
         "--hidden-import=bcrypt",
         "--hidden-import=nacl"
     )
+    "ssh_session_worker" = @(
+        "--hidden-import=paramiko",
+        "--hidden-import=cryptography",
+        "--hidden-import=bcrypt",
+        "--hidden-import=nacl"
+    )
+    "ssh_pool_worker" = @(
+        "--hidden-import=paramiko",
+        "--hidden-import=cryptography",
+        "--hidden-import=bcrypt",
+        "--hidden-import=nacl"
+    )
     "playlist_uploader_worker" = @(
         "--hidden-import=yt_dlp",
         "--hidden-import=paramiko",
@@ -1,7 +1,10 @@
 // Riot authentication command handlers
 use crate::file_manager::{read_json_file, write_json_file};
-use crate::models::{AuthStatus, RiotAuthCookies, Settings, ValorantCredentials};
-use crate::utils::{get_auth_json_path, get_settings_json_path};
+use crate::models::{
+    AuthStatus, RiotAccount, RiotAccounts, RiotAuthCookies, Settings, ValorantAccountInfo, ValorantCredentials,
+};
+use crate::utils::{get_auth_json_path, get_riot_accounts_json_path, get_settings_json_path};
+use crate::valorant::store_scheduler;
 use log::{debug, info, warn};
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 use url::Url;
@@ -10,8 +13,9 @@ const RIOT_AUTH_URL: &str = "https://playvalorant.com/en-us/platform-selection/"
 const RIOT_AUTH_DOMAIN: &str = "auth.riotgames.com";
 
 /// Open the Riot authentication window
+/// `add_account` opens it without the saved browser session, so a different account can log in
 #[tauri::command]
-pub async fn open_auth_window(app: AppHandle) -> Result<(), String> {
+pub async fn open_auth_window(app: AppHandle, add_account: Option<bool>) -> Result<(), String> {
     // Check if auth window already exists
     if app.get_webview_window("riot-auth").is_some() {
         return Err("Auth window already open".to_string());
@@ -29,6 +33,7 @@ pub async fn open_auth_window(app: AppHandle) -> Result<(), String> {
     .inner_size(500.0, 700.0)
     .resizable(true)
     .center()
+    .incognito(add_account.unwrap_or(false))
     .build()
     .map_err(|e| format!("Failed to create auth window: {}", e))?;
 
@@ -79,9 +84,10 @@ pub async fn capture_auth_cookies(app: AppHandle) -> Result<bool, String> {
 
     auth_cookies.captured_at = Some(chrono::Utc::now().to_rfc3339());
 
-    // Save cookies to auth.json
-    let auth_path = get_auth_json_path();
-    write_json_file(&auth_path, &auth_cookies)?;
+    // Save cookies to this account only and make it the active one
+    let mut accounts = load_riot_accounts()?;
+    add_or_update_account(&mut accounts, auth_cookies.clone());
+    save_riot_accounts(&accounts)?;
 
     // Update settings with PUUID if available
     if let Some(ref puuid) = auth_cookies.sub {
@@ -115,6 +121,61 @@ pub async fn close_auth_window(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Load riot_accounts.json, migrating the single-account auth.json the first time
+pub(crate) fn load_riot_accounts() -> Result<RiotAccounts, String> {
+    let path = get_riot_accounts_json_path();
+    if path.exists() {
+        return read_json_file(&path);
+    }
+
+    let mut accounts = RiotAccounts::default();
+    let legacy_path = get_auth_json_path();
+    if legacy_path.exists() {
+        if let Ok(cookies) = read_json_file::<RiotAuthCookies>(&legacy_path) {
+            if cookies.is_complete() {
+                add_or_update_account(&mut accounts, cookies);
+            }
+        }
+        save_riot_accounts(&accounts)?;
+        if let Err(e) = std::fs::remove_file(&legacy_path) {
+            warn!("Failed to remove migrated auth file: {}", e);
+        }
+        info!("Migrated auth.json to riot_accounts.json");
+    }
+
+    Ok(accounts)
+}
+
+pub(crate) fn save_riot_accounts(accounts: &RiotAccounts) -> Result<(), String> {
+    write_json_file(&get_riot_accounts_json_path(), accounts)
+}
+
+/// Store cookies under their account (PUUID), leaving other accounts untouched, and make it active
+fn add_or_update_account(accounts: &mut RiotAccounts, cookies: RiotAuthCookies) {
+    let id = cookies.sub.clone().unwrap_or_else(|| "default".to_string());
+
+    match accounts.find_mut(&id) {
+        Some(account) => account.cookies = cookies,
+        None => {
+            let credentials = crate::commands::settings::get_settings()
+                .unwrap_or_default()
+                .valorant_credentials;
+            let is_first = accounts.accounts.is_empty();
+            accounts.accounts.push(RiotAccount {
+                id: id.clone(),
+                name: credentials.as_ref().filter(|_| is_first).and_then(|c| c.username.clone()),
+                region: credentials.map(|c| c.region).unwrap_or_else(|| "ap".to_string()),
+                cookies,
+            });
+            if is_first {
+                crate::commands::valorant::adopt_legacy_store_history(&id);
+            }
+        }
+    }
+
+    accounts.active_account_id = Some(id);
+}
+
 /// Update settings with PUUID
 fn update_settings_puuid(puuid: &str) -> Result<(), String> {
     let settings_path = get_settings_json_path();
@@ -141,14 +202,11 @@ fn update_settings_puuid(puuid: &str) -> Result<(), String> {
 /// Get current authentication status
 #[tauri::command]
 pub fn get_auth_status() -> Result<AuthStatus, String> {
-    let auth_path = get_auth_json_path();
     let settings_path = get_settings_json_path();
 
-    let auth_cookies: Option<RiotAuthCookies> = if auth_path.exists() {
-        read_json_file(&auth_path).ok()
-    } else {
-        None
-    };
+    let accounts = load_riot_accounts()?;
+    let account = accounts.resolve(None);
+    let auth_cookies = account.map(|a| &a.cookies);
 
     let settings: Settings = if settings_path.exists() {
         read_json_file(&settings_path)?
@@ -168,16 +226,17 @@ pub fn get_auth_status() -> Result<AuthStatus, String> {
     Ok(AuthStatus {
         is_authenticated,
         has_full_cookies,
-        username: settings
-            .valorant_credentials
-            .as_ref()
-            .and_then(|c| c.username.clone()),
-        region: settings
-            .valorant_credentials
-            .as_ref()
-            .map(|c| c.region.clone())
+        username: account.and_then(|a| a.name.clone()).or_else(|| {
+            settings
+                .valorant_credentials
+                .as_ref()
+                .and_then(|c| c.username.clone())
+        }),
+        region: account
+            .map(|a| a.region.clone())
+            .or_else(|| settings.valorant_credentials.as_ref().map(|c| c.region.clone()))
             .unwrap_or_else(|| "ap".to_string()),
-        puuid: auth_cookies.as_ref().and_then(|c| c.sub.clone()),
+        puuid: auth_cookies.and_then(|c| c.sub.clone()),
         expires_hint: if has_full_cookies {
             Some("3 weeks".to_string())
         } else if is_authenticated {
@@ -188,33 +247,65 @@ pub fn get_auth_status() -> Result<AuthStatus, String> {
     })
 }
 
-/// Get stored credentials (cookies) for the Python worker
+/// Get stored credentials (cookies) of the active account for the Python worker
 #[tauri::command]
 pub fn get_stored_credentials() -> Result<Option<RiotAuthCookies>, String> {
-    let auth_path = get_auth_json_path();
+    let accounts = load_riot_accounts()?;
 
-    if !auth_path.exists() {
-        return Ok(None);
-    }
+    Ok(accounts
+        .resolve(None)
+        .map(|a| a.cookies.clone())
+        .filter(|cookies| cookies.is_complete()))
+}
 
-    let cookies: RiotAuthCookies = read_json_file(&auth_path)?;
+/// Remove an account's stored authentication (the active account when None)
+/// Its store history is kept, so logging back in restores it
+#[tauri::command]
+pub fn logout(account_id: Option<String>) -> Result<(), String> {
+    let mut accounts = load_riot_accounts()?;
+    let Some(id) = accounts.resolve(account_id.as_deref()).map(|a| a.id.clone()) else {
+        return Ok(());
+    };
 
-    if cookies.is_complete() {
-        Ok(Some(cookies))
-    } else {
-        Ok(None)
+    accounts.accounts.retain(|a| a.id != id);
+    if accounts.active_account_id.as_deref() == Some(id.as_str()) {
+        accounts.active_account_id = accounts.accounts.first().map(|a| a.id.clone());
     }
+
+    save_riot_accounts(&accounts)?;
+    store_scheduler::rearm();
+    Ok(())
 }
 
-/// Clear stored authentication
+/// All captured Riot accounts
 #[tauri::command]
-pub fn logout() -> Result<(), String> {
-    let auth_path = get_auth_json_path();
+pub fn list_valorant_accounts() -> Result<Vec<ValorantAccountInfo>, String> {
+    let accounts = load_riot_accounts()?;
+
+    Ok(accounts
+        .accounts
+        .iter()
+        .map(|a| ValorantAccountInfo {
+            id: a.id.clone(),
+            name: a.name.clone(),
+            region: a.region.clone(),
+            is_active: accounts.active_account_id.as_deref() == Some(a.id.as_str()),
+            has_full_cookies: a.cookies.has_full_auth(),
+            captured_at: a.cookies.captured_at.clone(),
+        })
+        .collect())
+}
 
-    if auth_path.exists() {
-        std::fs::remove_file(&auth_path)
-            .map_err(|e| format!("Failed to remove auth file: {}", e))?;
+/// Switch the account the Valorant store commands use by default
+#[tauri::command]
+pub fn set_active_valorant_account(id: String) -> Result<(), String> {
+    let mut accounts = load_riot_accounts()?;
+    if accounts.find(&id).is_none() {
+        return Err(format!("Unknown Valorant account: {}", id));
     }
 
-    Ok(())
+    accounts.active_account_id = Some(id.clone());
+    save_riot_accounts(&accounts)?;
+    store_scheduler::rearm();
+    update_settings_puuid(&id)
 }
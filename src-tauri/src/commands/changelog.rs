@@ -0,0 +1,267 @@
+// Release notes for the updater
+// Notes come from the GitHub releases of the Atlas repository, plus the `notes` of the selected
+// channel's update manifest for builds that aren't published there. Everything fetched is cached in
+// update_changelog.json, so the update prompt and the "What's new" dialog still work offline
+use crate::commands::updater::{channel_endpoint, selected_channel};
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::{ChangelogCache, ChangelogEntry, WhatsNew};
+use crate::utils::get_update_changelog_json_path;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const GITHUB_RELEASES_URL: &str = "https://api.github.com/repos/KaiC5504/Atlas/releases";
+
+/// Releases requested from GitHub per fetch
+const RELEASES_PER_FETCH: usize = 20;
+
+/// Releases returned before the requested one
+const RECENT_RELEASES: usize = 5;
+
+/// Age after which the cache is fetched again
+const CACHE_MAX_AGE_HOURS: i64 = 6;
+
+/// Minimum gap between fetches for a version that isn't published yet; GitHub allows 60 requests an hour
+const MISSING_VERSION_RETRY_MINUTES: i64 = 5;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+lazy_static! {
+    /// Notes of the update installed before this run, until the UI takes them
+    static ref WHATS_NEW: Mutex<Option<WhatsNew>> = Mutex::new(None);
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    body: Option<String>,
+    published_at: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateManifest {
+    version: String,
+    notes: Option<String>,
+    pub_date: Option<String>,
+}
+
+/// "v1.6.2" -> "1.6.2"
+fn normalize_version(version: &str) -> String {
+    version.trim().trim_start_matches('v').to_string()
+}
+
+/// Semver order; tags that aren't semver count as older than any that are, and compare as text
+/// among themselves, so the order stays total for sorting
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (semver::Version::parse(a), semver::Version::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Greater,
+        (Err(_), Ok(_)) => Ordering::Less,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+fn fetch_github_releases() -> Result<Vec<ChangelogEntry>, String> {
+    let releases: Vec<GithubRelease> = ureq::get(GITHUB_RELEASES_URL)
+        .query("per_page", &RELEASES_PER_FETCH.to_string())
+        .set("User-Agent", "Atlas")
+        .set("Accept", "application/vnd.github+json")
+        .timeout(REQUEST_TIMEOUT)
+        .call()
+        .map_err(|e| format!("GitHub releases request failed: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Invalid GitHub releases response: {}", e))?;
+
+    Ok(releases
+        .into_iter()
+        .filter(|r| !r.draft)
+        .map(|r| ChangelogEntry {
+            version: normalize_version(&r.tag_name),
+            date: r.published_at,
+            body: r.body.unwrap_or_default(),
+            prerelease: r.prerelease,
+        })
+        .collect())
+}
+
+fn fetch_manifest_entry(channel: &str) -> Result<ChangelogEntry, String> {
+    let manifest: UpdateManifest = ureq::get(channel_endpoint(channel))
+        .timeout(REQUEST_TIMEOUT)
+        .call()
+        .map_err(|e| format!("Update manifest request failed: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Invalid update manifest: {}", e))?;
+
+    Ok(ChangelogEntry {
+        version: normalize_version(&manifest.version),
+        date: manifest.pub_date,
+        body: manifest.notes.unwrap_or_default(),
+        prerelease: channel == "beta",
+    })
+}
+
+fn load_cache() -> ChangelogCache {
+    let path = get_update_changelog_json_path();
+    if !path.exists() {
+        return ChangelogCache::default();
+    }
+    read_json_file(&path).unwrap_or_else(|e| {
+        warn!("Failed to read release notes cache: {}", e);
+        ChangelogCache::default()
+    })
+}
+
+/// Add fetched entries, keeping the cache newest first
+/// A fetched entry replaces the cached one of its version unless it has no notes and the cached one does
+fn merge(cache: &mut ChangelogCache, entries: Vec<ChangelogEntry>) {
+    for entry in entries {
+        match cache.entries.iter_mut().find(|e| e.version == entry.version) {
+            Some(existing) if entry.body.is_empty() && !existing.body.is_empty() => {}
+            Some(existing) => *existing = entry,
+            None => cache.entries.push(entry),
+        }
+    }
+    cache.entries.sort_by(|a, b| compare_versions(&b.version, &a.version));
+}
+
+fn needs_fetch(cache: &ChangelogCache, wanted: Option<&str>) -> bool {
+    let Some(age) = cache
+        .fetched_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| Utc::now() - t.with_timezone(&Utc))
+    else {
+        return true;
+    };
+    let missing = wanted.is_some_and(|v| !cache.entries.iter().any(|e| e.version == v));
+    age > ChronoDuration::hours(CACHE_MAX_AGE_HOURS)
+        || (missing && age > ChronoDuration::minutes(MISSING_VERSION_RETRY_MINUTES))
+}
+
+/// The cached notes, fetched again when stale or missing `wanted`
+/// Fetch failures fall back to the cache; only an empty cache makes them an error
+fn refreshed_cache(channel: &str, wanted: Option<&str>) -> Result<ChangelogCache, String> {
+    let mut cache = load_cache();
+    if !needs_fetch(&cache, wanted) {
+        return Ok(cache);
+    }
+
+    let mut fetched = Vec::new();
+    let mut errors = Vec::new();
+    // GitHub goes second so its notes win over the manifest's for the same version
+    match fetch_manifest_entry(channel) {
+        Ok(entry) => fetched.push(entry),
+        Err(e) => errors.push(e),
+    }
+    match fetch_github_releases() {
+        Ok(entries) => fetched.extend(entries),
+        Err(e) => errors.push(e),
+    }
+    for error in &errors {
+        debug!("Release notes fetch failed: {}", error);
+    }
+
+    if fetched.is_empty() {
+        if cache.entries.is_empty() {
+            return Err(format!("Failed to fetch release notes: {}", errors.join("; ")));
+        }
+        warn!("Failed to fetch release notes, using the cached ones");
+        return Ok(cache);
+    }
+
+    merge(&mut cache, fetched);
+    cache.fetched_at = Some(Utc::now().to_rfc3339());
+    if let Err(e) = write_json_file(&get_update_changelog_json_path(), &cache) {
+        warn!("Failed to save release notes cache: {}", e);
+    }
+    Ok(cache)
+}
+
+/// `version` (the newest when None) and the releases before it
+/// Pre-releases are left out unless asked for, except the requested version itself
+fn select_entries(entries: &[ChangelogEntry], version: Option<&str>, include_prereleases: bool) -> Vec<ChangelogEntry> {
+    entries
+        .iter()
+        .filter(|e| version.map_or(true, |v| compare_versions(&e.version, v) != Ordering::Greater))
+        .filter(|e| include_prereleases || !e.prerelease || Some(e.version.as_str()) == version)
+        .take(RECENT_RELEASES + 1)
+        .cloned()
+        .collect()
+}
+
+/// Release notes of `version` (the newest release when None) and the few releases before it
+#[tauri::command]
+pub async fn get_update_changelog(version: Option<String>) -> Result<Vec<ChangelogEntry>, String> {
+    tokio::task::spawn_blocking(move || {
+        let channel = selected_channel();
+        let version = version.map(|v| normalize_version(&v));
+        let cache = refreshed_cache(&channel, version.as_deref())?;
+        Ok(select_entries(&cache.entries, version.as_deref(), channel == "beta"))
+    })
+    .await
+    .map_err(|e| format!("Failed to load release notes: {}", e))?
+}
+
+/// Fetch the notes of the version just installed and emit `update:whats_new`
+/// The payload is also kept for `take_whats_new`, as the window may not be listening yet
+pub fn announce_update(app: &AppHandle, previous_version: &str, version: &str, channel: &str) {
+    let app = app.clone();
+    let previous_version = normalize_version(previous_version);
+    let version = normalize_version(version);
+    let channel = channel.to_string();
+
+    std::thread::spawn(move || {
+        let entry = match refreshed_cache(&channel, Some(&version)) {
+            Ok(cache) => cache.entries.into_iter().find(|e| e.version == version),
+            Err(e) => {
+                warn!("Failed to fetch release notes of {}: {}", version, e);
+                None
+            }
+        };
+        let whats_new = WhatsNew {
+            version,
+            previous_version,
+            entry,
+        };
+        *WHATS_NEW.lock() = Some(whats_new.clone());
+        let _ = app.emit("update:whats_new", whats_new);
+    });
+}
+
+/// Notes of the update installed before this run, once; None if there was none or they were taken
+#[tauri::command]
+pub fn take_whats_new() -> Option<WhatsNew> {
+    WHATS_NEW.lock().take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10.0", "1.9.3"), Ordering::Greater);
+        assert_eq!(compare_versions("1.7.0-beta.2", "1.7.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.6.2", "1.6.2"), Ordering::Equal);
+        // Not semver: below every release, text order among themselves
+        assert_eq!(compare_versions("nightly", "0.1.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("nightly", "2.0"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_compare_versions_sorts_mixed_tags_consistently() {
+        let mut versions = vec!["1.10.0", "nightly", "1.9.0", "2.0", "1.7.0-beta.1", "10"];
+        versions.sort_by(|a, b| compare_versions(b, a));
+        assert_eq!(versions, vec!["1.10.0", "1.9.0", "1.7.0-beta.1", "nightly", "2.0", "10"]);
+    }
+}
@@ -0,0 +1,581 @@
+// Data directory command handlers: per-feature resets and relocation
+// Relocation copies every file to the new directory, verifies the copy and only then switches
+// data_location.json and restarts; the old directory is removed on the next start. Paths into the
+// old directory saved in JSON files (avatar, downloads, ML outputs) are rewritten during the copy.
+// Copies keep the modification time of their source, and files already copied with the same size
+// and time are skipped, so an interrupted move resumes where it stopped. Verification compares the
+// contents byte for byte. Downloads, ML jobs and gaming sessions can't start while a move runs,
+// and the move re-checks that nothing is running before it switches over. The icon cache lives
+// outside the data directory and stays where it is
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use log::{debug, info, warn};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::downloads::{count_active_downloads, invalidate_downloads_cache};
+use crate::commands::friends::is_friends_connected;
+use crate::commands::ml_jobs::has_processing_ml_jobs;
+use crate::file_manager::feature_data::write_feature_defaults;
+use crate::gaming::GamingSessionManager;
+use crate::launcher::{game_scan, PlaytimeTrackerState};
+use crate::models::{DataDirectoryInfo, DataFeature, DataFolderSize, DataMigration, DataMigrationProgress};
+use crate::valorant::store_scheduler;
+use crate::utils::data_location::{is_data_location_file, load_data_location, save_data_location};
+use crate::utils::{get_app_data_dir, get_default_app_data_dir};
+
+/// JSON files up to this size get their paths rewritten; bigger ones (performance captures) hold no paths
+const REBASE_JSON_MAX_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Files are copied under this suffix and renamed when complete, so a cut-off copy is never mistaken
+/// for a finished one
+const PARTIAL_SUFFIX: &str = ".atlas-partial";
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Top-level folder whose files are only appended to (the log), so a copy matching the start of
+/// its source is complete
+const APPEND_ONLY_DIR: &str = "logs";
+
+const COMPARE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Returned by work that can't start while the data directory is being moved
+pub(crate) const MIGRATING_MESSAGE: &str = "The data directory is being moved; try again once Atlas has restarted";
+
+static MIGRATING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the data directory is being moved, so no new work may write to it
+pub(crate) fn is_migrating() -> bool {
+    MIGRATING.load(Ordering::SeqCst)
+}
+
+/// A file found by `list_files`
+#[derive(Debug, Clone)]
+struct ListedFile {
+    /// Relative to the listed directory
+    relative: PathBuf,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Every file under `dir`, skipping the data location pointer and unfinished copies
+fn list_files(root: &Path, dir: &Path, files: &mut Vec<ListedFile>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let metadata = entry.metadata().map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if metadata.is_dir() {
+            list_files(root, &path, files)?;
+        } else if !is_data_location_file(&path) && !path.to_string_lossy().ends_with(PARTIAL_SUFFIX) {
+            let relative = path.strip_prefix(root).map_err(|e| e.to_string())?.to_path_buf();
+            files.push(ListedFile {
+                relative,
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let mut files = Vec::new();
+    let _ = list_files(dir, dir, &mut files);
+    files.iter().map(|file| file.size).sum()
+}
+
+fn has_user_files(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().any(|entry| !is_data_location_file(&entry.path())))
+        .unwrap_or(false)
+}
+
+/// Point strings that are paths inside `from` at the same place inside `to`; returns whether any changed
+fn rebase_paths(value: &mut Value, from: &Path, to: &Path) -> bool {
+    match value {
+        Value::String(s) => match Path::new(s.as_str()).strip_prefix(from) {
+            Ok(rest) => {
+                *s = to.join(rest).to_string_lossy().to_string();
+                true
+            }
+            Err(_) => false,
+        },
+        Value::Array(items) => items.iter_mut().fold(false, |changed, item| rebase_paths(item, from, to) | changed),
+        Value::Object(fields) => fields
+            .values_mut()
+            .fold(false, |changed, field| rebase_paths(field, from, to) | changed),
+        _ => false,
+    }
+}
+
+/// JSON with its paths rebased, or None when it has none (or isn't JSON) and can be copied as is
+fn rebased_json(source: &Path, size: u64, from: &Path, to: &Path) -> Option<Value> {
+    if size > REBASE_JSON_MAX_BYTES || source.extension().and_then(|e| e.to_str()) != Some("json") {
+        return None;
+    }
+    let mut value: Value = serde_json::from_slice(&fs::read(source).ok()?).ok()?;
+    rebase_paths(&mut value, from, to).then_some(value)
+}
+
+/// Copy `source` to `dest` with its modification time, through a partial file
+fn copy_file(source: &Path, dest: &Path, modified: Option<SystemTime>) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    let mut partial = dest.as_os_str().to_owned();
+    partial.push(PARTIAL_SUFFIX);
+    let partial = PathBuf::from(partial);
+
+    fs::copy(source, &partial).map_err(|e| format!("Failed to copy {:?}: {}", source, e))?;
+    if let Some(modified) = modified {
+        fs::File::options()
+            .write(true)
+            .open(&partial)
+            .and_then(|file| file.set_modified(modified))
+            .map_err(|e| format!("Failed to copy {:?}: {}", source, e))?;
+    }
+    fs::rename(&partial, dest).map_err(|e| format!("Failed to finish copying {:?}: {}", dest, e))
+}
+
+/// Whether `dest` is a finished copy of `file`, from an earlier run of an interrupted move
+fn is_copied(file: &ListedFile, dest: &Path) -> bool {
+    file.modified.is_some()
+        && fs::metadata(dest).is_ok_and(|m| m.len() == file.size && m.modified().ok() == file.modified)
+}
+
+/// Fill `buf` as far as the reader allows; returns how much was read
+fn read_chunk(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Whether `dest` holds the same bytes as `source`; with `prefix_only`, the same bytes as the start
+/// of a source that may have grown since
+fn same_contents(source: &Path, dest: &Path, prefix_only: bool) -> io::Result<bool> {
+    let (source_len, dest_len) = (fs::metadata(source)?.len(), fs::metadata(dest)?.len());
+    if source_len < dest_len || (!prefix_only && source_len != dest_len) {
+        return Ok(false);
+    }
+
+    let mut source = fs::File::open(source)?.take(dest_len);
+    let mut dest = fs::File::open(dest)?;
+    let (mut source_buf, mut dest_buf) = (vec![0; COMPARE_CHUNK_BYTES], vec![0; COMPARE_CHUNK_BYTES]);
+    loop {
+        let read = read_chunk(&mut source, &mut source_buf)?;
+        if read != read_chunk(&mut dest, &mut dest_buf)? || source_buf[..read] != dest_buf[..read] {
+            return Ok(false);
+        }
+        if read == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Copy `files` from `from` to `to`, skipping finished copies; JSON files get their paths into
+/// `from` rebased. Returns the files written rebased
+fn copy_files(
+    from: &Path,
+    to: &Path,
+    files: &[ListedFile],
+    mut on_progress: impl FnMut(usize, u64),
+) -> Result<HashSet<PathBuf>, String> {
+    let mut rebased = HashSet::new();
+    let mut bytes_done = 0;
+    for (index, file) in files.iter().enumerate() {
+        let (source, dest) = (from.join(&file.relative), to.join(&file.relative));
+        if let Some(value) = rebased_json(&source, file.size, from, to) {
+            crate::file_manager::write_json_file(&dest, &value)?;
+            rebased.insert(file.relative.clone());
+        } else if !is_copied(file, &dest) {
+            copy_file(&source, &dest, file.modified)?;
+        } else {
+            debug!("Already copied {:?}", file.relative);
+        }
+
+        bytes_done += file.size;
+        on_progress(index + 1, bytes_done);
+    }
+    Ok(rebased)
+}
+
+/// Check every copy against its source; returns the first file whose copy isn't intact, after
+/// deleting that copy so the next run copies it again instead of skipping it
+fn verify_files(
+    from: &Path,
+    to: &Path,
+    files: &[ListedFile],
+    rebased: &HashSet<PathBuf>,
+    mut on_progress: impl FnMut(usize),
+) -> Option<PathBuf> {
+    for (index, file) in files.iter().enumerate() {
+        let dest = to.join(&file.relative);
+        let intact = if rebased.contains(&file.relative) {
+            fs::read(&dest).ok().and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok()).is_some()
+        } else {
+            let append_only = file.relative.starts_with(APPEND_ONLY_DIR);
+            same_contents(&from.join(&file.relative), &dest, append_only).unwrap_or(false)
+        };
+        if !intact {
+            if let Err(e) = fs::remove_file(&dest) {
+                warn!("Failed to remove mismatched copy {:?}: {}", dest, e);
+            }
+            return Some(file.relative.clone());
+        }
+        on_progress(index + 1);
+    }
+    None
+}
+
+pub(crate) fn gaming_session_active(app: &AppHandle) -> bool {
+    app.try_state::<Arc<GamingSessionManager>>()
+        .is_some_and(|manager| manager.get_active_session().is_some())
+}
+
+/// Refuse to move data out from under running work
+/// Checked before the copy and again before switching over
+fn ensure_idle(app: &AppHandle) -> Result<(), String> {
+    if count_active_downloads()? > 0 {
+        return Err("Wait for the active downloads to finish before moving the data directory".to_string());
+    }
+    if has_processing_ml_jobs()? {
+        return Err("Wait for the running ML jobs to finish before moving the data directory".to_string());
+    }
+    if gaming_session_active(app) {
+        return Err("End the gaming session before moving the data directory".to_string());
+    }
+    Ok(())
+}
+
+/// Refuse to reset a feature's data while it is being worked on
+fn ensure_feature_idle(app: &AppHandle, feature: DataFeature) -> Result<(), String> {
+    let busy = match feature {
+        DataFeature::Downloads => {
+            (count_active_downloads()? > 0).then_some("Wait for the active downloads to finish")
+        }
+        DataFeature::GamingSessions | DataFeature::TaskMonitorProfiles => {
+            gaming_session_active(app).then_some("End the gaming session first")
+        }
+        DataFeature::GameLibrary => {
+            let playing = app
+                .try_state::<Arc<PlaytimeTrackerState>>()
+                .is_some_and(|state| state.active_sessions.read().is_ok_and(|sessions| !sessions.is_empty()));
+            if game_scan::is_scanning() {
+                Some("Wait for the game scan to finish")
+            } else {
+                playing.then_some("Close the running games first")
+            }
+        }
+        DataFeature::ValorantStore => None,
+        DataFeature::Friends => is_friends_connected()?.then_some("Disconnect from the friends server first"),
+    };
+    match busy {
+        Some(reason) => Err(reason.to_string()),
+        None => Ok(()),
+    }
+}
+
+struct ProgressReporter<'a> {
+    app: &'a AppHandle,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressReporter<'_> {
+    fn report(&mut self, progress: DataMigrationProgress) {
+        let done = progress.files_done == progress.files_total;
+        if !done && self.last_emit.is_some_and(|at| at.elapsed() < PROGRESS_INTERVAL) {
+            return;
+        }
+        self.last_emit = Some(Instant::now());
+        let _ = self.app.emit("data_directory:migration_progress", progress);
+    }
+}
+
+/// Copy, verify and switch over; the caller restarts the app afterwards
+fn migrate(app: &AppHandle, to: PathBuf) -> Result<(), String> {
+    let from = get_app_data_dir();
+    if !to.is_absolute() {
+        return Err("Pick a full folder path".to_string());
+    }
+    if to == from {
+        return Err("Atlas data is already in that folder".to_string());
+    }
+    if to.starts_with(&from) || from.starts_with(&to) {
+        return Err("The new folder can't be inside the current data directory or contain it".to_string());
+    }
+
+    let mut location = load_data_location();
+    let resuming = location.migration.as_ref().is_some_and(|m| Path::new(&m.to) == to);
+    if !resuming && has_user_files(&to) {
+        return Err("Pick an empty folder".to_string());
+    }
+    if !resuming {
+        location.migration = Some(DataMigration {
+            from: from.to_string_lossy().to_string(),
+            to: to.to_string_lossy().to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        });
+        save_data_location(&location)?;
+    }
+    fs::create_dir_all(&to).map_err(|e| format!("Failed to create {:?}: {}", to, e))?;
+    info!("Moving data directory from {:?} to {:?} (resuming: {})", from, to, resuming);
+
+    let mut files = Vec::new();
+    list_files(&from, &from, &mut files)?;
+    let files_total = files.len();
+    let bytes_total: u64 = files.iter().map(|file| file.size).sum();
+    let mut progress = ProgressReporter { app, last_emit: None };
+
+    // Copy
+    let rebased = copy_files(&from, &to, &files, |files_done, bytes_done| {
+        progress.report(DataMigrationProgress {
+            stage: "copying".to_string(),
+            files_done,
+            files_total,
+            bytes_done,
+            bytes_total,
+        })
+    })?;
+
+    // Verify; a file changed since it was copied no longer matches, and is deleted so resuming copies it again
+    let broken = verify_files(&from, &to, &files, &rebased, |files_done| {
+        progress.report(DataMigrationProgress {
+            stage: "verifying".to_string(),
+            files_done,
+            files_total,
+            bytes_done: bytes_total,
+            bytes_total,
+        })
+    });
+    if let Some(relative) = broken {
+        return Err(format!(
+            "Copy of {:?} doesn't match the original; run the move again to resume",
+            relative
+        ));
+    }
+
+    // Switch, unless work started while copying and wrote to the old directory
+    ensure_idle(app)?;
+    location.data_dir = (to != get_default_app_data_dir()).then(|| to.to_string_lossy().to_string());
+    location.migration = None;
+    location.pending_cleanup = Some(from.to_string_lossy().to_string());
+    save_data_location(&location)?;
+
+    info!("Data directory moved to {:?} ({} files, {} bytes)", to, files_total, bytes_total);
+    Ok(())
+}
+
+/// Reset one feature's data to what a fresh install starts with
+/// Emits `data:reset` so open views reload
+#[tauri::command]
+pub fn reset_feature_data(app: AppHandle, feature: DataFeature) -> Result<(), String> {
+    ensure_feature_idle(&app, feature)?;
+    write_feature_defaults(feature, true)?;
+
+    match feature {
+        DataFeature::Downloads => invalidate_downloads_cache(),
+        DataFeature::ValorantStore => store_scheduler::rearm(),
+        _ => {}
+    }
+
+    info!("Reset {:?} data", feature);
+    let _ = app.emit("data:reset", json!({ "feature": feature }));
+    Ok(())
+}
+
+/// Current data directory, its size per top-level folder and any interrupted move
+#[tauri::command]
+pub async fn get_data_directory_info() -> Result<DataDirectoryInfo, String> {
+    tokio::task::spawn_blocking(|| {
+        let root = get_app_data_dir();
+        let mut folders = Vec::new();
+        let mut total_bytes = 0;
+
+        let entries = fs::read_dir(&root).map_err(|e| format!("Failed to read {:?}: {}", root, e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_data_location_file(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                let bytes = dir_size(&path);
+                total_bytes += bytes;
+                folders.push(DataFolderSize {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    bytes,
+                });
+            } else {
+                total_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+        folders.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+
+        Ok(DataDirectoryInfo {
+            is_default: root == get_default_app_data_dir(),
+            path: root.to_string_lossy().to_string(),
+            total_bytes,
+            folders,
+            interrupted_migration: load_data_location().migration,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to read data directory: {}", e))?
+}
+
+/// Move all Atlas data to `new_path` and restart from there
+/// Progress is reported through `data_directory:migration_progress`; running it again with the same
+/// folder resumes an interrupted move
+#[tauri::command]
+pub async fn migrate_data_directory(app: AppHandle, new_path: String) -> Result<(), String> {
+    // Set before checking, so nothing can start in between
+    if MIGRATING.swap(true, Ordering::SeqCst) {
+        return Err("The data directory is already being moved".to_string());
+    }
+    if let Err(e) = ensure_idle(&app) {
+        MIGRATING.store(false, Ordering::SeqCst);
+        return Err(e);
+    }
+
+    let handle = app.clone();
+    let result = tokio::task::spawn_blocking(move || migrate(&handle, PathBuf::from(new_path.trim())))
+        .await
+        .map_err(|e| format!("Failed to move data directory: {}", e))
+        .and_then(|r| r);
+    MIGRATING.store(false, Ordering::SeqCst);
+    result?;
+
+    app.restart()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas_data_dir_test_{}_{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn listed(dir: &Path) -> Vec<ListedFile> {
+        let mut files = Vec::new();
+        list_files(dir, dir, &mut files).unwrap();
+        files.sort_by(|a, b| a.relative.cmp(&b.relative));
+        files
+    }
+
+    #[test]
+    fn test_rebase_paths_rewrites_nested_paths_inside_from_only() {
+        let from = Path::new("/data/old");
+        let to = Path::new("/data/new");
+        let mut value = json!({
+            "avatar": "/data/old/avatar.png",
+            "downloads": [{ "file_path": "/data/old/videos/a.mp4" }, { "file_path": "/elsewhere/b.mp4" }],
+            "name": "old",
+            "count": 3,
+        });
+
+        assert!(rebase_paths(&mut value, from, to));
+        assert_eq!(value["avatar"], Path::new("/data/new/avatar.png").to_string_lossy().as_ref());
+        assert_eq!(value["downloads"][0]["file_path"], Path::new("/data/new/videos/a.mp4").to_string_lossy().as_ref());
+        assert_eq!(value["downloads"][1]["file_path"], "/elsewhere/b.mp4");
+        assert_eq!(value["name"], "old");
+    }
+
+    #[test]
+    fn test_rebase_paths_reports_no_change() {
+        let mut value = json!({ "path": "/data/older/file", "list": ["/data", 1, null] });
+        let original = value.clone();
+        assert!(!rebase_paths(&mut value, Path::new("/data/old"), Path::new("/data/new")));
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn test_list_files_recurses_and_skips_partial_copies() {
+        let dir = temp_dir("list");
+        fs::create_dir_all(dir.join("logs")).unwrap();
+        fs::write(dir.join("settings.json"), "{}").unwrap();
+        fs::write(dir.join("logs").join("atlas.log"), "hello").unwrap();
+        fs::write(dir.join(format!("big.bin{}", PARTIAL_SUFFIX)), "cut off").unwrap();
+
+        let files = listed(&dir);
+        let names: Vec<_> = files.iter().map(|f| f.relative.clone()).collect();
+        assert_eq!(names, vec![PathBuf::from("logs").join("atlas.log"), PathBuf::from("settings.json")]);
+        assert_eq!(files[0].size, 5);
+        assert!(files[0].modified.is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_copy_resumes_and_skips_finished_copies() {
+        let (from, to) = (temp_dir("from"), temp_dir("to"));
+        fs::write(from.join("done.bin"), "finished").unwrap();
+        fs::write(from.join("cut.bin"), "interrupted").unwrap();
+        fs::write(from.join("changed.bin"), "new text").unwrap();
+
+        // An earlier run copied done.bin and changed.bin, then was cut off during cut.bin
+        let files = listed(&from);
+        for file in files.iter().filter(|f| f.relative != Path::new("cut.bin")) {
+            copy_file(&from.join(&file.relative), &to.join(&file.relative), file.modified).unwrap();
+        }
+        fs::write(to.join(format!("cut.bin{}", PARTIAL_SUFFIX)), "inter").unwrap();
+        // Marks the finished copy, which is kept as is when resuming
+        fs::write(to.join("done.bin"), "FINISHED").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(to.join("done.bin"))
+            .unwrap()
+            .set_modified(files.iter().find(|f| f.relative == Path::new("done.bin")).unwrap().modified.unwrap())
+            .unwrap();
+        // changed.bin was written to after it was copied
+        fs::write(from.join("changed.bin"), "newer ok").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::File::options().write(true).open(from.join("changed.bin")).unwrap().set_modified(later).unwrap();
+
+        let files = listed(&from);
+        let rebased = copy_files(&from, &to, &files, |_, _| {}).unwrap();
+        assert!(rebased.is_empty());
+        assert_eq!(fs::read_to_string(to.join("done.bin")).unwrap(), "FINISHED");
+        assert_eq!(fs::read_to_string(to.join("cut.bin")).unwrap(), "interrupted");
+        assert_eq!(fs::read_to_string(to.join("changed.bin")).unwrap(), "newer ok");
+
+        // The skipped copy's contents differ, which verification catches
+        assert_eq!(verify_files(&from, &to, &files, &rebased, |_| {}), Some(PathBuf::from("done.bin")));
+        assert!(!to.join("done.bin").exists());
+
+        // Running the move again copies the mismatched file afresh and verifies cleanly
+        let rebased = copy_files(&from, &to, &files, |_, _| {}).unwrap();
+        assert_eq!(fs::read_to_string(to.join("done.bin")).unwrap(), "finished");
+        assert_eq!(verify_files(&from, &to, &files, &rebased, |_| {}), None);
+
+        fs::remove_dir_all(&from).unwrap();
+        fs::remove_dir_all(&to).unwrap();
+    }
+
+    #[test]
+    fn test_same_contents_allows_grown_append_only_sources() {
+        let dir = temp_dir("compare");
+        let (source, dest) = (dir.join("source.log"), dir.join("dest.log"));
+        fs::write(&source, "line one\nline two\n").unwrap();
+        fs::write(&dest, "line one\n").unwrap();
+
+        assert!(same_contents(&source, &dest, true).unwrap());
+        assert!(!same_contents(&source, &dest, false).unwrap());
+        fs::write(&dest, "line 0ne\n").unwrap();
+        assert!(!same_contents(&source, &dest, true).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
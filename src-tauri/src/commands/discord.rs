@@ -1,5 +1,6 @@
 // Discord Rich Presence command handlers
-use crate::discord::DiscordPresenceManager;
+use crate::discord::{template, DiscordPresenceManager};
+use crate::models::{CurrentPresence, DiscordConnectionStatus, PresenceActivity, PresencePlaceholder};
 use std::sync::Arc;
 use tauri::State;
 
@@ -19,10 +20,53 @@ pub fn disconnect_discord(
     discord.disconnect()
 }
 
-/// Check if Discord Rich Presence is connected
+/// Discord Rich Presence connection state, with the last error and the reconnect countdown
 #[tauri::command]
 pub fn is_discord_connected(
     discord: State<'_, Arc<DiscordPresenceManager>>,
-) -> bool {
-    discord.is_connected()
+) -> DiscordConnectionStatus {
+    discord.connection_status()
+}
+
+/// Placeholders the presence templates can use
+#[tauri::command]
+pub fn get_presence_template_placeholders() -> Vec<PresencePlaceholder> {
+    template::placeholders()
+}
+
+/// The activity Atlas would send to Discord right now, also when not connected
+#[tauri::command]
+pub fn preview_discord_presence(
+    discord: State<'_, Arc<DiscordPresenceManager>>,
+) -> PresenceActivity {
+    discord.preview()
+}
+
+/// Show fixed text on Discord for `duration_minutes`, over the gaming and idle presence
+#[tauri::command]
+pub fn set_custom_presence(
+    discord: State<'_, Arc<DiscordPresenceManager>>,
+    details: Option<String>,
+    state: Option<String>,
+    duration_minutes: u32,
+) -> Result<CurrentPresence, String> {
+    discord.set_custom_presence(details, state, duration_minutes)?;
+    Ok(discord.current())
+}
+
+/// End a custom presence early
+#[tauri::command]
+pub fn clear_custom_presence(
+    discord: State<'_, Arc<DiscordPresenceManager>>,
+) -> Result<CurrentPresence, String> {
+    discord.clear_custom_presence()?;
+    Ok(discord.current())
+}
+
+/// The presence shown right now and whether it is custom, gaming or idle
+#[tauri::command]
+pub fn get_current_presence(
+    discord: State<'_, Arc<DiscordPresenceManager>>,
+) -> CurrentPresence {
+    discord.current()
 }
@@ -1,8 +1,9 @@
+use crate::commands::data_directory::{is_migrating, MIGRATING_MESSAGE};
 use crate::file_manager::{read_json_file, write_json_file};
 use crate::models::{Download, DownloadStatus, Settings};
 use crate::process_manager::{spawn_python_worker_async, WorkerMessage};
 use crate::utils::{get_downloads_json_path, get_settings_json_path, get_videos_dir};
-use log::debug;
+use log::{debug, warn};
 use parking_lot::RwLock;
 use serde::Serialize;
 use std::fs;
@@ -81,7 +82,7 @@ fn resolve_special_folder(name: &str) -> Option<PathBuf> {
 }
 
 /// Get the download directory from settings
-fn get_download_directory() -> PathBuf {
+pub(crate) fn get_download_directory() -> PathBuf {
     let settings_path = get_settings_json_path();
 
     if settings_path.exists() {
@@ -128,8 +129,13 @@ fn get_current_settings() -> Settings {
     }
 }
 
+/// Drop the cached download list after downloads.json was replaced from outside this module
+pub(crate) fn invalidate_downloads_cache() {
+    DOWNLOADS_CACHE.write().invalidate();
+}
+
 /// Count currently active (downloading) downloads
-fn count_active_downloads() -> Result<u32, String> {
+pub(crate) fn count_active_downloads() -> Result<u32, String> {
     let path = get_downloads_json_path();
 
     if !path.exists() {
@@ -145,6 +151,69 @@ fn count_active_downloads() -> Result<u32, String> {
     Ok(active_count)
 }
 
+/// Start pending downloads while there are free slots, oldest first
+/// Called when `max_concurrent_downloads` changes, so downloads that were turned away at the old
+/// limit start once it is raised
+pub(crate) fn start_pending_downloads(app: &AppHandle) {
+    let path = get_downloads_json_path();
+    if !path.exists() {
+        return;
+    }
+
+    let downloads: Vec<Download> = match read_json_file(&path) {
+        Ok(downloads) => downloads,
+        Err(e) => {
+            warn!("Failed to read downloads: {}", e);
+            return;
+        }
+    };
+    let active_count = downloads
+        .iter()
+        .filter(|d| d.status == DownloadStatus::Downloading)
+        .count() as u32;
+    let free_slots = get_current_settings()
+        .max_concurrent_downloads
+        .saturating_sub(active_count) as usize;
+
+    for download in downloads
+        .iter()
+        .filter(|d| d.status == DownloadStatus::Pending)
+        .take(free_slots)
+    {
+        debug!("Starting pending download {}", download.id);
+        let app = app.clone();
+        let job_id = download.id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = start_download(app, job_id.clone()).await {
+                warn!("Failed to start pending download {}: {}", job_id, e);
+            }
+        });
+    }
+}
+
+/// Add a file fetched outside the yt-dlp queue (e.g. from the server) to the list as completed
+pub(crate) fn register_completed_download(url: String, title: String, file_path: &std::path::Path) -> Result<Download, String> {
+    let path = get_downloads_json_path();
+    let mut downloads: Vec<Download> = if path.exists() {
+        read_json_file(&path)?
+    } else {
+        vec![]
+    };
+
+    let mut download = Download::new(uuid::Uuid::new_v4().to_string(), url, "original".to_string());
+    download.title = Some(title);
+    download.status = DownloadStatus::Completed;
+    download.progress = 100;
+    download.file_path = Some(file_path.to_string_lossy().to_string());
+    download.completed_at = Some(chrono::Utc::now().to_rfc3339());
+    downloads.push(download.clone());
+
+    write_json_file(&path, &downloads)?;
+    DOWNLOADS_CACHE.write().invalidate();
+    debug!("Registered completed download: {}", download.url);
+    Ok(download)
+}
+
 /// Result of validating a download path
 #[derive(Debug, Clone, Serialize)]
 pub struct DownloadPathValidation {
@@ -310,6 +379,10 @@ pub async fn start_download(app: AppHandle, job_id: String) -> Result<serde_json
         return Err("No downloads file found".to_string());
     }
 
+    if is_migrating() {
+        return Err(MIGRATING_MESSAGE.to_string());
+    }
+
     let settings = get_current_settings();
     let active_count = count_active_downloads()?;
 
@@ -2,6 +2,7 @@ use std::fs;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
+use crate::discord::presence::validate_game_app;
 use crate::file_manager::{read_json_file, write_json_file};
 use crate::gaming::{
     is_detection_running, start_game_detection, stop_game_detection,
@@ -26,6 +27,11 @@ pub fn get_game_whitelist() -> Result<GameWhitelist, String> {
 /// Update the entire game whitelist
 #[tauri::command]
 pub fn update_game_whitelist(whitelist: GameWhitelist) -> Result<(), String> {
+    for game in &whitelist.games {
+        if let Some(app) = &game.discord_app {
+            validate_game_app(app).map_err(|e| format!("{}: {}", game.name, e))?;
+        }
+    }
     write_json_file(&get_game_whitelist_json_path(), &whitelist)
 }
 
@@ -42,6 +48,9 @@ pub fn add_game_to_whitelist(game: GameEntry) -> Result<(), String> {
     {
         return Err("Game already exists in whitelist".to_string());
     }
+    if let Some(app) = &game.discord_app {
+        validate_game_app(app)?;
+    }
 
     whitelist.games.push(game);
     write_json_file(&get_game_whitelist_json_path(), &whitelist)
@@ -82,6 +91,24 @@ pub fn toggle_game_enabled(process_name: String, enabled: bool) -> Result<(), St
     }
 }
 
+/// Keep a whitelisted game's name out of the Discord and friends presence
+#[tauri::command]
+pub fn set_whitelist_game_hidden_from_presence(process_name: String, hidden: bool) -> Result<(), String> {
+    let mut whitelist = get_game_whitelist()?;
+
+    let game = whitelist.games.iter_mut().find(|g| {
+        g.process_name.to_lowercase() == process_name.to_lowercase()
+    });
+
+    match game {
+        Some(g) => {
+            g.hide_from_presence = hidden;
+            write_json_file(&get_game_whitelist_json_path(), &whitelist)
+        }
+        None => Err("Game not found in whitelist".to_string()),
+    }
+}
+
 /// Start game detection monitoring
 #[tauri::command]
 pub fn start_gaming_detection(
@@ -166,9 +193,12 @@ pub fn delete_gaming_session(session_id: String) -> Result<(), String> {
 /// Manually end the current gaming session
 #[tauri::command]
 pub fn end_gaming_session(
+    app: AppHandle,
     session_manager: State<'_, Arc<GamingSessionManager>>,
 ) -> Result<GamingSession, String> {
-    session_manager.end_session()
+    let session = session_manager.end_session()?;
+    crate::commands::settings_profiles::on_gaming_session_ended(&app);
+    Ok(session)
 }
 
 /// Get bottleneck detection thresholds
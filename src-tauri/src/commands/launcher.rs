@@ -2,14 +2,35 @@
 
 use crate::file_manager::{read_json_file, write_json_file};
 use crate::launcher::{
-    detect_hoyoplay_games, detect_steam_games, detect_riot_games,
+    detect_hoyoplay_games, detect_steam_games, detect_riot_games, detect_epic_games, epic_launch_url,
+    detect_battlenet_games, find_battlenet_client, BattleNetGameConfig, BATTLENET_APP_ID_PREFIX, EPIC_APP_ID_PREFIX,
+    detect_xbox_games, xbox_launch_target, XBOX_APP_ID_PREFIX, detect_ubisoft_games, ubisoft_launch_url,
+    UBISOFT_APP_ID_PREFIX, detect_ea_games, ea_launch_url, EA_APP_ID_PREFIX,
+    detect_itch_games, detect_steam_shortcuts,
+    game_hooks::{run_hook, validate_hook},
+    game_scan,
+    icon_extractor::{
+        custom_icon_path, download_hoyoplay_icon, download_riot_icon, download_steam_icon, extract_icon_from_exe,
+        get_icon_cache_dir, save_custom_icon,
+    },
+    launch_history::{load_launch_history, purge_launch_history, record_launch},
+    shortcuts::{icon_to_ico, shortcut_dir, shortcut_file_name, write_shortcut, ShortcutLocation, ShortcutSpec},
+    steamgriddb,
     playtime_tracker::{start_game_session, PlaytimeTrackerState},
 };
-use crate::models::{AddGameRequest, DetectedGame, GameEntry, GameLibrary, GameSource, GameWhitelist, LibraryGame, GameScanCache};
-use crate::utils::{get_game_library_json_path, get_game_whitelist_json_path, get_game_scan_cache_json_path};
-use std::path::Path;
+use crate::models::{
+    AddGameRequest, DetectedGame, GameEntry, GameLibrary, GameSource, GameWhitelist, LibraryGame, LibrarySortBy, GameScanCache,
+    ArtworkKind, GameArtwork, GameCollection, GameHookCommand, GamePlaytime, IgnoredGame, IgnoredGames, RecentlyPlayedGame, RunningGame,
+};
+use crate::task_monitor::{self, models::KillMode};
+use crate::utils::{
+    get_game_library_json_path, get_game_whitelist_json_path, get_game_scan_cache_json_path, get_ignored_games_json_path,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::{AppHandle, State, Emitter};
+use log::warn;
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 /// Read an icon file and return as base64 data URL
@@ -35,59 +56,345 @@ pub fn get_icon_base64(icon_path: String) -> Result<String, String> {
     Ok(format!("data:{};base64,{}", mime_type, base64_data))
 }
 
-/// Get the game library
+/// Use an image file (PNG, JPEG or ICO) as a game's icon
+/// It's converted to PNG in the icon cache (scaled down if very large) and kept across scans
 #[tauri::command]
-pub fn get_game_library() -> Result<GameLibrary, String> {
-    read_json_file(&get_game_library_json_path())
-        .map_err(|e| format!("Failed to read game library: {}", e))
+pub fn set_game_icon(game_id: String, image_path: String) -> Result<LibraryGame, String> {
+    let cache_dir = get_icon_cache_dir().ok_or_else(|| "Icon cache directory not available".to_string())?;
+    update_game_library(|library| {
+        let game = library.find_by_id_mut(&game_id).ok_or_else(|| "Game not found".to_string())?;
+        game.icon_path = Some(save_custom_icon(Path::new(&image_path), &game_id, &cache_dir)?);
+        game.icon_user_set = true;
+        Ok(game.clone())
+    })
 }
 
-/// Scan for games (Steam + HoyoPlay) with caching
+/// Drop a game's custom icon and get its icon the way a scan would
 #[tauri::command]
-pub fn scan_for_games(force_rescan: Option<bool>) -> Result<Vec<DetectedGame>, String> {
-    let cache_path = get_game_scan_cache_json_path();
+pub async fn reset_game_icon(game_id: String) -> Result<LibraryGame, String> {
+    tokio::task::spawn_blocking(move || {
+        update_game_library(|library| {
+            let game = library.find_by_id_mut(&game_id).ok_or_else(|| "Game not found".to_string())?;
+            if let Some(cache_dir) = get_icon_cache_dir() {
+                let _ = std::fs::remove_file(custom_icon_path(&game_id, &cache_dir));
+            }
+            game.icon_path = default_game_icon(game);
+            game.icon_user_set = false;
+            Ok(game.clone())
+        })
+    })
+    .await
+    .map_err(|e| format!("Icon reset failed: {}", e))?
+}
+
+/// A game's icon from the normal chain: what the last scan found, the store's art, then the exe's icon
+fn default_game_icon(game: &LibraryGame) -> Option<String> {
+    let scanned = read_json_file::<GameScanCache>(&get_game_scan_cache_json_path())
+        .ok()
+        .and_then(|cache| {
+            cache.games.into_iter().find(|g| match &game.app_id {
+                Some(_) => g.app_id == game.app_id,
+                None => g.executable_path.to_lowercase() == game.executable_path.to_lowercase(),
+            })
+        })
+        .and_then(|g| g.icon_path)
+        .filter(|p| Path::new(p).exists());
+    if scanned.is_some() {
+        return scanned;
+    }
+
+    let cache_dir = get_icon_cache_dir()?;
+    let store_icon = match game.source {
+        GameSource::Steam => game.app_id.as_deref().and_then(|app_id| download_steam_icon(app_id, &cache_dir)),
+        GameSource::HoyoPlay => download_hoyoplay_icon(&game.name, &cache_dir),
+        GameSource::Riot => game
+            .app_id
+            .as_deref()
+            .and_then(|app_id| app_id.strip_prefix("riot_"))
+            .and_then(|product| download_riot_icon(product, &cache_dir)),
+        _ => None,
+    };
+    store_icon.or_else(|| extract_icon_from_exe(Path::new(&game.executable_path), &cache_dir))
+}
+
+/// Download SteamGridDB cover, hero and logo art for a library game
+/// Uses `api_key` or the `steamgriddb_api_key` setting; without either the game keeps its current artwork
+#[tauri::command]
+pub async fn fetch_game_artwork(game_id: String, api_key: Option<String>) -> Result<GameArtwork, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+            .map_err(|e| format!("Failed to read game library: {}", e))?;
+        let game = library.find_by_id(&game_id).ok_or_else(|| "Game not found".to_string())?;
+
+        let api_key = api_key
+            .or_else(|| crate::commands::settings::get_settings().ok()?.steamgriddb_api_key)
+            .filter(|k| !k.trim().is_empty());
+        let Some(api_key) = api_key else {
+            return Ok(game.artwork.clone());
+        };
+
+        let steam_app_id = match game.source {
+            GameSource::Steam => game.app_id.clone(),
+            _ => None,
+        };
+        let cache_dir = get_icon_cache_dir().ok_or_else(|| "Icon cache directory unavailable".to_string())?;
+        let artwork = steamgriddb::fetch_artwork(&game.name, steam_app_id.as_deref(), api_key.trim(), &cache_dir)?;
+
+        // A lookup that found nothing leaves earlier artwork in place
+        if artwork.is_empty() {
+            return Ok(game.artwork.clone());
+        }
+        if let Some(game) = library.find_by_id_mut(&game_id) {
+            game.artwork = artwork.clone();
+        }
+        write_json_file(&get_game_library_json_path(), &library)
+            .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+        Ok(artwork)
+    })
+    .await
+    .map_err(|e| format!("Artwork fetch failed: {}", e))?
+}
+
+/// A library game's artwork as a data URL, falling back to its icon when that kind wasn't fetched
+#[tauri::command]
+pub fn get_game_artwork(game_id: String, kind: Option<ArtworkKind>) -> Result<String, String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let game = library.find_by_id(&game_id).ok_or_else(|| "Game not found".to_string())?;
+
+    let path = game
+        .artwork
+        .get(kind.unwrap_or_default())
+        .filter(|p| Path::new(p).exists())
+        .or(game.icon_path.as_ref())
+        .ok_or_else(|| "Game has no artwork".to_string())?;
+
+    get_icon_base64(path.clone())
+}
+
+/// Get the game library, favorites first then ordered by `sort_by` (default: the custom order)
+#[tauri::command]
+pub fn get_game_library(sort_by: Option<LibrarySortBy>, collection_id: Option<String>) -> Result<GameLibrary, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    if let Some(collection_id) = collection_id {
+        library.retain_collection(&collection_id)?;
+    }
+    library.sort(sort_by.unwrap_or_default());
+    Ok(library)
+}
+
+fn update_game_library<T>(update: impl FnOnce(&mut GameLibrary) -> Result<T, String>) -> Result<T, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let result = update(&mut library)?;
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn create_collection(name: String) -> Result<GameCollection, String> {
+    update_game_library(|library| library.create_collection(&name))
+}
+
+#[tauri::command]
+pub fn rename_collection(collection_id: String, name: String) -> Result<(), String> {
+    update_game_library(|library| library.rename_collection(&collection_id, &name))
+}
+
+/// Delete a collection - its games stay in the library
+#[tauri::command]
+pub fn delete_collection(collection_id: String) -> Result<(), String> {
+    update_game_library(|library| {
+        if library.delete_collection(&collection_id) {
+            Ok(())
+        } else {
+            Err("Collection not found".to_string())
+        }
+    })
+}
+
+/// Replace the collections a game is in
+#[tauri::command]
+pub fn set_game_collections(game_id: String, collection_ids: Vec<String>) -> Result<LibraryGame, String> {
+    update_game_library(|library| library.set_game_collections(&game_id, collection_ids).cloned())
+}
+
+/// Mark or unmark a game as a favorite
+#[tauri::command]
+pub fn set_game_favorite(game_id: String, favorite: bool) -> Result<GameLibrary, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    library
+        .find_by_id_mut(&game_id)
+        .ok_or_else(|| "Game not found".to_string())?
+        .favorite = favorite;
+
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+    library.sort(LibrarySortBy::Custom);
+    Ok(library)
+}
+
+/// Keep a game's name out of the Discord and friends presence; applies from its next presence update
+#[tauri::command]
+pub fn set_game_hidden_from_presence(game_id: String, hidden: bool) -> Result<GameLibrary, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    library
+        .find_by_id_mut(&game_id)
+        .ok_or_else(|| "Game not found".to_string())?
+        .hide_from_presence = hidden;
+
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+    library.sort(LibrarySortBy::Custom);
+    Ok(library)
+}
+
+/// Save the user's custom library order (games not listed keep their order after the listed ones)
+#[tauri::command]
+pub fn reorder_library(game_ids: Vec<String>) -> Result<GameLibrary, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    library.reorder(&game_ids)?;
+
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+    library.sort(LibrarySortBy::Custom);
+    Ok(library)
+}
+
+/// Scan for games (Steam, HoyoPlay, Riot, Epic, Battle.net, Xbox, Ubisoft, EA, itch.io) with caching
+/// Runs in the background and returns the scan id right away - progress is emitted as `launcher:scan_progress`,
+/// the new games as `launcher:scan_complete` and a cancelled scan as `launcher:scan_cancelled`.
+/// If a scan is already running, its id is returned instead of starting another
+#[tauri::command]
+pub fn scan_for_games(app: AppHandle, force_rescan: Option<bool>) -> Result<String, String> {
+    let scan = match game_scan::begin_scan(&app) {
+        Ok(scan) => scan,
+        Err(running_id) => return Ok(running_id),
+    };
     let force = force_rescan.unwrap_or(false);
 
+    let scan_id = scan.id.clone();
+    let id = scan_id.clone();
+    std::thread::spawn(move || {
+        let result = run_game_scan(force);
+        // Ended before emitting so a scan started from the completion handler isn't refused;
+        // should the scan panic, dropping the guard ends it instead
+        drop(scan);
+        let _ = match result {
+            Some(games) => app.emit("launcher:scan_complete", serde_json::json!({ "scan_id": id, "games": games })),
+            None => app.emit("launcher:scan_cancelled", serde_json::json!({ "scan_id": id })),
+        };
+    });
+
+    Ok(scan_id)
+}
+
+/// Cancel the running game scan (it stops at the next detector or drive)
+#[tauri::command]
+pub fn cancel_game_scan() -> Result<(), String> {
+    if game_scan::cancel_scan() {
+        Ok(())
+    } else {
+        Err("No game scan is running".to_string())
+    }
+}
+
+/// Detected games not yet in the library or ignored; None when the scan was cancelled
+fn run_game_scan(force: bool) -> Option<Vec<DetectedGame>> {
+    let cache_path = get_game_scan_cache_json_path();
+    let new_games = |games: Vec<DetectedGame>| {
+        let library: GameLibrary = read_json_file(&get_game_library_json_path()).unwrap_or_default();
+        let ignored: IgnoredGames = read_json_file(&get_ignored_games_json_path()).unwrap_or_default();
+        games
+            .into_iter()
+            .filter(|g| !library.has_game_with_path(&g.executable_path) && !ignored.is_ignored(g))
+            .collect::<Vec<_>>()
+    };
+
     // Try cache first
     if !force {
         if let Ok(cache) = read_json_file::<GameScanCache>(&cache_path) {
             if cache.is_valid(GameScanCache::DEFAULT_TTL_SECONDS) {
-                let library: GameLibrary = read_json_file(&get_game_library_json_path()).unwrap_or_default();
-                let new_games: Vec<DetectedGame> = cache.games
-                    .into_iter()
-                    .filter(|g| !library.has_game_with_path(&g.executable_path))
-                    .collect();
-                return Ok(new_games);
+                return Some(new_games(cache.games));
             }
         }
     }
 
     // Fresh scan
+    let mut detectors: Vec<(&str, fn() -> Vec<DetectedGame>)> = vec![
+        ("Steam", detect_steam_games),
+        ("HoYoPlay", detect_hoyoplay_games),
+        ("Riot", detect_riot_games),
+        ("Epic Games", detect_epic_games),
+        ("Battle.net", detect_battlenet_games),
+        ("Xbox", detect_xbox_games),
+        ("Ubisoft Connect", detect_ubisoft_games),
+        ("EA app", detect_ea_games),
+    ];
+    // Opt-in since most users don't have the itch app installed
+    if crate::commands::settings::get_settings().unwrap_or_default().itch_detection_enabled {
+        detectors.push(("itch.io", detect_itch_games));
+    }
+
     let mut all_games = Vec::new();
-    let steam_games = detect_steam_games();
-    all_games.extend(steam_games);
-    let hoyoplay_games = detect_hoyoplay_games();
-    all_games.extend(hoyoplay_games);
-    let riot_games = detect_riot_games();  // NEW: Detect Riot Games (Valorant, LoL, etc.)
-    all_games.extend(riot_games);
-
-    // Save to cache
-    let cache = GameScanCache::new(all_games.clone());
-    let _ = write_json_file(&cache_path, &cache);
-
-    // Filter against library
-    let library: GameLibrary = read_json_file(&get_game_library_json_path()).unwrap_or_default();
-    let new_games: Vec<DetectedGame> = all_games
+    for (phase, detect) in detectors {
+        if game_scan::is_cancelled() {
+            return None;
+        }
+        game_scan::report_phase(phase);
+        let games = detect();
+        game_scan::add_games_found(games.len());
+        all_games.extend(games);
+    }
+
+    if game_scan::is_cancelled() {
+        return None;
+    }
+    game_scan::report_phase("Steam shortcuts");
+    // Non-Steam shortcuts often point at games another detector already found
+    let detected_paths: HashSet<String> = all_games.iter().map(|g| g.executable_path.to_lowercase()).collect();
+    let shortcuts: Vec<DetectedGame> = detect_steam_shortcuts()
         .into_iter()
-        .filter(|g| !library.has_game_with_path(&g.executable_path))
+        .filter(|g| !detected_paths.contains(&g.executable_path.to_lowercase()))
         .collect();
+    game_scan::add_games_found(shortcuts.len());
+    all_games.extend(shortcuts);
+
+    // A detector may have stopped partway through its drives
+    if game_scan::is_cancelled() {
+        return None;
+    }
+
+    // Flag library games that have since been uninstalled
+    let _ = validate_game_library();
+
+    // Save to cache - unfiltered, so un-ignoring a game doesn't need a rescan.
+    // Skipped when the cache was cleared mid-scan
+    if game_scan::can_write_cache() {
+        let cache = GameScanCache::new(all_games.clone());
+        let _ = write_json_file(&cache_path, &cache);
+    }
 
-    Ok(new_games)
+    Some(new_games(all_games))
 }
 
 /// Clear game scan cache
 #[tauri::command]
 pub fn clear_game_scan_cache() -> Result<(), String> {
+    // A scan still running shouldn't write results from before the clear
+    game_scan::invalidate_cache();
     let cache_path = get_game_scan_cache_json_path();
     if cache_path.exists() {
         std::fs::remove_file(&cache_path)
@@ -96,19 +403,64 @@ pub fn clear_game_scan_cache() -> Result<(), String> {
     Ok(())
 }
 
+/// Hide a detected game from future scans
+/// `key` is the game's app_id, or its executable path when it has none
+#[tauri::command]
+pub fn ignore_detected_game(key: String, name: Option<String>) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Key is required".to_string());
+    }
+
+    let path = get_ignored_games_json_path();
+    let mut ignored: IgnoredGames = read_json_file(&path).unwrap_or_default();
+    if ignored.add(key, name) {
+        write_json_file(&path, &ignored)?;
+    }
+    Ok(())
+}
+
+/// Offer a previously ignored game in scans again
+#[tauri::command]
+pub fn unignore_detected_game(key: String) -> Result<(), String> {
+    let path = get_ignored_games_json_path();
+    let mut ignored: IgnoredGames = read_json_file(&path).unwrap_or_default();
+    if !ignored.remove(&key) {
+        return Err("Game is not ignored".to_string());
+    }
+    write_json_file(&path, &ignored)
+}
+
+#[tauri::command]
+pub fn get_ignored_games() -> Result<Vec<IgnoredGame>, String> {
+    let ignored: IgnoredGames = read_json_file(&get_ignored_games_json_path()).unwrap_or_default();
+    Ok(ignored.games)
+}
+
 /// Add detected games to library
 #[tauri::command]
 pub fn add_detected_games(games: Vec<DetectedGame>) -> Result<GameLibrary, String> {
     let mut library: GameLibrary = read_json_file(&get_game_library_json_path()).unwrap_or_default();
     let mut whitelist: GameWhitelist = read_json_file(&get_game_whitelist_json_path()).unwrap_or_default();
+    let ignored: IgnoredGames = read_json_file(&get_ignored_games_json_path()).unwrap_or_default();
 
     for game in games {
-        // Skip if already in library (check by app_id for Riot games, executable_path for others)
-        if game.app_id.is_some() {
-            if library.games.iter().any(|g| g.app_id == game.app_id) {
-                continue;
+        if ignored.is_ignored(&game) {
+            continue;
+        }
+
+        // Skip if already in library (check by app_id for launcher games, executable_path for others)
+        // Existing entries are left untouched so user-set launch options and collections survive re-scans;
+        // only a missing icon is filled in, and never over one the user chose
+        let existing = library.games.iter_mut().find(|g| match &game.app_id {
+            Some(_) => g.app_id == game.app_id,
+            None => g.executable_path.to_lowercase() == game.executable_path.to_lowercase(),
+        });
+        if let Some(existing) = existing {
+            let icon_missing = !existing.icon_path.as_deref().is_some_and(|p| Path::new(p).exists());
+            if !existing.icon_user_set && icon_missing && game.icon_path.is_some() {
+                existing.icon_path = game.icon_path;
             }
-        } else if library.has_game_with_path(&game.executable_path) {
             continue;
         }
 
@@ -124,11 +476,26 @@ pub fn add_detected_games(games: Vec<DetectedGame>) -> Result<GameLibrary, Strin
             source: game.source,
             app_id: game.app_id,
             icon_path: game.icon_path,
+            icon_user_set: false,
             process_name: process_name.clone(),
             added_at: chrono::Utc::now().to_rfc3339(),
             last_played: None,
             total_playtime_seconds: 0,
+            session_count: 0,
             launch_args: game.launch_args,
+            custom_launch_args: None,
+            run_as_admin: false,
+            launch_via_steam: None,
+            env_vars: HashMap::new(),
+            working_dir: None,
+            favorite: false,
+            sort_index: None,
+            artwork: GameArtwork::default(),
+            pre_launch_command: None,
+            post_exit_command: None,
+            installed: true,
+            collection_ids: Vec::new(),
+            hide_from_presence: false,
         };
 
         library.add_game(library_game);
@@ -140,6 +507,9 @@ pub fn add_detected_games(games: Vec<DetectedGame>) -> Result<GameLibrary, Strin
                 process_name,
                 icon: None,
                 enabled: true,
+                affinity_mask: None,
+                discord_app: None,
+                hide_from_presence: false,
             });
         }
     }
@@ -208,11 +578,26 @@ pub fn add_manual_game(request: AddGameRequest) -> Result<GameLibrary, String> {
         source: GameSource::Manual,
         app_id: None,
         icon_path: request.icon_path,
+        icon_user_set: false,
         process_name: process_name.clone(),
         added_at: chrono::Utc::now().to_rfc3339(),
         last_played: None,
         total_playtime_seconds: 0,
+        session_count: 0,
         launch_args: None,
+        custom_launch_args: None,
+        run_as_admin: false,
+        launch_via_steam: None,
+        env_vars: HashMap::new(),
+        working_dir: None,
+        favorite: false,
+        sort_index: None,
+        artwork: GameArtwork::default(),
+        pre_launch_command: None,
+        post_exit_command: None,
+        installed: true,
+        collection_ids: Vec::new(),
+        hide_from_presence: false,
     };
 
     library.add_game(library_game);
@@ -224,6 +609,9 @@ pub fn add_manual_game(request: AddGameRequest) -> Result<GameLibrary, String> {
             process_name,
             icon: None,
             enabled: true,
+            affinity_mask: None,
+            discord_app: None,
+            hide_from_presence: false,
         });
     }
 
@@ -245,6 +633,7 @@ pub fn remove_game_from_library(game_id: String) -> Result<GameLibrary, String>
     if !library.remove_game(&game_id) {
         return Err("Game not found in library".to_string());
     }
+    let _ = purge_launch_history(&game_id);
 
     write_json_file(&get_game_library_json_path(), &library)
         .map_err(|e| format!("Failed to save game library: {}", e))?;
@@ -252,13 +641,408 @@ pub fn remove_game_from_library(game_id: String) -> Result<GameLibrary, String>
     Ok(library)
 }
 
+/// Most environment variables a game can set
+const MAX_ENV_VARS: usize = 64;
+const MAX_ENV_NAME_LEN: usize = 256;
+const MAX_ENV_VALUE_LEN: usize = 4096;
+
+fn validate_env_vars(env_vars: &HashMap<String, String>) -> Result<(), String> {
+    if env_vars.len() > MAX_ENV_VARS {
+        return Err(format!("Too many environment variables (at most {})", MAX_ENV_VARS));
+    }
+    for (name, value) in env_vars {
+        let valid_name = !name.is_empty()
+            && name.len() <= MAX_ENV_NAME_LEN
+            && !name.chars().any(|c| c == '=' || c.is_whitespace() || c.is_control());
+        if !valid_name {
+            return Err(format!("Invalid environment variable name: {:?}", name));
+        }
+        if value.len() > MAX_ENV_VALUE_LEN || value.contains(['\n', '\r', '\0']) {
+            return Err(format!("Invalid value for environment variable {}", name));
+        }
+    }
+    Ok(())
+}
+
+/// Set a library game's own launch arguments, elevation, working directory, environment variables and
+/// (Steam games) protocol launch
+/// Arguments are appended to any the detector set; store-brokered launches (Epic, Battle.net, Ubisoft, EA, Xbox,
+/// Steam via steam://) skip all of these options and `launch_game` warns about it.
+/// `launch_via_steam: None` follows the global setting
+#[tauri::command]
+pub fn update_game_launch_options(
+    game_id: String,
+    args: Option<String>,
+    run_as_admin: bool,
+    working_dir: Option<String>,
+    launch_via_steam: Option<bool>,
+    env_vars: Option<HashMap<String, String>>,
+) -> Result<LibraryGame, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    let env_vars: HashMap<String, String> = env_vars
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name.trim().to_string(), value))
+        .collect();
+    validate_env_vars(&env_vars)?;
+
+    let args = args.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    let working_dir = working_dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    if let Some(dir) = &working_dir {
+        if !Path::new(dir).is_dir() {
+            return Err(format!("Working directory does not exist: {}", dir));
+        }
+    }
+
+    let game = library
+        .find_by_id_mut(&game_id)
+        .ok_or_else(|| "Game not found".to_string())?;
+    game.custom_launch_args = args;
+    game.run_as_admin = run_as_admin;
+    game.working_dir = working_dir;
+    game.launch_via_steam = launch_via_steam;
+    game.env_vars = env_vars;
+    let updated = game.clone();
+
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+    Ok(updated)
+}
+
+/// Set or clear a game's pre-launch and post-exit commands
+#[tauri::command]
+pub fn update_game_hooks(
+    game_id: String,
+    pre_launch_command: Option<GameHookCommand>,
+    post_exit_command: Option<GameHookCommand>,
+) -> Result<LibraryGame, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    for hook in pre_launch_command.iter().chain(post_exit_command.iter()) {
+        validate_hook(hook)?;
+    }
+
+    let game = library
+        .find_by_id_mut(&game_id)
+        .ok_or_else(|| "Game not found".to_string())?;
+    game.pre_launch_command = pre_launch_command;
+    game.post_exit_command = post_exit_command;
+    let updated = game.clone();
+
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+    Ok(updated)
+}
+
+/// Library games ordered by their most recent launch (default: the last 5)
+#[tauri::command]
+pub fn get_recently_played(limit: Option<usize>) -> Result<Vec<RecentlyPlayedGame>, String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let history = load_launch_history();
+
+    let mut recent: Vec<RecentlyPlayedGame> = library
+        .games
+        .into_iter()
+        .filter_map(|game| {
+            let last = history.last_launch(&game.id)?.clone();
+            Some(RecentlyPlayedGame {
+                game,
+                last_launched_at: last.launched_at,
+                last_exited_at: last.exited_at,
+            })
+        })
+        .collect();
+    recent.sort_by(|a, b| b.last_launched_at.cmp(&a.last_launched_at));
+    recent.truncate(limit.unwrap_or(5));
+
+    Ok(recent)
+}
+
+/// Accumulated playtime of one library game
+#[tauri::command]
+pub fn get_game_playtime(game_id: String) -> Result<GamePlaytime, String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    library
+        .find_by_id(&game_id)
+        .map(GamePlaytime::from)
+        .ok_or_else(|| "Game not found".to_string())
+}
+
+/// Accumulated playtime of every library game, most played first
+#[tauri::command]
+pub fn get_all_playtimes() -> Result<Vec<GamePlaytime>, String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    let mut playtimes: Vec<GamePlaytime> = library.games.iter().map(GamePlaytime::from).collect();
+    playtimes.sort_by(|a, b| b.total_seconds.cmp(&a.total_seconds));
+    Ok(playtimes)
+}
+
+/// Clear a game's accumulated playtime and session count (last played is kept)
+#[tauri::command]
+pub fn reset_game_playtime(game_id: String) -> Result<GamePlaytime, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    let game = library
+        .find_by_id_mut(&game_id)
+        .ok_or_else(|| "Game not found".to_string())?;
+    game.total_playtime_seconds = 0;
+    game.session_count = 0;
+    let playtime = GamePlaytime::from(&*game);
+
+    write_json_file(&get_game_library_json_path(), &library)
+        .map_err(|e| format!("Failed to save game library: {}", e))?;
+
+    Ok(playtime)
+}
+
+/// Prefix of the error `launch_game` returns for a game whose files are gone
+pub const GAME_UNINSTALLED_ERROR: &str = "Game appears uninstalled";
+
+/// Re-check every library game's files and update its `installed` flag
+/// Returns the games that are missing so the UI can offer to remove them - nothing is removed here
+#[tauri::command]
+pub fn validate_game_library() -> Result<Vec<LibraryGame>, String> {
+    let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    let mut changed = false;
+    for game in library.games.iter_mut() {
+        let installed = game.files_present();
+        changed |= game.installed != installed;
+        game.installed = installed;
+    }
+
+    if changed {
+        write_json_file(&get_game_library_json_path(), &library)
+            .map_err(|e| format!("Failed to save game library: {}", e))?;
+    }
+
+    Ok(library.games.into_iter().filter(|g| !g.installed).collect())
+}
+
+/// Prefix of the error `open_game_folder` / `reveal_game_executable` return when the path is gone
+pub const GAME_PATH_MISSING_ERROR: &str = "Game files not found";
+
+/// The folder a game is installed in
+/// Every detector sets install_path to the game's own folder (Riot's is the game, not the Riot Client;
+/// Xbox's is the package location); the exe's folder covers entries saved without one
+fn resolve_game_folder(game: &LibraryGame) -> Option<PathBuf> {
+    Some(PathBuf::from(&game.install_path))
+        .filter(|p| !p.as_os_str().is_empty())
+        .or_else(|| Path::new(&game.executable_path).parent().map(Path::to_path_buf))
+}
+
+/// The game's own exe - for launcher-started entries (Riot) that's the monitored process found
+/// under the install folder rather than the launcher in executable_path
+fn resolve_game_executable(game: &LibraryGame) -> PathBuf {
+    let exe_path = PathBuf::from(&game.executable_path);
+    let is_game_exe = exe_path
+        .file_name()
+        .map(|n| n.to_string_lossy().eq_ignore_ascii_case(&game.process_name))
+        .unwrap_or(false);
+    if is_game_exe {
+        return exe_path;
+    }
+
+    resolve_game_folder(game)
+        .and_then(|folder| find_file(&folder, &game.process_name, 5))
+        .unwrap_or(exe_path)
+}
+
+/// Depth-limited search for a file name (case-insensitive)
+fn find_file(dir: &Path, file_name: &str, max_depth: u32) -> Option<PathBuf> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).collect();
+    if let Some(found) = entries.iter().find(|p| {
+        p.is_file() && p.file_name().map(|n| n.to_string_lossy().eq_ignore_ascii_case(file_name)).unwrap_or(false)
+    }) {
+        return Some(found.clone());
+    }
+    if max_depth == 0 {
+        return None;
+    }
+    entries.iter().filter(|p| p.is_dir()).find_map(|p| find_file(p, file_name, max_depth - 1))
+}
+
+/// Open a library game's install folder in Explorer
+#[tauri::command]
+pub fn open_game_folder(game_id: String) -> Result<(), String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let game = library.find_by_id(&game_id).ok_or_else(|| "Game not found".to_string())?;
+
+    let folder = resolve_game_folder(game)
+        .filter(|p| p.is_dir())
+        .ok_or_else(|| format!("{}: {}", GAME_PATH_MISSING_ERROR, game.install_path))?;
+
+    show_in_explorer(&folder, false)
+}
+
+/// Open Explorer with a library game's executable selected
+#[tauri::command]
+pub fn reveal_game_executable(game_id: String) -> Result<(), String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let game = library.find_by_id(&game_id).ok_or_else(|| "Game not found".to_string())?;
+
+    let exe_path = resolve_game_executable(game);
+    if !exe_path.is_file() {
+        return Err(format!("{}: {}", GAME_PATH_MISSING_ERROR, exe_path.display()));
+    }
+
+    show_in_explorer(&exe_path, true)
+}
+
+#[cfg(windows)]
+fn show_in_explorer(path: &Path, select: bool) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+
+    let mut cmd = std::process::Command::new("explorer.exe");
+    if select {
+        // Explorer wants the quotes after the comma, which Command's own quoting doesn't produce
+        cmd.raw_arg(format!("/select,\"{}\"", path.display()));
+    } else {
+        cmd.arg(path);
+    }
+    cmd.spawn()
+        .map_err(|e| format!("Failed to open Explorer: {}", e))?;
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn show_in_explorer(path: &Path, select: bool) -> Result<(), String> {
+    let target = if select { path.parent().unwrap_or(path) } else { path };
+    std::process::Command::new("xdg-open")
+        .arg(target)
+        .spawn()
+        .map_err(|e| format!("Failed to open folder: {}", e))?;
+    Ok(())
+}
+
+/// Create a Desktop or Start Menu shortcut that launches a library game the same way `launch_game` does
+/// Returns the shortcut's path. Run-as-admin isn't carried over
+#[tauri::command]
+pub fn create_game_shortcut(game_id: String, location: ShortcutLocation, overwrite: Option<bool>) -> Result<String, String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let game = library.find_by_id(&game_id).ok_or_else(|| "Game not found".to_string())?;
+
+    let dir = shortcut_dir(location).ok_or_else(|| "Shortcut folder not found".to_string())?;
+    let path = dir.join(shortcut_file_name(&game.name));
+    if path.exists() && !overwrite.unwrap_or(false) {
+        return Err(format!("Shortcut already exists: {}", path.display()));
+    }
+
+    let (target, args) = launch_target(game)?;
+    // Store protocol URLs (Epic, Ubisoft, EA) can't be a .lnk target, so hand them to Explorer like Xbox apps
+    let (target, args) = if target.contains("://") {
+        ("explorer.exe".to_string(), Some(target))
+    } else {
+        (target, args)
+    };
+    let working_dir = game.working_dir.clone().or_else(|| {
+        Path::new(&target)
+            .parent()
+            .filter(|p| p.is_absolute())
+            .map(|p| p.to_string_lossy().to_string())
+    });
+
+    let icon_location = game
+        .icon_path
+        .as_deref()
+        .and_then(|icon| icon_to_ico(Path::new(icon)))
+        .or_else(|| Some(resolve_game_executable(game)).filter(|exe| exe.is_file()))
+        .map(|icon| format!("{},0", icon.display()));
+
+    write_shortcut(
+        &path,
+        &ShortcutSpec {
+            target: &target,
+            args: args.as_deref(),
+            working_dir: working_dir.as_deref(),
+            icon_location,
+        },
+    )?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Library games whose process is running, matched on the game's own process name
+/// (Riot and HoYoPlay games are tracked by the game process, so an open launcher doesn't count)
+#[tauri::command]
+pub fn get_running_games() -> Result<Vec<RunningGame>, String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+
+    let names: Vec<String> = library.games.iter().map(|g| g.process_name.clone()).collect();
+    let processes = task_monitor::find_processes_by_name(&names);
+
+    Ok(library
+        .games
+        .iter()
+        .flat_map(|game| {
+            processes
+                .iter()
+                .filter(|(_, name, _)| name.eq_ignore_ascii_case(&game.process_name))
+                .map(|(pid, name, started_at)| RunningGame {
+                    game_id: game.id.clone(),
+                    pid: *pid,
+                    process_name: name.clone(),
+                    started_at: *started_at,
+                })
+        })
+        .collect())
+}
+
+/// Close a running library game - its windows are asked to close first, then it's force-closed
+/// Unlike task monitor kills, the game isn't added to the restore list
+#[tauri::command]
+pub async fn stop_game(game_id: String) -> Result<(), String> {
+    let library: GameLibrary = read_json_file(&get_game_library_json_path())
+        .map_err(|e| format!("Failed to read game library: {}", e))?;
+    let game = library.find_by_id(&game_id).ok_or_else(|| "Game not found".to_string())?;
+
+    let processes = task_monitor::find_processes_by_name(std::slice::from_ref(&game.process_name));
+    if processes.is_empty() {
+        return Err("Game is not running".to_string());
+    }
+
+    // Every process is asked to close at once, so stopping waits for one graceful timeout, not one each
+    tokio::task::spawn_blocking(move || {
+        std::thread::scope(|scope| {
+            let closes: Vec<_> = processes
+                .iter()
+                .map(|(pid, _, _)| scope.spawn(move || task_monitor::close_process(*pid, KillMode::Graceful)))
+                .collect();
+            closes
+                .into_iter()
+                .map(|close| close.join().unwrap_or_else(|_| Err("Closing the game panicked".to_string())))
+                .collect::<Result<Vec<_>, String>>()
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to stop game: {}", e))??;
+    Ok(())
+}
+
 /// Launch a game
+/// Returns warnings about settings that couldn't be applied to this launch (e.g. environment variables)
 #[tauri::command]
-pub fn launch_game(
+pub async fn launch_game(
     app_handle: AppHandle,
     game_id: String,
     playtime_state: State<'_, Arc<PlaytimeTrackerState>>,
-) -> Result<(), String> {
+) -> Result<Vec<String>, String> {
     let mut library: GameLibrary = read_json_file(&get_game_library_json_path())
         .map_err(|e| format!("Failed to read game library: {}", e))?;
 
@@ -266,8 +1050,62 @@ pub fn launch_game(
         .find_by_id(&game_id)
         .ok_or_else(|| "Game not found".to_string())?;
 
-    let exe_path = game.executable_path.clone();
-    let launch_args = game.launch_args.clone();
+    if !game.files_present() {
+        return Err(format!("{} (last known path: {})", GAME_UNINSTALLED_ERROR, game.executable_path));
+    }
+
+    let (exe_path, launch_args) = launch_target(game)?;
+
+    // A store's launcher starts the game itself, so none of the game's own launch options can reach it
+    let mut warnings = Vec::new();
+    let brokered = exe_path != game.executable_path;
+    if brokered {
+        let ignored: Vec<&str> = [
+            (game.custom_launch_args.is_some(), "launch arguments"),
+            (game.run_as_admin, "run as administrator"),
+            (game.working_dir.is_some(), "working directory"),
+            (!game.env_vars.is_empty(), "environment variables"),
+        ]
+        .into_iter()
+        .filter_map(|(set, option)| set.then_some(option))
+        .collect();
+        if !ignored.is_empty() {
+            warnings.push(format!(
+                "Not applied because the game is started through its store: {}",
+                ignored.join(", ")
+            ));
+        }
+    }
+    let working_dir = game.working_dir.clone().filter(|_| !brokered);
+    let run_as_admin = game.run_as_admin && !brokered;
+
+    // Variables can only reach a process Atlas starts itself without elevation
+    let env_vars = if game.env_vars.is_empty() || brokered {
+        HashMap::new()
+    } else if run_as_admin {
+        warnings.push("Environment variables were not applied: they can't be passed to a game run as administrator".to_string());
+        HashMap::new()
+    } else {
+        game.env_vars.clone()
+    };
+
+    if let Some(hook) = game.pre_launch_command.clone() {
+        // Waiting for the hook would otherwise block the thread running this command
+        let required = hook.required;
+        let result = tokio::task::spawn_blocking(move || run_hook(&hook, "Pre-launch"))
+            .await
+            .map_err(|e| format!("Pre-launch command failed: {}", e))?;
+        if let Err(e) = result {
+            if required {
+                return Err(e);
+            }
+            let _ = app_handle.emit("launcher:hook_failed", serde_json::json!({
+                "game_id": game_id,
+                "hook": "pre_launch",
+                "error": e,
+            }));
+        }
+    }
     let process_name = game.process_name.clone();
     let game_id_clone = game_id.clone();
 
@@ -277,7 +1115,13 @@ pub fn launch_game(
     }
     let _ = write_json_file(&get_game_library_json_path(), &library);
 
-    launch_process_silent(&exe_path, launch_args.as_deref())?;
+    // ShellExecuteEx waits while a run-as-admin game's UAC prompt is open, so it runs on a blocking thread
+    tokio::task::spawn_blocking(move || {
+        launch_process_silent(&exe_path, launch_args.as_deref(), working_dir.as_deref(), run_as_admin, &env_vars)
+    })
+    .await
+    .map_err(|e| format!("Failed to launch game: {}", e))??;
+    let _ = record_launch(&game_id);
 
     start_game_session(
         app_handle.clone(),
@@ -288,70 +1132,298 @@ pub fn launch_game(
 
     let _ = app_handle.emit("launcher:navigate_to_gaming", ());
 
-    Ok(())
+    Ok(warnings)
+}
+
+/// Error returned by `launch_game` when the user declines the UAC prompt of a run-as-admin game
+#[cfg_attr(not(windows), allow(dead_code))]
+pub const UAC_CANCELLED_ERROR: &str = "Launch cancelled: administrator permission was not granted";
+
+/// What to hand to ShellExecute, with its arguments
+/// Stores that broker the launch themselves (online auth, updates) get their launcher instead of the exe,
+/// which has no way to forward custom arguments
+fn launch_target(game: &LibraryGame) -> Result<(String, Option<String>), String> {
+    let store_app = |prefix: &str| game.app_id.as_deref().and_then(|id| id.strip_prefix(prefix));
+
+    match game.source {
+        GameSource::Steam => {
+            let via_steam = game
+                .launch_via_steam
+                .unwrap_or_else(|| crate::commands::settings::get_settings().unwrap_or_default().steam_launch_via_protocol);
+            if via_steam {
+                // steam:// with Steam closed would sit through Steam's startup and login first
+                match &game.app_id {
+                    Some(app_id) if steam_is_running() => return Ok((format!("steam://rungameid/{}", app_id), None)),
+                    Some(_) => warn!("Steam is not running, launching {} directly", game.name),
+                    None => warn!("{} has no Steam app id, launching it directly", game.name),
+                }
+            }
+        }
+        GameSource::Epic => {
+            if let Some(app_name) = store_app(EPIC_APP_ID_PREFIX) {
+                return Ok((epic_launch_url(app_name), None));
+            }
+        }
+        GameSource::BattleNet => {
+            if let Some(config) = store_app(BATTLENET_APP_ID_PREFIX).and_then(BattleNetGameConfig::find_by_launch_code) {
+                let client = find_battlenet_client().ok_or_else(|| "Battle.net is not installed".to_string())?;
+                return Ok((client.to_string_lossy().to_string(), Some(config.get_launch_args())));
+            }
+        }
+        GameSource::Ubisoft => {
+            if let Some(install_id) = store_app(UBISOFT_APP_ID_PREFIX) {
+                return Ok((ubisoft_launch_url(install_id), None));
+            }
+        }
+        GameSource::Ea => {
+            if let Some(offer_ids) = store_app(EA_APP_ID_PREFIX) {
+                return Ok((ea_launch_url(offer_ids), None));
+            }
+        }
+        // Packaged apps can't be started from their exe
+        GameSource::Xbox => {
+            let app_user_model_id = store_app(XBOX_APP_ID_PREFIX)
+                .ok_or_else(|| "Xbox game is missing its package id - re-add it from a scan".to_string())?;
+            return Ok(("explorer.exe".to_string(), Some(xbox_launch_target(app_user_model_id))));
+        }
+        _ => {}
+    }
+
+    Ok((game.executable_path.clone(), game.combined_launch_args()))
+}
+
+fn steam_is_running() -> bool {
+    !task_monitor::find_processes_by_name(&["steam.exe".to_string()]).is_empty()
 }
 
 #[cfg(windows)]
-fn launch_process_silent(exe_path: &str, args: Option<&str>) -> Result<(), String> {
+fn launch_process_silent(
+    exe_path: &str,
+    args: Option<&str>,
+    working_dir: Option<&str>,
+    run_as_admin: bool,
+    env_vars: &HashMap<String, String>,
+) -> Result<(), String> {
     use std::ffi::OsStr;
     use std::os::windows::ffi::OsStrExt;
-    use std::ptr::null_mut;
-    use windows_sys::Win32::UI::Shell::ShellExecuteW;
+    use std::ptr::null;
+    use windows_sys::Win32::Foundation::{GetLastError, ERROR_CANCELLED};
+    use windows_sys::Win32::UI::Shell::{ShellExecuteExW, SEE_MASK_NOASYNC, SHELLEXECUTEINFOW};
     use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
 
     fn to_wide(s: &str) -> Vec<u16> {
         OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect()
     }
 
-    let operation = to_wide("open");
+    // ShellExecute can't set the new process's environment, so games with variables are started
+    // directly (launch_game never passes variables for elevated launches)
+    if !env_vars.is_empty() {
+        use std::os::windows::process::CommandExt;
+
+        let mut cmd = std::process::Command::new(exe_path);
+        if let Some(args) = args {
+            cmd.raw_arg(args);
+        }
+        if let Some(dir) = working_dir.map(Path::new).or_else(|| Path::new(exe_path).parent()) {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env_vars)
+            .spawn()
+            .map_err(|e| format!("Failed to launch game: {}", e))?;
+        return Ok(());
+    }
+
+    // "runas" raises the UAC prompt; the elevated game keeps its exe name, so the playtime tracker
+    // and gaming detector still find it by process name
+    let operation = to_wide(if run_as_admin { "runas" } else { "open" });
     let file = to_wide(exe_path);
 
     // Convert args to wide string if present
     let args_wide = args.map(|a| to_wide(a));
-    let args_ptr = args_wide.as_ref().map(|a| a.as_ptr()).unwrap_or(null_mut());
-
-    let result = unsafe {
-        ShellExecuteW(
-            null_mut(),           // hwnd
-            operation.as_ptr(),   // lpOperation ("open")
-            file.as_ptr(),        // lpFile (executable path)
-            args_ptr,             // lpParameters (command line arguments)
-            null_mut(),           // lpDirectory (working directory)
-            SW_SHOWNORMAL as i32, // nShowCmd
-        )
-    };
+    let dir_wide = working_dir.map(to_wide);
 
-    if result as isize > 32 {
-        Ok(())
-    } else {
-        let error_msg = match result as isize {
-            0 => "Out of memory",
-            2 => "File not found",
-            3 => "Path not found",
-            5 => "Access denied",
-            8 => "Out of memory",
-            11 => "Invalid executable format",
-            26 => "Sharing violation",
-            27 => "Association incomplete",
-            28 => "DDE timeout",
-            29 => "DDE failed",
-            30 => "DDE busy",
-            31 => "No association",
-            32 => "DLL not found",
-            _ => "Unknown error",
-        };
-        Err(format!("Failed to launch game: {} (code {})", error_msg, result as isize))
+    let mut info: SHELLEXECUTEINFOW = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<SHELLEXECUTEINFOW>() as u32;
+    info.fMask = SEE_MASK_NOASYNC; // Called on a blocking thread, so wait for the launch to be handed off
+    info.lpVerb = operation.as_ptr();
+    info.lpFile = file.as_ptr();
+    info.lpParameters = args_wide.as_ref().map(|a| a.as_ptr()).unwrap_or(null());
+    info.lpDirectory = dir_wide.as_ref().map(|d| d.as_ptr()).unwrap_or(null());
+    info.nShow = SW_SHOWNORMAL as i32;
+
+    if unsafe { ShellExecuteExW(&mut info) } != 0 {
+        return Ok(());
     }
+
+    if unsafe { GetLastError() } == ERROR_CANCELLED {
+        return Err(UAC_CANCELLED_ERROR.to_string());
+    }
+
+    // On failure hInstApp carries the same SE_ERR_* code ShellExecute returns
+    let code = info.hInstApp as isize;
+    let error_msg = match code {
+        0 => "Out of memory",
+        2 => "File not found",
+        3 => "Path not found",
+        5 => "Access denied",
+        8 => "Out of memory",
+        11 => "Invalid executable format",
+        26 => "Sharing violation",
+        27 => "Association incomplete",
+        28 => "DDE timeout",
+        29 => "DDE failed",
+        30 => "DDE busy",
+        31 => "No association",
+        32 => "DLL not found",
+        _ => "Unknown error",
+    };
+    Err(format!("Failed to launch game: {} (code {})", error_msg, code))
 }
 
 #[cfg(not(windows))]
-fn launch_process_silent(exe_path: &str, args: Option<&str>) -> Result<(), String> {
+fn launch_process_silent(
+    exe_path: &str,
+    args: Option<&str>,
+    working_dir: Option<&str>,
+    _run_as_admin: bool,
+    env_vars: &HashMap<String, String>,
+) -> Result<(), String> {
     let mut cmd = std::process::Command::new(exe_path);
+    cmd.envs(env_vars);
     if let Some(args_str) = args {
         cmd.args(args_str.split_whitespace());
     }
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
     cmd.spawn()
         .map_err(|e| format!("Failed to launch game: {}", e))?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const ALL_SOURCES: [GameSource; 11] = [
+        GameSource::Steam,
+        GameSource::HoyoPlay,
+        GameSource::Riot,
+        GameSource::Epic,
+        GameSource::BattleNet,
+        GameSource::Xbox,
+        GameSource::Ubisoft,
+        GameSource::Ea,
+        GameSource::Itch,
+        GameSource::SteamShortcut,
+        GameSource::Manual,
+    ];
+
+    fn library_game(source: GameSource, executable_path: &Path, install_path: &Path, process_name: &str) -> LibraryGame {
+        let mut game: LibraryGame = serde_json::from_value(serde_json::json!({
+            "id": "game",
+            "name": "Game",
+            "executable_path": executable_path.to_string_lossy(),
+            "install_path": install_path.to_string_lossy(),
+            "source": "manual",
+            "app_id": null,
+            "icon_path": null,
+            "process_name": process_name,
+            "added_at": "2026-01-01T00:00:00Z",
+            "last_played": null,
+            "total_playtime_seconds": 0,
+        }))
+        .unwrap();
+        game.source = source;
+        game
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas_game_folder_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_game_in_its_install_folder() {
+        let dir = temp_dir();
+        let exe = dir.join("Game.exe");
+        fs::write(&exe, b"").unwrap();
+
+        for source in [GameSource::Steam, GameSource::Epic, GameSource::Ubisoft, GameSource::Itch, GameSource::Manual] {
+            let game = library_game(source, &exe, &dir, "Game.exe");
+            assert_eq!(resolve_game_folder(&game), Some(dir.clone()));
+            assert_eq!(resolve_game_executable(&game), exe);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_riot_game_resolves_past_the_riot_client() {
+        let dir = temp_dir();
+        let client = dir.join("Riot Client").join("RiotClientServices.exe");
+        let install = dir.join("VALORANT").join("live");
+        let game_exe = install.join("ShooterGame").join("Binaries").join("Win64").join("VALORANT-Win64-Shipping.exe");
+        fs::create_dir_all(client.parent().unwrap()).unwrap();
+        fs::create_dir_all(game_exe.parent().unwrap()).unwrap();
+        fs::write(&client, b"").unwrap();
+        fs::write(&game_exe, b"").unwrap();
+
+        let game = library_game(GameSource::Riot, &client, &install, "valorant-win64-shipping.exe");
+        assert_eq!(resolve_game_folder(&game), Some(install.clone()));
+        assert_eq!(resolve_game_executable(&game), game_exe);
+
+        // Not installed under the folder: falls back to the launcher rather than failing
+        let game = library_game(GameSource::Riot, &client, &install, "LeagueClient.exe");
+        assert_eq!(resolve_game_executable(&game), client);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_xbox_and_launcher_games_use_the_install_folder() {
+        let dir = temp_dir();
+        let package = dir.join("WindowsApps").join("Microsoft.Game_1.0.0.0_x64__8wekyb3d8bbwe");
+        let exe = package.join("Content").join("Game.exe");
+        fs::create_dir_all(exe.parent().unwrap()).unwrap();
+        fs::write(&exe, b"").unwrap();
+
+        for source in [GameSource::Xbox, GameSource::HoyoPlay, GameSource::BattleNet, GameSource::Ea] {
+            let game = library_game(source, &exe, &package, "Game.exe");
+            assert_eq!(resolve_game_folder(&game), Some(package.clone()));
+            assert_eq!(resolve_game_executable(&game), exe);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_steam_shortcut_without_install_path_uses_the_exe_folder() {
+        let dir = temp_dir();
+        let exe = dir.join("Emulator.exe");
+        fs::write(&exe, b"").unwrap();
+
+        let game = library_game(GameSource::SteamShortcut, &exe, Path::new(""), "Emulator.exe");
+        assert_eq!(resolve_game_folder(&game), Some(dir.clone()));
+        assert_eq!(resolve_game_executable(&game), exe);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_unusual_entries_do_not_panic() {
+        for source in ALL_SOURCES {
+            let empty = library_game(source.clone(), Path::new(""), Path::new(""), "");
+            assert_eq!(resolve_game_folder(&empty), None);
+            assert_eq!(resolve_game_executable(&empty), PathBuf::new());
+
+            let missing = Path::new("/nonexistent/atlas/Game/Game.exe");
+            let gone = library_game(source, missing, Path::new("/nonexistent/atlas/Game"), "Other.exe");
+            assert_eq!(resolve_game_folder(&gone), Some(PathBuf::from("/nonexistent/atlas/Game")));
+            assert_eq!(resolve_game_executable(&gone), missing);
+        }
+    }
+}
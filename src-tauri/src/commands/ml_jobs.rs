@@ -1,16 +1,159 @@
+// ML job queue
+// Submitted jobs wait as pending until the scheduler starts them, at most `max_concurrent_ml_jobs`
+// at a time, highest priority first and then in submission order. Every change to the jobs file goes
+// through JOBS_LOCK so the scheduler, progress updates and commands don't overwrite each other
+use crate::commands::data_directory::{is_migrating, MIGRATING_MESSAGE};
+use crate::commands::settings::get_settings;
 use crate::file_manager::{read_json_file, write_json_file};
-use crate::models::{MLJob, MLJobStatus, Model, OutputFile};
+use crate::models::{MLJob, MLJobPriority, MLJobStatus, Model, OutputFile};
 use crate::process_manager::{spawn_python_worker_async, WorkerMessage};
 use crate::utils::{get_ml_jobs_json_path, get_models_dir, get_separated_audio_dir};
-use log::debug;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use parking_lot::Mutex;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fs;
 use std::path::Path;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 const PROGRESS_WRITE_DEBOUNCE_MS: u64 = 500;
 
+lazy_static! {
+    /// Held while the jobs file is read, changed and written back
+    static ref JOBS_LOCK: Mutex<()> = Mutex::new(());
+    /// Cancel senders of running jobs, by job id
+    static ref RUNNING_JOBS: Mutex<HashMap<String, oneshot::Sender<()>>> = Mutex::new(HashMap::new());
+}
+
+/// Read the jobs, let `change` edit them and write them back; nothing is written when it fails
+fn update_jobs<T>(change: impl FnOnce(&mut Vec<MLJob>) -> Result<T, String>) -> Result<T, String> {
+    let _guard = JOBS_LOCK.lock();
+    let path = get_ml_jobs_json_path();
+    let mut jobs: Vec<MLJob> = if path.exists() {
+        read_json_file(&path)?
+    } else {
+        vec![]
+    };
+
+    let value = change(&mut jobs)?;
+    write_json_file(&path, &jobs)?;
+    Ok(value)
+}
+
+fn max_concurrent_ml_jobs() -> u32 {
+    get_settings().unwrap_or_default().max_concurrent_ml_jobs.max(1)
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Indices of the pending jobs, in the order they will start
+fn queue_order(jobs: &[MLJob]) -> Vec<usize> {
+    let mut order: Vec<usize> = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, job)| job.status == MLJobStatus::Pending)
+        .map(|(index, _)| index)
+        .collect();
+    // Stable, so jobs of the same priority keep submission order
+    order.sort_by_key(|&index| Reverse(jobs[index].priority));
+    order
+}
+
+fn running_count(jobs: &[MLJob]) -> usize {
+    jobs.iter()
+        .filter(|job| job.status == MLJobStatus::Processing)
+        .count()
+}
+
+/// Average run time of the completed jobs that recorded when they started
+fn average_run_time(jobs: &[MLJob]) -> Option<chrono::Duration> {
+    let runs: Vec<i64> = jobs
+        .iter()
+        .filter(|job| job.status == MLJobStatus::Completed)
+        .filter_map(|job| {
+            let started = parse_time(job.started_at.as_deref()?)?;
+            let completed = parse_time(job.completed_at.as_deref()?)?;
+            Some((completed - started).num_seconds())
+        })
+        .filter(|&seconds| seconds > 0)
+        .collect();
+
+    if runs.is_empty() {
+        return None;
+    }
+    Some(chrono::Duration::seconds(runs.iter().sum::<i64>() / runs.len() as i64))
+}
+
+/// Fill in the queue position of the pending jobs, and their estimated start once there are past
+/// run times to go by
+fn annotate_queue(jobs: &mut [MLJob], max_concurrent: u32, now: DateTime<Utc>) {
+    for job in jobs.iter_mut() {
+        job.queue_position = None;
+        job.estimated_start = None;
+    }
+
+    let order = queue_order(jobs);
+    for (position, &index) in order.iter().enumerate() {
+        jobs[index].queue_position = Some(position as u32 + 1);
+    }
+
+    let Some(average) = average_run_time(jobs) else {
+        return;
+    };
+
+    // When each busy slot frees up; a job running longer than usual is assumed to end now
+    let mut busy_until: BinaryHeap<Reverse<DateTime<Utc>>> = jobs
+        .iter()
+        .filter(|job| job.status == MLJobStatus::Processing)
+        .map(|job| {
+            let ends = job
+                .started_at
+                .as_deref()
+                .and_then(parse_time)
+                .map_or(now, |started| started + average);
+            Reverse(ends.max(now))
+        })
+        .collect();
+
+    let slots = max_concurrent.max(1) as usize;
+    let mut clock = now;
+    for index in order {
+        while busy_until.len() >= slots {
+            if let Some(Reverse(frees_at)) = busy_until.pop() {
+                clock = clock.max(frees_at);
+            }
+        }
+        jobs[index].estimated_start = Some(clock.to_rfc3339());
+        busy_until.push(Reverse(clock + average));
+    }
+}
+
+/// Ids of the pending jobs that fit in the free slots, in queue order
+fn jobs_to_start(jobs: &[MLJob], max_concurrent: u32) -> Vec<String> {
+    let free_slots = (max_concurrent as usize).saturating_sub(running_count(jobs));
+    queue_order(jobs)
+        .into_iter()
+        .take(free_slots)
+        .map(|index| jobs[index].id.clone())
+        .collect()
+}
+
+fn mark_started(job: &mut MLJob) {
+    job.status = MLJobStatus::Processing;
+    job.progress = 0;
+    job.stage = None;
+    job.error = None;
+    job.started_at = Some(Utc::now().to_rfc3339());
+}
+
 #[tauri::command]
 pub fn list_ml_jobs() -> Result<Vec<MLJob>, String> {
     let path = get_ml_jobs_json_path();
@@ -19,24 +162,96 @@ pub fn list_ml_jobs() -> Result<Vec<MLJob>, String> {
         return Ok(vec![]);
     }
 
-    read_json_file(&path)
+    let mut jobs: Vec<MLJob> = read_json_file(&path)?;
+    annotate_queue(&mut jobs, max_concurrent_ml_jobs(), Utc::now());
+    Ok(jobs)
+}
+
+/// Whether an ML job is being processed right now
+pub(crate) fn has_processing_ml_jobs() -> Result<bool, String> {
+    Ok(list_ml_jobs()?.iter().any(|job| job.status == MLJobStatus::Processing))
+}
+
+/// Start queued jobs while there are free slots
+/// Called when a job is submitted or ends and when `max_concurrent_ml_jobs` changes
+pub(crate) fn start_queued_ml_jobs(app: &AppHandle) {
+    // Queued jobs stay queued until the moved data directory is in use
+    if is_migrating() {
+        return;
+    }
+    let max_concurrent = max_concurrent_ml_jobs();
+    let started = update_jobs(|jobs| {
+        let ids = jobs_to_start(jobs, max_concurrent);
+        let mut started = Vec::new();
+        for job in jobs.iter_mut().filter(|job| ids.contains(&job.id)) {
+            mark_started(job);
+            started.push(job.clone());
+        }
+        Ok(started)
+    });
+
+    let started = match started {
+        Ok(started) => started,
+        Err(e) => {
+            warn!("Failed to start queued ML jobs: {}", e);
+            return;
+        }
+    };
+
+    for job in started {
+        debug!("Starting queued ML job {} ({:?} priority)", job.id, job.priority);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            // Failures are reported through the job and the ml-job-error event
+            let _ = run_ml_job(app, job).await;
+        });
+    }
+}
+
+/// Put jobs a previous run left processing back in the queue, then start the queue if
+/// `resume_ml_jobs_on_startup` is on; otherwise it waits until a job is submitted
+pub(crate) fn resume_ml_job_queue(app: &AppHandle) {
+    let requeued = update_jobs(|jobs| {
+        let mut count = 0;
+        for job in jobs
+            .iter_mut()
+            .filter(|job| job.status == MLJobStatus::Processing)
+        {
+            job.status = MLJobStatus::Pending;
+            job.progress = 0;
+            job.stage = None;
+            job.started_at = None;
+            count += 1;
+        }
+        Ok(count)
+    });
+
+    match requeued {
+        Ok(0) => {}
+        Ok(count) => debug!("Requeued {} interrupted ML jobs", count),
+        Err(e) => warn!("Failed to requeue interrupted ML jobs: {}", e),
+    }
+    if get_settings().unwrap_or_default().resume_ml_jobs_on_startup {
+        start_queued_ml_jobs(app);
+    }
 }
 
 #[tauri::command]
 pub fn submit_ml_job(
+    app: AppHandle,
     input_file: String,
     model: String,
     output_dir: Option<String>,
+    priority: Option<MLJobPriority>,
 ) -> Result<serde_json::Value, String> {
     // Validate input file exists
     if !Path::new(&input_file).exists() {
         return Err(format!("Input file not found: {}", input_file));
     }
 
-    let path = get_ml_jobs_json_path();
-
     // Generate unique ID
     let job_id = uuid::Uuid::new_v4().to_string();
+    let priority = priority.unwrap_or_default();
 
     // Create new job entry
     let job = MLJob::new(
@@ -44,41 +259,63 @@ pub fn submit_ml_job(
         input_file.clone(),
         model.clone(),
         output_dir.clone(),
+        priority,
     );
 
-    // Read existing jobs
-    let mut jobs: Vec<MLJob> = if path.exists() {
-        read_json_file(&path)?
-    } else {
-        vec![]
-    };
-
-    // Add new job
-    jobs.push(job);
-
-    // Write back to file
-    write_json_file(&path, &jobs)?;
+    update_jobs(|jobs| {
+        jobs.push(job);
+        Ok(())
+    })?;
 
     debug!(
-        "Submitted ML job: {} with model: {}, output_dir: {:?}",
-        input_file, model, output_dir
+        "Submitted ML job: {} with model: {}, output_dir: {:?}, priority: {:?}",
+        input_file, model, output_dir, priority
     );
 
+    start_queued_ml_jobs(&app);
+
     Ok(serde_json::json!({ "job_id": job_id }))
 }
 
+/// Start a queued job right away, ahead of its place in the queue, when a slot is free
 #[tauri::command]
 pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::Value, String> {
-    let path = get_ml_jobs_json_path();
-
-    if !path.exists() {
-        return Err("No ML jobs file found".to_string());
+    if is_migrating() {
+        return Err(MIGRATING_MESSAGE.to_string());
     }
+    let max_concurrent = max_concurrent_ml_jobs();
+    let job = update_jobs(|jobs| {
+        let running = running_count(jobs);
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.id == job_id)
+            .ok_or_else(|| format!("ML job not found: {}", job_id))?;
 
-    let mut jobs: Vec<MLJob> = read_json_file(&path)?;
+        if job.status != MLJobStatus::Pending {
+            return Err(format!(
+                "Cannot start job with status {:?}",
+                job.status
+            ));
+        }
 
-    // Find the job and extract needed values
-    let (input_file, model, output_dir) = {
+        if running >= max_concurrent as usize {
+            return Err(format!(
+                "Maximum concurrent ML jobs reached ({}/{}). The job will start when one finishes.",
+                running, max_concurrent
+            ));
+        }
+
+        mark_started(job);
+        Ok(job.clone())
+    })?;
+
+    run_ml_job(app, job).await
+}
+
+/// Change the priority of a queued ML job, which moves it in the queue
+#[tauri::command]
+pub fn set_ml_job_priority(job_id: String, priority: MLJobPriority) -> Result<(), String> {
+    update_jobs(|jobs| {
         let job = jobs
             .iter_mut()
             .find(|j| j.id == job_id)
@@ -86,30 +323,53 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
 
         if job.status != MLJobStatus::Pending {
             return Err(format!(
-                "Cannot start job with status {:?}",
+                "Cannot change the priority of job with status {:?}",
                 job.status
             ));
         }
 
-        // Update status to processing
-        job.status = MLJobStatus::Processing;
+        job.priority = priority;
+        Ok(())
+    })?;
 
-        // Clone values we need for worker input
-        let output_dir = job
-            .output_dir
-            .clone()
-            .unwrap_or_else(|| get_separated_audio_dir().to_string_lossy().to_string());
+    debug!("Set ML job {} priority to {:?}", job_id, priority);
+    Ok(())
+}
 
-        (job.input_file.clone(), job.model.clone(), output_dir)
+/// Run a job already marked as processing, then start the next queued jobs
+async fn run_ml_job(app: AppHandle, job: MLJob) -> Result<serde_json::Value, String> {
+    let result = run_ml_worker(&app, job).await;
+    start_queued_ml_jobs(&app);
+    result
+}
+
+async fn run_ml_worker(app: &AppHandle, job: MLJob) -> Result<serde_json::Value, String> {
+    let job_id = job.id.clone();
+
+    // Registered before checking the status, so a cancel either shows up in the check or reaches
+    // the sender
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    RUNNING_JOBS.lock().insert(job_id.clone(), cancel_tx);
+    let still_processing = {
+        let _guard = JOBS_LOCK.lock();
+        read_json_file::<Vec<MLJob>>(&get_ml_jobs_json_path())?
+            .iter()
+            .any(|j| j.id == job_id && j.status == MLJobStatus::Processing)
     };
+    if !still_processing {
+        RUNNING_JOBS.lock().remove(&job_id);
+        return Err("ML job was cancelled".to_string());
+    }
 
-    // Now we can write without holding the mutable borrow
-    write_json_file(&path, &jobs)?;
+    let output_dir = job
+        .output_dir
+        .clone()
+        .unwrap_or_else(|| get_separated_audio_dir().to_string_lossy().to_string());
 
     // Prepare worker input
     let worker_input = serde_json::json!({
-        "input_file": input_file,
-        "model": model,
+        "input_file": job.input_file,
+        "model": job.model,
         "output_dir": output_dir,
         "job_id": job_id.clone()
     });
@@ -120,7 +380,6 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
     // Clone job_id and app for the progress task
     let progress_job_id = job_id.clone();
     let progress_app = app.clone();
-    let progress_path = path.clone();
 
     tokio::spawn(async move {
         let mut last_write = Instant::now() - Duration::from_millis(PROGRESS_WRITE_DEBOUNCE_MS);
@@ -132,14 +391,16 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
                 let should_write = percent == 100 || last_write.elapsed() >= debounce_duration;
 
                 if should_write {
-                    // Update job in file
-                    if let Ok(mut jobs) = read_json_file::<Vec<MLJob>>(&progress_path) {
-                        if let Some(job) = jobs.iter_mut().find(|j| j.id == progress_job_id) {
+                    // Update job in file, unless it was cancelled meanwhile
+                    let _ = update_jobs(|jobs| {
+                        if let Some(job) = jobs.iter_mut().find(|j| {
+                            j.id == progress_job_id && j.status == MLJobStatus::Processing
+                        }) {
                             job.progress = percent;
                             job.stage = Some(stage.clone());
-                            let _ = write_json_file(&progress_path, &jobs);
                         }
-                    }
+                        Ok(())
+                    });
                     last_write = Instant::now();
                 }
 
@@ -155,15 +416,17 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
         }
     });
 
-    // Spawn the Python worker asynchronously
-    let result = spawn_python_worker_async("audio_separator.py", worker_input, Some(tx)).await;
+    // Spawn the Python worker; cancelling drops it, which kills the process
+    let result = tokio::select! {
+        result = spawn_python_worker_async("audio_separator.py", worker_input, Some(tx)) => Some(result),
+        _ = cancel_rx => None,
+    };
+    RUNNING_JOBS.lock().remove(&job_id);
 
-    // Re-read jobs to update with result
-    let mut jobs: Vec<MLJob> = read_json_file(&path)?;
-    let job = jobs
-        .iter_mut()
-        .find(|j| j.id == job_id)
-        .ok_or_else(|| format!("ML job not found after worker: {}", job_id))?;
+    let Some(result) = result else {
+        debug!("Stopped cancelled ML job {}", job_id);
+        return Err("ML job was cancelled".to_string());
+    };
 
     match result {
         Ok(data) => {
@@ -181,13 +444,18 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
                 });
 
             // Update job with success info
-            job.status = MLJobStatus::Completed;
-            job.progress = 100;
-            job.stage = None;
-            job.completed_at = Some(chrono::Utc::now().to_rfc3339());
-            job.output_files = output_files.clone();
-
-            write_json_file(&path, &jobs)?;
+            update_jobs(|jobs| {
+                let job = jobs
+                    .iter_mut()
+                    .find(|j| j.id == job_id)
+                    .ok_or_else(|| format!("ML job not found after worker: {}", job_id))?;
+                job.status = MLJobStatus::Completed;
+                job.progress = 100;
+                job.stage = None;
+                job.completed_at = Some(Utc::now().to_rfc3339());
+                job.output_files = output_files.clone();
+                Ok(())
+            })?;
 
             // Emit completion event
             let _ = app.emit(
@@ -206,10 +474,15 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
         }
         Err(error) => {
             // Update job with failure info
-            job.status = MLJobStatus::Failed;
-            job.error = Some(error.clone());
-
-            write_json_file(&path, &jobs)?;
+            update_jobs(|jobs| {
+                let job = jobs
+                    .iter_mut()
+                    .find(|j| j.id == job_id)
+                    .ok_or_else(|| format!("ML job not found after worker: {}", job_id))?;
+                job.status = MLJobStatus::Failed;
+                job.error = Some(error.clone());
+                Ok(())
+            })?;
 
             // Emit error event
             let _ = app.emit(
@@ -225,36 +498,28 @@ pub async fn start_ml_job(app: AppHandle, job_id: String) -> Result<serde_json::
     }
 }
 
-/// Cancel a pending or in-progress ML job
+/// Cancel a queued or running ML job; a running job's worker is stopped
 #[tauri::command]
 pub fn cancel_ml_job(job_id: String) -> Result<(), String> {
-    let path = get_ml_jobs_json_path();
-
-    if !path.exists() {
-        return Err("No ML jobs file found".to_string());
-    }
-
-    let mut jobs: Vec<MLJob> = read_json_file(&path)?;
+    update_jobs(|jobs| {
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.id == job_id)
+            .ok_or_else(|| format!("ML job not found: {}", job_id))?;
 
-    // Find and update the job
-    let mut found = false;
-    for job in &mut jobs {
-        if job.id == job_id {
-            if job.status == MLJobStatus::Pending || job.status == MLJobStatus::Processing {
-                job.status = MLJobStatus::Cancelled;
-                found = true;
-            } else {
-                return Err(format!("Cannot cancel job with status {:?}", job.status));
-            }
-            break;
+        if job.status != MLJobStatus::Pending && job.status != MLJobStatus::Processing {
+            return Err(format!("Cannot cancel job with status {:?}", job.status));
         }
-    }
 
-    if !found {
-        return Err(format!("ML job not found: {}", job_id));
-    }
+        job.status = MLJobStatus::Cancelled;
+        job.stage = None;
+        Ok(())
+    })?;
 
-    write_json_file(&path, &jobs)?;
+    // The job's run frees its slot and starts the next queued job once the worker stops
+    if let Some(cancel) = RUNNING_JOBS.lock().remove(&job_id) {
+        let _ = cancel.send(());
+    }
 
     debug!("Cancelled ML job: {}", job_id);
     Ok(())
@@ -302,6 +567,7 @@ pub fn get_available_models() -> Result<Vec<Model>, String> {
 /// Delete an ML job from the list
 #[tauri::command]
 pub fn delete_ml_job(job_id: String, delete_output: bool) -> Result<(), String> {
+    let _guard = JOBS_LOCK.lock();
     let path = get_ml_jobs_json_path();
 
     if !path.exists() {
@@ -336,9 +602,116 @@ pub fn delete_ml_job(job_id: String, delete_output: bool) -> Result<(), String>
             jobs.remove(index);
             write_json_file(&path, &jobs)?;
 
+            // Stop the worker if the job was still running
+            if let Some(cancel) = RUNNING_JOBS.lock().remove(&job_id) {
+                let _ = cancel.send(());
+            }
+
             debug!("Deleted ML job: {}", job_id);
             Ok(())
         }
         None => Err(format!("ML job not found: {}", job_id)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, status: MLJobStatus, priority: MLJobPriority) -> MLJob {
+        let mut job = MLJob::new(
+            id.to_string(),
+            "song.mp3".to_string(),
+            "htdemucs".to_string(),
+            None,
+            priority,
+        );
+        job.status = status;
+        job
+    }
+
+    fn ran(mut job: MLJob, started: DateTime<Utc>, completed: Option<DateTime<Utc>>) -> MLJob {
+        job.started_at = Some(started.to_rfc3339());
+        job.completed_at = completed.map(|time| time.to_rfc3339());
+        job
+    }
+
+    #[test]
+    fn test_queue_order_by_priority_then_submission() {
+        let jobs = vec![
+            job("a", MLJobStatus::Pending, MLJobPriority::Normal),
+            job("b", MLJobStatus::Processing, MLJobPriority::High),
+            job("c", MLJobStatus::Pending, MLJobPriority::High),
+            job("d", MLJobStatus::Pending, MLJobPriority::Low),
+            job("e", MLJobStatus::Pending, MLJobPriority::Normal),
+            job("f", MLJobStatus::Completed, MLJobPriority::High),
+        ];
+        assert_eq!(queue_order(&jobs), vec![2, 0, 4, 3]);
+    }
+
+    #[test]
+    fn test_jobs_to_start_fills_free_slots() {
+        let jobs = vec![
+            job("running", MLJobStatus::Processing, MLJobPriority::Normal),
+            job("low", MLJobStatus::Pending, MLJobPriority::Low),
+            job("normal", MLJobStatus::Pending, MLJobPriority::Normal),
+            job("high", MLJobStatus::Pending, MLJobPriority::High),
+        ];
+        assert_eq!(jobs_to_start(&jobs, 3), vec!["high", "normal"]);
+        assert!(jobs_to_start(&jobs, 1).is_empty());
+        assert!(jobs_to_start(&jobs[..1], 4).is_empty());
+    }
+
+    #[test]
+    fn test_annotate_queue_positions_without_history() {
+        let now = Utc::now();
+        let mut jobs = vec![
+            job("a", MLJobStatus::Pending, MLJobPriority::Low),
+            job("b", MLJobStatus::Pending, MLJobPriority::High),
+            job("c", MLJobStatus::Failed, MLJobPriority::Normal),
+        ];
+        jobs[2].queue_position = Some(9);
+        annotate_queue(&mut jobs, 1, now);
+
+        assert_eq!(jobs[0].queue_position, Some(2));
+        assert_eq!(jobs[1].queue_position, Some(1));
+        assert_eq!(jobs[2].queue_position, None);
+        assert!(jobs.iter().all(|job| job.estimated_start.is_none()));
+    }
+
+    #[test]
+    fn test_annotate_queue_estimates_start_from_average_run_time() {
+        let now = Utc::now();
+        let minutes = chrono::Duration::minutes;
+        let mut jobs = vec![
+            ran(
+                job("done1", MLJobStatus::Completed, MLJobPriority::Normal),
+                now - minutes(60),
+                Some(now - minutes(52)),
+            ),
+            ran(
+                job("done2", MLJobStatus::Completed, MLJobPriority::Normal),
+                now - minutes(40),
+                Some(now - minutes(28)),
+            ),
+            ran(
+                job("running", MLJobStatus::Processing, MLJobPriority::Normal),
+                now - minutes(4),
+                None,
+            ),
+            job("normal", MLJobStatus::Pending, MLJobPriority::Normal),
+            job("high", MLJobStatus::Pending, MLJobPriority::High),
+            job("low", MLJobStatus::Pending, MLJobPriority::Low),
+        ];
+        annotate_queue(&mut jobs, 2, now);
+
+        // Average run is 10 min: "high" takes the free slot, "normal" waits for the running job,
+        // "low" for "high"
+        let estimate = |index: usize| jobs[index].estimated_start.as_deref().and_then(parse_time);
+        let at = |offset: chrono::Duration| parse_time(&(now + offset).to_rfc3339());
+        assert_eq!(estimate(4), at(minutes(0)));
+        assert_eq!(estimate(3), at(minutes(6)));
+        assert_eq!(estimate(5), at(minutes(10)));
+        assert_eq!(jobs[2].estimated_start, None);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod audio_detection;
 pub mod auth;
 pub mod autostart;
+pub mod changelog;
+pub mod data_directory;
 pub mod discord;
 pub mod downloads;
 pub mod friends;
@@ -11,7 +13,15 @@ pub mod ml_jobs;
 pub mod performance;
 pub mod playlist_uploader;
 pub mod server;
+pub mod server_metrics;
 pub mod settings;
+pub mod settings_profiles;
+pub mod sftp;
+pub mod ssh_tunnels;
+pub mod systemd;
 pub mod task_monitor;
+pub mod update_deferral;
+pub mod update_rollback;
+pub mod update_scheduler;
 pub mod updater;
 pub mod valorant;
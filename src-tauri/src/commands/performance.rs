@@ -1,12 +1,18 @@
 // Performance monitoring Tauri commands
 use crate::gaming::GamingSessionManager;
-use crate::models::performance::SystemMetrics;
-use crate::performance::{get_snapshot, is_nvidia_available, start_monitoring, stop_monitoring, MonitoringState, SharedMetrics};
+use crate::task_monitor;
+use crate::models::performance::{
+    CaptureStopReason, CaptureSummary, DiskMetrics, GpuMetrics, PerformanceAlert, PowerStatus, SystemMetrics,
+};
+use crate::performance::capture::PerformanceCapture;
+use crate::performance::alerts::{load_alerts, save_alerts};
+use crate::performance::{detect_gpus, get_snapshot, is_nvidia_available, list_disk_space, read_power_status, start_monitoring, stop_monitoring, MonitoringState, SharedMetrics};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
 /// Start performance monitoring in the background
-/// Emits "performance:update" events every 1 second
+/// Emits "performance:update" events at the configured poll interval
 /// Also updates shared metrics so gaming session recording can read them
 #[tauri::command]
 pub fn start_performance_monitoring(
@@ -20,10 +26,13 @@ pub fn start_performance_monitoring(
 
 /// Stop performance monitoring
 /// Note: Will not stop if there's an active gaming session to prevent data loss
+/// History is cleared unless `preserve_history` is true, so a later restart can keep drawing the same graph
 #[tauri::command]
 pub fn stop_performance_monitoring(
     state: State<'_, Arc<MonitoringState>>,
     session_manager: State<'_, Arc<GamingSessionManager>>,
+    shared_metrics: State<'_, Arc<SharedMetrics>>,
+    preserve_history: Option<bool>,
 ) -> Result<(), String> {
     // Don't stop monitoring if there's an active gaming session
     // The gaming session needs continuous metrics for recording
@@ -31,13 +40,43 @@ pub fn stop_performance_monitoring(
         return Ok(());
     }
     stop_monitoring(state.inner().clone());
+    if !preserve_history.unwrap_or(false) {
+        shared_metrics.clear_history();
+    }
     Ok(())
 }
 
+/// Get recent performance history for graphs
+/// Returns snapshots from the last `seconds`, evenly downsampled to at most `max_points` (0 = no limit)
+#[tauri::command]
+pub fn get_performance_history(
+    shared_metrics: State<'_, Arc<SharedMetrics>>,
+    seconds: u32,
+    max_points: u32,
+) -> Result<Vec<SystemMetrics>, String> {
+    Ok(shared_metrics.history(seconds, max_points))
+}
+
+/// Number of processes listed per category in `top_processes`
+const TOP_PROCESS_COUNT: usize = 5;
+
 /// Get a single performance snapshot
+/// Per-interface network rates are only included when `detailed` is true
+/// Top CPU/memory processes are only included when `include_processes` is true
 #[tauri::command]
-pub fn get_performance_snapshot() -> Result<SystemMetrics, String> {
-    Ok(get_snapshot())
+pub fn get_performance_snapshot(
+    detailed: Option<bool>,
+    include_processes: Option<bool>,
+) -> Result<SystemMetrics, String> {
+    let mut snapshot = get_snapshot();
+    if include_processes.unwrap_or(false) {
+        snapshot.top_processes = Some(task_monitor::get_top_processes(TOP_PROCESS_COUNT));
+    }
+    if detailed.unwrap_or(false) {
+        Ok(snapshot)
+    } else {
+        Ok(snapshot.without_details())
+    }
 }
 
 /// Check if performance monitoring is currently running
@@ -51,3 +90,86 @@ pub fn is_performance_monitoring(state: State<'_, Arc<MonitoringState>>) -> bool
 pub fn has_nvidia_gpu() -> bool {
     is_nvidia_available()
 }
+
+/// List every detected GPU (NVIDIA and Intel) with a one-off reading
+/// Used by the settings UI to pin a preferred adapter
+#[tauri::command]
+pub fn list_gpus() -> Result<Vec<GpuMetrics>, String> {
+    Ok(detect_gpus())
+}
+
+/// Get capacity and free space for each disk
+/// Cheap one-off query that works without performance monitoring running
+/// (used for free-space checks before large downloads/uploads)
+#[tauri::command]
+pub fn get_disk_space(include_removable: Option<bool>) -> Result<Vec<DiskMetrics>, String> {
+    Ok(list_disk_space(include_removable.unwrap_or(false)))
+}
+
+/// Get battery and power plan status
+/// Works without performance monitoring running
+#[tauri::command]
+pub fn get_power_status() -> Result<PowerStatus, String> {
+    Ok(read_power_status())
+}
+
+/// Get the configured performance alerts
+#[tauri::command]
+pub fn get_performance_alerts() -> Result<Vec<PerformanceAlert>, String> {
+    Ok(load_alerts())
+}
+
+/// Replace the performance alert list
+/// Takes effect on the next monitoring cycle without restarting monitoring
+#[tauri::command]
+pub fn update_performance_alerts(
+    state: State<'_, Arc<MonitoringState>>,
+    alerts: Vec<PerformanceAlert>,
+) -> Result<(), String> {
+    save_alerts(&alerts)?;
+    if let Ok(mut guard) = state.alerts.write() {
+        *guard = alerts;
+    }
+    Ok(())
+}
+
+/// Start capturing every performance sample to a JSON-lines file
+/// Starts monitoring if it isn't running; the capture ends on its own when the
+/// configured duration/size cap is reached (emits "performance:capture_stopped")
+#[tauri::command]
+pub fn start_performance_capture(
+    app: AppHandle,
+    state: State<'_, Arc<MonitoringState>>,
+    shared_metrics: State<'_, Arc<SharedMetrics>>,
+    path: String,
+) -> Result<(), String> {
+    let settings = crate::commands::settings::get_settings().unwrap_or_default();
+
+    {
+        let mut guard = state.capture.lock().map_err(|_| "Capture state is unavailable".to_string())?;
+        if guard.is_some() {
+            return Err("A performance capture is already running".to_string());
+        }
+        *guard = Some(PerformanceCapture::start(
+            PathBuf::from(path),
+            settings.performance_capture_max_minutes,
+            settings.performance_capture_max_mb,
+        )?);
+    }
+
+    start_monitoring(app, state.inner().clone(), shared_metrics.inner().clone());
+    Ok(())
+}
+
+/// Stop the active performance capture and report how much was written
+#[tauri::command]
+pub fn stop_performance_capture(state: State<'_, Arc<MonitoringState>>) -> Result<CaptureSummary, String> {
+    let capture = state
+        .capture
+        .lock()
+        .map_err(|_| "Capture state is unavailable".to_string())?
+        .take()
+        .ok_or_else(|| "No performance capture is running".to_string())?;
+
+    Ok(capture.finish(CaptureStopReason::Manual))
+}
@@ -1,12 +1,14 @@
+use crate::commands::server::ssh_worker_input;
 use crate::file_manager::read_json_file;
 use crate::models::{
     DownloadResult, MusicIndex, Playlist, PlaylistUploaderProgress, ServerConfig, SyncResult,
     UploadResult,
 };
 use crate::process_manager::{spawn_python_worker_async, WorkerMessage};
+use crate::server_monitor::{known_hosts, ssh_pool};
 use crate::utils::{
     get_music_dir, get_music_index_json_path, get_music_playlists_dir, get_music_tracks_dir,
-    get_server_config_json_path, get_ssh_credentials_json_path,
+    get_server_config_json_path,
 };
 use serde_json::json;
 use tauri::{AppHandle, Emitter};
@@ -90,33 +92,17 @@ pub async fn sync_from_server(
         return Err("Server not configured. Please configure in Server Monitor.".to_string());
     };
 
-    // Get password
-    let ssh_password = if let Some(pwd) = password {
-        pwd
-    } else {
-        let creds_path = get_ssh_credentials_json_path();
-        if !creds_path.exists() {
-            return Err("No SSH credentials saved. Please provide a password.".to_string());
-        }
-        let creds: serde_json::Value = read_json_file(&creds_path)?;
-        creds
-            .get("password")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "Invalid credentials format".to_string())?
-            .to_string()
-    };
-
     let music_dir = get_music_dir();
 
     // Prepare worker input
-    let worker_input = json!({
-        "action": "sync_from_server",
-        "music_dir": music_dir.to_string_lossy(),
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": ssh_password
-    });
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "action": "sync_from_server",
+            "music_dir": music_dir.to_string_lossy(),
+        }),
+    )?;
 
 
     // Set up progress channel
@@ -144,7 +130,10 @@ pub async fn sync_from_server(
     // Execute worker
     let result =
         spawn_python_worker_async("playlist_uploader_worker.py", worker_input, Some(progress_tx))
-            .await;
+            .await
+            .inspect_err(|e| {
+                known_hosts::report_error(Some(&app), &server_config, e);
+            });
 
     match result {
         Ok(output) => {
@@ -356,34 +345,18 @@ pub async fn upload_to_server(
         return Err("Server not configured. Please configure in Server Monitor.".to_string());
     };
 
-    // Get password
-    let ssh_password = if let Some(pwd) = password {
-        pwd
-    } else {
-        let creds_path = get_ssh_credentials_json_path();
-        if !creds_path.exists() {
-            return Err("No SSH credentials saved. Please provide a password.".to_string());
-        }
-        let creds: serde_json::Value = read_json_file(&creds_path)?;
-        creds
-            .get("password")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "Invalid credentials format".to_string())?
-            .to_string()
-    };
-
     let music_dir = get_music_dir();
 
-    let worker_input = json!({
-        "action": "upload_to_server",
-        "music_dir": music_dir.to_string_lossy(),
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": ssh_password,
-        "track_ids": track_ids,
-        "playlist_name": playlist_name
-    });
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "action": "upload_to_server",
+            "music_dir": music_dir.to_string_lossy(),
+            "track_ids": track_ids,
+            "playlist_name": playlist_name
+        }),
+    )?;
 
     // Set up progress channel
     let (progress_tx, mut progress_rx) = mpsc::channel::<WorkerMessage>(100);
@@ -408,7 +381,10 @@ pub async fn upload_to_server(
 
     let result =
         spawn_python_worker_async("playlist_uploader_worker.py", worker_input, Some(progress_tx))
-            .await;
+            .await
+            .inspect_err(|e| {
+                known_hosts::report_error(Some(&app), &server_config, e);
+            });
 
     match result {
         Ok(output) => {
@@ -480,33 +456,17 @@ pub async fn restart_discord_bot(_app: AppHandle, password: Option<String>) -> R
         return Err("Server not configured.".to_string());
     };
 
-    // Get password
-    let ssh_password = if let Some(pwd) = password {
-        pwd
-    } else {
-        let creds_path = get_ssh_credentials_json_path();
-        if !creds_path.exists() {
-            return Err("No SSH credentials saved.".to_string());
-        }
-        let creds: serde_json::Value = read_json_file(&creds_path)?;
-        creds
-            .get("password")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "Invalid credentials format".to_string())?
-            .to_string()
-    };
-
     // Use ssh_worker to execute the restart command
-    let worker_input = json!({
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": ssh_password,
-        "command": "export PATH=\"/root/.nvm/versions/node/v24.13.0/bin:$PATH\" && pm2 restart nino-music",
-        "session_id": uuid::Uuid::new_v4().to_string()
-    });
-
-    let result = spawn_python_worker_async("ssh_worker.py", worker_input, None).await;
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "command": "export PATH=\"/root/.nvm/versions/node/v24.13.0/bin:$PATH\" && pm2 restart nino-music",
+            "session_id": uuid::Uuid::new_v4().to_string()
+        }),
+    )?;
+
+    let result = ssh_pool::run(&server_config, worker_input, None).await;
 
     match result {
         Ok(output) => {
@@ -1,17 +1,26 @@
 // Server monitoring command handlers
 use crate::file_manager::{read_json_file, write_json_file};
+use crate::commands::systemd::systemd_service_action;
 use crate::models::{
-    CommandResult, CommandStatus, QuickAction, QuickActionsConfig, SSHCredentials, ServerConfig,
+    CommandResult, CommandStatus, HostKeyTrust, KnownHost, QuickAction, QuickActionVariable, QuickActionsConfig,
+    SSHAuthMethod, SSHCredentials, SSHPublicKey, ServerConfig, SshConnectionState, SshConnectionTest,
     SystemStatus,
 };
 use crate::process_manager::{spawn_python_worker_async, WorkerMessage};
+use crate::server_monitor::known_hosts::{self, HostKeyProblem};
+use crate::server_monitor::metrics_history::{MAX_INTERVAL_SECONDS, MIN_INTERVAL_SECONDS};
+use crate::server_monitor::metrics_sampler;
+use crate::server_monitor::ssh_pool::{self, MAX_IDLE_TIMEOUT_SECONDS, MIN_IDLE_TIMEOUT_SECONDS};
+use crate::utils::shell_template;
 use crate::utils::{
-    get_quick_actions_json_path, get_server_config_json_path, get_ssh_credentials_json_path,
+    get_generated_ssh_key_path, get_quick_actions_json_path, get_server_config_json_path,
+    get_ssh_credentials_json_path,
 };
 use chrono::Utc;
 use log::debug;
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::HashMap;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -22,6 +31,89 @@ pub struct UpdateServerConfigParams {
     pub port: Option<u16>,
     pub username: Option<String>,
     pub domain: Option<String>,
+    pub auth_method: Option<SSHAuthMethod>,
+    pub key_path: Option<String>,
+    /// Seconds between background metrics samples; 0 turns the sampler off
+    pub metrics_interval_seconds: Option<u32>,
+    /// Seconds a pooled SSH session may sit unused; 0 restores the default
+    pub ssh_idle_timeout_seconds: Option<u32>,
+    pub metrics_keep_session_open: Option<bool>,
+}
+
+/// `path` with a leading `~` replaced by `home`, as ssh would; other paths are returned unchanged
+fn expand_home(path: &str, home: Option<std::path::PathBuf>) -> String {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return path.to_string(),
+    };
+    match home {
+        Some(home) => format!("{}{}", home.to_string_lossy(), rest),
+        None => path.to_string(),
+    }
+}
+
+/// Private key for the configured key auth method
+fn ssh_key_path(config: &ServerConfig) -> Result<String, String> {
+    let path = match config.auth_method {
+        SSHAuthMethod::Password => return Err("Password auth uses no key".to_string()),
+        SSHAuthMethod::KeyFile => config
+            .key_path
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| expand_home(p, dirs::home_dir()))
+            .ok_or_else(|| "No SSH key file configured".to_string())?,
+        SSHAuthMethod::GeneratedKey => {
+            let path = get_generated_ssh_key_path();
+            if !path.exists() {
+                return Err("No SSH key generated yet. Generate one first.".to_string());
+            }
+            path.to_string_lossy().to_string()
+        }
+    };
+
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("SSH key file not found: {}", path));
+    }
+    Ok(path)
+}
+
+/// Connection and auth fields of an ssh_worker input, merged with `fields`
+/// `secret` is the password for password auth and the key passphrase for key auth; the saved
+/// credentials are used when it is None
+pub(crate) fn ssh_worker_input(
+    config: &ServerConfig,
+    secret: Option<String>,
+    fields: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let saved = get_ssh_credentials()?;
+    let mut input = json!({
+        "host": config.host,
+        "port": config.port,
+        "username": config.username,
+        "auth_method": config.auth_method,
+        "host_key": known_hosts::trusted(&config.server_key()),
+    });
+
+    match config.auth_method {
+        SSHAuthMethod::Password => {
+            let password = secret
+                .or_else(|| saved.and_then(|c| c.password))
+                .ok_or_else(|| "No SSH credentials saved. Please provide a password.".to_string())?;
+            input["password"] = json!(password);
+        }
+        SSHAuthMethod::KeyFile | SSHAuthMethod::GeneratedKey => {
+            let passphrase = secret
+                .filter(|s| !s.is_empty())
+                .or_else(|| saved.and_then(|c| c.key_passphrase));
+            input["key_path"] = json!(ssh_key_path(config)?);
+            input["key_passphrase"] = json!(passphrase);
+        }
+    }
+
+    if let (Some(input), serde_json::Value::Object(fields)) = (input.as_object_mut(), fields) {
+        input.extend(fields);
+    }
+    Ok(input)
 }
 
 /// Get current server configuration
@@ -46,6 +138,7 @@ pub fn update_server_config(config: UpdateServerConfigParams) -> Result<ServerCo
     } else {
         ServerConfig::default()
     };
+    let previous_key = current_config.server_key();
 
     if let Some(host) = config.host {
         current_config.host = host;
@@ -59,20 +152,53 @@ pub fn update_server_config(config: UpdateServerConfigParams) -> Result<ServerCo
     if let Some(domain) = config.domain {
         current_config.domain = Some(domain);
     }
+    if let Some(auth_method) = config.auth_method {
+        current_config.auth_method = auth_method;
+    }
+    if let Some(key_path) = config.key_path {
+        current_config.key_path = Some(key_path.trim().to_string()).filter(|p| !p.is_empty());
+    }
+    if let Some(interval) = config.metrics_interval_seconds {
+        if interval != 0 && !(MIN_INTERVAL_SECONDS..=MAX_INTERVAL_SECONDS).contains(&interval) {
+            return Err(format!(
+                "Metrics interval must be between {} and {} seconds",
+                MIN_INTERVAL_SECONDS, MAX_INTERVAL_SECONDS
+            ));
+        }
+        current_config.metrics_interval_seconds = Some(interval).filter(|i| *i != 0);
+    }
+    if let Some(timeout) = config.ssh_idle_timeout_seconds {
+        if timeout != 0 && !(MIN_IDLE_TIMEOUT_SECONDS..=MAX_IDLE_TIMEOUT_SECONDS).contains(&timeout) {
+            return Err(format!(
+                "SSH idle timeout must be between {} and {} seconds",
+                MIN_IDLE_TIMEOUT_SECONDS, MAX_IDLE_TIMEOUT_SECONDS
+            ));
+        }
+        current_config.ssh_idle_timeout_seconds = Some(timeout).filter(|t| *t != 0);
+    }
+    if let Some(keep_open) = config.metrics_keep_session_open {
+        current_config.metrics_keep_session_open = keep_open;
+    }
 
     write_json_file(&path, &current_config)?;
     debug!("Updated server config: {:?}", current_config);
+    metrics_sampler::rearm();
+    if previous_key != current_config.server_key() {
+        // Nothing connects to the old server any more
+        tauri::async_runtime::spawn(async move { ssh_pool::close(&previous_key).await });
+    }
 
     Ok(current_config)
 }
 
-/// Save SSH credentials (password)
+/// Save SSH credentials (password, or the passphrase of the private key)
 #[tauri::command]
-pub fn save_ssh_credentials(password: String) -> Result<(), String> {
+pub fn save_ssh_credentials(password: Option<String>, key_passphrase: Option<String>) -> Result<(), String> {
     let path = get_ssh_credentials_json_path();
 
     let credentials = SSHCredentials {
-        password,
+        password: password.filter(|p| !p.is_empty()),
+        key_passphrase: key_passphrase.filter(|p| !p.is_empty()),
         saved_at: Utc::now().to_rfc3339(),
     };
 
@@ -116,18 +242,78 @@ pub fn clear_ssh_credentials() -> Result<(), String> {
     Ok(())
 }
 
+/// Create the app's own ed25519 keypair, or return the existing one unless `overwrite` is set
+/// The public key goes into the server's ~/.ssh/authorized_keys; select `generated_key` auth to use it
+#[tauri::command]
+pub async fn generate_ssh_keypair(overwrite: Option<bool>) -> Result<SSHPublicKey, String> {
+    let key_path = get_generated_ssh_key_path();
+    if let Some(dir) = key_path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create SSH key directory: {}", e))?;
+    }
+
+    let worker_input = json!({
+        "action": "generate_keypair",
+        "key_path": key_path.to_string_lossy(),
+        "overwrite": overwrite.unwrap_or(false),
+        "comment": "atlas",
+    });
+
+    let output = spawn_python_worker_async("ssh_worker.py", worker_input, None)
+        .await
+        .map_err(|e| format!("Failed to generate SSH key: {}", e))?;
+    serde_json::from_value(output).map_err(|e| format!("Invalid SSH key worker output: {}", e))
+}
+
 /// Get quick actions configuration
 #[tauri::command]
 pub fn get_quick_actions() -> Result<Vec<QuickAction>, String> {
     let path = get_quick_actions_json_path();
 
-    if !path.exists() {
-        let default_config = QuickActionsConfig::default();
-        return Ok(default_config.quick_actions);
+    let config: QuickActionsConfig = if path.exists() {
+        read_json_file(&path)?
+    } else {
+        QuickActionsConfig::default()
+    };
+    Ok(config.quick_actions.into_iter().map(with_placeholder_variables).collect())
+}
+
+/// Add the command's placeholders the config doesn't declare, so the UI asks for them too
+fn with_placeholder_variables(mut action: QuickAction) -> QuickAction {
+    // A template with quoted placeholders is reported when the action runs
+    let names = shell_template::placeholders(&action.command).unwrap_or_default();
+    for name in names {
+        if !action.variables.iter().any(|v| v.name == name) {
+            action.variables.push(QuickActionVariable {
+                name,
+                label: None,
+                default: None,
+            });
+        }
     }
+    action
+}
 
-    let config: QuickActionsConfig = read_json_file(&path)?;
-    Ok(config.quick_actions)
+/// Run a quick action, filling its `{placeholders}` from `vars`
+/// Values are quoted for the shell, so they can't add commands of their own
+#[tauri::command]
+pub async fn execute_quick_action(
+    app: AppHandle,
+    id: String,
+    vars: Option<HashMap<String, String>>,
+    password: Option<String>,
+) -> Result<CommandResult, String> {
+    let action = get_quick_actions()?
+        .into_iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("Unknown quick action: {}", id))?;
+
+    if let Some(template) = action.service_action {
+        return systemd_service_action(template.service, template.action, password).await;
+    }
+
+    let command = shell_template::render(&action.command, &vars.unwrap_or_default())
+        .map_err(|e| format!("{}: {}", action.label, e))?;
+    execute_ssh_command(app, command, password).await
 }
 
 /// Execute an SSH command on the server
@@ -140,15 +326,6 @@ pub async fn execute_ssh_command(
     // Get server config
     let server_config = get_server_config()?;
 
-    // Get password from parameter or saved credentials
-    let ssh_password = if let Some(pwd) = password {
-        pwd
-    } else {
-        let creds = get_ssh_credentials()?
-            .ok_or_else(|| "No SSH credentials saved. Please provide a password.".to_string())?;
-        creds.password
-    };
-
     let session_id = Uuid::new_v4().to_string();
     let started_at = Utc::now().to_rfc3339();
 
@@ -162,14 +339,14 @@ pub async fn execute_ssh_command(
     };
 
     // Prepare input for Python worker
-    let worker_input = json!({
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": ssh_password,
-        "command": final_command,
-        "session_id": session_id
-    });
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "command": final_command,
+            "session_id": session_id
+        }),
+    )?;
 
     debug!(
         "Executing SSH command on {}@{}: {}",
@@ -201,8 +378,8 @@ pub async fn execute_ssh_command(
         }
     });
 
-    // Execute the Python worker
-    let result = spawn_python_worker_async("ssh_worker.py", worker_input, Some(progress_tx)).await;
+    // Run on the pooled session
+    let result = ssh_pool::run(&server_config, worker_input, Some(progress_tx)).await;
 
     match result {
         Ok(output) => {
@@ -269,6 +446,51 @@ pub async fn execute_ssh_command(
     }
 }
 
+/// Run a command on the server without streaming its output to the terminal
+pub(crate) async fn run_ssh_command(command: &str, password: Option<String>) -> Result<CommandResult, String> {
+    run_pooled_command(command, password, false).await
+}
+
+/// Run a command on the pooled session; with `close_session`, a session it had to open is closed
+/// again afterwards
+pub(crate) async fn run_pooled_command(
+    command: &str,
+    password: Option<String>,
+    close_session: bool,
+) -> Result<CommandResult, String> {
+    let server_config = get_server_config()?;
+    let started_at = Utc::now().to_rfc3339();
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "command": command,
+            "session_id": Uuid::new_v4().to_string()
+        }),
+    )?;
+
+    let output = if close_session {
+        ssh_pool::run_and_close(&server_config, worker_input, None).await?
+    } else {
+        ssh_pool::run(&server_config, worker_input, None).await?
+    };
+    let exit_code = output.get("exit_code").and_then(|v| v.as_i64()).map(|v| v as i32);
+
+    Ok(CommandResult {
+        command: command.to_string(),
+        status: if exit_code == Some(0) {
+            CommandStatus::Completed
+        } else {
+            CommandStatus::Failed
+        },
+        exit_code,
+        output: output.get("output").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        error: output.get("error").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        started_at,
+        completed_at: Some(Utc::now().to_rfc3339()),
+    })
+}
+
 /// Get system status from the server
 #[tauri::command]
 pub async fn get_system_status(
@@ -278,31 +500,15 @@ pub async fn get_system_status(
     // Get server config
     let server_config = get_server_config()?;
 
-    // Get password from parameter or saved credentials
-    let ssh_password = if let Some(pwd) = password {
-        pwd
-    } else {
-        let creds = get_ssh_credentials()?
-            .ok_or_else(|| "No SSH credentials saved. Please provide a password.".to_string())?;
-        creds.password
-    };
-
     // Prepare input for Python worker
-    let worker_input = json!({
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": ssh_password,
-        "action": "system_status"
-    });
+    let worker_input = ssh_worker_input(&server_config, password, json!({ "action": "system_status" }))?;
 
     debug!(
         "Getting system status from {}@{}",
         server_config.username, server_config.host
     );
 
-    // Execute the Python worker
-    let result = spawn_python_worker_async("ssh_worker.py", worker_input, None).await;
+    let result = ssh_pool::run(&server_config, worker_input, None).await;
 
     match result {
         Ok(output) => {
@@ -351,28 +557,97 @@ pub async fn get_system_status(
     }
 }
 
-/// Test SSH connection to the server
+/// State of the pooled SSH session to a server
+/// `profile` is the server's "host:port"; None means the configured server
+#[tauri::command]
+pub async fn get_ssh_connection_state(profile: Option<String>) -> Result<SshConnectionState, String> {
+    ssh_pool::connection_state(&resolve_profile(profile)?).await
+}
+
+/// The given server "host:port", or the configured server's when None
+fn resolve_profile(profile: Option<String>) -> Result<String, String> {
+    match profile.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+        Some(profile) => Ok(profile),
+        None => Ok(get_server_config()?.server_key()),
+    }
+}
+
+/// Trust or reject the host key a server presented, after the user compared its fingerprint
+/// Returns the now trusted key when accepted, and emits `server:host_key_trusted` so failed calls
+/// can be retried
+/// `profile` is the server's "host:port"; None means the configured server
 #[tauri::command]
-pub async fn test_ssh_connection(password: String) -> Result<bool, String> {
+pub fn verify_host_key(
+    app: AppHandle,
+    profile: Option<String>,
+    fingerprint: String,
+    accept: bool,
+) -> Result<Option<KnownHost>, String> {
+    let profile = resolve_profile(profile)?;
+    if !accept {
+        known_hosts::reject(&profile);
+        return Ok(None);
+    }
+    let known = known_hosts::trust(&profile, fingerprint.trim())?;
+    let _ = app.emit("server:host_key_trusted", json!({ "profile": profile }));
+    Ok(Some(known))
+}
+
+/// Forget the host key trusted for a server, for a legitimate key rotation
+/// The next connection presents the new key for verification
+/// `profile` is the server's "host:port"; None means the configured server
+#[tauri::command]
+pub async fn clear_known_host(profile: Option<String>) -> Result<bool, String> {
+    let profile = resolve_profile(profile)?;
+    let cleared = known_hosts::clear(&profile)?;
+    // A pooled session must not outlive trust in its key
+    ssh_pool::close(&profile).await;
+    Ok(cleared)
+}
+
+/// Test SSH connection to the server with the configured auth method, reporting its host key and
+/// whether that key is trusted
+/// Always connects afresh rather than through the pooled session, so the credentials are checked
+/// `password` is the key passphrase for key auth; saved credentials are used when it is None
+#[tauri::command]
+pub async fn test_ssh_connection(app: AppHandle, password: Option<String>) -> Result<SshConnectionTest, String> {
     let server_config = get_server_config()?;
 
-    let worker_input = json!({
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": password,
-        "command": "echo 'Connection successful'",
-        "session_id": Uuid::new_v4().to_string()
-    });
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "action": "test_connection",
+            "session_id": Uuid::new_v4().to_string()
+        }),
+    )?;
 
     let result = spawn_python_worker_async("ssh_worker.py", worker_input, None).await;
 
     match result {
         Ok(output) => {
             let exit_code = output.get("exit_code").and_then(|v| v.as_i64());
-            Ok(exit_code == Some(0))
+            let field = |name: &str| output.get(name).and_then(|v| v.as_str()).map(String::from);
+            Ok(SshConnectionTest {
+                success: exit_code == Some(0),
+                key_type: field("key_type"),
+                fingerprint: field("fingerprint"),
+                trust: Some(HostKeyTrust::Trusted),
+                error: None,
+            })
+        }
+        Err(e) => {
+            let (key_type, fingerprint, trust) = match known_hosts::report_error(Some(&app), &server_config, &e) {
+                Some(HostKeyProblem::Unknown { key_type, fingerprint }) => {
+                    (Some(key_type), Some(fingerprint), Some(HostKeyTrust::Unknown))
+                }
+                Some(HostKeyProblem::Changed { key_type, fingerprint }) => {
+                    (Some(key_type), Some(fingerprint), Some(HostKeyTrust::Changed))
+                }
+                None => (None, None, None),
+            };
+            Ok(SshConnectionTest { success: false, key_type, fingerprint, trust, error: Some(e) })
         }
-        Err(_) => Ok(false),
     }
 }
 
@@ -387,28 +662,19 @@ pub async fn upload_file_to_server(
     // Get server config
     let server_config = get_server_config()?;
 
-    // Get password from parameter or saved credentials
-    let ssh_password = if let Some(pwd) = password {
-        pwd
-    } else {
-        let creds = get_ssh_credentials()?
-            .ok_or_else(|| "No SSH credentials saved. Please provide a password.".to_string())?;
-        creds.password
-    };
-
     let session_id = Uuid::new_v4().to_string();
 
     // Prepare input for Python worker
-    let worker_input = json!({
-        "host": server_config.host,
-        "port": server_config.port,
-        "username": server_config.username,
-        "password": ssh_password,
-        "action": "upload_file",
-        "local_path": local_path,
-        "remote_path": remote_path,
-        "session_id": session_id
-    });
+    let worker_input = ssh_worker_input(
+        &server_config,
+        password,
+        json!({
+            "action": "upload_file",
+            "local_path": local_path,
+            "remote_path": remote_path,
+            "session_id": session_id
+        }),
+    )?;
 
     debug!(
         "Uploading file via SFTP: {} -> {}@{}:{}",
@@ -452,8 +718,7 @@ pub async fn upload_file_to_server(
         }
     });
 
-    // Execute the Python worker
-    let result = spawn_python_worker_async("ssh_worker.py", worker_input, Some(progress_tx)).await;
+    let result = ssh_pool::run(&server_config, worker_input, Some(progress_tx)).await;
 
     match result {
         Ok(output) => {
@@ -501,3 +766,21 @@ pub fn read_local_file(file_path: String) -> Result<String, String> {
 pub fn check_local_file_exists(file_path: String) -> bool {
     std::path::Path::new(&file_path).exists()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_expand_home() {
+        let home = || Some(PathBuf::from("C:\\Users\\kai"));
+        assert_eq!(expand_home("~/.ssh/id_ed25519", home()), "C:\\Users\\kai/.ssh/id_ed25519");
+        assert_eq!(expand_home("~\\.ssh\\id_rsa", home()), "C:\\Users\\kai\\.ssh\\id_rsa");
+        assert_eq!(expand_home("~", home()), "C:\\Users\\kai");
+        // Another user's home and plain paths are left alone
+        assert_eq!(expand_home("~bob/.ssh/id_rsa", home()), "~bob/.ssh/id_rsa");
+        assert_eq!(expand_home("D:\\keys\\id_rsa", home()), "D:\\keys\\id_rsa");
+        assert_eq!(expand_home("~/.ssh/id_rsa", None), "~/.ssh/id_rsa");
+    }
+}
@@ -0,0 +1,38 @@
+// Server metrics history
+// Samples are recorded in the background by server_monitor::metrics_sampler; this reads them back
+use chrono::Utc;
+
+use crate::commands::server::get_server_config;
+use crate::models::{MetricsRange, ServerMetricsHistory};
+use crate::server_monitor::metrics_history::{downsample, MetricsStore};
+
+/// Points returned for any range; enough for a smooth chart
+const MAX_CHART_POINTS: usize = 240;
+
+/// Metrics of a server over `range` (default 24h), averaged down to MAX_CHART_POINTS
+/// `profile` is the server's "host:port"; None means the configured server
+#[tauri::command]
+pub fn get_server_metrics_history(
+    profile: Option<String>,
+    range: Option<MetricsRange>,
+) -> Result<ServerMetricsHistory, String> {
+    let config = get_server_config()?;
+    let current = config.server_key();
+    let server = profile
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| current.clone());
+    let range = range.unwrap_or_default();
+
+    let store = MetricsStore::load(&server)?;
+    let since = Utc::now().timestamp() - range.seconds();
+    let (samples, bucket_seconds) = downsample(store.since(since), range.seconds(), MAX_CHART_POINTS);
+
+    Ok(ServerMetricsHistory {
+        interval_seconds: config.metrics_interval_seconds.filter(|_| server == current),
+        server,
+        range,
+        bucket_seconds,
+        samples,
+    })
+}
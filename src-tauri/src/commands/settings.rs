@@ -1,12 +1,25 @@
 // Settings command handlers - real implementation with file storage
+use crate::commands::autostart::{disable_autostart, enable_autostart};
 use crate::file_manager::{read_json_file, write_json_file};
-use crate::models::Settings;
-use crate::utils::{get_settings_json_path, get_data_dir};
-use log::debug;
+use crate::models::{
+    BottleneckThresholds, GameWhitelist, PresenceButton, QuickActionsConfig, RiotAccounts, SSHCredentials, SavedAvatar,
+    Settings, SettingsExport, SettingsFieldError, SettingsImportReport, SkippedSettingsField,
+    SkippedSettingsSection, SETTINGS_EXPORT_VERSION,
+};
+use crate::performance::{MonitoringState, SharedMetrics};
+use crate::utils::settings_events::{self, SENSITIVE_SETTINGS_FIELDS};
+use crate::utils::settings_validation;
+use crate::utils::{
+    get_bottleneck_thresholds_json_path, get_data_dir, get_game_whitelist_json_path, get_quick_actions_json_path,
+    get_riot_accounts_json_path, get_settings_json_path, get_ssh_credentials_json_path,
+};
+use log::{debug, info, warn};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 #[derive(Debug, Deserialize)]
@@ -15,6 +28,7 @@ pub struct UpdateSettingsParams {
     pub default_quality: Option<String>,
     pub max_concurrent_downloads: Option<u32>,
     pub max_concurrent_ml_jobs: Option<u32>,
+    pub resume_ml_jobs_on_startup: Option<bool>,
     pub atlas_project_path: Option<String>,
     pub remote_update_path: Option<String>,
     pub update_url_base: Option<String>,
@@ -22,6 +36,14 @@ pub struct UpdateSettingsParams {
     pub sidebar_order: Option<Vec<String>>,
     pub hidden_sidebar_items: Option<Vec<String>>,
     pub discord_rich_presence_enabled: Option<bool>,
+    pub discord_presence_details_template: Option<String>,
+    pub discord_presence_state_template: Option<String>,
+    pub discord_presence_buttons: Option<Vec<PresenceButton>>,
+    pub discord_presence_afk_enabled: Option<bool>,
+    pub discord_presence_afk_minutes: Option<u32>,
+    pub friends_presence_afk_away: Option<bool>,
+    pub presence_blocklist: Option<Vec<String>>,
+    pub presence_hidden_game_display: Option<String>,
     pub run_on_startup: Option<bool>,
     pub close_to_tray: Option<bool>,
     pub auto_restore_enabled: Option<bool>,
@@ -31,6 +53,26 @@ pub struct UpdateSettingsParams {
     pub partner_widget_enabled: Option<bool>,
     pub partner_widget_position_x: Option<f64>,
     pub partner_widget_position_y: Option<f64>,
+    pub performance_history_minutes: Option<u32>,
+    pub performance_poll_interval_ms: Option<u32>,
+    pub performance_include_removable_disks: Option<bool>,
+    pub preferred_gpu: Option<String>,
+    pub performance_capture_max_minutes: Option<u32>,
+    pub performance_capture_max_mb: Option<u32>,
+    pub auto_restore_delay_seconds: Option<u32>,
+    pub graceful_close_timeout_seconds: Option<u32>,
+    pub auto_execute_default_profile: Option<bool>,
+    pub leak_growth_mb_per_hour: Option<f64>,
+    pub leak_min_duration_minutes: Option<u32>,
+    pub leak_suspect_events: Option<bool>,
+    pub itch_detection_enabled: Option<bool>,
+    pub steamgriddb_api_key: Option<String>,
+    pub steam_launch_via_protocol: Option<bool>,
+    pub valorant_store_notifications: Option<bool>,
+    pub settings_profile_auto_switch: Option<bool>,
+    pub update_channel: Option<String>,
+    pub auto_download_updates: Option<bool>,
+    pub update_check_interval_hours: Option<u32>,
 }
 
 /// Get current settings from the JSON file
@@ -45,9 +87,21 @@ pub fn get_settings() -> Result<Settings, String> {
     read_json_file(&path)
 }
 
+/// Check a partial update without saving it; returns one entry per invalid field
+#[tauri::command]
+pub fn validate_settings(settings: UpdateSettingsParams) -> Vec<SettingsFieldError> {
+    settings_validation::validate(&settings)
+}
+
 /// Update settings with partial update support
+/// Nothing is written when any field fails validation
 #[tauri::command]
-pub fn update_settings(settings: UpdateSettingsParams) -> Result<Settings, String> {
+pub fn update_settings(app: AppHandle, settings: UpdateSettingsParams) -> Result<Settings, String> {
+    let errors = settings_validation::validate(&settings);
+    if !errors.is_empty() {
+        return Err(settings_validation::describe(&errors));
+    }
+
     let path = get_settings_json_path();
 
     let mut current_settings: Settings = if path.exists() {
@@ -55,6 +109,7 @@ pub fn update_settings(settings: UpdateSettingsParams) -> Result<Settings, Strin
     } else {
         Settings::default()
     };
+    let previous_settings = current_settings.clone();
 
     // Apply partial updates
     if let Some(download_path) = settings.download_path {
@@ -69,6 +124,9 @@ pub fn update_settings(settings: UpdateSettingsParams) -> Result<Settings, Strin
     if let Some(max_concurrent_ml_jobs) = settings.max_concurrent_ml_jobs {
         current_settings.max_concurrent_ml_jobs = max_concurrent_ml_jobs;
     }
+    if let Some(resume_ml_jobs_on_startup) = settings.resume_ml_jobs_on_startup {
+        current_settings.resume_ml_jobs_on_startup = resume_ml_jobs_on_startup;
+    }
     if let Some(atlas_project_path) = settings.atlas_project_path {
         current_settings.atlas_project_path = if atlas_project_path.is_empty() {
             None
@@ -110,6 +168,52 @@ pub fn update_settings(settings: UpdateSettingsParams) -> Result<Settings, Strin
     if let Some(discord_rich_presence_enabled) = settings.discord_rich_presence_enabled {
         current_settings.discord_rich_presence_enabled = discord_rich_presence_enabled;
     }
+    // Read by the presence manager on its next update, so no reconnect is needed; empty restores the default
+    if let Some(template) = settings.discord_presence_details_template {
+        current_settings.discord_presence_details_template = if template.trim().is_empty() {
+            Settings::default().discord_presence_details_template
+        } else {
+            template
+        };
+    }
+    if let Some(template) = settings.discord_presence_state_template {
+        current_settings.discord_presence_state_template = if template.trim().is_empty() {
+            Settings::default().discord_presence_state_template
+        } else {
+            template
+        };
+    }
+    if let Some(buttons) = settings.discord_presence_buttons {
+        current_settings.discord_presence_buttons = buttons
+            .into_iter()
+            .map(|b| PresenceButton {
+                label: b.label.trim().to_string(),
+                url: b.url.trim().to_string(),
+            })
+            .collect();
+    }
+    // Picked up by a running session within its presence refresh interval
+    if let Some(afk_enabled) = settings.discord_presence_afk_enabled {
+        current_settings.discord_presence_afk_enabled = afk_enabled;
+    }
+    if let Some(afk_minutes) = settings.discord_presence_afk_minutes {
+        current_settings.discord_presence_afk_minutes = afk_minutes;
+    }
+    if let Some(afk_away) = settings.friends_presence_afk_away {
+        current_settings.friends_presence_afk_away = afk_away;
+    }
+    // Like the AFK settings, a running session picks these up on its next presence refresh
+    if let Some(blocklist) = settings.presence_blocklist {
+        current_settings.presence_blocklist = blocklist
+            .iter()
+            .map(|g| g.trim())
+            .filter(|g| !g.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    if let Some(display) = settings.presence_hidden_game_display {
+        current_settings.presence_hidden_game_display = display;
+    }
     if let Some(run_on_startup) = settings.run_on_startup {
         current_settings.run_on_startup = run_on_startup;
     }
@@ -149,46 +253,417 @@ pub fn update_settings(settings: UpdateSettingsParams) -> Result<Settings, Strin
     if let Some(partner_widget_position_y) = settings.partner_widget_position_y {
         current_settings.partner_widget_position_y = Some(partner_widget_position_y);
     }
+    if let Some(performance_history_minutes) = settings.performance_history_minutes {
+        current_settings.performance_history_minutes = performance_history_minutes;
+    }
+    if let Some(performance_poll_interval_ms) = settings.performance_poll_interval_ms {
+        current_settings.performance_poll_interval_ms = performance_poll_interval_ms;
+    }
+    if let Some(performance_include_removable_disks) = settings.performance_include_removable_disks {
+        current_settings.performance_include_removable_disks = performance_include_removable_disks;
+    }
+    if let Some(preferred_gpu) = settings.preferred_gpu {
+        current_settings.preferred_gpu = if preferred_gpu.is_empty() {
+            None
+        } else {
+            Some(preferred_gpu)
+        };
+    }
+    if let Some(performance_capture_max_minutes) = settings.performance_capture_max_minutes {
+        current_settings.performance_capture_max_minutes = performance_capture_max_minutes;
+    }
+    if let Some(performance_capture_max_mb) = settings.performance_capture_max_mb {
+        current_settings.performance_capture_max_mb = performance_capture_max_mb;
+    }
+    if let Some(auto_restore_delay_seconds) = settings.auto_restore_delay_seconds {
+        current_settings.auto_restore_delay_seconds = auto_restore_delay_seconds;
+    }
+    if let Some(graceful_close_timeout_seconds) = settings.graceful_close_timeout_seconds {
+        current_settings.graceful_close_timeout_seconds = graceful_close_timeout_seconds;
+    }
+    if let Some(auto_execute_default_profile) = settings.auto_execute_default_profile {
+        current_settings.auto_execute_default_profile = auto_execute_default_profile;
+    }
+    if let Some(leak_growth_mb_per_hour) = settings.leak_growth_mb_per_hour {
+        current_settings.leak_growth_mb_per_hour = leak_growth_mb_per_hour;
+    }
+    if let Some(leak_min_duration_minutes) = settings.leak_min_duration_minutes {
+        current_settings.leak_min_duration_minutes = leak_min_duration_minutes;
+    }
+    if let Some(leak_suspect_events) = settings.leak_suspect_events {
+        current_settings.leak_suspect_events = leak_suspect_events;
+    }
+    if let Some(itch_detection_enabled) = settings.itch_detection_enabled {
+        current_settings.itch_detection_enabled = itch_detection_enabled;
+    }
+    if let Some(steamgriddb_api_key) = settings.steamgriddb_api_key {
+        current_settings.steamgriddb_api_key = if steamgriddb_api_key.trim().is_empty() {
+            None
+        } else {
+            Some(steamgriddb_api_key.trim().to_string())
+        };
+    }
+    if let Some(steam_launch_via_protocol) = settings.steam_launch_via_protocol {
+        current_settings.steam_launch_via_protocol = steam_launch_via_protocol;
+    }
+    if let Some(valorant_store_notifications) = settings.valorant_store_notifications {
+        current_settings.valorant_store_notifications = valorant_store_notifications;
+    }
+    if let Some(settings_profile_auto_switch) = settings.settings_profile_auto_switch {
+        current_settings.settings_profile_auto_switch = settings_profile_auto_switch;
+    }
+    if let Some(update_channel) = settings.update_channel {
+        current_settings.update_channel = update_channel;
+    }
+    if let Some(auto_download_updates) = settings.auto_download_updates {
+        current_settings.auto_download_updates = auto_download_updates;
+    }
+    if let Some(update_check_interval_hours) = settings.update_check_interval_hours {
+        current_settings.update_check_interval_hours = update_check_interval_hours;
+    }
 
     write_json_file(&path, &current_settings)?;
 
     debug!("Updated settings: {:?}", current_settings);
+    apply_saved_settings(&app, &previous_settings, &current_settings);
 
     Ok(current_settings)
 }
 
-/// Save user avatar image from base64 data
+/// Apply side effects of saved settings that changed and tell subscribers about them
+/// Shared by everything that writes settings.json, so an update, a reset and an import behave alike
+fn apply_saved_settings(app: &AppHandle, previous: &Settings, current: &Settings) {
+    // Running state that update paths apply directly rather than through subscribers
+    if previous.performance_history_minutes != current.performance_history_minutes {
+        if let Some(shared_metrics) = app.try_state::<Arc<SharedMetrics>>() {
+            shared_metrics.set_history_window_minutes(current.performance_history_minutes);
+        }
+    }
+    if previous.performance_poll_interval_ms != current.performance_poll_interval_ms {
+        // The monitoring loop reads this every cycle, so no restart is needed
+        if let Some(monitoring_state) = app.try_state::<Arc<MonitoringState>>() {
+            monitoring_state
+                .poll_interval_ms
+                .store(current.performance_poll_interval_ms, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+    if previous.performance_include_removable_disks != current.performance_include_removable_disks {
+        if let Some(monitoring_state) = app.try_state::<Arc<MonitoringState>>() {
+            monitoring_state.include_removable_disks.store(
+                current.performance_include_removable_disks,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+        }
+    }
+    if previous.preferred_gpu != current.preferred_gpu {
+        if let Some(monitoring_state) = app.try_state::<Arc<MonitoringState>>() {
+            if let Ok(mut guard) = monitoring_state.preferred_gpu.write() {
+                *guard = current.preferred_gpu.clone();
+            }
+        }
+    }
+    if previous.run_on_startup != current.run_on_startup {
+        let result = if current.run_on_startup {
+            enable_autostart(app.clone())
+        } else {
+            disable_autostart(app.clone())
+        };
+        if let Err(e) = result {
+            warn!("Failed to update the startup entry: {}", e);
+        }
+    }
+
+    settings_events::publish(app, settings_events::diff_settings(previous, current));
+}
+
+/// Config files exported next to settings.json: (section, path, holds credentials)
+fn auxiliary_sections() -> [(&'static str, PathBuf, bool); 5] {
+    [
+        ("bottleneck_thresholds", get_bottleneck_thresholds_json_path(), false),
+        ("quick_actions", get_quick_actions_json_path(), false),
+        ("game_whitelist", get_game_whitelist_json_path(), false),
+        ("ssh_credentials", get_ssh_credentials_json_path(), true),
+        ("riot_accounts", get_riot_accounts_json_path(), true),
+    ]
+}
+
+/// Check an auxiliary section parses as the config it replaces
+fn validate_section(section: &str, value: &serde_json::Value) -> Result<(), String> {
+    let value = value.clone();
+    let result = match section {
+        "bottleneck_thresholds" => serde_json::from_value::<BottleneckThresholds>(value).map(|_| ()),
+        "quick_actions" => serde_json::from_value::<QuickActionsConfig>(value).map(|_| ()),
+        "game_whitelist" => serde_json::from_value::<GameWhitelist>(value).map(|_| ()),
+        "ssh_credentials" => serde_json::from_value::<SSHCredentials>(value).map(|_| ()),
+        "riot_accounts" => serde_json::from_value::<RiotAccounts>(value).map(|_| ()),
+        _ => return Err("Unknown section".to_string()),
+    };
+    result.map_err(|e| format!("Invalid data: {}", e))
+}
+
+/// Restore default settings, keeping the old file as settings.{timestamp}.bak.json
+/// Emits `settings:changed` for the reset values and `data:reset` so open views reload
 #[tauri::command]
-pub fn save_user_avatar(image_data: String, file_extension: String) -> Result<String, String> {
-    // Decode base64 image data
-    let image_bytes = BASE64.decode(&image_data)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+pub fn reset_settings(app: AppHandle) -> Result<Settings, String> {
+    let path = get_settings_json_path();
+    let previous_settings = get_settings()?;
+    if path.exists() {
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let backup_path = path.with_file_name(format!("settings.{}.bak.json", stamp));
+        fs::copy(&path, &backup_path)
+            .map_err(|e| format!("Failed to back up settings to {:?}: {}", backup_path, e))?;
+        info!("Backed up settings to {:?} before resetting", backup_path);
+    }
 
-    // Create avatars directory
-    let avatars_dir = get_data_dir().join("avatars");
-    fs::create_dir_all(&avatars_dir)
-        .map_err(|e| format!("Failed to create avatars directory: {}", e))?;
+    let defaults = Settings::default();
+    write_json_file(&path, &defaults)?;
 
-    // Save with a fixed filename (overwrite previous avatar)
-    let avatar_path = avatars_dir.join(format!("user_avatar.{}", file_extension));
-    fs::write(&avatar_path, image_bytes)
-        .map_err(|e| format!("Failed to save avatar: {}", e))?;
+    apply_saved_settings(&app, &previous_settings, &defaults);
+    let _ = app.emit("data:reset", serde_json::json!({ "feature": "settings" }));
+    Ok(defaults)
+}
 
-    let path_str = avatar_path.to_string_lossy().to_string();
+/// Write settings and auxiliary configs into one JSON file; returns the exported section names
+/// Credentials are only included with `include_sensitive`
+#[tauri::command]
+pub fn export_settings(app: AppHandle, path: String, include_sensitive: Option<bool>) -> Result<Vec<String>, String> {
+    let include_sensitive = include_sensitive.unwrap_or(false);
+    let mut sections = BTreeMap::new();
 
-    // Update settings with new avatar path
-    let settings_path = get_settings_json_path();
-    let mut current_settings: Settings = if settings_path.exists() {
-        read_json_file(&settings_path)?
-    } else {
-        Settings::default()
+    let mut settings =
+        serde_json::to_value(get_settings()?).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let (false, Some(fields)) = (include_sensitive, settings.as_object_mut()) {
+        for field in SENSITIVE_SETTINGS_FIELDS {
+            fields.remove(*field);
+        }
+    }
+    sections.insert("settings".to_string(), settings);
+
+    for (section, section_path, sensitive) in auxiliary_sections() {
+        if (sensitive && !include_sensitive) || !section_path.exists() {
+            continue;
+        }
+        sections.insert(section.to_string(), read_json_file(&section_path)?);
+    }
+
+    let exported: Vec<String> = sections.keys().cloned().collect();
+    let export = SettingsExport {
+        schema_version: SETTINGS_EXPORT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_version: Some(app.package_info().version.to_string()),
+        includes_sensitive: include_sensitive,
+        sections,
     };
-    current_settings.user_avatar_path = Some(path_str.clone());
-    write_json_file(&settings_path, &current_settings)?;
+    write_json_file(Path::new(&path), &export)?;
 
-    debug!("Saved user avatar to: {}", path_str);
+    info!("Exported settings sections {:?} to {}", exported, path);
+    Ok(exported)
+}
+
+/// Apply a file written by `export_settings`
+/// Settings go through `update_settings`; each other section replaces its config file after
+/// validating. Sections that fail are reported as skipped and don't stop the rest; settings fields that can't be
+/// imported are reported in `skipped_fields`
+#[tauri::command]
+pub fn import_settings(
+    app: AppHandle,
+    path: String,
+    include_sensitive: Option<bool>,
+) -> Result<SettingsImportReport, String> {
+    let export: SettingsExport = read_json_file(Path::new(&path))?;
+    if export.schema_version == 0 || export.schema_version > SETTINGS_EXPORT_VERSION {
+        return Err(format!(
+            "Unsupported settings export version {} (this version of Atlas reads up to {})",
+            export.schema_version, SETTINGS_EXPORT_VERSION
+        ));
+    }
+
+    let include_sensitive = include_sensitive.unwrap_or(false);
+    let mut report = SettingsImportReport::default();
+    for (section, value) in export.sections {
+        match import_section(&app, &section, value, include_sensitive) {
+            Ok(skipped_fields) => {
+                report.applied.push(section);
+                report.skipped_fields.extend(skipped_fields);
+            }
+            Err(reason) => report.skipped.push(SkippedSettingsSection { section, reason }),
+        }
+    }
+
+    info!("Imported settings from {}: {:?}", path, report);
+    Ok(report)
+}
+
+/// Settings export with the fields `UpdateSettingsParams` doesn't take collected separately
+#[derive(Deserialize)]
+struct ImportedSettings {
+    #[serde(flatten)]
+    params: UpdateSettingsParams,
+    #[serde(flatten)]
+    unsupported: BTreeMap<String, serde_json::Value>,
+}
+
+/// The update an exported settings section makes, and the fields it leaves out
+/// Applying goes through `update_settings`, so side effects such as the startup entry follow
+fn settings_import_params(
+    mut value: serde_json::Value,
+    include_sensitive: bool,
+) -> Result<(UpdateSettingsParams, Vec<SkippedSettingsField>), String> {
+    let mut skipped = Vec::new();
+    let mut skip = |field: &str, reason: &str| {
+        skipped.push(SkippedSettingsField { field: field.to_string(), reason: reason.to_string() })
+    };
+
+    if let Some(fields) = value.as_object_mut() {
+        if !include_sensitive {
+            for field in SENSITIVE_SETTINGS_FIELDS {
+                if fields.remove(*field).is_some_and(|v| !v.is_null()) {
+                    skip(field, "Contains credentials; import with sensitive data enabled to apply it");
+                }
+            }
+        }
+        // The avatar image isn't part of the export, so a path from another install is dropped
+        let missing_avatar = fields
+            .get("user_avatar_path")
+            .and_then(|v| v.as_str())
+            .is_some_and(|p| !Path::new(p).exists());
+        if missing_avatar {
+            fields.remove("user_avatar_path");
+            skip("user_avatar_path", "The avatar image isn't on this computer");
+        }
+    }
 
-    Ok(path_str)
+    let imported: ImportedSettings =
+        serde_json::from_value(value).map_err(|e| format!("Invalid settings: {}", e))?;
+    for (field, value) in &imported.unsupported {
+        // The schema version describes the file, and is rewritten on save
+        if field != "schema_version" && !value.is_null() {
+            skip(field, "Can't be imported by this version of Atlas");
+        }
+    }
+    Ok((imported.params, skipped))
+}
+
+/// Apply one exported section; returns the fields of the settings section that were left out
+fn import_section(
+    app: &AppHandle,
+    section: &str,
+    value: serde_json::Value,
+    include_sensitive: bool,
+) -> Result<Vec<SkippedSettingsField>, String> {
+    if section == "settings" {
+        let (params, skipped) = settings_import_params(value, include_sensitive)?;
+        update_settings(app.clone(), params)?;
+        return Ok(skipped);
+    }
+
+    let (_, section_path, sensitive) = auxiliary_sections()
+        .into_iter()
+        .find(|(name, _, _)| *name == section)
+        .ok_or_else(|| "Unknown section".to_string())?;
+    if sensitive && !include_sensitive {
+        return Err("Contains credentials; import with sensitive data enabled to apply it".to_string());
+    }
+    validate_section(section, &value)?;
+    write_json_file(&section_path, &value)?;
+    Ok(Vec::new())
+}
+
+/// Largest side of the stored avatar
+const AVATAR_MAX_SIZE: u32 = 512;
+
+/// Side of the avatar thumbnail
+const AVATAR_THUMBNAIL_SIZE: u32 = 64;
+
+/// Files earlier versions saved the avatar as, removed when a new one is saved
+const LEGACY_AVATAR_EXTENSIONS: &[&str] = &["jpg", "jpeg", "gif", "webp"];
+
+fn avatar_thumbnail_path() -> PathBuf {
+    get_data_dir().join("avatars").join("user_avatar_thumb.png")
+}
+
+/// Decode an uploaded avatar, center-crop it to a square and shrink it to AVATAR_MAX_SIZE
+/// The format is detected from the content; animated GIFs keep only their first frame
+fn process_avatar(bytes: &[u8]) -> Result<image::DynamicImage, String> {
+    let format = image::guess_format(bytes).map_err(|_| "Avatar must be a PNG, JPEG, GIF or WebP image".to_string())?;
+    if !matches!(
+        format,
+        image::ImageFormat::Png | image::ImageFormat::Jpeg | image::ImageFormat::Gif | image::ImageFormat::WebP
+    ) {
+        return Err("Avatar must be a PNG, JPEG, GIF or WebP image".to_string());
+    }
+    let image =
+        image::load_from_memory_with_format(bytes, format).map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let side = image.width().min(image.height());
+    if side == 0 {
+        return Err("Avatar image is empty".to_string());
+    }
+    let square = image.crop_imm((image.width() - side) / 2, (image.height() - side) / 2, side, side);
+    Ok(if side > AVATAR_MAX_SIZE {
+        square.resize_exact(AVATAR_MAX_SIZE, AVATAR_MAX_SIZE, image::imageops::FilterType::Lanczos3)
+    } else {
+        square
+    })
+}
+
+fn save_avatar_thumbnail(avatar: &image::DynamicImage, path: &Path) -> Result<(), String> {
+    avatar
+        .resize_exact(AVATAR_THUMBNAIL_SIZE, AVATAR_THUMBNAIL_SIZE, image::imageops::FilterType::Lanczos3)
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to save avatar thumbnail: {}", e))
+}
+
+/// Save user avatar image from base64 data
+/// The image is stored as a square PNG of at most AVATAR_MAX_SIZE, plus a thumbnail
+#[tauri::command]
+pub async fn save_user_avatar(image_data: String) -> Result<SavedAvatar, String> {
+    tokio::task::spawn_blocking(move || {
+        let image_bytes = BASE64.decode(&image_data)
+            .map_err(|e| format!("Failed to decode image: {}", e))?;
+        let avatar = process_avatar(&image_bytes)?;
+
+        // Create avatars directory
+        let avatars_dir = get_data_dir().join("avatars");
+        fs::create_dir_all(&avatars_dir)
+            .map_err(|e| format!("Failed to create avatars directory: {}", e))?;
+
+        // Save with a fixed filename (overwrite previous avatar)
+        let avatar_path = avatars_dir.join("user_avatar.png");
+        avatar
+            .save_with_format(&avatar_path, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to save avatar: {}", e))?;
+        for extension in LEGACY_AVATAR_EXTENSIONS {
+            let _ = fs::remove_file(avatars_dir.join(format!("user_avatar.{}", extension)));
+        }
+        let thumbnail_path = avatar_thumbnail_path();
+        save_avatar_thumbnail(&avatar, &thumbnail_path)?;
+
+        let path_str = avatar_path.to_string_lossy().to_string();
+
+        // Update settings with new avatar path
+        let settings_path = get_settings_json_path();
+        let mut current_settings: Settings = if settings_path.exists() {
+            read_json_file(&settings_path)?
+        } else {
+            Settings::default()
+        };
+        current_settings.user_avatar_path = Some(path_str.clone());
+        write_json_file(&settings_path, &current_settings)?;
+
+        let bytes = fs::metadata(&avatar_path).map(|m| m.len()).unwrap_or(0);
+        debug!("Saved user avatar to: {} ({}x{}, {} bytes)", path_str, avatar.width(), avatar.height(), bytes);
+
+        Ok(SavedAvatar {
+            path: path_str,
+            thumbnail_path: thumbnail_path.to_string_lossy().to_string(),
+            width: avatar.width(),
+            height: avatar.height(),
+            bytes,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to save avatar: {}", e))?
 }
 
 /// Get the full path to the user's avatar if it exists
@@ -252,3 +727,104 @@ pub fn get_user_avatar_base64() -> Result<Option<String>, String> {
 
     Ok(None)
 }
+
+/// Get the 64x64 avatar thumbnail as a base64 PNG data URL
+/// Generated from the avatar when missing or older than it (e.g. avatars saved before thumbnails existed)
+#[tauri::command]
+pub async fn get_user_avatar_thumbnail_base64() -> Result<Option<String>, String> {
+    tokio::task::spawn_blocking(|| {
+        let Some(avatar_path) = get_user_avatar_path()? else {
+            return Ok(None);
+        };
+        let thumbnail_path = avatar_thumbnail_path();
+        refresh_avatar_thumbnail(Path::new(&avatar_path), &thumbnail_path)?;
+
+        let image_bytes = fs::read(&thumbnail_path)
+            .map_err(|e| format!("Failed to read avatar thumbnail: {}", e))?;
+        Ok(Some(format!("data:image/png;base64,{}", BASE64.encode(&image_bytes))))
+    })
+    .await
+    .map_err(|e| format!("Failed to load avatar thumbnail: {}", e))?
+}
+
+/// Regenerate `thumbnail_path` from `avatar_path` when it is missing or older than the avatar
+fn refresh_avatar_thumbnail(avatar_path: &Path, thumbnail_path: &Path) -> Result<(), String> {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    if modified(thumbnail_path).is_some() && modified(thumbnail_path) >= modified(avatar_path) {
+        return Ok(());
+    }
+
+    let avatar_bytes = fs::read(avatar_path)
+        .map_err(|e| format!("Failed to read avatar file: {}", e))?;
+    save_avatar_thumbnail(&process_avatar(&avatar_bytes)?, thumbnail_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::new_rgb8(width, height)
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_process_avatar_crops_and_shrinks() {
+        let avatar = process_avatar(&png(1200, 800)).unwrap();
+        assert_eq!((avatar.width(), avatar.height()), (AVATAR_MAX_SIZE, AVATAR_MAX_SIZE));
+
+        let small = process_avatar(&png(100, 40)).unwrap();
+        assert_eq!((small.width(), small.height()), (40, 40));
+
+        assert!(process_avatar(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_refresh_avatar_thumbnail() {
+        let dir = std::env::temp_dir().join(format!("atlas_avatar_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let avatar_path = dir.join("user_avatar.png");
+        let thumbnail_path = dir.join("user_avatar_thumb.png");
+        fs::write(&avatar_path, png(300, 300)).unwrap();
+
+        // Made when missing
+        refresh_avatar_thumbnail(&avatar_path, &thumbnail_path).unwrap();
+        let thumbnail = image::open(&thumbnail_path).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (AVATAR_THUMBNAIL_SIZE, AVATAR_THUMBNAIL_SIZE));
+
+        // Left alone while newer than the avatar
+        fs::write(&thumbnail_path, b"kept").unwrap();
+        refresh_avatar_thumbnail(&avatar_path, &thumbnail_path).unwrap();
+        assert_eq!(fs::read(&thumbnail_path).unwrap(), b"kept");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_settings_import_reports_skipped_fields() {
+        let exported = serde_json::json!({
+            "schema_version": 3,
+            "run_on_startup": true,
+            "steamgriddb_api_key": "secret",
+            "valorant_credentials": null,
+            "user_avatar_path": "/nonexistent/atlas/user_avatar.png",
+            "setting_from_a_newer_atlas": 5,
+        });
+
+        let (params, skipped) = settings_import_params(exported.clone(), false).unwrap();
+        assert_eq!(params.run_on_startup, Some(true));
+        assert_eq!(params.steamgriddb_api_key, None);
+        assert_eq!(params.user_avatar_path, None);
+        let fields: Vec<&str> = skipped.iter().map(|s| s.field.as_str()).collect();
+        assert_eq!(fields, vec!["steamgriddb_api_key", "user_avatar_path", "setting_from_a_newer_atlas"]);
+
+        let (params, skipped) = settings_import_params(exported, true).unwrap();
+        assert_eq!(params.steamgriddb_api_key.as_deref(), Some("secret"));
+        assert_eq!(skipped.len(), 2);
+
+        assert!(settings_import_params(serde_json::json!({ "run_on_startup": "yes" }), false).is_err());
+    }
+}
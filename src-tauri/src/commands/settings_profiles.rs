@@ -0,0 +1,151 @@
+// Settings profile command handlers
+// Profiles hold values for a whitelisted subset of settings and are applied through
+// `update_settings`, so changes are validated and announced like any other save
+use crate::commands::settings::{get_settings, update_settings, UpdateSettingsParams};
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::{Settings, SettingsProfile, SettingsProfiles, GAMING_SETTINGS_PROFILE, SETTINGS_PROFILE_FIELDS};
+use crate::utils::get_settings_profiles_json_path;
+use log::{debug, info, warn};
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+
+fn load_profiles() -> Result<SettingsProfiles, String> {
+    let path = get_settings_profiles_json_path();
+    if !path.exists() {
+        return Ok(SettingsProfiles::default());
+    }
+    read_json_file(&path)
+}
+
+fn save_profiles(profiles: &SettingsProfiles) -> Result<(), String> {
+    write_json_file(&get_settings_profiles_json_path(), profiles)
+}
+
+/// The whitelisted fields of `settings`
+fn capture(settings: &Settings) -> Result<Map<String, Value>, String> {
+    let Value::Object(fields) =
+        serde_json::to_value(settings).map_err(|e| format!("Failed to serialize settings: {}", e))?
+    else {
+        return Err("Settings did not serialize to an object".to_string());
+    };
+    Ok(fields
+        .into_iter()
+        .filter(|(key, _)| SETTINGS_PROFILE_FIELDS.contains(&key.as_str()))
+        .collect())
+}
+
+/// Save whitelisted values through `update_settings`; other fields in a hand-edited file are ignored
+fn apply_values(app: &AppHandle, values: Map<String, Value>) -> Result<Settings, String> {
+    let values: Map<String, Value> = values
+        .into_iter()
+        .filter(|(key, _)| SETTINGS_PROFILE_FIELDS.contains(&key.as_str()))
+        .collect();
+    let params: UpdateSettingsParams =
+        serde_json::from_value(Value::Object(values)).map_err(|e| format!("Invalid profile values: {}", e))?;
+    update_settings(app.clone(), params)
+}
+
+/// List saved settings profiles
+#[tauri::command]
+pub fn list_settings_profiles() -> Result<Vec<SettingsProfile>, String> {
+    Ok(load_profiles()?.profiles)
+}
+
+/// Save the current settings as a profile, replacing one with the same name
+#[tauri::command]
+pub fn save_settings_profile(name: String) -> Result<SettingsProfile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+
+    let profile = SettingsProfile {
+        values: capture(&get_settings()?)?,
+        saved_at: chrono::Utc::now().to_rfc3339(),
+        name,
+    };
+
+    let mut profiles = load_profiles()?;
+    match profiles.profiles.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&profile.name)) {
+        Some(existing) => *existing = profile.clone(),
+        None => profiles.profiles.push(profile.clone()),
+    }
+    save_profiles(&profiles)?;
+
+    debug!("Saved settings profile {}", profile.name);
+    Ok(profile)
+}
+
+/// Apply a saved profile; returns the resulting settings
+#[tauri::command]
+pub fn apply_settings_profile(app: AppHandle, name: String) -> Result<Settings, String> {
+    let profile = load_profiles()?
+        .profiles
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("Settings profile not found: {}", name))?;
+
+    info!("Applying settings profile {}", profile.name);
+    apply_values(&app, profile.values)
+}
+
+/// Delete a saved profile
+#[tauri::command]
+pub fn delete_settings_profile(name: String) -> Result<(), String> {
+    let mut profiles = load_profiles()?;
+    let before = profiles.profiles.len();
+    profiles.profiles.retain(|p| !p.name.eq_ignore_ascii_case(name.trim()));
+    if profiles.profiles.len() == before {
+        return Err(format!("Settings profile not found: {}", name));
+    }
+    save_profiles(&profiles)
+}
+
+/// Apply the "Gaming" profile when auto-switch is on, remembering the values it replaces
+/// A session starting before the previous one's values were restored keeps the original snapshot
+pub(crate) fn on_gaming_session_started(app: &AppHandle) {
+    let result = (|| -> Result<(), String> {
+        if !get_settings()?.settings_profile_auto_switch {
+            return Ok(());
+        }
+        let mut profiles = load_profiles()?;
+        let Some(gaming) = profiles
+            .profiles
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(GAMING_SETTINGS_PROFILE))
+            .cloned()
+        else {
+            debug!("No \"{}\" settings profile to switch to", GAMING_SETTINGS_PROFILE);
+            return Ok(());
+        };
+
+        if profiles.auto_switch_restore.is_none() {
+            profiles.auto_switch_restore = Some(capture(&get_settings()?)?);
+            save_profiles(&profiles)?;
+        }
+        info!("Gaming session started, applying the \"{}\" settings profile", gaming.name);
+        apply_values(app, gaming.values).map(|_| ())
+    })();
+
+    if let Err(e) = result {
+        warn!("Failed to switch settings profile: {}", e);
+    }
+}
+
+/// Restore the values the auto-switch replaced, if it switched
+pub(crate) fn on_gaming_session_ended(app: &AppHandle) {
+    let result = (|| -> Result<(), String> {
+        let mut profiles = load_profiles()?;
+        let Some(restore) = profiles.auto_switch_restore.take() else {
+            return Ok(());
+        };
+        save_profiles(&profiles)?;
+
+        info!("Gaming session ended, restoring settings from before the profile switch");
+        apply_values(app, restore).map(|_| ())
+    })();
+
+    if let Err(e) = result {
+        warn!("Failed to restore settings after gaming session: {}", e);
+    }
+}
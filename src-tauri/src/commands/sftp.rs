@@ -0,0 +1,470 @@
+// SFTP file browser
+// Lists, downloads, deletes and creates remote paths on the pooled SSH session to the configured
+// server. Remote paths must be absolute and free of `..`, and local download targets get
+// the same check, so a crafted path can't reach somewhere the user didn't browse to. Downloads go
+// to a .part file that is renamed when complete, report `server:transfer_progress` when they are
+// large enough to be worth watching, and can be cancelled with `cancel_sftp_transfer`. Before a
+// download starts, the destination must be writable and its disk must have room for the file
+use crate::commands::downloads::{get_download_directory, register_completed_download};
+use crate::commands::server::{get_server_config, ssh_worker_input};
+use crate::models::{SftpError, SftpListing, SftpTransferResult};
+use crate::performance::available_space_for;
+use crate::process_manager::WorkerMessage;
+use crate::server_monitor::ssh_pool;
+use lazy_static::lazy_static;
+use log::{debug, info};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+/// Smaller transfers finish too quickly for progress events to be useful
+const PROGRESS_THRESHOLD_BYTES: u64 = 1024 * 1024;
+
+/// Suffix of a download until it completes
+const PARTIAL_SUFFIX: &str = ".part";
+
+/// Device names Windows reserves, with or without an extension
+const RESERVED_FILE_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1",
+    "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+lazy_static! {
+    /// Cancel senders of running transfers, by transfer id
+    static ref TRANSFERS: Mutex<HashMap<String, oneshot::Sender<()>>> = Mutex::new(HashMap::new());
+}
+
+fn failed(message: impl Into<String>) -> SftpError {
+    SftpError::Failed {
+        message: message.into(),
+    }
+}
+
+fn invalid_path(path: &str, message: &str) -> SftpError {
+    SftpError::InvalidPath {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Absolute remote path without `..`, with `.` segments and repeated slashes dropped
+pub(crate) fn checked_remote_path(path: &str) -> Result<String, SftpError> {
+    let trimmed = path.trim();
+    if trimmed.contains('\0') || !trimmed.starts_with('/') {
+        return Err(invalid_path(path, "must be an absolute path"));
+    }
+
+    let mut segments = Vec::new();
+    for segment in trimmed.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => return Err(SftpError::PathTraversal { path: path.to_string() }),
+            segment => segments.push(segment),
+        }
+    }
+    Ok(format!("/{}", segments.join("/")))
+}
+
+/// Absolute local path without `..`
+pub(crate) fn checked_local_path(path: &str) -> Result<PathBuf, SftpError> {
+    let local = PathBuf::from(path.trim());
+    if !local.is_absolute() {
+        return Err(invalid_path(path, "must be an absolute path"));
+    }
+    if local.components().any(|c| matches!(c, Component::ParentDir)) {
+        return Err(SftpError::PathTraversal { path: path.to_string() });
+    }
+    Ok(local)
+}
+
+/// A remote file name made valid on Windows: reserved characters become `_`, trailing dots and
+/// spaces are dropped and device names such as `CON` get a `_` in front
+fn windows_file_name(name: &str) -> String {
+    let mut safe: String = name
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    safe.truncate(safe.trim_end_matches(['.', ' ']).len());
+
+    let stem = safe.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED_FILE_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        safe.insert(0, '_');
+    }
+    if safe.is_empty() {
+        safe.push_str("download");
+    }
+    safe
+}
+
+/// `dir/name`, or `dir/name (2).ext` and up when a file of that name is already there
+/// Only finished files count, so a `.part` left by an earlier attempt is still continued
+fn unique_download_path(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    if !path.exists() {
+        return path;
+    }
+
+    let (stem, extension) = match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    let mut n = 2;
+    loop {
+        let path = dir.join(format!("{} ({}){}", stem, n, extension));
+        if !path.exists() {
+            return path;
+        }
+        n += 1;
+    }
+}
+
+/// Run an ssh_worker SFTP action; an `sftp_error` in its output becomes the typed error
+async fn run_sftp_action(
+    password: Option<String>,
+    fields: serde_json::Value,
+    progress: Option<mpsc::Sender<WorkerMessage>>,
+) -> Result<serde_json::Value, SftpError> {
+    let config = get_server_config().map_err(failed)?;
+    let input = ssh_worker_input(&config, password, fields).map_err(failed)?;
+    let output = ssh_pool::run(&config, input, progress).await.map_err(failed)?;
+
+    if let Some(error) = output.get("sftp_error") {
+        return Err(serde_json::from_value(error.clone())
+            .unwrap_or_else(|e| failed(format!("Invalid SFTP error from worker: {}", e))));
+    }
+    Ok(output)
+}
+
+/// List a remote directory; the user's home directory when `path` is None
+#[tauri::command]
+pub async fn sftp_list_directory(
+    path: Option<String>,
+    password: Option<String>,
+) -> Result<SftpListing, SftpError> {
+    let path = path
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| checked_remote_path(&p))
+        .transpose()?;
+
+    let output = run_sftp_action(password, json!({ "action": "sftp_list", "path": path }), None).await?;
+    serde_json::from_value(output).map_err(|e| failed(format!("Invalid SFTP listing: {}", e)))
+}
+
+/// Download a remote file to `local`, replacing it when it exists
+/// `transfer_id` names the transfer for progress events and `cancel_sftp_transfer`; one is made up when None
+#[tauri::command]
+pub async fn sftp_download_file(
+    app: AppHandle,
+    remote: String,
+    local: String,
+    transfer_id: Option<String>,
+    password: Option<String>,
+) -> Result<SftpTransferResult, SftpError> {
+    let remote = checked_remote_path(&remote)?;
+    let local = checked_local_path(&local)?;
+    download(&app, remote, local, transfer_id, password, false).await
+}
+
+/// Download a remote file to `local_path`, or with `into_downloads` into the Atlas downloads
+/// directory, where it is listed with the other downloads; there the name is made valid on Windows
+/// and gets a " (2)" style suffix instead of replacing an existing file
+/// A partial file left by a failed or cancelled attempt is continued rather than started over
+#[tauri::command]
+pub async fn download_file_from_server(
+    app: AppHandle,
+    remote_path: String,
+    local_path: Option<String>,
+    into_downloads: Option<bool>,
+    transfer_id: Option<String>,
+    password: Option<String>,
+) -> Result<SftpTransferResult, SftpError> {
+    let remote = checked_remote_path(&remote_path)?;
+    let into_downloads = into_downloads.unwrap_or(false);
+    let local = if into_downloads {
+        let file_name = remote
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| invalid_path(&remote, "is not a file"))?;
+        unique_download_path(&get_download_directory(), &windows_file_name(file_name))
+    } else {
+        let local_path = local_path
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| failed("A local path is required unless downloading into the downloads directory"))?;
+        checked_local_path(&local_path)?
+    };
+
+    let result = download(&app, remote, local, transfer_id, password, true).await?;
+    if into_downloads {
+        let config = get_server_config().map_err(failed)?;
+        let url = format!(
+            "sftp://{}@{}:{}{}",
+            config.username, config.host, config.port, result.remote_path
+        );
+        let title = Path::new(&result.local_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let download = register_completed_download(url, title, Path::new(&result.local_path)).map_err(failed)?;
+        let _ = app.emit("download:added", download);
+    }
+    Ok(result)
+}
+
+/// Check that `local` can take `remote`, then download it through a .part file
+/// With `resume`, a partial file from an earlier attempt is continued and kept when this one fails
+async fn download(
+    app: &AppHandle,
+    remote: String,
+    local: PathBuf,
+    transfer_id: Option<String>,
+    password: Option<String>,
+    resume: bool,
+) -> Result<SftpTransferResult, SftpError> {
+    if local.is_dir() {
+        return Err(invalid_path(&local.to_string_lossy(), "is a directory"));
+    }
+    if let Some(parent) = local.parent().filter(|p| !p.is_dir()) {
+        return Err(SftpError::NotFound {
+            path: parent.to_string_lossy().to_string(),
+        });
+    }
+
+    let stat = run_sftp_action(password.clone(), json!({ "action": "sftp_stat", "path": remote }), None).await?;
+    if stat.get("is_dir").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Err(invalid_path(&remote, "is a directory"));
+    }
+    let size = stat.get("size").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    let partial = PathBuf::from(format!("{}{}", local.to_string_lossy(), PARTIAL_SUFFIX));
+    let kept = prepare_partial(&partial, resume)?;
+    let required = size.saturating_sub(kept);
+    if let Some(available) = available_space_for(&local).filter(|available| *available < required) {
+        if kept == 0 {
+            let _ = std::fs::remove_file(&partial);
+        }
+        return Err(SftpError::InsufficientSpace {
+            path: local.to_string_lossy().to_string(),
+            required,
+            available,
+        });
+    }
+
+    let transfer_id = transfer_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    {
+        let mut transfers = TRANSFERS.lock();
+        if transfers.contains_key(&transfer_id) {
+            return Err(failed(format!("Transfer {} is already running", transfer_id)));
+        }
+        transfers.insert(transfer_id.clone(), cancel_tx);
+    }
+
+    let (progress_tx, mut progress_rx) = mpsc::channel::<WorkerMessage>(100);
+    let app_clone = app.clone();
+    let transfer_id_clone = transfer_id.clone();
+    let remote_clone = remote.clone();
+    tokio::spawn(async move {
+        while let Some(msg) = progress_rx.recv().await {
+            if let WorkerMessage::Progress { percent, stage } = msg {
+                let _ = app_clone.emit(
+                    "server:transfer_progress",
+                    json!({
+                        "transfer_id": transfer_id_clone,
+                        "direction": "download",
+                        "remote_path": remote_clone,
+                        "percent": percent,
+                        "stage": stage
+                    }),
+                );
+            }
+        }
+    });
+
+    debug!("Downloading {} -> {}", remote, local.display());
+    let fields = json!({
+        "action": "sftp_download",
+        "remote_path": remote,
+        "local_path": local.to_string_lossy(),
+        "partial_path": partial.to_string_lossy(),
+        "progress_threshold": PROGRESS_THRESHOLD_BYTES,
+        "resume": resume,
+    });
+
+    // Dropping the request on cancel stops the transfer in the worker
+    let result = tokio::select! {
+        result = run_sftp_action(password, fields, Some(progress_tx)) => result,
+        _ = cancel_rx => Err(SftpError::Cancelled),
+    };
+    TRANSFERS.lock().remove(&transfer_id);
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e) => {
+            if !resume {
+                let _ = std::fs::remove_file(&partial);
+            }
+            return Err(e);
+        }
+    };
+
+    Ok(SftpTransferResult {
+        transfer_id,
+        remote_path: remote,
+        local_path: local.to_string_lossy().to_string(),
+        size: output.get("size").and_then(|v| v.as_u64()).unwrap_or(size),
+        resumed_from: output.get("resumed_from").and_then(|v| v.as_u64()).unwrap_or(0),
+    })
+}
+
+/// Open the .part file for writing, which also proves the destination is writable
+/// Returns the bytes an earlier attempt left in it when resuming
+fn prepare_partial(partial: &Path, resume: bool) -> Result<u64, SftpError> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(partial)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => SftpError::PermissionDenied {
+                path: partial.parent().unwrap_or(partial).to_string_lossy().to_string(),
+            },
+            _ => failed(format!("Can't write {}: {}", partial.display(), e)),
+        })?;
+    if !resume {
+        return Ok(0);
+    }
+    Ok(file.metadata().map(|m| m.len()).unwrap_or(0))
+}
+
+/// Cancel a running transfer; its partial file is removed unless the download can be resumed
+#[tauri::command]
+pub fn cancel_sftp_transfer(transfer_id: String) -> Result<(), String> {
+    let cancel = TRANSFERS
+        .lock()
+        .remove(&transfer_id)
+        .ok_or_else(|| format!("No running transfer {}", transfer_id))?;
+    let _ = cancel.send(());
+    info!("SFTP transfer {} cancelled", transfer_id);
+    Ok(())
+}
+
+/// Delete a remote file or empty directory
+#[tauri::command]
+pub async fn sftp_delete(remote: String, password: Option<String>) -> Result<(), SftpError> {
+    let remote = checked_remote_path(&remote)?;
+    if remote == "/" {
+        return Err(invalid_path(&remote, "the root directory can't be deleted"));
+    }
+
+    run_sftp_action(password, json!({ "action": "sftp_delete", "path": remote }), None).await?;
+    info!("Deleted remote path {}", remote);
+    Ok(())
+}
+
+/// Create a remote directory; its parent must exist
+#[tauri::command]
+pub async fn sftp_mkdir(remote: String, password: Option<String>) -> Result<(), SftpError> {
+    let remote = checked_remote_path(&remote)?;
+
+    run_sftp_action(password, json!({ "action": "sftp_mkdir", "path": remote }), None).await?;
+    info!("Created remote directory {}", remote);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_remote_path_normalizes() {
+        assert_eq!(checked_remote_path(" /home/atlas/ ").unwrap(), "/home/atlas");
+        assert_eq!(checked_remote_path("//var/./log//atlas").unwrap(), "/var/log/atlas");
+        assert_eq!(checked_remote_path("/").unwrap(), "/");
+        assert_eq!(checked_remote_path("/srv/..hidden").unwrap(), "/srv/..hidden");
+    }
+
+    #[test]
+    fn test_checked_remote_path_rejects_traversal() {
+        for path in ["/home/atlas/../../etc/passwd", "/..", "/srv/files/.."] {
+            assert!(
+                matches!(checked_remote_path(path), Err(SftpError::PathTraversal { .. })),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_remote_path_rejects_relative() {
+        for path in ["", "home/atlas", "./etc", "../etc", "~/files", "/etc\0/passwd"] {
+            assert!(
+                matches!(checked_remote_path(path), Err(SftpError::InvalidPath { .. })),
+                "{:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_windows_file_name() {
+        assert_eq!(windows_file_name("report.pdf"), "report.pdf");
+        assert_eq!(windows_file_name("a<b>c:d\"e|f?g*h\\i.txt"), "a_b_c_d_e_f_g_h_i.txt");
+        assert_eq!(windows_file_name("tab\there.log"), "tab_here.log");
+        assert_eq!(windows_file_name("notes. . "), "notes");
+        assert_eq!(windows_file_name("CON"), "_CON");
+        assert_eq!(windows_file_name("nul.tar.gz"), "_nul.tar.gz");
+        assert_eq!(windows_file_name("com1 .txt"), "_com1 .txt");
+        assert_eq!(windows_file_name("console.log"), "console.log");
+        assert_eq!(windows_file_name("..."), "download");
+    }
+
+    #[test]
+    fn test_unique_download_path() {
+        let dir = std::env::temp_dir().join(format!("atlas_sftp_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(unique_download_path(&dir, "backup.tar.gz"), dir.join("backup.tar.gz"));
+        std::fs::write(dir.join("backup.tar.gz"), b"").unwrap();
+        assert_eq!(unique_download_path(&dir, "backup.tar.gz"), dir.join("backup.tar (2).gz"));
+        std::fs::write(dir.join("backup.tar (2).gz"), b"").unwrap();
+        assert_eq!(unique_download_path(&dir, "backup.tar.gz"), dir.join("backup.tar (3).gz"));
+
+        std::fs::write(dir.join(".env"), b"").unwrap();
+        assert_eq!(unique_download_path(&dir, ".env"), dir.join(".env (2)"));
+
+        // A partial download of the name is continued, not moved aside
+        std::fs::write(dir.join(format!("notes.txt{}", PARTIAL_SUFFIX)), b"").unwrap();
+        assert_eq!(unique_download_path(&dir, "notes.txt"), dir.join("notes.txt"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_checked_local_path() {
+        let dir = std::env::temp_dir().join("atlas_sftp_test");
+        let file = dir.join("notes.txt");
+        assert_eq!(checked_local_path(&format!(" {} ", file.display())).unwrap(), file);
+
+        let traversal = dir.join("..").join("..").join("notes.txt");
+        assert!(matches!(
+            checked_local_path(&traversal.to_string_lossy()),
+            Err(SftpError::PathTraversal { .. })
+        ));
+
+        for path in ["", "notes.txt", "downloads/notes.txt", "../notes.txt"] {
+            assert!(
+                matches!(checked_local_path(path), Err(SftpError::InvalidPath { .. })),
+                "{:?}",
+                path
+            );
+        }
+    }
+}
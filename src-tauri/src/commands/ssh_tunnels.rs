@@ -0,0 +1,274 @@
+// SSH local port forwarding
+// Tunnels run in ssh_session_worker, which holds one SSH session for all of them and
+// re-establishes it when it drops; each change is passed on as `server:tunnel_connection`. The
+// worker is started with the first tunnel and stopped with the last. Should the worker itself
+// die, it is started again and the tunnels reopened on their old ports
+use crate::commands::server::{get_server_config, ssh_worker_input};
+use crate::models::SshTunnel;
+use crate::process_manager::{PersistentWorker, WorkerEvent, WORKER_EXITED_EVENT};
+use crate::server_monitor::known_hosts;
+use chrono::Utc;
+use lazy_static::lazy_static;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+const SESSION_WORKER: &str = "ssh_session_worker.py";
+
+/// Ports below this need admin rights to bind
+const FIRST_UNPRIVILEGED_PORT: u16 = 1024;
+
+lazy_static! {
+    static ref SESSION: tokio::sync::Mutex<Option<Arc<PersistentWorker>>> = tokio::sync::Mutex::new(None);
+    /// Open tunnels; traffic stats come from the worker
+    static ref TUNNELS: Mutex<Vec<SshTunnel>> = Mutex::new(Vec::new());
+}
+
+/// The running session worker, started and connected when there is none
+async fn ensure_session(app: &AppHandle, password: Option<String>) -> Result<Arc<PersistentWorker>, String> {
+    if let Some(worker) = SESSION.lock().await.clone().filter(|w| w.is_running()) {
+        return Ok(worker);
+    }
+
+    let config = get_server_config()?;
+    let connect_params = ssh_worker_input(&config, password, json!({}))?;
+
+    let (events_tx, events_rx) = mpsc::channel(32);
+    let worker = Arc::new(PersistentWorker::spawn(SESSION_WORKER, events_tx)?);
+    let adopted = Arc::new(AtomicBool::new(false));
+    tokio::spawn(forward_events(app.clone(), events_rx, adopted.clone()));
+
+    // Connected without holding SESSION, so listing and closing tunnels don't wait on a slow login
+    worker.request("connect", connect_params).await.inspect_err(|e| {
+        known_hosts::report_error(Some(app), &config, e);
+    })?;
+
+    let mut session = SESSION.lock().await;
+    if let Some(existing) = session.as_ref().filter(|w| w.is_running()) {
+        // Another caller connected meanwhile; dropping ours ends its session
+        return Ok(existing.clone());
+    }
+    *session = Some(worker.clone());
+    adopted.store(true, Ordering::SeqCst);
+    Ok(worker)
+}
+
+/// Pass the worker's session events on to the UI
+/// `adopted` is set once the worker became the session; one that failed to connect isn't restarted
+async fn forward_events(app: AppHandle, mut events: mpsc::Receiver<WorkerEvent>, adopted: Arc<AtomicBool>) {
+    while let Some(event) = events.recv().await {
+        let state = match event.name.as_str() {
+            "connection_lost" => "lost",
+            "reconnect_failed" => "reconnecting",
+            "reconnected" => "reconnected",
+            WORKER_EXITED_EVENT => {
+                if adopted.load(Ordering::SeqCst) && !TUNNELS.lock().is_empty() {
+                    warn!("SSH session worker exited with tunnels open, restarting it");
+                    let _ = app.emit("server:tunnel_connection", json!({ "state": "lost" }));
+                    tokio::spawn(restore_tunnels(app.clone()));
+                }
+                return;
+            }
+            _ => continue,
+        };
+        info!("SSH tunnel session: {} {}", state, event.data);
+        let mut payload = event.data;
+        payload["state"] = json!(state);
+        let _ = app.emit("server:tunnel_connection", payload);
+    }
+}
+
+/// Start a new worker after the old one died and reopen every tunnel in it
+/// Tunnels that can't be reopened are dropped and reported in the `failed` event
+async fn restore_tunnels(app: AppHandle) {
+    let tunnels = TUNNELS.lock().clone();
+    let mut failed = Vec::new();
+    let mut error = None;
+
+    match ensure_session(&app, None).await {
+        Ok(worker) => {
+            for tunnel in &tunnels {
+                if let Err(e) = worker.request("open_tunnel", open_params(tunnel)).await {
+                    warn!("Failed to reopen SSH tunnel on port {}: {}", tunnel.local_port, e);
+                    failed.push(tunnel.id.clone());
+                    error = Some(e);
+                }
+            }
+        }
+        Err(e) => {
+            failed = tunnels.iter().map(|t| t.id.clone()).collect();
+            error = Some(e);
+        }
+    }
+
+    let Some(error) = error else {
+        let _ = app.emit("server:tunnel_connection", json!({ "state": "reconnected" }));
+        return;
+    };
+
+    warn!("Failed to restore {} of {} SSH tunnels: {}", failed.len(), tunnels.len(), error);
+    let remaining = {
+        let mut open = TUNNELS.lock();
+        open.retain(|t| !failed.contains(&t.id));
+        open.len()
+    };
+    if remaining == 0 {
+        *SESSION.lock().await = None;
+    }
+    let _ = app.emit(
+        "server:tunnel_connection",
+        json!({
+            "state": "failed",
+            "message": format!("{} of {} tunnels could not be reopened: {}", failed.len(), tunnels.len(), error),
+            "closed": failed,
+        }),
+    );
+}
+
+fn open_params(tunnel: &SshTunnel) -> serde_json::Value {
+    json!({
+        "id": tunnel.id,
+        "local_port": tunnel.local_port,
+        "remote_host": tunnel.remote_host,
+        "remote_port": tunnel.remote_port,
+    })
+}
+
+/// Copy the worker's stats for `tunnel` into it
+fn apply_stats(tunnel: &mut SshTunnel, stats: &serde_json::Value) {
+    let count = |key: &str| stats.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    tunnel.bytes_sent = count("bytes_sent");
+    tunnel.bytes_received = count("bytes_received");
+    tunnel.connections = count("connections");
+    tunnel.active_connections = count("active_connections");
+}
+
+/// Forward `local_port` on this PC to `remote_host:remote_port` as seen from the server
+/// `remote_host` defaults to "localhost", i.e. a service on the server itself
+#[tauri::command]
+pub async fn create_ssh_tunnel(
+    app: AppHandle,
+    local_port: u16,
+    remote_host: Option<String>,
+    remote_port: u16,
+    password: Option<String>,
+) -> Result<SshTunnel, String> {
+    if local_port < FIRST_UNPRIVILEGED_PORT {
+        return Err(format!(
+            "Local port {} is privileged; use a port from {} up",
+            local_port, FIRST_UNPRIVILEGED_PORT
+        ));
+    }
+    if remote_port == 0 {
+        return Err("Remote port is required".to_string());
+    }
+    let remote_host = remote_host
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "localhost".to_string());
+    if remote_host.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(format!("Invalid remote host: {}", remote_host));
+    }
+
+    if let Some(existing) = TUNNELS.lock().iter().find(|t| t.local_port == local_port) {
+        return Err(format!(
+            "Local port {} is already forwarded to {}:{}",
+            local_port, existing.remote_host, existing.remote_port
+        ));
+    }
+    // Checked here too so a conflict is reported before connecting
+    TcpListener::bind(("127.0.0.1", local_port))
+        .map_err(|e| format!("Local port {} is already in use: {}", local_port, e))?;
+
+    let mut tunnel = SshTunnel {
+        id: Uuid::new_v4().to_string(),
+        local_port,
+        remote_host,
+        remote_port,
+        created_at: Utc::now().to_rfc3339(),
+        status: "connected".to_string(),
+        bytes_sent: 0,
+        bytes_received: 0,
+        connections: 0,
+        active_connections: 0,
+    };
+
+    let worker = ensure_session(&app, password).await?;
+    let stats = worker.request("open_tunnel", open_params(&tunnel)).await?;
+    apply_stats(&mut tunnel, &stats);
+
+    info!(
+        "Opened SSH tunnel 127.0.0.1:{} -> {}:{}",
+        tunnel.local_port, tunnel.remote_host, tunnel.remote_port
+    );
+    TUNNELS.lock().push(tunnel.clone());
+    Ok(tunnel)
+}
+
+/// Close a tunnel; the SSH session closes with the last one
+#[tauri::command]
+pub async fn close_ssh_tunnel(id: String) -> Result<(), String> {
+    let remaining = {
+        let mut tunnels = TUNNELS.lock();
+        let before = tunnels.len();
+        tunnels.retain(|t| t.id != id);
+        if tunnels.len() == before {
+            return Err(format!("No tunnel {}", id));
+        }
+        tunnels.len()
+    };
+
+    let mut session = SESSION.lock().await;
+    if let Some(worker) = session.as_ref().filter(|w| w.is_running()) {
+        if let Err(e) = worker.request("close_tunnel", json!({ "id": id })).await {
+            warn!("Failed to close SSH tunnel {}: {}", id, e);
+        }
+    }
+    if remaining == 0 {
+        // Dropping the worker closes its stdin and ends the session
+        *session = None;
+        info!("Last SSH tunnel closed, SSH session stopped");
+    }
+    Ok(())
+}
+
+/// Open tunnels with their session status and traffic
+#[tauri::command]
+pub async fn list_ssh_tunnels() -> Result<Vec<SshTunnel>, String> {
+    let worker = SESSION.lock().await.clone().filter(|w| w.is_running());
+    let listing = match worker {
+        Some(worker) => Some(worker.request("list_tunnels", json!({})).await?),
+        None => None,
+    };
+
+    let mut tunnels = TUNNELS.lock().clone();
+    let state = listing
+        .as_ref()
+        .and_then(|l| l.get("state"))
+        .and_then(|s| s.as_str())
+        .unwrap_or("disconnected")
+        .to_string();
+    let stats = listing
+        .as_ref()
+        .and_then(|l| l.get("tunnels"))
+        .and_then(|t| t.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    for tunnel in &mut tunnels {
+        tunnel.status = state.clone();
+        let tunnel_stats = stats
+            .iter()
+            .find(|s| s.get("id").and_then(|id| id.as_str()) == Some(tunnel.id.as_str()));
+        if let Some(tunnel_stats) = tunnel_stats {
+            apply_stats(tunnel, tunnel_stats);
+        }
+    }
+    Ok(tunnels)
+}
@@ -0,0 +1,144 @@
+// systemd services on the server
+// Services are listed with `systemctl list-units --output=json`, with memory use from
+// `systemctl show` in the same round trip. Actions run through sudo for users other than root;
+// `sudo -n` fails at once where a password prompt would hang the worker, so the server needs a
+// NOPASSWD rule for systemctl, like the sudo quick actions already assume
+use crate::commands::server::{get_server_config, run_ssh_command};
+use crate::models::{CommandResult, SystemdService, SystemdServiceAction};
+use log::{debug, info};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Separates the unit list from the memory section in the combined output
+const MEMORY_MARKER: &str = "---ATLAS-MEMORY---";
+
+/// `MemoryCurrent` when memory accounting is off
+const MEMORY_NOT_SET: u64 = u64::MAX;
+
+#[derive(Debug, Deserialize)]
+struct ListedUnit {
+    unit: String,
+    #[serde(default)]
+    load: String,
+    #[serde(default)]
+    active: String,
+    #[serde(default)]
+    sub: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Unit names are restricted so they can go into a shell command unquoted
+fn checked_service_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '@' | '.' | '_' | ':' | '-'));
+    if !valid {
+        return Err(format!("Invalid service name: {}", name));
+    }
+    Ok(if name.ends_with(".service") {
+        name.to_string()
+    } else {
+        format!("{}.service", name)
+    })
+}
+
+/// "Id=nginx.service\nMemoryCurrent=123\n\nId=..." -> memory by unit
+fn parse_memory(output: &str) -> HashMap<String, u64> {
+    let mut memory = HashMap::new();
+    for block in output.split("\n\n") {
+        let mut id = None;
+        let mut current = None;
+        for line in block.lines() {
+            if let Some(value) = line.trim().strip_prefix("Id=") {
+                id = Some(value.to_string());
+            } else if let Some(value) = line.trim().strip_prefix("MemoryCurrent=") {
+                current = value.parse::<u64>().ok().filter(|v| *v != MEMORY_NOT_SET);
+            }
+        }
+        if let (Some(id), Some(current)) = (id, current) {
+            memory.insert(id, current);
+        }
+    }
+    memory
+}
+
+/// Services on the server, optionally only those whose name or description contains `filter`
+#[tauri::command]
+pub async fn get_systemd_services(
+    filter: Option<String>,
+    password: Option<String>,
+) -> Result<Vec<SystemdService>, String> {
+    let command = format!(
+        "systemctl list-units --type=service --all --output=json --no-pager && echo '{}' && \
+         systemctl show --property=Id,MemoryCurrent --no-pager '*.service'",
+        MEMORY_MARKER
+    );
+    let result = run_ssh_command(&command, password).await?;
+    if result.exit_code != Some(0) {
+        return Err(format!(
+            "Failed to list services: {}",
+            result.error.unwrap_or_else(|| "systemctl failed".to_string())
+        ));
+    }
+
+    let (units_json, memory_output) = result
+        .output
+        .split_once(MEMORY_MARKER)
+        .unwrap_or((result.output.as_str(), ""));
+    let units: Vec<ListedUnit> = serde_json::from_str(units_json.trim())
+        .map_err(|e| format!("Unexpected systemctl output (systemd 246 or newer is needed): {}", e))?;
+    let memory = parse_memory(memory_output);
+
+    let filter = filter.map(|f| f.trim().to_lowercase()).filter(|f| !f.is_empty());
+    let services: Vec<SystemdService> = units
+        .into_iter()
+        .filter(|u| {
+            filter.as_deref().map_or(true, |f| {
+                u.unit.to_lowercase().contains(f) || u.description.to_lowercase().contains(f)
+            })
+        })
+        .map(|u| SystemdService {
+            memory_bytes: memory.get(&u.unit).copied(),
+            name: u.unit,
+            description: u.description,
+            load_state: u.load,
+            active_state: u.active,
+            sub_state: u.sub,
+        })
+        .collect();
+
+    debug!("Listed {} systemd services", services.len());
+    Ok(services)
+}
+
+/// Start, stop, restart, enable or disable a service
+#[tauri::command]
+pub async fn systemd_service_action(
+    name: String,
+    action: SystemdServiceAction,
+    password: Option<String>,
+) -> Result<CommandResult, String> {
+    let service = checked_service_name(&name)?;
+    let config = get_server_config()?;
+    let sudo = if config.username == "root" { "" } else { "sudo -n " };
+    let command = format!("{}systemctl {} {}", sudo, action.as_str(), service);
+
+    info!("Running '{}' on {}", command, config.host);
+    let result = run_ssh_command(&command, password).await?;
+
+    let needs_password = result
+        .error
+        .as_deref()
+        .is_some_and(|e| e.contains("a password is required"));
+    if needs_password {
+        return Err(format!(
+            "sudo asked for a password. Allow '{}' to run systemctl without one (NOPASSWD in sudoers).",
+            config.username
+        ));
+    }
+    Ok(result)
+}
@@ -1,13 +1,51 @@
 use crate::task_monitor::{
     self,
-    models::{GamingProfile, KillResult, ProcessCategory, ProcessInfo, SystemSummary},
-    profiles,
-    restore::{self, RestoreList, RestoreResult},
+    affinity, inspect,
+    models::{
+        GamingProfile, KillMode, KillRecommendation, KillResult, ProcessAffinity, ProcessCategory,
+        ProcessCategoryConfig, ProcessDetails, ProcessHistory, ProcessInfo, ProcessListPage,
+        ProcessListQuery, ProcessPriority, ProcessSortBy, ProfileImportResult, ProfilePreview,
+        ServicesSummary, StartupItem, SystemSummary,
+    },
+    overrides, profiles, protection,
+    restore::{self, KilledProcessInfo, RestoreList, RestoreResult},
+    services, startup, suspend,
 };
+use std::path::Path;
 
+/// Process list, optionally filtered, sorted and paged so only the requested slice is sent
 #[tauri::command]
-pub fn get_process_list() -> Result<Vec<ProcessInfo>, String> {
-    Ok(task_monitor::get_all_processes())
+pub fn get_process_list(
+    filter: Option<String>,
+    category: Option<ProcessCategory>,
+    sort_by: Option<ProcessSortBy>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<ProcessListPage, String> {
+    Ok(task_monitor::query_processes(&ProcessListQuery {
+        filter,
+        category,
+        sort_by,
+        limit,
+        offset: offset.unwrap_or(0),
+    }))
+}
+
+#[tauri::command]
+pub fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
+    inspect::get_process_details(pid)
+}
+
+/// Defaults to the full 30 minutes kept per process
+#[tauri::command]
+pub fn get_process_history(pid: u32, seconds: Option<u64>) -> Result<ProcessHistory, String> {
+    task_monitor::get_process_history(pid, seconds.unwrap_or(1800))
+}
+
+/// Sampled at most every 5 seconds - the first call after startup reports no traffic
+#[tauri::command]
+pub fn get_top_network_processes(limit: Option<usize>) -> Result<Vec<ProcessInfo>, String> {
+    task_monitor::get_top_network_processes(limit.unwrap_or(10))
 }
 
 #[tauri::command]
@@ -15,26 +53,70 @@ pub fn get_system_summary() -> Result<SystemSummary, String> {
     Ok(task_monitor::get_system_summary())
 }
 
+/// Run a kill off the command thread - graceful closes wait for the process to exit
+async fn run_kill<T: Send + 'static>(kill: impl FnOnce() -> Result<T, String> + Send + 'static) -> Result<T, String> {
+    tokio::task::spawn_blocking(kill)
+        .await
+        .map_err(|e| format!("Failed to kill process: {}", e))?
+}
+
+#[tauri::command]
+pub async fn kill_single_process(pid: u32, include_children: Option<bool>, force: Option<bool>) -> Result<(), String> {
+    run_kill(move || {
+        if !include_children.unwrap_or(false) {
+            let mode = if force.unwrap_or(false) { KillMode::Force } else { KillMode::Auto };
+            return task_monitor::kill_process_with(pid, mode).map(|_| ());
+        }
+
+        let result = task_monitor::kill_process_tree(pid)?;
+        if result.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(result.errors.join("; "))
+        }
+    })
+    .await
+}
+
 #[tauri::command]
-pub fn kill_single_process(pid: u32) -> Result<(), String> {
-    task_monitor::kill_process(pid)
+pub async fn kill_process_tree(pid: u32) -> Result<KillResult, String> {
+    run_kill(move || task_monitor::kill_process_tree(pid)).await
 }
 
 #[tauri::command]
-pub fn kill_multiple_processes(pids: Vec<u32>) -> Result<KillResult, String> {
-    Ok(task_monitor::kill_multiple_processes(&pids))
+pub async fn kill_multiple_processes(pids: Vec<u32>) -> Result<KillResult, String> {
+    run_kill(move || Ok(task_monitor::kill_multiple_processes(&pids))).await
 }
 
 #[tauri::command]
-pub fn kill_by_category(category: String) -> Result<KillResult, String> {
+pub async fn kill_by_category(category: String) -> Result<KillResult, String> {
     let cat = match category.as_str() {
         "MicrosoftBloat" => ProcessCategory::MicrosoftBloat,
         "UserApplication" => ProcessCategory::UserApplication,
         "BackgroundService" => ProcessCategory::BackgroundService,
         "Unknown" => ProcessCategory::Unknown,
-        _ => return Err(format!("Cannot kill category: {}", category)),
+        _ if ProcessCategory::from_name(&category).is_some() => {
+            return Err(format!("Cannot kill category: {}", category))
+        }
+        // Custom categories are only bulk-killed when marked killable
+        _ => return run_kill(move || task_monitor::kill_by_custom_category(&category)).await,
     };
-    Ok(task_monitor::kill_by_category(&cat))
+    run_kill(move || Ok(task_monitor::kill_by_category(&cat))).await
+}
+
+#[tauri::command]
+pub fn set_process_priority(pid: u32, priority: ProcessPriority) -> Result<Option<ProcessPriority>, String> {
+    task_monitor::set_process_priority(pid, priority)
+}
+
+#[tauri::command]
+pub fn get_process_affinity(pid: u32) -> Result<ProcessAffinity, String> {
+    affinity::get_affinity(pid)
+}
+
+#[tauri::command]
+pub fn set_process_affinity(pid: u32, core_mask: u64) -> Result<(), String> {
+    task_monitor::set_process_affinity(pid, core_mask)
 }
 
 #[tauri::command]
@@ -58,15 +140,121 @@ pub fn set_default_gaming_profile(id: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn execute_gaming_profile(id: String) -> Result<KillResult, String> {
-    task_monitor::execute_profile(&id)
+pub async fn execute_gaming_profile(id: String, only_pids: Option<Vec<u32>>) -> Result<KillResult, String> {
+    run_kill(move || task_monitor::execute_profile(&id, only_pids.as_deref())).await
 }
 
 #[tauri::command]
-pub fn get_kill_recommendations(min_memory_mb: f64) -> Result<Vec<ProcessInfo>, String> {
+pub fn preview_gaming_profile(profile_id: String) -> Result<ProfilePreview, String> {
+    task_monitor::preview_profile(&profile_id)
+}
+
+/// Export all profiles (or only `ids`) to a JSON file, returning how many were written
+#[tauri::command]
+pub fn export_gaming_profiles(path: String, ids: Option<Vec<String>>) -> Result<usize, String> {
+    profiles::export_profiles(Path::new(&path), ids.as_deref())
+}
+
+/// Import profiles from a file written by `export_gaming_profiles`
+#[tauri::command]
+pub fn import_gaming_profiles(path: String, overwrite: bool) -> Result<ProfileImportResult, String> {
+    profiles::import_profiles(Path::new(&path), overwrite)
+}
+
+#[tauri::command]
+pub fn get_kill_recommendations(min_memory_mb: f64) -> Result<Vec<KillRecommendation>, String> {
     Ok(task_monitor::get_kill_recommendations(min_memory_mb))
 }
 
+// Protected process commands
+#[tauri::command]
+pub fn get_protected_processes() -> Result<Vec<String>, String> {
+    protection::get_protected_processes()
+}
+
+#[tauri::command]
+pub fn add_protected_process(name: String) -> Result<(), String> {
+    protection::add_protected_process(&name)
+}
+
+#[tauri::command]
+pub fn remove_protected_process(name: String) -> Result<(), String> {
+    protection::remove_protected_process(&name)
+}
+
+// Category override commands
+#[tauri::command]
+pub fn get_process_category_config() -> Result<ProcessCategoryConfig, String> {
+    overrides::get_config()
+}
+
+#[tauri::command]
+pub fn set_process_category_override(
+    name: String,
+    category: String,
+    display_name: Option<String>,
+    description: Option<String>,
+) -> Result<(), String> {
+    overrides::set_override(&name, &category, display_name, description)
+}
+
+#[tauri::command]
+pub fn clear_process_category_override(name: String) -> Result<(), String> {
+    overrides::clear_override(&name)
+}
+
+#[tauri::command]
+pub fn save_custom_category(name: String, killable: bool) -> Result<(), String> {
+    overrides::save_custom_category(&name, killable)
+}
+
+#[tauri::command]
+pub fn delete_custom_category(name: String) -> Result<(), String> {
+    overrides::delete_custom_category(&name)
+}
+
+#[tauri::command]
+pub fn get_category_exclusions(category: String) -> Result<Vec<String>, String> {
+    overrides::get_exclusions(&category)
+}
+
+#[tauri::command]
+pub fn set_category_exclusions(category: String, names: Vec<String>) -> Result<(), String> {
+    overrides::set_exclusions(&category, &names)
+}
+
+// Startup program commands
+#[tauri::command]
+pub fn get_startup_items() -> Result<Vec<StartupItem>, String> {
+    startup::get_startup_items()
+}
+
+#[tauri::command]
+pub fn disable_startup_item(id: String) -> Result<(), String> {
+    startup::disable_startup_item(&id)
+}
+
+#[tauri::command]
+pub fn enable_startup_item(id: String) -> Result<(), String> {
+    startup::enable_startup_item(&id)
+}
+
+// Windows service commands
+#[tauri::command]
+pub fn get_services_summary() -> Result<ServicesSummary, String> {
+    services::get_services_summary()
+}
+
+#[tauri::command]
+pub fn stop_service(name: String) -> Result<(), String> {
+    services::stop_service(&name)
+}
+
+#[tauri::command]
+pub fn start_service(name: String) -> Result<(), String> {
+    services::start_service(&name)
+}
+
 // Restore feature commands
 #[tauri::command]
 pub fn get_restore_list() -> Result<RestoreList, String> {
@@ -81,8 +269,34 @@ pub fn clear_restore_list() -> Result<(), String> {
 #[tauri::command]
 pub fn restore_processes_now() -> Result<RestoreResult, String> {
     let list = restore::load_restore_list()?;
-    let result = restore::restore_all_processes(&list);
+    let mut result = restore::restore_all_processes(&list);
+    result.priorities_restored = restore::restore_priorities(&list.deprioritized);
+    result.services_restored = restore::restore_services(&list.stopped_services);
+    result.processes_resumed = suspend::resume_suspended_since(0);
     // Clear the restore list after restoration
     let _ = restore::clear_restore_list();
     Ok(result)
 }
+
+/// Freeze a process instead of killing it - resumed by `resume_process` or the end-of-session restore
+#[tauri::command]
+pub fn suspend_process(pid: u32) -> Result<(), String> {
+    suspend::suspend_process(pid)
+}
+
+#[tauri::command]
+pub fn resume_process(pid: u32) -> Result<(), String> {
+    suspend::resume_process(pid)
+}
+
+/// Relaunch the most recently killed process
+#[tauri::command]
+pub fn undo_last_kill() -> Result<KilledProcessInfo, String> {
+    task_monitor::undo_last_kill()
+}
+
+/// Relaunch a specific entry from the restore list
+#[tauri::command]
+pub fn restore_single_process(exe_path: String) -> Result<KilledProcessInfo, String> {
+    restore::restore_single_process(&exe_path)
+}
@@ -0,0 +1,114 @@
+// Skipped and snoozed updates
+// `check_for_update` still returns a deferred update, flagged, so the UI can offer it anyway; the
+// background checker leaves it alone. A release newer than the skipped version clears the skip
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::UpdateDeferral;
+use crate::utils::get_update_deferral_json_path;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use log::{info, warn};
+
+/// Longest snooze
+const MAX_SNOOZE_HOURS: u32 = 24 * 30;
+
+/// Whether an update is deferred, as reported with it
+pub(crate) struct DeferralStatus {
+    pub skipped: bool,
+    pub snoozed_until: Option<String>,
+}
+
+impl DeferralStatus {
+    pub fn is_deferred(&self) -> bool {
+        self.skipped || self.snoozed_until.is_some()
+    }
+}
+
+fn load() -> UpdateDeferral {
+    let path = get_update_deferral_json_path();
+    if !path.exists() {
+        return UpdateDeferral::default();
+    }
+    read_json_file(&path).unwrap_or_else(|e| {
+        warn!("Failed to read update deferral: {}", e);
+        UpdateDeferral::default()
+    })
+}
+
+fn save(deferral: &UpdateDeferral) -> Result<(), String> {
+    write_json_file(&get_update_deferral_json_path(), deferral)
+}
+
+/// Whether `version` is newer than `than`; versions that aren't semver only compare equal or not
+fn is_newer(version: &str, than: &str) -> bool {
+    match (semver::Version::parse(version), semver::Version::parse(than)) {
+        (Ok(version), Ok(than)) => version > than,
+        _ => version != than,
+    }
+}
+
+/// Whether `version` is skipped or snoozed; drops a skip it supersedes and a snooze that has ended
+pub(crate) fn deferral_for(version: &str) -> DeferralStatus {
+    let mut deferral = load();
+    let mut changed = false;
+
+    if let Some(skipped) = deferral.skipped_version.clone().filter(|skipped| is_newer(version, skipped)) {
+        info!("Update {} is newer than the skipped {}, offering it again", version, skipped);
+        deferral.skipped_version = None;
+        changed = true;
+    }
+    let snooze_over = deferral
+        .snoozed_until
+        .as_deref()
+        .and_then(|until| DateTime::parse_from_rfc3339(until).ok())
+        .map_or(true, |until| until.with_timezone(&Utc) <= Utc::now());
+    if deferral.snoozed_until.is_some() && snooze_over {
+        deferral.snoozed_until = None;
+        changed = true;
+    }
+
+    if changed {
+        if let Err(e) = save(&deferral) {
+            warn!("Failed to save update deferral: {}", e);
+        }
+    }
+
+    DeferralStatus {
+        skipped: deferral.skipped_version.as_deref() == Some(version),
+        snoozed_until: deferral.snoozed_until,
+    }
+}
+
+/// Stop offering `version`; a newer release is offered as usual
+#[tauri::command]
+pub fn skip_update_version(version: String) -> Result<(), String> {
+    let version = version.trim().trim_start_matches('v').to_string();
+    if version.is_empty() {
+        return Err("Version is required".to_string());
+    }
+
+    let mut deferral = load();
+    info!("Skipping update {}", version);
+    deferral.skipped_version = Some(version);
+    save(&deferral)
+}
+
+/// Stop offering updates for `hours`; returns when the snooze ends
+#[tauri::command]
+pub fn snooze_update(hours: u32) -> Result<String, String> {
+    if !(1..=MAX_SNOOZE_HOURS).contains(&hours) {
+        return Err(format!("Snooze must be between 1 and {} hours", MAX_SNOOZE_HOURS));
+    }
+
+    let until = (Utc::now() + ChronoDuration::hours(hours as i64)).to_rfc3339();
+    let mut deferral = load();
+    deferral.snoozed_until = Some(until.clone());
+    save(&deferral)?;
+
+    info!("Update prompts snoozed until {}", until);
+    Ok(until)
+}
+
+/// Drop the skip and the snooze, e.g. when the user asks to update after all
+#[tauri::command]
+pub fn clear_update_deferral() -> Result<(), String> {
+    save(&UpdateDeferral::default())
+}
@@ -0,0 +1,194 @@
+// Rolling back a bad update
+// Every update installed through Atlas is recorded with its download URL and signature. Installing
+// the next one moves the running version's record into the single rollback slot, so
+// `rollback_update` can download that build again and run its installer. The slot is cleared once
+// the new version has been in use for ROLLBACK_GRACE_DAYS, or after a rollback. The installer is
+// verified against the updater key and run directly, so no release from the update server is needed
+use crate::commands::updater::verify_signature;
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::{InstalledRelease, RollbackInfo, RollbackSlot, UpdateRollbackState};
+use crate::utils::get_update_rollback_json_path;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use log::{info, warn};
+use std::fs;
+use std::io::Read;
+use std::process::Command;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_updater::Update;
+
+/// Days the new version has to run before its rollback slot is cleared
+const ROLLBACK_GRACE_DAYS: i64 = 7;
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Larger downloads are refused; installers are far smaller
+const MAX_INSTALLER_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// NSIS arguments the updater plugin uses for a passive install: progress only, relaunch Atlas after
+const INSTALLER_ARGS: &[&str] = &["/P", "/R", "/UPDATE"];
+
+fn load_state() -> UpdateRollbackState {
+    let path = get_update_rollback_json_path();
+    if !path.exists() {
+        return UpdateRollbackState::default();
+    }
+    read_json_file(&path).unwrap_or_else(|e| {
+        warn!("Failed to read update rollback state: {}", e);
+        UpdateRollbackState::default()
+    })
+}
+
+fn save_state(state: &UpdateRollbackState) -> Result<(), String> {
+    write_json_file(&get_update_rollback_json_path(), state)
+}
+
+fn expires_at(slot: &RollbackSlot) -> Option<DateTime<Utc>> {
+    let first_run = DateTime::parse_from_rfc3339(slot.first_run_at.as_deref()?).ok()?;
+    Some(first_run.with_timezone(&Utc) + ChronoDuration::days(ROLLBACK_GRACE_DAYS))
+}
+
+/// Record `update` as installed, moving the running version's record into the rollback slot
+/// Returns the state from before, for `restore` if the installer doesn't start
+pub(crate) fn prepare(current_version: &str, update: &Update) -> Result<UpdateRollbackState, String> {
+    let before = load_state();
+    let mut state = before.clone();
+
+    // A version installed by hand has no record, so there is nothing to go back to
+    state.previous = state
+        .installed
+        .take()
+        .filter(|release| release.version == current_version)
+        .map(|release| RollbackSlot {
+            release,
+            replaced_by: update.version.clone(),
+            saved_at: Utc::now().to_rfc3339(),
+            first_run_at: None,
+        });
+    state.installed = Some(InstalledRelease {
+        version: update.version.clone(),
+        download_url: update.download_url.to_string(),
+        signature: update.signature.clone(),
+    });
+
+    save_state(&state)?;
+    Ok(before)
+}
+
+pub(crate) fn restore(state: &UpdateRollbackState) {
+    if let Err(e) = save_state(state) {
+        warn!("Failed to restore update rollback state: {}", e);
+    }
+}
+
+/// Start the grace period on the first run of the new version and clear the slot when it ends
+/// A slot for another version than the running one (rolled back, or reinstalled by hand) is cleared too
+pub fn on_startup(current_version: &str) {
+    let mut state = load_state();
+    let Some(slot) = state.previous.as_mut() else {
+        return;
+    };
+
+    if slot.replaced_by != current_version {
+        info!("Running {} instead of {}, clearing the rollback slot", current_version, slot.replaced_by);
+        state.previous = None;
+    } else if slot.first_run_at.is_none() {
+        slot.first_run_at = Some(Utc::now().to_rfc3339());
+    } else if expires_at(slot).is_some_and(|at| Utc::now() >= at) {
+        info!("{} has run for {} days, clearing the rollback slot", current_version, ROLLBACK_GRACE_DAYS);
+        state.previous = None;
+    } else {
+        return;
+    }
+
+    if let Err(e) = save_state(&state) {
+        warn!("Failed to save update rollback state: {}", e);
+    }
+}
+
+/// Whether there is a version to roll back to, and which
+#[tauri::command]
+pub fn get_rollback_info() -> RollbackInfo {
+    let slot = load_state().previous;
+    RollbackInfo {
+        available: slot.is_some(),
+        version: slot.as_ref().map(|s| s.release.version.clone()),
+        replaced_by: slot.as_ref().map(|s| s.replaced_by.clone()),
+        saved_at: slot.as_ref().map(|s| s.saved_at.clone()),
+        expires_at: slot.as_ref().and_then(expires_at).map(|at| at.to_rfc3339()),
+    }
+}
+
+fn download_installer(url: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .call()
+        .map_err(|e| format!("Failed to download the previous version: {}", e))?;
+
+    let too_large = || {
+        format!("The previous version's installer is larger than {} MB", MAX_INSTALLER_BYTES / 1024 / 1024)
+    };
+    let announced = response.header("Content-Length").and_then(|len| len.parse::<u64>().ok());
+    if announced.is_some_and(|len| len > MAX_INSTALLER_BYTES) {
+        return Err(too_large());
+    }
+
+    // One byte past the limit is enough to tell an oversized download from one that fits
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_INSTALLER_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to download the previous version: {}", e))?;
+    if bytes.len() as u64 > MAX_INSTALLER_BYTES {
+        return Err(too_large());
+    }
+    Ok(bytes)
+}
+
+/// Write the installer to the temp directory and start it
+fn run_installer(bytes: &[u8]) -> Result<(), String> {
+    let path = std::env::temp_dir().join("Atlas-rollback-setup.exe");
+    fs::write(&path, bytes).map_err(|e| format!("Failed to save the installer: {}", e))?;
+    Command::new(&path)
+        .args(INSTALLER_ARGS)
+        .spawn()
+        .map_err(|e| format!("Failed to start the installer: {}", e))?;
+    Ok(())
+}
+
+/// Download the version the last update replaced and run its installer
+/// Atlas exits once the installer has started; the installer relaunches it when done
+#[tauri::command]
+pub async fn rollback_update(app: AppHandle) -> Result<(), String> {
+    let before = load_state();
+    let slot = before
+        .previous
+        .clone()
+        .ok_or_else(|| "No previous version to roll back to".to_string())?;
+
+    info!("Rolling back from {} to {}", slot.replaced_by, slot.release.version);
+    let _ = app.emit(
+        "updater:rollback_started",
+        serde_json::json!({ "version": slot.release.version }),
+    );
+
+    let url = slot.release.download_url.clone();
+    let bytes = tokio::task::spawn_blocking(move || download_installer(&url))
+        .await
+        .map_err(|e| format!("Failed to download the previous version: {}", e))??;
+    verify_signature(&app, &bytes, &slot.release.signature)
+        .map_err(|e| format!("Previous version failed signature verification: {}", e))?;
+
+    let mut state = before.clone();
+    state.installed = Some(slot.release);
+    state.previous = None;
+    save_state(&state)?;
+
+    if let Err(e) = run_installer(&bytes) {
+        restore(&before);
+        return Err(format!("Failed to install the previous version: {}", e));
+    }
+    app.exit(0);
+    Ok(())
+}
@@ -0,0 +1,129 @@
+// Background update downloads
+// With `auto_download_updates` on, the selected channel is checked shortly after startup and every
+// `update_check_interval_hours`. A found update is downloaded into `DownloadedUpdateBytes` and
+// announced with `update:ready` and a notification; installing is always left to the user. Checks
+// are put off while a gaming session runs so the download doesn't compete with the game, and
+// skipped or snoozed updates are left alone
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::data_directory::gaming_session_active;
+use crate::commands::settings::get_settings;
+use crate::commands::update_deferral::deferral_for;
+use crate::commands::updater::{
+    channel_updater, discard_stale_download, download_and_store, downloaded_version, update_info, UpdateInfo,
+};
+
+/// Wait after startup before the first check, so it doesn't slow down loading
+const STARTUP_DELAY_SECONDS: i64 = 30;
+
+/// Longest single wait, so wall-clock jumps (sleep, clock changes) are noticed
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Wait before checking again while a gaming session runs
+const GAMING_RETRY_MINUTES: i64 = 15;
+
+/// Wait before retrying after a failed check or download
+const RETRY_DELAY_MINUTES: i64 = 30;
+
+lazy_static! {
+    static ref REARM: Mutex<Option<Sender<()>>> = Mutex::new(None);
+}
+
+/// Start the checker thread
+pub fn start(app: AppHandle) {
+    let (sender, receiver) = mpsc::channel();
+    *REARM.lock() = Some(sender);
+
+    std::thread::spawn(move || {
+        let mut next_check = Utc::now() + ChronoDuration::seconds(STARTUP_DELAY_SECONDS);
+        loop {
+            let wait = (next_check - Utc::now()).to_std().unwrap_or(Duration::ZERO).min(POLL_INTERVAL);
+            match receiver.recv_timeout(wait) {
+                Ok(()) => {
+                    next_check = Utc::now();
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+            if Utc::now() < next_check {
+                continue;
+            }
+
+            next_check = match run_check(&app) {
+                Ok(at) => {
+                    debug!("Next background update check at {}", at);
+                    at
+                }
+                Err(e) => {
+                    warn!("Background update check failed: {}", e);
+                    Utc::now() + ChronoDuration::minutes(RETRY_DELAY_MINUTES)
+                }
+            };
+        }
+    });
+}
+
+/// Check again now, after the update settings changed
+pub fn rearm() {
+    if let Some(sender) = REARM.lock().as_ref() {
+        let _ = sender.send(());
+    }
+}
+
+/// Download the latest release if there is a new one; returns when to check next
+fn run_check(app: &AppHandle) -> Result<DateTime<Utc>, String> {
+    let settings = get_settings()?;
+    let next = Utc::now() + ChronoDuration::hours(settings.update_check_interval_hours.max(1) as i64);
+    if !settings.auto_download_updates {
+        return Ok(next);
+    }
+    if gaming_session_active(app) {
+        debug!("Gaming session active, putting off the update check");
+        return Ok(Utc::now() + ChronoDuration::minutes(GAMING_RETRY_MINUTES));
+    }
+
+    let updater = channel_updater(app, &settings.update_channel, false)?;
+    let update = tauri::async_runtime::block_on(updater.check())
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+    let Some(update) = update else {
+        return Ok(next);
+    };
+
+    discard_stale_download(app, &update.version);
+    if deferral_for(&update.version).is_deferred() {
+        debug!("Update {} is skipped or snoozed", update.version);
+        return Ok(next);
+    }
+    if downloaded_version(app).as_deref() == Some(update.version.as_str()) {
+        return Ok(next);
+    }
+
+    info!("Downloading update {} in the background", update.version);
+    let info = update_info(&update, settings.update_channel);
+    tauri::async_runtime::block_on(download_and_store(app, update, true))?;
+    notify_ready(app, &info);
+    Ok(next)
+}
+
+fn notify_ready(app: &AppHandle, info: &UpdateInfo) {
+    info!("Update {} downloaded and ready to install", info.version);
+    let _ = app.emit("update:ready", info);
+
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Atlas update ready")
+        .body(format!("Version {} is downloaded. Restart Atlas to install it.", info.version))
+        .show()
+    {
+        warn!("Failed to show update notification: {}", e);
+    }
+}
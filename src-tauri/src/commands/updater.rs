@@ -1,12 +1,38 @@
+use crate::commands::settings::get_settings;
+use crate::commands::update_deferral::deferral_for;
+use crate::commands::update_rollback;
+use crate::models::UpdateInstallError;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use log::info;
+use minisign_verify::{PublicKey, Signature};
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
-use tauri_plugin_updater::UpdaterExt;
+use tauri_plugin_updater::{Update, Updater, UpdaterExt};
 use time::format_description::well_known::Rfc3339;
+use tokio::sync::oneshot;
 
-pub struct DownloadedUpdateBytes(pub Mutex<Option<Vec<u8>>>);
+/// Manifest of the stable channel, same as the endpoint in tauri.conf.json
+const STABLE_UPDATE_ENDPOINT: &str = "https://updates.kaic5504.com/atlas/update.json";
+
+/// Manifest of the beta channel
+const BETA_UPDATE_ENDPOINT: &str = "https://updates.kaic5504.com/atlas/beta/update.json";
+
+/// A downloaded update, kept with the release it was downloaded from so installing doesn't check again
+pub struct DownloadedUpdate {
+    pub update: Update,
+    pub bytes: Vec<u8>,
+    /// Size announced by the server, when it sent one
+    pub expected_len: Option<u64>,
+}
+
+pub struct DownloadedUpdateBytes(pub Mutex<Option<DownloadedUpdate>>);
+
+/// Stops the download in progress; set while one runs, so a manual and a background download don't overlap
+static DOWNLOAD_CANCEL: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
+
+const DOWNLOAD_CANCELLED: &str = "Update download cancelled";
 
 /// Information about an available update
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +41,15 @@ pub struct UpdateInfo {
     pub current_version: String,
     pub date: Option<String>,
     pub body: Option<String>,
+    /// Release channel the update came from
+    pub channel: String,
+    /// Older than the installed version, e.g. the latest stable after switching back from a beta build;
+    /// downloading it needs `allow_downgrade`
+    pub downgrade: bool,
+    /// The user chose to skip this version
+    pub skipped: bool,
+    /// Update prompts are snoozed until then
+    pub snoozed_until: Option<String>,
 }
 
 /// Progress information during download
@@ -23,34 +58,114 @@ pub struct UpdateProgress {
     pub downloaded: u64,
     pub total: u64,
     pub percent: u32,
+    /// Downloaded by the background checker rather than from the update prompt
+    pub background: bool,
+}
+
+/// Release channel selected in settings
+pub fn selected_channel() -> String {
+    get_settings().map(|s| s.update_channel).unwrap_or_else(|_| "stable".to_string())
 }
 
-/// Check if an update is available
+/// Channel the build with `version` was released on: prereleases ("1.7.0-beta.1") are beta builds
+pub fn build_channel(version: &str) -> &'static str {
+    match semver::Version::parse(version) {
+        Ok(version) if !version.pre.is_empty() => "beta",
+        _ => "stable",
+    }
+}
+
+/// Update manifest URL of `channel`
+pub(crate) fn channel_endpoint(channel: &str) -> &'static str {
+    match channel {
+        "beta" => BETA_UPDATE_ENDPOINT,
+        _ => STABLE_UPDATE_ENDPOINT,
+    }
+}
+
+/// Updater for `channel`
+/// Only newer versions count as updates unless `allow_downgrade` is set, so leaving the beta channel
+/// doesn't offer an older stable build on its own
+pub(crate) fn channel_updater(app: &AppHandle, channel: &str, allow_downgrade: bool) -> Result<Updater, String> {
+    let endpoint = url::Url::parse(channel_endpoint(channel)).map_err(|e| e.to_string())?;
+    let mut builder = app.updater_builder().endpoints(vec![endpoint]).map_err(|e| e.to_string())?;
+    if allow_downgrade {
+        builder = builder.version_comparator(|current, release| release.version != current);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Check if an update is available on the selected channel
+/// When the running build is from the other channel and nothing newer is out, that channel's latest
+/// release is offered as a downgrade
 #[tauri::command]
 pub async fn check_for_update(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
-    let updater = app.updater().map_err(|e| e.to_string())?;
-
-    match updater.check().await {
-        Ok(Some(update)) => {
-            let info = UpdateInfo {
-                version: update.version.clone(),
-                current_version: update.current_version.clone(),
-                date: update.date.and_then(|d| d.format(&Rfc3339).ok()),
-                body: update.body.clone(),
-            };
-            Ok(Some(info))
-        }
-        Ok(None) => Ok(None),
-        Err(e) => Err(format!("Failed to check for updates: {}", e)),
+    let channel = selected_channel();
+    let mut update = channel_updater(&app, &channel, false)?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    let current_version = app.package_info().version.to_string();
+    if update.is_none() && build_channel(&current_version) != channel {
+        update = channel_updater(&app, &channel, true)?
+            .check()
+            .await
+            .map_err(|e| format!("Failed to check for updates: {}", e))?;
+    }
+
+    Ok(update.map(|update| {
+        discard_stale_download(&app, &update.version);
+        update_info(&update, channel)
+    }))
+}
+
+pub(crate) fn update_info(update: &Update, channel: String) -> UpdateInfo {
+    let deferral = deferral_for(&update.version);
+    UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        date: update.date.and_then(|d| d.format(&Rfc3339).ok()),
+        body: update.body.clone(),
+        channel,
+        downgrade: is_older(&update.version, &update.current_version),
+        skipped: deferral.skipped,
+        snoozed_until: deferral.snoozed_until,
+    }
+}
+
+fn is_older(version: &str, than: &str) -> bool {
+    match (semver::Version::parse(version), semver::Version::parse(than)) {
+        (Ok(version), Ok(than)) => version < than,
+        _ => false,
     }
 }
 
+/// Version of the downloaded, not yet installed update
+pub(crate) fn downloaded_version(app: &AppHandle) -> Option<String> {
+    let state = app.state::<DownloadedUpdateBytes>();
+    let stored = state.0.lock().ok()?;
+    stored.as_ref().map(|d| d.update.version.clone())
+}
+
+/// Drop downloaded bytes that are no longer the latest release
+pub(crate) fn discard_stale_download(app: &AppHandle, latest_version: &str) {
+    let state = app.state::<DownloadedUpdateBytes>();
+    let Ok(mut stored) = state.0.lock() else {
+        return;
+    };
+    if stored.as_ref().is_some_and(|d| d.update.version != latest_version) {
+        info!("Discarding downloaded update, {} is now the latest", latest_version);
+        *stored = None;
+    }
+}
+
+/// Download the latest release of the selected channel
+/// `allow_downgrade` accepts a release older than the installed version, e.g. going back to stable
+/// from a newer beta
 #[tauri::command]
-pub async fn download_update(app: AppHandle) -> Result<(), String> {
-    let updater = app.updater().map_err(|e| e.to_string())?;
-    let app_handle = app.clone();
-    let app_handle_complete = app.clone();
-    let app_for_store = app.clone();
+pub async fn download_update(app: AppHandle, allow_downgrade: Option<bool>) -> Result<(), String> {
+    let updater = channel_updater(&app, &selected_channel(), allow_downgrade.unwrap_or(false))?;
 
     let update = updater
         .check()
@@ -58,83 +173,171 @@ pub async fn download_update(app: AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Failed to check for updates: {}", e))?
         .ok_or_else(|| "No update available".to_string())?;
 
-    let _ = app.emit("update:downloading", ());
+    download_and_store(&app, update, false).await
+}
+
+/// Download `update` into `DownloadedUpdateBytes`
+/// Emits `updater:download_started`, throttled `updater:download_progress` and
+/// `updater:download_finished`; `cancel_update_download` stops it early
+pub(crate) async fn download_and_store(app: &AppHandle, update: Update, background: bool) -> Result<(), String> {
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    {
+        let mut cancel = DOWNLOAD_CANCEL.lock().map_err(|e| e.to_string())?;
+        if cancel.is_some() {
+            return Err("An update is already downloading".to_string());
+        }
+        *cancel = Some(cancel_tx);
+    }
+
+    // Dropping the download future on cancel closes the connection
+    let result = tokio::select! {
+        result = download_bytes(app, &update, background) => result,
+        _ = cancel_rx => Err(DOWNLOAD_CANCELLED.to_string()),
+    };
+    if let Ok(mut cancel) = DOWNLOAD_CANCEL.lock() {
+        *cancel = None;
+    }
+    let (bytes, expected_len) = result?;
+
+    let _ = app.emit(
+        "updater:download_finished",
+        serde_json::json!({ "version": update.version, "bytes": bytes.len(), "background": background }),
+    );
+
+    let state = app.state::<DownloadedUpdateBytes>();
+    let mut stored_bytes = state.0.lock().map_err(|e| e.to_string())?;
+    *stored_bytes = Some(DownloadedUpdate {
+        update,
+        bytes,
+        expected_len,
+    });
+
+    Ok(())
+}
+
+/// The update's bytes and the size the server announced
+async fn download_bytes(app: &AppHandle, update: &Update, background: bool) -> Result<(Vec<u8>, Option<u64>), String> {
+    let _ = app.emit(
+        "updater:download_started",
+        serde_json::json!({ "version": update.version, "background": background }),
+    );
 
     // Throttle progress events to prevent overwhelming the frontend
-    let last_emit = std::sync::Mutex::new(Instant::now());
-    let last_percent = AtomicU64::new(0);
+    let mut last_emit = Instant::now();
+    let mut last_percent = 0;
     let throttle_duration = Duration::from_millis(100); // Emit at most every 100ms
+    let mut downloaded: u64 = 0;
+    let mut expected_len = None;
 
     let bytes = update
         .download(
-            |downloaded, total| {
-                let total_bytes = total.unwrap_or(0);
+            |chunk_length, content_length| {
+                // The plugin reports each chunk, not the running total
+                downloaded += chunk_length as u64;
+                expected_len = content_length;
+                let total_bytes = content_length.unwrap_or(0);
                 let percent = if total_bytes > 0 {
-                    ((downloaded as f64 / total_bytes as f64) * 100.0) as u32
+                    ((downloaded as f64 / total_bytes as f64) * 100.0).min(100.0) as u32
                 } else {
                     0
                 };
 
-                // Check if we should emit this progress update
-                let should_emit = {
-                    let mut last = last_emit.lock().unwrap();
-                    let now = Instant::now();
-                    let prev_percent = last_percent.load(Ordering::Relaxed);
-
-                    // Emit if: enough time has passed OR percent changed significantly
-                    if now.duration_since(*last) >= throttle_duration || percent as u64 != prev_percent {
-                        *last = now;
-                        last_percent.store(percent as u64, Ordering::Relaxed);
-                        true
-                    } else {
-                        false
-                    }
-                };
-
-                if should_emit {
+                // Emit if: enough time has passed OR percent changed
+                let now = Instant::now();
+                if now.duration_since(last_emit) >= throttle_duration || percent != last_percent {
+                    last_emit = now;
+                    last_percent = percent;
                     let progress = UpdateProgress {
-                        downloaded: downloaded as u64,
+                        downloaded,
                         total: total_bytes,
                         percent,
+                        background,
                     };
-                    let _ = app_handle.emit("update:progress", progress);
+                    let _ = app.emit("updater:download_progress", progress);
                 }
             },
-            || {
-                let _ = app_handle_complete.emit("update:downloaded", ());
-            },
+            || {},
         )
         .await
         .map_err(|e| format!("Failed to download update: {}", e))?;
 
-    let state = app_for_store.state::<DownloadedUpdateBytes>();
-    let mut stored_bytes = state.0.lock().map_err(|e| e.to_string())?;
-    *stored_bytes = Some(bytes);
+    Ok((bytes, expected_len))
+}
+
+/// Stop the update download in progress and drop any downloaded update
+#[tauri::command]
+pub fn cancel_update_download(app: AppHandle) -> Result<(), String> {
+    let cancel = DOWNLOAD_CANCEL.lock().map_err(|e| e.to_string())?.take();
+    if let Some(cancel) = cancel {
+        let _ = cancel.send(());
+        info!("Update download cancelled");
+    }
 
+    let state = app.state::<DownloadedUpdateBytes>();
+    *state.0.lock().map_err(|e| e.to_string())? = None;
     Ok(())
 }
 
+/// Check `bytes` against a release's minisign `signature` with the updater's public key
+pub(crate) fn verify_signature(app: &AppHandle, bytes: &[u8], signature: &str) -> Result<(), String> {
+    let pubkey = app
+        .config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|key| key.as_str())
+        .ok_or_else(|| "No updater public key configured".to_string())?;
+    let decode = |value: &str| {
+        BASE64
+            .decode(value)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| "Key or signature is not valid base64".to_string())
+    };
+    let public_key = PublicKey::decode(&decode(pubkey)?).map_err(|e| format!("Invalid public key: {}", e))?;
+    let signature = Signature::decode(&decode(signature)?).map_err(|e| format!("Invalid signature: {}", e))?;
+    public_key.verify(bytes, &signature, true).map_err(|e| e.to_string())
+}
+
+/// Check the downloaded bytes against the announced size and the release signature
+/// The plugin verifies the signature while downloading too; this catches bytes that changed in memory
+/// since, and a download cut short without the connection reporting an error
+fn verify_download(app: &AppHandle, downloaded: &DownloadedUpdate) -> Result<(), UpdateInstallError> {
+    let actual = downloaded.bytes.len() as u64;
+    if actual == 0 || downloaded.expected_len.is_some_and(|expected| expected != actual) {
+        return Err(UpdateInstallError::LengthMismatch {
+            expected: downloaded.expected_len,
+            actual,
+        });
+    }
+    verify_signature(app, &downloaded.bytes, &downloaded.update.signature)
+        .map_err(|message| UpdateInstallError::SignatureInvalid { message })
+}
+
 #[tauri::command]
-pub async fn install_update(app: AppHandle) -> Result<(), String> {
-    let bytes = {
+pub async fn install_update(app: AppHandle) -> Result<(), UpdateInstallError> {
+    let downloaded = {
         let state = app.state::<DownloadedUpdateBytes>();
-        let mut stored_bytes = state.0.lock().map_err(|e| e.to_string())?;
-        stored_bytes
-            .take()
-            .ok_or_else(|| "No downloaded update available. Please download first.".to_string())?
+        let mut stored_bytes = state
+            .0
+            .lock()
+            .map_err(|e| UpdateInstallError::InstallFailed { message: e.to_string() })?;
+        stored_bytes.take().ok_or(UpdateInstallError::NotDownloaded)?
     };
 
-    let updater = app.updater().map_err(|e| e.to_string())?;
+    // Bytes that fail verification are dropped, so the next attempt downloads them again
+    verify_download(&app, &downloaded)?;
 
-    let update = updater
-        .check()
-        .await
-        .map_err(|e| format!("Failed to check for updates: {}", e))?
-        .ok_or_else(|| "No update available".to_string())?;
+    // Written before installing, as the installer may exit the app
+    let current_version = app.package_info().version.to_string();
+    let rollback_before = update_rollback::prepare(&current_version, &downloaded.update)
+        .map_err(|message| UpdateInstallError::InstallFailed { message })?;
 
-    update
-        .install(bytes)
-        .map_err(|e| format!("Failed to install update: {}", e))?;
+    if let Err(e) = downloaded.update.install(downloaded.bytes) {
+        update_rollback::restore(&rollback_before);
+        return Err(UpdateInstallError::InstallFailed { message: e.to_string() });
+    }
 
     Ok(())
 }
@@ -144,3 +347,22 @@ pub async fn install_update(app: AppHandle) -> Result<(), String> {
 pub fn get_current_version(app: AppHandle) -> String {
     app.package_info().version.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_channel() {
+        assert_eq!(build_channel("1.7.0-beta.1"), "beta");
+        assert_eq!(build_channel("1.6.2"), "stable");
+        assert_eq!(build_channel("not a version"), "stable");
+    }
+
+    #[test]
+    fn test_is_older() {
+        assert!(is_older("1.6.2", "1.7.0-beta.1"));
+        assert!(!is_older("1.7.0", "1.7.0-beta.1"));
+        assert!(!is_older("1.6.2", "1.6.2"));
+    }
+}
@@ -1,14 +1,24 @@
 // Valorant command handlers - real implementation with file storage
 use crate::file_manager::{read_json_file, write_json_file};
-use crate::models::{RiotAuthCookies, ValorantItem, ValorantStore};
+use crate::commands::auth::{load_riot_accounts, save_riot_accounts};
+use crate::models::{
+    BundleHistoryEntry, RiotAuthCookies, SkinAppearanceStats, SkinMetadata, StoreStreaks, ValorantBundle,
+    ValorantItem, ValorantNightMarket, ValorantStore, ValorantWishlistHit, ValorantWishlistItem,
+};
 use crate::process_manager::spawn_python_worker_async;
-use crate::utils::{get_auth_json_path, get_valorant_store_json_path};
+use crate::utils::{
+    get_account_valorant_store_json_path, get_valorant_store_json_path, get_valorant_wishlist_json_path,
+};
+use crate::valorant::{content_cache, store_scheduler, store_stats};
 use chrono::{FixedOffset, TimeZone, Timelike, Utc};
-use log::debug;
+use log::{debug, info, warn};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
 
 /// Get the start time of the current store rotation (8AM GMT+8)
 /// Store resets at 8AM GMT+8 daily, so each rotation is 8AM to next 8AM
-fn get_current_rotation_start() -> chrono::DateTime<Utc> {
+pub(crate) fn get_current_rotation_start() -> chrono::DateTime<Utc> {
     let gmt8 = FixedOffset::east_opt(8 * 3600).unwrap();
     let now_gmt8 = Utc::now().with_timezone(&gmt8);
 
@@ -29,18 +39,49 @@ fn get_current_rotation_start() -> chrono::DateTime<Utc> {
     rotation_start_gmt8.with_timezone(&Utc)
 }
 
-/// Check if auto-refresh should happen
-/// Returns true if no store data exists or last check was before current rotation
-#[tauri::command]
-pub fn should_auto_refresh_store() -> Result<bool, String> {
-    let path = get_valorant_store_json_path();
+/// Store history file of an account (the active one when None); Ok(None) when no account is set up
+fn store_history_path(account_id: Option<&str>) -> Result<Option<PathBuf>, String> {
+    let accounts = load_riot_accounts()?;
+    match accounts.resolve(account_id) {
+        Some(account) => Ok(Some(get_account_valorant_store_json_path(&account.id))),
+        None => match account_id {
+            Some(id) => Err(format!("Unknown Valorant account: {}", id)),
+            None => Ok(None),
+        },
+    }
+}
 
-    // If no store file exists, need to refresh
-    if !path.exists() {
-        return Ok(true);
+fn read_store_history(account_id: Option<&str>) -> Result<Vec<ValorantStore>, String> {
+    match store_history_path(account_id)? {
+        Some(path) if path.exists() => read_json_file(&path),
+        _ => Ok(vec![]),
     }
+}
 
-    let stores: Vec<ValorantStore> = read_json_file(&path)?;
+/// Move the single-account store history to the first account added
+pub(crate) fn adopt_legacy_store_history(account_id: &str) {
+    let legacy_path = get_valorant_store_json_path();
+    let account_path = get_account_valorant_store_json_path(account_id);
+    if !legacy_path.exists() || account_path.exists() {
+        return;
+    }
+
+    let stores: Vec<ValorantStore> = read_json_file(&legacy_path).unwrap_or_default();
+    if let Err(e) = write_json_file(&account_path, &stores) {
+        warn!("Failed to move store history to account {}: {}", account_id, e);
+        return;
+    }
+    let _ = std::fs::remove_file(&legacy_path);
+    info!("Moved {} stored rotations to account {}", stores.len(), account_id);
+}
+
+/// Check if auto-refresh should happen
+/// Returns true if no store data exists, the last check was before the current rotation, or a
+/// Night Market seen in the last check has since ended. An active Night Market alone never forces
+/// a refetch, since its offers stay the same until it expires
+#[tauri::command]
+pub fn should_auto_refresh_store(account_id: Option<String>) -> Result<bool, String> {
+    let stores = read_store_history(account_id.as_deref())?;
 
     // If no stores, need to refresh
     let last_store = match stores.last() {
@@ -57,55 +98,94 @@ pub fn should_auto_refresh_store() -> Result<bool, String> {
     // Get the current rotation start time
     let rotation_start = get_current_rotation_start();
 
+    // Night Market that ended after the last check
+    let night_market_ended = last_store
+        .night_market
+        .as_ref()
+        .and_then(|nm| nm.expires_at())
+        .is_some_and(|expires_at| last_checked < expires_at && expires_at <= Utc::now());
+
     // If last check was before the current rotation started, need to refresh
-    let should_refresh = last_checked < rotation_start;
+    let should_refresh = last_checked < rotation_start || night_market_ended;
 
     debug!(
-        "Auto-refresh check: last_checked={}, rotation_start={}, should_refresh={}",
-        last_checked, rotation_start, should_refresh
+        "Auto-refresh check: last_checked={}, rotation_start={}, night_market_ended={}, should_refresh={}",
+        last_checked, rotation_start, night_market_ended, should_refresh
     );
 
     Ok(should_refresh)
 }
 
-/// Get the most recent Valorant store data from the JSON file
+/// Get the most recent Valorant store data of an account (the active one when None)
 #[tauri::command]
-pub fn get_valorant_store() -> Result<Option<ValorantStore>, String> {
-    let path = get_valorant_store_json_path();
-
-    if !path.exists() {
-        return Ok(None);
-    }
-
-    let stores: Vec<ValorantStore> = read_json_file(&path)?;
-
-    // Return the most recent store (last item)
-    Ok(stores.last().cloned())
+pub fn get_valorant_store(account_id: Option<String>) -> Result<Option<ValorantStore>, String> {
+    let stores = read_store_history(account_id.as_deref())?;
+
+    // Return the most recent store (last item), without a Night Market or bundles that have ended,
+    // and with skin names and images filled in from the content cache
+    Ok(stores.last().cloned().map(|mut store| {
+        if store.night_market.as_ref().is_some_and(|nm| nm.is_expired()) {
+            store.night_market = None;
+        }
+        let mut bundles = store
+            .bundle
+            .take()
+            .into_iter()
+            .chain(store.other_bundles.drain(..))
+            .filter(|b| !b.is_expired())
+            .collect::<Vec<_>>()
+            .into_iter();
+        store.bundle = bundles.next();
+        store.other_bundles = bundles.collect();
+        content_cache::apply_to_store(&mut store, false);
+        store
+    }))
 }
 
 /// Check the Valorant store (fetches fresh data)
 #[tauri::command]
-pub async fn check_valorant_store(region: Option<String>) -> Result<ValorantStore, String> {
-    let region = region.unwrap_or_else(|| "na".to_string());
+pub async fn check_valorant_store(
+    app: AppHandle,
+    region: Option<String>,
+    account_id: Option<String>,
+) -> Result<ValorantStore, String> {
+    fetch_valorant_store(app, region, account_id).await
+}
+
+/// Fetch an account's store through the worker and save it to that account's history
+/// Region and account default to the active account's. Shared by `check_valorant_store` and the
+/// store reset scheduler
+pub(crate) async fn fetch_valorant_store(
+    app: AppHandle,
+    region: Option<String>,
+    account_id: Option<String>,
+) -> Result<ValorantStore, String> {
+    // Get the account's auth cookies (run blocking file I/O on spawn_blocking)
+    let account = tokio::task::spawn_blocking(move || {
+        load_riot_accounts().map(|accounts| accounts.resolve(account_id.as_deref()).cloned())
+    })
+    .await
+    .map_err(|e| format!("Failed to read auth cookies: {}", e))??
+    .ok_or_else(|| "No Riot account logged in".to_string())?;
 
-    debug!("Checking Valorant store for region: {}", region);
+    let region = region.unwrap_or_else(|| account.region.clone());
+    debug!("Checking Valorant store for account {} in region: {}", account.id, region);
 
-    // Get stored auth cookies (run blocking file I/O on spawn_blocking)
-    let auth_path = get_auth_json_path();
-    let auth_cookies: Option<RiotAuthCookies> = tokio::task::spawn_blocking(move || {
-        if auth_path.exists() {
-            read_json_file(&auth_path).ok()
-        } else {
-            None
+    // Bring the skin catalog up to date first, so the worker resolves offers from it
+    let content_cache_path = tokio::task::spawn_blocking(|| {
+        if let Err(e) = content_cache::refresh_if_needed() {
+            warn!("Valorant content catalog refresh failed: {}", e);
         }
+        content_cache::catalog_path()
     })
     .await
-    .map_err(|e| format!("Failed to read auth cookies: {}", e))?;
+    .map_err(|e| format!("Failed to refresh content catalog: {}", e))?;
 
     // Prepare worker input with cookies
     let worker_input = serde_json::json!({
         "region": region,
-        "cookies": auth_cookies
+        "cookies": account.cookies,
+        "content_cache_path": content_cache_path
     });
 
     // Spawn the Python worker asynchronously (non-blocking)
@@ -142,6 +222,8 @@ pub async fn check_valorant_store(region: Option<String>) -> Result<ValorantStor
                             .and_then(|v| v.as_str())
                             .unwrap_or("skin")
                             .to_string(),
+                        uuid: item.get("uuid").and_then(|v| v.as_str()).map(String::from),
+                        local_image: None,
                     })
                 })
                 .collect()
@@ -152,17 +234,85 @@ pub async fn check_valorant_store(region: Option<String>) -> Result<ValorantStor
         .get("is_real_data")
         .and_then(|v| v.as_bool());
 
+    let expires_at = result
+        .get("expires_at")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    let night_market = result
+        .get("night_market")
+        .filter(|v| !v.is_null())
+        .and_then(|v| match serde_json::from_value::<ValorantNightMarket>(v.clone()) {
+            Ok(nm) => Some(nm),
+            Err(e) => {
+                debug!("Ignoring malformed Night Market data: {}", e);
+                None
+            }
+        });
+
+    let mut bundles = result
+        .get("bundles")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|b| match serde_json::from_value::<ValorantBundle>(b.clone()) {
+                    Ok(bundle) => Some(bundle),
+                    Err(e) => {
+                        debug!("Ignoring malformed bundle data: {}", e);
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+        .into_iter();
+    let bundle = bundles.next();
+    let other_bundles: Vec<ValorantBundle> = bundles.collect();
+
     let store = ValorantStore {
         date,
         items,
         checked_at,
         is_real_data,
+        expires_at,
+        night_market,
+        bundle,
+        other_bundles,
+        wishlist_hits: vec![],
     };
 
+    // Download item images so this store still shows them offline
+    let mut store = tokio::task::spawn_blocking(move || {
+        let mut store = store;
+        content_cache::apply_to_store(&mut store, true);
+        store
+    })
+    .await
+    .map_err(|e| format!("Failed to cache store images: {}", e))?;
+
+    let wishlist = tokio::task::spawn_blocking(get_valorant_wishlist)
+        .await
+        .map_err(|e| format!("Failed to read wishlist: {}", e))??;
+    store.wishlist_hits = find_wishlist_hits(&store, &wishlist);
+
+    let refreshed_cookies: Option<RiotAuthCookies> = result
+        .get("refreshed_cookies")
+        .filter(|v| !v.is_null())
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let player_name = result
+        .get("player_name")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
     // Save to history (run blocking file I/O on spawn_blocking)
+    // Returns the hits that today's earlier checks hadn't already reported
     let store_clone = store.clone();
-    tokio::task::spawn_blocking(move || -> Result<(), String> {
-        let path = get_valorant_store_json_path();
+    let new_hits = tokio::task::spawn_blocking(move || -> Result<Vec<ValorantWishlistHit>, String> {
+        if refreshed_cookies.is_some() || player_name.is_some() {
+            update_account_after_check(&account.id, refreshed_cookies, player_name)?;
+        }
+
+        let path = get_account_valorant_store_json_path(&account.id);
         let mut stores: Vec<ValorantStore> = if path.exists() {
             read_json_file(&path)?
         } else {
@@ -173,6 +323,13 @@ pub async fn check_valorant_store(region: Option<String>) -> Result<ValorantStor
         let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
         let existing_index = stores.iter().position(|s| s.date == today);
 
+        let new_hits: Vec<ValorantWishlistHit> = store_clone
+            .wishlist_hits
+            .iter()
+            .filter(|hit| !existing_index.is_some_and(|idx| stores[idx].wishlist_hits.contains(hit)))
+            .cloned()
+            .collect();
+
         match existing_index {
             Some(idx) => {
                 // Update existing entry for today
@@ -183,27 +340,184 @@ pub async fn check_valorant_store(region: Option<String>) -> Result<ValorantStor
                 stores.push(store_clone);
             }
         }
+        store_stats::compact_history(&mut stores, chrono::Utc::now().date_naive());
 
         write_json_file(&path, &stores)?;
         debug!("Valorant store checked and saved");
-        Ok(())
+        Ok(new_hits)
     })
     .await
     .map_err(|e| format!("Failed to save store: {}", e))??;
 
+    notify_wishlist_hits(&app, &new_hits);
+    // The auto-refresh timer waits for the new expiry from here on
+    store_scheduler::rearm();
+
     Ok(store)
 }
 
-/// Get store history
+/// Save cookies the worker's reauth renewed, and the account's Riot ID
+/// Re-reads the accounts file and only touches this account, so other accounts' cookies are kept
+fn update_account_after_check(
+    account_id: &str,
+    refreshed_cookies: Option<RiotAuthCookies>,
+    player_name: Option<String>,
+) -> Result<(), String> {
+    let mut accounts = load_riot_accounts()?;
+    let Some(account) = accounts.find_mut(account_id) else {
+        // Logged out while the check was running
+        return Ok(());
+    };
+
+    if let Some(refreshed) = refreshed_cookies {
+        let cookies = &mut account.cookies;
+        cookies.tdid = refreshed.tdid.or(cookies.tdid.take());
+        cookies.clid = refreshed.clid.or(cookies.clid.take());
+        cookies.csid = refreshed.csid.or(cookies.csid.take());
+        cookies.ssid = refreshed.ssid.or(cookies.ssid.take());
+    }
+    if player_name.is_some() {
+        account.name = player_name;
+    }
+
+    save_riot_accounts(&accounts)
+}
+
+/// Wishlist entries offered in the daily rotation or the Night Market
+fn find_wishlist_hits(store: &ValorantStore, wishlist: &[ValorantWishlistItem]) -> Vec<ValorantWishlistHit> {
+    let daily = store
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item.uuid.as_deref(), item.price, false));
+    let night_market = store
+        .night_market
+        .iter()
+        .flat_map(|nm| nm.items.iter())
+        .map(|item| (item.name.as_str(), item.uuid.as_deref(), item.final_price, true));
+
+    daily
+        .chain(night_market)
+        .filter_map(|(name, uuid, price, night_market)| {
+            let wanted = wishlist.iter().find(|w| w.matches(name, uuid))?;
+            Some(ValorantWishlistHit {
+                wishlist_item_id: wanted.id.clone(),
+                name: name.to_string(),
+                price,
+                night_market,
+            })
+        })
+        .collect()
+}
+
+/// Emit `valorant:wishlist_hit` for each hit and show one notification listing them
+fn notify_wishlist_hits(app: &AppHandle, hits: &[ValorantWishlistHit]) {
+    if hits.is_empty() {
+        return;
+    }
+    for hit in hits {
+        let _ = app.emit("valorant:wishlist_hit", hit);
+    }
+
+    let body = hits
+        .iter()
+        .map(|hit| {
+            let where_offered = if hit.night_market { " (Night Market)" } else { "" };
+            format!("{} - {} VP{}", hit.name, hit.price, where_offered)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Wishlisted skin in your Valorant store")
+        .body(body)
+        .show()
+    {
+        debug!("Failed to show wishlist notification: {}", e);
+    }
+}
+
+/// Catalog entry of a skin, skin level or chroma UUID (name, tier, image, video)
+/// The image is downloaded into the local cache on first request
 #[tauri::command]
-pub fn get_store_history(limit: Option<u32>) -> Result<Vec<ValorantStore>, String> {
-    let path = get_valorant_store_json_path();
+pub async fn get_skin_metadata(uuid: String) -> Result<Option<SkinMetadata>, String> {
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = content_cache::refresh_if_needed() {
+            warn!("Valorant content catalog refresh failed: {}", e);
+        }
+        content_cache::skin_metadata(&uuid, true)
+    })
+    .await
+    .map_err(|e| format!("Failed to read skin metadata: {}", e))
+}
+
+/// Get the skin wishlist
+#[tauri::command]
+pub fn get_valorant_wishlist() -> Result<Vec<ValorantWishlistItem>, String> {
+    let path = get_valorant_wishlist_json_path();
 
     if !path.exists() {
         return Ok(vec![]);
     }
 
-    let mut stores: Vec<ValorantStore> = read_json_file(&path)?;
+    read_json_file(&path)
+}
+
+/// Add a skin to the wishlist by skin level UUID, or by a name to look for in offer names
+#[tauri::command]
+pub fn add_valorant_wishlist_item(skin_name_or_uuid: String) -> Result<ValorantWishlistItem, String> {
+    let query = skin_name_or_uuid.trim();
+    if query.is_empty() {
+        return Err("Skin name or UUID is required".to_string());
+    }
+
+    let (skin_uuid, name) = match uuid::Uuid::parse_str(query) {
+        Ok(uuid) => (Some(uuid.to_string()), None),
+        Err(_) => (None, Some(query.to_string())),
+    };
+
+    let mut wishlist = get_valorant_wishlist()?;
+    let duplicate = wishlist.iter().any(|w| match (&skin_uuid, &name) {
+        (Some(uuid), _) => w.skin_uuid.as_ref() == Some(uuid),
+        (None, Some(name)) => w.name.as_ref().is_some_and(|n| n.eq_ignore_ascii_case(name)),
+        (None, None) => false,
+    });
+    if duplicate {
+        return Err(format!("{} is already on the wishlist", query));
+    }
+
+    let item = ValorantWishlistItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        skin_uuid,
+        name,
+        added_at: Utc::now().to_rfc3339(),
+    };
+    wishlist.push(item.clone());
+    write_json_file(&get_valorant_wishlist_json_path(), &wishlist)?;
+
+    Ok(item)
+}
+
+/// Remove a wishlist entry
+#[tauri::command]
+pub fn remove_valorant_wishlist_item(id: String) -> Result<(), String> {
+    let mut wishlist = get_valorant_wishlist()?;
+    let before = wishlist.len();
+    wishlist.retain(|w| w.id != id);
+    if wishlist.len() == before {
+        return Err(format!("Wishlist item not found: {}", id));
+    }
+
+    write_json_file(&get_valorant_wishlist_json_path(), &wishlist)
+}
+
+/// Get store history of an account (the active one when None)
+#[tauri::command]
+pub fn get_store_history(
+    limit: Option<u32>,
+    account_id: Option<String>,
+) -> Result<Vec<ValorantStore>, String> {
+    let mut stores = read_store_history(account_id.as_deref())?;
 
     // Sort by date descending (most recent first)
     stores.sort_by(|a, b| b.date.cmp(&a.date));
@@ -216,3 +530,57 @@ pub fn get_store_history(limit: Option<u32>) -> Result<Vec<ValorantStore>, Strin
 
     Ok(stores)
 }
+
+/// Featured bundles seen in the store history, most recently seen first
+#[tauri::command]
+pub fn get_bundle_history(
+    limit: Option<u32>,
+    account_id: Option<String>,
+) -> Result<Vec<BundleHistoryEntry>, String> {
+    let mut stores = read_store_history(account_id.as_deref())?;
+    stores.sort_by(|a, b| a.date.cmp(&b.date));
+
+    let mut entries: Vec<BundleHistoryEntry> = Vec::new();
+    for store in &stores {
+        for bundle in store.bundle.iter().chain(store.other_bundles.iter()) {
+            match entries.iter_mut().find(|e| e.bundle.uuid == bundle.uuid) {
+                Some(entry) => {
+                    entry.bundle = bundle.clone();
+                    entry.last_seen = store.date.clone();
+                }
+                None => entries.push(BundleHistoryEntry {
+                    bundle: bundle.clone(),
+                    first_seen: store.date.clone(),
+                    last_seen: store.date.clone(),
+                }),
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    entries.truncate(limit.unwrap_or(30) as usize);
+
+    Ok(entries)
+}
+
+/// How often a skin (name or UUID) has shown up in the daily store
+#[tauri::command]
+pub fn get_skin_appearance_stats(
+    skin: String,
+    account_id: Option<String>,
+) -> Result<SkinAppearanceStats, String> {
+    if skin.trim().is_empty() {
+        return Err("Skin name or UUID is required".to_string());
+    }
+    Ok(store_stats::skin_appearance_stats(&read_store_history(account_id.as_deref())?, &skin))
+}
+
+/// Streaks and totals over the store history
+#[tauri::command]
+pub fn get_store_streaks(account_id: Option<String>) -> Result<StoreStreaks, String> {
+    Ok(store_stats::store_streaks(
+        &read_store_history(account_id.as_deref())?,
+        &get_valorant_wishlist()?,
+        Utc::now().date_naive(),
+    ))
+}
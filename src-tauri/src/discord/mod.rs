@@ -1,3 +1,4 @@
 pub mod presence;
+pub mod template;
 
 pub use presence::DiscordPresenceManager;
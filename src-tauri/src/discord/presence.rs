@@ -1,18 +1,107 @@
+use crate::commands::settings::get_settings;
 use crate::models::gaming::BottleneckType;
+use crate::models::{
+    CurrentPresence, DiscordConnectionStatus, DiscordGameApp, PresenceActivity, PresenceButton,
+    PresenceMode, Settings,
+};
+use crate::utils::settings_validation::{presence_button_problem, MAX_PRESENCE_BUTTONS};
 use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
+use log::{debug, info, warn};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc, Mutex,
 };
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+use super::template::{self, PresenceContext};
 
 const DISCORD_CLIENT_ID: &str = "1461387162720272445";
 
+const DEFAULT_LARGE_IMAGE: &str = "atlas_logo";
+
+/// Shown instead of the templates while the player is idle
+const AFK_DETAILS_TEMPLATE: &str = "AFK in {game}";
+const AFK_STATE: &str = "💤 - Away";
+
+/// Shown for a game hidden from presence, when the settings ask for a generic line
+const HIDDEN_GAME_DETAILS: &str = "Playing a game";
+
+/// How long a custom presence may be set for, in minutes
+const CUSTOM_PRESENCE_MINUTES: std::ops::RangeInclusive<u32> = 1..=1440;
+
+/// Reconnect attempts start this far apart and double up to the max while Discord stays away
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_secs(5);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(300);
+
+/// How often a live connection is checked by sending the current activity again
+/// Nothing is sent while the presence is unchanged, so a closed Discord would go unnoticed otherwise
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Discord application IDs are snowflakes
+const APP_ID_DIGITS: std::ops::RangeInclusive<usize> = 17..=20;
+
+/// Longest asset key or image URL accepted for a game
+pub const MAX_LARGE_IMAGE_CHARS: usize = 256;
+
+/// Game the gaming presence is shown for, with the values it was last rendered from
+struct GamingPresence {
+    game: String,
+    started_at: i64,
+    bottleneck: BottleneckType,
+    cpu_percent: Option<f32>,
+    gpu_percent: Option<f32>,
+    app: Option<DiscordGameApp>,
+    /// Player is idle; see `gaming::idle`
+    afk: bool,
+    /// Game is hidden from presence, so neither its name nor its Discord application is shown
+    hidden: bool,
+}
+
+/// Text set with `set_custom_presence`, shown instead of the automatic presence until it ends
+struct CustomPresence {
+    details: Option<String>,
+    state: Option<String>,
+    /// Unix seconds
+    until: i64,
+}
+
+/// Connection to a game's own Discord application
+struct GameClient {
+    app_id: String,
+    client: DiscordIpcClient,
+}
+
+/// Reconnect state kept for the watchdog and `is_discord_connected`
+struct ConnectionHealth {
+    last_error: Option<String>,
+    next_retry: Option<Instant>,
+    backoff: Duration,
+    last_check: Instant,
+}
+
 /// Thread-safe Discord Rich Presence manager
+/// Games with their own Discord application are shown through a second client for that
+/// application. It stays connected after the game ends, so starting the same game again doesn't
+/// reconnect; only one client shows an activity at a time
 pub struct DiscordPresenceManager {
     client: Arc<Mutex<Option<DiscordIpcClient>>>,
+    game_client: Mutex<Option<GameClient>>,
+    /// Whether the activity is currently shown through `game_client`
+    game_client_showing: AtomicBool,
     is_enabled: Arc<AtomicBool>,
     is_connected: Arc<AtomicBool>,
+    gaming: Mutex<Option<GamingPresence>>,
+    /// Overrides the gaming and idle presence while set; games are still tracked underneath
+    custom: Mutex<Option<CustomPresence>>,
+    /// Bumped when a game starts or ends, so a client switch finishing late doesn't send stale presence
+    epoch: AtomicU64,
+    /// Activity last sent, so an unchanged one isn't sent again
+    last_sent: Mutex<Option<PresenceActivity>>,
+    health: Mutex<ConnectionHealth>,
+    /// Set by `start_watchdog`; connection changes are emitted through it
+    app: Mutex<Option<AppHandle>>,
 }
 
 impl Default for DiscordPresenceManager {
@@ -25,44 +114,201 @@ impl DiscordPresenceManager {
     pub fn new() -> Self {
         Self {
             client: Arc::new(Mutex::new(None)),
+            game_client: Mutex::new(None),
+            game_client_showing: AtomicBool::new(false),
             is_enabled: Arc::new(AtomicBool::new(false)),
             is_connected: Arc::new(AtomicBool::new(false)),
+            gaming: Mutex::new(None),
+            custom: Mutex::new(None),
+            epoch: AtomicU64::new(0),
+            last_sent: Mutex::new(None),
+            health: Mutex::new(ConnectionHealth {
+                last_error: None,
+                next_retry: None,
+                backoff: RECONNECT_BACKOFF_MIN,
+                last_check: Instant::now(),
+            }),
+            app: Mutex::new(None),
         }
     }
 
     /// Connect to Discord
+    /// A failure is returned as is; use `enable` to keep retrying until Discord is running
     pub fn connect(&self) -> Result<(), String> {
         let mut client = DiscordIpcClient::new(DISCORD_CLIENT_ID);
 
-        client
-            .connect()
-            .map_err(|e| format!("Failed to connect to Discord: {}", e))?;
+        if let Err(e) = client.connect() {
+            let error = format!("Failed to connect to Discord: {}", e);
+            if let Ok(mut health) = self.health.lock() {
+                health.last_error = Some(error.clone());
+            }
+            return Err(error);
+        }
 
         if let Ok(mut c) = self.client.lock() {
             *c = Some(client);
         }
+        // Nothing has been sent on the new connection
+        if let Ok(mut last_sent) = self.last_sent.lock() {
+            *last_sent = None;
+        }
+        if let Ok(mut health) = self.health.lock() {
+            health.last_error = None;
+            health.next_retry = None;
+            health.backoff = RECONNECT_BACKOFF_MIN;
+            health.last_check = Instant::now();
+        }
 
         self.is_connected.store(true, Ordering::SeqCst);
         self.is_enabled.store(true, Ordering::SeqCst);
+        self.notify_connection_changed();
 
-        self.set_idle_presence()?;
+        // Shows the running game, or idle, through the right application
+        self.apply(self.epoch.load(Ordering::SeqCst))
+    }
 
-        Ok(())
+    /// Turn presence on; when Discord isn't reachable the watchdog keeps retrying
+    pub fn enable(&self) -> Result<(), String> {
+        self.is_enabled.store(true, Ordering::SeqCst);
+        self.connect().inspect_err(|_| self.schedule_retry())
     }
 
     /// Disconnect from Discord
     pub fn disconnect(&self) -> Result<(), String> {
         self.is_enabled.store(false, Ordering::SeqCst);
+        self.close_clients();
+        if let Ok(mut health) = self.health.lock() {
+            health.next_retry = None;
+            health.backoff = RECONNECT_BACKOFF_MIN;
+        }
+        self.notify_connection_changed();
+        Ok(())
+    }
+
+    /// Connection state for the settings view
+    pub fn connection_status(&self) -> DiscordConnectionStatus {
+        let (last_error, retry_in_seconds) = match self.health.lock() {
+            Ok(health) => (
+                health.last_error.clone(),
+                health
+                    .next_retry
+                    .map(|at| at.saturating_duration_since(Instant::now()).as_secs()),
+            ),
+            Err(_) => (None, None),
+        };
+        DiscordConnectionStatus {
+            connected: self.is_connected(),
+            enabled: self.is_enabled(),
+            last_error,
+            retry_in_seconds,
+        }
+    }
+
+    /// Watch the connection in the background: a lost one is retried with backoff while presence
+    /// is enabled, and the current presence is applied again once Discord is back
+    pub fn start_watchdog(self: &Arc<Self>, app: AppHandle) {
+        if let Ok(mut handle) = self.app.lock() {
+            *handle = Some(app);
+        }
+        // Weak, so the thread ends with the manager
+        let manager = Arc::downgrade(self);
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_secs(1));
+            let Some(manager) = manager.upgrade() else {
+                return;
+            };
+            manager.expire_custom_presence();
+            if manager.is_enabled() {
+                manager.check_connection();
+            }
+        });
+    }
+
+    /// One watchdog tick
+    fn check_connection(&self) {
+        let now = Instant::now();
+        if !self.is_connected() {
+            let due = self
+                .health
+                .lock()
+                .map(|h| h.next_retry.is_none_or(|at| now >= at))
+                .unwrap_or(false);
+            if !due {
+                return;
+            }
+            match self.connect() {
+                Ok(()) => info!("Reconnected to Discord"),
+                Err(e) => {
+                    debug!("Discord reconnect failed: {}", e);
+                    self.schedule_retry();
+                }
+            }
+            return;
+        }
+
+        let check_due = self
+            .health
+            .lock()
+            .map(|h| now.duration_since(h.last_check) >= HEALTH_CHECK_INTERVAL)
+            .unwrap_or(false);
+        if check_due {
+            if let Ok(mut health) = self.health.lock() {
+                health.last_check = now;
+            }
+            if let Ok(mut last_sent) = self.last_sent.lock() {
+                *last_sent = None;
+            }
+            // A failed send marks the connection lost
+            let _ = self.send(&self.preview());
+        }
+    }
 
+    /// Plan the next reconnect attempt, doubling the wait each time
+    fn schedule_retry(&self) {
+        if let Ok(mut health) = self.health.lock() {
+            let wait = health.backoff;
+            health.next_retry = Some(Instant::now() + wait);
+            health.backoff = (wait * 2).min(RECONNECT_BACKOFF_MAX);
+        }
+        self.notify_connection_changed();
+    }
+
+    /// Discord went away (closed, restarted or crashed)
+    fn connection_lost(&self, error: &str) {
+        if !self.is_connected.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        warn!("Lost connection to Discord: {}", error);
+        self.close_clients();
+        if let Ok(mut health) = self.health.lock() {
+            health.last_error = Some(error.to_string());
+            health.backoff = RECONNECT_BACKOFF_MIN;
+        }
+        self.schedule_retry();
+    }
+
+    fn close_clients(&self) {
         if let Ok(mut client_guard) = self.client.lock() {
             if let Some(ref mut client) = *client_guard {
                 let _ = client.close();
             }
             *client_guard = None;
         }
-
+        if let Ok(mut game_client) = self.game_client.lock() {
+            if let Some(mut game) = game_client.take() {
+                let _ = game.client.close();
+            }
+        }
+        self.game_client_showing.store(false, Ordering::SeqCst);
         self.is_connected.store(false, Ordering::SeqCst);
-        Ok(())
+    }
+
+    fn notify_connection_changed(&self) {
+        if let Ok(app) = self.app.lock() {
+            if let Some(app) = app.as_ref() {
+                let _ = app.emit("discord:connection_changed", self.connection_status());
+            }
+        }
     }
 
     /// Check if Rich Presence is enabled
@@ -75,71 +321,329 @@ impl DiscordPresenceManager {
         self.is_connected.load(Ordering::SeqCst)
     }
 
-    /// Set idle presence
-    pub fn set_idle_presence(&self) -> Result<(), String> {
+    /// Show a game that just started, through its own Discord application when it has one
+    /// `started_at` is the session start in Unix seconds, which Discord counts the elapsed time from
+    /// A `hidden` game is shown as the settings' `presence_hidden_game_display` says instead
+    /// Blocks while that application's client connects
+    pub fn begin_game(
+        &self,
+        game_name: &str,
+        started_at: i64,
+        app: Option<DiscordGameApp>,
+        hidden: bool,
+    ) -> Result<(), String> {
+        let epoch = self.epoch.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Ok(mut gaming) = self.gaming.lock() {
+            *gaming = Some(GamingPresence {
+                game: game_name.to_string(),
+                started_at,
+                bottleneck: BottleneckType::Balanced,
+                cpu_percent: None,
+                gpu_percent: None,
+                app,
+                afk: false,
+                hidden,
+            });
+        }
         if !self.is_enabled() {
             return Ok(());
         }
 
-        self.set_presence("💤 - Idle", "Atlas", None)
+        self.apply(epoch)
     }
 
-    /// Update presence for gaming session with bottleneck status
+    /// Set idle presence
+    pub fn set_idle_presence(&self) -> Result<(), String> {
+        self.epoch.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut gaming) = self.gaming.lock() {
+            *gaming = None;
+        }
+
+        self.refresh()
+    }
+
+    /// Update presence for gaming session with bottleneck status and usage
+    /// The text comes from the templates in Settings, read on every call so edits show on the next update
+    /// `started_at` only applies when a different game starts; for the same game the timestamp
+    /// set first is kept, so Discord's elapsed counter doesn't restart
+    /// `hidden` is passed on every update, so a game added to the blocklist mid-session is hidden
     pub fn update_gaming_presence(
         &self,
         game_name: &str,
+        started_at: i64,
+        hidden: bool,
         bottleneck_type: &BottleneckType,
+        cpu_percent: Option<f32>,
+        gpu_percent: Option<f32>,
     ) -> Result<(), String> {
-        if !self.is_enabled() {
-            return Ok(());
+        {
+            let mut gaming = self.gaming.lock().map_err(|e| format!("Lock error: {}", e))?;
+            let (started_at, app, afk) = match gaming.take() {
+                Some(current) if current.game == game_name => {
+                    (current.started_at, current.app, current.afk)
+                }
+                _ => (started_at, None, false),
+            };
+            *gaming = Some(GamingPresence {
+                game: game_name.to_string(),
+                started_at,
+                bottleneck: bottleneck_type.clone(),
+                cpu_percent,
+                gpu_percent,
+                app,
+                afk,
+                hidden,
+            });
         }
 
-        let details = format!("Playing {}", game_name);
-        let state = bottleneck_to_status(bottleneck_type);
+        self.refresh()
+    }
 
-        self.set_presence(&state, &details, Some(get_current_timestamp()))
+    /// Show the game as AFK while the player is idle, and the templates again once they're back
+    pub fn set_afk(&self, afk: bool) -> Result<(), String> {
+        {
+            let mut gaming = self.gaming.lock().map_err(|e| format!("Lock error: {}", e))?;
+            match gaming.as_mut() {
+                Some(current) if current.afk != afk => current.afk = afk,
+                _ => return Ok(()),
+            }
+        }
+
+        self.refresh()
     }
 
-    fn set_presence(
+    /// Show fixed text for `duration_minutes`, whatever the game state
+    /// Games starting or ending meanwhile are tracked, and shown once it ends or is cleared
+    pub fn set_custom_presence(
         &self,
-        state: &str,
-        details: &str,
-        start_timestamp: Option<i64>,
+        details: Option<String>,
+        state: Option<String>,
+        duration_minutes: u32,
     ) -> Result<(), String> {
+        let details = custom_line("Details", details)?;
+        let state = custom_line("State", state)?;
+        if details.is_none() && state.is_none() {
+            return Err("Enter a details or a state line".to_string());
+        }
+        if !CUSTOM_PRESENCE_MINUTES.contains(&duration_minutes) {
+            return Err(format!(
+                "Duration must be between {} and {} minutes",
+                CUSTOM_PRESENCE_MINUTES.start(),
+                CUSTOM_PRESENCE_MINUTES.end()
+            ));
+        }
+
+        if let Ok(mut custom) = self.custom.lock() {
+            *custom = Some(CustomPresence {
+                details,
+                state,
+                until: get_current_timestamp() + duration_minutes as i64 * 60,
+            });
+        }
+        self.refresh()
+    }
+
+    /// Go back to the automatic presence
+    pub fn clear_custom_presence(&self) -> Result<(), String> {
+        let had_custom = self
+            .custom
+            .lock()
+            .map(|mut custom| custom.take().is_some())
+            .unwrap_or(false);
+        if !had_custom || !self.is_enabled() {
+            return Ok(());
+        }
+
+        // The game's own application may be needed again
+        self.apply(self.epoch.load(Ordering::SeqCst))
+    }
+
+    /// Return to the automatic presence once the custom one ran out; called by the watchdog
+    fn expire_custom_presence(&self) {
+        let expired = self
+            .custom
+            .lock()
+            .map(|custom| custom.as_ref().is_some_and(|c| c.until <= get_current_timestamp()))
+            .unwrap_or(false);
+        if expired {
+            info!("Custom Discord presence ended");
+            let _ = self.clear_custom_presence();
+        }
+    }
+
+    /// The presence the current state would show, with where it comes from
+    pub fn current(&self) -> CurrentPresence {
+        let now = get_current_timestamp();
+        let settings = get_settings().unwrap_or_default();
+        let custom = self.custom.lock().ok().and_then(|custom| {
+            let custom = custom.as_ref().filter(|c| c.until > now)?;
+            Some((custom_activity(&settings, custom), custom.until))
+        });
+
+        let (mode, activity, custom_until) = match custom {
+            Some((activity, until)) => (PresenceMode::Custom, activity, Some(until)),
+            None => match self.gaming.lock().ok().as_deref().and_then(|g| g.as_ref()) {
+                Some(gaming) => (PresenceMode::Gaming, gaming_activity(&settings, gaming), None),
+                None => (PresenceMode::Idle, idle_activity(&settings), None),
+            },
+        };
+        CurrentPresence {
+            mode,
+            activity,
+            custom_until,
+            shown: self.is_connected(),
+        }
+    }
+
+    /// The activity the current state would send, whether or not Discord is connected
+    pub fn preview(&self) -> PresenceActivity {
+        self.current().activity
+    }
+
+    /// Send the current activity again, e.g. after its templates or buttons changed
+    pub fn refresh(&self) -> Result<(), String> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        self.send(&self.preview())
+    }
+
+    /// Connect the client the current activity needs, then send the activity
+    /// Nothing is sent when a game started or ended since `epoch`; that call sends its own
+    fn apply(&self, epoch: u64) -> Result<(), String> {
+        let wanted = Some(self.preview().application_id).filter(|id| id != DISCORD_CLIENT_ID);
+
+        if let Some(app_id) = wanted {
+            let cached = self
+                .game_client
+                .lock()
+                .map(|g| g.as_ref().is_some_and(|c| c.app_id == app_id))
+                .unwrap_or(false);
+            if !cached {
+                // Connected without holding the locks, so presence updates aren't held up meanwhile
+                let mut client = DiscordIpcClient::new(&app_id);
+                match client.connect() {
+                    Ok(()) => {
+                        info!("Connected Discord application {}", app_id);
+                        if let Ok(mut game_client) = self.game_client.lock() {
+                            if let Some(mut previous) = game_client.take() {
+                                let _ = previous.client.close();
+                            }
+                            self.game_client_showing.store(false, Ordering::SeqCst);
+                            *game_client = Some(GameClient { app_id, client });
+                        }
+                    }
+                    // The activity falls back to Atlas's application
+                    Err(e) => warn!("Failed to connect Discord application {}: {}", app_id, e),
+                }
+            }
+        }
+
+        if self.epoch.load(Ordering::SeqCst) != epoch {
+            return Ok(());
+        }
+        self.send(&self.preview())
+    }
+
+    /// Send an activity unless it is the one sent last
+    /// It goes through the game's client when that is connected for its application, else through
+    /// Atlas's, and the activity on the other client is cleared
+    fn send(&self, presence: &PresenceActivity) -> Result<(), String> {
+        if let Ok(last_sent) = self.last_sent.lock() {
+            if last_sent.as_ref() == Some(presence) {
+                return Ok(());
+            }
+        }
+
         let mut client_guard = self
             .client
             .lock()
             .map_err(|e| format!("Lock error: {}", e))?;
+        let mut game_guard = self
+            .game_client
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
 
-        if let Some(ref mut client) = *client_guard {
-            let mut activity_builder = activity::Activity::new().state(state).details(details);
+        let on_game_client = game_guard
+            .as_ref()
+            .is_some_and(|g| g.app_id == presence.application_id);
+        // Asset keys belong to the game's application; image URLs work with any
+        let large_image = if on_game_client
+            || presence.application_id == DISCORD_CLIENT_ID
+            || presence.large_image.starts_with("https://")
+        {
+            presence.large_image.as_str()
+        } else {
+            DEFAULT_LARGE_IMAGE
+        };
 
-            if let Some(ts) = start_timestamp {
-                activity_builder =
-                    activity_builder.timestamps(activity::Timestamps::new().start(ts));
-            }
-
-            activity_builder = activity_builder.assets(
-                activity::Assets::new()
-                    .large_image("atlas_logo")
-                    .large_text("Atlas"),
-            );
+        let mut activity_builder = activity::Activity::new();
+        if let Some(state) = &presence.state {
+            activity_builder = activity_builder.state(state.as_str());
+        }
+        if let Some(details) = &presence.details {
+            activity_builder = activity_builder.details(details.as_str());
+        }
 
-            client
-                .set_activity(activity_builder)
-                .map_err(|e| format!("Failed to set activity: {}", e))?;
+        if let Some(ts) = presence.start_timestamp {
+            activity_builder =
+                activity_builder.timestamps(activity::Timestamps::new().start(ts));
         }
 
-        Ok(())
-    }
+        activity_builder = activity_builder.assets(
+            activity::Assets::new()
+                .large_image(large_image)
+                .large_text(presence.large_text.as_str()),
+        );
+
+        if !presence.buttons.is_empty() {
+            activity_builder = activity_builder.buttons(
+                presence
+                    .buttons
+                    .iter()
+                    .map(|b| activity::Button::new(b.label.as_str(), b.url.as_str()))
+                    .collect(),
+            );
+        }
 
-    #[allow(dead_code)] 
-    pub fn try_reconnect(&self) -> Result<(), String> {
-        if self.is_connected() {
+        let was_on_game_client = self.game_client_showing.load(Ordering::SeqCst);
+        let result = if on_game_client {
+            let result = match game_guard.as_mut() {
+                Some(game) => game.client.set_activity(activity_builder),
+                None => Ok(()),
+            };
+            if result.is_ok() && !was_on_game_client {
+                if let Some(client) = client_guard.as_mut() {
+                    let _ = client.clear_activity();
+                }
+            }
+            result
+        } else if let Some(client) = client_guard.as_mut() {
+            let result = client.set_activity(activity_builder);
+            if result.is_ok() && was_on_game_client {
+                if let Some(game) = game_guard.as_mut() {
+                    let _ = game.client.clear_activity();
+                }
+            }
+            result
+        } else {
             return Ok(());
+        };
+        drop(game_guard);
+        drop(client_guard);
+        if let Err(e) = result {
+            let error = format!("Failed to set activity: {}", e);
+            // The IPC pipe only fails like this once Discord is gone
+            self.connection_lost(&error);
+            return Err(error);
         }
+        self.game_client_showing.store(on_game_client, Ordering::SeqCst);
 
-        self.connect()
+        if let Ok(mut last_sent) = self.last_sent.lock() {
+            *last_sent = Some(presence.clone());
+        }
+
+        Ok(())
     }
 }
 
@@ -149,16 +653,132 @@ impl Drop for DiscordPresenceManager {
     }
 }
 
-/// Convert bottleneck type to emoji + word status
-fn bottleneck_to_status(bottleneck_type: &BottleneckType) -> String {
-    match bottleneck_type {
-        BottleneckType::Balanced => "✨ - Smooth".to_string(),
-        BottleneckType::CpuBound => "💪 - Pushing".to_string(),
-        BottleneckType::GpuBound => "🔥 - Maxed".to_string(),
-        BottleneckType::CpuThermal | BottleneckType::GpuThermal => "🌡️ - Toasty".to_string(),
-        BottleneckType::RamLimited => "📦 - Packed".to_string(),
-        BottleneckType::VramLimited => "🎨 - Full".to_string(),
+/// Check a game's Discord application before it is saved to the whitelist
+pub fn validate_game_app(app: &DiscordGameApp) -> Result<(), String> {
+    if let Some(app_id) = &app.app_id {
+        if !APP_ID_DIGITS.contains(&app_id.len()) || !app_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!(
+                "Discord application ID must be {} to {} digits",
+                APP_ID_DIGITS.start(),
+                APP_ID_DIGITS.end()
+            ));
+        }
     }
+    if let Some(image) = &app.large_image {
+        if image.trim().is_empty() || image.chars().count() > MAX_LARGE_IMAGE_CHARS {
+            return Err(format!("Discord image must be 1 to {} characters", MAX_LARGE_IMAGE_CHARS));
+        }
+        if image.contains("://") && !image.starts_with("https://") {
+            return Err("Discord image URL must be an https link".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn idle_activity(settings: &Settings) -> PresenceActivity {
+    PresenceActivity {
+        application_id: DISCORD_CLIENT_ID.to_string(),
+        details: Some("Atlas".to_string()),
+        state: Some("💤 - Idle".to_string()),
+        start_timestamp: None,
+        large_image: DEFAULT_LARGE_IMAGE.to_string(),
+        large_text: "Atlas".to_string(),
+        buttons: presence_buttons(settings),
+    }
+}
+
+fn gaming_activity(settings: &Settings, gaming: &GamingPresence) -> PresenceActivity {
+    if gaming.hidden {
+        return hidden_game_activity(settings, gaming);
+    }
+    let context = PresenceContext {
+        game: &gaming.game,
+        elapsed_seconds: (get_current_timestamp() - gaming.started_at).max(0) as u64,
+        bottleneck: &gaming.bottleneck,
+        cpu_percent: gaming.cpu_percent,
+        gpu_percent: gaming.gpu_percent,
+    };
+    let (details, state) = if gaming.afk {
+        (template::render(AFK_DETAILS_TEMPLATE, &context), Some(AFK_STATE.to_string()))
+    } else {
+        (
+            template::render(&settings.discord_presence_details_template, &context),
+            template::render(&settings.discord_presence_state_template, &context),
+        )
+    };
+    let app = gaming.app.as_ref();
+    let large_image = app.and_then(|a| a.large_image.clone()).filter(|i| !i.trim().is_empty());
+    PresenceActivity {
+        application_id: app
+            .and_then(|a| a.app_id.clone())
+            .unwrap_or_else(|| DISCORD_CLIENT_ID.to_string()),
+        details,
+        state,
+        start_timestamp: Some(gaming.started_at),
+        // Game art is captioned with the game, Atlas's logo with Atlas
+        large_text: if large_image.is_some() { gaming.game.clone() } else { "Atlas".to_string() },
+        large_image: large_image.unwrap_or_else(|| DEFAULT_LARGE_IMAGE.to_string()),
+        buttons: presence_buttons(settings),
+    }
+}
+
+fn custom_activity(settings: &Settings, custom: &CustomPresence) -> PresenceActivity {
+    PresenceActivity {
+        application_id: DISCORD_CLIENT_ID.to_string(),
+        details: custom.details.clone(),
+        state: custom.state.clone(),
+        start_timestamp: None,
+        large_image: DEFAULT_LARGE_IMAGE.to_string(),
+        large_text: "Atlas".to_string(),
+        buttons: presence_buttons(settings),
+    }
+}
+
+/// A custom presence line with its whitespace collapsed; None when left empty
+fn custom_line(name: &str, line: Option<String>) -> Result<Option<String>, String> {
+    let Some(line) = line.map(|l| l.split_whitespace().collect::<Vec<_>>().join(" ")) else {
+        return Ok(None);
+    };
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let chars = line.chars().count();
+    if !(template::MIN_LINE_CHARS..=template::MAX_LINE_CHARS).contains(&chars) {
+        return Err(format!(
+            "{} must be {} to {} characters",
+            name,
+            template::MIN_LINE_CHARS,
+            template::MAX_LINE_CHARS
+        ));
+    }
+    Ok(Some(line))
+}
+
+/// A hidden game shows as the idle presence, or as "Playing a game" under Atlas's application
+fn hidden_game_activity(settings: &Settings, gaming: &GamingPresence) -> PresenceActivity {
+    if settings.presence_hidden_game_display == "idle" {
+        return idle_activity(settings);
+    }
+    PresenceActivity {
+        application_id: DISCORD_CLIENT_ID.to_string(),
+        details: Some(HIDDEN_GAME_DETAILS.to_string()),
+        state: gaming.afk.then(|| AFK_STATE.to_string()),
+        start_timestamp: Some(gaming.started_at),
+        large_image: DEFAULT_LARGE_IMAGE.to_string(),
+        large_text: "Atlas".to_string(),
+        buttons: presence_buttons(settings),
+    }
+}
+
+/// The configured buttons Discord would accept; settings.json may have been edited by hand
+fn presence_buttons(settings: &Settings) -> Vec<PresenceButton> {
+    settings
+        .discord_presence_buttons
+        .iter()
+        .filter(|b| presence_button_problem(b).is_none())
+        .take(MAX_PRESENCE_BUTTONS)
+        .cloned()
+        .collect()
 }
 
 fn get_current_timestamp() -> i64 {
@@ -167,3 +787,129 @@ fn get_current_timestamp() -> i64 {
         .unwrap_or_default()
         .as_secs() as i64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(app_id: Option<&str>, large_image: Option<&str>) -> DiscordGameApp {
+        DiscordGameApp {
+            app_id: app_id.map(str::to_string),
+            large_image: large_image.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_validate_game_app() {
+        assert!(validate_game_app(&app(None, None)).is_ok());
+        assert!(validate_game_app(&app(Some("1461387162720272445"), Some("cover"))).is_ok());
+        assert!(validate_game_app(&app(None, Some("https://example.com/cover.png"))).is_ok());
+        assert!(validate_game_app(&app(Some("12345"), None)).is_err());
+        assert!(validate_game_app(&app(Some("14613871627202724a5"), None)).is_err());
+        assert!(validate_game_app(&app(None, Some("  "))).is_err());
+        assert!(validate_game_app(&app(None, Some("http://example.com/cover.png"))).is_err());
+    }
+
+    #[test]
+    fn test_game_activity_uses_the_games_application() {
+        let settings = Settings::default();
+        let mut gaming = GamingPresence {
+            game: "Elden Ring".to_string(),
+            started_at: 1_700_000_000,
+            bottleneck: BottleneckType::Balanced,
+            cpu_percent: None,
+            gpu_percent: None,
+            app: None,
+            afk: false,
+            hidden: false,
+        };
+        let activity = gaming_activity(&settings, &gaming);
+        assert_eq!(activity.application_id, DISCORD_CLIENT_ID);
+        assert_eq!(activity.large_image, DEFAULT_LARGE_IMAGE);
+        assert_eq!(activity.large_text, "Atlas");
+
+        gaming.app = Some(app(Some("123456789012345678"), Some("cover")));
+        let activity = gaming_activity(&settings, &gaming);
+        assert_eq!(activity.application_id, "123456789012345678");
+        assert_eq!(activity.large_image, "cover");
+        assert_eq!(activity.large_text, "Elden Ring");
+    }
+
+    #[test]
+    fn test_afk_activity_keeps_the_start_time() {
+        let gaming = GamingPresence {
+            game: "Elden Ring".to_string(),
+            started_at: 1_700_000_000,
+            bottleneck: BottleneckType::GpuBound,
+            cpu_percent: Some(2.0),
+            gpu_percent: Some(3.0),
+            app: None,
+            afk: true,
+            hidden: false,
+        };
+        let activity = gaming_activity(&Settings::default(), &gaming);
+        assert_eq!(activity.details.as_deref(), Some("AFK in Elden Ring"));
+        assert_eq!(activity.state.as_deref(), Some(AFK_STATE));
+        assert_eq!(activity.start_timestamp, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_custom_line() {
+        assert_eq!(custom_line("State", None), Ok(None));
+        assert_eq!(custom_line("State", Some("   ".to_string())), Ok(None));
+        assert_eq!(
+            custom_line("State", Some(" streaming  tonight at 8 ".to_string())),
+            Ok(Some("streaming tonight at 8".to_string()))
+        );
+        assert!(custom_line("State", Some("x".to_string())).is_err());
+        assert!(custom_line("State", Some("x".repeat(template::MAX_LINE_CHARS + 1))).is_err());
+    }
+
+    #[test]
+    fn test_custom_presence_overrides_the_game_until_it_ends() {
+        let manager = DiscordPresenceManager::new();
+        manager.begin_game("Elden Ring", 1_700_000_000, None, false).unwrap();
+        manager
+            .set_custom_presence(None, Some("streaming tonight at 8".to_string()), 30)
+            .unwrap();
+
+        // A game ending during the override changes what's underneath, not what's shown
+        manager.set_idle_presence().unwrap();
+        let current = manager.current();
+        assert_eq!(current.mode, PresenceMode::Custom);
+        assert_eq!(current.activity.state.as_deref(), Some("streaming tonight at 8"));
+
+        manager.begin_game("Elden Ring", 1_700_000_000, None, false).unwrap();
+        if let Ok(mut custom) = manager.custom.lock() {
+            custom.as_mut().unwrap().until = get_current_timestamp() - 1;
+        }
+        assert_eq!(manager.current().mode, PresenceMode::Gaming);
+        manager.expire_custom_presence();
+        assert!(manager.custom.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_hidden_game_activity_leaves_out_the_game() {
+        let mut settings = Settings::default();
+        let gaming = GamingPresence {
+            game: "Elden Ring".to_string(),
+            started_at: 1_700_000_000,
+            bottleneck: BottleneckType::Balanced,
+            cpu_percent: None,
+            gpu_percent: None,
+            app: Some(DiscordGameApp {
+                app_id: Some("123456789012345678".to_string()),
+                large_image: Some("cover".to_string()),
+            }),
+            afk: false,
+            hidden: true,
+        };
+        let activity = gaming_activity(&settings, &gaming);
+        assert_eq!(activity.details.as_deref(), Some(HIDDEN_GAME_DETAILS));
+        assert_eq!(activity.application_id, DISCORD_CLIENT_ID);
+        assert_eq!(activity.large_image, DEFAULT_LARGE_IMAGE);
+
+        settings.presence_hidden_game_display = "idle".to_string();
+        assert_eq!(gaming_activity(&settings, &gaming), idle_activity(&settings));
+    }
+}
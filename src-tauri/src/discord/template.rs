@@ -0,0 +1,158 @@
+// Rich Presence text templates
+// The details and state lines come from user templates in Settings with `{name}` placeholders.
+// A placeholder Atlas doesn't know, or one without a value right now (e.g. {gpu} without a GPU),
+// renders as nothing, so a typo never keeps the presence from being shown
+
+use crate::models::gaming::BottleneckType;
+use crate::models::PresencePlaceholder;
+
+/// Discord rejects details/state lines outside this length
+pub const MIN_LINE_CHARS: usize = 2;
+pub const MAX_LINE_CHARS: usize = 128;
+
+/// (name, description, example value)
+const PLACEHOLDERS: &[(&str, &str, &str)] = &[
+    ("game", "Name of the game being played", "Elden Ring"),
+    ("elapsed", "Time spent in the current session", "1h 05m"),
+    ("bottleneck", "What limits performance right now", "✨ - Smooth"),
+    ("cpu", "CPU usage", "42%"),
+    ("gpu", "GPU usage; empty without a GPU", "87%"),
+];
+
+/// Values the placeholders resolve to
+#[derive(Debug, Clone)]
+pub struct PresenceContext<'a> {
+    pub game: &'a str,
+    pub elapsed_seconds: u64,
+    pub bottleneck: &'a BottleneckType,
+    pub cpu_percent: Option<f32>,
+    pub gpu_percent: Option<f32>,
+}
+
+impl PresenceContext<'_> {
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "game" => Some(self.game.to_string()),
+            "elapsed" => Some(format_elapsed(self.elapsed_seconds)),
+            "bottleneck" => Some(bottleneck_to_status(self.bottleneck)),
+            "cpu" => self.cpu_percent.map(format_percent),
+            "gpu" => self.gpu_percent.map(format_percent),
+            _ => None,
+        }
+    }
+}
+
+/// Placeholders for the template editor
+pub fn placeholders() -> Vec<PresencePlaceholder> {
+    PLACEHOLDERS
+        .iter()
+        .map(|(name, description, example)| PresencePlaceholder {
+            placeholder: format!("{{{}}}", name),
+            description: description.to_string(),
+            example: example.to_string(),
+        })
+        .collect()
+}
+
+/// Fill in `template`; None when the result is too short for Discord to show
+/// Text in braces that can't be a placeholder name (e.g. "{ }" or an unclosed "{") is kept as is
+pub fn render(template: &str, context: &PresenceContext) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}') {
+            Some(close) if is_placeholder_name(&after[..close]) => {
+                if let Some(value) = context.value(&after[..close]) {
+                    out.push_str(&value);
+                }
+                rest = &after[close + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+
+    let line = out.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() < MIN_LINE_CHARS {
+        return None;
+    }
+    Some(line.chars().take(MAX_LINE_CHARS).collect())
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn format_elapsed(seconds: u64) -> String {
+    let (hours, minutes) = (seconds / 3600, seconds / 60 % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn format_percent(percent: f32) -> String {
+    format!("{:.0}%", percent.clamp(0.0, 100.0))
+}
+
+/// Convert bottleneck type to emoji + word status
+fn bottleneck_to_status(bottleneck_type: &BottleneckType) -> String {
+    match bottleneck_type {
+        BottleneckType::Balanced => "✨ - Smooth".to_string(),
+        BottleneckType::CpuBound => "💪 - Pushing".to_string(),
+        BottleneckType::GpuBound => "🔥 - Maxed".to_string(),
+        BottleneckType::CpuThermal | BottleneckType::GpuThermal => "🌡️ - Toasty".to_string(),
+        BottleneckType::RamLimited => "📦 - Packed".to_string(),
+        BottleneckType::VramLimited => "🎨 - Full".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(bottleneck: &BottleneckType) -> PresenceContext<'_> {
+        PresenceContext {
+            game: "Elden Ring",
+            elapsed_seconds: 3900,
+            bottleneck,
+            cpu_percent: Some(41.6),
+            gpu_percent: None,
+        }
+    }
+
+    #[test]
+    fn test_renders_placeholders() {
+        let ctx = context(&BottleneckType::GpuBound);
+        assert_eq!(render("Playing {game}", &ctx).as_deref(), Some("Playing Elden Ring"));
+        assert_eq!(render("{bottleneck} for {elapsed}", &ctx).as_deref(), Some("🔥 - Maxed for 1h 05m"));
+        assert_eq!(render("CPU {cpu}", &ctx).as_deref(), Some("CPU 42%"));
+    }
+
+    #[test]
+    fn test_unknown_and_missing_placeholders_render_empty() {
+        let ctx = context(&BottleneckType::Balanced);
+        assert_eq!(render("{game} {fps} {gpu}", &ctx).as_deref(), Some("Elden Ring"));
+        assert_eq!(render("{gpu}", &ctx), None);
+    }
+
+    #[test]
+    fn test_text_that_is_not_a_placeholder_is_kept() {
+        let ctx = context(&BottleneckType::Balanced);
+        assert_eq!(render("{ game } {game", &ctx).as_deref(), Some("{ game } {game"));
+        assert_eq!(render("}{game}{", &ctx).as_deref(), Some("}Elden Ring{"));
+    }
+
+    #[test]
+    fn test_long_lines_are_cut_to_discords_limit() {
+        let ctx = context(&BottleneckType::Balanced);
+        let line = render(&"x".repeat(200), &ctx).unwrap();
+        assert_eq!(line.chars().count(), MAX_LINE_CHARS);
+    }
+}
@@ -0,0 +1,83 @@
+// Default data files of each feature
+// Startup only creates the files that are missing; a reset replaces them with the same defaults
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use super::{initialize_json_file, write_json_file};
+use crate::models::{DataFeature, GameLibrary, GamingSession};
+use crate::task_monitor;
+use crate::utils::{
+    get_downloads_json_path, get_friends_dir, get_game_library_json_path, get_gaming_sessions_dir,
+    get_gaming_sessions_json_path, get_memories_dir, get_valorant_notifier_json_path, get_valorant_store_json_path,
+    get_valorant_stores_dir,
+};
+
+fn write_default<T: Serialize>(path: &Path, default: &T, overwrite: bool) -> Result<(), String> {
+    if overwrite {
+        write_json_file(path, default)
+    } else {
+        initialize_json_file(path, default)
+    }
+}
+
+/// Empty `dir`, leaving it in place
+fn clear_dir(dir: &Path) -> Result<(), String> {
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(|e| format!("Failed to clear {:?}: {}", dir, e))?;
+    }
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))
+}
+
+fn remove_file(path: &Path) -> Result<(), String> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to remove {:?}: {}", path, e)),
+        _ => Ok(()),
+    }
+}
+
+/// Write the default files of `feature`; `overwrite` replaces existing data instead of only filling gaps
+pub fn write_feature_defaults(feature: DataFeature, overwrite: bool) -> Result<(), String> {
+    match feature {
+        DataFeature::Downloads => {
+            write_default(&get_downloads_json_path(), &Vec::<serde_json::Value>::new(), overwrite)
+        }
+        DataFeature::GamingSessions => {
+            if overwrite {
+                clear_dir(&get_gaming_sessions_dir())?;
+            }
+            write_default(&get_gaming_sessions_json_path(), &Vec::<GamingSession>::new(), overwrite)
+        }
+        DataFeature::GameLibrary => write_default(&get_game_library_json_path(), &GameLibrary::new(), overwrite),
+        DataFeature::TaskMonitorProfiles => {
+            if overwrite {
+                task_monitor::profiles::write_default_profiles()
+            } else {
+                task_monitor::profiles::initialize_profiles()
+            }
+        }
+        // Store history is created on the first fetch
+        DataFeature::ValorantStore => {
+            if overwrite {
+                let stores_dir = get_valorant_stores_dir();
+                if stores_dir.exists() {
+                    fs::remove_dir_all(&stores_dir)
+                        .map_err(|e| format!("Failed to clear {:?}: {}", stores_dir, e))?;
+                }
+                remove_file(&get_valorant_store_json_path())?;
+                remove_file(&get_valorant_notifier_json_path())?;
+            }
+            Ok(())
+        }
+        // Friends files are created on registration; startup only creates the directories
+        DataFeature::Friends => {
+            if overwrite {
+                clear_dir(&get_friends_dir())?;
+                fs::create_dir_all(get_memories_dir())
+                    .map_err(|e| format!("Failed to create memories directory: {}", e))?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,4 +1,6 @@
 // File-based data management
+pub mod feature_data;
 pub mod json_ops;
+pub mod settings_migrations;
 
 pub use json_ops::*;
@@ -0,0 +1,181 @@
+// settings.json schema migrations
+// Each step upgrades the raw JSON by one version, so a file from any older release is walked
+// forward one version at a time. To change the schema: bump SETTINGS_SCHEMA_VERSION, append a step
+// to MIGRATIONS and add a fixture of the outgoing version under fixtures/
+
+use log::{info, warn};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+use super::{read_json_file, write_json_file};
+use crate::models::{Settings, SETTINGS_SCHEMA_VERSION};
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// MIGRATIONS[n] upgrades version n to n + 1
+const MIGRATIONS: [Migration; SETTINGS_SCHEMA_VERSION as usize] = [migrate_v0_to_v1];
+
+/// Optional text fields that releases before versioning saved as "" instead of null
+const V0_OPTIONAL_STRINGS: &[&str] = &[
+    "atlas_project_path",
+    "remote_update_path",
+    "update_url_base",
+    "user_display_name",
+    "user_avatar_path",
+    "preferred_gpu",
+    "steamgriddb_api_key",
+];
+
+/// Optional lists that releases before versioning saved as [] instead of null
+const V0_OPTIONAL_LISTS: &[&str] = &["sidebar_order", "hidden_sidebar_items"];
+
+fn migrate_v0_to_v1(fields: &mut Map<String, Value>) {
+    for key in V0_OPTIONAL_STRINGS {
+        if fields.get(*key).and_then(|v| v.as_str()).is_some_and(|s| s.trim().is_empty()) {
+            fields.insert(key.to_string(), Value::Null);
+        }
+    }
+    for key in V0_OPTIONAL_LISTS {
+        if fields.get(*key).and_then(|v| v.as_array()).is_some_and(|a| a.is_empty()) {
+            fields.insert(key.to_string(), Value::Null);
+        }
+    }
+}
+
+fn schema_version(fields: &Map<String, Value>) -> u32 {
+    fields.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(0) as u32
+}
+
+/// Upgrade raw settings JSON to the current schema; returns the version it started at
+/// Files from a newer Atlas are left as they are
+pub fn migrate(value: &mut Value) -> Result<u32, String> {
+    let fields = value
+        .as_object_mut()
+        .ok_or_else(|| "Settings file is not a JSON object".to_string())?;
+    let from = schema_version(fields);
+
+    if from > SETTINGS_SCHEMA_VERSION {
+        warn!(
+            "settings.json is schema version {}, newer than this build's {}; leaving it as is",
+            from, SETTINGS_SCHEMA_VERSION
+        );
+        return Ok(from);
+    }
+
+    for version in from..SETTINGS_SCHEMA_VERSION {
+        MIGRATIONS[version as usize](fields);
+        fields.insert("schema_version".to_string(), Value::from(version + 1));
+    }
+    Ok(from)
+}
+
+/// Create settings.json with defaults, or upgrade an existing one to the current schema
+/// The file is copied to settings.v{N}.bak.json before an upgraded version is written
+pub fn initialize_settings_file(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return write_json_file(path, &Settings::default());
+    }
+
+    let mut value: Value = read_json_file(path)?;
+    let from = migrate(&mut value)?;
+    if from >= SETTINGS_SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    // Make sure the upgraded file still loads before replacing the old one
+    serde_json::from_value::<Settings>(value.clone())
+        .map_err(|e| format!("Migrated settings are invalid: {}", e))?;
+
+    let backup_path = path.with_file_name(format!("settings.v{}.bak.json", from));
+    fs::copy(path, &backup_path).map_err(|e| format!("Failed to back up settings to {:?}: {}", backup_path, e))?;
+    write_json_file(path, &value)?;
+
+    info!(
+        "Migrated settings.json from schema {} to {} (backup at {:?})",
+        from, SETTINGS_SCHEMA_VERSION, backup_path
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE_V0: &str = include_str!("fixtures/settings_v0.json");
+    const FIXTURE_V1: &str = include_str!("fixtures/settings_v1.json");
+
+    fn migrated(fixture: &str) -> (u32, Settings) {
+        let mut value: Value = serde_json::from_str(fixture).unwrap();
+        let from = migrate(&mut value).unwrap();
+        (from, serde_json::from_value(value).unwrap())
+    }
+
+    #[test]
+    fn test_migrate_v0() {
+        let (from, settings) = migrated(FIXTURE_V0);
+        assert_eq!(from, 0);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+
+        // User values survive
+        assert_eq!(settings.download_path, "D:\\Videos\\Atlas");
+        assert_eq!(settings.default_quality, "1080p");
+        assert!(settings.developer_mode_enabled);
+        assert!(!settings.partner_widget_enabled);
+        assert_eq!(settings.hidden_sidebar_items, Some(vec!["server".to_string()]));
+
+        // Empty placeholders become unset
+        assert_eq!(settings.atlas_project_path, None);
+        assert_eq!(settings.update_url_base, None);
+        assert_eq!(settings.user_display_name, None);
+        assert_eq!(settings.sidebar_order, None);
+
+        // Fields added later take their defaults
+        assert_eq!(settings.performance_history_minutes, 15);
+        assert!(settings.extra.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_v1_is_current() {
+        let (from, settings) = migrated(FIXTURE_V1);
+        assert_eq!(from, 1);
+        assert_eq!(settings.update_url_base.as_deref(), Some("https://updates.example.com/atlas"));
+        assert_eq!(settings.partner_widget_position_x, Some(1600.0));
+        assert!(settings.valorant_store_notifications);
+    }
+
+    #[test]
+    fn test_newer_schema_keeps_unknown_fields() {
+        let mut value: Value = serde_json::from_str(FIXTURE_V1).unwrap();
+        value["schema_version"] = Value::from(SETTINGS_SCHEMA_VERSION + 1);
+        value["renamed_in_future"] = Value::from("kept");
+
+        let before = value.clone();
+        assert_eq!(migrate(&mut value).unwrap(), SETTINGS_SCHEMA_VERSION + 1);
+        assert_eq!(value, before);
+
+        let settings: Settings = serde_json::from_value(value).unwrap();
+        let saved = serde_json::to_value(&settings).unwrap();
+        assert_eq!(saved["renamed_in_future"], "kept");
+        assert_eq!(saved["schema_version"], SETTINGS_SCHEMA_VERSION + 1);
+    }
+
+    #[test]
+    fn test_initialize_settings_file_backs_up_before_migrating() {
+        let dir = std::env::temp_dir().join(format!("atlas_settings_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        fs::write(&path, FIXTURE_V0).unwrap();
+
+        initialize_settings_file(&path).unwrap();
+        let upgraded: Settings = read_json_file(&path).unwrap();
+        assert_eq!(upgraded.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(fs::read_to_string(dir.join("settings.v0.bak.json")).unwrap(), FIXTURE_V0);
+
+        // Already current: nothing else is written
+        initialize_settings_file(&path).unwrap();
+        assert!(!dir.join("settings.v1.bak.json").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
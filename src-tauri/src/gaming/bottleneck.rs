@@ -63,9 +63,7 @@ impl BottleneckAnalyzer {
             }
         }
 
-        if metrics.ram_percent >= self.thresholds.ram_high {
-            let over = metrics.ram_percent - self.thresholds.ram_high;
-            let severity = calculate_usage_severity(over);
+        if let Some(severity) = self.detect_ram_pressure(metrics) {
             return (BottleneckType::RamLimited, severity);
         }
 
@@ -86,6 +84,30 @@ impl BottleneckAnalyzer {
         (BottleneckType::Balanced, 0)
     }
 
+    /// RAM is limiting when available memory runs low or commit charge nears the limit
+    /// A high usage percent alone is often just standby cache, so it is only used for
+    /// snapshots recorded before available MB was tracked
+    fn detect_ram_pressure(&self, metrics: &MetricsSnapshot) -> Option<u8> {
+        let min_available = self.thresholds.ram_available_min_mb as f32;
+        let available_severity = match metrics.ram_available_mb {
+            Some(available) if available < min_available => {
+                Some(calculate_available_severity(available, min_available))
+            }
+            Some(_) => None,
+            None if metrics.ram_percent >= self.thresholds.ram_high => {
+                Some(calculate_usage_severity(metrics.ram_percent - self.thresholds.ram_high))
+            }
+            None => None,
+        };
+
+        let commit_severity = metrics
+            .commit_percent
+            .filter(|commit| *commit >= self.thresholds.commit_high)
+            .map(|commit| calculate_usage_severity(commit - self.thresholds.commit_high));
+
+        available_severity.max(commit_severity)
+    }
+
     #[allow(dead_code)] 
     pub fn is_bottleneck_active(&self, metrics: &MetricsSnapshot, check_type: &BottleneckType) -> bool {
         let (detected, _) = self.detect_bottleneck(metrics);
@@ -149,6 +171,16 @@ fn calculate_usage_severity(percent_over: f32) -> u8 {
     }
 }
 
+fn calculate_available_severity(available_mb: f32, min_available_mb: f32) -> u8 {
+    if available_mb <= min_available_mb * 0.25 {
+        3
+    } else if available_mb <= min_available_mb * 0.5 {
+        2
+    } else {
+        1
+    }
+}
+
 fn calculate_bound_severity(delta: f32) -> u8 {
     if delta >= 40.0 {
         3
@@ -174,6 +206,14 @@ mod tests {
             vram_percent: None,
             cpu_temp: None,
             gpu_temp: None,
+            gpu_power_watts: None,
+            gpu_power_limit_watts: None,
+            gpu_fan_percent: None,
+            cpu_clock_mhz: None,
+            gpu_core_clock_mhz: None,
+            gpu_memory_clock_mhz: None,
+            ram_available_mb: None,
+            commit_percent: None,
         }
     }
 
@@ -208,4 +248,28 @@ mod tests {
         let status = analyzer.analyze(&snapshot);
         assert_eq!(status.bottleneck_type, BottleneckType::RamLimited);
     }
+
+    #[test]
+    fn test_standby_cache_is_not_ram_limited() {
+        let analyzer = BottleneckAnalyzer::with_thresholds(BottleneckThresholds::default());
+        // 95% "used" but plenty of memory is still available
+        let mut snapshot = create_test_snapshot(50.0, Some(50.0), 95.0);
+        snapshot.ram_available_mb = Some(6000.0);
+        snapshot.commit_percent = Some(60.0);
+        assert_eq!(analyzer.analyze(&snapshot).bottleneck_type, BottleneckType::Balanced);
+
+        snapshot.ram_available_mb = Some(400.0);
+        let status = analyzer.analyze(&snapshot);
+        assert_eq!(status.bottleneck_type, BottleneckType::RamLimited);
+        assert_eq!(status.severity, 3);
+    }
+
+    #[test]
+    fn test_commit_pressure_is_ram_limited() {
+        let analyzer = BottleneckAnalyzer::with_thresholds(BottleneckThresholds::default());
+        let mut snapshot = create_test_snapshot(50.0, Some(50.0), 60.0);
+        snapshot.ram_available_mb = Some(6000.0);
+        snapshot.commit_percent = Some(97.0);
+        assert_eq!(analyzer.analyze(&snapshot).bottleneck_type, BottleneckType::RamLimited);
+    }
 }
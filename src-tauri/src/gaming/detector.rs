@@ -1,7 +1,7 @@
 use log::{debug, error, info, warn};
 use serde_json::json;
 use std::collections::HashSet;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -11,9 +11,16 @@ use tauri::{AppHandle, Emitter};
 use crate::file_manager::read_json_file;
 use crate::models::gaming::GameWhitelist;
 use crate::performance::{stop_monitoring, MonitoringState};
+use crate::task_monitor::{self, affinity, profiles};
 use crate::utils::get_game_whitelist_json_path;
 use super::session::GamingSessionManager;
 
+/// Minimum seconds between automatic profile executions (two whitelisted games starting together)
+const PROFILE_AUTO_EXECUTE_COOLDOWN_SECONDS: i64 = 60;
+
+/// Unix seconds of the last automatic profile execution
+static LAST_PROFILE_AUTO_EXECUTE: AtomicI64 = AtomicI64::new(0);
+
 /// Represents the result of attempting to wait for a process
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WaitResult {
@@ -62,6 +69,56 @@ pub fn handle_wait_result(result: WaitResult) -> WaitResultAction {
     }
 }
 
+/// Whether enough time has passed since the last automatic profile execution
+pub fn should_auto_execute_profile(last: i64, now: i64) -> bool {
+    now - last >= PROFILE_AUTO_EXECUTE_COOLDOWN_SECONDS
+}
+
+/// Run the default gaming profile in the background and report the result
+/// Killed processes go on the restore list as usual, so the end-of-session auto-restore brings them back
+fn auto_execute_default_profile(app: &AppHandle) {
+    let now = chrono::Utc::now().timestamp();
+    let last = LAST_PROFILE_AUTO_EXECUTE.load(Ordering::SeqCst);
+    if !should_auto_execute_profile(last, now)
+        || LAST_PROFILE_AUTO_EXECUTE
+            .compare_exchange(last, now, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+    {
+        debug!("Default profile was auto-executed moments ago, skipping");
+        return;
+    }
+
+    let profile = match profiles::get_default_profile() {
+        Ok(Some(profile)) => profile,
+        Ok(None) => {
+            debug!("No default gaming profile set, nothing to auto-execute");
+            return;
+        }
+        Err(e) => {
+            warn!("Failed to load gaming profiles: {}", e);
+            return;
+        }
+    };
+
+    let app = app.clone();
+    thread::spawn(move || {
+        info!("Auto-executing default gaming profile: {}", profile.name);
+        match task_monitor::execute_profile(&profile.id, None) {
+            Ok(result) => {
+                let payload = json!({
+                    "profile_id": profile.id,
+                    "profile_name": profile.name,
+                    "result": result,
+                });
+                if let Err(e) = app.emit("task_monitor:profile_auto_executed", payload) {
+                    warn!("Failed to emit profile_auto_executed event: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to auto-execute gaming profile {}: {}", profile.name, e),
+        }
+    });
+}
+
 /// State for tracking if game detection is active
 pub struct GameDetectionState {
     pub is_running: Arc<AtomicBool>,
@@ -80,6 +137,7 @@ struct NormalizedGame {
     process_name: String,
     normalized_process: String, 
     enabled: bool,
+    affinity_mask: Option<u64>,
 }
 
 fn load_game_whitelist_normalized() -> Vec<NormalizedGame> {
@@ -94,6 +152,7 @@ fn load_game_whitelist_normalized() -> Vec<NormalizedGame> {
             .trim_end_matches(".EXE")
             .to_lowercase(),
         enabled: g.enabled,
+        affinity_mask: g.affinity_mask,
     }).collect()
 }
 
@@ -151,11 +210,11 @@ pub fn start_game_detection(
                 })
                 .collect();
 
-            let mut found_game: Option<(String, String)> = None;
+            let mut found_game: Option<(String, String, Option<u64>)> = None;
 
             for game in whitelist.iter().filter(|g| g.enabled) {
                 if running_processes.contains(&game.normalized_process) {
-                    found_game = Some((game.name.clone(), game.process_name.clone()));
+                    found_game = Some((game.name.clone(), game.process_name.clone(), game.affinity_mask));
                     debug!("Matched game: {} (process: {})", game.name, game.process_name);
                     break;
                 }
@@ -168,14 +227,36 @@ pub fn start_game_detection(
             thread::sleep(Duration::from_secs(3));
         };
 
-        let (game_name, process_name) = detected_game;
+        let (game_name, process_name, affinity_mask) = detected_game;
         info!("Game detected: {} ({}) - stopping detection polling", game_name, process_name);
 
-        match session_manager.start_session(&game_name, &process_name) {
+        let process_name_lower = process_name
+            .trim_end_matches(".exe")
+            .trim_end_matches(".EXE")
+            .to_lowercase();
+
+        // Lets a session left open by a previous Atlas run resume with its start time
+        let process_started_at = system.processes().values()
+            .filter(|p| {
+                p.name().to_string()
+                    .trim_end_matches(".exe")
+                    .trim_end_matches(".EXE")
+                    .to_lowercase() == process_name_lower
+            })
+            .map(|p| p.start_time() as i64)
+            .min();
+
+        match session_manager.start_session(&game_name, &process_name, process_started_at) {
             Ok(session) => {
                 if let Err(e) = app.emit("gaming:session_started", json!({ "session": session })) {
                     warn!("Failed to emit session_started event: {}", e);
                 }
+
+                crate::commands::settings_profiles::on_gaming_session_started(&app);
+
+                if crate::commands::settings::get_settings().unwrap_or_default().auto_execute_default_profile {
+                    auto_execute_default_profile(&app);
+                }
             }
             Err(e) => {
                 error!("Failed to start session for {}: {}", game_name, e);
@@ -190,10 +271,28 @@ pub fn start_game_detection(
         }
         debug!("Detection turned off after game detected");
 
-        let process_name_lower = process_name
-            .trim_end_matches(".exe")
-            .trim_end_matches(".EXE")
-            .to_lowercase();
+        // Pin the game to its configured cores; undone when the session ends
+        let pinned_pid = affinity_mask.and_then(|mask| {
+            let pid = system.processes().iter()
+                .find(|(_, p)| {
+                    p.name().to_string()
+                        .trim_end_matches(".exe")
+                        .trim_end_matches(".EXE")
+                        .to_lowercase() == process_name_lower
+                })
+                .map(|(pid, _)| pid.as_u32())?;
+
+            match affinity::set_affinity(pid, mask) {
+                Ok(()) => {
+                    info!("Applied CPU affinity {:#x} to {} (PID: {})", mask, process_name, pid);
+                    Some(pid)
+                }
+                Err(e) => {
+                    warn!("Failed to apply CPU affinity to {}: {}", process_name, e);
+                    None
+                }
+            }
+        });
 
         debug!("Phase 2: Monitoring for process exit: {}", process_name_lower);
 
@@ -204,18 +303,30 @@ pub fn start_game_detection(
                                        monitoring_state: Arc<MonitoringState>| {
             info!("Game process exited: {}", process_name);
 
+            // Only matters if the session ended while the game is still running
+            if let Some(pid) = pinned_pid {
+                let _ = affinity::reset_affinity(pid);
+            }
+
             // End the gaming session
-            match session_manager.end_session_by_process(process_name) {
+            let session_start = match session_manager.end_session_by_process(process_name) {
                 Ok(session) => {
                     info!("Gaming session ended successfully");
+                    let start = chrono::DateTime::parse_from_rfc3339(&session.start_time)
+                        .map(|t| t.timestamp())
+                        .ok();
                     if let Err(e) = app.emit("gaming:session_ended", json!({ "session": session })) {
                         warn!("Failed to emit session_ended event: {}", e);
                     }
+                    start
                 }
                 Err(e) => {
                     error!("Failed to end session for {}: {}", process_name, e);
+                    None
                 }
-            }
+            };
+
+            crate::commands::settings_profiles::on_gaming_session_ended(app);
 
             debug!("Stopping performance monitoring...");
             stop_monitoring(monitoring_state);
@@ -229,26 +340,33 @@ pub fn start_game_detection(
             use crate::task_monitor::restore;
 
             let settings = get_settings().unwrap_or_default();
-            if settings.auto_restore_enabled {
-                info!("Auto-restore enabled, waiting 3 seconds before restoring processes...");
-
-                std::thread::sleep(std::time::Duration::from_secs(3));
-
-                if let Ok(restore_list) = restore::load_restore_list() {
-                    if !restore_list.processes.is_empty() {
-                        info!("Restoring {} killed processes...", restore_list.processes.len());
-                        let result = restore::restore_all_processes(&restore_list);
-                        info!("Restore complete: {} restored, {} skipped, {} failed",
-                                 result.restored, result.skipped_self_restoring, result.failed);
-
-                        if let Err(e) = app.emit("task_monitor:restore_completed", &result) {
-                            warn!("Failed to emit restore_completed event: {}", e);
-                        }
-
-                        if let Err(e) = restore::clear_restore_list() {
-                            warn!("Failed to clear restore list: {}", e);
+            if let (true, Some(session_start)) = (settings.auto_restore_enabled, session_start) {
+                let delay = settings.auto_restore_delay_seconds;
+                info!("Auto-restore enabled, waiting {} seconds before restoring processes...", delay);
+
+                std::thread::sleep(std::time::Duration::from_secs(delay as u64));
+
+                // Only entries killed during this session; older ones are left for the manual restore
+                match restore::restore_killed_since(session_start) {
+                    Ok(result) => {
+                        let attempted =
+                            result.restored.len() + result.skipped_self_restoring.len() + result.failed.len();
+                        if attempted > 0
+                            || result.priorities_restored > 0
+                            || result.services_restored > 0
+                            || result.processes_resumed > 0
+                        {
+                            info!("Restore complete: {} restored, {} skipped, {} failed",
+                                     result.restored.len(), result.skipped_self_restoring.len(), result.failed.len());
+
+                            if let Err(e) = app.emit("task_monitor:processes_restored", &result) {
+                                warn!("Failed to emit processes_restored event: {}", e);
+                            }
                         }
                     }
+                    Err(e) => {
+                        warn!("Failed to restore killed processes: {}", e);
+                    }
                 }
             }
         };
@@ -357,6 +475,13 @@ pub fn is_detection_running(detection_state: Arc<GameDetectionState>) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_profile_auto_execute_cooldown() {
+        assert!(should_auto_execute_profile(0, 1_700_000_000));
+        assert!(!should_auto_execute_profile(1_700_000_000, 1_700_000_005));
+        assert!(should_auto_execute_profile(1_700_000_000, 1_700_000_060));
+    }
+
     #[test]
     fn test_detection_strategy_windows_handle_acquired() {
         let strategy = determine_detection_strategy(true, true);
@@ -0,0 +1,131 @@
+// Idle (AFK) detection during a gaming session
+// The player counts as idle once the system has been nearly unloaded (e.g. a game paused in a
+// frame-capped menu) and there has been no keyboard or mouse input, both for the configured threshold.
+// No input alone isn't enough: controller play never shows up as input. Either signal ending brings
+// them back
+use std::time::{Duration, Instant};
+
+/// CPU and GPU usage below this counts as the game doing nothing
+pub const LOW_USAGE_PERCENT: f32 = 10.0;
+
+/// Time since the last keyboard or mouse input; None where that can't be read
+#[cfg(windows)]
+pub fn time_since_last_input() -> Option<Duration> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if GetLastInputInfo(&mut info) == 0 {
+            return None;
+        }
+        // Both are milliseconds since boot and wrap after ~49 days
+        Some(Duration::from_millis(GetTickCount().wrapping_sub(info.dwTime) as u64))
+    }
+}
+
+#[cfg(not(windows))]
+pub fn time_since_last_input() -> Option<Duration> {
+    None
+}
+
+/// Tracks whether the player has gone idle, from one sample per recording tick
+#[derive(Debug, Default)]
+pub struct IdleTracker {
+    low_usage_since: Option<Instant>,
+    idle: bool,
+}
+
+impl IdleTracker {
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Feed a sample; returns the new state when the player went idle or came back
+    pub fn update(
+        &mut self,
+        now: Instant,
+        since_input: Option<Duration>,
+        cpu_percent: f32,
+        gpu_percent: Option<f32>,
+        threshold: Duration,
+    ) -> Option<bool> {
+        let low_usage =
+            cpu_percent < LOW_USAGE_PERCENT && gpu_percent.is_none_or(|gpu| gpu < LOW_USAGE_PERCENT);
+        if !low_usage {
+            self.low_usage_since = None;
+        } else if self.low_usage_since.is_none() {
+            self.low_usage_since = Some(now);
+        }
+
+        // Where input can't be read, low usage decides on its own
+        let no_input = since_input.is_none_or(|since| since >= threshold);
+        let unloaded = self
+            .low_usage_since
+            .is_some_and(|since| now.duration_since(since) >= threshold);
+        let idle = no_input && unloaded;
+        if idle == self.idle {
+            return None;
+        }
+        self.idle = idle;
+        Some(idle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(600);
+
+    #[test]
+    fn test_no_input_under_load_is_not_idle() {
+        // Controller play: no keyboard or mouse input, but the game is busy
+        let mut tracker = IdleTracker::default();
+        let start = Instant::now();
+        assert_eq!(tracker.update(start, Some(THRESHOLD), 80.0, Some(95.0), THRESHOLD), None);
+        assert_eq!(tracker.update(start + THRESHOLD * 2, Some(THRESHOLD * 3), 80.0, Some(95.0), THRESHOLD), None);
+        assert!(!tracker.is_idle());
+    }
+
+    #[test]
+    fn test_no_input_and_low_usage_for_the_threshold_is_idle() {
+        let mut tracker = IdleTracker::default();
+        let start = Instant::now();
+        assert_eq!(tracker.update(start, Some(THRESHOLD), 3.0, Some(4.0), THRESHOLD), None);
+        assert_eq!(tracker.update(start + THRESHOLD, Some(THRESHOLD * 2), 3.0, Some(4.0), THRESHOLD), Some(true));
+        assert_eq!(tracker.update(start + THRESHOLD, Some(Duration::ZERO), 3.0, Some(4.0), THRESHOLD), Some(false));
+    }
+
+    #[test]
+    fn test_recent_input_keeps_an_unloaded_system_active() {
+        let mut tracker = IdleTracker::default();
+        let start = Instant::now();
+        tracker.update(start, Some(Duration::ZERO), 3.0, Some(4.0), THRESHOLD);
+        let since_input = Some(Duration::from_secs(599));
+        assert_eq!(tracker.update(start + THRESHOLD, since_input, 3.0, Some(4.0), THRESHOLD), None);
+    }
+
+    #[test]
+    fn test_low_usage_for_the_threshold_is_idle() {
+        let mut tracker = IdleTracker::default();
+        let start = Instant::now();
+        assert_eq!(tracker.update(start, None, 3.0, Some(4.0), THRESHOLD), None);
+        assert_eq!(tracker.update(start + Duration::from_secs(300), None, 3.0, None, THRESHOLD), None);
+        assert_eq!(tracker.update(start + THRESHOLD, None, 3.0, Some(4.0), THRESHOLD), Some(true));
+        assert!(tracker.is_idle());
+        assert_eq!(tracker.update(start + THRESHOLD, None, 3.0, Some(60.0), THRESHOLD), Some(false));
+    }
+
+    #[test]
+    fn test_load_in_between_restarts_the_low_usage_clock() {
+        let mut tracker = IdleTracker::default();
+        let start = Instant::now();
+        tracker.update(start, None, 3.0, Some(4.0), THRESHOLD);
+        tracker.update(start + Duration::from_secs(300), None, 50.0, Some(4.0), THRESHOLD);
+        assert_eq!(tracker.update(start + THRESHOLD, None, 3.0, Some(4.0), THRESHOLD), None);
+    }
+}
@@ -2,6 +2,7 @@
 pub mod detector;
 pub mod session;
 pub mod bottleneck;
+pub mod idle;
 
 pub use detector::{start_game_detection, stop_game_detection, is_detection_running, GameDetectionState};
 pub use session::GamingSessionManager;
@@ -2,20 +2,31 @@ use log::{debug, info};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
+use crate::commands::data_directory::{is_migrating, MIGRATING_MESSAGE};
+use crate::commands::settings::get_settings;
 use crate::discord::DiscordPresenceManager;
 use crate::file_manager::{read_json_file, write_json_file};
 use crate::models::gaming::{
     ActiveSessionState, BottleneckEvent, BottleneckType, CurrentBottleneckStatus,
-    GamingSession, GamingSessionData, MetricStats, MetricsSnapshot, SessionStatus,
+    GameWhitelist, GamingSession, GamingSessionData, MetricStats, MetricsSnapshot, SessionStatus,
     SessionSummary, TopCoreInfo, BottleneckBreakdown,
 };
-use crate::performance::{MonitoringState, SharedMetrics};
+use crate::models::{GameLibrary, PresenceStatus, UpdatePresenceRequest};
+use crate::performance::{busiest_gpu, MonitoringState, SharedMetrics};
 use crate::task_monitor::gpu_tracker::GAMING_ACTIVE;
-use crate::utils::{get_gaming_sessions_json_path, get_session_data_path};
+use crate::utils::{
+    get_game_library_json_path, get_game_whitelist_json_path, get_gaming_sessions_json_path,
+    get_session_data_path,
+};
 use super::bottleneck::BottleneckAnalyzer;
+use super::idle::{self, IdleTracker};
+
+/// How often the gaming presence is re-rendered between bottleneck changes
+/// Discord allows about one activity update every 15 seconds
+const PRESENCE_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
 
 /// Active session data (internal use)
 struct ActiveSessionData {
@@ -54,7 +65,19 @@ impl GamingSessionManager {
         }
     }
 
-    pub fn start_session(&self, game_name: &str, process_name: &str) -> Result<GamingSession, String> {
+    /// Start a session for a detected game
+    /// `process_started_at` is when the game process started, in Unix seconds. A session still marked
+    /// active for that process (Atlas closed while the game kept running) is resumed with its
+    /// original start time instead of starting a new one
+    pub fn start_session(
+        &self,
+        game_name: &str,
+        process_name: &str,
+        process_started_at: Option<i64>,
+    ) -> Result<GamingSession, String> {
+        if is_migrating() {
+            return Err(MIGRATING_MESSAGE.to_string());
+        }
         {
             let guard = self.active_session.lock().map_err(|e| e.to_string())?;
             if guard.is_some() {
@@ -62,42 +85,76 @@ impl GamingSessionManager {
             }
         }
 
-        let session_id = uuid::Uuid::new_v4().to_string();
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let session = GamingSession {
-            id: session_id.clone(),
-            game_name: game_name.to_string(),
-            process_name: process_name.to_string(),
-            start_time: now,
-            end_time: None,
-            status: SessionStatus::Active,
-            summary: None,
+        let sessions: Vec<GamingSession> =
+            read_json_file(&get_gaming_sessions_json_path()).unwrap_or_else(|_| Vec::new());
+        let session = match process_started_at
+            .and_then(|started| find_recoverable_session(&sessions, process_name, started))
+        {
+            Some(existing) => {
+                info!("Recovered gaming session {} started at {}", existing.id, existing.start_time);
+                existing.clone()
+            }
+            None => {
+                let session = GamingSession {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    game_name: game_name.to_string(),
+                    process_name: process_name.to_string(),
+                    start_time: chrono::Utc::now().to_rfc3339(),
+                    end_time: None,
+                    status: SessionStatus::Active,
+                    summary: None,
+                };
+                self.add_session_to_list(&session)?;
+                session
+            }
         };
-
-        self.add_session_to_list(&session)?;
+        let session_id = session.id.clone();
 
         // Enable gaming mode to reduce monitoring overhead
         self.monitoring_state.gaming_active.store(true, Ordering::Relaxed);
         GAMING_ACTIVE.store(true, Ordering::Relaxed);
 
-        // Update Discord Rich Presence
-        let _ = self.discord.update_gaming_presence(game_name, &BottleneckType::Balanced);
+        // Update Discord Rich Presence, under the game's own Discord application if it has one
+        // Connecting that application can take a moment, so it doesn't hold up the session
+        let entry = read_json_file::<GameWhitelist>(&get_game_whitelist_json_path())
+            .unwrap_or_else(|_| GameWhitelist::default_whitelist())
+            .games
+            .into_iter()
+            .find(|g| g.process_name.eq_ignore_ascii_case(process_name));
+        let hidden_by_game = entry.as_ref().is_some_and(|g| g.hide_from_presence)
+            || read_json_file::<GameLibrary>(&get_game_library_json_path()).is_ok_and(|library| {
+                library
+                    .games
+                    .iter()
+                    .any(|g| g.hide_from_presence && g.process_name.eq_ignore_ascii_case(process_name))
+            });
+        let hidden = hidden_by_game
+            || is_blocklisted(&get_settings().unwrap_or_default().presence_blocklist, &session);
+        let discord = self.discord.clone();
+        let presence_game = game_name.to_string();
+        let started_at = session_start_timestamp(&session);
+        let discord_app = entry.and_then(|g| g.discord_app);
+        thread::spawn(move || {
+            let _ = discord.begin_game(&presence_game, started_at, discord_app, hidden);
+        });
 
         // Start metrics recording
-        self.start_recording(session.clone());
+        self.start_recording(session.clone(), hidden_by_game);
 
         info!("Started gaming session: {} ({})", game_name, session_id);
         Ok(session)
     }
 
-    fn start_recording(&self, session: GamingSession) {
+    /// `hidden_by_game` is the game's own presence opt-out; the blocklist is re-checked as settings change
+    fn start_recording(&self, session: GamingSession, hidden_by_game: bool) {
         let active_session = self.active_session.clone();
         let app = self.app.clone();
         let analyzer = self.bottleneck_analyzer.clone();
         let shared_metrics = self.shared_metrics.clone();
         let discord = self.discord.clone();
         let game_name = session.game_name.clone();
+        let started_at = session_start_timestamp(&session);
+        let blocklist_session = session.clone();
         let is_recording = Arc::new(AtomicBool::new(true));
         let is_recording_clone = is_recording.clone();
         let session_id = session.id.clone();
@@ -130,6 +187,12 @@ impl GamingSessionManager {
 
             const WARMUP_SAMPLES: u32 = 3;
             let mut warmup_count: u32 = 0;
+            let mut last_presence_update = Instant::now();
+            let mut settings = get_settings().unwrap_or_default();
+            let mut settings_read_at = Instant::now();
+            let mut hidden = hidden_by_game || is_blocklisted(&settings.presence_blocklist, &blocklist_session);
+            let mut idle_tracker = IdleTracker::default();
+            let mut friends_away = false;
 
             while is_recording_clone.load(Ordering::SeqCst) {
                 if let Some(system_metrics) = shared_metrics.get() {
@@ -167,18 +230,62 @@ impl GamingSessionManager {
                                 }
 
                                 data.current_bottleneck = Some(new_bottleneck.clone());
+                                last_presence_update = Instant::now();
 
                                 // Update Discord Rich Presence
-                                let _ = discord.update_gaming_presence(&game_name, &new_bottleneck);
+                                let _ = discord.update_gaming_presence(
+                                    &game_name,
+                                    started_at,
+                                    &new_bottleneck,
+                                    Some(snapshot.cpu_percent),
+                                    snapshot.gpu_percent,
+                                );
 
                                 let _ = app.emit("gaming:bottleneck", GamingBottleneckEvent {
                                     session_id: session_id.clone(),
                                     status: status.clone(),
                                 });
+                            } else if last_presence_update.elapsed() >= PRESENCE_REFRESH_INTERVAL {
+                                // Keeps {elapsed}, {cpu} and {gpu} current; unchanged text isn't resent
+                                last_presence_update = Instant::now();
+                                let _ = discord.update_gaming_presence(
+                                    &game_name,
+                                    started_at,
+                                    &new_bottleneck,
+                                    Some(snapshot.cpu_percent),
+                                    snapshot.gpu_percent,
+                                );
                             }
                         }
                     }
 
+                    // AFK: the presence shows it while idle, and the friends presence optionally goes Away
+                    if settings_read_at.elapsed() >= PRESENCE_REFRESH_INTERVAL {
+                        settings = get_settings().unwrap_or_default();
+                        settings_read_at = Instant::now();
+                        hidden = hidden_by_game || is_blocklisted(&settings.presence_blocklist, &blocklist_session);
+                    }
+                    let afk_threshold = Duration::from_secs(settings.discord_presence_afk_minutes as u64 * 60);
+                    if let Some(now_idle) = idle_tracker.update(
+                        Instant::now(),
+                        idle::time_since_last_input(),
+                        snapshot.cpu_percent,
+                        snapshot.gpu_percent,
+                        afk_threshold,
+                    ) {
+                        debug!("Player {} in {}", if now_idle { "went idle" } else { "is back" }, game_name);
+                        // A hidden game's name isn't shared with friends either
+                        let friends_game = (!hidden).then_some(game_name.as_str());
+                        if now_idle && settings.friends_presence_afk_away {
+                            set_friends_presence(&app, PresenceStatus::Away, friends_game);
+                            friends_away = true;
+                        } else if !now_idle && friends_away {
+                            set_friends_presence(&app, PresenceStatus::InGame, friends_game);
+                            friends_away = false;
+                        }
+                    }
+                    let _ = discord.set_afk(settings.discord_presence_afk_enabled && idle_tracker.is_idle());
+
                     let _ = app.emit("gaming:metrics", GamingMetricsEvent {
                         session_id: session_id.clone(),
                         snapshot: snapshot.clone(),
@@ -188,6 +295,9 @@ impl GamingSessionManager {
                 thread::sleep(Duration::from_secs(1));
             }
 
+            if friends_away {
+                set_friends_presence(&app, PresenceStatus::Online, None);
+            }
             debug!("Session recording stopped");
         });
     }
@@ -318,6 +428,10 @@ impl GamingSessionManager {
         let vram_values: Vec<f32> = snapshots.iter().filter_map(|s| s.vram_percent).collect();
         let cpu_temp_values: Vec<f32> = snapshots.iter().filter_map(|s| s.cpu_temp).collect();
         let gpu_temp_values: Vec<f32> = snapshots.iter().filter_map(|s| s.gpu_temp).collect();
+        let gpu_power_values: Vec<f32> = snapshots.iter().filter_map(|s| s.gpu_power_watts).collect();
+        let cpu_clock_values: Vec<f32> = snapshots.iter().filter_map(|s| s.cpu_clock_mhz).collect();
+        let gpu_core_clock_values: Vec<f32> = snapshots.iter().filter_map(|s| s.gpu_core_clock_mhz).collect();
+        let gpu_memory_clock_values: Vec<f32> = snapshots.iter().filter_map(|s| s.gpu_memory_clock_mhz).collect();
 
         // Calculate top core statistics
         let top_core_1_values: Vec<f32> = snapshots.iter()
@@ -333,8 +447,13 @@ impl GamingSessionManager {
         let vram = if vram_values.is_empty() { None } else { Some(calculate_stats(&vram_values)) };
         let cpu_temp = if cpu_temp_values.is_empty() { None } else { Some(calculate_stats(&cpu_temp_values)) };
         let gpu_temp = if gpu_temp_values.is_empty() { None } else { Some(calculate_stats(&gpu_temp_values)) };
+        let gpu_power = if gpu_power_values.is_empty() { None } else { Some(calculate_stats(&gpu_power_values)) };
+        let cpu_clock = if cpu_clock_values.is_empty() { None } else { Some(calculate_stats(&cpu_clock_values)) };
+        let gpu_core_clock = if gpu_core_clock_values.is_empty() { None } else { Some(calculate_stats(&gpu_core_clock_values)) };
+        let gpu_memory_clock = if gpu_memory_clock_values.is_empty() { None } else { Some(calculate_stats(&gpu_memory_clock_values)) };
         let top_core_1 = if top_core_1_values.is_empty() { None } else { Some(calculate_stats(&top_core_1_values)) };
         let top_core_2 = if top_core_2_values.is_empty() { None } else { Some(calculate_stats(&top_core_2_values)) };
+        let top_core = find_dominant_top_core(snapshots);
 
         // Calculate bottleneck breakdown
         let bottleneck_breakdown = self.calculate_bottleneck_breakdown(events, duration);
@@ -356,11 +475,16 @@ impl GamingSessionManager {
             cpu,
             top_core_1,
             top_core_2,
+            top_core,
             gpu,
             ram,
             vram,
             cpu_temp,
             gpu_temp,
+            gpu_power,
+            cpu_clock,
+            gpu_core_clock,
+            gpu_memory_clock,
             total_bottleneck_seconds,
             dominant_bottleneck,
             bottleneck_breakdown,
@@ -427,20 +551,79 @@ impl GamingSessionManager {
     }
 }
 
+/// Whether the user's presence blocklist names this session's game, by display or process name
+fn is_blocklisted(blocklist: &[String], session: &GamingSession) -> bool {
+    let process = session.process_name.trim_end_matches(".exe").trim_end_matches(".EXE");
+    blocklist.iter().map(|entry| entry.trim()).any(|entry| {
+        entry.eq_ignore_ascii_case(&session.game_name)
+            || entry.trim_end_matches(".exe").trim_end_matches(".EXE").eq_ignore_ascii_case(process)
+    })
+}
+
+/// Update the friends presence without holding up the caller, as it syncs to the server
+/// Fails quietly when no friends account is set up
+fn set_friends_presence(app: &AppHandle, status: PresenceStatus, game: Option<&str>) {
+    let app = app.clone();
+    let request = UpdatePresenceRequest {
+        status: Some(status),
+        current_game: game.map(str::to_string),
+        mood_message: None,
+        performance_stats: None,
+    };
+    thread::spawn(move || {
+        if let Err(e) = crate::commands::friends::update_presence(app, request) {
+            debug!("Friends presence not updated: {}", e);
+        }
+    });
+}
+
+/// Slack between a game process starting and its session starting; covers the detection interval
+const SESSION_START_SLACK_SECONDS: i64 = 60;
+
+/// Session start in Unix seconds; now if the stored time can't be parsed
+fn session_start_timestamp(session: &GamingSession) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(&session.start_time)
+        .map(|t| t.timestamp())
+        .unwrap_or_else(|_| chrono::Utc::now().timestamp())
+}
+
+/// A session left active for this process, started after the process was
+/// One that started earlier belonged to a previous run of the game
+fn find_recoverable_session<'a>(
+    sessions: &'a [GamingSession],
+    process_name: &str,
+    process_started_at: i64,
+) -> Option<&'a GamingSession> {
+    sessions.iter().rev().find(|s| {
+        s.status == SessionStatus::Active
+            && s.end_time.is_none()
+            && s.process_name.eq_ignore_ascii_case(process_name)
+            && chrono::DateTime::parse_from_rfc3339(&s.start_time)
+                .is_ok_and(|t| t.timestamp() >= process_started_at - SESSION_START_SLACK_SECONDS)
+    })
+}
+
 /// Convert SystemMetrics to gaming MetricsSnapshot
+/// GPU values come from whichever adapter was busiest this tick, regardless of the pinned primary GPU
 fn convert_to_snapshot(metrics: &crate::models::performance::SystemMetrics) -> MetricsSnapshot {
-    let gpu_percent = metrics.gpu.as_ref().map(|g| g.usage_percent);
-    let vram_percent = metrics.gpu.as_ref().map(|g| {
+    let gpu = busiest_gpu(&metrics.gpus).or(metrics.gpu.as_ref());
+    let gpu_percent = gpu.map(|g| g.usage_percent);
+    let vram_percent = gpu.map(|g| {
         if g.memory_total_mb > 0 {
             (g.memory_used_mb as f32 / g.memory_total_mb as f32) * 100.0
         } else {
             0.0
         }
     });
-    let gpu_temp = metrics.gpu.as_ref().and_then(|g| g.temperature_celsius);
+    let gpu_temp = gpu.and_then(|g| g.temperature_celsius);
+    let gpu_power_watts = gpu.and_then(|g| g.power_draw_watts);
+    let gpu_power_limit_watts = gpu.and_then(|g| g.power_limit_watts);
+    let gpu_fan_percent = gpu.and_then(|g| g.fan_speed_percent);
+    let gpu_core_clock_mhz = gpu.and_then(|g| g.core_clock_mhz).map(|c| c as f32);
+    let gpu_memory_clock_mhz = gpu.and_then(|g| g.memory_clock_mhz).map(|c| c as f32);
 
     // Calculate top 2 CPU cores
-    let (top_core_1, top_core_2) = get_top_two_cores(&metrics.cpu.per_core_usage);
+    let (top_core_1, top_core_2) = get_top_two_cores(&metrics.cpu);
 
     MetricsSnapshot {
         timestamp: metrics.timestamp,
@@ -452,11 +635,20 @@ fn convert_to_snapshot(metrics: &crate::models::performance::SystemMetrics) -> M
         vram_percent,
         cpu_temp: metrics.cpu.temperature_celsius,
         gpu_temp,
+        gpu_power_watts,
+        gpu_power_limit_watts,
+        gpu_fan_percent,
+        cpu_clock_mhz: metrics.cpu.average_frequency_mhz.map(|f| f as f32),
+        gpu_core_clock_mhz,
+        gpu_memory_clock_mhz,
+        ram_available_mb: (metrics.ram.total_mb > 0).then_some(metrics.ram.available_mb as f32),
+        commit_percent: metrics.ram.commit_percent(),
     }
 }
 
-/// Get the top 2 highest CPU cores by usage
-fn get_top_two_cores(per_core_usage: &[f32]) -> (Option<TopCoreInfo>, Option<TopCoreInfo>) {
+/// Get the top 2 highest CPU cores by usage, labelled with core kind and temperature
+fn get_top_two_cores(cpu: &crate::models::performance::CpuMetrics) -> (Option<TopCoreInfo>, Option<TopCoreInfo>) {
+    let per_core_usage = &cpu.per_core_usage;
     if per_core_usage.is_empty() {
         return (None, None);
     }
@@ -471,19 +663,46 @@ fn get_top_two_cores(per_core_usage: &[f32]) -> (Option<TopCoreInfo>, Option<Top
     // Sort by usage descending
     indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-    let top1 = indexed.first().map(|(idx, usage)| TopCoreInfo {
+    let to_info = |(idx, usage): &(usize, f32)| TopCoreInfo {
         core_index: *idx,
         usage_percent: *usage,
-    });
+        core_kind: cpu.core_kinds.as_ref().and_then(|kinds| kinds.get(*idx).copied()),
+        temperature_celsius: cpu.per_core_temperature.get(*idx).copied().flatten(),
+    };
 
-    let top2 = indexed.get(1).map(|(idx, usage)| TopCoreInfo {
-        core_index: *idx,
-        usage_percent: *usage,
-    });
+    let top1 = indexed.first().map(to_info);
+    let top2 = indexed.get(1).map(to_info);
 
     (top1, top2)
 }
 
+/// Find the core that was most often the busiest during the session
+/// Usage is averaged over the ticks it was on top; temperature is the max seen
+fn find_dominant_top_core(snapshots: &[MetricsSnapshot]) -> Option<TopCoreInfo> {
+    use std::collections::HashMap;
+
+    let mut per_core: HashMap<usize, (usize, f32, Option<f32>, Option<crate::models::performance::CoreKind>)> = HashMap::new();
+
+    for core in snapshots.iter().filter_map(|s| s.top_core_1.as_ref()) {
+        let entry = per_core.entry(core.core_index).or_insert((0, 0.0, None, core.core_kind));
+        entry.0 += 1;
+        entry.1 += core.usage_percent;
+        if let Some(temp) = core.temperature_celsius {
+            entry.2 = Some(entry.2.map_or(temp, |t: f32| t.max(temp)));
+        }
+    }
+
+    per_core
+        .into_iter()
+        .max_by(|a, b| a.1 .0.cmp(&b.1 .0).then(b.0.cmp(&a.0)))
+        .map(|(core_index, (count, usage_sum, max_temp, core_kind))| TopCoreInfo {
+            core_index,
+            usage_percent: usage_sum / count as f32,
+            core_kind,
+            temperature_celsius: max_temp,
+        })
+}
+
 /// Calculate statistics for a list of values
 fn calculate_stats(values: &[f32]) -> MetricStats {
     if values.is_empty() {
@@ -567,4 +786,49 @@ mod tests {
 
         assert_eq!(metrics_emit_count, cycles, "Metrics should emit every cycle");
     }
+
+    fn session(process_name: &str, start_time: &str, status: SessionStatus) -> GamingSession {
+        GamingSession {
+            id: start_time.to_string(),
+            game_name: "Game".to_string(),
+            process_name: process_name.to_string(),
+            start_time: start_time.to_string(),
+            end_time: None,
+            status,
+            summary: None,
+        }
+    }
+
+    /// Test: only an active session started during the running process is recovered
+    #[test]
+    fn test_find_recoverable_session() {
+        let process_started_at = chrono::DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .timestamp();
+        let sessions = vec![
+            session("Game.exe", "2024-05-01T10:00:00Z", SessionStatus::Active),
+            session("game.exe", "2024-05-01T12:00:03Z", SessionStatus::Active),
+            session("Game.exe", "2024-05-01T12:00:05Z", SessionStatus::Completed),
+            session("Other.exe", "2024-05-01T12:00:05Z", SessionStatus::Active),
+        ];
+
+        let found = find_recoverable_session(&sessions, "Game.exe", process_started_at).unwrap();
+        assert_eq!(found.start_time, "2024-05-01T12:00:03Z");
+        assert_eq!(session_start_timestamp(found), process_started_at + 3);
+        assert!(find_recoverable_session(&sessions[..1], "Game.exe", process_started_at).is_none());
+    }
+
+    /// Test: blocklist entries match the game or its process, ignoring case and ".exe"
+    #[test]
+    fn test_is_blocklisted() {
+        let game = GamingSession {
+            game_name: "Elden Ring".to_string(),
+            ..session("eldenring.exe", "2024-05-01T12:00:00Z", SessionStatus::Active)
+        };
+        assert!(is_blocklisted(&["elden ring".to_string()], &game));
+        assert!(is_blocklisted(&[" EldenRing ".to_string()], &game));
+        assert!(is_blocklisted(&["eldenring.exe".to_string()], &game));
+        assert!(!is_blocklisted(&["Elden".to_string(), "Dark Souls".to_string()], &game));
+        assert!(!is_blocklisted(&[], &game));
+    }
 }
@@ -0,0 +1,225 @@
+// Battle.net (Blizzard) detector
+// Reads the per-game uninstall registry entries (read-only). Games are launched through Battle.net,
+// which most titles require anyway
+#![cfg_attr(not(windows), allow(dead_code, unused_imports))]
+
+use std::path::PathBuf;
+
+#[cfg(windows)]
+use winreg::enums::*;
+#[cfg(windows)]
+use winreg::RegKey;
+
+use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir};
+use crate::models::launcher::{DetectedGame, GameSource};
+
+/// Prefix of the app_id given to Battle.net games, followed by the launch code
+pub const BATTLENET_APP_ID_PREFIX: &str = "battlenet_";
+
+#[derive(Debug, Clone)]
+pub struct BattleNetGameConfig {
+    pub name: &'static str,
+    /// `--uid=` value in the game's uninstall entry
+    pub uid: &'static str,
+    /// Product code Battle.net's `--exec="launch <code>"` expects
+    pub launch_code: &'static str,
+    /// Game executable relative to the install folder
+    pub game_exe_path: &'static str,
+}
+
+impl BattleNetGameConfig {
+    pub const WORLD_OF_WARCRAFT: BattleNetGameConfig = BattleNetGameConfig {
+        name: "World of Warcraft",
+        uid: "wow",
+        launch_code: "WoW",
+        game_exe_path: r"_retail_\Wow.exe",
+    };
+
+    pub const OVERWATCH: BattleNetGameConfig = BattleNetGameConfig {
+        name: "Overwatch 2",
+        uid: "prometheus",
+        launch_code: "Pro",
+        game_exe_path: r"_retail_\Overwatch.exe",
+    };
+
+    pub const DIABLO_IV: BattleNetGameConfig = BattleNetGameConfig {
+        name: "Diablo IV",
+        uid: "fenris",
+        launch_code: "Fen",
+        game_exe_path: r"Diablo IV.exe",
+    };
+
+    pub const DIABLO_III: BattleNetGameConfig = BattleNetGameConfig {
+        name: "Diablo III",
+        uid: "diablo3",
+        launch_code: "D3",
+        game_exe_path: r"x64\Diablo III64.exe",
+    };
+
+    pub const DIABLO_II_RESURRECTED: BattleNetGameConfig = BattleNetGameConfig {
+        name: "Diablo II: Resurrected",
+        uid: "osi",
+        launch_code: "OSI",
+        game_exe_path: r"D2R.exe",
+    };
+
+    pub const HEARTHSTONE: BattleNetGameConfig = BattleNetGameConfig {
+        name: "Hearthstone",
+        uid: "hs_beta",
+        launch_code: "WTCG",
+        game_exe_path: r"Hearthstone.exe",
+    };
+
+    pub const WARCRAFT_III: BattleNetGameConfig = BattleNetGameConfig {
+        name: "Warcraft III: Reforged",
+        uid: "w3",
+        launch_code: "W3",
+        game_exe_path: r"_retail_\x86_64\Warcraft III.exe",
+    };
+
+    pub fn all() -> Vec<BattleNetGameConfig> {
+        vec![
+            Self::WORLD_OF_WARCRAFT,
+            Self::OVERWATCH,
+            Self::DIABLO_IV,
+            Self::DIABLO_III,
+            Self::DIABLO_II_RESURRECTED,
+            Self::HEARTHSTONE,
+            Self::WARCRAFT_III,
+        ]
+    }
+
+    pub fn find_by_uid(uid: &str) -> Option<BattleNetGameConfig> {
+        Self::all().into_iter().find(|c| c.uid.eq_ignore_ascii_case(uid))
+    }
+
+    pub fn find_by_launch_code(code: &str) -> Option<BattleNetGameConfig> {
+        Self::all().into_iter().find(|c| c.launch_code.eq_ignore_ascii_case(code))
+    }
+
+    pub fn get_launch_args(&self) -> String {
+        format!("--exec=\"launch {}\"", self.launch_code)
+    }
+}
+
+/// The `--uid=` value from a Blizzard uninstall string
+/// e.g. `"...\Blizzard Uninstaller.exe" --lang=enUS --uid=wow --displayname="World of Warcraft"`
+fn parse_uninstall_uid(uninstall_string: &str) -> Option<String> {
+    uninstall_string
+        .split_whitespace()
+        .find_map(|arg| arg.strip_prefix("--uid="))
+        .map(|uid| uid.trim_matches('"').to_string())
+        .filter(|uid| !uid.is_empty())
+}
+
+/// Blizzard installs are 32-bit-registered, so the WOW6432Node view comes first
+#[cfg(windows)]
+fn open_uninstall_keys() -> Vec<RegKey> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    [
+        r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ]
+    .iter()
+    .filter_map(|path| hklm.open_subkey(path).ok())
+    .collect()
+}
+
+/// Battle.net.exe, from its uninstall entry or the default install folder
+#[cfg(windows)]
+pub fn find_battlenet_client() -> Option<PathBuf> {
+    for uninstall_key in open_uninstall_keys() {
+        if let Ok(key) = uninstall_key.open_subkey("Battle.net") {
+            if let Ok(install_path) = key.get_value::<String, _>("InstallLocation") {
+                let client = PathBuf::from(install_path).join("Battle.net.exe");
+                if client.exists() {
+                    return Some(client);
+                }
+            }
+        }
+    }
+
+    std::env::var_os("ProgramFiles(x86)")
+        .map(|p| PathBuf::from(p).join("Battle.net").join("Battle.net.exe"))
+        .filter(|p| p.exists())
+}
+
+#[cfg(not(windows))]
+pub fn find_battlenet_client() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(windows)]
+pub fn detect_battlenet_games() -> Vec<DetectedGame> {
+    let mut games = Vec::new();
+
+    for uninstall_key in open_uninstall_keys() {
+        for key_name in uninstall_key.enum_keys().filter_map(|k| k.ok()) {
+            let Ok(subkey) = uninstall_key.open_subkey(&key_name) else {
+                continue;
+            };
+
+            let Some(config) = subkey
+                .get_value::<String, _>("UninstallString")
+                .ok()
+                .and_then(|s| parse_uninstall_uid(&s))
+                .and_then(|uid| BattleNetGameConfig::find_by_uid(&uid))
+            else {
+                continue;
+            };
+
+            let app_id = format!("{}{}", BATTLENET_APP_ID_PREFIX, config.launch_code);
+            if games.iter().any(|g: &DetectedGame| g.app_id.as_ref() == Some(&app_id)) {
+                continue;
+            }
+
+            let Ok(install_location) = subkey.get_value::<String, _>("InstallLocation") else {
+                continue;
+            };
+            let install_path = PathBuf::from(install_location);
+            let game_exe_path = install_path.join(config.game_exe_path);
+            if !game_exe_path.exists() {
+                continue;
+            }
+
+            let icon_path = get_icon_cache_dir().and_then(|cache_dir| extract_icon_from_exe(&game_exe_path, &cache_dir));
+
+            games.push(DetectedGame {
+                name: config.name.to_string(),
+                executable_path: game_exe_path.to_string_lossy().to_string(),
+                install_path: install_path.to_string_lossy().to_string(),
+                source: GameSource::BattleNet,
+                app_id: Some(app_id),
+                icon_path,
+                launch_args: None,
+            });
+        }
+    }
+
+    games
+}
+
+#[cfg(not(windows))]
+pub fn detect_battlenet_games() -> Vec<DetectedGame> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_uninstall_uid() {
+        let uninstall = r#""C:\ProgramData\Battle.net\Agent\Blizzard Uninstaller.exe" --lang=enUS --uid=wow --displayname="World of Warcraft""#;
+        assert_eq!(parse_uninstall_uid(uninstall), Some("wow".to_string()));
+        assert_eq!(parse_uninstall_uid(r#""C:\Games\uninstall.exe" /S"#), None);
+    }
+
+    #[test]
+    fn test_battlenet_game_configs() {
+        let overwatch = BattleNetGameConfig::find_by_uid("prometheus").unwrap();
+        assert_eq!(overwatch.get_launch_args(), "--exec=\"launch Pro\"");
+        assert_eq!(BattleNetGameConfig::find_by_launch_code("wow").unwrap().uid, "wow");
+        assert!(BattleNetGameConfig::find_by_uid("battle.net").is_none());
+    }
+}
@@ -0,0 +1,222 @@
+// EA app detector
+// Install folders come from the `Install Dir` values under the Electronic Arts / EA Games registry
+// keys (read-only); each install's __Installer\installerdata.xml gives the title, the offer ids the
+// EA app launches by, and the game exe. The EA app's own content database is encrypted, so it isn't read
+#![cfg_attr(not(windows), allow(dead_code, unused_imports))]
+
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use winreg::enums::*;
+#[cfg(windows)]
+use winreg::RegKey;
+
+use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir};
+use crate::models::launcher::{DetectedGame, GameSource};
+
+/// Prefix of the app_id given to EA games, followed by their comma-separated offer ids
+pub const EA_APP_ID_PREFIX: &str = "ea_";
+
+/// Registry keys holding one subkey per installed game
+const EA_REGISTRY_ROOTS: &[&str] = &[
+    r"SOFTWARE\WOW6432Node\Electronic Arts",
+    r"SOFTWARE\WOW6432Node\EA Games",
+    r"SOFTWARE\Electronic Arts",
+    r"SOFTWARE\EA Games",
+];
+
+/// Subkeys of the roots that belong to the EA app itself
+const EA_CLIENT_KEYS: &[&str] = &["ea desktop", "ea core", "origin"];
+
+/// EA app protocol URL that starts a game by its offer ids
+pub fn ea_launch_url(offer_ids: &str) -> String {
+    format!("origin2://game/launch?offerIds={}&autoDownload=1", offer_ids)
+}
+
+/// What installerdata.xml says about a game
+#[derive(Debug, PartialEq)]
+struct EaInstallerData {
+    title: Option<String>,
+    content_ids: Vec<String>,
+    /// Relative to the install folder
+    exe_path: Option<String>,
+}
+
+/// Text content of every `<tag ...>value</tag>` in `xml`
+fn tag_values<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut values = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Don't match longer tag names sharing the prefix (<contentIDs> vs <contentID>)
+        if !after.starts_with('>') && !after.starts_with(' ') {
+            rest = after;
+            continue;
+        }
+        let Some(attrs_end) = after.find('>') else {
+            break;
+        };
+        let body = &after[attrs_end + 1..];
+        let Some(end) = body.find(&close) else {
+            break;
+        };
+        values.push((&after[..attrs_end], body[..end].trim()));
+        rest = &body[end + close.len()..];
+    }
+
+    values
+}
+
+fn parse_installer_data(xml: &str) -> EaInstallerData {
+    let titles = tag_values(xml, "gameTitle");
+    let title = titles
+        .iter()
+        .find(|(attrs, _)| attrs.contains("en_US"))
+        .or(titles.first())
+        .map(|(_, title)| title.to_string())
+        .filter(|t| !t.is_empty());
+
+    let content_ids = tag_values(xml, "contentID")
+        .into_iter()
+        .map(|(_, id)| id.to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+
+    // "[HKEY_LOCAL_MACHINE\SOFTWARE\Respawn\Apex\Install Dir]r5apex.exe" - the bracketed part is the install folder
+    let exe_path = tag_values(xml, "filePath")
+        .into_iter()
+        .map(|(_, path)| path.rsplit(']').next().unwrap_or(path).trim_start_matches(['\\', '/']))
+        .find(|path| path.to_lowercase().ends_with(".exe"))
+        .map(|path| path.to_string());
+
+    EaInstallerData {
+        title,
+        content_ids,
+        exe_path,
+    }
+}
+
+/// A detected game from one install folder, or None if it isn't a launchable EA app install
+fn game_from_install(install_dir: &Path) -> Option<DetectedGame> {
+    let xml = std::fs::read_to_string(install_dir.join("__Installer").join("installerdata.xml")).ok()?;
+    let data = parse_installer_data(&xml);
+    if data.content_ids.is_empty() {
+        return None;
+    }
+
+    let exe_path = install_dir.join(data.exe_path?);
+    if !exe_path.exists() {
+        return None;
+    }
+
+    let name = data
+        .title
+        .or_else(|| install_dir.file_name().map(|n| n.to_string_lossy().to_string()))?;
+    let icon_path = get_icon_cache_dir().and_then(|cache_dir| extract_icon_from_exe(&exe_path, &cache_dir));
+
+    Some(DetectedGame {
+        name,
+        executable_path: exe_path.to_string_lossy().to_string(),
+        install_path: install_dir.to_string_lossy().to_string(),
+        source: GameSource::Ea,
+        app_id: Some(format!("{}{}", EA_APP_ID_PREFIX, data.content_ids.join(","))),
+        icon_path,
+        launch_args: None,
+    })
+}
+
+#[cfg(windows)]
+fn find_install_dirs() -> Vec<PathBuf> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let mut dirs: Vec<PathBuf> = Vec::new();
+
+    for root in EA_REGISTRY_ROOTS {
+        let Ok(root_key) = hklm.open_subkey(root) else {
+            continue;
+        };
+        for key_name in root_key.enum_keys().filter_map(|k| k.ok()) {
+            if EA_CLIENT_KEYS.contains(&key_name.to_lowercase().as_str()) {
+                continue;
+            }
+            let Ok(install_dir) = root_key
+                .open_subkey(&key_name)
+                .and_then(|key| key.get_value::<String, _>("Install Dir"))
+            else {
+                continue;
+            };
+            let dir = PathBuf::from(install_dir.trim_end_matches(['\\', '/']));
+            if dir.exists() && !dirs.iter().any(|d| d.to_string_lossy().eq_ignore_ascii_case(&dir.to_string_lossy())) {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    dirs
+}
+
+#[cfg(windows)]
+pub fn detect_ea_games() -> Vec<DetectedGame> {
+    let mut games: Vec<DetectedGame> = Vec::new();
+
+    for dir in find_install_dirs() {
+        if let Some(game) = game_from_install(&dir) {
+            if !games.iter().any(|g| g.app_id == game.app_id) {
+                games.push(game);
+            }
+        }
+    }
+
+    games
+}
+
+#[cfg(not(windows))]
+pub fn detect_ea_games() -> Vec<DetectedGame> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_installer_data() {
+        let xml = r#"<DiPManifest version="4.0">
+            <contentIDs>
+                <contentID>1035052</contentID>
+                <contentID>196716</contentID>
+            </contentIDs>
+            <gameTitles>
+                <gameTitle locale="de_DE">Apex Legends DE</gameTitle>
+                <gameTitle locale="en_US">Apex Legends</gameTitle>
+            </gameTitles>
+            <runtime>
+                <launcher uid="1">
+                    <filePath>[HKEY_LOCAL_MACHINE\SOFTWARE\Respawn\Apex\Install Dir]r5apex.exe</filePath>
+                </launcher>
+            </runtime>
+        </DiPManifest>"#;
+
+        assert_eq!(
+            parse_installer_data(xml),
+            EaInstallerData {
+                title: Some("Apex Legends".to_string()),
+                content_ids: vec!["1035052".to_string(), "196716".to_string()],
+                exe_path: Some("r5apex.exe".to_string()),
+            }
+        );
+
+        let empty = parse_installer_data("<DiPManifest></DiPManifest>");
+        assert!(empty.content_ids.is_empty() && empty.title.is_none() && empty.exe_path.is_none());
+    }
+
+    #[test]
+    fn test_ea_launch_url() {
+        assert_eq!(
+            ea_launch_url("1035052,196716"),
+            "origin2://game/launch?offerIds=1035052,196716&autoDownload=1"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// Epic Games Store detector
+// Reads the launcher's install manifests (read-only). Games are launched through the launcher URL
+// rather than their exe so Epic's online auth works
+#![cfg_attr(not(windows), allow(dead_code, unused_imports))]
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::launcher::icon_extractor::{download_epic_icon, extract_icon_from_exe, get_icon_cache_dir};
+use crate::models::launcher::{DetectedGame, GameSource};
+
+/// Prefix of the app_id given to Epic games, followed by the manifest's AppName
+pub const EPIC_APP_ID_PREFIX: &str = "epic_";
+
+/// The fields of an `.item` manifest the detector needs
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EpicManifest {
+    display_name: String,
+    install_location: String,
+    launch_executable: String,
+    app_name: String,
+    #[serde(default)]
+    main_game_app_name: Option<String>,
+    #[serde(default)]
+    catalog_namespace: String,
+    #[serde(default)]
+    catalog_item_id: String,
+    #[serde(default)]
+    app_categories: Vec<String>,
+    #[serde(default, rename = "bIsIncompleteInstall")]
+    is_incomplete_install: bool,
+}
+
+/// Parse a manifest, skipping DLC, half-finished installs and non-game apps (e.g. Unreal Engine)
+fn parse_manifest(content: &str) -> Option<EpicManifest> {
+    let manifest: EpicManifest = serde_json::from_str(content).ok()?;
+
+    if manifest.is_incomplete_install || manifest.launch_executable.is_empty() {
+        return None;
+    }
+    if manifest.main_game_app_name.as_ref().is_some_and(|main| *main != manifest.app_name) {
+        return None;
+    }
+    if !manifest.app_categories.is_empty() && !manifest.app_categories.iter().any(|c| c == "games") {
+        return None;
+    }
+
+    Some(manifest)
+}
+
+/// Launcher URL that starts a game by its AppName
+pub fn epic_launch_url(app_name: &str) -> String {
+    format!("com.epicgames.launcher://apps/{}?action=launch&silent=true", app_name)
+}
+
+fn get_manifests_dir() -> PathBuf {
+    let program_data = std::env::var_os("PROGRAMDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"));
+
+    program_data.join("Epic").join("EpicGamesLauncher").join("Data").join("Manifests")
+}
+
+#[cfg(windows)]
+pub fn detect_epic_games() -> Vec<DetectedGame> {
+    let mut games = Vec::new();
+
+    let entries = match std::fs::read_dir(get_manifests_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return games,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().map(|e| e.to_ascii_lowercase() != "item").unwrap_or(true) {
+            continue;
+        }
+
+        let Some(manifest) = std::fs::read_to_string(&path).ok().and_then(|c| parse_manifest(&c)) else {
+            continue;
+        };
+
+        let install_path = PathBuf::from(&manifest.install_location);
+        let exe_path = install_path.join(&manifest.launch_executable);
+        if !exe_path.exists() {
+            continue;
+        }
+
+        let app_id = format!("{}{}", EPIC_APP_ID_PREFIX, manifest.app_name);
+        if games.iter().any(|g: &DetectedGame| g.app_id.as_ref() == Some(&app_id)) {
+            continue;
+        }
+
+        let icon_path = get_icon_cache_dir().and_then(|cache_dir| {
+            extract_icon_from_exe(&exe_path, &cache_dir).or_else(|| {
+                download_epic_icon(
+                    &manifest.catalog_namespace,
+                    &manifest.catalog_item_id,
+                    &manifest.app_name,
+                    &cache_dir,
+                )
+            })
+        });
+
+        games.push(DetectedGame {
+            name: manifest.display_name,
+            executable_path: exe_path.to_string_lossy().to_string(),
+            install_path: install_path.to_string_lossy().to_string(),
+            source: GameSource::Epic,
+            app_id: Some(app_id),
+            icon_path,
+            launch_args: None,
+        });
+    }
+
+    games
+}
+
+#[cfg(not(windows))]
+pub fn detect_epic_games() -> Vec<DetectedGame> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let game = r#"{
+            "DisplayName": "Fortnite",
+            "InstallLocation": "C:\\Program Files\\Epic Games\\Fortnite",
+            "LaunchExecutable": "FortniteGame/Binaries/Win64/FortniteLauncher.exe",
+            "AppName": "Fortnite",
+            "MainGameAppName": "Fortnite",
+            "CatalogNamespace": "fn",
+            "CatalogItemId": "4fe75bbc5a674f4f9b356b5c90567da5",
+            "AppCategories": ["public", "games", "applications"],
+            "bIsIncompleteInstall": false
+        }"#;
+        let manifest = parse_manifest(game).unwrap();
+        assert_eq!(manifest.display_name, "Fortnite");
+        assert_eq!(manifest.catalog_namespace, "fn");
+
+        let dlc = game.replace(r#""MainGameAppName": "Fortnite""#, r#""MainGameAppName": "Other""#);
+        assert!(parse_manifest(&dlc).is_none());
+
+        let incomplete = game.replace(r#""bIsIncompleteInstall": false"#, r#""bIsIncompleteInstall": true"#);
+        assert!(parse_manifest(&incomplete).is_none());
+
+        let engine = game.replace(r#"["public", "games", "applications"]"#, r#"["engines"]"#);
+        assert!(parse_manifest(&engine).is_none());
+    }
+
+    #[test]
+    fn test_epic_launch_url() {
+        assert_eq!(
+            epic_launch_url("Fortnite"),
+            "com.epicgames.launcher://apps/Fortnite?action=launch&silent=true"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+// Per-game pre-launch / post-exit commands
+// Run hidden through the shell. A command still running at its timeout is left alone rather than
+// killed, so helpers like controller remappers can be started directly
+
+use log::{info, warn};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+use crate::models::GameHookCommand;
+
+/// Timeout when the hook doesn't set one
+const DEFAULT_HOOK_TIMEOUT_SECS: u32 = 10;
+
+/// Longest a hook may hold up its launch or the end of its session
+pub const MAX_HOOK_TIMEOUT_SECS: u32 = 300;
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]).creation_flags(CREATE_NO_WINDOW);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// Check a hook before saving it
+pub fn validate_hook(hook: &GameHookCommand) -> Result<(), String> {
+    if hook.command.trim().is_empty() {
+        return Err("Hook command is empty".to_string());
+    }
+    if let Some(dir) = &hook.working_dir {
+        if !std::path::Path::new(dir).is_dir() {
+            return Err(format!("Working directory does not exist: {}", dir));
+        }
+    }
+    if hook.timeout_seconds.is_some_and(|t| t > MAX_HOOK_TIMEOUT_SECS) {
+        return Err(format!("Hook timeout must be at most {} seconds", MAX_HOOK_TIMEOUT_SECS));
+    }
+    Ok(())
+}
+
+/// Run a hook and wait up to its timeout; blocks, so async callers run it with spawn_blocking
+/// Ok(Some(code)) when it finished with exit code 0, Ok(None) when it was still running at the timeout
+pub fn run_hook(hook: &GameHookCommand, label: &str) -> Result<Option<i32>, String> {
+    let mut cmd = shell_command(&hook.command);
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    if let Some(dir) = &hook.working_dir {
+        cmd.current_dir(dir);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start {} command: {}", label, e))?;
+
+    // Hooks saved before the cap existed are held to it too
+    let timeout_secs = hook.timeout_seconds.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS).min(MAX_HOOK_TIMEOUT_SECS);
+    let timeout = Duration::from_secs(timeout_secs as u64);
+    let started = Instant::now();
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let code = status.code().unwrap_or(-1);
+                info!("{} command exited with code {}: {}", label, code, hook.command);
+                return if status.success() {
+                    Ok(Some(code))
+                } else {
+                    Err(format!("{} command exited with code {}", label, code))
+                };
+            }
+            Ok(None) if started.elapsed() >= timeout => {
+                warn!("{} command still running after {}s, leaving it running: {}", label, timeout.as_secs(), hook.command);
+                return Ok(None);
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("Failed to wait for {} command: {}", label, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(command: &str) -> GameHookCommand {
+        GameHookCommand {
+            command: command.to_string(),
+            working_dir: None,
+            timeout_seconds: Some(5),
+            required: false,
+        }
+    }
+
+    #[test]
+    fn test_run_hook_exit_codes() {
+        assert_eq!(run_hook(&hook("exit 0"), "Test"), Ok(Some(0)));
+        assert!(run_hook(&hook("exit 3"), "Test").unwrap_err().contains("code 3"));
+    }
+
+    #[test]
+    fn test_validate_hook() {
+        assert!(validate_hook(&hook("  ")).is_err());
+        let mut missing_dir = hook("echo hi");
+        missing_dir.working_dir = Some("/definitely/not/a/real/dir".to_string());
+        assert!(validate_hook(&missing_dir).is_err());
+        assert!(validate_hook(&hook("echo hi")).is_ok());
+        let mut slow = hook("echo hi");
+        slow.timeout_seconds = Some(MAX_HOOK_TIMEOUT_SECS + 1);
+        assert!(validate_hook(&slow).is_err());
+    }
+}
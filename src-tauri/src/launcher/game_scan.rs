@@ -0,0 +1,109 @@
+// Background game scan state
+// One scan runs at a time. Detectors report progress and check for cancellation through the
+// free functions here, which are no-ops when no scan is active
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter};
+
+struct ActiveScan {
+    id: String,
+    app: AppHandle,
+    cancelled: bool,
+    games_found: usize,
+    /// CACHE_GENERATION when the scan started
+    cache_generation: u64,
+}
+
+lazy_static! {
+    static ref ACTIVE_SCAN: Mutex<Option<ActiveScan>> = Mutex::new(None);
+}
+
+/// Bumped when the scan cache is cleared, so scans started before that don't write their results
+static CACHE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// The running scan, tracked until this is dropped - also when the scan panics
+pub struct ScanGuard {
+    pub id: String,
+}
+
+impl Drop for ScanGuard {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_SCAN.lock();
+        if active.as_ref().is_some_and(|scan| scan.id == self.id) {
+            let scan = active.take();
+            // A scan that ended normally reports its result itself
+            if let Some(scan) = scan.filter(|_| std::thread::panicking()) {
+                let _ = scan.app.emit("launcher:scan_cancelled", json!({ "scan_id": scan.id }));
+            }
+        }
+    }
+}
+
+/// Start tracking a scan; Err(id of the running scan) if one is already in progress
+pub fn begin_scan(app: &AppHandle) -> Result<ScanGuard, String> {
+    let mut active = ACTIVE_SCAN.lock();
+    if let Some(scan) = active.as_ref() {
+        return Err(scan.id.clone());
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    *active = Some(ActiveScan {
+        id: id.clone(),
+        app: app.clone(),
+        cancelled: false,
+        games_found: 0,
+        cache_generation: CACHE_GENERATION.load(Ordering::SeqCst),
+    });
+    Ok(ScanGuard { id })
+}
+
+/// Emit `launcher:scan_progress` for the phase starting now
+pub fn report_phase(phase: &str) {
+    if let Some(scan) = ACTIVE_SCAN.lock().as_ref() {
+        let _ = scan.app.emit(
+            "launcher:scan_progress",
+            json!({ "scan_id": scan.id, "phase": phase, "games_found": scan.games_found }),
+        );
+    }
+}
+
+pub fn add_games_found(count: usize) {
+    if let Some(scan) = ACTIVE_SCAN.lock().as_mut() {
+        scan.games_found += count;
+    }
+}
+
+/// Checked between detectors and drives
+pub fn is_cancelled() -> bool {
+    ACTIVE_SCAN.lock().as_ref().is_some_and(|scan| scan.cancelled)
+}
+
+/// Returns false if no scan is running
+pub fn cancel_scan() -> bool {
+    match ACTIVE_SCAN.lock().as_mut() {
+        Some(scan) => {
+            scan.cancelled = true;
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn invalidate_cache() {
+    CACHE_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Whether the running scan may write its results to the scan cache
+pub fn can_write_cache() -> bool {
+    ACTIVE_SCAN
+        .lock()
+        .as_ref()
+        .is_some_and(|scan| !scan.cancelled && scan.cache_generation == CACHE_GENERATION.load(Ordering::SeqCst))
+}
+
+pub fn is_scanning() -> bool {
+    ACTIVE_SCAN.lock().is_some()
+}
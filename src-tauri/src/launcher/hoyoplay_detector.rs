@@ -1,4 +1,6 @@
 use crate::models::{DetectedGame, GameSource, HoyoPlayGameConfig};
+#[cfg(windows)]
+use crate::launcher::game_scan;
 use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir, download_hoyoplay_icon};
 use std::path::{Path, PathBuf};
 
@@ -386,12 +388,17 @@ pub fn find_hoyoplay_paths() -> Vec<PathBuf> {
                 continue;
             }
 
+            if game_scan::is_cancelled() {
+                break;
+            }
+
             let drive = format!("{}:\\", drive_letter);
             let drive_path = PathBuf::from(&drive);
 
             if !drive_path.exists() {
                 continue;
             }
+            game_scan::report_phase(&format!("HoYoPlay ({})", drive));
 
             // SAFETY: All operations here are path.join() and path.exists()
             let possible_paths = [
@@ -644,12 +651,17 @@ fn detect_standalone_from_folders() -> Vec<DetectedGame> {
                 continue;
             }
 
+            if game_scan::is_cancelled() {
+                break;
+            }
+
             let drive = format!("{}:\\", drive_letter);
             let drive_path = PathBuf::from(&drive);
 
             if !drive_path.exists() {
                 continue;
             }
+            game_scan::report_phase(&format!("HoYoPlay ({})", drive));
 
             // Common standalone installation patterns
             for config in HoyoPlayGameConfig::all() {
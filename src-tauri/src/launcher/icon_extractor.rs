@@ -1,21 +1,64 @@
 // Icon extractor for game executables
-// Extracts icons from .exe files on Windows using PowerShell
+// Reads the icon straight out of the exe's PE resources. The old PowerShell extraction is kept as a
+// fallback (the `powershell-icons` feature) for executables the native reader can't handle
 
 use log::warn;
-use std::io::Read;
+use std::io::{Cursor, Read};
 use std::path::Path;
-use std::process::Command;
 
-#[cfg(windows)]
+#[cfg(all(windows, feature = "powershell-icons"))]
 use std::os::windows::process::CommandExt;
+#[cfg(all(windows, feature = "powershell-icons"))]
+use std::process::Command;
 
 // Windows constant to hide console window
-#[cfg(windows)]
+#[cfg(all(windows, feature = "powershell-icons"))]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-/// Extract icon from an executable and save it as PNG (256x256 HD)
+/// The largest image in an ICO file, encoded as PNG
+/// Larger sizes win, then higher bit depth
+pub(crate) fn ico_to_png(ico: &[u8]) -> Option<Vec<u8>> {
+    let icon_dir = ico::IconDir::read(Cursor::new(ico)).ok()?;
+    let entry = icon_dir
+        .entries()
+        .iter()
+        .max_by_key(|e| (e.width() * e.height(), e.bits_per_pixel()))?;
+
+    let mut png = Vec::new();
+    entry.decode().ok()?.write_png(&mut png).ok()?;
+    Some(png)
+}
+
+/// Convert an .ico file to PNG at `output_path`
+pub(crate) fn ico_file_to_png(ico_path: &Path, output_path: &Path) -> Option<String> {
+    let png = ico_to_png(&std::fs::read(ico_path).ok()?)?;
+    std::fs::write(output_path, png).ok()?;
+    Some(output_path.to_string_lossy().to_string())
+}
+
+/// The exe's main icon (its first icon group) as an ICO file
+fn exe_icon_group(exe: &[u8]) -> Option<Vec<u8>> {
+    let pe = pelite::PeFile::from_bytes(exe).ok()?;
+    let resources = pe.resources().ok()?;
+    let (_, group) = resources.icons().find_map(Result::ok)?;
+
+    let mut ico = Vec::new();
+    group.write(&mut ico).ok()?;
+    Some(ico)
+}
+
+/// Read the largest icon out of an executable's resources and save it as PNG
+/// The exe is memory-mapped, so only the pages holding its headers and icon resources are read
+/// (game executables can be gigabytes)
+fn extract_icon_native(exe_path: &Path, output_path: &Path) -> Option<String> {
+    let exe = pelite::FileMap::open(exe_path).ok()?;
+    let png = ico_to_png(&exe_icon_group(exe.as_ref())?)?;
+    std::fs::write(output_path, png).ok()?;
+    Some(output_path.to_string_lossy().to_string())
+}
+
+/// Extract icon from an executable and save it as PNG (largest size available, usually 256x256)
 /// Returns the path to the saved icon file, or None if extraction failed
-#[cfg(windows)]
 pub fn extract_icon_from_exe(exe_path: &Path, output_dir: &Path) -> Option<String> {
     use std::fs;
 
@@ -40,12 +83,21 @@ pub fn extract_icon_from_exe(exe_path: &Path, output_dir: &Path) -> Option<Strin
         return None;
     }
 
+    if let Some(result) = extract_icon_native(exe_path, &output_path) {
+        return Some(result);
+    }
+
+    extract_icon_powershell(exe_path, &output_path)
+}
+
+#[cfg(all(windows, feature = "powershell-icons"))]
+fn extract_icon_powershell(exe_path: &Path, output_path: &Path) -> Option<String> {
     let exe_path_str = exe_path.to_string_lossy().to_string();
     let output_path_str = output_path.to_string_lossy().to_string();
 
     // Try advanced method first for high-quality 256x256 icons
     // Only fall back to simple method if advanced fails
-    if let Some(result) = extract_icon_advanced(&exe_path_str, &output_path_str, &output_path) {
+    if let Some(result) = extract_icon_advanced(&exe_path_str, &output_path_str, output_path) {
         return Some(result);
     }
 
@@ -53,8 +105,13 @@ pub fn extract_icon_from_exe(exe_path: &Path, output_dir: &Path) -> Option<Strin
     extract_icon_simple(&exe_path_str, &output_path_str)
 }
 
+#[cfg(not(all(windows, feature = "powershell-icons")))]
+fn extract_icon_powershell(_exe_path: &Path, _output_path: &Path) -> Option<String> {
+    None
+}
+
 /// Simple icon extraction using ExtractAssociatedIcon (more reliable)
-#[cfg(windows)]
+#[cfg(all(windows, feature = "powershell-icons"))]
 fn extract_icon_simple(exe_path_str: &str, output_path_str: &str) -> Option<String> {
     let ps_script = format!(
         r#"
@@ -101,7 +158,7 @@ try {{
 }
 
 /// Advanced icon extraction for higher quality (256x256) icons
-#[cfg(windows)]
+#[cfg(all(windows, feature = "powershell-icons"))]
 fn extract_icon_advanced(exe_path_str: &str, output_path_str: &str, output_path: &Path) -> Option<String> {
 
     // PowerShell script to extract largest icon (256x256 if available)
@@ -237,9 +294,45 @@ if ($icon) {{
     }
 }
 
-#[cfg(not(windows))]
-pub fn extract_icon_from_exe(_exe_path: &Path, _output_dir: &Path) -> Option<String> {
-    None
+/// Largest side of a user-chosen icon; bigger images are scaled down
+const MAX_CUSTOM_ICON_SIZE: u32 = 1024;
+
+/// Format of a PNG, JPEG or ICO image, by its magic bytes
+fn sniff_image_format(bytes: &[u8]) -> Option<image::ImageFormat> {
+    if bytes.starts_with(b"\x89PNG") {
+        Some(image::ImageFormat::Png)
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        Some(image::ImageFormat::Jpeg)
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        Some(image::ImageFormat::Ico)
+    } else {
+        None
+    }
+}
+
+/// Cache file of a game's user-chosen icon
+pub fn custom_icon_path(game_id: &str, output_dir: &Path) -> std::path::PathBuf {
+    output_dir.join(format!("custom_{}.png", game_id))
+}
+
+/// Save a user-chosen PNG/JPEG/ICO image as the game's custom icon (PNG, at most MAX_CUSTOM_ICON_SIZE)
+pub fn save_custom_icon(image_path: &Path, game_id: &str, output_dir: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(image_path).map_err(|e| format!("Failed to read image: {}", e))?;
+    let format = sniff_image_format(&bytes).ok_or_else(|| "Icon must be a PNG, JPEG or ICO image".to_string())?;
+    let image = image::load_from_memory_with_format(&bytes, format)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    let image = if image.width() > MAX_CUSTOM_ICON_SIZE || image.height() > MAX_CUSTOM_ICON_SIZE {
+        image.thumbnail(MAX_CUSTOM_ICON_SIZE, MAX_CUSTOM_ICON_SIZE)
+    } else {
+        image
+    };
+
+    std::fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create icon cache: {}", e))?;
+    let output_path = custom_icon_path(game_id, output_dir);
+    image
+        .save_with_format(&output_path, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to save icon: {}", e))?;
+    Ok(output_path.to_string_lossy().to_string())
 }
 
 /// Get the icon cache directory
@@ -434,3 +527,171 @@ pub fn download_riot_icon(game_id: &str, output_dir: &std::path::Path) -> Option
 
     None
 }
+
+/// Largest image `download_image` will save
+const MAX_IMAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Download an image and save it if it really is a PNG or JPEG (and no larger than MAX_IMAGE_BYTES)
+pub(crate) fn download_image(url: &str, output_path: &Path) -> Option<String> {
+    use std::fs;
+
+    let response = ureq::get(url).call().ok()?;
+    if response.status() != 200 {
+        return None;
+    }
+
+    let mut bytes = Vec::new();
+    response.into_reader().take(MAX_IMAGE_BYTES + 1).read_to_end(&mut bytes).ok()?;
+    if bytes.len() as u64 > MAX_IMAGE_BYTES {
+        warn!("Skipping oversized image from {}", url);
+        return None;
+    }
+    let is_valid_image = bytes.len() > 8
+        && ((bytes[0] == 0x89 && bytes[1] == 0x50 && bytes[2] == 0x4E && bytes[3] == 0x47) // PNG
+            || (bytes[0] == 0xFF && bytes[1] == 0xD8)); // JPEG
+    if !is_valid_image {
+        return None;
+    }
+
+    fs::write(output_path, &bytes).ok()?;
+    Some(output_path.to_string_lossy().to_string())
+}
+
+/// Cache file name for an Epic game's artwork
+/// `app_name` comes from the game's manifest, so anything but letters, digits, '-' and '_' is replaced
+fn epic_icon_file_name(app_name: &str) -> String {
+    let safe: String = app_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("epic_{}.jpg", safe)
+}
+
+/// Download Epic game artwork via the public catalog (used when the exe has no usable icon)
+/// Returns the path to the saved icon file, or None if the catalog or image couldn't be fetched
+pub fn download_epic_icon(
+    namespace: &str,
+    catalog_item_id: &str,
+    app_name: &str,
+    output_dir: &std::path::Path,
+) -> Option<String> {
+    use std::fs;
+
+    if namespace.is_empty() || catalog_item_id.is_empty() {
+        return None;
+    }
+
+    fs::create_dir_all(output_dir).ok()?;
+
+    let output_path = output_dir.join(epic_icon_file_name(app_name));
+
+    // If icon already exists, return it
+    if output_path.exists() {
+        return Some(output_path.to_string_lossy().to_string());
+    }
+
+    let catalog_url = format!(
+        "https://catalog-public-service-prod06.ol.epicgames.com/catalog/api/shared/namespace/{}/bulk/items?id={}&country=US&locale=en-US",
+        namespace, catalog_item_id
+    );
+    let catalog: serde_json::Value = ureq::get(&catalog_url).call().ok()?.into_json().ok()?;
+    let images = catalog.get(catalog_item_id)?.get("keyImages")?.as_array()?;
+
+    // Portrait box art first, like the Steam library_600x900 art
+    for image_type in ["DieselGameBoxTall", "DieselGameBox", "Thumbnail"] {
+        let url = images
+            .iter()
+            .find(|i| i.get("type").and_then(|t| t.as_str()) == Some(image_type))
+            .and_then(|i| i.get("url"))
+            .and_then(|u| u.as_str());
+
+        if let Some(path) = url.and_then(|u| download_image(u, &output_path)) {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_size(png: &[u8]) -> (u32, u32) {
+        let image = ico::IconImage::read_png(Cursor::new(png)).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn test_epic_icon_file_name() {
+        assert_eq!(epic_icon_file_name("Fortnite"), "epic_Fortnite.jpg");
+        assert_eq!(epic_icon_file_name("..\\..\\evil"), "epic_______evil.jpg");
+        assert_eq!(epic_icon_file_name("a/b:c"), "epic_a_b_c.jpg");
+    }
+
+    #[test]
+    fn test_ico_to_png_picks_largest_png_entry() {
+        let png = ico_to_png(include_bytes!("../../icons/icon.ico")).unwrap();
+        assert_eq!(png_size(&png), (256, 256));
+    }
+
+    #[test]
+    fn test_ico_to_png_decodes_bmp_entries() {
+        let mut icon_dir = ico::IconDir::new(ico::ResourceType::Icon);
+        for size in [16, 32, 24] {
+            let image = ico::IconImage::from_rgba_data(size, size, vec![0x80; (size * size * 4) as usize]);
+            icon_dir.add_entry(ico::IconDirEntry::encode_as_bmp(&image).unwrap());
+        }
+        let mut ico_bytes = Vec::new();
+        icon_dir.write(&mut ico_bytes).unwrap();
+
+        let png = ico_to_png(&ico_bytes).unwrap();
+        assert_eq!(png_size(&png), (32, 32));
+    }
+
+    #[test]
+    fn test_save_custom_icon() {
+        let dir = std::env::temp_dir().join(format!("atlas_icon_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let saved = save_custom_icon(Path::new("icons/icon.ico"), "game", &dir).unwrap();
+        assert_eq!(png_size(&std::fs::read(&saved).unwrap()), (256, 256));
+
+        let not_an_image = dir.join("notes.txt");
+        std::fs::write(&not_an_image, "hello").unwrap();
+        assert!(save_custom_icon(&not_an_image, "game", &dir).unwrap_err().contains("PNG, JPEG or ICO"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Resource-only PE32 with one icon group of 16x16, 48x48 (green) and 32x32 PNG icons
+    const ICON_GROUPS_EXE: &str = "src/launcher/fixtures/icon_groups.exe";
+
+    #[test]
+    fn test_exe_icon_group_keeps_every_icon() {
+        let ico_bytes = exe_icon_group(include_bytes!("fixtures/icon_groups.exe")).unwrap();
+        let icon_dir = ico::IconDir::read(Cursor::new(&ico_bytes)).unwrap();
+        let sizes: Vec<_> = icon_dir.entries().iter().map(|e| e.width()).collect();
+        assert_eq!(sizes, vec![16, 48, 32]);
+    }
+
+    #[test]
+    fn test_extract_icon_from_exe_caches_largest_icon() {
+        let dir = std::env::temp_dir().join(format!("atlas_exe_icon_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let saved = extract_icon_from_exe(Path::new(ICON_GROUPS_EXE), &dir).unwrap();
+        assert_eq!(Path::new(&saved), dir.join("icon_groups.png"));
+        let image = ico::IconImage::read_png(Cursor::new(std::fs::read(&saved).unwrap())).unwrap();
+        assert_eq!((image.width(), image.height()), (48, 48));
+        assert_eq!(&image.rgba_data()[..4], &[0, 255, 0, 255]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_non_pe_has_no_icon() {
+        assert!(exe_icon_group(b"MZ but not really an executable").is_none());
+        assert!(ico_to_png(b"not an icon").is_none());
+    }
+}
@@ -0,0 +1,244 @@
+// itch.io detector
+// Walks the itch app's install locations under %APPDATA%\itch and reads each game's butler receipt
+// (.itch\receipt.json.gz, read-only). Install locations added in newer itch versions only live in
+// butler's SQLite database, which isn't read - games there are found once the folder is in
+// preferences.json or the default apps folder
+#![cfg_attr(not(windows), allow(dead_code, unused_imports))]
+
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir};
+use crate::models::launcher::{DetectedGame, GameSource};
+
+/// Prefix of the app_id given to itch games, followed by the itch.io game id
+pub const ITCH_APP_ID_PREFIX: &str = "itch_";
+
+/// Executables shipped next to games that are never the game itself
+const NON_GAME_EXES: &[&str] = &["unins", "uninstall", "crashhandler", "crashreporter", "unitycrashhandler", "vc_redist", "dxsetup"];
+
+#[derive(Debug, Deserialize)]
+struct ItchReceipt {
+    game: ItchGame,
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItchGame {
+    id: u64,
+    title: String,
+    /// "game", "tool", "assets", "comic", ...
+    #[serde(default)]
+    classification: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItchPreferences {
+    #[serde(default, rename = "installLocations")]
+    install_locations: HashMap<String, ItchInstallLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItchInstallLocation {
+    path: String,
+}
+
+fn get_itch_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|appdata| appdata.join("itch"))
+}
+
+/// Parse a receipt, skipping anything itch doesn't classify as a game
+fn parse_receipt(content: &str) -> Option<ItchReceipt> {
+    let receipt: ItchReceipt = serde_json::from_str(content).ok()?;
+    if receipt.game.classification.as_deref().is_some_and(|c| c != "game") {
+        return None;
+    }
+    Some(receipt)
+}
+
+fn read_receipt(install_dir: &Path) -> Option<ItchReceipt> {
+    let file = std::fs::File::open(install_dir.join(".itch").join("receipt.json.gz")).ok()?;
+    let mut content = String::new();
+    GzDecoder::new(file).read_to_string(&mut content).ok()?;
+    parse_receipt(&content)
+}
+
+/// Exe paths of the actions in a game's .itch.toml manifest, "play" first
+/// Only the `[[actions]]` tables are read, so a full TOML parser isn't needed
+fn parse_manifest_actions(manifest: &str) -> Vec<String> {
+    let mut actions: Vec<(Option<String>, Option<String>)> = Vec::new();
+    let mut in_action = false;
+
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_action = line == "[[actions]]";
+            if in_action {
+                actions.push((None, None));
+            }
+            continue;
+        }
+        let (Some(action), Some((key, value))) = (actions.last_mut().filter(|_| in_action), line.split_once('=')) else {
+            continue;
+        };
+        let value = value.trim().trim_matches(['"', '\'']).to_string();
+        match key.trim() {
+            "name" => action.0 = Some(value),
+            "path" => action.1 = Some(value),
+            _ => {}
+        }
+    }
+
+    let mut exe_actions: Vec<(bool, String)> = actions
+        .into_iter()
+        .filter_map(|(name, path)| {
+            let path = path.filter(|p| p.to_lowercase().ends_with(".exe"))?;
+            Some((name.as_deref() != Some("play"), path))
+        })
+        .collect();
+    // Stable sort keeps manifest order among the non-"play" actions
+    exe_actions.sort_by_key(|(not_play, _)| *not_play);
+    exe_actions.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Fallback when there's no manifest: the shallowest exe in the receipt's file list that isn't a known helper
+fn pick_receipt_exe(files: &[String]) -> Option<&str> {
+    files
+        .iter()
+        .filter(|f| f.to_lowercase().ends_with(".exe"))
+        .filter(|f| {
+            let name = f.rsplit(['/', '\\']).next().unwrap_or(f).to_lowercase();
+            !NON_GAME_EXES.iter().any(|skip| name.contains(skip))
+        })
+        .min_by_key(|f| f.matches(['/', '\\']).count())
+        .map(|f| f.as_str())
+}
+
+/// Folders games are installed into: the default apps folder plus any listed in preferences.json
+fn find_install_locations(itch_dir: &Path) -> Vec<PathBuf> {
+    let mut locations = vec![itch_dir.join("apps")];
+
+    if let Some(preferences) = std::fs::read_to_string(itch_dir.join("preferences.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<ItchPreferences>(&c).ok())
+    {
+        for location in preferences.install_locations.into_values() {
+            let path = PathBuf::from(location.path);
+            if !locations.contains(&path) {
+                locations.push(path);
+            }
+        }
+    }
+
+    locations
+}
+
+fn game_from_install(install_dir: &Path) -> Option<DetectedGame> {
+    let receipt = read_receipt(install_dir)?;
+
+    let manifest_exes = std::fs::read_to_string(install_dir.join(".itch.toml"))
+        .map(|m| parse_manifest_actions(&m))
+        .unwrap_or_default();
+    let exe_path = manifest_exes
+        .iter()
+        .map(|p| install_dir.join(p))
+        .find(|p| p.exists())
+        .or_else(|| pick_receipt_exe(&receipt.files).map(|p| install_dir.join(p)))
+        .filter(|p| p.exists())?;
+
+    let icon_path = get_icon_cache_dir().and_then(|cache_dir| extract_icon_from_exe(&exe_path, &cache_dir));
+
+    Some(DetectedGame {
+        name: receipt.game.title,
+        executable_path: exe_path.to_string_lossy().to_string(),
+        install_path: install_dir.to_string_lossy().to_string(),
+        source: GameSource::Itch,
+        app_id: Some(format!("{}{}", ITCH_APP_ID_PREFIX, receipt.game.id)),
+        icon_path,
+        launch_args: None,
+    })
+}
+
+#[cfg(windows)]
+pub fn detect_itch_games() -> Vec<DetectedGame> {
+    let mut games: Vec<DetectedGame> = Vec::new();
+    let Some(itch_dir) = get_itch_dir().filter(|d| d.exists()) else {
+        return games;
+    };
+
+    for location in find_install_locations(&itch_dir) {
+        let Ok(entries) = std::fs::read_dir(&location) else {
+            continue;
+        };
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            if let Some(game) = game_from_install(&entry.path()) {
+                if !games.iter().any(|g| g.app_id == game.app_id) {
+                    games.push(game);
+                }
+            }
+        }
+    }
+
+    games
+}
+
+#[cfg(not(windows))]
+pub fn detect_itch_games() -> Vec<DetectedGame> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_receipt() {
+        let receipt = r#"{
+            "game": {"id": 1234, "title": "Celeste Classic", "classification": "game"},
+            "files": ["Celeste.exe", "data/level.bin"],
+            "installerName": "archive"
+        }"#;
+        let parsed = parse_receipt(receipt).unwrap();
+        assert_eq!(parsed.game.id, 1234);
+        assert_eq!(parsed.files.len(), 2);
+
+        let tool = receipt.replace(r#""classification": "game""#, r#""classification": "tool""#);
+        assert!(parse_receipt(&tool).is_none());
+    }
+
+    #[test]
+    fn test_parse_manifest_actions() {
+        let manifest = r#"
+[[actions]]
+name = "editor"
+path = "tools/editor.exe"
+
+[[actions]]
+name = "play"
+path = "bin/game.exe"
+
+[[actions]]
+name = "manual"
+path = "manual.pdf"
+
+[[prereqs]]
+name = "vcredist-2015-x64"
+"#;
+        assert_eq!(parse_manifest_actions(manifest), vec!["bin/game.exe", "tools/editor.exe"]);
+        assert!(parse_manifest_actions("").is_empty());
+    }
+
+    #[test]
+    fn test_pick_receipt_exe() {
+        let files = vec![
+            "UnityCrashHandler64.exe".to_string(),
+            "Game_Data/Plugins/helper.exe".to_string(),
+            "Game.exe".to_string(),
+        ];
+        assert_eq!(pick_receipt_exe(&files), Some("Game.exe"));
+        assert_eq!(pick_receipt_exe(&["readme.txt".to_string()]), None);
+    }
+}
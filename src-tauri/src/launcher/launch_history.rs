@@ -0,0 +1,89 @@
+// Launch history
+// Every launch_game call is recorded in launch_history.json (with the exit time once the playtime
+// tracker sees the game close) to drive the recently played list
+
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::{LaunchHistory, LaunchRecord};
+use crate::utils::get_launch_history_json_path;
+
+/// Launches kept per game - only the latest matter for the recently played list
+const MAX_LAUNCHES_PER_GAME: usize = 20;
+
+impl LaunchHistory {
+    fn push_launch(&mut self, game_id: &str, launched_at: String) {
+        let records = self.games.entry(game_id.to_string()).or_default();
+        records.push(LaunchRecord { launched_at, exited_at: None });
+        if records.len() > MAX_LAUNCHES_PER_GAME {
+            records.drain(..records.len() - MAX_LAUNCHES_PER_GAME);
+        }
+    }
+
+    /// Close the game's latest launch if it's still open
+    fn mark_exit(&mut self, game_id: &str, exited_at: String) {
+        if let Some(record) = self.games.get_mut(game_id).and_then(|r| r.last_mut()) {
+            if record.exited_at.is_none() {
+                record.exited_at = Some(exited_at);
+            }
+        }
+    }
+
+    pub fn last_launch(&self, game_id: &str) -> Option<&LaunchRecord> {
+        self.games.get(game_id).and_then(|r| r.last())
+    }
+}
+
+pub fn load_launch_history() -> LaunchHistory {
+    read_json_file(&get_launch_history_json_path()).unwrap_or_default()
+}
+
+fn update_launch_history(update: impl FnOnce(&mut LaunchHistory)) -> Result<(), String> {
+    let mut history = load_launch_history();
+    update(&mut history);
+    write_json_file(&get_launch_history_json_path(), &history)
+}
+
+pub fn record_launch(game_id: &str) -> Result<(), String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    update_launch_history(|history| history.push_launch(game_id, now))
+}
+
+pub fn record_exit(game_id: &str) -> Result<(), String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    update_launch_history(|history| history.mark_exit(game_id, now))
+}
+
+pub fn purge_launch_history(game_id: &str) -> Result<(), String> {
+    update_launch_history(|history| {
+        history.games.remove(game_id);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_history_is_capped() {
+        let mut history = LaunchHistory::default();
+        for i in 0..MAX_LAUNCHES_PER_GAME + 5 {
+            history.push_launch("game", i.to_string());
+        }
+
+        let records = &history.games["game"];
+        assert_eq!(records.len(), MAX_LAUNCHES_PER_GAME);
+        assert_eq!(records[0].launched_at, "5");
+        assert_eq!(history.last_launch("game").unwrap().launched_at, (MAX_LAUNCHES_PER_GAME + 4).to_string());
+    }
+
+    #[test]
+    fn test_mark_exit_closes_latest_launch_once() {
+        let mut history = LaunchHistory::default();
+        history.push_launch("game", "t1".to_string());
+        history.mark_exit("game", "t2".to_string());
+        history.mark_exit("game", "t3".to_string());
+        history.mark_exit("missing", "t4".to_string());
+
+        assert_eq!(history.last_launch("game").unwrap().exited_at.as_deref(), Some("t2"));
+        assert!(history.last_launch("missing").is_none());
+    }
+}
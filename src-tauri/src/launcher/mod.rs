@@ -1,10 +1,27 @@
 pub mod steam_detector;
 pub mod hoyoplay_detector;
 pub mod riot_detector;  // NEW: Riot Games detector
+pub mod epic_detector;
+pub mod battlenet_detector;
+pub mod xbox_detector;
+pub mod ubisoft_detector;
+pub mod ea_detector;
+pub mod itch_detector;
 pub mod playtime_tracker;
+pub mod launch_history;
+pub mod game_hooks;
+pub mod game_scan;
+pub mod shortcuts;
+pub mod steamgriddb;
 pub mod icon_extractor;
 
 pub use steam_detector::*;
 pub use hoyoplay_detector::*;
 pub use riot_detector::*;  // NEW: Export Riot detector
+pub use epic_detector::*;
+pub use battlenet_detector::*;
+pub use xbox_detector::*;
+pub use ubisoft_detector::*;
+pub use ea_detector::*;
+pub use itch_detector::*;
 pub use playtime_tracker::*;
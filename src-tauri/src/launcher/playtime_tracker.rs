@@ -1,6 +1,10 @@
 use crate::file_manager::{read_json_file, write_json_file};
-use crate::models::GameLibrary;
-use crate::utils::get_game_library_json_path;
+use crate::launcher::game_hooks::run_hook;
+use crate::launcher::launch_history::record_exit;
+use crate::models::{GameLibrary, GamingSession, SessionStatus};
+use crate::utils::{get_game_library_json_path, get_gaming_sessions_json_path};
+use chrono::{DateTime, Utc};
+use log::warn;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
@@ -16,8 +20,20 @@ pub struct ActiveGameSession {
     pub game_id: String,
     pub process_name: String,
     pub start_time: Instant,
+    pub started_at: DateTime<Utc>,
 }
 
+/// How long to wait for the gaming detector to close its session after the game exits
+/// (the detector backs off to 30s between checks)
+const GAMING_SESSION_WAIT_SECS: u64 = 45;
+
+/// Slack for the gaming detector noticing the game before the launcher records the launch
+const GAMING_SESSION_START_SLACK_SECS: i64 = 60;
+
+/// How long to wait for the game's process to appear - protocol launches (steam://, Epic, EA) return
+/// before the store has started the game, which can take a while if it updates first
+const PROCESS_START_TIMEOUT_SECS: u64 = 180;
+
 impl Default for PlaytimeTrackerState {
     fn default() -> Self {
         Self {
@@ -50,6 +66,7 @@ pub fn start_game_session(
         game_id: game_id.clone(),
         process_name: process_name.clone(),
         start_time: Instant::now(),
+        started_at: Utc::now(),
     };
 
     {
@@ -64,21 +81,45 @@ pub fn start_game_session(
     let process_name_clone = process_name.clone();
 
     std::thread::spawn(move || {
-        std::thread::sleep(std::time::Duration::from_secs(3));
-        wait_for_process_exit(&process_name_clone);
-        let elapsed_secs = {
-            let sessions = state_clone.active_sessions.read().unwrap();
-            if let Some(session) = sessions.get(&game_id_clone) {
-                session.start_time.elapsed().as_secs()
+        let started = wait_for_process_start(&process_name_clone);
+        if started {
+            // Time spent in the store's launcher or an update before the game starts isn't playtime
+            if let Some(session) = state_clone.active_sessions.write().unwrap().get_mut(&game_id_clone) {
+                session.start_time = Instant::now();
+                session.started_at = Utc::now();
+            }
+            wait_for_process_exit(&process_name_clone);
+        } else {
+            warn!("{} did not start within {}s", process_name_clone, PROCESS_START_TIMEOUT_SECS);
+        }
+        let _ = record_exit(&game_id_clone);
+        // A game that never started has no session, and its post-exit command has nothing to undo
+        let elapsed_secs = if started {
+            run_post_exit_hook(&app_handle, &game_id_clone);
+            let (tracked_secs, started_at) = {
+                let sessions = state_clone.active_sessions.read().unwrap();
+                if let Some(session) = sessions.get(&game_id_clone) {
+                    (session.start_time.elapsed().as_secs(), session.started_at)
+                } else {
+                    (0, Utc::now())
+                }
+            };
+            // Whitelisted games also get a gaming session, whose duration comes from the detector's
+            // own process tracking and doesn't include the launcher/login time
+            if tracked_secs > 0 {
+                wait_for_gaming_session_duration(&process_name_clone, started_at).unwrap_or(tracked_secs)
             } else {
                 0
             }
+        } else {
+            0
         };
 
         if elapsed_secs > 0 {
             if let Ok(mut lib) = read_json_file::<GameLibrary>(&get_game_library_json_path()) {
                 if let Some(game) = lib.find_by_id_mut(&game_id_clone) {
                     game.total_playtime_seconds += elapsed_secs;
+                    game.session_count += 1;
                     game.last_played = Some(chrono::Utc::now().to_rfc3339());
                     let _ = write_json_file(&get_game_library_json_path(), &lib);
                 }
@@ -97,6 +138,75 @@ pub fn start_game_session(
     });
 }
 
+/// Run the game's post-exit command, if it has one
+/// Only games launched from Atlas get here, so a post-exit command never runs without its pre-launch one
+fn run_post_exit_hook(app_handle: &AppHandle, game_id: &str) {
+    let hook = read_json_file::<GameLibrary>(&get_game_library_json_path())
+        .ok()
+        .and_then(|lib| lib.find_by_id(game_id).and_then(|g| g.post_exit_command.clone()));
+
+    if let Some(hook) = hook {
+        if let Err(e) = run_hook(&hook, "Post-exit") {
+            let _ = app_handle.emit("launcher:hook_failed", serde_json::json!({
+                "game_id": game_id,
+                "hook": "post_exit",
+                "error": e,
+            }));
+        }
+    }
+}
+
+/// The gaming session for `process_name` that started around or after `launched_at`
+fn find_gaming_session<'a>(
+    sessions: &'a [GamingSession],
+    process_name: &str,
+    launched_at: DateTime<Utc>,
+) -> Option<&'a GamingSession> {
+    let earliest = launched_at - chrono::Duration::seconds(GAMING_SESSION_START_SLACK_SECS);
+    sessions.iter().rev().find(|s| {
+        s.process_name.eq_ignore_ascii_case(process_name)
+            && s.status != SessionStatus::Cancelled
+            && DateTime::parse_from_rfc3339(&s.start_time).map(|t| t >= earliest).unwrap_or(false)
+    })
+}
+
+/// Duration of the matching gaming session once the detector has completed it
+/// None if the game isn't whitelisted (no session) or the session doesn't close in time
+fn wait_for_gaming_session_duration(process_name: &str, launched_at: DateTime<Utc>) -> Option<u64> {
+    let deadline = Instant::now() + std::time::Duration::from_secs(GAMING_SESSION_WAIT_SECS);
+
+    loop {
+        let sessions: Vec<GamingSession> = read_json_file(&get_gaming_sessions_json_path()).unwrap_or_default();
+        let session = find_gaming_session(&sessions, process_name, launched_at)?;
+
+        if session.status == SessionStatus::Completed {
+            return session.summary.as_ref().map(|s| s.duration_seconds.round() as u64).filter(|secs| *secs > 0);
+        }
+        if Instant::now() >= deadline {
+            return None;
+        }
+        std::thread::sleep(std::time::Duration::from_secs(5));
+    }
+}
+
+/// Wait for the game's process to show up; false if it didn't within PROCESS_START_TIMEOUT_SECS
+fn wait_for_process_start(process_name: &str) -> bool {
+    use sysinfo::{ProcessRefreshKind, System};
+
+    let process_name_lower = process_name.to_lowercase();
+    let mut sys = System::new();
+    let started = Instant::now();
+
+    while started.elapsed().as_secs() < PROCESS_START_TIMEOUT_SECS {
+        sys.refresh_processes_specifics(ProcessRefreshKind::new());
+        if sys.processes().values().any(|p| p.name().to_lowercase() == process_name_lower) {
+            return true;
+        }
+        std::thread::sleep(std::time::Duration::from_secs(2));
+    }
+    false
+}
+
 fn wait_for_process_exit(process_name: &str) {
     use sysinfo::{ProcessRefreshKind, System};
 
@@ -135,8 +245,37 @@ pub fn get_active_game_sessions(state: &PlaytimeTrackerState) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use sysinfo::{ProcessRefreshKind, System};
 
+    fn gaming_session(process_name: &str, start_time: &str, status: SessionStatus) -> GamingSession {
+        GamingSession {
+            id: start_time.to_string(),
+            game_name: "Game".to_string(),
+            process_name: process_name.to_string(),
+            start_time: start_time.to_string(),
+            end_time: None,
+            status,
+            summary: None,
+        }
+    }
+
+    /// Test: only sessions for the same process started around the launch count
+    #[test]
+    fn test_find_gaming_session() {
+        let launched_at = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let sessions = vec![
+            gaming_session("Game.exe", "2024-05-01T10:00:00Z", SessionStatus::Completed),
+            gaming_session("Other.exe", "2024-05-01T12:00:30Z", SessionStatus::Completed),
+            gaming_session("game.exe", "2024-05-01T11:59:30Z", SessionStatus::Active),
+            gaming_session("Game.exe", "2024-05-01T12:01:00Z", SessionStatus::Cancelled),
+        ];
+
+        let found = find_gaming_session(&sessions, "Game.exe", launched_at).unwrap();
+        assert_eq!(found.start_time, "2024-05-01T11:59:30Z");
+        assert!(find_gaming_session(&sessions[..2], "Game.exe", launched_at).is_none());
+    }
+
     /// Test: ProcessRefreshKind::new() creates minimal refresh scope
     #[test]
     fn test_minimal_process_refresh_kind() {
@@ -4,14 +4,16 @@ use std::path::PathBuf;
 use winreg::enums::*;
 #[cfg(windows)]
 use winreg::RegKey;
-#[cfg(windows)]
+#[cfg(all(windows, feature = "powershell-icons"))]
 use std::os::windows::process::CommandExt;
 
-#[cfg(windows)]
+#[cfg(all(windows, feature = "powershell-icons"))]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 use crate::models::launcher::{DetectedGame, GameSource};
-use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir, download_riot_icon};
+#[cfg(windows)]
+use crate::launcher::game_scan;
+use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir, download_riot_icon, ico_file_to_png};
 
 #[derive(Debug, Clone)]
 pub struct RiotGameConfig {
@@ -243,8 +245,12 @@ fn find_ico_in_folder(game_folder: &std::path::Path, cache_dir: &std::path::Path
     None
 }
 
-#[cfg(windows)]
 fn convert_ico_to_png(ico_path: &std::path::Path, output_path: &std::path::Path) -> Option<String> {
+    ico_file_to_png(ico_path, output_path).or_else(|| convert_ico_to_png_powershell(ico_path, output_path))
+}
+
+#[cfg(all(windows, feature = "powershell-icons"))]
+fn convert_ico_to_png_powershell(ico_path: &std::path::Path, output_path: &std::path::Path) -> Option<String> {
     use std::process::Command;
 
     let ps_script = format!(
@@ -335,8 +341,8 @@ Write-Output 'SUCCESS'
     }
 }
 
-#[cfg(not(windows))]
-fn convert_ico_to_png(_ico_path: &std::path::Path, _output_path: &std::path::Path) -> Option<String> {
+#[cfg(not(all(windows, feature = "powershell-icons")))]
+fn convert_ico_to_png_powershell(_ico_path: &std::path::Path, _output_path: &std::path::Path) -> Option<String> {
     None
 }
 
@@ -520,6 +526,9 @@ fn find_riot_from_folders() -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
     for letter in b'C'..=b'Z' {
+        if game_scan::is_cancelled() {
+            break;
+        }
         let drive_char = letter as char;
         let drive = format!("{}:\\", drive_char);
         let drive_path = PathBuf::from(&drive);
@@ -537,6 +546,7 @@ fn find_riot_from_folders() -> Vec<PathBuf> {
         if !drive_path.exists() {
             continue;
         }
+        game_scan::report_phase(&format!("Riot ({})", drive));
 
         let possible_paths = [
             drive_path.join("Riot Games"),
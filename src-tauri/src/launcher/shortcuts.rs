@@ -0,0 +1,151 @@
+// Desktop / Start Menu shortcuts for library games
+// .lnk files are written through WScript.Shell in PowerShell, like the icon extractor. Shortcut
+// icons must be .ico, so cached PNG icons are wrapped into one (ICO files may embed PNG data)
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutLocation {
+    Desktop,
+    StartMenu,
+}
+
+/// Everything a .lnk points at
+pub struct ShortcutSpec<'a> {
+    pub target: &'a str,
+    pub args: Option<&'a str>,
+    pub working_dir: Option<&'a str>,
+    /// `path,index` icon location
+    pub icon_location: Option<String>,
+}
+
+pub fn shortcut_dir(location: ShortcutLocation) -> Option<PathBuf> {
+    match location {
+        ShortcutLocation::Desktop => dirs::desktop_dir(),
+        ShortcutLocation::StartMenu => dirs::config_dir().map(|appdata| {
+            appdata.join("Microsoft").join("Windows").join("Start Menu").join("Programs")
+        }),
+    }
+}
+
+/// A game name made safe for use as a file name
+pub fn shortcut_file_name(game_name: &str) -> String {
+    let name: String = game_name
+        .chars()
+        .map(|c| if r#"<>:"/\|?*"#.contains(c) || c.is_control() { '_' } else { c })
+        .collect();
+    let name = name.trim().trim_end_matches('.');
+    format!("{}.lnk", if name.is_empty() { "Game" } else { name })
+}
+
+/// Wrap PNG data in a single-image ICO container
+fn png_to_ico(png: &[u8]) -> Option<Vec<u8>> {
+    if png.len() < 24 || &png[..8] != b"\x89PNG\r\n\x1a\n" {
+        return None;
+    }
+    // IHDR width/height (big-endian); the ICO directory stores 256+ as 0
+    let width = u32::from_be_bytes(png[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(png[20..24].try_into().ok()?);
+    let dim = |d: u32| if d >= 256 { 0 } else { d as u8 };
+
+    let mut ico = Vec::with_capacity(22 + png.len());
+    ico.extend_from_slice(&[0, 0, 1, 0, 1, 0]); // reserved, type = icon, 1 image
+    ico.extend_from_slice(&[dim(width), dim(height), 0, 0]); // size, no palette, reserved
+    ico.extend_from_slice(&1u16.to_le_bytes()); // color planes
+    ico.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+    ico.extend_from_slice(&(png.len() as u32).to_le_bytes());
+    ico.extend_from_slice(&22u32.to_le_bytes()); // image data offset
+    ico.extend_from_slice(png);
+    Some(ico)
+}
+
+/// An .ico next to a cached PNG icon, created on first use
+pub fn icon_to_ico(icon_path: &Path) -> Option<PathBuf> {
+    let ico_path = icon_path.with_extension("ico");
+    if ico_path.exists() {
+        return Some(ico_path);
+    }
+    let ico = png_to_ico(&std::fs::read(icon_path).ok()?)?;
+    std::fs::write(&ico_path, ico).ok()?;
+    Some(ico_path)
+}
+
+#[cfg(windows)]
+pub fn write_shortcut(path: &Path, spec: &ShortcutSpec) -> Result<(), String> {
+    use std::process::Command;
+
+    // Values reach the script as environment variables, never as script text, so no quote
+    // character in a name or path (PowerShell also treats U+2018-U+201B as quotes) can break out
+    let mut env = vec![("ATLAS_SHORTCUT_PATH", path.to_string_lossy().to_string())];
+    let mut ps_script = String::from(
+        "$s = (New-Object -ComObject WScript.Shell).CreateShortcut($env:ATLAS_SHORTCUT_PATH)\n",
+    );
+    let properties = [
+        ("TargetPath", "ATLAS_SHORTCUT_TARGET", Some(spec.target)),
+        ("Arguments", "ATLAS_SHORTCUT_ARGS", spec.args),
+        ("WorkingDirectory", "ATLAS_SHORTCUT_DIR", spec.working_dir),
+        ("IconLocation", "ATLAS_SHORTCUT_ICON", spec.icon_location.as_deref()),
+    ];
+    for (property, var, value) in properties {
+        if let Some(value) = value {
+            ps_script.push_str(&format!("$s.{} = $env:{}\n", property, var));
+            env.push((var, value.to_string()));
+        }
+    }
+    ps_script.push_str("$s.Save()\n");
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", &ps_script])
+        .envs(env)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Failed to run PowerShell: {}", e))?;
+
+    if output.status.success() && path.exists() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to create shortcut: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(not(windows))]
+pub fn write_shortcut(_path: &Path, _spec: &ShortcutSpec) -> Result<(), String> {
+    Err("Shortcuts are only supported on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcut_file_name() {
+        assert_eq!(shortcut_file_name("Diablo II: Resurrected"), "Diablo II_ Resurrected.lnk");
+        assert_eq!(shortcut_file_name("What?."), "What_.lnk");
+        assert_eq!(shortcut_file_name("  "), "Game.lnk");
+    }
+
+    #[test]
+    fn test_png_to_ico() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend_from_slice(&256u32.to_be_bytes());
+        png.extend_from_slice(&48u32.to_be_bytes());
+
+        let ico = png_to_ico(&png).unwrap();
+        assert_eq!(&ico[..6], &[0, 0, 1, 0, 1, 0]);
+        assert_eq!((ico[6], ico[7]), (0, 48));
+        assert_eq!(&ico[22..], &png[..]);
+        assert!(png_to_ico(b"\xFF\xD8 not a png at all......").is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::models::{DetectedGame, GameSource};
-use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir, download_steam_icon};
+use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir, download_steam_icon, ico_file_to_png};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -285,10 +285,279 @@ pub fn detect_steam_games() -> Vec<DetectedGame> {
     games
 }
 
+/// A value in Steam's binary VDF format (shortcuts.vdf)
+#[derive(Debug)]
+enum BinaryVdfValue {
+    String(String),
+    Int(u32),
+    Map(Vec<(String, BinaryVdfValue)>),
+}
+
+struct BinaryVdfReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BinaryVdfReader<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let b = *self.data.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn cstring(&mut self) -> Option<String> {
+        let len = self.data.get(self.pos..)?.iter().position(|&b| b == 0)?;
+        let s = String::from_utf8_lossy(&self.data[self.pos..self.pos + len]).to_string();
+        self.pos += len + 1;
+        Some(s)
+    }
+
+    fn int(&mut self) -> Option<u32> {
+        let bytes = self.data.get(self.pos..self.pos + 4)?;
+        self.pos += 4;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Fields of a map, up to and including its end marker
+    fn map(&mut self) -> Option<Vec<(String, BinaryVdfValue)>> {
+        let mut fields = Vec::new();
+        loop {
+            let kind = self.byte()?;
+            if kind == 0x08 {
+                return Some(fields);
+            }
+            let key = self.cstring()?;
+            let value = match kind {
+                0x00 => BinaryVdfValue::Map(self.map()?),
+                0x01 => BinaryVdfValue::String(self.cstring()?),
+                0x02 => BinaryVdfValue::Int(self.int()?),
+                _ => return None,
+            };
+            fields.push((key, value));
+        }
+    }
+}
+
+/// Start of the next `"<index>" { ...` shortcut entry at or after `from`
+fn find_next_shortcut_entry(data: &[u8], from: usize) -> Option<usize> {
+    (from..data.len()).find(|&i| {
+        let digits = data[i + 1..].iter().take_while(|b| b.is_ascii_digit()).count();
+        data[i] == 0
+            && digits > 0
+            && data.get(i + 1 + digits) == Some(&0)
+            && matches!(data.get(i + 2 + digits), Some(0x01) | Some(0x02))
+    })
+}
+
+/// A non-Steam game added to the Steam library
+#[derive(Debug, Clone, PartialEq)]
+struct SteamShortcut {
+    app_id: u32,
+    name: String,
+    exe: String,
+    start_dir: Option<String>,
+    launch_options: Option<String>,
+    icon: Option<String>,
+    hidden: bool,
+}
+
+impl SteamShortcut {
+    fn from_fields(fields: &[(String, BinaryVdfValue)]) -> Option<Self> {
+        // Key casing differs between Steam versions ("AppName" vs "appname")
+        let string = |key: &str| {
+            fields.iter().find_map(|(k, v)| match v {
+                BinaryVdfValue::String(s) if k.eq_ignore_ascii_case(key) => {
+                    Some(s.trim().trim_matches('"').to_string()).filter(|s| !s.is_empty())
+                }
+                _ => None,
+            })
+        };
+        let int = |key: &str| {
+            fields.iter().find_map(|(k, v)| match v {
+                BinaryVdfValue::Int(i) if k.eq_ignore_ascii_case(key) => Some(*i),
+                _ => None,
+            })
+        };
+
+        Some(SteamShortcut {
+            app_id: int("appid").unwrap_or(0),
+            name: string("AppName")?,
+            exe: string("Exe")?,
+            start_dir: string("StartDir"),
+            launch_options: string("LaunchOptions"),
+            icon: string("icon"),
+            hidden: int("IsHidden").unwrap_or(0) != 0,
+        })
+    }
+}
+
+/// Parse a shortcuts.vdf file
+/// A corrupt entry is skipped by resuming at the next entry header, so one bad entry doesn't lose the rest
+fn parse_shortcuts_vdf(data: &[u8]) -> Vec<SteamShortcut> {
+    let mut shortcuts = Vec::new();
+    let mut reader = BinaryVdfReader { data, pos: 0 };
+
+    let is_shortcuts_file = reader.byte() == Some(0x00)
+        && reader.cstring().is_some_and(|root| root.eq_ignore_ascii_case("shortcuts"));
+    if !is_shortcuts_file {
+        return shortcuts;
+    }
+
+    loop {
+        let entry_start = reader.pos;
+        // 0x08 closes the shortcuts map
+        if reader.byte() != Some(0x00) {
+            break;
+        }
+
+        match reader.cstring().and_then(|_| reader.map()) {
+            Some(fields) => shortcuts.extend(SteamShortcut::from_fields(&fields)),
+            None => match find_next_shortcut_entry(data, entry_start + 1) {
+                Some(next) => reader.pos = next,
+                None => break,
+            },
+        }
+    }
+
+    shortcuts
+}
+
+/// Icon for a shortcut: its custom icon when it has one, otherwise the exe's
+fn shortcut_icon(shortcut: &SteamShortcut, exe_path: &Path, cache_dir: &Path) -> Option<String> {
+    let custom = shortcut.icon.as_deref().map(Path::new).filter(|p| p.is_file()).and_then(|icon| {
+        let ext = icon.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" => Some(icon.to_string_lossy().to_string()),
+            "ico" => {
+                fs::create_dir_all(cache_dir).ok()?;
+                ico_file_to_png(icon, &cache_dir.join(format!("steam_shortcut_{}.png", shortcut.app_id)))
+            }
+            "exe" => extract_icon_from_exe(icon, cache_dir),
+            _ => None,
+        }
+    });
+    custom.or_else(|| extract_icon_from_exe(exe_path, cache_dir))
+}
+
+/// Non-Steam games from every Steam user's shortcuts.vdf (read-only)
+/// Hidden shortcuts and ones whose exe no longer exists are skipped
+pub fn detect_steam_shortcuts() -> Vec<DetectedGame> {
+    let mut games: Vec<DetectedGame> = Vec::new();
+
+    let Some(steam_path) = find_steam_path() else {
+        return games;
+    };
+    let Ok(users) = fs::read_dir(steam_path.join("userdata")) else {
+        return games;
+    };
+
+    for user in users.flatten() {
+        let Ok(data) = fs::read(user.path().join("config").join("shortcuts.vdf")) else {
+            continue;
+        };
+
+        for shortcut in parse_shortcuts_vdf(&data) {
+            let exe_path = PathBuf::from(&shortcut.exe);
+            if shortcut.hidden || !exe_path.is_file() {
+                continue;
+            }
+            // Several Steam accounts on one PC often share shortcuts
+            if games.iter().any(|g| g.executable_path.eq_ignore_ascii_case(&shortcut.exe)) {
+                continue;
+            }
+
+            let install_path = shortcut
+                .start_dir
+                .clone()
+                .or_else(|| exe_path.parent().map(|p| p.to_string_lossy().to_string()))
+                .unwrap_or_default();
+            let icon_path = get_icon_cache_dir().and_then(|cache_dir| shortcut_icon(&shortcut, &exe_path, &cache_dir));
+
+            games.push(DetectedGame {
+                name: shortcut.name,
+                executable_path: shortcut.exe,
+                install_path,
+                source: GameSource::SteamShortcut,
+                app_id: None,
+                icon_path,
+                launch_args: shortcut.launch_options,
+            });
+        }
+    }
+
+    games
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn vdf_string(out: &mut Vec<u8>, key: &str, value: &str) {
+        out.push(0x01);
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+        out.extend_from_slice(value.as_bytes());
+        out.push(0);
+    }
+
+    fn vdf_int(out: &mut Vec<u8>, key: &str, value: u32) {
+        out.push(0x02);
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn vdf_entry(out: &mut Vec<u8>, index: usize, app_id: u32, name: &str, exe: &str) {
+        out.push(0x00);
+        out.extend_from_slice(index.to_string().as_bytes());
+        out.push(0);
+        vdf_int(out, "appid", app_id);
+        vdf_string(out, "AppName", name);
+        vdf_string(out, "Exe", &format!("\"{}\"", exe));
+        vdf_string(out, "StartDir", "\"C:\\Games\\\"");
+        vdf_string(out, "LaunchOptions", "-windowed");
+        out.extend_from_slice(b"\x00tags\x00\x01\x30\x00Favorites\x00\x08");
+        out.push(0x08);
+    }
+
+    #[test]
+    fn test_parse_shortcuts_vdf() {
+        let mut data = b"\x00shortcuts\x00".to_vec();
+        vdf_entry(&mut data, 0, 123, "Minecraft", "C:\\Games\\Minecraft.exe");
+        vdf_entry(&mut data, 1, 456, "Osu", "C:\\Games\\osu!.exe");
+        data.extend_from_slice(b"\x08\x08");
+
+        let shortcuts = parse_shortcuts_vdf(&data);
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].app_id, 123);
+        assert_eq!(shortcuts[0].name, "Minecraft");
+        assert_eq!(shortcuts[0].exe, "C:\\Games\\Minecraft.exe");
+        assert_eq!(shortcuts[0].start_dir.as_deref(), Some("C:\\Games\\"));
+        assert_eq!(shortcuts[0].launch_options.as_deref(), Some("-windowed"));
+        assert!(!shortcuts[0].hidden);
+        assert_eq!(shortcuts[1].name, "Osu");
+    }
+
+    #[test]
+    fn test_parse_shortcuts_vdf_skips_corrupt_entry() {
+        let mut data = b"\x00shortcuts\x00".to_vec();
+        vdf_entry(&mut data, 0, 1, "First", "C:\\first.exe");
+        // Unknown field type in the middle of entry 1
+        data.extend_from_slice(b"\x001\x00\x01AppName\x00Broken\x00\x07junk\x00");
+        vdf_entry(&mut data, 2, 3, "Third", "C:\\third.exe");
+        data.extend_from_slice(b"\x08\x08");
+
+        let names: Vec<String> = parse_shortcuts_vdf(&data).into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["First", "Third"]);
+
+        // Truncated file keeps what was read before the cut
+        let mut truncated = b"\x00shortcuts\x00".to_vec();
+        vdf_entry(&mut truncated, 0, 1, "First", "C:\\first.exe");
+        truncated.extend_from_slice(b"\x001\x00\x01AppName\x00Cut");
+        assert_eq!(parse_shortcuts_vdf(&truncated).len(), 1);
+        assert!(parse_shortcuts_vdf(b"not a vdf").is_empty());
+    }
+
     #[test]
     fn test_extract_vdf_value() {
         assert_eq!(
@@ -0,0 +1,143 @@
+// SteamGridDB artwork
+// Looks games up by name (or Steam app id) and downloads grid/hero/logo art into the icon cache.
+// Lookups are cached for the session and requests are spaced out to stay clear of rate limits
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::launcher::icon_extractor::download_image;
+use crate::models::{ArtworkKind, GameArtwork};
+
+const API_BASE: &str = "https://www.steamgriddb.com/api/v2";
+
+/// Minimum gap between API requests
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(500);
+
+lazy_static! {
+    static ref LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+    /// Lookup key -> SteamGridDB game id (None = no match), so repeated fetches skip the search
+    static ref GAME_ID_CACHE: Mutex<HashMap<String, Option<u64>>> = Mutex::new(HashMap::new());
+}
+
+/// Every endpoint wraps its payload in `{ "success": bool, "data": ... }`
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    data: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiGame {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiImage {
+    url: String,
+}
+
+impl ArtworkKind {
+    /// API path segment and the query narrowing results to what the library displays
+    fn endpoint(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            ArtworkKind::Grid => Some(("grids", "dimensions=600x900&mimes=image/png,image/jpeg")),
+            ArtworkKind::Hero => Some(("heroes", "mimes=image/png,image/jpeg")),
+            ArtworkKind::Logo => Some(("logos", "mimes=image/png")),
+            ArtworkKind::Icon => None,
+        }
+    }
+}
+
+/// GET an API endpoint's `data`; None when SteamGridDB has nothing for it
+fn get<T: for<'de> Deserialize<'de>>(url: &str, api_key: &str) -> Result<Option<T>, String> {
+    {
+        let mut last = LAST_REQUEST.lock();
+        if let Some(wait) = last.and_then(|t| MIN_REQUEST_INTERVAL.checked_sub(t.elapsed())) {
+            std::thread::sleep(wait);
+        }
+        *last = Some(Instant::now());
+    }
+
+    match ureq::get(url).set("Authorization", &format!("Bearer {}", api_key)).call() {
+        Ok(response) => response
+            .into_json::<ApiResponse<T>>()
+            .map(|r| r.data)
+            .map_err(|e| format!("Invalid SteamGridDB response: {}", e)),
+        Err(ureq::Error::Status(401, _)) => Err("SteamGridDB rejected the API key".to_string()),
+        Err(ureq::Error::Status(404, _)) => Ok(None),
+        Err(e) => Err(format!("SteamGridDB request failed: {}", e)),
+    }
+}
+
+/// SteamGridDB game id, by Steam app id when there is one, otherwise by name search
+fn find_game_id(name: &str, steam_app_id: Option<&str>, api_key: &str) -> Result<Option<u64>, String> {
+    let cache_key = match steam_app_id {
+        Some(app_id) => format!("steam:{}", app_id),
+        None => format!("name:{}", name.to_lowercase()),
+    };
+    if let Some(id) = GAME_ID_CACHE.lock().get(&cache_key) {
+        return Ok(*id);
+    }
+
+    let by_steam_id = match steam_app_id {
+        Some(app_id) => get::<ApiGame>(&format!("{}/games/steam/{}", API_BASE, app_id), api_key)?.map(|g| g.id),
+        None => None,
+    };
+    let id = match by_steam_id {
+        Some(id) => Some(id),
+        None => get::<Vec<ApiGame>>(&format!("{}/search/autocomplete/{}", API_BASE, encode_segment(name)), api_key)?
+            .and_then(|results| results.first().map(|g| g.id)),
+    };
+
+    GAME_ID_CACHE.lock().insert(cache_key, id);
+    Ok(id)
+}
+
+/// Percent-encode a URL path segment
+fn encode_segment(segment: &str) -> String {
+    url::form_urlencoded::byte_serialize(segment.as_bytes()).collect::<String>().replace('+', "%20")
+}
+
+/// Download the first usable image of each kind into `output_dir`
+/// Files already in the cache are reused; kinds with no art stay None
+pub fn fetch_artwork(
+    name: &str,
+    steam_app_id: Option<&str>,
+    api_key: &str,
+    output_dir: &Path,
+) -> Result<GameArtwork, String> {
+    let Some(sgdb_id) = find_game_id(name, steam_app_id, api_key)? else {
+        return Ok(GameArtwork::default());
+    };
+    std::fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create icon cache: {}", e))?;
+
+    let mut artwork = GameArtwork::default();
+    for kind in [ArtworkKind::Grid, ArtworkKind::Hero, ArtworkKind::Logo] {
+        let Some((segment, query)) = kind.endpoint() else {
+            continue;
+        };
+
+        let cached = ["png", "jpg"]
+            .iter()
+            .map(|ext| output_dir.join(format!("sgdb_{}_{}.{}", sgdb_id, segment, ext)))
+            .find(|p| p.exists());
+        let path = match cached {
+            Some(path) => Some(path.to_string_lossy().to_string()),
+            None => get::<Vec<ApiImage>>(&format!("{}/{}/game/{}?{}", API_BASE, segment, sgdb_id, query), api_key)?
+                .unwrap_or_default()
+                .iter()
+                .take(3)
+                .find_map(|image| {
+                    // Keep the extension so get_icon_base64 reports the right MIME type
+                    let ext = if image.url.to_lowercase().ends_with(".png") { "png" } else { "jpg" };
+                    download_image(&image.url, &output_dir.join(format!("sgdb_{}_{}.{}", sgdb_id, segment, ext)))
+                }),
+        };
+        artwork.set(kind, path);
+    }
+
+    Ok(artwork)
+}
@@ -0,0 +1,406 @@
+// Ubisoft Connect detector
+// Install folders come from the launcher's Installs registry keys (read-only) and names from the
+// launcher's configuration cache, a stream of protobuf records each holding a game's YAML config.
+// Games missing from the cache fall back to their uninstall entry's DisplayName, then the folder
+// name. Games launch through uplay:// so Ubisoft Connect can sign in first
+#![cfg_attr(not(windows), allow(dead_code, unused_imports))]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use winreg::enums::*;
+#[cfg(windows)]
+use winreg::RegKey;
+
+use crate::launcher::icon_extractor::{extract_icon_from_exe, get_icon_cache_dir};
+use crate::models::launcher::{DetectedGame, GameSource};
+
+/// Prefix of the app_id given to Ubisoft games, followed by the launcher's install id
+pub const UBISOFT_APP_ID_PREFIX: &str = "ubisoft_";
+
+/// Executables shipped next to games that are never the game itself
+const NON_GAME_EXES: &[&str] = &[
+    "unins",
+    "uninstall",
+    "crashreporter",
+    "crash_reporter",
+    "ubisoftgamelauncher",
+    "uplaywebcore",
+    "vc_redist",
+    "vcredist",
+    "dxsetup",
+    "dotnet",
+    "easyanticheat",
+    "battleye",
+];
+
+pub fn ubisoft_launch_url(install_id: &str) -> String {
+    format!("uplay://launch/{}/0", install_id)
+}
+
+/// An install found in the registry
+#[derive(Debug, Clone, PartialEq)]
+struct UbisoftInstall {
+    id: String,
+    name: String,
+    install_dir: PathBuf,
+}
+
+/// Combine the Installs entries (id, InstallDir) with the names from the configuration cache and uninstall entries
+/// Entries without an install dir are dropped; unnamed ones use their folder name
+fn parse_installs(entries: Vec<(String, Option<String>)>, names: &HashMap<String, String>) -> Vec<UbisoftInstall> {
+    entries
+        .into_iter()
+        .filter_map(|(id, install_dir)| {
+            let install_dir = PathBuf::from(install_dir?.trim().trim_end_matches(['/', '\\']));
+            if install_dir.as_os_str().is_empty() {
+                return None;
+            }
+            let name = names
+                .get(&id)
+                .cloned()
+                .or_else(|| install_dir.file_name().map(|n| n.to_string_lossy().to_string()))?;
+            Some(UbisoftInstall { id, name, install_dir })
+        })
+        .collect()
+}
+
+/// Configuration cache, relative to the launcher's install folder
+const CONFIGURATION_CACHE: &str = r"cache\configuration\configurations";
+
+/// A protobuf field value; fixed-size fields are skipped
+enum ProtoField<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+/// Read the varint at `pos`, moving past it
+fn read_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// (field number, value) of each field in a protobuf message, up to the first malformed one
+fn read_proto_fields(message: &[u8]) -> Vec<(u64, ProtoField<'_>)> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < message.len() {
+        let Some(key) = read_varint(message, &mut pos) else {
+            break;
+        };
+        let value = match key & 7 {
+            0 => match read_varint(message, &mut pos) {
+                Some(value) => ProtoField::Varint(value),
+                None => break,
+            },
+            2 => {
+                let end = read_varint(message, &mut pos)
+                    .and_then(|len| pos.checked_add(usize::try_from(len).ok()?))
+                    .filter(|&end| end <= message.len());
+                let Some(end) = end else {
+                    break;
+                };
+                let bytes = &message[pos..end];
+                pos = end;
+                ProtoField::Bytes(bytes)
+            }
+            1 => {
+                pos += 8;
+                continue;
+            }
+            5 => {
+                pos += 4;
+                continue;
+            }
+            _ => break,
+        };
+        fields.push((key >> 3, value));
+    }
+    fields
+}
+
+/// Game names by install id from the configuration cache
+/// Each top-level record (field 1) holds the install id (field 1) and the game's YAML config (field 3)
+fn parse_configuration_names(cache: &[u8]) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    for (field, value) in read_proto_fields(cache) {
+        let (1, ProtoField::Bytes(record)) = (field, value) else {
+            continue;
+        };
+        let mut id = None;
+        let mut name = None;
+        for (field, value) in read_proto_fields(record) {
+            match (field, value) {
+                (1, ProtoField::Varint(value)) => id = Some(value),
+                (3, ProtoField::Bytes(yaml)) => name = configuration_name(&String::from_utf8_lossy(yaml)),
+                _ => {}
+            }
+        }
+        if let (Some(id), Some(name)) = (id, name) {
+            names.entry(id.to_string()).or_insert(name);
+        }
+    }
+    names
+}
+
+/// `root.name` of a game's YAML config, looked up in its localizations when it is a string key ("l1")
+fn configuration_name(yaml: &str) -> Option<String> {
+    let unquote = |value: &str| value.trim().trim_matches(['"', '\'']).trim().to_string();
+
+    let mut section = "";
+    let mut name = None;
+    for line in yaml.lines() {
+        if !line.starts_with(' ') {
+            section = line.trim_end();
+            continue;
+        }
+        if section == "root:" {
+            if let Some(value) = line.strip_prefix("  name:") {
+                name = Some(unquote(value));
+                break;
+            }
+        }
+    }
+    let name = name.filter(|n| !n.is_empty())?;
+
+    let localized = yaml
+        .lines()
+        .skip_while(|line| line.trim_end() != "localizations:")
+        .skip(1)
+        .take_while(|line| line.starts_with(' ') || line.trim().is_empty())
+        .find_map(|line| line.trim().strip_prefix(name.as_str())?.strip_prefix(':').map(unquote))
+        .filter(|n| !n.is_empty());
+    Some(localized.unwrap_or(name))
+}
+
+/// Install id from an uninstall key name ("Uplay Install 635")
+fn parse_uninstall_key_id(key_name: &str) -> Option<String> {
+    key_name
+        .strip_prefix("Uplay Install ")
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The game exe among `candidates` (path, size in bytes): the largest one that isn't a known helper
+fn pick_game_exe(candidates: Vec<(PathBuf, u64)>) -> Option<PathBuf> {
+    candidates
+        .into_iter()
+        .filter(|(path, _)| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+            !NON_GAME_EXES.iter().any(|skip| name.contains(skip))
+        })
+        .max_by_key(|(_, size)| *size)
+        .map(|(path, _)| path)
+}
+
+/// Executables in the install folder and one level below (many games keep theirs in bin/ or a platform folder)
+fn find_exe_candidates(install_dir: &Path) -> Vec<(PathBuf, u64)> {
+    let mut candidates = Vec::new();
+    let mut dirs = vec![install_dir.to_path_buf()];
+
+    if let Ok(entries) = std::fs::read_dir(install_dir) {
+        dirs.extend(entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()));
+    }
+
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e.to_ascii_lowercase() == "exe").unwrap_or(false) {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                candidates.push((path, size));
+            }
+        }
+    }
+
+    candidates
+}
+
+#[cfg(windows)]
+fn read_registry_installs() -> Vec<UbisoftInstall> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+
+    let entries: Vec<(String, Option<String>)> = hklm
+        .open_subkey(r"SOFTWARE\WOW6432Node\Ubisoft\Launcher\Installs")
+        .map(|installs| {
+            installs
+                .enum_keys()
+                .filter_map(|k| k.ok())
+                .map(|id| {
+                    let install_dir = installs
+                        .open_subkey(&id)
+                        .and_then(|key| key.get_value::<String, _>("InstallDir"))
+                        .ok();
+                    (id, install_dir)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut names = hklm
+        .open_subkey(r"SOFTWARE\WOW6432Node\Ubisoft\Launcher")
+        .and_then(|key| key.get_value::<String, _>("InstallDir"))
+        .ok()
+        .and_then(|launcher_dir| std::fs::read(Path::new(&launcher_dir).join(CONFIGURATION_CACHE)).ok())
+        .map(|cache| parse_configuration_names(&cache))
+        .unwrap_or_default();
+    for uninstall_path in [
+        r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ] {
+        let Ok(uninstall_key) = hklm.open_subkey(uninstall_path) else {
+            continue;
+        };
+        for key_name in uninstall_key.enum_keys().filter_map(|k| k.ok()) {
+            let Some(id) = parse_uninstall_key_id(&key_name) else {
+                continue;
+            };
+            if let Ok(name) = uninstall_key
+                .open_subkey(&key_name)
+                .and_then(|key| key.get_value::<String, _>("DisplayName"))
+            {
+                names.entry(id).or_insert(name);
+            }
+        }
+    }
+
+    parse_installs(entries, &names)
+}
+
+#[cfg(windows)]
+pub fn detect_ubisoft_games() -> Vec<DetectedGame> {
+    let mut games = Vec::new();
+
+    for install in read_registry_installs() {
+        if !install.install_dir.exists() {
+            continue;
+        }
+        let Some(game_exe_path) = pick_game_exe(find_exe_candidates(&install.install_dir)) else {
+            continue;
+        };
+
+        let app_id = format!("{}{}", UBISOFT_APP_ID_PREFIX, install.id);
+        if games.iter().any(|g: &DetectedGame| g.app_id.as_ref() == Some(&app_id)) {
+            continue;
+        }
+
+        let icon_path = get_icon_cache_dir().and_then(|cache_dir| extract_icon_from_exe(&game_exe_path, &cache_dir));
+
+        games.push(DetectedGame {
+            name: install.name,
+            executable_path: game_exe_path.to_string_lossy().to_string(),
+            install_path: install.install_dir.to_string_lossy().to_string(),
+            source: GameSource::Ubisoft,
+            app_id: Some(app_id),
+            icon_path,
+            launch_args: None,
+        });
+    }
+
+    games
+}
+
+#[cfg(not(windows))]
+pub fn detect_ubisoft_games() -> Vec<DetectedGame> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_installs() {
+        let names = HashMap::from([("635".to_string(), "Far Cry 5".to_string())]);
+        let installs = parse_installs(
+            vec![
+                ("635".to_string(), Some(r"D:/Games/Ubisoft/Far Cry 5/".to_string())),
+                ("4311".to_string(), Some(r"D:/Games/Ubisoft/Rainbow Six Siege".to_string())),
+                ("99".to_string(), None),
+                ("100".to_string(), Some("  ".to_string())),
+            ],
+            &names,
+        );
+
+        assert_eq!(installs.len(), 2);
+        assert_eq!(installs[0].name, "Far Cry 5");
+        assert_eq!(installs[0].install_dir, PathBuf::from("D:/Games/Ubisoft/Far Cry 5"));
+        assert_eq!(installs[1].name, "Rainbow Six Siege");
+    }
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while value >= 0x80 {
+            bytes.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+        bytes
+    }
+
+    /// A configuration cache record as the launcher writes it
+    fn cache_record(install_id: u64, yaml: &str) -> Vec<u8> {
+        let mut record = vec![0x08];
+        record.extend(varint(install_id));
+        record.push(0x10);
+        record.extend(varint(install_id + 1000));
+        record.push(0x1a);
+        record.extend(varint(yaml.len() as u64));
+        record.extend(yaml.as_bytes());
+
+        let mut bytes = vec![0x0a];
+        bytes.extend(varint(record.len() as u64));
+        bytes.extend(record);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_configuration_names() {
+        let localized = "version: 2.0\nroot:\n  name: l1\n  icon_image: icon.ico\nlocalizations:\n  default:\n    l1: \"Far Cry® 5\"\n";
+        let plain = "root:\n  start_game:\n    online:\n      name: Launcher\n  name: 'Rainbow Six Siege'\n";
+        let mut cache = cache_record(635, localized);
+        cache.extend(cache_record(4311, plain));
+        cache.extend(cache_record(99, "root:\n  installer: {}\n"));
+        // A truncated record at the end is ignored
+        cache.extend([0x0a, 0x40, 0x08]);
+
+        let names = parse_configuration_names(&cache);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["635"], "Far Cry® 5");
+        assert_eq!(names["4311"], "Rainbow Six Siege");
+    }
+
+    #[test]
+    fn test_parse_uninstall_key_id() {
+        assert_eq!(parse_uninstall_key_id("Uplay Install 635"), Some("635".to_string()));
+        assert_eq!(parse_uninstall_key_id("Uplay"), None);
+        assert_eq!(parse_uninstall_key_id("Uplay Install abc"), None);
+    }
+
+    #[test]
+    fn test_pick_game_exe() {
+        let picked = pick_game_exe(vec![
+            (PathBuf::from("FarCry5.exe"), 300_000_000),
+            (PathBuf::from("unins000.exe"), 400_000_000),
+            (PathBuf::from("CrashReporter.exe"), 2_000_000),
+        ]);
+        assert_eq!(picked, Some(PathBuf::from("FarCry5.exe")));
+        assert_eq!(pick_game_exe(vec![(PathBuf::from("vc_redist.x64.exe"), 1)]), None);
+    }
+
+    #[test]
+    fn test_ubisoft_launch_url() {
+        assert_eq!(ubisoft_launch_url("635"), "uplay://launch/635/0");
+    }
+}
@@ -0,0 +1,210 @@
+// Xbox / Microsoft Store (Game Pass) detector
+// Enumerates installed packages with Get-AppxPackage (read-only) and keeps the ones shipping a
+// MicrosoftGame.config, which only Xbox/GDK games have. Packages have no launchable exe path,
+// so games start through their shell:appsFolder entry
+
+#![cfg_attr(not(windows), allow(dead_code, unused_imports))]
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+use crate::launcher::icon_extractor::get_icon_cache_dir;
+use crate::models::launcher::{DetectedGame, GameSource};
+
+/// Prefix of the app_id given to Xbox games, followed by `<PackageFamilyName>!<ApplicationId>`
+pub const XBOX_APP_ID_PREFIX: &str = "xbox_";
+
+/// Stub the GDK puts in front of some games - the real game process is listed after it
+const LAUNCH_HELPER_EXE: &str = "gamelaunchhelper.exe";
+
+/// One package as printed by the PowerShell query
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XboxPackage {
+    name: String,
+    display_name: Option<String>,
+    package_family_name: String,
+    install_location: String,
+    app_id: Option<String>,
+    logo: Option<String>,
+}
+
+/// `explorer.exe` argument that starts a packaged app
+pub fn xbox_launch_target(app_user_model_id: &str) -> String {
+    format!(r"shell:appsFolder\{}", app_user_model_id)
+}
+
+/// The game's own executable from MicrosoftGame.config, skipping the GDK launch helper
+fn parse_game_executable(config: &str) -> Option<String> {
+    let executables: Vec<&str> = config
+        .match_indices("<Executable ")
+        .filter_map(|(start, _)| {
+            let tag = &config[start..];
+            let tag = &tag[..tag.find('>')?];
+            let name_start = tag.find("Name=\"")? + "Name=\"".len();
+            let name_len = tag[name_start..].find('"')?;
+            Some(&tag[name_start..name_start + name_len])
+        })
+        .collect();
+
+    executables
+        .iter()
+        .find(|e| !e.to_lowercase().ends_with(LAUNCH_HELPER_EXE))
+        .or(executables.first())
+        .map(|e| e.to_string())
+}
+
+/// Manifest logos are listed without their scale qualifier ("Assets\StoreLogo.png" is on disk as
+/// "Assets\StoreLogo.scale-200.png"), so pick the largest scaled variant
+fn resolve_logo_asset(install_path: &Path, logo: &str) -> Option<PathBuf> {
+    let exact = install_path.join(logo);
+    if exact.exists() {
+        return Some(exact);
+    }
+
+    let stem = exact.file_stem()?.to_string_lossy().to_lowercase();
+    let scale_of = |name: &str| -> Option<u32> {
+        let rest = name.strip_prefix(&stem)?.strip_prefix(".scale-")?;
+        rest.strip_suffix(".png")?.parse().ok()
+    };
+
+    std::fs::read_dir(exact.parent()?)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            scale_of(&name).map(|scale| (scale, entry.path()))
+        })
+        .max_by_key(|(scale, _)| *scale)
+        .map(|(_, path)| path)
+}
+
+/// Copy the package logo into the icon cache (packaged assets live under the protected WindowsApps folder)
+fn cache_package_logo(install_path: &Path, logo: &str, package_family_name: &str) -> Option<String> {
+    let source = resolve_logo_asset(install_path, logo)?;
+    let cache_dir = get_icon_cache_dir()?;
+    std::fs::create_dir_all(&cache_dir).ok()?;
+
+    let output_path = cache_dir.join(format!("xbox_{}.png", package_family_name));
+    if !output_path.exists() {
+        std::fs::copy(&source, &output_path).ok()?;
+    }
+    Some(output_path.to_string_lossy().to_string())
+}
+
+#[cfg(windows)]
+fn query_game_packages() -> Vec<XboxPackage> {
+    use std::process::Command;
+
+    let ps_script = r#"
+$ErrorActionPreference = 'SilentlyContinue'
+$games = Get-AppxPackage | Where-Object {
+    -not $_.IsFramework -and $_.InstallLocation -and (Test-Path (Join-Path $_.InstallLocation 'MicrosoftGame.config'))
+} | ForEach-Object {
+    $manifest = Get-AppxPackageManifest $_
+    $app = @($manifest.Package.Applications.Application)[0]
+    [PSCustomObject]@{
+        Name = $_.Name
+        DisplayName = $manifest.Package.Properties.DisplayName
+        PackageFamilyName = $_.PackageFamilyName
+        InstallLocation = $_.InstallLocation
+        AppId = $app.Id
+        Logo = $manifest.Package.Properties.Logo
+    }
+}
+ConvertTo-Json -InputObject @($games) -Compress
+"#;
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", ps_script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            serde_json::from_slice(&output.stdout).unwrap_or_default()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(windows)]
+pub fn detect_xbox_games() -> Vec<DetectedGame> {
+    let mut games = Vec::new();
+
+    for package in query_game_packages() {
+        let install_path = PathBuf::from(&package.install_location);
+        let Some(game_exe) = std::fs::read_to_string(install_path.join("MicrosoftGame.config"))
+            .ok()
+            .and_then(|config| parse_game_executable(&config))
+        else {
+            continue;
+        };
+
+        // Unresolved resource references ("ms-resource:...") fall back to the package name
+        let name = package
+            .display_name
+            .filter(|n| !n.is_empty() && !n.starts_with("ms-resource:"))
+            .unwrap_or(package.name);
+        let app_user_model_id = format!("{}!{}", package.package_family_name, package.app_id.as_deref().unwrap_or("App"));
+
+        let icon_path = package
+            .logo
+            .as_deref()
+            .and_then(|logo| cache_package_logo(&install_path, logo, &package.package_family_name));
+
+        games.push(DetectedGame {
+            name,
+            executable_path: install_path.join(&game_exe).to_string_lossy().to_string(),
+            install_path: install_path.to_string_lossy().to_string(),
+            source: GameSource::Xbox,
+            app_id: Some(format!("{}{}", XBOX_APP_ID_PREFIX, app_user_model_id)),
+            icon_path,
+            launch_args: None,
+        });
+    }
+
+    games
+}
+
+#[cfg(not(windows))]
+pub fn detect_xbox_games() -> Vec<DetectedGame> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_game_executable() {
+        let config = r#"<Game configVersion="1">
+            <ExecutableList>
+                <Executable Name="gamelaunchhelper.exe" Id="Game" />
+                <Executable Name="Binaries\WinGDK\Game-WinGDK-Shipping.exe" TargetDeviceFamily="PC" />
+            </ExecutableList>
+        </Game>"#;
+        assert_eq!(
+            parse_game_executable(config),
+            Some(r"Binaries\WinGDK\Game-WinGDK-Shipping.exe".to_string())
+        );
+
+        let single = r#"<ExecutableList><Executable Name="Forza.exe" Id="Game"/></ExecutableList>"#;
+        assert_eq!(parse_game_executable(single), Some("Forza.exe".to_string()));
+        assert_eq!(parse_game_executable("<Game></Game>"), None);
+    }
+
+    #[test]
+    fn test_xbox_launch_target() {
+        assert_eq!(
+            xbox_launch_target("Microsoft.SunriseBaseGame_8wekyb3d8bbwe!SunriseReleaseFinal"),
+            r"shell:appsFolder\Microsoft.SunriseBaseGame_8wekyb3d8bbwe!SunriseReleaseFinal"
+        );
+    }
+}
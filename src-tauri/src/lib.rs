@@ -7,8 +7,10 @@ mod logging;
 mod models;
 mod performance;
 mod process_manager;
+mod server_monitor;
 mod task_monitor;
 mod utils;
+mod valorant;
 
 use commands::{
     audio_detection::{
@@ -17,9 +19,20 @@ use commands::{
         list_audio_detection_jobs, list_feedback_sessions, save_feedback_session,
         start_audio_detection_job, start_model_training, submit_audio_detection_job,
     },
-    auth::{capture_auth_cookies, close_auth_window, get_auth_status, get_stored_credentials, logout, open_auth_window},
+    auth::{
+        capture_auth_cookies, close_auth_window, get_auth_status, get_stored_credentials, list_valorant_accounts,
+        logout, open_auth_window, set_active_valorant_account,
+    },
     autostart::{disable_autostart, enable_autostart, is_autostart_enabled},
-    discord::{connect_discord, disconnect_discord, is_discord_connected},
+    changelog::{announce_update, get_update_changelog, take_whats_new},
+    update_deferral::{clear_update_deferral, skip_update_version, snooze_update},
+    update_rollback::{get_rollback_info, rollback_update},
+    data_directory::{get_data_directory_info, migrate_data_directory, reset_feature_data},
+    discord::{
+        clear_custom_presence, connect_discord, disconnect_discord, get_current_presence,
+        get_presence_template_placeholders, is_discord_connected, preview_discord_presence,
+        set_custom_presence,
+    },
     downloads::{add_download, cancel_download, delete_download, list_downloads, start_download, validate_download_path},
     friends::{
         add_friend_by_code, add_friend_locally, add_wishlist_item, clear_friends_data,
@@ -45,17 +58,27 @@ use commands::{
         add_game_to_whitelist, delete_gaming_session, end_gaming_session,
         get_active_gaming_session, get_active_session_state, get_bottleneck_thresholds,
         get_game_whitelist, get_gaming_sessions, get_session_details,
-        is_gaming_detection_running, remove_game_from_whitelist, start_gaming_detection,
-        stop_gaming_detection, toggle_game_enabled, update_bottleneck_thresholds,
+        is_gaming_detection_running, remove_game_from_whitelist, set_whitelist_game_hidden_from_presence,
+        start_gaming_detection, stop_gaming_detection, toggle_game_enabled, update_bottleneck_thresholds,
         update_game_whitelist,
     },
     launcher::{
-        add_detected_games, add_manual_game, clear_game_scan_cache, get_game_library, get_icon_base64,
-        launch_game, remove_game_from_library, scan_for_games,
+        add_detected_games, add_manual_game, cancel_game_scan, clear_game_scan_cache, create_collection,
+        create_game_shortcut, delete_collection, fetch_game_artwork, get_all_playtimes, get_game_artwork,
+        get_game_library, get_game_playtime, get_icon_base64, get_ignored_games, get_recently_played,
+        get_running_games, ignore_detected_game, launch_game, open_game_folder, remove_game_from_library,
+        rename_collection, reorder_library, reset_game_icon, reset_game_playtime, reveal_game_executable,
+        scan_for_games, set_game_collections, set_game_favorite, set_game_hidden_from_presence, set_game_icon,
+        stop_game, unignore_detected_game, update_game_hooks, update_game_launch_options, validate_game_library,
+    },
+    ml_jobs::{
+        cancel_ml_job, delete_ml_job, get_available_models, list_ml_jobs, set_ml_job_priority, start_ml_job,
+        submit_ml_job,
     },
-    ml_jobs::{cancel_ml_job, delete_ml_job, get_available_models, list_ml_jobs, start_ml_job, submit_ml_job},
     performance::{
-        get_performance_snapshot, has_nvidia_gpu, is_performance_monitoring,
+        get_disk_space, get_performance_history, get_performance_snapshot, get_power_status, has_nvidia_gpu,
+        is_performance_monitoring, list_gpus, get_performance_alerts, update_performance_alerts,
+        start_performance_capture, stop_performance_capture,
         start_performance_monitoring, stop_performance_monitoring,
     },
     playlist_uploader::{
@@ -63,26 +86,56 @@ use commands::{
         restart_discord_bot, sync_from_server, upload_to_server,
     },
     server::{
-        check_local_file_exists, clear_ssh_credentials, execute_ssh_command, get_quick_actions,
-        get_server_config, get_ssh_credentials, get_system_status, has_ssh_credentials,
-        read_local_file, save_ssh_credentials, test_ssh_connection, update_server_config,
-        upload_file_to_server,
+        check_local_file_exists, clear_known_host, clear_ssh_credentials, execute_quick_action,
+        execute_ssh_command, generate_ssh_keypair, get_quick_actions, get_server_config, get_ssh_connection_state,
+        get_ssh_credentials, get_system_status, has_ssh_credentials, read_local_file, save_ssh_credentials,
+        test_ssh_connection, update_server_config, upload_file_to_server, verify_host_key,
+    },
+    server_metrics::get_server_metrics_history,
+    settings::{
+        export_settings, get_settings, get_user_avatar_base64, get_user_avatar_path,
+        get_user_avatar_thumbnail_base64, import_settings, reset_settings, save_user_avatar, update_settings,
+        validate_settings,
+    },
+    settings_profiles::{
+        apply_settings_profile, delete_settings_profile, list_settings_profiles, save_settings_profile,
+    },
+    sftp::{
+        cancel_sftp_transfer, download_file_from_server, sftp_delete, sftp_download_file, sftp_list_directory,
+        sftp_mkdir,
     },
-    settings::{get_settings, update_settings, save_user_avatar, get_user_avatar_path, get_user_avatar_base64},
+    ssh_tunnels::{close_ssh_tunnel, create_ssh_tunnel, list_ssh_tunnels},
+    systemd::{get_systemd_services, systemd_service_action},
     task_monitor::{
-        clear_restore_list, delete_gaming_profile, execute_gaming_profile, get_gaming_profiles,
-        get_kill_recommendations, get_process_list, get_restore_list, get_system_summary,
-        kill_by_category, kill_multiple_processes, kill_single_process, restore_processes_now,
-        save_gaming_profile, set_default_gaming_profile,
+        add_protected_process, clear_process_category_override, clear_restore_list,
+        delete_custom_category, delete_gaming_profile, disable_startup_item, enable_startup_item,
+        execute_gaming_profile, export_gaming_profiles, get_category_exclusions,
+        get_gaming_profiles, get_kill_recommendations, get_process_affinity,
+        get_process_category_config, get_process_details, get_process_history, get_process_list,
+        get_protected_processes, get_restore_list, get_services_summary, get_startup_items,
+        get_system_summary, get_top_network_processes, import_gaming_profiles, kill_by_category,
+        kill_multiple_processes, kill_process_tree, kill_single_process, preview_gaming_profile,
+        remove_protected_process, restore_processes_now, restore_single_process, resume_process,
+        save_custom_category, save_gaming_profile, set_category_exclusions,
+        set_default_gaming_profile, set_process_affinity, set_process_category_override,
+        set_process_priority, start_service, stop_service, suspend_process, undo_last_kill,
+    },
+    updater::{
+        cancel_update_download, check_for_update, download_update, get_current_version, install_update,
+        build_channel, DownloadedUpdateBytes,
+    },
+    valorant::{
+        add_valorant_wishlist_item, check_valorant_store, get_bundle_history, get_skin_appearance_stats,
+        get_skin_metadata, get_store_history, get_store_streaks, get_valorant_store, get_valorant_wishlist,
+        remove_valorant_wishlist_item, should_auto_refresh_store,
     },
-    updater::{check_for_update, download_update, get_current_version, install_update, DownloadedUpdateBytes},
-    valorant::{check_valorant_store, get_store_history, get_valorant_store, should_auto_refresh_store},
 };
 use discord::DiscordPresenceManager;
+use file_manager::feature_data::write_feature_defaults;
 use file_manager::initialize_json_file;
 use gaming::{BottleneckAnalyzer, GameDetectionState, GamingSessionManager};
 use launcher::PlaytimeTrackerState;
-use models::{BottleneckThresholds, GameLibrary, GameWhitelist, GamingSession, QuickActionsConfig, ServerConfig, Settings};
+use models::{BottleneckThresholds, DataFeature, GameWhitelist, QuickActionsConfig, ServerConfig};
 use performance::{MonitoringState, SharedMetrics};
 use std::fs;
 use std::sync::Arc;
@@ -92,12 +145,12 @@ use tauri::{
     Manager, WindowEvent,
 };
 use log::{error, info, warn};
+use utils::settings_events;
 use utils::{
-    get_audio_detection_jobs_json_path, get_bottleneck_thresholds_json_path, get_downloads_json_path,
-    get_game_library_json_path, get_game_whitelist_json_path, get_gaming_sessions_json_path,
-    get_last_run_version_path, get_logs_dir, get_ml_jobs_json_path, get_quick_actions_json_path,
-    get_server_config_json_path, get_settings_json_path, get_valorant_store_json_path,
-    initialize_data_directories,
+    get_audio_detection_jobs_json_path, get_bottleneck_thresholds_json_path, get_game_whitelist_json_path,
+    get_last_run_channel_path, get_last_run_version_path, get_logs_dir, get_ml_jobs_json_path,
+    get_performance_alerts_json_path, get_quick_actions_json_path, get_server_config_json_path,
+    get_settings_json_path, initialize_data_directories,
 };
 
 fn initialize_app_data() -> Result<(), String> {
@@ -105,11 +158,10 @@ fn initialize_app_data() -> Result<(), String> {
 
     let empty_vec: Vec<serde_json::Value> = vec![];
 
-    initialize_json_file(&get_downloads_json_path(), &empty_vec)?;
+    write_feature_defaults(DataFeature::Downloads, false)?;
     initialize_json_file(&get_ml_jobs_json_path(), &empty_vec)?;
-    initialize_json_file(&get_valorant_store_json_path(), &empty_vec)?;
     initialize_json_file(&get_audio_detection_jobs_json_path(), &empty_vec)?;
-    initialize_json_file(&get_settings_json_path(), &Settings::default())?;
+    file_manager::settings_migrations::initialize_settings_file(&get_settings_json_path())?;
 
     // Server monitoring config files
     initialize_json_file(&get_server_config_json_path(), &ServerConfig::default())?;
@@ -117,14 +169,15 @@ fn initialize_app_data() -> Result<(), String> {
 
     // Gaming performance analyzer files
     initialize_json_file(&get_game_whitelist_json_path(), &GameWhitelist::default_whitelist())?;
-    initialize_json_file(&get_gaming_sessions_json_path(), &Vec::<GamingSession>::new())?;
+    write_feature_defaults(DataFeature::GamingSessions, false)?;
     initialize_json_file(&get_bottleneck_thresholds_json_path(), &BottleneckThresholds::default())?;
+    initialize_json_file(&get_performance_alerts_json_path(), &performance::alerts::default_alerts())?;
 
     // Game launcher files
-    initialize_json_file(&get_game_library_json_path(), &GameLibrary::new())?;
+    write_feature_defaults(DataFeature::GameLibrary, false)?;
 
     // Task monitor files - initialize gaming profiles
-    task_monitor::profiles::initialize_profiles()?;
+    write_feature_defaults(DataFeature::TaskMonitorProfiles, false)?;
 
     info!("App data initialized successfully");
     Ok(())
@@ -160,6 +213,7 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
@@ -209,6 +263,12 @@ pub fn run() {
             // Clean up old log files (7+ days old)
             logging::cleanup_old_logs();
 
+            // Processes still suspended when Atlas last exited would otherwise stay frozen
+            std::thread::spawn(task_monitor::suspend::resume_left_suspended);
+
+            task_monitor::start_history_sampler();
+            task_monitor::leaks::start_leak_detector(app.handle().clone());
+
             let current_version = app.package_info().version.to_string();
             let version_file = get_last_run_version_path();
             let last_version = fs::read_to_string(&version_file).unwrap_or_default();
@@ -216,9 +276,27 @@ pub fn run() {
             let just_updated = !last_version.is_empty() && last_version.trim() != current_version;
 
             let _ = fs::write(&version_file, &current_version);
+            commands::update_rollback::on_startup(&current_version);
+
+            // Channel the previous build was released on; runs before channels existed were stable
+            let channel_file = get_last_run_channel_path();
+            let current_channel = build_channel(&current_version);
+            let last_channel = fs::read_to_string(&channel_file)
+                .map(|c| c.trim().to_string())
+                .ok()
+                .filter(|c| !c.is_empty())
+                .unwrap_or_else(|| "stable".to_string());
+            let _ = fs::write(&channel_file, current_channel);
 
             if just_updated {
-                info!("App updated from {} to {} - bringing window to foreground", last_version.trim(), current_version);
+                info!(
+                    "App updated from {} ({}) to {} ({}) - bringing window to foreground",
+                    last_version.trim(),
+                    last_channel,
+                    current_version,
+                    current_channel
+                );
+                announce_update(app.handle(), last_version.trim(), &current_version, current_channel);
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
                     let _ = window.unminimize();
@@ -227,14 +305,72 @@ pub fn run() {
             }
 
             let settings = get_settings().unwrap_or_default();
+            shared_metrics.set_history_window_minutes(settings.performance_history_minutes);
+
+            discord_manager.start_watchdog(app.handle().clone());
             if settings.discord_rich_presence_enabled {
-                if let Err(e) = discord_manager.connect() {
+                // Retried by the watchdog when Discord isn't running yet
+                if let Err(e) = discord_manager.enable() {
                     warn!("Failed to connect to Discord: {}", e);
                 } else {
                     info!("Discord Rich Presence connected");
                 }
             }
 
+            // React to settings saved from anywhere (settings view, imports)
+            let presence = discord_manager.clone();
+            settings_events::subscribe(move |changes| {
+                let Some(enabled) =
+                    settings_events::changed_value(changes, "discord_rich_presence_enabled").and_then(|v| v.as_bool())
+                else {
+                    return;
+                };
+                // The settings view connects before saving, so only act when the state differs
+                if enabled && !presence.is_connected() {
+                    if let Err(e) = presence.enable() {
+                        warn!("Failed to connect to Discord: {}", e);
+                    }
+                } else if !enabled && presence.is_enabled() {
+                    let _ = presence.disconnect();
+                }
+            });
+            let presence = discord_manager.clone();
+            settings_events::subscribe(move |changes| {
+                let appearance_changed = [
+                    "discord_presence_details_template",
+                    "discord_presence_state_template",
+                    "discord_presence_buttons",
+                ]
+                .iter()
+                .any(|key| settings_events::changed_value(changes, key).is_some());
+                if appearance_changed {
+                    if let Err(e) = presence.refresh() {
+                        warn!("Failed to update Discord presence: {}", e);
+                    }
+                }
+            });
+            let downloads_app = app.handle().clone();
+            settings_events::subscribe(move |changes| {
+                if settings_events::changed_value(changes, "max_concurrent_downloads").is_some() {
+                    commands::downloads::start_pending_downloads(&downloads_app);
+                }
+            });
+            let ml_jobs_app = app.handle().clone();
+            commands::ml_jobs::resume_ml_job_queue(&ml_jobs_app);
+            settings_events::subscribe(move |changes| {
+                if settings_events::changed_value(changes, "max_concurrent_ml_jobs").is_some() {
+                    commands::ml_jobs::start_queued_ml_jobs(&ml_jobs_app);
+                }
+            });
+            settings_events::subscribe(|changes| {
+                let schedule_changed = ["auto_download_updates", "update_check_interval_hours", "update_channel"]
+                    .iter()
+                    .any(|key| settings_events::changed_value(changes, key).is_some());
+                if schedule_changed {
+                    commands::update_scheduler::rearm();
+                }
+            });
+
             // Create tray menu
             let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
             let quit_item = MenuItem::with_id(app, "quit", "Quit Atlas", true, None::<&str>)?;
@@ -292,6 +428,11 @@ pub fn run() {
                 monitoring_state.clone(),
             ));
             app.manage(session_manager);
+
+            valorant::store_scheduler::start(app.handle().clone());
+            commands::update_scheduler::start(app.handle().clone());
+            server_monitor::ssh_pool::init(app.handle().clone());
+            server_monitor::metrics_sampler::start(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -302,6 +443,8 @@ pub fn run() {
             get_auth_status,
             get_stored_credentials,
             logout,
+            list_valorant_accounts,
+            set_active_valorant_account,
             // Download commands
             list_downloads,
             add_download,
@@ -314,6 +457,7 @@ pub fn run() {
             submit_ml_job,
             start_ml_job,
             cancel_ml_job,
+            set_ml_job_priority,
             delete_ml_job,
             get_available_models,
             // Audio Detection commands
@@ -336,19 +480,44 @@ pub fn run() {
             check_valorant_store,
             get_store_history,
             should_auto_refresh_store,
+            get_skin_metadata,
+            get_valorant_wishlist,
+            add_valorant_wishlist_item,
+            remove_valorant_wishlist_item,
+            get_bundle_history,
+            get_skin_appearance_stats,
+            get_store_streaks,
             get_settings,
             update_settings,
+            validate_settings,
             save_user_avatar,
             get_user_avatar_path,
             get_user_avatar_base64,
+            get_user_avatar_thumbnail_base64,
+            export_settings,
+            import_settings,
+            reset_settings,
+            list_settings_profiles,
+            save_settings_profile,
+            apply_settings_profile,
+            delete_settings_profile,
             // Discord Rich Presence
             connect_discord,
             disconnect_discord,
             is_discord_connected,
+            get_presence_template_placeholders,
+            preview_discord_presence,
+            set_custom_presence,
+            clear_custom_presence,
+            get_current_presence,
             // Autostart commands
             enable_autostart,
             disable_autostart,
             is_autostart_enabled,
+            // Data directory
+            get_data_directory_info,
+            migrate_data_directory,
+            reset_feature_data,
             // Server monitoring
             get_server_config,
             update_server_config,
@@ -356,25 +525,51 @@ pub fn run() {
             get_ssh_credentials,
             has_ssh_credentials,
             clear_ssh_credentials,
+            generate_ssh_keypair,
             get_quick_actions,
+            execute_quick_action,
             execute_ssh_command,
             get_system_status,
+            get_ssh_connection_state,
+            verify_host_key,
+            clear_known_host,
             test_ssh_connection,
             upload_file_to_server,
+            sftp_list_directory,
+            sftp_download_file,
+            download_file_from_server,
+            cancel_sftp_transfer,
+            sftp_delete,
+            sftp_mkdir,
+            get_systemd_services,
+            systemd_service_action,
+            create_ssh_tunnel,
+            close_ssh_tunnel,
+            list_ssh_tunnels,
+            get_server_metrics_history,
             read_local_file,
             check_local_file_exists,
             // Performance monitoring commands
             start_performance_monitoring,
             stop_performance_monitoring,
             get_performance_snapshot,
+            get_performance_history,
             is_performance_monitoring,
             has_nvidia_gpu,
+            get_disk_space,
+            get_power_status,
+            list_gpus,
+            get_performance_alerts,
+            update_performance_alerts,
+            start_performance_capture,
+            stop_performance_capture,
             // Gaming performance commands
             get_game_whitelist,
             update_game_whitelist,
             add_game_to_whitelist,
             remove_game_from_whitelist,
             toggle_game_enabled,
+            set_whitelist_game_hidden_from_presence,
             start_gaming_detection,
             stop_gaming_detection,
             is_gaming_detection_running,
@@ -389,17 +584,52 @@ pub fn run() {
             // Updater commands
             check_for_update,
             download_update,
+            cancel_update_download,
             install_update,
+            get_rollback_info,
+            rollback_update,
+            skip_update_version,
+            snooze_update,
+            clear_update_deferral,
             get_current_version,
+            get_update_changelog,
+            take_whats_new,
             // Game launcher commands
             get_game_library,
             scan_for_games,
             clear_game_scan_cache,
+            cancel_game_scan,
             add_detected_games,
             add_manual_game,
             remove_game_from_library,
+            update_game_launch_options,
+            update_game_hooks,
+            set_game_favorite,
+            set_game_hidden_from_presence,
+            reorder_library,
+            get_recently_played,
+            get_game_playtime,
+            get_all_playtimes,
+            reset_game_playtime,
             launch_game,
             get_icon_base64,
+            fetch_game_artwork,
+            get_game_artwork,
+            open_game_folder,
+            reveal_game_executable,
+            create_game_shortcut,
+            validate_game_library,
+            get_running_games,
+            stop_game,
+            ignore_detected_game,
+            unignore_detected_game,
+            get_ignored_games,
+            create_collection,
+            rename_collection,
+            delete_collection,
+            set_game_collections,
+            set_game_icon,
+            reset_game_icon,
             // Gacha history commands
             get_gacha_accounts,
             get_gacha_history,
@@ -421,8 +651,15 @@ pub fn run() {
             restart_discord_bot,
             // Task monitor commands
             get_process_list,
+            get_process_details,
+            get_process_history,
+            get_top_network_processes,
             get_system_summary,
             kill_single_process,
+            kill_process_tree,
+            set_process_priority,
+            get_process_affinity,
+            set_process_affinity,
             kill_multiple_processes,
             kill_by_category,
             get_gaming_profiles,
@@ -430,11 +667,38 @@ pub fn run() {
             delete_gaming_profile,
             set_default_gaming_profile,
             execute_gaming_profile,
+            preview_gaming_profile,
+            export_gaming_profiles,
+            import_gaming_profiles,
             get_kill_recommendations,
+            // Task monitor protected process commands
+            get_protected_processes,
+            add_protected_process,
+            remove_protected_process,
+            // Task monitor category override commands
+            get_process_category_config,
+            set_process_category_override,
+            clear_process_category_override,
+            save_custom_category,
+            delete_custom_category,
+            get_category_exclusions,
+            set_category_exclusions,
+            // Task monitor startup program commands
+            get_startup_items,
+            disable_startup_item,
+            enable_startup_item,
+            // Task monitor service commands
+            get_services_summary,
+            stop_service,
+            start_service,
             // Task monitor restore commands
             get_restore_list,
             clear_restore_list,
             restore_processes_now,
+            undo_last_kill,
+            suspend_process,
+            resume_process,
+            restore_single_process,
             // Friends commands
             get_local_user,
             save_local_user,
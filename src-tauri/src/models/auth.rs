@@ -34,6 +34,49 @@ impl RiotAuthCookies {
     }
 }
 
+/// A Riot account with its own cookies, keyed by PUUID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiotAccount {
+    pub id: String,
+    /// Riot ID (name#tag), filled in by store checks
+    pub name: Option<String>,
+    pub region: String,
+    pub cookies: RiotAuthCookies,
+}
+
+/// All captured Riot accounts (riot_accounts.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RiotAccounts {
+    pub active_account_id: Option<String>,
+    pub accounts: Vec<RiotAccount>,
+}
+
+impl RiotAccounts {
+    pub fn find(&self, id: &str) -> Option<&RiotAccount> {
+        self.accounts.iter().find(|a| a.id == id)
+    }
+
+    pub fn find_mut(&mut self, id: &str) -> Option<&mut RiotAccount> {
+        self.accounts.iter_mut().find(|a| a.id == id)
+    }
+
+    /// The given account, or the active one when None
+    pub fn resolve(&self, id: Option<&str>) -> Option<&RiotAccount> {
+        self.find(id.or(self.active_account_id.as_deref())?)
+    }
+}
+
+/// Account summary for the frontend (no cookies)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValorantAccountInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub region: String,
+    pub is_active: bool,
+    pub has_full_cookies: bool,
+    pub captured_at: Option<String>,
+}
+
 /// Authentication status for the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthStatus {
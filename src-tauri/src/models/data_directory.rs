@@ -0,0 +1,62 @@
+// Data directory location and relocation models
+use serde::{Deserialize, Serialize};
+
+/// data_location.json, kept in the default data directory so it can be found before the data is
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataLocation {
+    /// Relocated data directory; None means the default one
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    /// Relocation that was started but not switched over to, so it can be resumed
+    #[serde(default)]
+    pub migration: Option<DataMigration>,
+    /// Old data directory, deleted on the next start once nothing runs from it
+    #[serde(default)]
+    pub pending_cleanup: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataMigration {
+    pub from: String,
+    pub to: String,
+    pub started_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataFolderSize {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDirectoryInfo {
+    pub path: String,
+    pub is_default: bool,
+    pub total_bytes: u64,
+    /// Top-level folders, largest first
+    pub folders: Vec<DataFolderSize>,
+    pub interrupted_migration: Option<DataMigration>,
+}
+
+/// Payload of the data_directory:migration_progress event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataMigrationProgress {
+    /// "copying" or "verifying"
+    pub stage: String,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// Feature whose data `reset_feature_data` can reset on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataFeature {
+    Downloads,
+    GamingSessions,
+    GameLibrary,
+    TaskMonitorProfiles,
+    ValorantStore,
+    Friends,
+}
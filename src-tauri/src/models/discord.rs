@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+/// A placeholder usable in the presence templates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresencePlaceholder {
+    /// As written in a template, e.g. "{game}"
+    pub placeholder: String,
+    pub description: String,
+    pub example: String,
+}
+
+/// A link button under the presence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceButton {
+    pub label: String,
+    /// https only
+    pub url: String,
+}
+
+/// Activity sent to Discord, as returned by `preview_discord_presence`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceActivity {
+    /// Discord application the activity is shown under
+    pub application_id: String,
+    /// None when the template rendered too short to show
+    pub details: Option<String>,
+    pub state: Option<String>,
+    /// Unix seconds Discord counts the elapsed time from
+    pub start_timestamp: Option<i64>,
+    pub large_image: String,
+    pub large_text: String,
+    pub buttons: Vec<PresenceButton>,
+}
+
+/// Discord application a game's presence is shown under, instead of Atlas's
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscordGameApp {
+    /// Application (client) ID from the Discord developer portal; None keeps Atlas's application
+    #[serde(default)]
+    pub app_id: Option<String>,
+    /// Large image: an asset key of that application, or an https image URL
+    #[serde(default)]
+    pub large_image: Option<String>,
+}
+
+/// Discord connection health, from `is_discord_connected` and `discord:connection_changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordConnectionStatus {
+    pub connected: bool,
+    /// Presence is turned on, so a lost connection is retried
+    pub enabled: bool,
+    /// Why the last connection attempt failed or the connection was lost
+    pub last_error: Option<String>,
+    /// Seconds until the next reconnect attempt; None when none is planned
+    pub retry_in_seconds: Option<u64>,
+}
+
+/// Where the shown presence comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceMode {
+    /// Set with `set_custom_presence`
+    Custom,
+    Gaming,
+    Idle,
+}
+
+/// The presence Atlas shows, from `get_current_presence`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentPresence {
+    pub mode: PresenceMode,
+    pub activity: PresenceActivity,
+    /// Unix seconds the custom presence ends at
+    pub custom_until: Option<i64>,
+    /// False while Discord isn't connected, so nothing is shown
+    pub shown: bool,
+}
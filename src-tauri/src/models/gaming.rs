@@ -1,5 +1,7 @@
 // Gaming Performance Analyzer data structures
 use serde::{Deserialize, Serialize};
+use super::discord::DiscordGameApp;
+use super::performance::CoreKind;
 
 /// Game whitelist configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -14,6 +16,12 @@ pub struct GameEntry {
     pub process_name: String,   // Process name to watch (e.g., "VALORANT-Win64-Shipping.exe")
     pub icon: Option<String>,   // Optional icon identifier
     pub enabled: bool,          // Whether this entry is enabled for detection
+    #[serde(default)]
+    pub affinity_mask: Option<u64>, // CPU cores to pin the game to when detected - None leaves it alone
+    #[serde(default)]
+    pub discord_app: Option<DiscordGameApp>, // Discord application and art for the presence - None uses Atlas's
+    #[serde(default)]
+    pub hide_from_presence: bool, // Keep the game's name out of the Discord and friends presence
 }
 
 /// Gaming session data
@@ -42,6 +50,10 @@ pub enum SessionStatus {
 pub struct TopCoreInfo {
     pub core_index: usize,              // Core number (0-based)
     pub usage_percent: f32,             // Usage percentage (0-100)
+    #[serde(default)]
+    pub core_kind: Option<CoreKind>,    // P-core / E-core on hybrid CPUs
+    #[serde(default)]
+    pub temperature_celsius: Option<f32>, // Core temperature (package temp if no per-core sensor)
 }
 
 /// Metrics snapshot during gaming session
@@ -56,6 +68,22 @@ pub struct MetricsSnapshot {
     pub vram_percent: Option<f32>,      // VRAM usage (0-100) - None if no GPU
     pub cpu_temp: Option<f32>,          // CPU temp in Celsius
     pub gpu_temp: Option<f32>,          // GPU temp in Celsius
+    #[serde(default)]
+    pub gpu_power_watts: Option<f32>,   // GPU board power draw in watts
+    #[serde(default)]
+    pub gpu_power_limit_watts: Option<f32>, // GPU enforced power limit in watts
+    #[serde(default)]
+    pub gpu_fan_percent: Option<f32>,   // GPU fan speed (0-100)
+    #[serde(default)]
+    pub cpu_clock_mhz: Option<f32>,     // Average CPU frequency in MHz
+    #[serde(default)]
+    pub gpu_core_clock_mhz: Option<f32>, // GPU graphics clock in MHz
+    #[serde(default)]
+    pub gpu_memory_clock_mhz: Option<f32>, // GPU memory clock in MHz
+    #[serde(default)]
+    pub ram_available_mb: Option<f32>,  // Available physical memory in MB
+    #[serde(default)]
+    pub commit_percent: Option<f32>,    // Committed memory as % of the commit limit
 }
 
 /// Bottleneck event during session
@@ -88,11 +116,21 @@ pub struct SessionSummary {
     pub cpu: MetricStats,               // CPU statistics
     pub top_core_1: Option<MetricStats>, // Top core 1 statistics
     pub top_core_2: Option<MetricStats>, // Top core 2 statistics
+    #[serde(default)]
+    pub top_core: Option<TopCoreInfo>,  // Core most often the busiest (avg usage, max temp while busiest)
     pub gpu: Option<MetricStats>,       // GPU statistics
     pub ram: MetricStats,               // RAM statistics
     pub vram: Option<MetricStats>,      // VRAM statistics
     pub cpu_temp: Option<MetricStats>,  // CPU temperature statistics
     pub gpu_temp: Option<MetricStats>,  // GPU temperature statistics
+    #[serde(default)]
+    pub gpu_power: Option<MetricStats>, // GPU power draw statistics (watts)
+    #[serde(default)]
+    pub cpu_clock: Option<MetricStats>, // Average CPU frequency statistics (MHz)
+    #[serde(default)]
+    pub gpu_core_clock: Option<MetricStats>, // GPU graphics clock statistics (MHz)
+    #[serde(default)]
+    pub gpu_memory_clock: Option<MetricStats>, // GPU memory clock statistics (MHz)
     pub total_bottleneck_seconds: f64,  // Time spent in bottleneck state
     pub dominant_bottleneck: BottleneckType, // Most frequent bottleneck
     pub bottleneck_breakdown: Vec<BottleneckBreakdown>,
@@ -141,13 +179,19 @@ pub struct BottleneckThresholds {
     pub gpu_high: f32,              // GPU considered bottleneck when above (default: 90)
     pub cpu_low: f32,               // CPU considered underutilized when below (default: 70)
     pub gpu_low: f32,               // GPU considered underutilized when below (default: 70)
-    pub ram_high: f32,              // RAM bottleneck threshold (default: 90)
+    pub ram_high: f32,              // RAM % threshold, only used when available MB is unknown (default: 90)
     pub ram_available_min_mb: u64,  // Minimum available RAM in MB (default: 2048)
+    #[serde(default = "default_commit_high")]
+    pub commit_high: f32,           // Commit charge % of commit limit (default: 90)
     pub vram_high: f32,             // VRAM bottleneck threshold (default: 90)
     pub cpu_thermal_limit: f32,     // CPU thermal throttle temp (default: 90C)
     pub gpu_thermal_limit: f32,     // GPU thermal throttle temp (default: 85C)
 }
 
+fn default_commit_high() -> f32 {
+    90.0
+}
+
 impl Default for BottleneckThresholds {
     fn default() -> Self {
         Self {
@@ -157,6 +201,7 @@ impl Default for BottleneckThresholds {
             gpu_low: 70.0,
             ram_high: 90.0,
             ram_available_min_mb: 2048,
+            commit_high: default_commit_high(),
             vram_high: 90.0,
             cpu_thermal_limit: 90.0,
             gpu_thermal_limit: 85.0,
@@ -176,6 +221,14 @@ impl Default for MetricsSnapshot {
             vram_percent: None,
             cpu_temp: None,
             gpu_temp: None,
+            gpu_power_watts: None,
+            gpu_power_limit_watts: None,
+            gpu_fan_percent: None,
+            cpu_clock_mhz: None,
+            gpu_core_clock_mhz: None,
+            gpu_memory_clock_mhz: None,
+            ram_available_mb: None,
+            commit_percent: None,
         }
     }
 }
@@ -209,60 +262,90 @@ impl GameWhitelist {
                     process_name: "VALORANT-Win64-Shipping.exe".to_string(),
                     icon: Some("valorant".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "League of Legends".to_string(),
                     process_name: "League of Legends.exe".to_string(),
                     icon: Some("lol".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Counter-Strike 2".to_string(),
                     process_name: "cs2.exe".to_string(),
                     icon: Some("cs2".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Apex Legends".to_string(),
                     process_name: "r5apex.exe".to_string(),
                     icon: Some("apex".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Overwatch 2".to_string(),
                     process_name: "Overwatch.exe".to_string(),
                     icon: Some("overwatch".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Fortnite".to_string(),
                     process_name: "FortniteClient-Win64-Shipping.exe".to_string(),
                     icon: Some("fortnite".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Minecraft".to_string(),
                     process_name: "javaw.exe".to_string(),
                     icon: Some("minecraft".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Genshin Impact".to_string(),
                     process_name: "GenshinImpact.exe".to_string(),
                     icon: Some("genshin".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "PUBG".to_string(),
                     process_name: "TslGame.exe".to_string(),
                     icon: Some("pubg".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
                 GameEntry {
                     name: "Dota 2".to_string(),
                     process_name: "dota2.exe".to_string(),
                     icon: Some("dota2".to_string()),
                     enabled: true,
+                    affinity_mask: None,
+                    discord_app: None,
+                    hide_from_presence: false,
                 },
             ],
         }
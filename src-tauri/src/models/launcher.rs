@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Source of a detected game
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -7,6 +9,14 @@ pub enum GameSource {
     Steam,
     HoyoPlay,
     Riot,    // NEW: For Valorant, League of Legends, etc.
+    Epic,
+    BattleNet,
+    Xbox,
+    Ubisoft,
+    Ea,
+    Itch,
+    /// Non-Steam game added to the Steam library
+    SteamShortcut,
     Manual,
 }
 
@@ -41,23 +51,212 @@ pub struct LibraryGame {
     pub source: GameSource,
     pub app_id: Option<String>,
     pub icon_path: Option<String>,
+    /// Set by `set_game_icon` - scans never replace a user-chosen icon
+    #[serde(default)]
+    pub icon_user_set: bool,
     pub process_name: String,
     pub added_at: String,
     pub last_played: Option<String>,
     pub total_playtime_seconds: u64,
+    /// Number of play sessions counted into `total_playtime_seconds`
+    #[serde(default)]
+    pub session_count: u32,
     #[serde(default)]
     pub launch_args: Option<String>,  // Arguments to pass when launching (e.g., for Riot Client)
+    /// User-set arguments, appended after the detector's `launch_args`
+    #[serde(default)]
+    pub custom_launch_args: Option<String>,
+    #[serde(default)]
+    pub run_as_admin: bool,
+    /// Steam games: launch through steam://rungameid (None = the `steam_launch_via_protocol` setting)
+    #[serde(default)]
+    pub launch_via_steam: Option<bool>,
+    /// Working directory to launch in (None = ShellExecute's default)
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// Environment variables for the game's process (direct, non-elevated launches only)
+    #[serde(default)]
+    pub env_vars: HashMap<String, String>,
+    /// Favorites are pinned to the top of every sort order
+    #[serde(default)]
+    pub favorite: bool,
+    /// Position in the user's custom order (None = after the ordered games, in insertion order)
+    #[serde(default)]
+    pub sort_index: Option<u32>,
+    /// SteamGridDB art downloaded by `fetch_game_artwork`
+    #[serde(default)]
+    pub artwork: GameArtwork,
+    /// Run before the game is launched
+    #[serde(default)]
+    pub pre_launch_command: Option<GameHookCommand>,
+    /// Run after the game's process exits
+    #[serde(default)]
+    pub post_exit_command: Option<GameHookCommand>,
+    /// False once `validate_game_library` finds the game's files gone
+    #[serde(default = "default_installed")]
+    pub installed: bool,
+    /// Ids of the user collections the game is in
+    #[serde(default)]
+    pub collection_ids: Vec<String>,
+    /// Keep the game's name out of the Discord and friends presence
+    #[serde(default)]
+    pub hide_from_presence: bool,
+}
+
+fn default_installed() -> bool {
+    true
+}
+
+impl LibraryGame {
+    /// Whether the exe and install folder are still on disk
+    /// (Riot entries point at the shared Riot Client, so the game folder is what tells)
+    pub fn files_present(&self) -> bool {
+        Path::new(&self.executable_path).is_file()
+            && (self.install_path.is_empty() || Path::new(&self.install_path).is_dir())
+    }
+}
+
+/// A shell command run around a game launch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameHookCommand {
+    pub command: String,
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// Seconds to wait for the command (default 10, at most 300) - it's left running past this, not killed
+    #[serde(default)]
+    pub timeout_seconds: Option<u32>,
+    /// Pre-launch only: don't launch the game if the command fails
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Kinds of game art the library can show
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtworkKind {
+    /// The detector's icon (`icon_path`)
+    #[default]
+    Icon,
+    /// Portrait cover (600x900)
+    Grid,
+    /// Wide banner
+    Hero,
+    /// Transparent title logo
+    Logo,
+}
+
+/// Cached artwork file paths of a library game
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GameArtwork {
+    pub grid: Option<String>,
+    pub hero: Option<String>,
+    pub logo: Option<String>,
+}
+
+impl GameArtwork {
+    pub fn get(&self, kind: ArtworkKind) -> Option<&String> {
+        match kind {
+            ArtworkKind::Icon => None,
+            ArtworkKind::Grid => self.grid.as_ref(),
+            ArtworkKind::Hero => self.hero.as_ref(),
+            ArtworkKind::Logo => self.logo.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, kind: ArtworkKind, path: Option<String>) {
+        match kind {
+            ArtworkKind::Icon => {}
+            ArtworkKind::Grid => self.grid = path,
+            ArtworkKind::Hero => self.hero = path,
+            ArtworkKind::Logo => self.logo = path,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.grid.is_none() && self.hero.is_none() && self.logo.is_none()
+    }
+}
+
+impl LibraryGame {
+    /// Detector arguments followed by the user's own
+    pub fn combined_launch_args(&self) -> Option<String> {
+        match (self.launch_args.as_deref(), self.custom_launch_args.as_deref()) {
+            (Some(base), Some(custom)) => Some(format!("{} {}", base, custom)),
+            (base, custom) => base.or(custom).map(|a| a.to_string()),
+        }
+    }
+}
+
+/// A library game whose process is running
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningGame {
+    pub game_id: String,
+    pub pid: u32,
+    pub process_name: String,
+    /// Unix seconds
+    pub started_at: u64,
+}
+
+/// Accumulated playtime of a library game
+#[derive(Debug, Clone, Serialize)]
+pub struct GamePlaytime {
+    pub game_id: String,
+    pub name: String,
+    pub total_seconds: u64,
+    pub session_count: u32,
+    pub last_played: Option<String>,
+    pub average_session_seconds: u64,
+}
+
+impl From<&LibraryGame> for GamePlaytime {
+    fn from(game: &LibraryGame) -> Self {
+        Self {
+            game_id: game.id.clone(),
+            name: game.name.clone(),
+            total_seconds: game.total_playtime_seconds,
+            session_count: game.session_count,
+            last_played: game.last_played.clone(),
+            average_session_seconds: game.total_playtime_seconds.checked_div(game.session_count as u64).unwrap_or(0),
+        }
+    }
+}
+
+/// Sort orders for `get_game_library`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LibrarySortBy {
+    Name,
+    /// Most recently played first
+    LastPlayed,
+    /// Most played first
+    Playtime,
+    #[default]
+    Custom,
+}
+
+/// Built-in collection of games added in the last RECENTLY_ADDED_DAYS days, computed from `added_at`
+pub const RECENTLY_ADDED_COLLECTION_ID: &str = "recently_added";
+pub const RECENTLY_ADDED_DAYS: i64 = 14;
+
+/// A user-defined group of library games
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameCollection {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
 }
 
 /// The complete game library
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GameLibrary {
     pub games: Vec<LibraryGame>,
+    #[serde(default)]
+    pub collections: Vec<GameCollection>,
 }
 
 impl GameLibrary {
     pub fn new() -> Self {
-        Self { games: vec![] }
+        Self { games: vec![], collections: vec![] }
     }
 
     pub fn find_by_id(&self, id: &str) -> Option<&LibraryGame> {
@@ -81,10 +280,124 @@ impl GameLibrary {
         )
     }
 
-    pub fn add_game(&mut self, game: LibraryGame) {
+    /// Once the user has a custom order, new games go to the end of it
+    pub fn add_game(&mut self, mut game: LibraryGame) {
+        if game.sort_index.is_none() {
+            game.sort_index = self.games.iter().filter_map(|g| g.sort_index).max().map(|max| max + 1);
+        }
         self.games.push(game);
     }
 
+    /// Sort favorites first, then by `sort_by` (stable, so ties keep library order)
+    pub fn sort(&mut self, sort_by: LibrarySortBy) {
+        self.games.sort_by(|a, b| {
+            b.favorite.cmp(&a.favorite).then_with(|| match sort_by {
+                LibrarySortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                // RFC 3339 timestamps compare chronologically as strings
+                LibrarySortBy::LastPlayed => b.last_played.cmp(&a.last_played),
+                LibrarySortBy::Playtime => b.total_playtime_seconds.cmp(&a.total_playtime_seconds),
+                LibrarySortBy::Custom => a.sort_index.unwrap_or(u32::MAX).cmp(&b.sort_index.unwrap_or(u32::MAX)),
+            })
+        });
+    }
+
+    /// Give `game_ids` custom positions in the order listed; unlisted games keep their relative order after them
+    pub fn reorder(&mut self, game_ids: &[String]) -> Result<(), String> {
+        if let Some(missing) = game_ids.iter().find(|id| self.find_by_id(id).is_none()) {
+            return Err(format!("Game not found: {}", missing));
+        }
+
+        self.sort(LibrarySortBy::Custom);
+        let mut ordered: Vec<LibraryGame> = Vec::with_capacity(self.games.len());
+        for id in game_ids {
+            if let Some(pos) = self.games.iter().position(|g| &g.id == id) {
+                ordered.push(self.games.remove(pos));
+            }
+        }
+        ordered.append(&mut self.games);
+
+        for (index, game) in ordered.iter_mut().enumerate() {
+            game.sort_index = Some(index as u32);
+        }
+        self.games = ordered;
+        Ok(())
+    }
+
+    fn validate_collection_name(&self, name: &str, except_id: Option<&str>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Collection name is required".to_string());
+        }
+        let taken = self
+            .collections
+            .iter()
+            .any(|c| Some(c.id.as_str()) != except_id && c.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(format!("A collection named \"{}\" already exists", name));
+        }
+        Ok(name.to_string())
+    }
+
+    pub fn create_collection(&mut self, name: &str) -> Result<GameCollection, String> {
+        let collection = GameCollection {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.validate_collection_name(name, None)?,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+        self.collections.push(collection.clone());
+        Ok(collection)
+    }
+
+    pub fn rename_collection(&mut self, id: &str, name: &str) -> Result<(), String> {
+        let name = self.validate_collection_name(name, Some(id))?;
+        self.collections
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| "Collection not found".to_string())?
+            .name = name;
+        Ok(())
+    }
+
+    /// Remove a collection; its games stay in the library
+    pub fn delete_collection(&mut self, id: &str) -> bool {
+        let initial_len = self.collections.len();
+        self.collections.retain(|c| c.id != id);
+        for game in &mut self.games {
+            game.collection_ids.retain(|c| c != id);
+        }
+        self.collections.len() < initial_len
+    }
+
+    pub fn set_game_collections(&mut self, game_id: &str, collection_ids: Vec<String>) -> Result<&LibraryGame, String> {
+        if let Some(unknown) = collection_ids.iter().find(|id| !self.collections.iter().any(|c| &c.id == *id)) {
+            return Err(format!("Collection not found: {}", unknown));
+        }
+
+        let mut collection_ids = collection_ids;
+        collection_ids.sort();
+        collection_ids.dedup();
+        let game = self.find_by_id_mut(game_id).ok_or_else(|| "Game not found".to_string())?;
+        game.collection_ids = collection_ids;
+        Ok(game)
+    }
+
+    /// Keep only the games in a collection (user-defined or RECENTLY_ADDED_COLLECTION_ID)
+    pub fn retain_collection(&mut self, collection_id: &str) -> Result<(), String> {
+        if collection_id == RECENTLY_ADDED_COLLECTION_ID {
+            let cutoff = chrono::Utc::now() - chrono::Duration::days(RECENTLY_ADDED_DAYS);
+            self.games.retain(|g| {
+                chrono::DateTime::parse_from_rfc3339(&g.added_at).is_ok_and(|added| added >= cutoff)
+            });
+            return Ok(());
+        }
+
+        if !self.collections.iter().any(|c| c.id == collection_id) {
+            return Err("Collection not found".to_string());
+        }
+        self.games.retain(|g| g.collection_ids.iter().any(|c| c == collection_id));
+        Ok(())
+    }
+
     pub fn remove_game(&mut self, id: &str) -> bool {
         let initial_len = self.games.len();
         self.games.retain(|g| g.id != id);
@@ -92,6 +405,30 @@ impl GameLibrary {
     }
 }
 
+/// One `launch_game` call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LaunchRecord {
+    pub launched_at: String,
+    /// Set by the playtime tracker when the game's process exits
+    #[serde(default)]
+    pub exited_at: Option<String>,
+}
+
+/// Launch history per library game id, newest last
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LaunchHistory {
+    pub games: HashMap<String, Vec<LaunchRecord>>,
+}
+
+/// A library game with its most recent launch, for `get_recently_played`
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentlyPlayedGame {
+    #[serde(flatten)]
+    pub game: LibraryGame,
+    pub last_launched_at: String,
+    pub last_exited_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameScanCache {
     pub timestamp: i64,
@@ -114,6 +451,57 @@ impl GameScanCache {
     }
 }
 
+/// A detected game the user doesn't want offered again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgnoredGame {
+    /// The detected game's app_id, or its executable path when it has none
+    pub key: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub ignored_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IgnoredGames {
+    pub games: Vec<IgnoredGame>,
+}
+
+impl DetectedGame {
+    /// Key used by the ignore list - app_id for launcher games, executable path for the rest
+    pub fn ignore_key(&self) -> &str {
+        self.app_id.as_deref().unwrap_or(&self.executable_path)
+    }
+}
+
+impl IgnoredGames {
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.games.iter().any(|g| g.key.eq_ignore_ascii_case(key))
+    }
+
+    pub fn is_ignored(&self, game: &DetectedGame) -> bool {
+        self.contains_key(game.ignore_key())
+    }
+
+    /// Returns false if the key was already ignored
+    pub fn add(&mut self, key: &str, name: Option<String>) -> bool {
+        if self.contains_key(key) {
+            return false;
+        }
+        self.games.push(IgnoredGame {
+            key: key.to_string(),
+            name,
+            ignored_at: chrono::Utc::now().to_rfc3339(),
+        });
+        true
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        let initial_len = self.games.len();
+        self.games.retain(|g| !g.key.eq_ignore_ascii_case(key));
+        self.games.len() < initial_len
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HoyoPlayGameConfig {
     pub name: &'static str,
@@ -11,6 +11,16 @@ pub enum MLJobStatus {
     Cancelled,
 }
 
+/// Queued jobs start highest priority first, then in submission order
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum MLJobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputFile {
     pub stem: String,
@@ -24,28 +34,49 @@ pub struct MLJob {
     pub model: String,
     pub output_dir: Option<String>,
     pub status: MLJobStatus,
+    #[serde(default)]
+    pub priority: MLJobPriority,
     pub progress: u8, // 0-100
     pub stage: Option<String>,
     pub output_files: Option<Vec<OutputFile>>,
     pub error: Option<String>,
     pub created_at: String,
+    /// When the worker was started
+    #[serde(default)]
+    pub started_at: Option<String>,
     pub completed_at: Option<String>,
+    /// 1-based place in the queue while pending; filled in by `list_ml_jobs`
+    #[serde(default)]
+    pub queue_position: Option<u32>,
+    /// When a pending job is expected to start, from past run times; filled in by `list_ml_jobs`
+    #[serde(default)]
+    pub estimated_start: Option<String>,
 }
 
 impl MLJob {
-    pub fn new(id: String, input_file: String, model: String, output_dir: Option<String>) -> Self {
+    pub fn new(
+        id: String,
+        input_file: String,
+        model: String,
+        output_dir: Option<String>,
+        priority: MLJobPriority,
+    ) -> Self {
         Self {
             id,
             input_file,
             model,
             output_dir,
             status: MLJobStatus::Pending,
+            priority,
             progress: 0,
             stage: None,
             output_files: None,
             error: None,
             created_at: chrono::Utc::now().to_rfc3339(),
+            started_at: None,
             completed_at: None,
+            queue_position: None,
+            estimated_start: None,
         }
     }
 }
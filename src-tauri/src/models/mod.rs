@@ -1,6 +1,8 @@
 // Data models (structs)
 pub mod auth;
 pub mod audio_detection;
+pub mod data_directory;
+pub mod discord;
 pub mod download;
 pub mod friends;
 pub mod gacha;
@@ -11,10 +13,13 @@ pub mod performance;
 pub mod playlist_uploader;
 pub mod server;
 pub mod settings;
+pub mod updater;
 pub mod valorant;
 
 pub use auth::*;
 pub use audio_detection::*;
+pub use data_directory::*;
+pub use discord::*;
 pub use download::*;
 pub use friends::*;
 pub use gacha::*;
@@ -24,4 +29,5 @@ pub use ml_job::*;
 pub use playlist_uploader::*;
 pub use server::*;
 pub use settings::*;
+pub use updater::*;
 pub use valorant::*;
@@ -2,14 +2,35 @@
 use serde::{Deserialize, Serialize};
 
 /// Container for all system performance metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SystemMetrics {
     pub cpu: CpuMetrics,
+    /// Primary GPU (the user's pinned adapter, otherwise the busiest one)
     pub gpu: Option<GpuMetrics>,
+    /// Every detected GPU, ordered by `GpuMetrics::index`
+    #[serde(default)]
+    pub gpus: Vec<GpuMetrics>,
     pub ram: RamMetrics,
+    #[serde(default)]
+    pub network: NetworkMetrics,
+    #[serde(default)]
+    pub disks: Vec<DiskMetrics>,
+    #[serde(default)]
+    pub power: PowerStatus,
+    /// Heaviest processes by CPU and memory (only filled on request)
+    #[serde(default)]
+    pub top_processes: Option<TopProcesses>,
     pub timestamp: i64, // Unix timestamp in milliseconds
 }
 
+impl SystemMetrics {
+    /// Drop the optional per-interface/per-device breakdowns to keep event payloads small
+    pub fn without_details(mut self) -> Self {
+        self.network.interfaces = None;
+        self
+    }
+}
+
 /// CPU performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuMetrics {
@@ -19,19 +40,59 @@ pub struct CpuMetrics {
     pub per_core_usage: Vec<f32>,
     /// CPU temperature in Celsius (if available)
     pub temperature_celsius: Option<f32>,
+    /// Per-core temperatures in Celsius, indexed like `per_core_usage`
+    /// Falls back to the package temperature when there are no per-core sensors
+    #[serde(default)]
+    pub per_core_temperature: Vec<Option<f32>>,
+    /// Core kind (P/E) per logical core, indexed like `per_core_usage` - None on non-hybrid CPUs
+    #[serde(default)]
+    pub core_kinds: Option<Vec<CoreKind>>,
     /// Current CPU frequency in MHz (if available)
     pub frequency_mhz: Option<u64>,
+    /// Average frequency across all cores in MHz (None when it can't be read, e.g. some VMs)
+    #[serde(default)]
+    pub average_frequency_mhz: Option<u64>,
+    /// Highest core frequency in MHz
+    #[serde(default)]
+    pub max_frequency_mhz: Option<u64>,
     /// Number of CPU cores
     pub core_count: usize,
     /// CPU model name
     pub name: String,
 }
 
-/// GPU performance metrics (NVIDIA only for now)
+/// Core type on hybrid CPUs (Intel 12th gen+)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoreKind {
+    /// Performance core (P-core)
+    Performance,
+    /// Efficiency core (E-core)
+    Efficiency,
+}
+
+/// Backend that produced a set of GPU metrics
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuProviderKind {
+    /// NVIDIA Management Library
+    #[default]
+    Nvml,
+    /// Windows GPU performance counters (Intel iGPU / Arc)
+    WindowsCounters,
+}
+
+/// GPU performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuMetrics {
+    /// Adapter index within `SystemMetrics::gpus`
+    #[serde(default)]
+    pub index: usize,
     /// GPU model name
     pub name: String,
+    /// Stable adapter id (NVML UUID, or PCI vendor/device/subsystem ids) that a preferred GPU is saved by
+    #[serde(default)]
+    pub adapter_id: Option<String>,
     /// GPU utilization percentage (0-100)
     pub usage_percent: f32,
     /// VRAM used in megabytes
@@ -40,6 +101,24 @@ pub struct GpuMetrics {
     pub memory_total_mb: u64,
     /// GPU temperature in Celsius (if available)
     pub temperature_celsius: Option<f32>,
+    /// Board power draw in watts (if supported)
+    #[serde(default)]
+    pub power_draw_watts: Option<f32>,
+    /// Enforced power limit in watts (if supported)
+    #[serde(default)]
+    pub power_limit_watts: Option<f32>,
+    /// Average fan speed percentage (None for fanless or unsupported GPUs)
+    #[serde(default)]
+    pub fan_speed_percent: Option<f32>,
+    /// Current graphics (core) clock in MHz
+    #[serde(default)]
+    pub core_clock_mhz: Option<u32>,
+    /// Current memory clock in MHz
+    #[serde(default)]
+    pub memory_clock_mhz: Option<u32>,
+    /// Backend the metrics were read from
+    #[serde(default)]
+    pub provider: GpuProviderKind,
 }
 
 /// RAM/Memory performance metrics
@@ -53,6 +132,85 @@ pub struct RamMetrics {
     pub available_bytes: u64,
     /// Memory usage percentage (0-100)
     pub usage_percent: f32,
+    /// Total physical memory in megabytes
+    #[serde(default)]
+    pub total_mb: u64,
+    /// Used physical memory in megabytes
+    #[serde(default)]
+    pub used_mb: u64,
+    /// Available physical memory (free + reclaimable standby) in megabytes
+    #[serde(default)]
+    pub available_mb: u64,
+    /// Committed memory in megabytes
+    #[serde(default)]
+    pub committed_mb: Option<u64>,
+    /// Commit limit (physical memory + page file) in megabytes
+    #[serde(default)]
+    pub commit_limit_mb: Option<u64>,
+    /// Standby cache size in megabytes (reclaimable; Linux reports the page cache)
+    #[serde(default)]
+    pub standby_cache_mb: Option<u64>,
+    /// Page file / swap in use in megabytes
+    #[serde(default)]
+    pub pagefile_used_mb: Option<u64>,
+    /// Page file / swap size in megabytes
+    #[serde(default)]
+    pub pagefile_total_mb: Option<u64>,
+}
+
+impl RamMetrics {
+    /// Committed memory as a percentage of the commit limit
+    pub fn commit_percent(&self) -> Option<f32> {
+        match (self.committed_mb, self.commit_limit_mb) {
+            (Some(committed), Some(limit)) if limit > 0 => Some(committed as f32 / limit as f32 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Network throughput metrics
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NetworkMetrics {
+    /// Total download rate across all interfaces (bytes/sec)
+    pub download_bytes_per_sec: u64,
+    /// Total upload rate across all interfaces (bytes/sec)
+    pub upload_bytes_per_sec: u64,
+    /// Per-interface breakdown (only included when requested)
+    pub interfaces: Option<Vec<NetworkInterfaceMetrics>>,
+}
+
+/// Throughput of a single network interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInterfaceMetrics {
+    /// Interface name/alias (e.g. "Ethernet", "Wi-Fi")
+    pub name: String,
+    /// Download rate (bytes/sec)
+    pub download_bytes_per_sec: u64,
+    /// Upload rate (bytes/sec)
+    pub upload_bytes_per_sec: u64,
+}
+
+/// Capacity and activity of a single disk/volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskMetrics {
+    /// Device/volume name
+    pub name: String,
+    /// Mount point (drive letter on Windows, e.g. "C:\\")
+    pub mount_point: String,
+    /// File system (e.g. "NTFS")
+    pub file_system: String,
+    /// Total capacity in bytes
+    pub total_bytes: u64,
+    /// Free space available to the user in bytes
+    pub available_bytes: u64,
+    /// Space usage percentage (0-100)
+    pub usage_percent: f32,
+    /// Whether this is a removable drive (USB stick, SD card)
+    pub is_removable: bool,
+    /// Read throughput in bytes/sec (None when not monitored or unavailable)
+    pub read_bytes_per_sec: Option<u64>,
+    /// Write throughput in bytes/sec (None when not monitored or unavailable)
+    pub write_bytes_per_sec: Option<u64>,
 }
 
 impl Default for CpuMetrics {
@@ -61,7 +219,11 @@ impl Default for CpuMetrics {
             usage_percent: 0.0,
             per_core_usage: Vec::new(),
             temperature_celsius: None,
+            per_core_temperature: Vec::new(),
+            core_kinds: None,
             frequency_mhz: None,
+            average_frequency_mhz: None,
+            max_frequency_mhz: None,
             core_count: 0,
             name: String::new(),
         }
@@ -75,6 +237,14 @@ impl Default for RamMetrics {
             used_bytes: 0,
             available_bytes: 0,
             usage_percent: 0.0,
+            total_mb: 0,
+            used_mb: 0,
+            available_mb: 0,
+            committed_mb: None,
+            commit_limit_mb: None,
+            standby_cache_mb: None,
+            pagefile_used_mb: None,
+            pagefile_total_mb: None,
         }
     }
 }
@@ -85,7 +255,143 @@ impl Default for SystemMetrics {
             cpu: CpuMetrics::default(),
             gpu: None,
             ram: RamMetrics::default(),
+            network: NetworkMetrics::default(),
+            disks: Vec::new(),
             timestamp: 0,
         }
     }
 }
+
+/// Battery and power plan status
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PowerStatus {
+    /// True on AC power, false on battery (None if unknown)
+    pub on_ac_power: Option<bool>,
+    /// Whether the system has a battery at all
+    pub has_battery: bool,
+    /// Battery charge percentage (0-100)
+    pub battery_percent: Option<u8>,
+    /// Whether the battery is currently charging
+    pub is_charging: bool,
+    /// Estimated battery time remaining in seconds (only while discharging)
+    pub battery_seconds_remaining: Option<u32>,
+    /// Active Windows power plan name (e.g. "Balanced", "High performance")
+    pub power_plan: Option<String>,
+}
+
+/// Payload for the "performance:power_source_changed" event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSourceChangedEvent {
+    pub on_ac_power: bool,
+    pub battery_percent: Option<u8>,
+}
+
+/// Top resource-consuming processes
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TopProcesses {
+    /// Highest CPU usage first
+    pub by_cpu: Vec<ProcessUsage>,
+    /// Highest memory usage first
+    pub by_memory: Vec<ProcessUsage>,
+}
+
+/// Resource usage of a single process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    /// CPU usage normalized to total capacity (0-100)
+    pub cpu_percent: f32,
+    /// Resident memory in megabytes
+    pub memory_mb: f64,
+}
+
+/// Metric a performance alert watches
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertMetric {
+    CpuUsage,
+    CpuTemp,
+    GpuUsage,
+    GpuTemp,
+    VramUsage,
+    RamUsage,
+    RamAvailableMb,
+}
+
+/// How the metric is compared against the alert value
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertComparator {
+    Above,
+    Below,
+}
+
+/// User-configurable threshold alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceAlert {
+    pub id: String,
+    pub name: String,
+    pub metric: AlertMetric,
+    pub comparator: AlertComparator,
+    pub value: f32,
+    /// Minimum time between two firings of this alert
+    pub cooldown_seconds: u64,
+    pub enabled: bool,
+    /// Whether the UI should show a native notification (not just an in-app toast)
+    #[serde(default)]
+    pub notify: bool,
+}
+
+/// Payload for the "performance:alert" event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceAlertEvent {
+    pub alert_id: String,
+    pub name: String,
+    pub metric: AlertMetric,
+    pub comparator: AlertComparator,
+    pub threshold: f32,
+    /// Value that triggered the alert
+    pub value: f32,
+    pub notify: bool,
+    pub timestamp: i64,
+}
+
+/// First line of a performance capture file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureHeader {
+    pub format: String,
+    pub app_version: String,
+    /// Unix timestamp in milliseconds
+    pub started_at: i64,
+    pub cpu_model: String,
+    pub cpu_core_count: usize,
+    pub gpu_names: Vec<String>,
+    pub ram_total_mb: u64,
+}
+
+/// Why a performance capture ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureStopReason {
+    /// Stopped by the user
+    Manual,
+    /// Reached the configured maximum duration
+    DurationLimit,
+    /// Reached the configured maximum file size
+    SizeLimit,
+    /// Writing to the file failed
+    Error,
+    /// Performance monitoring, which feeds the capture, was stopped
+    MonitoringStopped,
+}
+
+/// Result of a finished capture (also the "performance:capture_stopped" event payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureSummary {
+    pub path: String,
+    pub samples_written: u64,
+    pub bytes_written: u64,
+    pub duration_seconds: f64,
+    pub stop_reason: CaptureStopReason,
+}
@@ -1,4 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How Atlas authenticates to the server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SSHAuthMethod {
+    #[default]
+    Password,
+    /// Private key file chosen by the user (`key_path`)
+    KeyFile,
+    /// Keypair created by `generate_ssh_keypair`, kept in the app data directory
+    GeneratedKey,
+}
 
 /// Server connection configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,6 +20,28 @@ pub struct ServerConfig {
     pub port: u16,
     pub username: String,
     pub domain: Option<String>,
+    #[serde(default)]
+    pub auth_method: SSHAuthMethod,
+    /// Private key used with `SSHAuthMethod::KeyFile`
+    #[serde(default)]
+    pub key_path: Option<String>,
+    /// Seconds between background metrics samples; None leaves the sampler off
+    #[serde(default)]
+    pub metrics_interval_seconds: Option<u32>,
+    /// Seconds a pooled SSH session may sit unused before it is closed; None uses the default
+    #[serde(default)]
+    pub ssh_idle_timeout_seconds: Option<u32>,
+    /// Keep the pooled SSH session open between metrics samples; off closes a session a sample
+    /// opened once it is done
+    #[serde(default)]
+    pub metrics_keep_session_open: bool,
+}
+
+impl ServerConfig {
+    /// Identifies the server in per-server data such as the metrics history
+    pub fn server_key(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
 }
 
 impl Default for ServerConfig {
@@ -16,6 +51,11 @@ impl Default for ServerConfig {
             port: 22,
             username: String::new(),
             domain: None,
+            auth_method: SSHAuthMethod::Password,
+            key_path: None,
+            metrics_interval_seconds: None,
+            ssh_idle_timeout_seconds: None,
+            metrics_keep_session_open: false,
         }
     }
 }
@@ -23,8 +63,19 @@ impl Default for ServerConfig {
 /// SSH credentials stored locally
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SSHCredentials {
-    pub password: String,   // Stored password
-    pub saved_at: String,   // ISO timestamp
+    #[serde(default)]
+    pub password: Option<String>,       // Stored password, for password auth
+    #[serde(default)]
+    pub key_passphrase: Option<String>, // Passphrase of an encrypted private key
+    pub saved_at: String,               // ISO timestamp
+}
+
+/// Public half of the generated keypair, for the server's authorized_keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SSHPublicKey {
+    pub public_key: String,
+    pub key_path: String,
+    pub fingerprint: String,
 }
 
 /// Status of an SSH command execution
@@ -48,15 +99,65 @@ pub struct CommandResult {
     pub completed_at: Option<String>,
 }
 
+/// What `systemd_service_action` can do to a service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemdServiceAction {
+    Start,
+    Stop,
+    Restart,
+    Enable,
+    Disable,
+}
+
+impl SystemdServiceAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SystemdServiceAction::Start => "start",
+            SystemdServiceAction::Stop => "stop",
+            SystemdServiceAction::Restart => "restart",
+            SystemdServiceAction::Enable => "enable",
+            SystemdServiceAction::Disable => "disable",
+        }
+    }
+}
+
+/// A systemd service action run by a quick action instead of a shell command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceActionTemplate {
+    pub service: String,
+    pub action: SystemdServiceAction,
+}
+
+/// A `{name}` placeholder in a quick action command, asked for when the action runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickActionVariable {
+    pub name: String,
+    /// Shown instead of the name when asking for the value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
 /// Quick action definition for the UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuickAction {
     pub id: String,
     pub label: String,
-    pub command: String,
+    #[serde(default)]
+    pub command: String,        // Empty when `service_action` is set
     pub category: String,       // "login", "status", "service", "logs"
     pub icon: String,           // Icon name for UI
     pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_action: Option<ServiceActionTemplate>,
+    /// Placeholders in `command`; ones not listed in the config are added when actions are loaded
+    #[serde(default)]
+    pub variables: Vec<QuickActionVariable>,
+    /// Ask before running, for destructive actions
+    #[serde(default)]
+    pub requires_confirmation: bool,
 }
 
 /// Quick actions configuration
@@ -76,6 +177,9 @@ impl Default for QuickActionsConfig {
                     category: "login".to_string(),
                     icon: "Terminal".to_string(),
                     description: "Open SSH session".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "uptime".to_string(),
@@ -84,6 +188,9 @@ impl Default for QuickActionsConfig {
                     category: "status".to_string(),
                     icon: "Clock".to_string(),
                     description: "Show server uptime".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "disk_usage".to_string(),
@@ -92,6 +199,9 @@ impl Default for QuickActionsConfig {
                     category: "status".to_string(),
                     icon: "HardDrive".to_string(),
                     description: "Show disk usage".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "memory".to_string(),
@@ -100,6 +210,9 @@ impl Default for QuickActionsConfig {
                     category: "status".to_string(),
                     icon: "Cpu".to_string(),
                     description: "Show memory usage".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "top_processes".to_string(),
@@ -108,6 +221,9 @@ impl Default for QuickActionsConfig {
                     category: "status".to_string(),
                     icon: "Activity".to_string(),
                     description: "Show top processes by memory".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "nginx_status".to_string(),
@@ -116,14 +232,23 @@ impl Default for QuickActionsConfig {
                     category: "service".to_string(),
                     icon: "Server".to_string(),
                     description: "Check Nginx service status".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "nginx_restart".to_string(),
                     label: "Restart Nginx".to_string(),
-                    command: "sudo systemctl restart nginx".to_string(),
+                    command: String::new(),
                     category: "service".to_string(),
                     icon: "RotateCcw".to_string(),
                     description: "Restart Nginx service".to_string(),
+                    service_action: Some(ServiceActionTemplate {
+                        service: "nginx".to_string(),
+                        action: SystemdServiceAction::Restart,
+                    }),
+                    variables: Vec::new(),
+                    requires_confirmation: true,
                 },
                 QuickAction {
                     id: "docker_ps".to_string(),
@@ -132,6 +257,24 @@ impl Default for QuickActionsConfig {
                     category: "service".to_string(),
                     icon: "Box".to_string(),
                     description: "List Docker containers".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
+                },
+                QuickAction {
+                    id: "kill_process".to_string(),
+                    label: "Kill Process".to_string(),
+                    command: "pkill -x -- {name}".to_string(),
+                    category: "service".to_string(),
+                    icon: "XCircle".to_string(),
+                    description: "Kill every process with the given name".to_string(),
+                    service_action: None,
+                    variables: vec![QuickActionVariable {
+                        name: "name".to_string(),
+                        label: Some("Process name".to_string()),
+                        default: None,
+                    }],
+                    requires_confirmation: true,
                 },
                 QuickAction {
                     id: "nginx_logs".to_string(),
@@ -140,6 +283,9 @@ impl Default for QuickActionsConfig {
                     category: "logs".to_string(),
                     icon: "FileText".to_string(),
                     description: "Show last 50 Nginx access log lines".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "nginx_error_logs".to_string(),
@@ -148,6 +294,9 @@ impl Default for QuickActionsConfig {
                     category: "logs".to_string(),
                     icon: "AlertTriangle".to_string(),
                     description: "Show last 50 Nginx error log lines".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
                 QuickAction {
                     id: "system_logs".to_string(),
@@ -156,12 +305,31 @@ impl Default for QuickActionsConfig {
                     category: "logs".to_string(),
                     icon: "ScrollText".to_string(),
                     description: "Show last 50 system journal entries".to_string(),
+                    service_action: None,
+                    variables: Vec::new(),
+                    requires_confirmation: false,
                 },
             ],
         }
     }
 }
 
+/// A systemd service on the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemdService {
+    /// Unit name, e.g. "nginx.service"
+    pub name: String,
+    pub description: String,
+    /// "loaded", "not-found", ...
+    pub load_state: String,
+    /// "active", "inactive", "failed", ...
+    pub active_state: String,
+    /// "running", "exited", "dead", ...
+    pub sub_state: String,
+    /// Current memory use; None when systemd doesn't account it
+    pub memory_bytes: Option<u64>,
+}
+
 /// System status information parsed from server
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemStatus {
@@ -174,6 +342,55 @@ pub struct SystemStatus {
     pub cpu_usage: String,
 }
 
+/// One background reading of the server's resource use
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerMetricsSample {
+    /// Unix seconds
+    pub timestamp: i64,
+    pub cpu_percent: f32,
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+    pub disk_used_bytes: u64,
+    pub disk_total_bytes: u64,
+    /// 1-minute load average
+    pub load_average: f32,
+}
+
+/// Span of metrics history to return
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum MetricsRange {
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "6h")]
+    SixHours,
+    #[serde(rename = "24h")]
+    #[default]
+    Day,
+}
+
+impl MetricsRange {
+    pub fn seconds(self) -> i64 {
+        match self {
+            MetricsRange::Hour => 3600,
+            MetricsRange::SixHours => 6 * 3600,
+            MetricsRange::Day => 24 * 3600,
+        }
+    }
+}
+
+/// Metrics history of one server, averaged down to a chartable number of points
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerMetricsHistory {
+    /// "host:port"
+    pub server: String,
+    pub range: MetricsRange,
+    /// Sampler interval, None while the sampler is off
+    pub interval_seconds: Option<u32>,
+    /// Span each returned point averages; 0 when the samples are returned as recorded
+    pub bucket_seconds: i64,
+    pub samples: Vec<ServerMetricsSample>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)] 
 pub struct SSHOutputEvent {
@@ -190,3 +407,148 @@ pub struct SSHCompleteEvent {
     pub exit_code: i32,
     pub error: Option<String>,
 }
+
+/// Entry of a remote directory listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SftpEntry {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    /// Last modification time (RFC 3339)
+    pub modified: Option<String>,
+    /// `ls -l` style, e.g. "rwxr-xr-x"
+    pub permissions: String,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+}
+
+/// A remote directory and its entries, directories first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SftpListing {
+    /// Absolute path of the listed directory
+    pub path: String,
+    pub entries: Vec<SftpEntry>,
+}
+
+/// A finished SFTP download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SftpTransferResult {
+    pub transfer_id: String,
+    pub remote_path: String,
+    pub local_path: String,
+    pub size: u64,
+    /// Bytes kept from an earlier partial download; 0 when it started from the beginning
+    #[serde(default)]
+    pub resumed_from: u64,
+}
+
+/// Why an SFTP command failed; serialized as `{ kind, ... }` for the UI
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SftpError {
+    #[error("Path is not allowed: {path} ({message})")]
+    InvalidPath { path: String, message: String },
+    #[error("Path may not contain '..': {path}")]
+    PathTraversal { path: String },
+    #[error("Permission denied: {path}")]
+    PermissionDenied { path: String },
+    #[error("No such file or directory: {path}")]
+    NotFound { path: String },
+    #[error("Already exists: {path}")]
+    AlreadyExists { path: String },
+    #[error("Directory is not empty: {path}")]
+    NotEmpty { path: String },
+    #[error("Not enough space for {path}: {required} bytes needed, {available} available")]
+    InsufficientSpace { path: String, required: u64, available: u64 },
+    #[error("Transfer cancelled")]
+    Cancelled,
+    #[error("{message}")]
+    Failed { message: String },
+}
+
+/// A local port forwarded through the SSH session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshTunnel {
+    pub id: String,
+    pub local_port: u16,
+    /// Host the server connects to, usually "localhost" for a service on the server itself
+    pub remote_host: String,
+    pub remote_port: u16,
+    pub created_at: String,
+    /// "connected", "reconnecting" or "disconnected", from the session the tunnel runs on
+    pub status: String,
+    /// Bytes sent from the local side to the remote side
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// Connections accepted since the tunnel opened
+    pub connections: u64,
+    pub active_connections: u64,
+}
+
+/// State of the pooled SSH session to a server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SshConnectionStatus {
+    Connected,
+    Connecting,
+    /// The session dropped and is being re-established
+    Reconnecting,
+    Disconnected,
+}
+
+/// The pooled SSH session to a server profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshConnectionState {
+    /// The server's "host:port"
+    pub profile: String,
+    pub state: SshConnectionStatus,
+    #[serde(default)]
+    pub connected_since: Option<String>,
+    /// Commands and transfers currently using the session
+    #[serde(default)]
+    pub active_requests: u32,
+    /// Seconds since the session was last used
+    #[serde(default)]
+    pub idle_seconds: u64,
+    #[serde(default)]
+    pub idle_timeout_seconds: u32,
+}
+
+/// Host key trusted for a server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownHost {
+    /// e.g. "ssh-ed25519"
+    pub key_type: String,
+    /// SHA256 fingerprint as printed by `ssh-keygen -l`
+    pub fingerprint: String,
+    pub trusted_at: String,
+}
+
+/// Trusted host keys by server "host:port"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KnownHostsConfig {
+    #[serde(default)]
+    pub hosts: HashMap<String, KnownHost>,
+}
+
+/// Whether the key a server presented is the trusted one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostKeyTrust {
+    Trusted,
+    /// No key is trusted for the server yet
+    Unknown,
+    /// The server presented a different key than the trusted one
+    Changed,
+}
+
+/// Outcome of `test_ssh_connection`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshConnectionTest {
+    pub success: bool,
+    /// Host key the server presented; None when the connection failed before it was seen
+    pub key_type: Option<String>,
+    pub fingerprint: Option<String>,
+    pub trust: Option<HostKeyTrust>,
+    pub error: Option<String>,
+}
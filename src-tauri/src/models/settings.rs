@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use super::discord::PresenceButton;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ValorantCredentials {
@@ -8,12 +9,22 @@ pub struct ValorantCredentials {
     pub puuid: Option<String>,
 }
 
+/// Current settings.json schema; older files are upgraded by `file_manager::settings_migrations`
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
+    /// Missing in files from before versioning, which count as version 0
+    #[serde(default)]
+    pub schema_version: u32,
     pub download_path: String,
     pub default_quality: String,
     pub max_concurrent_downloads: u32,
     pub max_concurrent_ml_jobs: u32,
+    /// Start the ML job queue when Atlas launches; off leaves queued and interrupted jobs waiting
+    /// until the queue next runs (a job is submitted or ends)
+    #[serde(default)]
+    pub resume_ml_jobs_on_startup: bool,
     pub valorant_credentials: Option<ValorantCredentials>,
     #[serde(default)]
     pub atlas_project_path: Option<String>,
@@ -29,6 +40,30 @@ pub struct Settings {
     pub hidden_sidebar_items: Option<Vec<String>>,
     #[serde(default)]
     pub discord_rich_presence_enabled: bool,
+    /// Template of the presence details line while gaming; see `discord::template` for placeholders
+    #[serde(default = "default_discord_presence_details_template")]
+    pub discord_presence_details_template: String,
+    /// Template of the presence state line while gaming
+    #[serde(default = "default_discord_presence_state_template")]
+    pub discord_presence_state_template: String,
+    /// Link buttons under the presence, at most two
+    #[serde(default)]
+    pub discord_presence_buttons: Vec<PresenceButton>,
+    /// Show "AFK in {game}" once the player has been idle during a session
+    #[serde(default = "default_discord_presence_afk_enabled")]
+    pub discord_presence_afk_enabled: bool,
+    /// Minutes without input and with the system nearly unloaded before the player counts as idle
+    #[serde(default = "default_discord_presence_afk_minutes")]
+    pub discord_presence_afk_minutes: u32,
+    /// Also set the friends presence to Away while idle
+    #[serde(default)]
+    pub friends_presence_afk_away: bool,
+    /// Games (display or process names) kept out of the Discord and friends presence
+    #[serde(default)]
+    pub presence_blocklist: Vec<String>,
+    /// What Discord shows while a hidden game runs: "generic" ("Playing a game") or "idle"
+    #[serde(default = "default_presence_hidden_game_display")]
+    pub presence_hidden_game_display: String,
     #[serde(default)]
     pub run_on_startup: bool,
     #[serde(default)]
@@ -53,19 +88,148 @@ pub struct Settings {
     /// Y position of the floating partner widget
     #[serde(default)]
     pub partner_widget_position_y: Option<f64>,
+    /// How many minutes of performance history to keep in memory for graphs
+    #[serde(default = "default_performance_history_minutes")]
+    pub performance_history_minutes: u32,
+    /// Performance monitor poll interval in milliseconds (0 = automatic: faster while gaming, slower when idle)
+    #[serde(default = "default_performance_poll_interval_ms")]
+    pub performance_poll_interval_ms: u32,
+    /// Include removable drives (USB sticks, SD cards) in the performance monitor's disk metrics
+    #[serde(default)]
+    pub performance_include_removable_disks: bool,
+    /// GPU (by `GpuMetrics::adapter_id`, or adapter name) to report as the primary GPU - None picks the busiest adapter
+    #[serde(default)]
+    pub preferred_gpu: Option<String>,
+    /// Performance captures stop automatically after this many minutes
+    #[serde(default = "default_performance_capture_max_minutes")]
+    pub performance_capture_max_minutes: u32,
+    /// Performance captures stop automatically once the file reaches this size (MB)
+    #[serde(default = "default_performance_capture_max_mb")]
+    pub performance_capture_max_mb: u32,
+    /// Seconds to wait after a gaming session ends before auto-restoring killed processes
+    #[serde(default = "default_auto_restore_delay_seconds")]
+    pub auto_restore_delay_seconds: u32,
+    /// Seconds to wait for a process to close after WM_CLOSE before force-killing it
+    #[serde(default = "default_graceful_close_timeout_seconds")]
+    pub graceful_close_timeout_seconds: u32,
+    /// Run the default gaming profile automatically when a whitelisted game starts
+    #[serde(default)]
+    pub auto_execute_default_profile: bool,
+    /// Memory growth rate (MB/hour) at which a process is flagged as a possible leak
+    #[serde(default = "default_leak_growth_mb_per_hour")]
+    pub leak_growth_mb_per_hour: f64,
+    /// How long the growth must be sustained before flagging (capped by the 30 minutes of history kept)
+    #[serde(default = "default_leak_min_duration_minutes")]
+    pub leak_min_duration_minutes: u32,
+    /// Emit `task_monitor:leak_suspect` when a new process is flagged
+    #[serde(default = "default_leak_suspect_events")]
+    pub leak_suspect_events: bool,
+    /// Include games installed through the itch.io app when scanning for games
+    #[serde(default)]
+    pub itch_detection_enabled: bool,
+    /// SteamGridDB API key used to fetch game artwork (None = keep detector icons only)
+    #[serde(default)]
+    pub steamgriddb_api_key: Option<String>,
+    /// Launch Steam games through steam://rungameid (overlay, cloud saves) unless the game overrides it
+    #[serde(default)]
+    pub steam_launch_via_protocol: bool,
+    /// Check the Valorant store when it resets and show a notification with the new offers
+    #[serde(default)]
+    pub valorant_store_notifications: bool,
+    /// Apply the "Gaming" settings profile while a gaming session runs and restore the previous values after
+    #[serde(default)]
+    pub settings_profile_auto_switch: bool,
+    /// Release channel the updater checks: "stable" or "beta"
+    #[serde(default = "default_update_channel")]
+    pub update_channel: String,
+    /// Download updates in the background when found; they are still only installed when asked to
+    #[serde(default)]
+    pub auto_download_updates: bool,
+    /// Hours between background update checks
+    #[serde(default = "default_update_check_interval_hours")]
+    pub update_check_interval_hours: u32,
+    /// Fields this version doesn't know (e.g. written by a newer Atlas), kept so saving doesn't drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 fn default_partner_widget_enabled() -> bool {
     true
 }
 
+fn default_discord_presence_details_template() -> String {
+    String::from("Playing {game}")
+}
+
+fn default_discord_presence_state_template() -> String {
+    String::from("{bottleneck}")
+}
+
+fn default_discord_presence_afk_enabled() -> bool {
+    true
+}
+
+fn default_discord_presence_afk_minutes() -> u32 {
+    10
+}
+
+fn default_presence_hidden_game_display() -> String {
+    String::from("generic")
+}
+
+fn default_performance_history_minutes() -> u32 {
+    15
+}
+
+fn default_performance_poll_interval_ms() -> u32 {
+    1000
+}
+
+fn default_performance_capture_max_minutes() -> u32 {
+    120
+}
+
+fn default_performance_capture_max_mb() -> u32 {
+    200
+}
+
+fn default_auto_restore_delay_seconds() -> u32 {
+    3
+}
+
+fn default_graceful_close_timeout_seconds() -> u32 {
+    5
+}
+
+fn default_leak_growth_mb_per_hour() -> f64 {
+    500.0
+}
+
+fn default_leak_min_duration_minutes() -> u32 {
+    20
+}
+
+fn default_leak_suspect_events() -> bool {
+    true
+}
+
+fn default_update_channel() -> String {
+    String::from("stable")
+}
+
+fn default_update_check_interval_hours() -> u32 {
+    6
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             download_path: String::from("Downloads"),
             default_quality: String::from("best"),
             max_concurrent_downloads: 3,
             max_concurrent_ml_jobs: 1,
+            resume_ml_jobs_on_startup: false,
             valorant_credentials: None,
             atlas_project_path: None,
             remote_update_path: None,
@@ -74,6 +238,14 @@ impl Default for Settings {
             sidebar_order: None,
             hidden_sidebar_items: None,
             discord_rich_presence_enabled: false,
+            discord_presence_details_template: default_discord_presence_details_template(),
+            discord_presence_state_template: default_discord_presence_state_template(),
+            discord_presence_buttons: Vec::new(),
+            discord_presence_afk_enabled: default_discord_presence_afk_enabled(),
+            discord_presence_afk_minutes: default_discord_presence_afk_minutes(),
+            friends_presence_afk_away: false,
+            presence_blocklist: Vec::new(),
+            presence_hidden_game_display: default_presence_hidden_game_display(),
             run_on_startup: false,
             close_to_tray: false,
             auto_restore_enabled: false,
@@ -83,6 +255,151 @@ impl Default for Settings {
             partner_widget_enabled: true,
             partner_widget_position_x: None,
             partner_widget_position_y: None,
+            performance_history_minutes: default_performance_history_minutes(),
+            performance_poll_interval_ms: default_performance_poll_interval_ms(),
+            performance_include_removable_disks: false,
+            preferred_gpu: None,
+            performance_capture_max_minutes: default_performance_capture_max_minutes(),
+            performance_capture_max_mb: default_performance_capture_max_mb(),
+            auto_restore_delay_seconds: default_auto_restore_delay_seconds(),
+            graceful_close_timeout_seconds: default_graceful_close_timeout_seconds(),
+            auto_execute_default_profile: false,
+            leak_growth_mb_per_hour: default_leak_growth_mb_per_hour(),
+            leak_min_duration_minutes: default_leak_min_duration_minutes(),
+            leak_suspect_events: default_leak_suspect_events(),
+            itch_detection_enabled: false,
+            steamgriddb_api_key: None,
+            steam_launch_via_protocol: false,
+            valorant_store_notifications: false,
+            settings_profile_auto_switch: false,
+            update_channel: default_update_channel(),
+            auto_download_updates: false,
+            update_check_interval_hours: default_update_check_interval_hours(),
+            extra: serde_json::Map::new(),
         }
     }
 }
+
+/// Current format of settings export files
+pub const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+/// Settings backup written by `export_settings`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsExport {
+    pub schema_version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub app_version: Option<String>,
+    /// Whether credentials were included
+    #[serde(default)]
+    pub includes_sensitive: bool,
+    /// Section name -> contents; kept as raw JSON so one bad section doesn't fail the whole import
+    pub sections: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedSettingsSection {
+    pub section: String,
+    pub reason: String,
+}
+
+/// A field of an imported settings section that was left out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedSettingsField {
+    pub field: String,
+    pub reason: String,
+}
+
+/// Result of `import_settings`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsImportReport {
+    pub applied: Vec<String>,
+    pub skipped: Vec<SkippedSettingsSection>,
+    /// Fields of an applied settings section that weren't imported
+    pub skipped_fields: Vec<SkippedSettingsField>,
+}
+
+/// One top-level settings field whose value changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingChange {
+    pub key: String,
+    /// New value (null when the field was removed)
+    pub value: serde_json::Value,
+}
+
+/// Payload of `settings:changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsChangedEvent {
+    pub changes: Vec<SettingChange>,
+}
+
+/// A settings field that failed validation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsFieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Result of saving a user avatar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAvatar {
+    pub path: String,
+    pub thumbnail_path: String,
+    pub width: u32,
+    pub height: u32,
+    /// Size of the stored file
+    pub bytes: u64,
+}
+
+/// Settings fields a profile captures; paths, credentials and per-machine state are left out
+pub const SETTINGS_PROFILE_FIELDS: &[&str] = &[
+    "default_quality",
+    "max_concurrent_downloads",
+    "max_concurrent_ml_jobs",
+    "resume_ml_jobs_on_startup",
+    "discord_rich_presence_enabled",
+    "discord_presence_details_template",
+    "discord_presence_state_template",
+    "discord_presence_buttons",
+    "discord_presence_afk_enabled",
+    "discord_presence_afk_minutes",
+    "friends_presence_afk_away",
+    "presence_blocklist",
+    "presence_hidden_game_display",
+    "close_to_tray",
+    "auto_restore_enabled",
+    "auto_restore_delay_seconds",
+    "graceful_close_timeout_seconds",
+    "auto_execute_default_profile",
+    "partner_widget_enabled",
+    "performance_history_minutes",
+    "performance_poll_interval_ms",
+    "performance_include_removable_disks",
+    "performance_capture_max_minutes",
+    "performance_capture_max_mb",
+    "leak_growth_mb_per_hour",
+    "leak_min_duration_minutes",
+    "leak_suspect_events",
+    "valorant_store_notifications",
+];
+
+/// Profile the auto-switch applies during gaming sessions
+pub const GAMING_SETTINGS_PROFILE: &str = "Gaming";
+
+/// A named set of values for `SETTINGS_PROFILE_FIELDS`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    pub values: serde_json::Map<String, serde_json::Value>,
+    pub saved_at: String,
+}
+
+/// settings_profiles.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsProfiles {
+    #[serde(default)]
+    pub profiles: Vec<SettingsProfile>,
+    /// Values the auto-switch replaced, restored when the gaming session ends
+    #[serde(default)]
+    pub auto_switch_restore: Option<serde_json::Map<String, serde_json::Value>>,
+}
@@ -0,0 +1,97 @@
+// Updater models
+use serde::{Deserialize, Serialize};
+
+/// Release notes of one version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub date: Option<String>,
+    /// Markdown
+    pub body: String,
+    #[serde(default)]
+    pub prerelease: bool,
+}
+
+/// update_changelog.json; entries newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangelogCache {
+    #[serde(default)]
+    pub fetched_at: Option<String>,
+    #[serde(default)]
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// Payload of `update:whats_new`, sent on the first run after an update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhatsNew {
+    pub version: String,
+    pub previous_version: String,
+    /// None when the notes couldn't be fetched
+    pub entry: Option<ChangelogEntry>,
+}
+
+/// Why `install_update` refused or failed; serialized as `{ kind, ... }` for the UI
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UpdateInstallError {
+    #[error("No downloaded update available. Please download first.")]
+    NotDownloaded,
+    #[error("Downloaded update is incomplete ({actual} bytes, expected {expected:?})")]
+    LengthMismatch { expected: Option<u64>, actual: u64 },
+    #[error("Downloaded update failed signature verification: {message}")]
+    SignatureInvalid { message: String },
+    #[error("Failed to install update: {message}")]
+    InstallFailed { message: String },
+}
+
+/// A release installed through the updater, enough to download and check it again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledRelease {
+    pub version: String,
+    pub download_url: String,
+    pub signature: String,
+}
+
+/// The version an update replaced, kept so the update can be undone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackSlot {
+    pub release: InstalledRelease,
+    /// Version installed over it
+    pub replaced_by: String,
+    pub saved_at: String,
+    /// First start of `replaced_by`, when the grace period began
+    #[serde(default)]
+    pub first_run_at: Option<String>,
+}
+
+/// update_rollback.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateRollbackState {
+    /// Release of the running version, if it was installed through the updater
+    #[serde(default)]
+    pub installed: Option<InstalledRelease>,
+    /// The single rollback slot
+    #[serde(default)]
+    pub previous: Option<RollbackSlot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackInfo {
+    pub available: bool,
+    pub version: Option<String>,
+    pub replaced_by: Option<String>,
+    pub saved_at: Option<String>,
+    /// When the slot is cleared; None until the new version has started
+    pub expires_at: Option<String>,
+}
+
+/// update_deferral.json: updates the user chose not to be prompted about
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateDeferral {
+    /// Version not to offer; a newer release clears it
+    #[serde(default)]
+    pub skipped_version: Option<String>,
+    /// No prompts for any version before this time
+    #[serde(default)]
+    pub snoozed_until: Option<String>,
+}
@@ -1,11 +1,112 @@
 use serde::{Deserialize, Serialize};
 
+fn parse_utc(rfc3339: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(rfc3339)
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValorantItem {
     pub name: String,
     pub price: u32,
     pub image_url: Option<String>,
     pub item_type: String,
+    /// Skin level UUID from the store API
+    #[serde(default)]
+    pub uuid: Option<String>,
+    /// Downloaded copy of the image, filled in from the content cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_image: Option<String>,
+}
+
+/// A discounted Night Market offer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NightMarketItem {
+    pub name: String,
+    pub image_url: Option<String>,
+    pub item_type: String,
+    #[serde(default)]
+    pub uuid: Option<String>,
+    pub original_price: u32,
+    pub discount_percent: u32,
+    pub final_price: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_image: Option<String>,
+}
+
+/// Night Market (BonusStore) offers, which outlive the daily rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValorantNightMarket {
+    /// RFC 3339 time the Night Market ends
+    pub expires_at: String,
+    pub items: Vec<NightMarketItem>,
+}
+
+impl ValorantNightMarket {
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_utc(&self.expires_at)
+    }
+
+    /// Unparseable expiry counts as expired
+    pub fn is_expired(&self) -> bool {
+        !self.expires_at().is_some_and(|expires_at| expires_at > chrono::Utc::now())
+    }
+}
+
+/// An item in a featured bundle (skins, buddies, sprays, cards, titles)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleItem {
+    pub name: String,
+    pub item_type: String,
+    pub uuid: String,
+    pub image_url: Option<String>,
+    #[serde(default = "default_bundle_item_amount")]
+    pub amount: u32,
+    /// Price when bought on its own
+    pub base_price: u32,
+    pub discounted_price: u32,
+}
+
+fn default_bundle_item_amount() -> u32 {
+    1
+}
+
+/// A featured store bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValorantBundle {
+    pub uuid: String,
+    pub name: String,
+    pub image_url: Option<String>,
+    pub items: Vec<BundleItem>,
+    /// Sum of the individual item prices
+    pub base_price: u32,
+    /// Price of the whole bundle
+    pub bundle_price: u32,
+    /// Remaining duration reported when the store was checked
+    pub duration_remaining_seconds: u64,
+    /// RFC 3339 time the bundle leaves the store
+    pub expires_at: String,
+}
+
+impl ValorantBundle {
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_utc(&self.expires_at)
+    }
+
+    /// Unparseable expiry counts as expired
+    pub fn is_expired(&self) -> bool {
+        !self.expires_at().is_some_and(|expires_at| expires_at > chrono::Utc::now())
+    }
+}
+
+/// A bundle as seen across the store history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleHistoryEntry {
+    pub bundle: ValorantBundle,
+    /// Store dates the bundle was first and last seen on
+    pub first_seen: String,
+    pub last_seen: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,9 +116,27 @@ pub struct ValorantStore {
     pub checked_at: String,
     #[serde(default)]
     pub is_real_data: Option<bool>,
+    /// RFC 3339 time the daily offers rotate, as reported by the store API
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    #[serde(default)]
+    pub night_market: Option<ValorantNightMarket>,
+    /// Featured bundle
+    #[serde(default)]
+    pub bundle: Option<ValorantBundle>,
+    /// Further bundles in weeks that feature more than one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub other_bundles: Vec<ValorantBundle>,
+    /// Wishlisted skins offered in this rotation
+    #[serde(default)]
+    pub wishlist_hits: Vec<ValorantWishlistHit>,
 }
 
 impl ValorantStore {
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_utc(self.expires_at.as_deref()?)
+    }
+
     #[allow(dead_code)] 
     pub fn new(items: Vec<ValorantItem>) -> Self {
         let now = chrono::Utc::now();
@@ -26,6 +145,95 @@ impl ValorantStore {
             items,
             checked_at: now.to_rfc3339(),
             is_real_data: None,
+            expires_at: None,
+            night_market: None,
+            bundle: None,
+            other_bundles: vec![],
+            wishlist_hits: vec![],
+        }
+    }
+}
+
+/// A skin the user wants to be told about, by UUID or by (part of) its name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValorantWishlistItem {
+    pub id: String,
+    /// Lowercase skin level UUID
+    pub skin_uuid: Option<String>,
+    pub name: Option<String>,
+    pub added_at: String,
+}
+
+impl ValorantWishlistItem {
+    /// UUID match when both sides have one, otherwise a case-insensitive name-contains match
+    pub fn matches(&self, item_name: &str, item_uuid: Option<&str>) -> bool {
+        if let (Some(wanted), Some(uuid)) = (&self.skin_uuid, item_uuid) {
+            if wanted.eq_ignore_ascii_case(uuid) {
+                return true;
+            }
         }
+        self.name
+            .as_ref()
+            .is_some_and(|name| item_name.to_lowercase().contains(&name.to_lowercase()))
     }
 }
+
+/// A wishlisted skin found in a store check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValorantWishlistHit {
+    pub wishlist_item_id: String,
+    pub name: String,
+    pub price: u32,
+    /// Offered in the Night Market rather than the daily rotation
+    #[serde(default)]
+    pub night_market: bool,
+}
+
+/// One daily rotation a skin was offered in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkinAppearance {
+    pub date: String,
+    pub price: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkinAppearanceStats {
+    /// Name the skin was last offered under (the query when it never was)
+    pub skin: String,
+    pub appearance_count: u32,
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    /// Average days between appearances; None with fewer than two
+    pub average_gap_days: Option<f64>,
+    /// Oldest first
+    pub appearances: Vec<SkinAppearance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreStreaks {
+    pub rotations_tracked: u32,
+    pub distinct_skins_seen: u32,
+    pub last_wishlist_hit: Option<String>,
+    pub days_since_wishlist_hit: Option<i64>,
+    pub most_frequent_skin: Option<String>,
+    pub most_frequent_skin_count: u32,
+    /// Longest run of rotations offering only skins never seen before
+    pub longest_fresh_streak: u32,
+}
+
+/// Catalog entry for a skin, skin level or chroma UUID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkinMetadata {
+    pub uuid: String,
+    /// Parent skin of a level or chroma (equal to `uuid` for the skin itself)
+    pub skin_uuid: String,
+    pub name: String,
+    /// Content tier dev name (Select, Deluxe, Premium, Exclusive, Ultra)
+    pub tier: Option<String>,
+    /// Tier highlight color as RRGGBBAA hex
+    pub tier_color: Option<String>,
+    pub image_url: Option<String>,
+    /// Downloaded copy of the image, when one has been cached
+    pub local_image: Option<String>,
+    pub video_url: Option<String>,
+}
@@ -0,0 +1,196 @@
+// Threshold alerts evaluated by the performance monitor each cycle
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::performance::{
+    AlertComparator, AlertMetric, PerformanceAlert, PerformanceAlertEvent, SystemMetrics,
+};
+use crate::utils::get_performance_alerts_json_path;
+use std::collections::HashMap;
+
+/// Load alerts from disk (defaults when the file is missing or unreadable)
+pub fn load_alerts() -> Vec<PerformanceAlert> {
+    read_json_file(&get_performance_alerts_json_path()).unwrap_or_else(|_| default_alerts())
+}
+
+/// Persist alerts to disk
+pub fn save_alerts(alerts: &[PerformanceAlert]) -> Result<(), String> {
+    write_json_file(&get_performance_alerts_json_path(), &alerts.to_vec())
+}
+
+/// Built-in alerts, all disabled until the user opts in
+pub fn default_alerts() -> Vec<PerformanceAlert> {
+    vec![
+        PerformanceAlert {
+            id: "cpu_temp_high".to_string(),
+            name: "CPU temperature high".to_string(),
+            metric: AlertMetric::CpuTemp,
+            comparator: AlertComparator::Above,
+            value: 90.0,
+            cooldown_seconds: 300,
+            enabled: false,
+            notify: true,
+        },
+        PerformanceAlert {
+            id: "gpu_temp_high".to_string(),
+            name: "GPU temperature high".to_string(),
+            metric: AlertMetric::GpuTemp,
+            comparator: AlertComparator::Above,
+            value: 90.0,
+            cooldown_seconds: 300,
+            enabled: false,
+            notify: true,
+        },
+        PerformanceAlert {
+            id: "ram_available_low".to_string(),
+            name: "Available memory low".to_string(),
+            metric: AlertMetric::RamAvailableMb,
+            comparator: AlertComparator::Below,
+            value: 1024.0,
+            cooldown_seconds: 600,
+            enabled: false,
+            notify: true,
+        },
+    ]
+}
+
+/// Read the value an alert watches from a snapshot (None when not available on this system)
+fn metric_value(metric: AlertMetric, metrics: &SystemMetrics) -> Option<f32> {
+    match metric {
+        AlertMetric::CpuUsage => Some(metrics.cpu.usage_percent),
+        AlertMetric::CpuTemp => metrics.cpu.temperature_celsius,
+        AlertMetric::GpuUsage => metrics.gpu.as_ref().map(|g| g.usage_percent),
+        AlertMetric::GpuTemp => metrics.gpu.as_ref().and_then(|g| g.temperature_celsius),
+        AlertMetric::VramUsage => metrics
+            .gpu
+            .as_ref()
+            .filter(|g| g.memory_total_mb > 0)
+            .map(|g| g.memory_used_mb as f32 / g.memory_total_mb as f32 * 100.0),
+        AlertMetric::RamUsage => Some(metrics.ram.usage_percent),
+        AlertMetric::RamAvailableMb => (metrics.ram.total_mb > 0).then_some(metrics.ram.available_mb as f32),
+    }
+}
+
+/// Human-readable line for a fired alert, e.g. "CPU temperature is 93.4°C (above 90°C)"
+pub fn describe_alert(event: &PerformanceAlertEvent) -> String {
+    let (label, unit) = match event.metric {
+        AlertMetric::CpuUsage => ("CPU usage", "%"),
+        AlertMetric::CpuTemp => ("CPU temperature", "°C"),
+        AlertMetric::GpuUsage => ("GPU usage", "%"),
+        AlertMetric::GpuTemp => ("GPU temperature", "°C"),
+        AlertMetric::VramUsage => ("VRAM usage", "%"),
+        AlertMetric::RamUsage => ("RAM usage", "%"),
+        AlertMetric::RamAvailableMb => ("Available RAM", " MB"),
+    };
+    let comparator = match event.comparator {
+        AlertComparator::Above => "above",
+        AlertComparator::Below => "below",
+    };
+    format!("{} is {:.1}{} ({} {}{})", label, event.value, unit, comparator, event.threshold, unit)
+}
+
+/// Tracks per-alert cooldowns so a sustained spike fires once per cooldown window
+#[derive(Default)]
+pub struct AlertEvaluator {
+    /// Alert id -> timestamp (ms) it last fired
+    last_fired: HashMap<String, i64>,
+}
+
+impl AlertEvaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate enabled alerts against a snapshot and return the ones that fire
+    pub fn evaluate(&mut self, alerts: &[PerformanceAlert], metrics: &SystemMetrics) -> Vec<PerformanceAlertEvent> {
+        let now = metrics.timestamp;
+        let mut fired = Vec::new();
+
+        for alert in alerts.iter().filter(|a| a.enabled) {
+            let Some(value) = metric_value(alert.metric, metrics) else {
+                continue;
+            };
+            let triggered = match alert.comparator {
+                AlertComparator::Above => value > alert.value,
+                AlertComparator::Below => value < alert.value,
+            };
+            if !triggered {
+                continue;
+            }
+
+            let cooldown_ms = alert.cooldown_seconds as i64 * 1000;
+            if let Some(last) = self.last_fired.get(&alert.id) {
+                if now - last < cooldown_ms {
+                    continue;
+                }
+            }
+
+            self.last_fired.insert(alert.id.clone(), now);
+            fired.push(PerformanceAlertEvent {
+                alert_id: alert.id.clone(),
+                name: alert.name.clone(),
+                metric: alert.metric,
+                comparator: alert.comparator,
+                threshold: alert.value,
+                value,
+                notify: alert.notify,
+                timestamp: now,
+            });
+        }
+
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::performance::CpuMetrics;
+
+    fn snapshot(timestamp: i64, cpu_temp: f32) -> SystemMetrics {
+        SystemMetrics {
+            cpu: CpuMetrics {
+                temperature_celsius: Some(cpu_temp),
+                ..CpuMetrics::default()
+            },
+            timestamp,
+            ..SystemMetrics::default()
+        }
+    }
+
+    #[test]
+    fn test_defaults_are_disabled() {
+        let alerts = default_alerts();
+        assert!(!alerts.is_empty());
+        assert!(alerts.iter().all(|a| !a.enabled));
+
+        let mut evaluator = AlertEvaluator::new();
+        assert!(evaluator.evaluate(&alerts, &snapshot(0, 99.0)).is_empty());
+    }
+
+    #[test]
+    fn test_cooldown_suppresses_repeat_alerts() {
+        let mut alerts = default_alerts();
+        alerts.iter_mut().for_each(|a| a.enabled = true);
+        let mut evaluator = AlertEvaluator::new();
+
+        let first = evaluator.evaluate(&alerts, &snapshot(0, 95.0));
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].alert_id, "cpu_temp_high");
+
+        // Still hot 10s later - within the 300s cooldown
+        assert!(evaluator.evaluate(&alerts, &snapshot(10_000, 96.0)).is_empty());
+        // Below threshold never fires
+        assert!(evaluator.evaluate(&alerts, &snapshot(400_000, 70.0)).is_empty());
+        // Cooldown elapsed
+        assert_eq!(evaluator.evaluate(&alerts, &snapshot(401_000, 95.0)).len(), 1);
+    }
+
+    #[test]
+    fn test_describe_alert_uses_metric_units() {
+        let mut alerts = default_alerts();
+        alerts.iter_mut().for_each(|a| a.enabled = true);
+        let mut evaluator = AlertEvaluator::new();
+
+        let fired = evaluator.evaluate(&alerts, &snapshot(0, 93.44));
+        assert_eq!(describe_alert(&fired[0]), "CPU temperature is 93.4°C (above 90°C)");
+    }
+}
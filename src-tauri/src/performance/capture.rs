@@ -0,0 +1,120 @@
+// Raw performance capture to a JSON-lines file (for sharing in support threads)
+// The first line is a `CaptureHeader` with hardware info, every following line is a `SystemMetrics` sample
+use crate::models::performance::{CaptureHeader, CaptureStopReason, CaptureSummary, SystemMetrics};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+pub struct PerformanceCapture {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    started: Instant,
+    started_at: i64,
+    header_written: bool,
+    samples_written: u64,
+    bytes_written: u64,
+    max_duration: Duration,
+    max_bytes: u64,
+}
+
+impl PerformanceCapture {
+    /// Create (or truncate) the capture file
+    pub fn start(path: PathBuf, max_minutes: u32, max_mb: u32) -> Result<Self, String> {
+        let file = File::create(&path).map_err(|e| format!("Failed to create capture file: {}", e))?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+            started_at: chrono::Utc::now().timestamp_millis(),
+            header_written: false,
+            samples_written: 0,
+            bytes_written: 0,
+            max_duration: Duration::from_secs(max_minutes.max(1) as u64 * 60),
+            max_bytes: max_mb.max(1) as u64 * 1024 * 1024,
+        })
+    }
+
+    /// Append a sample; returns the reason when a duration/size cap has been reached
+    /// The header is written with the first sample so it can describe the actual hardware
+    pub fn append(&mut self, metrics: &SystemMetrics) -> Result<Option<CaptureStopReason>, String> {
+        if !self.header_written {
+            let header = CaptureHeader {
+                format: "atlas-performance-capture".to_string(),
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                started_at: self.started_at,
+                cpu_model: metrics.cpu.name.clone(),
+                cpu_core_count: metrics.cpu.core_count,
+                gpu_names: metrics.gpus.iter().map(|g| g.name.clone()).collect(),
+                ram_total_mb: metrics.ram.total_mb,
+            };
+            self.write_line(&header)?;
+            self.header_written = true;
+        }
+
+        self.write_line(metrics)?;
+        self.samples_written += 1;
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to flush capture file: {}", e))?;
+
+        if self.bytes_written >= self.max_bytes {
+            Ok(Some(CaptureStopReason::SizeLimit))
+        } else if self.started.elapsed() >= self.max_duration {
+            Ok(Some(CaptureStopReason::DurationLimit))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Flush and close the file
+    pub fn finish(mut self, reason: CaptureStopReason) -> CaptureSummary {
+        let _ = self.writer.flush();
+
+        CaptureSummary {
+            path: self.path.to_string_lossy().to_string(),
+            samples_written: self.samples_written,
+            bytes_written: self.bytes_written,
+            duration_seconds: self.started.elapsed().as_secs_f64(),
+            stop_reason: reason,
+        }
+    }
+
+    fn write_line<T: serde::Serialize>(&mut self, value: &T) -> Result<(), String> {
+        let mut line =
+            serde_json::to_vec(value).map_err(|e| format!("Failed to serialize capture sample: {}", e))?;
+        line.push(b'\n');
+        self.writer
+            .write_all(&line)
+            .map_err(|e| format!("Failed to write capture file: {}", e))?;
+        self.bytes_written += line.len() as u64;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_writes_header_and_samples() {
+        let path = std::env::temp_dir().join(format!("atlas_capture_test_{}.jsonl", uuid::Uuid::new_v4()));
+        let mut capture = PerformanceCapture::start(path.clone(), 60, 100).unwrap();
+
+        let metrics = SystemMetrics::default();
+        assert_eq!(capture.append(&metrics).unwrap(), None);
+        assert_eq!(capture.append(&metrics).unwrap(), None);
+
+        let summary = capture.finish(CaptureStopReason::Manual);
+        assert_eq!(summary.samples_written, 2);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("atlas-performance-capture"));
+        assert_eq!(summary.bytes_written, content.len() as u64);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -1,19 +1,42 @@
 // Performance data collector
-use crate::models::performance::{CpuMetrics, GpuMetrics, RamMetrics, SystemMetrics};
-use super::gpu::NvidiaGpu;
+use crate::models::performance::{
+    CaptureStopReason, CpuMetrics, GpuMetrics, PerformanceAlert, PowerSourceChangedEvent, RamMetrics,
+    SystemMetrics,
+};
+use super::alerts::{describe_alert, load_alerts, AlertEvaluator};
+use super::capture::PerformanceCapture;
+use super::cpu_topology::{parse_cpu_temperatures, CpuTopology};
+use super::disk::DiskTracker;
+use super::gpu::{busiest_gpu, GpuProvider, NvidiaGpu};
+use super::history::MetricsHistory;
+use super::intel_gpu::IntelGpu;
+use super::memory::MemoryTracker;
+use super::network::NetworkTracker;
+use super::power::PowerTracker;
 use log::{debug, info, warn};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
 use std::time::Duration;
-use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+use sysinfo::{Components, CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
 use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
 
 /// State for tracking if monitoring is active
 pub struct MonitoringState {
     pub is_running: Arc<AtomicBool>,
     /// Gaming mode flag - when true, reduces NVML polling frequency to minimize FPS impact
     pub gaming_active: Arc<AtomicBool>,
+    /// Poll interval from settings (0 = automatic); read every cycle so changes apply live
+    pub poll_interval_ms: Arc<AtomicU32>,
+    /// Whether disk metrics include removable drives, from settings
+    pub include_removable_disks: Arc<AtomicBool>,
+    /// Name of the GPU the user pinned as primary (None = busiest adapter)
+    pub preferred_gpu: Arc<RwLock<Option<String>>>,
+    /// Threshold alerts evaluated every cycle
+    pub alerts: Arc<RwLock<Vec<PerformanceAlert>>>,
+    /// Active raw data capture, appended to every cycle
+    pub capture: Arc<Mutex<Option<PerformanceCapture>>>,
 }
 
 impl Default for MonitoringState {
@@ -21,20 +44,50 @@ impl Default for MonitoringState {
         Self {
             is_running: Arc::new(AtomicBool::new(false)),
             gaming_active: Arc::new(AtomicBool::new(false)),
+            poll_interval_ms: Arc::new(AtomicU32::new(DEFAULT_POLL_INTERVAL_MS)),
+            include_removable_disks: Arc::new(AtomicBool::new(false)),
+            preferred_gpu: Arc::new(RwLock::new(None)),
+            alerts: Arc::new(RwLock::new(Vec::new())),
+            capture: Arc::new(Mutex::new(None)),
         }
     }
 }
 
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Default fixed poll interval
+const DEFAULT_POLL_INTERVAL_MS: u32 = 1000;
+/// Fastest/slowest fixed poll interval accepted from settings
+pub const MIN_POLL_INTERVAL_MS: u32 = 250;
+pub const MAX_POLL_INTERVAL_MS: u32 = 60_000;
+/// Automatic mode: tight sampling during a gaming session, relaxed when idle to save CPU
+const AUTO_GAMING_POLL_INTERVAL_MS: u32 = 1000;
+const AUTO_IDLE_POLL_INTERVAL_MS: u32 = 5000;
+
+/// Resolve the sleep between samples from the configured interval (0 = automatic)
+fn poll_interval(configured_ms: u32, gaming_active: bool) -> Duration {
+    let ms = match (configured_ms, gaming_active) {
+        (0, true) => AUTO_GAMING_POLL_INTERVAL_MS,
+        (0, false) => AUTO_IDLE_POLL_INTERVAL_MS,
+        // A fixed interval is the user's explicit choice, gaming or not
+        (ms, _) => ms.clamp(MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS),
+    };
+    Duration::from_millis(ms as u64)
+}
+
 /// Shared metrics state - allows other components to read the latest metrics
 /// without creating their own collectors (avoids duplicate NVML queries)
 pub struct SharedMetrics {
     pub latest: RwLock<Option<SystemMetrics>>,
+    /// Rolling history of recent snapshots for graphs
+    history: RwLock<MetricsHistory>,
 }
 
 impl SharedMetrics {
     pub fn new() -> Self {
         Self {
             latest: RwLock::new(None),
+            history: RwLock::new(MetricsHistory::default()),
         }
     }
 
@@ -45,10 +98,35 @@ impl SharedMetrics {
 
     /// Update the latest metrics (called by the performance collector)
     pub fn set(&self, metrics: SystemMetrics) {
+        if let Ok(mut history) = self.history.write() {
+            history.push(metrics.clone());
+        }
         if let Ok(mut guard) = self.latest.write() {
             *guard = Some(metrics);
         }
     }
+
+    /// Get recent history, downsampled to at most `max_points` (0 = no limit)
+    pub fn history(&self, seconds: u32, max_points: u32) -> Vec<SystemMetrics> {
+        self.history
+            .read()
+            .map(|history| history.query(seconds, max_points))
+            .unwrap_or_default()
+    }
+
+    /// Change how many minutes of history are retained
+    pub fn set_history_window_minutes(&self, minutes: u32) {
+        if let Ok(mut history) = self.history.write() {
+            history.set_window_minutes(minutes);
+        }
+    }
+
+    /// Drop all retained history
+    pub fn clear_history(&self) {
+        if let Ok(mut history) = self.history.write() {
+            history.clear();
+        }
+    }
 }
 
 impl Default for SharedMetrics {
@@ -61,20 +139,26 @@ impl Default for SharedMetrics {
 /// Caches immutable values (CPU name, core count) to avoid redundant allocations
 pub struct PerformanceCollector {
     system: System,
-    nvidia_gpu: Option<NvidiaGpu>,
+    components: Components,
+    gpu_providers: Vec<Box<dyn GpuProvider>>,
+    network: NetworkTracker,
+    disks: DiskTracker,
+    power: PowerTracker,
+    memory: MemoryTracker,
     cached_cpu_name: String,
     cached_core_count: usize,
+    topology: CpuTopology,
+    preferred_gpu: Option<String>,
 }
 
 impl PerformanceCollector {
     /// Create a new performance collector
     /// Caches CPU name and core count at initialization (these never change)
     pub fn new() -> Self {
-        // Initialize system with CPU usage and memory refresh capabilities
-        // Only request cpu_usage - frequency polling adds overhead for data we don't need per-second
+        // Initialize system with CPU usage, frequency and memory refresh capabilities
         let system = System::new_with_specifics(
             RefreshKind::new()
-                .with_cpu(CpuRefreshKind::new().with_cpu_usage())
+                .with_cpu(CpuRefreshKind::new().with_cpu_usage().with_frequency())
                 .with_memory(MemoryRefreshKind::everything()),
         );
 
@@ -84,33 +168,54 @@ impl PerformanceCollector {
             .map(|cpu| cpu.brand().to_string())
             .unwrap_or_else(|| "Unknown CPU".to_string());
         let cached_core_count = system.cpus().len();
-
-        // Try to initialize NVIDIA GPU (will be None if not available)
-        let nvidia_gpu = NvidiaGpu::new().ok();
-        if nvidia_gpu.is_some() {
-            debug!("NVIDIA GPU detected and initialized");
-        } else {
-            debug!("No NVIDIA GPU detected or NVML not available");
-        }
+        let topology = CpuTopology::detect(cached_core_count);
+        let components = Components::new_with_refreshed_list();
 
         Self {
             system,
-            nvidia_gpu,
+            components,
+            gpu_providers: init_gpu_providers(),
+            network: NetworkTracker::new(),
+            disks: DiskTracker::new(),
+            power: PowerTracker::new(),
+            memory: MemoryTracker::new(),
             cached_cpu_name,
             cached_core_count,
+            topology,
+            preferred_gpu: None,
         }
     }
 
+    /// Report removable drives alongside fixed disks
+    pub fn set_include_removable_disks(&mut self, include: bool) {
+        self.disks.set_include_removable(include);
+    }
+
+    /// Pin a GPU (by name) as the primary adapter; None reports the busiest one
+    pub fn set_preferred_gpu(&mut self, name: Option<String>) {
+        self.preferred_gpu = name;
+    }
+
     /// Collect all system metrics
     pub fn collect(&mut self) -> SystemMetrics {
         // Refresh CPU and memory data
-        self.system.refresh_cpu();
+        self.system
+            .refresh_cpu_specifics(CpuRefreshKind::new().with_cpu_usage().with_frequency());
         self.system.refresh_memory();
+        self.components.refresh();
+
+        let gpus = collect_gpus(&self.gpu_providers);
+        let gpu = select_primary_gpu(&gpus, self.preferred_gpu.as_deref()).cloned();
 
         SystemMetrics {
             cpu: self.collect_cpu(),
-            gpu: self.collect_gpu(),
+            gpu,
+            gpus,
             ram: self.collect_ram(),
+            network: self.network.sample(),
+            disks: self.disks.sample(),
+            power: self.power.sample(),
+            top_processes: None,
             timestamp: chrono::Utc::now().timestamp_millis(),
         }
     }
@@ -132,21 +237,49 @@ impl PerformanceCollector {
         // Get frequency from first core (in MHz) - only if needed for display
         let frequency_mhz = cpus.first().map(|cpu| cpu.frequency());
 
+        // Virtualized environments often report 0 MHz - treat that as unavailable
+        let frequencies: Vec<u64> = cpus.iter().map(|cpu| cpu.frequency()).filter(|f| *f > 0).collect();
+        let average_frequency_mhz = if frequencies.is_empty() {
+            None
+        } else {
+            Some(frequencies.iter().sum::<u64>() / frequencies.len() as u64)
+        };
+        let max_frequency_mhz = frequencies.iter().max().copied();
+
+        // Per-core sensors are matched through the physical core id; systems that only
+        // expose a package (or ACPI thermal zone) sensor report that value for every core
+        let readings: Vec<(String, f32)> = self
+            .components
+            .iter()
+            .map(|c| (c.label().to_string(), c.temperature()))
+            .collect();
+        let temps = parse_cpu_temperatures(&readings);
+        let per_core_temperature: Vec<Option<f32>> = (0..per_core_usage.len())
+            .map(|i| {
+                self.topology
+                    .physical_core_ids
+                    .get(i)
+                    .copied()
+                    .flatten()
+                    .and_then(|core_id| temps.per_core.get(&core_id).copied())
+                    .or(temps.package)
+            })
+            .collect();
+
         CpuMetrics {
             usage_percent,
             per_core_usage,
-            temperature_celsius: None, // sysinfo doesn't provide CPU temp on Windows directly
+            temperature_celsius: temps.package,
+            per_core_temperature,
+            core_kinds: self.topology.core_kinds.clone(),
             frequency_mhz,
+            average_frequency_mhz,
+            max_frequency_mhz,
             core_count: self.cached_core_count,
             name: self.cached_cpu_name.clone(),
         }
     }
 
-    /// Collect GPU metrics
-    fn collect_gpu(&self) -> Option<GpuMetrics> {
-        self.nvidia_gpu.as_ref().and_then(|gpu| gpu.collect().ok())
-    }
-
     /// Collect RAM metrics
     fn collect_ram(&self) -> RamMetrics {
         let total = self.system.total_memory();
@@ -159,15 +292,89 @@ impl PerformanceCollector {
             0.0
         };
 
+        let details = self.memory.sample();
+        let swap_total = self.system.total_swap();
+        let (pagefile_used_mb, pagefile_total_mb) = if swap_total > 0 {
+            (Some(self.system.used_swap() / BYTES_PER_MB), Some(swap_total / BYTES_PER_MB))
+        } else {
+            (None, None)
+        };
+
         RamMetrics {
             total_bytes: total,
             used_bytes: used,
             available_bytes: available,
             usage_percent,
+            total_mb: total / BYTES_PER_MB,
+            used_mb: used / BYTES_PER_MB,
+            available_mb: available / BYTES_PER_MB,
+            committed_mb: details.committed_mb,
+            commit_limit_mb: details.commit_limit_mb,
+            standby_cache_mb: details.standby_cache_mb,
+            pagefile_used_mb,
+            pagefile_total_mb,
         }
     }
 }
 
+/// Initialize every GPU backend that is available on this machine
+fn init_gpu_providers() -> Vec<Box<dyn GpuProvider>> {
+    let mut gpu_providers: Vec<Box<dyn GpuProvider>> = Vec::new();
+    match NvidiaGpu::new() {
+        Ok(gpu) => {
+            debug!("NVIDIA GPU detected and initialized");
+            gpu_providers.push(Box::new(gpu));
+        }
+        Err(e) => debug!("No NVIDIA GPU detected or NVML not available: {}", e),
+    }
+    match IntelGpu::new() {
+        Ok(gpu) => {
+            debug!("Intel GPU detected and initialized");
+            gpu_providers.push(Box::new(gpu));
+        }
+        Err(e) => debug!("No Intel GPU metrics available: {}", e),
+    }
+    gpu_providers
+}
+
+/// Collect metrics for every adapter of every provider, indexed in provider order (NVML first)
+fn collect_gpus(providers: &[Box<dyn GpuProvider>]) -> Vec<GpuMetrics> {
+    let mut gpus: Vec<GpuMetrics> = providers
+        .iter()
+        .filter_map(|provider| match provider.collect() {
+            Ok(metrics) => Some(metrics),
+            Err(e) => {
+                debug!("GPU provider {:?} failed to collect: {}", provider.kind(), e);
+                None
+            }
+        })
+        .flatten()
+        .collect();
+
+    for (index, gpu) in gpus.iter_mut().enumerate() {
+        gpu.index = index;
+    }
+    gpus
+}
+
+/// Pick the primary GPU: the pinned adapter when present, otherwise the busiest one
+/// (on hybrid iGPU + dGPU systems that is the adapter actually rendering)
+/// `preferred` is an adapter id; adapter names saved by older versions still match by name
+fn select_primary_gpu<'a>(gpus: &'a [GpuMetrics], preferred: Option<&str>) -> Option<&'a GpuMetrics> {
+    preferred
+        .and_then(|preferred| {
+            gpus.iter()
+                .find(|gpu| gpu.adapter_id.as_deref() == Some(preferred))
+                .or_else(|| gpus.iter().find(|gpu| gpu.name == preferred))
+        })
+        .or_else(|| busiest_gpu(gpus))
+}
+
+/// List every detected GPU with a one-off reading (for picking a preferred adapter)
+pub fn detect_gpus() -> Vec<GpuMetrics> {
+    collect_gpus(&init_gpu_providers())
+}
+
 /// Start performance monitoring in a background thread
 /// The shared_metrics parameter allows other components (like gaming session recording)
 /// to read the latest metrics without creating duplicate collectors
@@ -184,8 +391,25 @@ pub fn start_monitoring(app: AppHandle, state: Arc<MonitoringState>, shared_metr
     state.is_running.store(true, Ordering::SeqCst);
     info!("Starting performance monitoring...");
 
+    let settings = crate::commands::settings::get_settings().unwrap_or_default();
+    state.poll_interval_ms.store(settings.performance_poll_interval_ms, Ordering::Relaxed);
+    state
+        .include_removable_disks
+        .store(settings.performance_include_removable_disks, Ordering::Relaxed);
+
     let is_running = state.is_running.clone();
     let gaming_active = state.gaming_active.clone();
+    let poll_interval_ms = state.poll_interval_ms.clone();
+    let include_removable_disks = state.include_removable_disks.clone();
+    let preferred_gpu = state.preferred_gpu.clone();
+    if let Ok(mut guard) = preferred_gpu.write() {
+        *guard = settings.preferred_gpu.clone();
+    }
+    let alerts = state.alerts.clone();
+    let capture = state.capture.clone();
+    if let Ok(mut guard) = alerts.write() {
+        *guard = load_alerts();
+    }
 
     // Spawn monitoring thread
     thread::spawn(move || {
@@ -213,31 +437,115 @@ pub fn start_monitoring(app: AppHandle, state: Arc<MonitoringState>, shared_metr
         // Otherwise there's nearly zero time between refreshes, causing incorrect readings
         thread::sleep(Duration::from_millis(500));
 
+        let mut last_on_ac_power: Option<bool> = None;
+        let mut alert_evaluator = AlertEvaluator::new();
+
         while is_running.load(Ordering::SeqCst) {
+            collector.set_preferred_gpu(preferred_gpu.read().ok().and_then(|guard| guard.clone()));
+            collector.set_include_removable_disks(include_removable_disks.load(Ordering::Relaxed));
             let metrics = collector.collect();
 
+            // Notify listeners when switching between AC and battery (performance usually drops on battery)
+            if let Some(on_ac_power) = metrics.power.on_ac_power {
+                if last_on_ac_power.is_some_and(|previous| previous != on_ac_power) {
+                    info!("Power source changed: {}", if on_ac_power { "AC" } else { "battery" });
+                    let event = PowerSourceChangedEvent {
+                        on_ac_power,
+                        battery_percent: metrics.power.battery_percent,
+                    };
+                    if let Err(e) = app.emit("performance:power_source_changed", &event) {
+                        warn!("Failed to emit power source change: {}", e);
+                    }
+                }
+                last_on_ac_power = Some(on_ac_power);
+            }
+
+            append_capture_sample(&app, &capture, &metrics);
+
+            if let Ok(alerts) = alerts.read() {
+                for event in alert_evaluator.evaluate(&alerts, &metrics) {
+                    info!("Performance alert fired: {} ({:.1})", event.name, event.value);
+                    if let Err(e) = app.emit("performance:alert", &event) {
+                        warn!("Failed to emit performance alert: {}", e);
+                    }
+                    if event.notify {
+                        if let Err(e) = app
+                            .notification()
+                            .builder()
+                            .title(&event.name)
+                            .body(describe_alert(&event))
+                            .show()
+                        {
+                            warn!("Failed to show performance alert notification: {}", e);
+                        }
+                    }
+                }
+            }
+
             // Update shared metrics so other components can read them
             shared_metrics.set(metrics.clone());
 
-            // Emit event to frontend
-            if let Err(e) = app.emit("performance:update", &metrics) {
+            // Emit event to frontend (without per-interface breakdowns to keep the payload small)
+            if let Err(e) = app.emit("performance:update", &metrics.without_details()) {
                 warn!("Failed to emit performance update: {}", e);
             }
 
-            // Gaming mode: 3 seconds to reduce GPU driver interruptions
-            // Normal mode: 1 second for responsive monitoring
-            let interval = if gaming_active.load(Ordering::Relaxed) {
-                Duration::from_secs(3)
-            } else {
-                Duration::from_secs(1)
-            };
+            // Re-read every cycle so settings changes apply without restarting the monitor
+            let interval = poll_interval(
+                poll_interval_ms.load(Ordering::Relaxed),
+                gaming_active.load(Ordering::Relaxed),
+            );
             thread::sleep(interval);
         }
 
+        // No more samples will arrive, so a running capture is closed rather than left open
+        end_capture(&app, &capture, CaptureStopReason::MonitoringStopped);
         debug!("Performance monitoring stopped");
     });
 }
 
+/// Append a sample to the active capture, ending it when a limit is hit or writing fails
+fn append_capture_sample(app: &AppHandle, capture: &Mutex<Option<PerformanceCapture>>, metrics: &SystemMetrics) {
+    let Ok(mut guard) = capture.lock() else {
+        return;
+    };
+    let Some(active) = guard.as_mut() else {
+        return;
+    };
+
+    let stop_reason = match active.append(metrics) {
+        Ok(reason) => reason,
+        Err(e) => {
+            warn!("Performance capture failed: {}", e);
+            Some(CaptureStopReason::Error)
+        }
+    };
+
+    if let Some(reason) = stop_reason {
+        finish_capture(app, guard.take(), reason);
+    }
+}
+
+/// Finish the active capture, if any, and emit `performance:capture_stopped`
+fn end_capture(app: &AppHandle, capture: &Mutex<Option<PerformanceCapture>>, reason: CaptureStopReason) {
+    let active = capture.lock().ok().and_then(|mut guard| guard.take());
+    finish_capture(app, active, reason);
+}
+
+fn finish_capture(app: &AppHandle, capture: Option<PerformanceCapture>, reason: CaptureStopReason) {
+    let Some(capture) = capture else {
+        return;
+    };
+    let summary = capture.finish(reason);
+    info!(
+        "Performance capture stopped ({:?}) after {} samples",
+        reason, summary.samples_written
+    );
+    if let Err(e) = app.emit("performance:capture_stopped", &summary) {
+        warn!("Failed to emit capture stopped event: {}", e);
+    }
+}
+
 /// Stop performance monitoring
 pub fn stop_monitoring(state: Arc<MonitoringState>) {
     debug!("Stopping performance monitoring...");
@@ -247,6 +555,9 @@ pub fn stop_monitoring(state: Arc<MonitoringState>) {
 /// Get a single performance snapshot (for one-time queries)
 pub fn get_snapshot() -> SystemMetrics {
     let mut collector = PerformanceCollector::new();
+    let settings = crate::commands::settings::get_settings().unwrap_or_default();
+    collector.set_preferred_gpu(settings.preferred_gpu);
+    collector.set_include_removable_disks(settings.performance_include_removable_disks);
 
     // Need to wait a bit for CPU usage to be accurate
     thread::sleep(Duration::from_millis(200));
@@ -255,3 +566,67 @@ pub fn get_snapshot() -> SystemMetrics {
 
     collector.collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_interval_fixed_and_auto() {
+        assert_eq!(poll_interval(1000, false), Duration::from_millis(1000));
+        assert_eq!(poll_interval(1000, true), Duration::from_millis(1000));
+        assert_eq!(poll_interval(10, false), Duration::from_millis(250));
+        assert_eq!(poll_interval(0, true), Duration::from_millis(1000));
+        assert_eq!(poll_interval(0, false), Duration::from_millis(5000));
+    }
+
+    fn gpu(name: &str, usage_percent: f32) -> GpuMetrics {
+        GpuMetrics {
+            index: 0,
+            name: name.to_string(),
+            adapter_id: None,
+            usage_percent,
+            memory_used_mb: 0,
+            memory_total_mb: 0,
+            temperature_celsius: None,
+            power_draw_watts: None,
+            power_limit_watts: None,
+            fan_speed_percent: None,
+            core_clock_mhz: None,
+            memory_clock_mhz: None,
+            provider: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_select_primary_gpu() {
+        let gpus = vec![gpu("RTX 4070 Laptop", 10.0), gpu("Intel Iris Xe", 60.0)];
+
+        assert_eq!(select_primary_gpu(&gpus, None).map(|g| g.name.as_str()), Some("Intel Iris Xe"));
+        assert_eq!(
+            select_primary_gpu(&gpus, Some("RTX 4070 Laptop")).map(|g| g.name.as_str()),
+            Some("RTX 4070 Laptop")
+        );
+        // A pinned adapter that is no longer present falls back to the busiest
+        assert_eq!(
+            select_primary_gpu(&gpus, Some("Missing GPU")).map(|g| g.name.as_str()),
+            Some("Intel Iris Xe")
+        );
+        assert!(select_primary_gpu(&[], None).is_none());
+    }
+
+    #[test]
+    fn test_select_primary_gpu_by_adapter_id() {
+        // Two identical boards: only the adapter id tells them apart
+        let mut first = gpu("RTX 4090", 80.0);
+        first.adapter_id = Some("nvml:GPU-aaaa".to_string());
+        let mut second = gpu("RTX 4090", 5.0);
+        second.adapter_id = Some("nvml:GPU-bbbb".to_string());
+        second.index = 1;
+        let gpus = vec![first, second];
+
+        assert_eq!(select_primary_gpu(&gpus, Some("nvml:GPU-bbbb")).map(|g| g.index), Some(1));
+        assert_eq!(select_primary_gpu(&gpus, Some("RTX 4090")).map(|g| g.index), Some(0));
+        assert_eq!(select_primary_gpu(&gpus, Some("nvml:GPU-gone")).map(|g| g.index), Some(0));
+    }
+}
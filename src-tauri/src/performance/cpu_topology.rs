@@ -0,0 +1,275 @@
+// CPU topology detection - hybrid core (P/E) labels and logical -> physical core mapping
+use crate::models::performance::CoreKind;
+use std::collections::HashMap;
+
+/// Static CPU topology, detected once when the collector is created
+#[derive(Debug, Clone, Default)]
+pub struct CpuTopology {
+    /// Core kind per logical CPU - None when the CPU is not hybrid or detection failed
+    pub core_kinds: Option<Vec<CoreKind>>,
+    /// Physical core id per logical CPU (used to match per-core temperature sensors)
+    pub physical_core_ids: Vec<Option<usize>>,
+}
+
+impl CpuTopology {
+    /// Detect the topology for `logical_count` logical CPUs
+    pub fn detect(logical_count: usize) -> Self {
+        let topology = detect_platform(logical_count);
+        if topology.physical_core_ids.len() != logical_count {
+            return Self {
+                core_kinds: None,
+                physical_core_ids: vec![None; logical_count],
+            };
+        }
+        topology
+    }
+}
+
+#[cfg(windows)]
+fn detect_platform(logical_count: usize) -> CpuTopology {
+    use windows_sys::Win32::System::SystemInformation::{
+        GetLogicalProcessorInformationEx, RelationProcessorCore, SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+    };
+
+    let mut length: u32 = 0;
+    unsafe {
+        GetLogicalProcessorInformationEx(RelationProcessorCore, std::ptr::null_mut(), &mut length);
+    }
+    if length == 0 {
+        return CpuTopology::default();
+    }
+
+    // Pad by one full struct so reading the last (possibly shorter) record stays in bounds
+    let mut buffer: Vec<u8> =
+        vec![0; length as usize + std::mem::size_of::<SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX>()];
+    let ok = unsafe {
+        GetLogicalProcessorInformationEx(
+            RelationProcessorCore,
+            buffer.as_mut_ptr() as *mut SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+            &mut length,
+        )
+    };
+    if ok == 0 {
+        return CpuTopology::default();
+    }
+
+    let mut efficiency_classes: Vec<Option<u8>> = vec![None; logical_count];
+    let mut physical_core_ids: Vec<Option<usize>> = vec![None; logical_count];
+
+    let mut offset = 0usize;
+    let mut core_id = 0usize;
+    while offset < length as usize {
+        // Records are variable-sized; read unaligned to stay safe
+        let info = unsafe {
+            std::ptr::read_unaligned(buffer.as_ptr().add(offset) as *const SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX)
+        };
+        if info.Size == 0 {
+            break;
+        }
+
+        if info.Relationship == RelationProcessorCore {
+            let processor = unsafe { info.Anonymous.Processor };
+            let mask = processor.GroupMask[0];
+            for bit in 0..usize::BITS as usize {
+                if mask.Mask & (1usize << bit) != 0 {
+                    let logical = mask.Group as usize * usize::BITS as usize + bit;
+                    if logical < logical_count {
+                        efficiency_classes[logical] = Some(processor.EfficiencyClass);
+                        physical_core_ids[logical] = Some(core_id);
+                    }
+                }
+            }
+            core_id += 1;
+        }
+
+        offset += info.Size as usize;
+    }
+
+    CpuTopology {
+        core_kinds: classify_efficiency_classes(&efficiency_classes),
+        physical_core_ids,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn detect_platform(logical_count: usize) -> CpuTopology {
+    use std::fs;
+
+    let physical_core_ids = (0..logical_count)
+        .map(|cpu| {
+            fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/topology/core_id", cpu))
+                .ok()
+                .and_then(|s| s.trim().parse::<usize>().ok())
+        })
+        .collect();
+
+    // Hybrid Intel CPUs expose separate PMUs for P-cores and E-cores
+    let p_cores = fs::read_to_string("/sys/devices/cpu_core/cpus").ok().map(|s| parse_cpu_list(&s));
+    let e_cores = fs::read_to_string("/sys/devices/cpu_atom/cpus").ok().map(|s| parse_cpu_list(&s));
+
+    let core_kinds = match (p_cores, e_cores) {
+        (Some(p), Some(e)) if !p.is_empty() && !e.is_empty() => Some(
+            (0..logical_count)
+                .map(|cpu| if e.contains(&cpu) { CoreKind::Efficiency } else { CoreKind::Performance })
+                .collect(),
+        ),
+        _ => None,
+    };
+
+    CpuTopology {
+        core_kinds,
+        physical_core_ids,
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn detect_platform(_logical_count: usize) -> CpuTopology {
+    CpuTopology::default()
+}
+
+/// Map Windows efficiency classes to core kinds
+/// The highest class is the performance class; all cores sharing one class means not hybrid
+#[cfg_attr(not(windows), allow(dead_code))]
+fn classify_efficiency_classes(classes: &[Option<u8>]) -> Option<Vec<CoreKind>> {
+    let known: Vec<u8> = classes.iter().flatten().copied().collect();
+    let max = *known.iter().max()?;
+    let min = *known.iter().min()?;
+    if max == min {
+        return None;
+    }
+
+    Some(
+        classes
+            .iter()
+            .map(|class| match class {
+                Some(c) if *c == max => CoreKind::Performance,
+                _ => CoreKind::Efficiency,
+            })
+            .collect(),
+    )
+}
+
+/// Parse a sysfs CPU list such as "0-7,16-19"
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = part.trim().parse::<usize>() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
+
+/// CPU temperatures extracted from sensor readings
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuTemperatures {
+    /// Package / die temperature (or the hottest generic CPU sensor)
+    pub package: Option<f32>,
+    /// Temperature per physical core id, when the sensor source reports it
+    pub per_core: HashMap<usize, f32>,
+}
+
+/// Sort sensor readings (label, temperature) into package and per-core temperatures
+/// Recognizes coretemp ("Core 3", "Package id 0"), k10temp ("Tctl", "Tdie") and the
+/// ACPI thermal zone sysinfo reports on Windows ("Computer")
+pub fn parse_cpu_temperatures(readings: &[(String, f32)]) -> CpuTemperatures {
+    let mut temps = CpuTemperatures::default();
+
+    for (label, value) in readings {
+        if !value.is_finite() || *value <= 0.0 {
+            continue;
+        }
+        let lower = label.to_lowercase();
+
+        if let Some(core_id) = parse_core_label(&lower) {
+            let entry = temps.per_core.entry(core_id).or_insert(*value);
+            if *value > *entry {
+                *entry = *value;
+            }
+            continue;
+        }
+
+        if lower.contains("package")
+            || lower.contains("tctl")
+            || lower.contains("tdie")
+            || lower.contains("cpu")
+            || lower == "computer"
+        {
+            temps.package = Some(temps.package.map_or(*value, |p| p.max(*value)));
+        }
+    }
+
+    // Fall back to the hottest core when no package sensor exists
+    if temps.package.is_none() {
+        temps.package = temps.per_core.values().copied().reduce(f32::max);
+    }
+
+    temps
+}
+
+/// Extract N from labels like "coretemp core 12"
+fn parse_core_label(lower_label: &str) -> Option<usize> {
+    let idx = lower_label.find("core ")?;
+    lower_label[idx + 5..]
+        .split_whitespace()
+        .next()
+        .and_then(|n| n.parse::<usize>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3\n"), vec![0, 1, 2, 3]);
+        assert_eq!(parse_cpu_list("0-1,16-17,20"), vec![0, 1, 16, 17, 20]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+
+    #[test]
+    fn test_classify_efficiency_classes() {
+        let hybrid = classify_efficiency_classes(&[Some(1), Some(1), Some(0), Some(0)]);
+        assert_eq!(
+            hybrid,
+            Some(vec![CoreKind::Performance, CoreKind::Performance, CoreKind::Efficiency, CoreKind::Efficiency])
+        );
+
+        assert_eq!(classify_efficiency_classes(&[Some(0), Some(0)]), None);
+        assert_eq!(classify_efficiency_classes(&[None, None]), None);
+    }
+
+    #[test]
+    fn test_parse_cpu_temperatures_per_core() {
+        let readings = vec![
+            ("coretemp Package id 0".to_string(), 80.0),
+            ("coretemp Core 0".to_string(), 75.0),
+            ("coretemp Core 4".to_string(), 92.0),
+            ("nvme Composite".to_string(), 45.0),
+        ];
+        let temps = parse_cpu_temperatures(&readings);
+        assert_eq!(temps.package, Some(80.0));
+        assert_eq!(temps.per_core.get(&4), Some(&92.0));
+        assert_eq!(temps.per_core.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_cpu_temperatures_package_only() {
+        let temps = parse_cpu_temperatures(&[("Computer".to_string(), 61.0)]);
+        assert_eq!(temps.package, Some(61.0));
+        assert!(temps.per_core.is_empty());
+
+        let none = parse_cpu_temperatures(&[]);
+        assert_eq!(none, CpuTemperatures::default());
+    }
+}
@@ -0,0 +1,203 @@
+// Disk capacity and activity tracking
+use crate::models::performance::DiskMetrics;
+use sysinfo::{Disk, Disks};
+
+/// Re-enumerate disks every N samples to pick up drives being mounted/unmounted
+const DISK_RELIST_EVERY: u32 = 30;
+
+pub struct DiskTracker {
+    disks: Disks,
+    #[cfg(windows)]
+    io: Option<DiskIoCounters>,
+    samples_since_relist: u32,
+    include_removable: bool,
+}
+
+impl DiskTracker {
+    pub fn new() -> Self {
+        Self {
+            disks: Disks::new_with_refreshed_list(),
+            #[cfg(windows)]
+            io: DiskIoCounters::new().ok(),
+            samples_since_relist: 0,
+            include_removable: false,
+        }
+    }
+
+    /// Report removable drives alongside fixed disks
+    pub fn set_include_removable(&mut self, include: bool) {
+        self.include_removable = include;
+    }
+
+    /// Refresh space usage and read/write rates for fixed disks, and removable ones when included
+    pub fn sample(&mut self) -> Vec<DiskMetrics> {
+        self.samples_since_relist += 1;
+        if self.samples_since_relist >= DISK_RELIST_EVERY {
+            self.disks.refresh_list();
+            self.samples_since_relist = 0;
+        } else {
+            self.disks.refresh();
+        }
+
+        #[cfg(windows)]
+        let rates = self.io.as_ref().and_then(|io| io.read_rates().ok()).unwrap_or_default();
+
+        self.disks
+            .iter()
+            .filter(|disk| self.include_removable || !disk.is_removable())
+            .map(|disk| {
+                #[allow(unused_mut)]
+                let mut metrics = disk_to_metrics(disk);
+                #[cfg(windows)]
+                {
+                    let key = volume_key(&metrics.mount_point);
+                    if let Some((read, write)) = rates.get(&key) {
+                        metrics.read_bytes_per_sec = Some(*read);
+                        metrics.write_bytes_per_sec = Some(*write);
+                    }
+                }
+                metrics
+            })
+            .collect()
+    }
+}
+
+impl Default for DiskTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One-off free space listing that does not need the monitoring loop
+/// Removable drives are skipped unless `include_removable` is set
+pub fn list_disk_space(include_removable: bool) -> Vec<DiskMetrics> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| include_removable || !disk.is_removable())
+        .map(disk_to_metrics)
+        .collect()
+}
+
+/// Free bytes on the disk holding `path`; None when no disk's mount point contains it
+pub fn available_space_for(path: &std::path::Path) -> Option<u64> {
+    disk_for_path(&list_disk_space(true), path).map(|disk| disk.available_bytes)
+}
+
+/// The disk whose mount point is the longest prefix of `path`
+fn disk_for_path<'a>(disks: &'a [DiskMetrics], path: &std::path::Path) -> Option<&'a DiskMetrics> {
+    let path = volume_key(&path.to_string_lossy());
+    disks
+        .iter()
+        .filter(|disk| {
+            let mount = volume_key(&disk.mount_point);
+            // A mount point only contains paths that continue with a separator
+            path == mount || (path.starts_with(&mount) && path[mount.len()..].starts_with(['\\', '/']))
+        })
+        .max_by_key(|disk| disk.mount_point.len())
+}
+
+fn disk_to_metrics(disk: &Disk) -> DiskMetrics {
+    let total = disk.total_space();
+    let available = disk.available_space();
+    let usage_percent = if total > 0 {
+        ((total - available.min(total)) as f64 / total as f64 * 100.0) as f32
+    } else {
+        0.0
+    };
+
+    DiskMetrics {
+        name: disk.name().to_string_lossy().to_string(),
+        mount_point: disk.mount_point().to_string_lossy().to_string(),
+        file_system: disk.file_system().to_string_lossy().to_string(),
+        total_bytes: total,
+        available_bytes: available,
+        usage_percent,
+        is_removable: disk.is_removable(),
+        read_bytes_per_sec: None,
+        write_bytes_per_sec: None,
+    }
+}
+
+/// Normalize a mount point / PDH LogicalDisk instance ("C:\\" / "C:") to a lookup key
+fn volume_key(name: &str) -> String {
+    name.trim_end_matches(['\\', '/']).to_uppercase()
+}
+
+/// Per-volume read/write byte rates from the LogicalDisk performance counters
+#[cfg(windows)]
+struct DiskIoCounters {
+    query: super::pdh::PdhQuery,
+    read_counter: usize,
+    write_counter: usize,
+}
+
+#[cfg(windows)]
+impl DiskIoCounters {
+    fn new() -> Result<Self, String> {
+        let mut query = super::pdh::PdhQuery::new()?;
+        let read_counter = query.add_counter("\\LogicalDisk(*)\\Disk Read Bytes/sec")?;
+        let write_counter = query.add_counter("\\LogicalDisk(*)\\Disk Write Bytes/sec")?;
+        // Prime rate counters
+        query.collect()?;
+        Ok(Self {
+            query,
+            read_counter,
+            write_counter,
+        })
+    }
+
+    /// Map of volume key -> (read bytes/sec, write bytes/sec)
+    fn read_rates(&self) -> Result<std::collections::HashMap<String, (u64, u64)>, String> {
+        use std::collections::HashMap;
+
+        self.query.collect()?;
+        let mut rates: HashMap<String, (u64, u64)> = HashMap::new();
+
+        for (instance, value) in self.query.read_array(self.read_counter)? {
+            rates.entry(volume_key(&instance)).or_default().0 = value.max(0.0) as u64;
+        }
+        for (instance, value) in self.query.read_array(self.write_counter)? {
+            rates.entry(volume_key(&instance)).or_default().1 = value.max(0.0) as u64;
+        }
+
+        Ok(rates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_key_matches_mount_point_and_instance() {
+        assert_eq!(volume_key("C:\\"), volume_key("C:"));
+        assert_eq!(volume_key("d:\\"), "D:");
+    }
+
+    fn disk(mount_point: &str, available_bytes: u64) -> DiskMetrics {
+        DiskMetrics {
+            name: String::new(),
+            mount_point: mount_point.to_string(),
+            file_system: String::new(),
+            total_bytes: available_bytes,
+            available_bytes,
+            usage_percent: 0.0,
+            is_removable: false,
+            read_bytes_per_sec: None,
+            write_bytes_per_sec: None,
+        }
+    }
+
+    #[test]
+    fn test_disk_for_path_picks_longest_mount_point() {
+        let disks = vec![disk("/", 1), disk("/home", 2), disk("C:\\", 3)];
+        let available = |path: &str| disk_for_path(&disks, std::path::Path::new(path)).map(|d| d.available_bytes);
+
+        assert_eq!(available("/home/user/file"), Some(2));
+        assert_eq!(available("/homework/file"), Some(1));
+        assert_eq!(available("/var/file"), Some(1));
+        assert_eq!(available("c:\\Users\\file"), Some(3));
+        assert_eq!(available("D:\\file"), None);
+    }
+}
@@ -1,43 +1,93 @@
-// NVIDIA GPU metrics collection using NVML
-use crate::models::performance::GpuMetrics;
+// GPU metrics collection - provider abstraction and the NVML backend
+use crate::models::performance::{GpuMetrics, GpuProviderKind};
+use log::warn;
 use nvml_wrapper::Nvml;
 use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::enum_wrappers::device::Clock;
 use nvml_wrapper::Device;
 
+/// A source of GPU metrics (NVML, Windows performance counters, ...)
+/// The collector holds one provider per backend that initialized successfully
+pub trait GpuProvider: Send {
+    /// Which backend this provider uses
+    fn kind(&self) -> GpuProviderKind;
+
+    /// Collect metrics for every adapter this provider is responsible for
+    fn collect(&self) -> Result<Vec<GpuMetrics>, String>;
+}
+
+/// NVIDIA device discovered at initialization
+struct NvidiaDevice {
+    device_index: u32,
+    cached_name: String,
+    /// "nvml:<UUID>" - unlike the index, the UUID stays with the board across reboots and slot changes
+    adapter_id: Option<String>,
+}
+
 /// NVIDIA GPU wrapper for collecting GPU metrics
-/// Caches the device handle and GPU name to avoid redundant NVML calls
+/// Caches the device indices and GPU names to avoid redundant NVML calls
 pub struct NvidiaGpu {
     nvml: Nvml,
-    device_index: u32,
-    cached_name: String,
+    devices: Vec<NvidiaDevice>,
 }
 
 impl NvidiaGpu {
-    /// Initialize NVML and create a new NvidiaGpu instance
-    /// Caches the GPU name at initialization (never changes at runtime)
+    /// Initialize NVML and enumerate all NVIDIA GPUs
+    /// Caches the GPU names at initialization (never change at runtime)
     /// Returns Err if NVIDIA drivers are not installed or NVML fails to initialize
     pub fn new() -> Result<Self, NvmlError> {
         let nvml = Nvml::init()?;
-        let device = nvml.device_by_index(0)?;
-        let cached_name = device.name()?;
+        let count = nvml.device_count()?;
 
-        Ok(Self {
-            nvml,
-            device_index: 0,
-            cached_name,
-        })
+        // A device that can't be opened or named is skipped so the others are still monitored
+        let mut devices = Vec::new();
+        for device_index in 0..count {
+            let named = nvml
+                .device_by_index(device_index)
+                .and_then(|device| Ok((device.name()?, device.uuid().ok())));
+            match named {
+                Ok((cached_name, uuid)) => devices.push(NvidiaDevice {
+                    device_index,
+                    cached_name,
+                    adapter_id: uuid.map(|uuid| format!("nvml:{}", uuid)),
+                }),
+                Err(e) => warn!("Skipping NVIDIA GPU {}: {}", device_index, e),
+            }
+        }
+        if devices.is_empty() {
+            return Err(NvmlError::NotFound);
+        }
+
+        Ok(Self { nvml, devices })
     }
 
     /// Get the device handle (internal helper)
-    fn get_device(&self) -> Result<Device<'_>, String> {
-        self.nvml.device_by_index(self.device_index)
+    fn get_device(&self, device_index: u32) -> Result<Device<'_>, String> {
+        self.nvml.device_by_index(device_index)
             .map_err(|e| format!("Failed to get GPU device: {}", e))
     }
 
-    /// Collect GPU metrics from the first NVIDIA GPU
-    /// Uses cached name to avoid redundant NVML calls
-    pub fn collect(&self) -> Result<GpuMetrics, String> {
-        let device = self.get_device()?;
+    /// Collect GPU metrics from every NVIDIA GPU
+    /// A device that fails to report is skipped; Err only when none could be read
+    pub fn collect(&self) -> Result<Vec<GpuMetrics>, String> {
+        let mut metrics = Vec::with_capacity(self.devices.len());
+        let mut last_error = None;
+        for device in &self.devices {
+            match self.collect_device(device) {
+                Ok(m) => metrics.push(m),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match (metrics.is_empty(), last_error) {
+            (true, Some(e)) => Err(e),
+            _ => Ok(metrics),
+        }
+    }
+
+    /// Collect metrics for a single device (uses cached name)
+    fn collect_device(&self, nvidia_device: &NvidiaDevice) -> Result<GpuMetrics, String> {
+        let device = self.get_device(nvidia_device.device_index)?;
 
         // Get GPU utilization
         let utilization = device.utilization_rates()
@@ -51,16 +101,60 @@ impl NvidiaGpu {
         let temperature = device.temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
             .ok();
 
+        // Power and fan queries return NotSupported on some GPUs/laptop variants - leave None
+        let power_draw_watts = device.power_usage().ok().map(|mw| mw as f32 / 1000.0);
+        let power_limit_watts = device.enforced_power_limit().ok().map(|mw| mw as f32 / 1000.0);
+        let fan_speed_percent = read_fan_speed(&device);
+        let core_clock_mhz = device.clock_info(Clock::Graphics).ok();
+        let memory_clock_mhz = device.clock_info(Clock::Memory).ok();
+
         Ok(GpuMetrics {
-            name: self.cached_name.clone(),
+            index: 0,
+            name: nvidia_device.cached_name.clone(),
+            adapter_id: nvidia_device.adapter_id.clone(),
             usage_percent: utilization.gpu as f32,
             memory_used_mb: memory_info.used / (1024 * 1024),
             memory_total_mb: memory_info.total / (1024 * 1024),
             temperature_celsius: temperature.map(|t| t as f32),
+            power_draw_watts,
+            power_limit_watts,
+            fan_speed_percent,
+            core_clock_mhz,
+            memory_clock_mhz,
+            provider: GpuProviderKind::Nvml,
         })
     }
 }
 
+/// Average fan speed across all fans (None for fanless/passively cooled or unsupported GPUs)
+fn read_fan_speed(device: &Device<'_>) -> Option<f32> {
+    let fan_count = device.num_fans().unwrap_or(1);
+    let speeds: Vec<u32> = (0..fan_count).filter_map(|i| device.fan_speed(i).ok()).collect();
+    if speeds.is_empty() {
+        None
+    } else {
+        Some(speeds.iter().sum::<u32>() as f32 / speeds.len() as f32)
+    }
+}
+
+impl GpuProvider for NvidiaGpu {
+    fn kind(&self) -> GpuProviderKind {
+        GpuProviderKind::Nvml
+    }
+
+    fn collect(&self) -> Result<Vec<GpuMetrics>, String> {
+        NvidiaGpu::collect(self)
+    }
+}
+
+/// The adapter doing the most work (ties keep the earlier adapter)
+pub fn busiest_gpu(gpus: &[GpuMetrics]) -> Option<&GpuMetrics> {
+    gpus.iter().fold(None, |best: Option<&GpuMetrics>, current| match best {
+        Some(b) if b.usage_percent >= current.usage_percent => Some(b),
+        _ => Some(current),
+    })
+}
+
 impl Drop for NvidiaGpu {
     fn drop(&mut self) {
         // NVML shutdown is handled automatically by the Nvml struct's Drop implementation
@@ -0,0 +1,133 @@
+// Rolling in-memory history of performance snapshots
+// Lets the frontend redraw graphs after a remount without subscribing to every update
+use crate::models::performance::SystemMetrics;
+use std::collections::VecDeque;
+
+/// Default history window
+pub const DEFAULT_HISTORY_MINUTES: u32 = 15;
+/// Largest history window that can be configured
+pub const MAX_HISTORY_MINUTES: u32 = 60;
+/// Hard cap on stored samples, independent of the poll interval, to keep memory bounded
+const MAX_HISTORY_SAMPLES: usize = 7200;
+
+pub struct MetricsHistory {
+    samples: VecDeque<SystemMetrics>,
+    window_ms: i64,
+}
+
+impl MetricsHistory {
+    pub fn new(window_minutes: u32) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            window_ms: window_to_ms(window_minutes),
+        }
+    }
+
+    /// Change the retention window; shrinking it drops the oldest samples immediately
+    pub fn set_window_minutes(&mut self, window_minutes: u32) {
+        self.window_ms = window_to_ms(window_minutes);
+        self.prune();
+    }
+
+    /// Append a sample (per-interface details are stripped to keep entries small)
+    pub fn push(&mut self, metrics: SystemMetrics) {
+        self.samples.push_back(metrics.without_details());
+        self.prune();
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Samples from the last `seconds` (relative to the newest sample), downsampled to at most `max_points`
+    pub fn query(&self, seconds: u32, max_points: u32) -> Vec<SystemMetrics> {
+        let newest = match self.samples.back() {
+            Some(sample) => sample.timestamp,
+            None => return Vec::new(),
+        };
+        let cutoff = newest - seconds as i64 * 1000;
+        let recent: Vec<&SystemMetrics> = self.samples.iter().filter(|s| s.timestamp >= cutoff).collect();
+
+        downsample(&recent, max_points as usize)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    fn prune(&mut self) {
+        if let Some(newest) = self.samples.back().map(|s| s.timestamp) {
+            let cutoff = newest - self.window_ms;
+            while self.samples.front().is_some_and(|s| s.timestamp < cutoff) {
+                self.samples.pop_front();
+            }
+        }
+        while self.samples.len() > MAX_HISTORY_SAMPLES {
+            self.samples.pop_front();
+        }
+    }
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_MINUTES)
+    }
+}
+
+fn window_to_ms(window_minutes: u32) -> i64 {
+    window_minutes.clamp(1, MAX_HISTORY_MINUTES) as i64 * 60 * 1000
+}
+
+/// Pick at most `max_points` evenly spaced items, always keeping the first and last
+/// `max_points == 0` means no limit
+fn downsample<T: Copy>(items: &[T], max_points: usize) -> Vec<T> {
+    if max_points == 0 || items.len() <= max_points {
+        return items.to_vec();
+    }
+    if max_points == 1 {
+        return items.last().copied().into_iter().collect();
+    }
+
+    let last = items.len() - 1;
+    (0..max_points)
+        .map(|i| items[(i * last + (max_points - 1) / 2) / (max_points - 1)])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64) -> SystemMetrics {
+        SystemMetrics {
+            timestamp,
+            ..SystemMetrics::default()
+        }
+    }
+
+    #[test]
+    fn test_downsample_keeps_endpoints() {
+        let items: Vec<u32> = (0..100).collect();
+        let result = downsample(&items, 5);
+        assert_eq!(result.len(), 5);
+        assert_eq!(result.first(), Some(&0));
+        assert_eq!(result.last(), Some(&99));
+
+        assert_eq!(downsample(&items[..3], 5), vec![0, 1, 2]);
+        assert_eq!(downsample(&items, 0).len(), 100);
+    }
+
+    #[test]
+    fn test_history_prunes_outside_window() {
+        let mut history = MetricsHistory::new(1);
+        for second in 0..120 {
+            history.push(sample(second * 1000));
+        }
+        // 60s window relative to the newest sample (119s)
+        let all = history.query(u32::MAX, 0);
+        assert_eq!(all.first().map(|s| s.timestamp), Some(59_000));
+        assert_eq!(all.len(), 61);
+
+        let last_ten = history.query(10, 0);
+        assert_eq!(last_ten.len(), 11);
+    }
+}
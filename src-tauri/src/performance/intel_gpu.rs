@@ -0,0 +1,338 @@
+// Intel GPU (iGPU / Arc) metrics collection using Windows GPU performance counters
+// Intel has no NVML equivalent we can rely on being installed, so adapters are
+// enumerated through D3DKMT and utilization/memory come from the "GPU Engine" and
+// "GPU Adapter Memory" PDH counter sets that Task Manager also uses.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use crate::models::performance::{GpuMetrics, GpuProviderKind};
+use super::gpu::GpuProvider;
+use std::collections::HashMap;
+
+/// Intel adapter discovered at initialization
+struct IntelAdapter {
+    /// LUID key as it appears in PDH instance names (lowercase, e.g. "luid_0x00000000_0x0000d1b7")
+    luid_key: String,
+    name: String,
+    /// PCI ids, since the LUID changes with every boot
+    adapter_id: Option<String>,
+    /// Dedicated + shared memory budget in bytes
+    memory_total_bytes: u64,
+}
+
+/// Intel GPU provider backed by Windows performance counters
+pub struct IntelGpu {
+    adapters: Vec<IntelAdapter>,
+    #[cfg(windows)]
+    query: super::pdh::PdhQuery,
+    #[cfg(windows)]
+    engine_counter: usize,
+    #[cfg(windows)]
+    dedicated_counter: usize,
+    #[cfg(windows)]
+    shared_counter: usize,
+}
+
+impl IntelGpu {
+    /// Enumerate Intel adapters and open the PDH counters
+    /// Returns Err if no Intel adapter is present or the counters are unavailable
+    #[cfg(windows)]
+    pub fn new() -> Result<Self, String> {
+        use super::pdh::PdhQuery;
+
+        let adapters = enumerate_intel_adapters()?;
+        if adapters.is_empty() {
+            return Err("No Intel GPU detected".to_string());
+        }
+
+        let mut query = PdhQuery::new()?;
+        let engine_counter = query.add_counter("\\GPU Engine(*)\\Utilization Percentage")?;
+        let dedicated_counter = query.add_counter("\\GPU Adapter Memory(*)\\Dedicated Usage")?;
+        let shared_counter = query.add_counter("\\GPU Adapter Memory(*)\\Shared Usage")?;
+
+        // Prime rate counters so the first real collection returns valid data
+        query.collect()?;
+
+        Ok(Self {
+            adapters,
+            query,
+            engine_counter,
+            dedicated_counter,
+            shared_counter,
+        })
+    }
+
+    #[cfg(not(windows))]
+    pub fn new() -> Result<Self, String> {
+        Err("Intel GPU metrics are only supported on Windows".to_string())
+    }
+
+    /// Collect metrics for every Intel adapter
+    #[cfg(windows)]
+    fn collect_all(&self) -> Result<Vec<GpuMetrics>, String> {
+        self.query.collect()?;
+
+        let engine_samples = self.query.read_array(self.engine_counter)?;
+        let dedicated_samples = self.query.read_array(self.dedicated_counter).unwrap_or_default();
+        let shared_samples = self.query.read_array(self.shared_counter).unwrap_or_default();
+
+        let usage_by_adapter = aggregate_3d_utilization(&engine_samples);
+        let memory_by_adapter = aggregate_memory_usage(&dedicated_samples, &shared_samples);
+
+        Ok(self
+            .adapters
+            .iter()
+            .map(|adapter| {
+                let usage = usage_by_adapter.get(&adapter.luid_key).copied().unwrap_or(0.0);
+                let used_bytes = memory_by_adapter.get(&adapter.luid_key).copied().unwrap_or(0);
+                GpuMetrics {
+                    index: 0,
+                    name: adapter.name.clone(),
+                    adapter_id: adapter.adapter_id.clone(),
+                    usage_percent: usage.min(100.0) as f32,
+                    memory_used_mb: used_bytes / (1024 * 1024),
+                    memory_total_mb: adapter.memory_total_bytes / (1024 * 1024),
+                    // Temperature is not exposed through the performance counters
+                    temperature_celsius: None,
+                    power_draw_watts: None,
+                    power_limit_watts: None,
+                    fan_speed_percent: None,
+                    core_clock_mhz: None,
+                    memory_clock_mhz: None,
+                    provider: GpuProviderKind::WindowsCounters,
+                }
+            })
+            .collect())
+    }
+
+    #[cfg(not(windows))]
+    fn collect_all(&self) -> Result<Vec<GpuMetrics>, String> {
+        Err("Intel GPU metrics are only supported on Windows".to_string())
+    }
+}
+
+impl GpuProvider for IntelGpu {
+    fn kind(&self) -> GpuProviderKind {
+        GpuProviderKind::WindowsCounters
+    }
+
+    fn collect(&self) -> Result<Vec<GpuMetrics>, String> {
+        self.collect_all()
+    }
+}
+
+/// Enumerate display adapters through D3DKMT and keep the Intel ones
+#[cfg(windows)]
+fn enumerate_intel_adapters() -> Result<Vec<IntelAdapter>, String> {
+    use windows_sys::Wdk::Graphics::Direct3D::{
+        D3DKMTCloseAdapter, D3DKMTEnumAdapters2, D3DKMTQueryAdapterInfo, D3DKMT_ADAPTERINFO,
+        D3DKMT_ADAPTERREGISTRYINFO, D3DKMT_CLOSEADAPTER, D3DKMT_ENUMADAPTERS2,
+        D3DKMT_QUERYADAPTERINFO, D3DKMT_QUERY_DEVICE_IDS, D3DKMT_SEGMENTSIZEINFO, KMTQAITYPE_ADAPTERREGISTRYINFO,
+        KMTQAITYPE_GETSEGMENTSIZE, KMTQAITYPE_PHYSICALADAPTERDEVICEIDS,
+    };
+
+    let mut enum_args = D3DKMT_ENUMADAPTERS2 {
+        NumAdapters: 0,
+        pAdapters: std::ptr::null_mut(),
+    };
+
+    // First call returns the adapter count
+    let status = unsafe { D3DKMTEnumAdapters2(&mut enum_args) };
+    if status < 0 {
+        return Err(format!("D3DKMTEnumAdapters2 failed: 0x{:08X}", status));
+    }
+
+    let mut adapter_infos: Vec<D3DKMT_ADAPTERINFO> =
+        vec![unsafe { std::mem::zeroed() }; enum_args.NumAdapters as usize];
+    enum_args.pAdapters = adapter_infos.as_mut_ptr();
+
+    let status = unsafe { D3DKMTEnumAdapters2(&mut enum_args) };
+    if status < 0 {
+        return Err(format!("D3DKMTEnumAdapters2 failed: 0x{:08X}", status));
+    }
+    adapter_infos.truncate(enum_args.NumAdapters as usize);
+
+    let mut adapters = Vec::new();
+
+    for info in &adapter_infos {
+        let mut registry_info: D3DKMT_ADAPTERREGISTRYINFO = unsafe { std::mem::zeroed() };
+        let mut query = D3DKMT_QUERYADAPTERINFO {
+            hAdapter: info.hAdapter,
+            Type: KMTQAITYPE_ADAPTERREGISTRYINFO,
+            pPrivateDriverData: &mut registry_info as *mut _ as *mut core::ffi::c_void,
+            PrivateDriverDataSize: std::mem::size_of::<D3DKMT_ADAPTERREGISTRYINFO>() as u32,
+        };
+        let name = if unsafe { D3DKMTQueryAdapterInfo(&mut query) } >= 0 {
+            utf16_until_nul(&registry_info.AdapterString)
+        } else {
+            String::new()
+        };
+
+        let mut segment_info: D3DKMT_SEGMENTSIZEINFO = unsafe { std::mem::zeroed() };
+        let mut query = D3DKMT_QUERYADAPTERINFO {
+            hAdapter: info.hAdapter,
+            Type: KMTQAITYPE_GETSEGMENTSIZE,
+            pPrivateDriverData: &mut segment_info as *mut _ as *mut core::ffi::c_void,
+            PrivateDriverDataSize: std::mem::size_of::<D3DKMT_SEGMENTSIZEINFO>() as u32,
+        };
+        let memory_total_bytes = if unsafe { D3DKMTQueryAdapterInfo(&mut query) } >= 0 {
+            segment_info.DedicatedVideoMemorySize + segment_info.SharedSystemMemorySize
+        } else {
+            0
+        };
+
+        let mut device_ids: D3DKMT_QUERY_DEVICE_IDS = unsafe { std::mem::zeroed() };
+        let mut query = D3DKMT_QUERYADAPTERINFO {
+            hAdapter: info.hAdapter,
+            Type: KMTQAITYPE_PHYSICALADAPTERDEVICEIDS,
+            pPrivateDriverData: &mut device_ids as *mut _ as *mut core::ffi::c_void,
+            PrivateDriverDataSize: std::mem::size_of::<D3DKMT_QUERY_DEVICE_IDS>() as u32,
+        };
+        let adapter_id = (unsafe { D3DKMTQueryAdapterInfo(&mut query) } >= 0).then(|| {
+            let ids = &device_ids.DeviceIds;
+            format_pci_adapter_id(ids.VendorID, ids.DeviceID, ids.SubSystemID)
+        });
+
+        let close = D3DKMT_CLOSEADAPTER { hAdapter: info.hAdapter };
+        unsafe { D3DKMTCloseAdapter(&close) };
+
+        if is_intel_adapter(&name) {
+            adapters.push(IntelAdapter {
+                luid_key: format_luid_key(info.AdapterLuid.HighPart as u32, info.AdapterLuid.LowPart),
+                name,
+                adapter_id,
+                memory_total_bytes,
+            });
+        }
+    }
+
+    Ok(adapters)
+}
+
+#[cfg(windows)]
+fn utf16_until_nul(buf: &[u16]) -> String {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..len])
+}
+
+fn is_intel_adapter(name: &str) -> bool {
+    name.to_lowercase().contains("intel")
+}
+
+/// Adapter id from PCI ids, e.g. "pci:8086:a7a0:0b251028"
+fn format_pci_adapter_id(vendor_id: u32, device_id: u32, subsystem_id: u32) -> String {
+    format!("pci:{:04x}:{:04x}:{:08x}", vendor_id, device_id, subsystem_id)
+}
+
+/// Format a LUID the way PDH GPU instance names do
+fn format_luid_key(high: u32, low: u32) -> String {
+    format!("luid_0x{:08x}_0x{:08x}", high, low)
+}
+
+/// Extract the lowercase LUID key from a PDH GPU instance name
+/// e.g. "pid_1234_luid_0x00000000_0x0000D1B7_phys_0_eng_3_engtype_3D" -> "luid_0x00000000_0x0000d1b7"
+fn parse_luid_key(instance: &str) -> Option<String> {
+    let lower = instance.to_lowercase();
+    let start = lower.find("luid_")?;
+    let parts: Vec<&str> = lower[start..].splitn(4, '_').collect();
+    if parts.len() < 3 || !parts[1].starts_with("0x") || !parts[2].starts_with("0x") {
+        return None;
+    }
+    Some(format!("luid_{}_{}", parts[1], parts[2]))
+}
+
+/// Extract the per-adapter engine key (LUID + physical adapter + engine index)
+/// so that per-process samples for the same engine can be summed
+fn parse_engine_key(instance: &str) -> Option<String> {
+    let lower = instance.to_lowercase();
+    let start = lower.find("luid_")?;
+    let end = lower.find("_engtype_").unwrap_or(lower.len());
+    if end <= start {
+        return None;
+    }
+    Some(lower[start..end].to_string())
+}
+
+/// Sum 3D engine utilization across processes per engine, then take the busiest
+/// engine per adapter (same method Task Manager uses for its "3D" graph)
+fn aggregate_3d_utilization(samples: &[(String, f64)]) -> HashMap<String, f64> {
+    let mut per_engine: HashMap<String, f64> = HashMap::new();
+
+    for (instance, value) in samples {
+        if !instance.to_lowercase().ends_with("engtype_3d") {
+            continue;
+        }
+        if let Some(engine) = parse_engine_key(instance) {
+            *per_engine.entry(engine).or_insert(0.0) += value.max(0.0);
+        }
+    }
+
+    let mut per_adapter: HashMap<String, f64> = HashMap::new();
+    for (engine, usage) in per_engine {
+        if let Some(luid) = parse_luid_key(&engine) {
+            let entry = per_adapter.entry(luid).or_insert(0.0);
+            if usage > *entry {
+                *entry = usage;
+            }
+        }
+    }
+
+    per_adapter
+}
+
+/// Sum dedicated and shared memory usage per adapter (bytes)
+fn aggregate_memory_usage(dedicated: &[(String, f64)], shared: &[(String, f64)]) -> HashMap<String, u64> {
+    let mut per_adapter: HashMap<String, u64> = HashMap::new();
+
+    for (instance, value) in dedicated.iter().chain(shared.iter()) {
+        if let Some(luid) = parse_luid_key(instance) {
+            *per_adapter.entry(luid).or_insert(0) += value.max(0.0) as u64;
+        }
+    }
+
+    per_adapter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_luid_key() {
+        assert_eq!(
+            parse_luid_key("pid_1234_luid_0x00000000_0x0000D1B7_phys_0_eng_3_engtype_3D"),
+            Some("luid_0x00000000_0x0000d1b7".to_string())
+        );
+        assert_eq!(
+            parse_luid_key("luid_0x00000000_0x0000D1B7_phys_0"),
+            Some("luid_0x00000000_0x0000d1b7".to_string())
+        );
+        assert_eq!(parse_luid_key("_Total"), None);
+        assert_eq!(format_luid_key(0, 0xD1B7), "luid_0x00000000_0x0000d1b7");
+    }
+
+    #[test]
+    fn test_format_pci_adapter_id() {
+        assert_eq!(format_pci_adapter_id(0x8086, 0xA7A0, 0x0B25_1028), "pci:8086:a7a0:0b251028");
+    }
+
+    #[test]
+    fn test_aggregate_3d_utilization_sums_processes_per_engine() {
+        let samples = vec![
+            ("pid_1_luid_0x00000000_0x0000AAAA_phys_0_eng_0_engtype_3D".to_string(), 30.0),
+            ("pid_2_luid_0x00000000_0x0000AAAA_phys_0_eng_0_engtype_3D".to_string(), 25.0),
+            ("pid_2_luid_0x00000000_0x0000AAAA_phys_0_eng_1_engtype_VideoDecode".to_string(), 90.0),
+            ("pid_3_luid_0x00000000_0x0000BBBB_phys_0_eng_0_engtype_3D".to_string(), 10.0),
+        ];
+
+        let usage = aggregate_3d_utilization(&samples);
+        assert_eq!(usage.get("luid_0x00000000_0x0000aaaa"), Some(&55.0));
+        assert_eq!(usage.get("luid_0x00000000_0x0000bbbb"), Some(&10.0));
+    }
+
+    #[test]
+    fn test_is_intel_adapter() {
+        assert!(is_intel_adapter("Intel(R) Arc(TM) A770 Graphics"));
+        assert!(is_intel_adapter("Intel(R) UHD Graphics 770"));
+        assert!(!is_intel_adapter("NVIDIA GeForce RTX 4070"));
+    }
+}
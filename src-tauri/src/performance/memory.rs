@@ -0,0 +1,142 @@
+// Detailed memory breakdown - commit charge and standby cache
+// `used`/`available` alone can't tell real memory pressure apart from a large standby cache
+
+#[cfg_attr(not(windows), allow(dead_code))]
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Memory details that sysinfo does not expose
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryDetails {
+    pub committed_mb: Option<u64>,
+    pub commit_limit_mb: Option<u64>,
+    pub standby_cache_mb: Option<u64>,
+}
+
+pub struct MemoryTracker {
+    #[cfg(windows)]
+    standby: Option<StandbyCounters>,
+}
+
+impl MemoryTracker {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(windows)]
+            standby: StandbyCounters::new().ok(),
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn sample(&self) -> MemoryDetails {
+        use windows_sys::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+        let mut details = MemoryDetails::default();
+
+        let mut status: MEMORYSTATUSEX = unsafe { std::mem::zeroed() };
+        status.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+        if unsafe { GlobalMemoryStatusEx(&mut status) } != 0 {
+            // The "page file" fields are the system commit limit and remaining commit
+            details.commit_limit_mb = Some(status.ullTotalPageFile / BYTES_PER_MB);
+            details.committed_mb =
+                Some(status.ullTotalPageFile.saturating_sub(status.ullAvailPageFile) / BYTES_PER_MB);
+        }
+
+        details.standby_cache_mb = self
+            .standby
+            .as_ref()
+            .and_then(|counters| counters.read_bytes().ok())
+            .map(|bytes| bytes / BYTES_PER_MB);
+
+        details
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn sample(&self) -> MemoryDetails {
+        std::fs::read_to_string("/proc/meminfo")
+            .map(|content| parse_meminfo(&content))
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(any(windows, target_os = "linux")))]
+    pub fn sample(&self) -> MemoryDetails {
+        MemoryDetails::default()
+    }
+}
+
+impl Default for MemoryTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse Committed_AS / CommitLimit / Cached (kB values) from /proc/meminfo
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_meminfo(content: &str) -> MemoryDetails {
+    let mut details = MemoryDetails::default();
+    for line in content.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(kb) = rest.split_whitespace().next().and_then(|v| v.parse::<u64>().ok()) else {
+            continue;
+        };
+        let mb = Some(kb / 1024);
+        match key {
+            "Committed_AS" => details.committed_mb = mb,
+            "CommitLimit" => details.commit_limit_mb = mb,
+            "Cached" => details.standby_cache_mb = mb,
+            _ => {}
+        }
+    }
+    details
+}
+
+/// Standby list size from the Memory performance counters (sum of all priorities)
+#[cfg(windows)]
+struct StandbyCounters {
+    query: super::pdh::PdhQuery,
+    counters: Vec<usize>,
+}
+
+#[cfg(windows)]
+impl StandbyCounters {
+    fn new() -> Result<Self, String> {
+        let mut query = super::pdh::PdhQuery::new()?;
+        let counters = vec![
+            query.add_counter("\\Memory\\Standby Cache Core Bytes")?,
+            query.add_counter("\\Memory\\Standby Cache Normal Priority Bytes")?,
+            query.add_counter("\\Memory\\Standby Cache Reserve Bytes")?,
+        ];
+        Ok(Self { query, counters })
+    }
+
+    fn read_bytes(&self) -> Result<u64, String> {
+        self.query.collect()?;
+        let mut total = 0u64;
+        for counter in &self.counters {
+            total += self
+                .query
+                .read_array(*counter)?
+                .iter()
+                .map(|(_, value)| value.max(0.0) as u64)
+                .sum::<u64>();
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let content = "MemTotal:       32768000 kB\n\
+                       Cached:          4194304 kB\n\
+                       CommitLimit:    20480000 kB\n\
+                       Committed_AS:   10240000 kB\n";
+        let details = parse_meminfo(content);
+        assert_eq!(details.standby_cache_mb, Some(4096));
+        assert_eq!(details.commit_limit_mb, Some(20000));
+        assert_eq!(details.committed_mb, Some(10000));
+    }
+}
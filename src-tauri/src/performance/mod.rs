@@ -1,8 +1,21 @@
+pub mod alerts;
+pub mod capture;
 pub mod collector;
+pub mod cpu_topology;
+pub mod disk;
 pub mod gpu;
+pub mod history;
+pub mod intel_gpu;
+pub mod memory;
+pub mod network;
+pub mod power;
+#[cfg(windows)]
+pub mod pdh;
 
 pub use collector::{
-    get_snapshot, start_monitoring, stop_monitoring, MonitoringState,
+    detect_gpus, get_snapshot, start_monitoring, stop_monitoring, MonitoringState,
     SharedMetrics,
 };
-pub use gpu::is_nvidia_available;
+pub use disk::{available_space_for, list_disk_space};
+pub use gpu::{busiest_gpu, is_nvidia_available};
+pub use power::read_power_status;
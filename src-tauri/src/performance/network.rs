@@ -0,0 +1,134 @@
+// Network throughput tracking
+// Rates are computed from cumulative interface counters so that interfaces appearing or
+// disappearing between refreshes (VPN up/down, Wi-Fi reconnects) never produce bogus spikes
+use crate::models::performance::{NetworkInterfaceMetrics, NetworkMetrics};
+use std::collections::HashMap;
+use std::time::Instant;
+use sysinfo::Networks;
+
+/// Re-enumerate interfaces every N samples; plain refreshes in between are cheaper
+const INTERFACE_RELIST_EVERY: u32 = 10;
+
+pub struct NetworkTracker {
+    networks: Networks,
+    /// Cumulative (received, transmitted) bytes per interface at the previous sample
+    previous: HashMap<String, (u64, u64)>,
+    last_sample: Instant,
+    samples_since_relist: u32,
+}
+
+impl NetworkTracker {
+    /// Create a tracker and take the baseline sample
+    pub fn new() -> Self {
+        let networks = Networks::new_with_refreshed_list();
+        let previous = read_totals(&networks);
+
+        Self {
+            networks,
+            previous,
+            last_sample: Instant::now(),
+            samples_since_relist: 0,
+        }
+    }
+
+    /// Refresh counters and compute rates since the previous sample
+    pub fn sample(&mut self) -> NetworkMetrics {
+        self.samples_since_relist += 1;
+        if self.samples_since_relist >= INTERFACE_RELIST_EVERY {
+            self.networks.refresh_list();
+            self.samples_since_relist = 0;
+        } else {
+            self.networks.refresh();
+        }
+
+        let now = Instant::now();
+        let elapsed_secs = now.duration_since(self.last_sample).as_secs_f64();
+        self.last_sample = now;
+
+        let current = read_totals(&self.networks);
+        let interfaces = compute_interface_rates(&self.previous, &current, elapsed_secs);
+        self.previous = current;
+
+        NetworkMetrics {
+            download_bytes_per_sec: interfaces.iter().map(|i| i.download_bytes_per_sec).sum(),
+            upload_bytes_per_sec: interfaces.iter().map(|i| i.upload_bytes_per_sec).sum(),
+            interfaces: Some(interfaces),
+        }
+    }
+}
+
+impl Default for NetworkTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_totals(networks: &Networks) -> HashMap<String, (u64, u64)> {
+    networks
+        .iter()
+        .map(|(name, data)| (name.clone(), (data.total_received(), data.total_transmitted())))
+        .collect()
+}
+
+/// Compute per-interface rates from two sets of cumulative counters
+/// Interfaces without a previous sample (just appeared) are skipped for this cycle,
+/// and counters that went backwards (adapter reset) report zero instead of a negative rate
+fn compute_interface_rates(
+    previous: &HashMap<String, (u64, u64)>,
+    current: &HashMap<String, (u64, u64)>,
+    elapsed_secs: f64,
+) -> Vec<NetworkInterfaceMetrics> {
+    if elapsed_secs <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut interfaces: Vec<NetworkInterfaceMetrics> = current
+        .iter()
+        .filter_map(|(name, (rx, tx))| {
+            let (prev_rx, prev_tx) = previous.get(name)?;
+            Some(NetworkInterfaceMetrics {
+                name: name.clone(),
+                download_bytes_per_sec: (rx.saturating_sub(*prev_rx) as f64 / elapsed_secs) as u64,
+                upload_bytes_per_sec: (tx.saturating_sub(*prev_tx) as f64 / elapsed_secs) as u64,
+            })
+        })
+        .collect();
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_from_counter_deltas() {
+        let previous = HashMap::from([("Ethernet".to_string(), (1_000, 500))]);
+        let current = HashMap::from([("Ethernet".to_string(), (3_000, 1_500))]);
+
+        let rates = compute_interface_rates(&previous, &current, 2.0);
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].download_bytes_per_sec, 1_000);
+        assert_eq!(rates[0].upload_bytes_per_sec, 500);
+    }
+
+    #[test]
+    fn test_new_and_removed_interfaces_are_ignored() {
+        let previous = HashMap::from([("Wi-Fi".to_string(), (100, 100))]);
+        let current = HashMap::from([("VPN".to_string(), (9_999_999, 9_999_999))]);
+
+        let rates = compute_interface_rates(&previous, &current, 1.0);
+        assert!(rates.is_empty());
+    }
+
+    #[test]
+    fn test_counter_reset_does_not_go_negative() {
+        let previous = HashMap::from([("Ethernet".to_string(), (5_000, 5_000))]);
+        let current = HashMap::from([("Ethernet".to_string(), (10, 20))]);
+
+        let rates = compute_interface_rates(&previous, &current, 1.0);
+        assert_eq!(rates[0].download_bytes_per_sec, 0);
+        assert_eq!(rates[0].upload_bytes_per_sec, 0);
+    }
+}
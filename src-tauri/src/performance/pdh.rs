@@ -0,0 +1,142 @@
+// Thin wrapper around the Windows Performance Data Helper (PDH) API
+// Used for metrics that have no vendor SDK (GPU engine counters, etc.)
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use windows_sys::Win32::System::Performance::{
+    PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+    PdhOpenQueryW, PDH_CSTATUS_NEW_DATA, PDH_CSTATUS_VALID_DATA, PDH_FMT_COUNTERVALUE_ITEM_W,
+    PDH_FMT_DOUBLE, PDH_MORE_DATA,
+};
+
+const ERROR_SUCCESS: u32 = 0;
+
+fn to_wide(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// An open PDH query with a set of wildcard counters
+/// Rate counters need two collections before they return meaningful values,
+/// so callers should collect once right after adding counters
+pub struct PdhQuery {
+    query: isize,
+    counters: Vec<isize>,
+}
+
+impl PdhQuery {
+    /// Open a new real-time PDH query
+    pub fn new() -> Result<Self, String> {
+        let mut query: isize = 0;
+        let status = unsafe { PdhOpenQueryW(std::ptr::null(), 0, &mut query) };
+        if status != ERROR_SUCCESS {
+            return Err(format!("PdhOpenQueryW failed: 0x{:08X}", status));
+        }
+
+        Ok(Self {
+            query,
+            counters: Vec::new(),
+        })
+    }
+
+    /// Add an English counter path (e.g. "\\GPU Engine(*)\\Utilization Percentage")
+    /// Returns the counter index used with `read_array`
+    pub fn add_counter(&mut self, path: &str) -> Result<usize, String> {
+        let wide_path = to_wide(path);
+        let mut counter: isize = 0;
+        let status = unsafe { PdhAddEnglishCounterW(self.query, wide_path.as_ptr(), 0, &mut counter) };
+        if status != ERROR_SUCCESS {
+            return Err(format!("PdhAddEnglishCounterW failed for {}: 0x{:08X}", path, status));
+        }
+
+        self.counters.push(counter);
+        Ok(self.counters.len() - 1)
+    }
+
+    /// Sample all counters in the query
+    pub fn collect(&self) -> Result<(), String> {
+        let status = unsafe { PdhCollectQueryData(self.query) };
+        if status != ERROR_SUCCESS {
+            return Err(format!("PdhCollectQueryData failed: 0x{:08X}", status));
+        }
+        Ok(())
+    }
+
+    /// Read every instance of a wildcard counter as (instance name, value) pairs
+    pub fn read_array(&self, index: usize) -> Result<Vec<(String, f64)>, String> {
+        let counter = *self
+            .counters
+            .get(index)
+            .ok_or_else(|| format!("Invalid PDH counter index: {}", index))?;
+
+        let mut buffer_size: u32 = 0;
+        let mut item_count: u32 = 0;
+
+        // First call reports the required buffer size
+        let status = unsafe {
+            PdhGetFormattedCounterArrayW(
+                counter,
+                PDH_FMT_DOUBLE,
+                &mut buffer_size,
+                &mut item_count,
+                std::ptr::null_mut(),
+            )
+        };
+        if status != PDH_MORE_DATA {
+            if status == ERROR_SUCCESS {
+                return Ok(Vec::new());
+            }
+            return Err(format!("PdhGetFormattedCounterArrayW failed: 0x{:08X}", status));
+        }
+
+        // Buffer holds the item array followed by the instance name strings
+        let item_size = std::mem::size_of::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+        let mut buffer: Vec<PDH_FMT_COUNTERVALUE_ITEM_W> =
+            Vec::with_capacity((buffer_size as usize).div_ceil(item_size));
+
+        let status = unsafe {
+            PdhGetFormattedCounterArrayW(
+                counter,
+                PDH_FMT_DOUBLE,
+                &mut buffer_size,
+                &mut item_count,
+                buffer.as_mut_ptr(),
+            )
+        };
+        if status != ERROR_SUCCESS {
+            return Err(format!("PdhGetFormattedCounterArrayW failed: 0x{:08X}", status));
+        }
+
+        let items = unsafe { std::slice::from_raw_parts(buffer.as_ptr(), item_count as usize) };
+
+        Ok(items
+            .iter()
+            .filter(|item| {
+                item.FmtValue.CStatus == PDH_CSTATUS_VALID_DATA
+                    || item.FmtValue.CStatus == PDH_CSTATUS_NEW_DATA
+            })
+            .map(|item| {
+                let name = unsafe { wide_ptr_to_string(item.szName) };
+                let value = unsafe { item.FmtValue.Anonymous.doubleValue };
+                (name, value)
+            })
+            .collect())
+    }
+}
+
+impl Drop for PdhQuery {
+    fn drop(&mut self) {
+        unsafe {
+            PdhCloseQuery(self.query);
+        }
+    }
+}
+
+unsafe fn wide_ptr_to_string(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len))
+}
@@ -0,0 +1,189 @@
+// Battery and power plan status
+// These change rarely, so the monitoring loop only re-reads them every few seconds
+use crate::models::performance::PowerStatus;
+use std::time::{Duration, Instant};
+
+/// How often the monitoring loop re-reads power status
+const POWER_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct PowerTracker {
+    cached: PowerStatus,
+    last_refresh: Option<Instant>,
+}
+
+impl PowerTracker {
+    pub fn new() -> Self {
+        Self {
+            cached: PowerStatus::default(),
+            last_refresh: None,
+        }
+    }
+
+    /// Cached power status, refreshed when older than the refresh interval
+    pub fn sample(&mut self) -> PowerStatus {
+        let stale = match self.last_refresh {
+            Some(last) => last.elapsed() >= POWER_REFRESH_INTERVAL,
+            None => true,
+        };
+        if stale {
+            self.cached = read_power_status();
+            self.last_refresh = Some(Instant::now());
+        }
+        self.cached.clone()
+    }
+}
+
+impl Default for PowerTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read the current battery and power plan status
+#[cfg(windows)]
+pub fn read_power_status() -> PowerStatus {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut raw: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    let ok = unsafe { GetSystemPowerStatus(&mut raw) };
+
+    let mut status = PowerStatus {
+        power_plan: read_active_power_plan(),
+        ..Default::default()
+    };
+    if ok == 0 {
+        return status;
+    }
+
+    // 255 / u32::MAX mean "unknown"; BatteryFlag 128 means no system battery
+    status.on_ac_power = match raw.ACLineStatus {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    };
+    status.has_battery = raw.BatteryFlag != 128 && raw.BatteryFlag != 255;
+    if status.has_battery {
+        status.battery_percent = (raw.BatteryLifePercent <= 100).then_some(raw.BatteryLifePercent);
+        status.is_charging = raw.BatteryFlag & 8 != 0;
+        status.battery_seconds_remaining = (raw.BatteryLifeTime != u32::MAX).then_some(raw.BatteryLifeTime);
+    }
+
+    status
+}
+
+/// Friendly name of the active power plan (Balanced, High performance, ...)
+#[cfg(windows)]
+fn read_active_power_plan() -> Option<String> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let schemes = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey("SYSTEM\\CurrentControlSet\\Control\\Power\\User\\PowerSchemes")
+        .ok()?;
+    let active: String = schemes.get_value("ActivePowerScheme").ok()?;
+
+    schemes
+        .open_subkey(&active)
+        .ok()
+        .and_then(|scheme| scheme.get_value::<String, _>("FriendlyName").ok())
+        .and_then(|raw| parse_friendly_name(&raw))
+        .or_else(|| well_known_plan_name(&active).map(str::to_string))
+}
+
+/// Read the current battery status from sysfs (power plans are a Windows concept)
+#[cfg(target_os = "linux")]
+pub fn read_power_status() -> PowerStatus {
+    use std::fs;
+
+    let mut status = PowerStatus::default();
+    let entries = match fs::read_dir("/sys/class/power_supply") {
+        Ok(entries) => entries,
+        Err(_) => return status,
+    };
+
+    let read = |path: &std::path::Path, name: &str| {
+        fs::read_to_string(path.join(name)).ok().map(|s| s.trim().to_string())
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read(&path, "type").as_deref() {
+            Some("Mains") => {
+                if let Some(online) = read(&path, "online") {
+                    status.on_ac_power = Some(status.on_ac_power.unwrap_or(false) || online == "1");
+                }
+            }
+            Some("Battery") if !status.has_battery => {
+                status.has_battery = true;
+                status.battery_percent = read(&path, "capacity").and_then(|c| c.parse::<u8>().ok());
+                let state = read(&path, "status").unwrap_or_default();
+                status.is_charging = state == "Charging";
+
+                // energy_now (uWh) / power_now (uW) gives hours remaining while discharging
+                if state == "Discharging" {
+                    let energy = read(&path, "energy_now").and_then(|v| v.parse::<f64>().ok());
+                    let power = read(&path, "power_now").and_then(|v| v.parse::<f64>().ok());
+                    if let (Some(energy), Some(power)) = (energy, power) {
+                        if power > 0.0 {
+                            status.battery_seconds_remaining = Some((energy / power * 3600.0) as u32);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    status
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn read_power_status() -> PowerStatus {
+    PowerStatus::default()
+}
+
+/// Extract the display name from a FriendlyName registry value
+/// Built-in plans store an indirect string like "@%SystemRoot%\system32\powrprof.dll,-12,High performance"
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_friendly_name(raw: &str) -> Option<String> {
+    let name = if raw.starts_with('@') {
+        raw.rsplit(',').next()?
+    } else {
+        raw
+    };
+    let name = name.trim().trim_end_matches('\0');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Names for the built-in power scheme GUIDs
+#[cfg_attr(not(windows), allow(dead_code))]
+fn well_known_plan_name(guid: &str) -> Option<&'static str> {
+    match guid.trim_matches(['{', '}']).to_lowercase().as_str() {
+        "381b4222-f694-41f0-9685-ff5bb260df2e" => Some("Balanced"),
+        "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c" => Some("High performance"),
+        "a1841308-3541-4fab-bc81-f71556f20b4a" => Some("Power saver"),
+        "e9a42b02-d5df-448d-aa00-03f14749eb61" => Some("Ultimate Performance"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_friendly_name() {
+        assert_eq!(
+            parse_friendly_name("@%SystemRoot%\\system32\\powrprof.dll,-12,High performance"),
+            Some("High performance".to_string())
+        );
+        assert_eq!(parse_friendly_name("My Gaming Plan"), Some("My Gaming Plan".to_string()));
+        assert_eq!(parse_friendly_name(""), None);
+    }
+
+    #[test]
+    fn test_well_known_plan_name() {
+        assert_eq!(well_known_plan_name("381B4222-F694-41F0-9685-FF5BB260DF2E"), Some("Balanced"));
+        assert_eq!(well_known_plan_name("00000000-0000-0000-0000-000000000000"), None);
+    }
+}
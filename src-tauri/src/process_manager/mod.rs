@@ -1,4 +1,6 @@
 // Python worker process management
+pub mod persistent_worker;
 pub mod python_worker;
 
+pub use persistent_worker::*;
 pub use python_worker::*;
@@ -0,0 +1,255 @@
+// Long-running Python workers
+// Unlike `spawn_python_worker_async`, which writes one input and waits for one result, a persistent
+// worker keeps stdin open: every request is a JSON line `{ id, action, params }`, answered by a
+// `result` or `error` line with the same id. Progress and output lines carrying an id go to that
+// request's progress channel; lines without one are logs, or events the worker raises on its own
+// (e.g. a dropped connection), which go to the event channel. A request dropped before its answer
+// is cancelled in the worker. Dropping the worker kills the process
+use log::{debug, error, info, warn};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin};
+use tokio::sync::{mpsc, oneshot};
+
+use super::{worker_command, WorkerMessage};
+
+struct PendingRequest {
+    answer: oneshot::Sender<Result<serde_json::Value, String>>,
+    progress: Option<mpsc::Sender<WorkerMessage>>,
+}
+
+type PendingRequests = Arc<Mutex<HashMap<u64, PendingRequest>>>;
+type SharedStdin = Arc<tokio::sync::Mutex<ChildStdin>>;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PersistentWorkerMessage {
+    Result {
+        id: u64,
+        data: serde_json::Value,
+    },
+    Error {
+        id: Option<u64>,
+        message: String,
+    },
+    Log {
+        #[serde(default)]
+        id: Option<u64>,
+        level: String,
+        message: String,
+    },
+    Progress {
+        #[serde(default)]
+        id: Option<u64>,
+        percent: u8,
+        #[serde(default)]
+        stage: String,
+    },
+    Event {
+        name: String,
+        #[serde(default)]
+        data: serde_json::Value,
+    },
+}
+
+/// Something a persistent worker reported on its own
+#[derive(Debug, Clone)]
+pub struct WorkerEvent {
+    pub name: String,
+    pub data: serde_json::Value,
+}
+
+/// Name of the event sent when the worker process ends
+pub const WORKER_EXITED_EVENT: &str = "worker_exited";
+
+pub struct PersistentWorker {
+    script: String,
+    stdin: SharedStdin,
+    pending: PendingRequests,
+    next_id: AtomicU64,
+    running: Arc<AtomicBool>,
+    _child: Child,
+}
+
+impl PersistentWorker {
+    /// Start `script`; its events, and `WORKER_EXITED_EVENT` when it ends, go to `events`
+    pub fn spawn(script: &str, events: mpsc::Sender<WorkerEvent>) -> Result<Self, String> {
+        let mut child = worker_command(script)?
+            .spawn()
+            .map_err(|e| format!("Failed to spawn Python process: {}", e))?;
+
+        let stdin = child.stdin.take().ok_or("Failed to capture stdin")?;
+        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+        let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+
+        let script_name = script.to_string();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if !line.trim().is_empty() {
+                    warn!(target: "python_worker", "[{}] stderr: {}", script_name, line);
+                }
+            }
+        });
+
+        let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+        let running = Arc::new(AtomicBool::new(true));
+        let reader_pending = pending.clone();
+        let reader_running = running.clone();
+        let script_name = script.to_string();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                match serde_json::from_str::<PersistentWorkerMessage>(&line) {
+                    Ok(message) => handle_message(&script_name, message, &reader_pending, &events).await,
+                    Err(_) if !line.trim().is_empty() => {
+                        debug!(target: "python_worker", "[{}] raw output: {}", script_name, line);
+                    }
+                    Err(_) => {}
+                }
+            }
+
+            reader_running.store(false, Ordering::SeqCst);
+            for (_, request) in reader_pending.lock().drain() {
+                let _ = request.answer.send(Err(format!("{} exited", script_name)));
+            }
+            info!(target: "python_worker", "Persistent worker {} exited", script_name);
+            let _ = events
+                .send(WorkerEvent {
+                    name: WORKER_EXITED_EVENT.to_string(),
+                    data: serde_json::Value::Null,
+                })
+                .await;
+        });
+
+        info!(target: "python_worker", "Started persistent worker {}", script);
+        Ok(Self {
+            script: script.to_string(),
+            stdin: Arc::new(tokio::sync::Mutex::new(stdin)),
+            pending,
+            next_id: AtomicU64::new(1),
+            running,
+            _child: child,
+        })
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Send a request and wait for its result
+    pub async fn request(&self, action: &str, params: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.request_with_progress(action, params, None).await
+    }
+
+    /// Send a request and wait for its result, passing its progress and output lines to `progress`
+    pub async fn request_with_progress(
+        &self,
+        action: &str,
+        params: serde_json::Value,
+        progress: Option<mpsc::Sender<WorkerMessage>>,
+    ) -> Result<serde_json::Value, String> {
+        if !self.is_running() {
+            return Err(format!("{} is not running", self.script));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (answer, receiver) = oneshot::channel();
+        self.pending.lock().insert(id, PendingRequest { answer, progress });
+        let _cancel = CancelOnDrop {
+            id,
+            stdin: self.stdin.clone(),
+            pending: self.pending.clone(),
+        };
+
+        let line = serde_json::json!({ "id": id, "action": action, "params": params }).to_string();
+        if let Err(e) = write_line(&self.stdin, &line).await {
+            self.pending.lock().remove(&id);
+            return Err(format!("Failed to send request to {}: {}", self.script, e));
+        }
+
+        receiver
+            .await
+            .map_err(|_| format!("{} exited before answering", self.script))?
+    }
+}
+
+/// Tells the worker to cancel a request that was dropped before its answer arrived
+struct CancelOnDrop {
+    id: u64,
+    stdin: SharedStdin,
+    pending: PendingRequests,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        // Answered requests are no longer pending
+        if self.pending.lock().remove(&self.id).is_none() {
+            return;
+        }
+        let stdin = self.stdin.clone();
+        let line = serde_json::json!({ "id": null, "action": "cancel", "params": { "request_id": self.id } })
+            .to_string();
+        tauri::async_runtime::spawn(async move {
+            let _ = write_line(&stdin, &line).await;
+        });
+    }
+}
+
+async fn write_line(stdin: &SharedStdin, line: &str) -> std::io::Result<()> {
+    let mut stdin = stdin.lock().await;
+    stdin.write_all(format!("{}\n", line).as_bytes()).await?;
+    stdin.flush().await
+}
+
+/// Progress channel of a pending request
+fn progress_sender(pending: &PendingRequests, id: u64) -> Option<mpsc::Sender<WorkerMessage>> {
+    pending.lock().get(&id).and_then(|request| request.progress.clone())
+}
+
+async fn handle_message(
+    script: &str,
+    message: PersistentWorkerMessage,
+    pending: &PendingRequests,
+    events: &mpsc::Sender<WorkerEvent>,
+) {
+    match message {
+        PersistentWorkerMessage::Result { id, data } => {
+            if let Some(request) = pending.lock().remove(&id) {
+                let _ = request.answer.send(Ok(data));
+            }
+        }
+        PersistentWorkerMessage::Error { id: Some(id), message } => {
+            if let Some(request) = pending.lock().remove(&id) {
+                let _ = request.answer.send(Err(message));
+            }
+        }
+        PersistentWorkerMessage::Error { id: None, message } => {
+            error!(target: "python_worker", "[{}] {}", script, message);
+        }
+        PersistentWorkerMessage::Log { id: Some(id), level, message } => match progress_sender(pending, id) {
+            Some(progress) => {
+                let _ = progress.send(WorkerMessage::Log { level, message }).await;
+            }
+            None => debug!(target: "python_worker", "[{}] #{} {}: {}", script, id, level, message),
+        },
+        PersistentWorkerMessage::Progress { id, percent, stage } => {
+            if let Some(progress) = id.and_then(|id| progress_sender(pending, id)) {
+                let _ = progress.send(WorkerMessage::Progress { percent, stage }).await;
+            }
+        }
+        PersistentWorkerMessage::Log { id: None, level, message } => match level.as_str() {
+            "error" => error!(target: "python_worker", "[{}] {}", script, message),
+            "warning" => warn!(target: "python_worker", "[{}] {}", script, message),
+            "debug" => debug!(target: "python_worker", "[{}] {}", script, message),
+            _ => info!(target: "python_worker", "[{}] {}", script, message),
+        },
+        PersistentWorkerMessage::Event { name, data } => {
+            let _ = events.send(WorkerEvent { name, data }).await;
+        }
+    }
+}
@@ -218,11 +218,8 @@ pub fn find_worker_executable(script: &str) -> Result<WorkerExecutable, String>
     ))
 }
 
-pub async fn spawn_python_worker_async(
-    script: &str,
-    input: serde_json::Value,
-    progress_callback: Option<mpsc::Sender<WorkerMessage>>,
-) -> Result<serde_json::Value, String> {
+/// Command running `script` with piped stdio, killed when dropped
+pub(crate) fn worker_command(script: &str) -> Result<Command, String> {
     let worker_exec = find_worker_executable(script)?;
 
     info!(target: "python_worker", "Spawning worker: {:?}", worker_exec);
@@ -246,7 +243,15 @@ pub async fn spawn_python_worker_async(
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
 
-    let mut child = cmd
+    Ok(cmd)
+}
+
+pub async fn spawn_python_worker_async(
+    script: &str,
+    input: serde_json::Value,
+    progress_callback: Option<mpsc::Sender<WorkerMessage>>,
+) -> Result<serde_json::Value, String> {
+    let mut child = worker_command(script)?
         .spawn()
         .map_err(|e| format!("Failed to spawn Python process: {}", e))?;
 
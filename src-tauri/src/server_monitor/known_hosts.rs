@@ -0,0 +1,184 @@
+// Trusted SSH host keys
+// known_hosts.json holds the host key trusted for each server ("host:port"). It goes to the SSH
+// workers with every connection input, and they refuse any other key before authenticating. A
+// server without a trusted key is refused as well: the key it presented is offered to the UI as
+// `server:host_key_unknown` and trusted through `verify_host_key`. Only a key a server actually
+// presented can be trusted, so the UI can't pin a fingerprint of its own making
+
+use chrono::Utc;
+use lazy_static::lazy_static;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::{KnownHost, KnownHostsConfig, ServerConfig};
+use crate::utils::get_known_hosts_json_path;
+
+/// Start of the worker errors for an untrusted key, worded in common/ssh_auth.py
+const UNKNOWN_KEY_PREFIX: &str = "Unknown host key ";
+const CHANGED_KEY_PREFIX: &str = "HOST KEY CHANGED: server presented ";
+
+lazy_static! {
+    /// Keys servers presented that aren't trusted yet, by profile
+    static ref OFFERED: Mutex<HashMap<String, HostKeyProblem>> = Mutex::new(HashMap::new());
+}
+
+/// Why a worker refused a server's host key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostKeyProblem {
+    Unknown { key_type: String, fingerprint: String },
+    Changed { key_type: String, fingerprint: String },
+}
+
+/// The host key problem a worker error reports, if any
+/// The error may be wrapped, e.g. "Failed to connect: HOST KEY CHANGED: ..."
+pub fn parse_error(message: &str) -> Option<HostKeyProblem> {
+    let key_after = |prefix: &str| {
+        let start = message.find(prefix)? + prefix.len();
+        let mut words = message[start..].split_whitespace();
+        let key_type = words.next()?.to_string();
+        let fingerprint = words.next()?.trim_end_matches([',', ';']).to_string();
+        Some((key_type, fingerprint))
+    };
+
+    if let Some((key_type, fingerprint)) = key_after(UNKNOWN_KEY_PREFIX) {
+        return Some(HostKeyProblem::Unknown { key_type, fingerprint });
+    }
+    key_after(CHANGED_KEY_PREFIX).map(|(key_type, fingerprint)| HostKeyProblem::Changed { key_type, fingerprint })
+}
+
+fn load() -> Result<KnownHostsConfig, String> {
+    let path = get_known_hosts_json_path();
+    if !path.exists() {
+        return Ok(KnownHostsConfig::default());
+    }
+    read_json_file(&path)
+}
+
+/// The key trusted for `profile`
+pub fn trusted(profile: &str) -> Option<KnownHost> {
+    match load() {
+        Ok(config) => config.hosts.get(profile).cloned(),
+        Err(e) => {
+            // Nothing is trusted, so connections fail rather than accepting any key
+            warn!("Failed to read known hosts: {}", e);
+            None
+        }
+    }
+}
+
+/// Trust the key `profile` presented, which must have `fingerprint`
+pub fn trust(profile: &str, fingerprint: &str) -> Result<KnownHost, String> {
+    let offered = OFFERED.lock().get(profile).cloned();
+    let key_type = match offered {
+        Some(HostKeyProblem::Unknown { key_type, fingerprint: offered }) if offered == fingerprint => key_type,
+        Some(HostKeyProblem::Unknown { .. }) => {
+            return Err(format!("{} doesn't match the host key {} presented", fingerprint, profile))
+        }
+        // A changed key is only trusted after the old one is cleared and the server connected to again
+        Some(HostKeyProblem::Changed { .. }) => {
+            return Err(format!(
+                "The host key of {} changed; clear the known host first if the change is expected",
+                profile
+            ))
+        }
+        None => return Err(format!("{} hasn't presented a host key to verify; connect first", profile)),
+    };
+
+    let mut config = load()?;
+    let known = KnownHost {
+        key_type,
+        fingerprint: fingerprint.to_string(),
+        trusted_at: Utc::now().to_rfc3339(),
+    };
+    config.hosts.insert(profile.to_string(), known.clone());
+    write_json_file(&get_known_hosts_json_path(), &config)?;
+    OFFERED.lock().remove(profile);
+
+    info!("Trusted host key {} {} for {}", known.key_type, known.fingerprint, profile);
+    Ok(known)
+}
+
+/// Drop the key `profile` presented without trusting it
+pub fn reject(profile: &str) {
+    OFFERED.lock().remove(profile);
+}
+
+/// Forget the key trusted for `profile`; returns whether there was one
+pub fn clear(profile: &str) -> Result<bool, String> {
+    let mut config = load()?;
+    let removed = config.hosts.remove(profile).is_some();
+    if removed {
+        write_json_file(&get_known_hosts_json_path(), &config)?;
+        info!("Cleared the trusted host key of {}", profile);
+    }
+    Ok(removed)
+}
+
+/// Look at an error from connecting to `config`'s server; a refused host key is remembered so it
+/// can be trusted, and reported to the UI
+pub fn report_error(app: Option<&AppHandle>, config: &ServerConfig, message: &str) -> Option<HostKeyProblem> {
+    let problem = parse_error(message)?;
+    let profile = config.server_key();
+    OFFERED.lock().insert(profile.clone(), problem.clone());
+
+    let (event, key_type, fingerprint) = match &problem {
+        HostKeyProblem::Unknown { key_type, fingerprint } => ("server:host_key_unknown", key_type, fingerprint),
+        HostKeyProblem::Changed { key_type, fingerprint } => {
+            warn!("Host key of {} changed to {} {}", profile, key_type, fingerprint);
+            ("server:host_key_changed", key_type, fingerprint)
+        }
+    };
+    if let Some(app) = app {
+        let _ = app.emit(
+            event,
+            json!({
+                "profile": profile,
+                "host": config.host,
+                "port": config.port,
+                "key_type": key_type,
+                "fingerprint": fingerprint,
+                "trusted_fingerprint": trusted(&profile).map(|k| k.fingerprint),
+            }),
+        );
+    }
+    Some(problem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unknown_key_error() {
+        let message = "Validation error: Unknown host key ssh-ed25519 SHA256:abc+/d; verify it in Atlas before connecting";
+        assert_eq!(
+            parse_error(message),
+            Some(HostKeyProblem::Unknown {
+                key_type: "ssh-ed25519".to_string(),
+                fingerprint: "SHA256:abc+/d".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_changed_key_error() {
+        let message = "Failed to connect: HOST KEY CHANGED: server presented ssh-rsa SHA256:new, expected SHA256:old";
+        assert_eq!(
+            parse_error(message),
+            Some(HostKeyProblem::Changed {
+                key_type: "ssh-rsa".to_string(),
+                fingerprint: "SHA256:new".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_other_errors_are_not_host_key_problems() {
+        assert_eq!(parse_error("SSH authentication failed. Check username and password."), None);
+        assert_eq!(parse_error("Unknown host key"), None);
+    }
+}
@@ -0,0 +1,265 @@
+// Server metrics history
+// Parsing of the sampler's command output, the bounded per-server store, and averaging a range of
+// samples down to a number of points a chart can draw
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::ServerMetricsSample;
+use crate::utils::get_server_metrics_json_path;
+
+/// Samples older than this are dropped
+pub const MAX_AGE_SECONDS: i64 = 24 * 3600;
+
+/// Shortest sampler interval; with MAX_AGE_SECONDS it bounds the store size
+pub const MIN_INTERVAL_SECONDS: u32 = 30;
+
+pub const MAX_INTERVAL_SECONDS: u32 = 3600;
+
+const MAX_SAMPLES: usize = (MAX_AGE_SECONDS / MIN_INTERVAL_SECONDS as i64) as usize;
+
+/// Reads CPU time twice a second apart, memory, root filesystem and load in one round trip
+/// Everything comes from /proc and df, so it works without extra packages and costs next to nothing
+pub const SAMPLE_COMMAND: &str = "head -1 /proc/stat; sleep 1; head -1 /proc/stat; \
+     grep -E '^(MemTotal|MemAvailable):' /proc/meminfo; df -P -B1 / | tail -1; cat /proc/loadavg";
+
+/// (busy, total) jiffies of a "cpu  user nice system idle iowait ..." line
+fn cpu_times(line: &str) -> Option<(u64, u64)> {
+    let values: Vec<u64> = line
+        .strip_prefix("cpu ")?
+        .split_whitespace()
+        .map(|v| v.parse().ok())
+        .collect::<Option<_>>()?;
+    if values.len() < 4 {
+        return None;
+    }
+    let total: u64 = values.iter().sum();
+    // idle + iowait
+    let idle = values[3] + values.get(4).copied().unwrap_or(0);
+    Some((total - idle, total))
+}
+
+fn meminfo_bytes(line: &str, key: &str) -> Option<u64> {
+    let kb: u64 = line.strip_prefix(key)?.trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Parse the output of SAMPLE_COMMAND
+pub fn parse_sample(output: &str, timestamp: i64) -> Result<ServerMetricsSample, String> {
+    let mut cpu = Vec::new();
+    let (mut memory_total, mut memory_available) = (None, None);
+    let mut disk = None;
+    let mut load_average = None;
+
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("cpu ") {
+            cpu.extend(cpu_times(line));
+        } else if let Some(bytes) = meminfo_bytes(line, "MemTotal:") {
+            memory_total = Some(bytes);
+        } else if let Some(bytes) = meminfo_bytes(line, "MemAvailable:") {
+            memory_available = Some(bytes);
+        } else {
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                // df: "filesystem 1B-blocks used available capacity /"
+                [_, total, used, _, _, "/"] => {
+                    if let (Ok(total), Ok(used)) = (total.parse::<u64>(), used.parse::<u64>()) {
+                        disk = Some((used, total));
+                    }
+                }
+                // loadavg: "0.15 0.10 0.05 1/123 4567"
+                [load, _, _, tasks, _] if tasks.contains('/') => load_average = load.parse::<f32>().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    let cpu_percent = match cpu.as_slice() {
+        [(busy_before, total_before), (busy_after, total_after), ..] if total_after > total_before => {
+            (busy_after.saturating_sub(*busy_before)) as f32 / (total_after - total_before) as f32 * 100.0
+        }
+        _ => return Err("Missing CPU readings in metrics output".to_string()),
+    };
+    let memory_total = memory_total.ok_or("Missing MemTotal in metrics output")?;
+    let memory_available = memory_available.ok_or("Missing MemAvailable in metrics output")?;
+    let (disk_used, disk_total) = disk.ok_or("Missing disk usage in metrics output")?;
+
+    Ok(ServerMetricsSample {
+        timestamp,
+        cpu_percent: cpu_percent.clamp(0.0, 100.0),
+        memory_used_bytes: memory_total.saturating_sub(memory_available),
+        memory_total_bytes: memory_total,
+        disk_used_bytes: disk_used,
+        disk_total_bytes: disk_total,
+        load_average: load_average.unwrap_or(0.0),
+    })
+}
+
+/// Saved samples of one server, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MetricsStore {
+    #[serde(default)]
+    pub samples: Vec<ServerMetricsSample>,
+}
+
+impl MetricsStore {
+    /// Saved history of `server_key`, empty when there is none yet
+    pub fn load(server_key: &str) -> Result<Self, String> {
+        let path = get_server_metrics_json_path(server_key);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        read_json_file(&path)
+    }
+
+    pub fn save(&self, server_key: &str) -> Result<(), String> {
+        write_json_file(&get_server_metrics_json_path(server_key), self)
+    }
+
+    /// Append a sample and drop what has aged out or exceeds the cap
+    pub fn push(&mut self, sample: ServerMetricsSample) {
+        let cutoff = sample.timestamp - MAX_AGE_SECONDS;
+        self.samples.retain(|s| s.timestamp > cutoff && s.timestamp < sample.timestamp);
+        self.samples.push(sample);
+        if self.samples.len() > MAX_SAMPLES {
+            let excess = self.samples.len() - MAX_SAMPLES;
+            self.samples.drain(..excess);
+        }
+    }
+
+    /// Samples from `since` on
+    pub fn since(&self, since: i64) -> &[ServerMetricsSample] {
+        let start = self.samples.partition_point(|s| s.timestamp < since);
+        &self.samples[start..]
+    }
+}
+
+/// Average `samples` into at most `max_points` points over `range_seconds`
+/// Returns the samples and 0 when they already fit, otherwise the averages and the bucket width
+pub fn downsample(
+    samples: &[ServerMetricsSample],
+    range_seconds: i64,
+    max_points: usize,
+) -> (Vec<ServerMetricsSample>, i64) {
+    if samples.len() <= max_points || max_points == 0 {
+        return (samples.to_vec(), 0);
+    }
+
+    let bucket_seconds = (range_seconds + max_points as i64 - 1) / max_points as i64;
+    let bucket_seconds = bucket_seconds.max(1);
+    let mut points = Vec::new();
+    let mut bucket: Vec<&ServerMetricsSample> = Vec::new();
+
+    for sample in samples {
+        let index = sample.timestamp.div_euclid(bucket_seconds);
+        let same_bucket = bucket
+            .first()
+            .is_some_and(|first| first.timestamp.div_euclid(bucket_seconds) == index);
+        if !same_bucket && !bucket.is_empty() {
+            points.push(average(&bucket));
+            bucket.clear();
+        }
+        bucket.push(sample);
+    }
+    if !bucket.is_empty() {
+        points.push(average(&bucket));
+    }
+    (points, bucket_seconds)
+}
+
+fn average(samples: &[&ServerMetricsSample]) -> ServerMetricsSample {
+    let n = samples.len() as i64;
+    let mean_i64 = |f: fn(&ServerMetricsSample) -> i64| samples.iter().map(|s| f(s)).sum::<i64>() / n;
+    let mean_u64 = |f: fn(&ServerMetricsSample) -> u64| {
+        (samples.iter().map(|s| f(s) as u128).sum::<u128>() / n as u128) as u64
+    };
+    let mean_f32 = |f: fn(&ServerMetricsSample) -> f32| samples.iter().map(|s| f(s)).sum::<f32>() / n as f32;
+
+    ServerMetricsSample {
+        timestamp: mean_i64(|s| s.timestamp),
+        cpu_percent: mean_f32(|s| s.cpu_percent),
+        memory_used_bytes: mean_u64(|s| s.memory_used_bytes),
+        memory_total_bytes: mean_u64(|s| s.memory_total_bytes),
+        disk_used_bytes: mean_u64(|s| s.disk_used_bytes),
+        disk_total_bytes: mean_u64(|s| s.disk_total_bytes),
+        load_average: mean_f32(|s| s.load_average),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "cpu  1000 0 500 8000 500 0 0 0 0 0
+cpu  1060 0 530 8100 510 0 0 0 0 0
+MemTotal:        2000000 kB
+MemAvailable:     500000 kB
+/dev/vda1 50000000000 20000000000 30000000000 40% /
+0.42 0.30 0.25 2/180 12345
+";
+
+    fn sample(timestamp: i64, cpu_percent: f32) -> ServerMetricsSample {
+        ServerMetricsSample {
+            timestamp,
+            cpu_percent,
+            memory_used_bytes: 1000,
+            memory_total_bytes: 4000,
+            disk_used_bytes: 10,
+            disk_total_bytes: 100,
+            load_average: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        let parsed = parse_sample(OUTPUT, 1_700_000_000).unwrap();
+        // 90 busy of 200 jiffies
+        assert!((parsed.cpu_percent - 45.0).abs() < 0.01);
+        assert_eq!(parsed.memory_total_bytes, 2_000_000 * 1024);
+        assert_eq!(parsed.memory_used_bytes, 1_500_000 * 1024);
+        assert_eq!(parsed.disk_total_bytes, 50_000_000_000);
+        assert_eq!(parsed.disk_used_bytes, 20_000_000_000);
+        assert!((parsed.load_average - 0.42).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_sample_rejects_incomplete_output() {
+        assert!(parse_sample("", 0).is_err());
+        let without_disk: String = OUTPUT.lines().filter(|l| !l.starts_with('/')).collect::<Vec<_>>().join("\n");
+        assert!(parse_sample(&without_disk, 0).is_err());
+        let one_cpu_line: String = OUTPUT.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert!(parse_sample(&one_cpu_line, 0).is_err());
+    }
+
+    #[test]
+    fn test_store_drops_old_samples_and_is_capped() {
+        let mut store = MetricsStore::default();
+        store.push(sample(0, 1.0));
+        store.push(sample(MAX_AGE_SECONDS + 10, 2.0));
+        assert_eq!(store.samples.len(), 1);
+
+        let mut store = MetricsStore::default();
+        for i in 0..(MAX_SAMPLES as i64 + 50) {
+            store.push(sample(i, 1.0));
+        }
+        assert_eq!(store.samples.len(), MAX_SAMPLES);
+        assert_eq!(store.samples.last().unwrap().timestamp, MAX_SAMPLES as i64 + 49);
+        assert_eq!(store.since(MAX_SAMPLES as i64 + 40).len(), 10);
+    }
+
+    #[test]
+    fn test_downsample_averages_buckets() {
+        let samples: Vec<ServerMetricsSample> = (0..100).map(|i| sample(i * 10, i as f32)).collect();
+
+        let (points, bucket_seconds) = downsample(&samples, 1000, 200);
+        assert_eq!(points.len(), 100);
+        assert_eq!(bucket_seconds, 0);
+
+        let (points, bucket_seconds) = downsample(&samples, 1000, 10);
+        assert_eq!(bucket_seconds, 100);
+        assert_eq!(points.len(), 10);
+        // First bucket holds samples 0..=9
+        assert!((points[0].cpu_percent - 4.5).abs() < 0.001);
+        assert_eq!(points[0].timestamp, 45);
+        assert_eq!(points[0].memory_used_bytes, 1000);
+    }
+}
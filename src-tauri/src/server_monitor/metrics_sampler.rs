@@ -0,0 +1,154 @@
+// Background server metrics sampling
+// With `metrics_interval_seconds` set in the server config, SAMPLE_COMMAND runs over SSH every
+// interval; the reading is added to the server's history and emitted as `server:metrics_sample`.
+// Samples run on the pooled SSH session. A session a sample opened is closed again afterwards, so
+// sampling alone keeps no connection open, unless `metrics_keep_session_open` opts in.
+// While the server can't be reached the wait doubles with each failure, up to MAX_BACKOFF_SECONDS,
+// and sampling is skipped while no credentials are saved to connect with
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use parking_lot::Mutex;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use super::metrics_history::{parse_sample, MetricsStore, MAX_INTERVAL_SECONDS, MIN_INTERVAL_SECONDS, SAMPLE_COMMAND};
+use crate::commands::server::{get_server_config, run_pooled_command, ssh_worker_input};
+use crate::models::{ServerConfig, ServerMetricsSample};
+
+/// Wait after startup before the first sample, so it doesn't slow down loading
+const STARTUP_DELAY_SECONDS: i64 = 20;
+
+/// Longest single wait, so wall-clock jumps (sleep, clock changes) are noticed
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Longest wait between attempts while the server is unreachable
+const MAX_BACKOFF_SECONDS: i64 = 30 * 60;
+
+lazy_static! {
+    static ref REARM: Mutex<Option<Sender<()>>> = Mutex::new(None);
+}
+
+/// Start the sampler thread
+pub fn start(app: AppHandle) {
+    let (sender, receiver) = mpsc::channel();
+    *REARM.lock() = Some(sender);
+
+    std::thread::spawn(move || {
+        let mut next_sample = Utc::now() + ChronoDuration::seconds(STARTUP_DELAY_SECONDS);
+        let mut failures = 0;
+        loop {
+            let wait = (next_sample - Utc::now()).to_std().unwrap_or(Duration::ZERO).min(POLL_INTERVAL);
+            match receiver.recv_timeout(wait) {
+                Ok(()) => {
+                    failures = 0;
+                    next_sample = Utc::now();
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+            if Utc::now() < next_sample {
+                continue;
+            }
+            next_sample = run_sample(&app, &mut failures);
+        }
+    });
+}
+
+/// Sample again now, after the server config changed
+pub fn rearm() {
+    if let Some(sender) = REARM.lock().as_ref() {
+        let _ = sender.send(());
+    }
+}
+
+/// Wait after `failures` failed attempts in a row
+fn backoff_seconds(interval_seconds: u32, failures: u32) -> i64 {
+    (interval_seconds as i64)
+        .saturating_mul(1 << failures.min(16))
+        .min(MAX_BACKOFF_SECONDS)
+}
+
+/// Take a sample if one is due; returns when to sample next
+fn run_sample(app: &AppHandle, failures: &mut u32) -> DateTime<Utc> {
+    let config = match get_server_config() {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("Server metrics sampler can't read the server config: {}", e);
+            return Utc::now() + ChronoDuration::minutes(1);
+        }
+    };
+    // Off until an interval is configured; `rearm` wakes the thread when it is
+    let Some(interval) = config.metrics_interval_seconds else {
+        return Utc::now() + ChronoDuration::hours(1);
+    };
+    let interval = interval.clamp(MIN_INTERVAL_SECONDS, MAX_INTERVAL_SECONDS);
+    let next = Utc::now() + ChronoDuration::seconds(interval as i64);
+
+    if config.host.is_empty() || ssh_worker_input(&config, None, serde_json::json!({})).is_err() {
+        debug!("Skipping server metrics sample: no server or saved credentials");
+        return next;
+    }
+
+    match take_sample(&config) {
+        Ok(sample) => {
+            *failures = 0;
+            let server = config.server_key();
+            let mut store = MetricsStore::load(&server).unwrap_or_default();
+            store.push(sample.clone());
+            if let Err(e) = store.save(&server) {
+                warn!("Failed to save server metrics: {}", e);
+            }
+            let _ = app.emit(
+                "server:metrics_sample",
+                serde_json::json!({ "server": server, "sample": sample }),
+            );
+            next
+        }
+        Err(e) => {
+            *failures += 1;
+            let retry_in = backoff_seconds(interval, *failures);
+            warn!(
+                "Server metrics sample failed ({} in a row), retrying in {}s: {}",
+                failures, retry_in, e
+            );
+            let _ = app.emit(
+                "server:metrics_unreachable",
+                serde_json::json!({
+                    "server": config.server_key(),
+                    "message": e,
+                    "failures": *failures,
+                    "retry_in_seconds": retry_in,
+                }),
+            );
+            Utc::now() + ChronoDuration::seconds(retry_in)
+        }
+    }
+}
+
+fn take_sample(config: &ServerConfig) -> Result<ServerMetricsSample, String> {
+    let close_session = !config.metrics_keep_session_open;
+    let result = tauri::async_runtime::block_on(run_pooled_command(SAMPLE_COMMAND, None, close_session))?;
+    if result.exit_code != Some(0) {
+        return Err(result
+            .error
+            .unwrap_or_else(|| format!("Metrics command failed on {}", config.host)));
+    }
+    parse_sample(&result.output, Utc::now().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff_seconds(60, 1), 120);
+        assert_eq!(backoff_seconds(60, 2), 240);
+        assert_eq!(backoff_seconds(60, 5), MAX_BACKOFF_SECONDS);
+        assert_eq!(backoff_seconds(60, 40), MAX_BACKOFF_SECONDS);
+    }
+}
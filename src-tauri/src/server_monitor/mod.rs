@@ -0,0 +1,4 @@
+pub mod known_hosts;
+pub mod metrics_history;
+pub mod metrics_sampler;
+pub mod ssh_pool;
@@ -0,0 +1,127 @@
+// Pooled SSH sessions
+// ssh_pool_worker keeps one authenticated session per server profile ("host:port") open, so
+// commands, status checks and SFTP reuse it instead of connecting every time. Sessions send
+// keepalives, reconnect on their own after a drop and close after the configured idle timeout;
+// each state change is emitted as `server:ssh_connection_state`. The worker starts with the first
+// request and is started again by the next one should it die
+
+use lazy_static::lazy_static;
+use log::{info, warn};
+use serde_json::json;
+use std::sync::{Arc, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+use crate::models::{ServerConfig, SshConnectionState, SshConnectionStatus};
+use crate::process_manager::{PersistentWorker, WorkerEvent, WorkerMessage, WORKER_EXITED_EVENT};
+use crate::server_monitor::known_hosts;
+
+const POOL_WORKER: &str = "ssh_pool_worker.py";
+
+pub const DEFAULT_IDLE_TIMEOUT_SECONDS: u32 = 5 * 60;
+pub const MIN_IDLE_TIMEOUT_SECONDS: u32 = 30;
+pub const MAX_IDLE_TIMEOUT_SECONDS: u32 = 24 * 60 * 60;
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+lazy_static! {
+    static ref WORKER: tokio::sync::Mutex<Option<Arc<PersistentWorker>>> = tokio::sync::Mutex::new(None);
+}
+
+/// Remember the app handle that session events are emitted on
+pub fn init(app: AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// The running pool worker, started when there is none
+async fn worker() -> Result<Arc<PersistentWorker>, String> {
+    let mut worker = WORKER.lock().await;
+    if let Some(running) = worker.as_ref().filter(|w| w.is_running()) {
+        return Ok(running.clone());
+    }
+
+    let (events_tx, events_rx) = mpsc::channel(32);
+    let started = Arc::new(PersistentWorker::spawn(POOL_WORKER, events_tx)?);
+    tokio::spawn(forward_events(events_rx));
+    *worker = Some(started.clone());
+    Ok(started)
+}
+
+/// Pass session state changes on to the UI
+async fn forward_events(mut events: mpsc::Receiver<WorkerEvent>) {
+    while let Some(event) = events.recv().await {
+        match event.name.as_str() {
+            "connection_state" => {
+                info!("SSH session: {}", event.data);
+                if let Some(app) = APP.get() {
+                    let _ = app.emit("server:ssh_connection_state", event.data);
+                }
+            }
+            WORKER_EXITED_EVENT => {
+                warn!("SSH pool worker exited, its sessions are closed");
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Run an input made by `ssh_worker_input` on the pooled session to `config`'s server
+/// A refused host key is reported through `known_hosts`
+pub async fn run(
+    config: &ServerConfig,
+    input: serde_json::Value,
+    progress: Option<mpsc::Sender<WorkerMessage>>,
+) -> Result<serde_json::Value, String> {
+    let mut params = input;
+    params["profile"] = json!(config.server_key());
+    params["idle_timeout"] = json!(config.ssh_idle_timeout_seconds.unwrap_or(DEFAULT_IDLE_TIMEOUT_SECONDS));
+
+    worker()
+        .await?
+        .request_with_progress("run", params, progress)
+        .await
+        .inspect_err(|e| {
+            known_hosts::report_error(APP.get(), config, e);
+        })
+}
+
+/// Like `run`, but a session this request had to open is closed again afterwards, unless another
+/// request is using it by then
+pub async fn run_and_close(
+    config: &ServerConfig,
+    input: serde_json::Value,
+    progress: Option<mpsc::Sender<WorkerMessage>>,
+) -> Result<serde_json::Value, String> {
+    let mut params = input;
+    params["close_when_done"] = json!(true);
+    run(config, params, progress).await
+}
+
+/// State of the pooled session to `profile`
+pub async fn connection_state(profile: &str) -> Result<SshConnectionState, String> {
+    let running = WORKER.lock().await.clone().filter(|w| w.is_running());
+    let Some(worker) = running else {
+        return Ok(SshConnectionState {
+            profile: profile.to_string(),
+            state: SshConnectionStatus::Disconnected,
+            connected_since: None,
+            active_requests: 0,
+            idle_seconds: 0,
+            idle_timeout_seconds: 0,
+        });
+    };
+
+    let state = worker.request("state", json!({ "profile": profile })).await?;
+    serde_json::from_value(state).map_err(|e| format!("Invalid SSH session state: {}", e))
+}
+
+/// Close the pooled session to `profile`, if one is open
+pub async fn close(profile: &str) {
+    let running = WORKER.lock().await.clone().filter(|w| w.is_running());
+    if let Some(worker) = running {
+        if let Err(e) = worker.request("close", json!({ "profile": profile })).await {
+            warn!("Failed to close SSH session to {}: {}", profile, e);
+        }
+    }
+}
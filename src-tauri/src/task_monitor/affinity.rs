@@ -0,0 +1,121 @@
+// CPU affinity (which logical cores a process may run on)
+// Masks are limited to a single processor group, i.e. the first 64 logical cores
+use super::models::ProcessAffinity;
+
+/// Reject empty masks and masks naming cores outside `system_mask`
+/// The system mask can have gaps (parked or disabled cores), so it is checked bit by bit
+pub fn validate_affinity_mask(mask: u64, system_mask: u64) -> Result<(), String> {
+    if mask == 0 {
+        return Err("Affinity mask must include at least one core".to_string());
+    }
+
+    let unavailable = mask & !system_mask;
+    if unavailable != 0 {
+        return Err(format!(
+            "Affinity mask {:#x} includes cores not available to the system (available: {:#x})",
+            mask, system_mask
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn get_affinity(pid: u32) -> Result<ProcessAffinity, String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetProcessAffinityMask, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err("Failed to open process (Access Denied or process no longer exists)".to_string());
+        }
+
+        let mut process_mask = 0usize;
+        let mut system_mask = 0usize;
+        let result = GetProcessAffinityMask(handle, &mut process_mask, &mut system_mask);
+        CloseHandle(handle);
+
+        if result == 0 {
+            return Err("Failed to read process affinity".to_string());
+        }
+
+        Ok(ProcessAffinity {
+            pid,
+            mask: process_mask as u64,
+            system_mask: system_mask as u64,
+            core_count: (system_mask as u64).count_ones() as usize,
+        })
+    }
+}
+
+/// Apply `mask` after validating it against the system's affinity mask
+#[cfg(windows)]
+pub fn set_affinity(pid: u32, mask: u64) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, SetProcessAffinityMask, PROCESS_SET_INFORMATION};
+
+    let current = get_affinity(pid)?;
+    validate_affinity_mask(mask, current.system_mask)?;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err("Failed to open process (Access Denied or process no longer exists)".to_string());
+        }
+        let result = SetProcessAffinityMask(handle, mask as usize);
+        CloseHandle(handle);
+
+        if result == 0 {
+            return Err("Failed to set process affinity".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Allow the process to run on every core again
+#[cfg(windows)]
+pub fn reset_affinity(pid: u32) -> Result<(), String> {
+    let current = get_affinity(pid)?;
+    set_affinity(pid, current.system_mask)
+}
+
+#[cfg(not(windows))]
+pub fn get_affinity(_pid: u32) -> Result<ProcessAffinity, String> {
+    Err("Process affinity is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn set_affinity(_pid: u32, _mask: u64) -> Result<(), String> {
+    Err("Process affinity is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn reset_affinity(_pid: u32) -> Result<(), String> {
+    Err("Process affinity is only supported on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_affinity_mask() {
+        assert!(validate_affinity_mask(0, 0xff).is_err());
+        assert!(validate_affinity_mask(0b1111_1110, 0xff).is_ok());
+        assert!(validate_affinity_mask(0b1_0000_0000, 0xff).is_err());
+        assert!(validate_affinity_mask(u64::MAX, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_validate_affinity_mask_with_gaps() {
+        // Cores 0-3 and 6-7 available, 4 and 5 not
+        let system_mask = 0b1100_1111;
+        assert!(validate_affinity_mask(0b1100_0000, system_mask).is_ok());
+        assert!(validate_affinity_mask(0b0001_0000, system_mask).is_err());
+        assert!(validate_affinity_mask(0b0010_0001, system_mask).is_err());
+    }
+}
@@ -434,6 +434,14 @@ pub fn can_kill_process(category: &ProcessCategory) -> bool {
     }
 }
 
+/// Categories that stay protected even if the user overrides the category
+pub fn is_always_protected(category: &ProcessCategory) -> bool {
+    matches!(
+        category,
+        ProcessCategory::AntiCheatProtected | ProcessCategory::SystemCritical
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -476,4 +484,13 @@ mod tests {
         assert!(can_kill_process(&ProcessCategory::MicrosoftBloat));
         assert!(can_kill_process(&ProcessCategory::UserApplication));
     }
+
+    #[test]
+    fn test_category_from_name() {
+        assert_eq!(
+            ProcessCategory::from_name("MicrosoftBloat"),
+            Some(ProcessCategory::MicrosoftBloat)
+        );
+        assert_eq!(ProcessCategory::from_name("Monitoring"), None);
+    }
 }
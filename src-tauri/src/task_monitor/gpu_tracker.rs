@@ -13,6 +13,63 @@ pub static GAMING_ACTIVE: AtomicBool = AtomicBool::new(false);
 pub struct GpuProcessTracker {
     nvml: Option<Nvml>,
     cache: RwLock<ProcessGpuCache>,
+    /// Per-process engine utilization from the "GPU Engine" counters (any GPU vendor)
+    engine_cache: RwLock<EngineUsageCache>,
+    #[cfg(windows)]
+    engine_query: std::sync::Mutex<Option<EngineQuery>>,
+    /// Set once opening the counters fails so it isn't retried on every refresh
+    #[cfg(windows)]
+    engine_unavailable: AtomicBool,
+}
+
+#[cfg(windows)]
+struct EngineQuery {
+    query: crate::performance::pdh::PdhQuery,
+    counter: usize,
+}
+
+struct EngineUsageCache {
+    /// None when the counters aren't available
+    data: Option<HashMap<u32, f32>>,
+    last_update: Instant,
+}
+
+impl Default for EngineUsageCache {
+    fn default() -> Self {
+        Self {
+            data: None,
+            last_update: Instant::now() - Duration::from_secs(10),
+        }
+    }
+}
+
+/// PID and engine key from a "GPU Engine" instance name
+/// e.g. "pid_1234_luid_0x00000000_0x0000D1B7_phys_0_eng_3_engtype_3D" -> (1234, "luid_..._engtype_3d")
+fn parse_engine_instance(instance: &str) -> Option<(u32, String)> {
+    let rest = instance.strip_prefix("pid_")?;
+    let (pid, engine) = rest.split_once('_')?;
+    Some((pid.parse().ok()?, engine.to_lowercase()))
+}
+
+/// Per-process GPU usage: utilization summed per engine, then the busiest engine (as Task Manager shows it)
+fn aggregate_engine_utilization_by_pid(samples: &[(String, f64)]) -> HashMap<u32, f32> {
+    let mut per_engine: HashMap<(u32, String), f64> = HashMap::new();
+    for (instance, value) in samples {
+        if let Some(key) = parse_engine_instance(instance) {
+            *per_engine.entry(key).or_insert(0.0) += value.max(0.0);
+        }
+    }
+
+    let mut per_pid: HashMap<u32, f32> = HashMap::new();
+    for ((pid, _), usage) in per_engine {
+        let usage = usage.min(100.0) as f32;
+        let entry = per_pid.entry(pid).or_insert(0.0);
+        if usage > *entry {
+            *entry = usage;
+        }
+    }
+
+    per_pid
 }
 
 struct ProcessGpuCache {
@@ -41,6 +98,11 @@ impl GpuProcessTracker {
         Self {
             nvml,
             cache: RwLock::new(ProcessGpuCache::default()),
+            engine_cache: RwLock::new(EngineUsageCache::default()),
+            #[cfg(windows)]
+            engine_query: std::sync::Mutex::new(None),
+            #[cfg(windows)]
+            engine_unavailable: AtomicBool::new(false),
         }
     }
 
@@ -143,6 +205,66 @@ impl GpuProcessTracker {
     }
 }
 
+impl GpuProcessTracker {
+    /// Busiest-engine GPU utilization per PID, refreshed at most every 2 seconds
+    /// A single wildcard PDH collection, so it's cheap enough for every process list refresh
+    /// Returns None when the GPU Engine counters aren't available
+    pub fn get_all_engine_usage(&self) -> Option<HashMap<u32, f32>> {
+        let stale = self
+            .engine_cache
+            .read()
+            .map(|cache| cache.last_update.elapsed() > Duration::from_secs(2))
+            .unwrap_or(true);
+
+        if stale {
+            let data = self.sample_engine_usage();
+            if let Ok(mut cache) = self.engine_cache.write() {
+                cache.data = data;
+                cache.last_update = Instant::now();
+            }
+        }
+
+        self.engine_cache.read().ok()?.data.clone()
+    }
+
+    #[cfg(windows)]
+    fn sample_engine_usage(&self) -> Option<HashMap<u32, f32>> {
+        use crate::performance::pdh::PdhQuery;
+
+        if self.engine_unavailable.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let mut guard = self.engine_query.lock().ok()?;
+        if guard.is_none() {
+            let opened = PdhQuery::new().and_then(|mut query| {
+                let counter = query.add_counter("\\GPU Engine(*)\\Utilization Percentage")?;
+                // Rate counter - prime it so the next collection has a baseline
+                query.collect()?;
+                Ok(EngineQuery { query, counter })
+            });
+            match opened {
+                Ok(engine) => *guard = Some(engine),
+                Err(e) => {
+                    debug!("GpuProcessTracker: GPU Engine counters unavailable: {}", e);
+                    self.engine_unavailable.store(true, Ordering::Relaxed);
+                    return None;
+                }
+            }
+        }
+
+        let engine = guard.as_ref()?;
+        engine.query.collect().ok()?;
+        let samples = engine.query.read_array(engine.counter).ok()?;
+        Some(aggregate_engine_utilization_by_pid(&samples))
+    }
+
+    #[cfg(not(windows))]
+    fn sample_engine_usage(&self) -> Option<HashMap<u32, f32>> {
+        None
+    }
+}
+
 impl Default for GpuProcessTracker {
     fn default() -> Self {
         Self::new()
@@ -162,4 +284,20 @@ mod tests {
         let tracker = GpuProcessTracker::new();
         let _ = tracker.is_available();
     }
+
+    #[test]
+    fn test_aggregate_engine_utilization_by_pid() {
+        let samples = vec![
+            ("pid_10_luid_0x00000000_0x0000AAAA_phys_0_eng_0_engtype_3D".to_string(), 30.0),
+            ("pid_10_luid_0x00000000_0x0000AAAA_phys_0_eng_1_engtype_VideoDecode".to_string(), 45.0),
+            ("pid_20_luid_0x00000000_0x0000AAAA_phys_0_eng_0_engtype_3D".to_string(), 5.0),
+            ("_Total".to_string(), 80.0),
+        ];
+
+        let usage = aggregate_engine_utilization_by_pid(&samples);
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage.get(&10), Some(&45.0));
+        assert_eq!(usage.get(&20), Some(&5.0));
+        assert_eq!(parse_engine_instance("pid_abc_luid"), None);
+    }
 }
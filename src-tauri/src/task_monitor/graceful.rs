@@ -0,0 +1,75 @@
+// Graceful close: ask a process's windows to close before resorting to TerminateProcess
+// Gives apps like VS Code or OBS the chance to save state or prompt the user
+use std::time::Duration;
+
+/// Post WM_CLOSE to the windows of every process in `pids` first, then wait for them against one
+/// shared deadline, so closing many processes takes one `timeout` rather than one each
+/// Returns the processes that exited; ones without windows are left running
+#[cfg(windows)]
+pub fn close_all_gracefully(pids: &[u32], timeout: Duration) -> Vec<u32> {
+    use std::time::Instant;
+    use windows_sys::Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM, WAIT_OBJECT_0};
+    use windows_sys::Win32::System::Threading::{OpenProcess, WaitForSingleObject, PROCESS_SYNCHRONIZE};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, WM_CLOSE,
+    };
+
+    struct Search<'a> {
+        pids: &'a [u32],
+        windows: Vec<(u32, HWND)>,
+    }
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam as *mut Search);
+        let mut owner = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut owner);
+        if search.pids.contains(&owner) && IsWindowVisible(hwnd) != 0 {
+            search.windows.push((owner, hwnd));
+        }
+        1
+    }
+
+    let mut search = Search {
+        pids,
+        windows: Vec::new(),
+    };
+
+    unsafe {
+        EnumWindows(Some(collect), &mut search as *mut Search as LPARAM);
+
+        // Open before posting so an exit can't be missed
+        let mut waiting = Vec::new();
+        for &pid in pids {
+            if !search.windows.iter().any(|(owner, _)| *owner == pid) {
+                continue;
+            }
+            let handle = OpenProcess(PROCESS_SYNCHRONIZE, 0, pid);
+            if !handle.is_null() {
+                waiting.push((pid, handle));
+            }
+        }
+
+        for (owner, hwnd) in &search.windows {
+            if waiting.iter().any(|(pid, _)| pid == owner) {
+                PostMessageW(*hwnd, WM_CLOSE, 0, 0);
+            }
+        }
+
+        let deadline = Instant::now() + timeout;
+        let mut exited = Vec::new();
+        for (pid, handle) in waiting {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if WaitForSingleObject(handle, remaining.as_millis().min(u32::MAX as u128) as u32) == WAIT_OBJECT_0 {
+                exited.push(pid);
+            }
+            CloseHandle(handle);
+        }
+        exited
+    }
+}
+
+#[cfg(not(windows))]
+#[allow(dead_code)]
+pub fn close_all_gracefully(_pids: &[u32], _timeout: Duration) -> Vec<u32> {
+    Vec::new()
+}
@@ -0,0 +1,243 @@
+// Per-process CPU/memory history, sampled on SYSTEM_TRACKER refreshes
+// `start_history_sampler` samples in the background so the series has no gaps while nothing polls
+// Bounded in every direction: sample rate, points per process, tracked processes, and how long exited processes linger
+use std::collections::{HashMap, VecDeque};
+
+use super::models::{ProcessHistory, ProcessHistoryPoint};
+
+/// Minimum spacing between samples (refreshes can be as frequent as once a second)
+pub const SAMPLE_INTERVAL_SECONDS: i64 = 5;
+/// Points older than this are dropped
+pub const MAX_HISTORY_SECONDS: i64 = 30 * 60;
+/// 30 minutes at the sample interval
+const MAX_POINTS: usize = (MAX_HISTORY_SECONDS / SAMPLE_INTERVAL_SECONDS) as usize;
+const MAX_TRACKED_PROCESSES: usize = 100;
+/// Processes below both thresholds aren't tracked until they grow
+const MIN_MEMORY_MB: f64 = 100.0;
+const MIN_CPU_PERCENT: f32 = 2.0;
+/// How long an exited process's history stays queryable
+const EXITED_GRACE_SECONDS: i64 = 120;
+/// Dips smaller than this don't break a growth run (allocator noise)
+const GROWTH_TOLERANCE_MB: f64 = 5.0;
+
+/// One process's raw usage at refresh time
+pub struct ProcessSample<'a> {
+    pub pid: u32,
+    pub name: &'a str,
+    pub cpu_usage: f32,
+    pub memory_mb: f64,
+}
+
+/// Sustained memory growth of a live process
+pub struct MemoryGrowth {
+    pub pid: u32,
+    pub name: String,
+    pub growth_mb: f64,
+    pub duration_seconds: i64,
+}
+
+impl MemoryGrowth {
+    pub fn mb_per_hour(&self) -> f64 {
+        if self.duration_seconds <= 0 {
+            return 0.0;
+        }
+        self.growth_mb / self.duration_seconds as f64 * 3600.0
+    }
+}
+
+/// Growth over the trailing run of points where memory never dropped, as (growth_mb, duration_seconds)
+fn trailing_growth(points: &VecDeque<ProcessHistoryPoint>) -> Option<(f64, i64)> {
+    let last = points.back()?;
+    let mut start = last;
+    for point in points.iter().rev().skip(1) {
+        if point.memory_mb > start.memory_mb + GROWTH_TOLERANCE_MB {
+            break;
+        }
+        start = point;
+    }
+    Some((last.memory_mb - start.memory_mb, last.timestamp - start.timestamp))
+}
+
+struct TrackedProcess {
+    name: String,
+    points: VecDeque<ProcessHistoryPoint>,
+    last_seen: i64,
+}
+
+#[derive(Default)]
+pub struct HistoryBuffer {
+    processes: HashMap<u32, TrackedProcess>,
+    last_sample: Option<i64>,
+}
+
+impl HistoryBuffer {
+    /// Record a sample for every process above the footprint threshold (or already tracked)
+    pub fn record<'a>(&mut self, now: i64, samples: impl IntoIterator<Item = ProcessSample<'a>>) {
+        if self.last_sample.is_some_and(|last| now - last < SAMPLE_INTERVAL_SECONDS) {
+            return;
+        }
+        self.last_sample = Some(now);
+
+        for sample in samples {
+            // A different name under the same PID means the PID was reused
+            if self.processes.get(&sample.pid).is_some_and(|t| t.name != sample.name) {
+                self.processes.remove(&sample.pid);
+            }
+
+            if !self.processes.contains_key(&sample.pid) {
+                let significant = sample.memory_mb >= MIN_MEMORY_MB || sample.cpu_usage >= MIN_CPU_PERCENT;
+                if !significant || self.processes.len() >= MAX_TRACKED_PROCESSES {
+                    continue;
+                }
+                self.processes.insert(
+                    sample.pid,
+                    TrackedProcess {
+                        name: sample.name.to_string(),
+                        points: VecDeque::with_capacity(MAX_POINTS),
+                        last_seen: now,
+                    },
+                );
+            }
+            let Some(tracked) = self.processes.get_mut(&sample.pid) else {
+                continue;
+            };
+
+            while tracked.points.len() >= MAX_POINTS
+                || tracked.points.front().is_some_and(|p| now - p.timestamp > MAX_HISTORY_SECONDS)
+            {
+                tracked.points.pop_front();
+            }
+            tracked.points.push_back(ProcessHistoryPoint {
+                timestamp: now,
+                cpu_usage: sample.cpu_usage,
+                memory_mb: sample.memory_mb,
+            });
+            tracked.last_seen = now;
+        }
+
+        self.processes.retain(|_, tracked| now - tracked.last_seen <= EXITED_GRACE_SECONDS);
+    }
+
+    /// Points from the last `seconds` seconds for `pid`
+    pub fn get(&self, pid: u32, seconds: i64, now: i64) -> Option<ProcessHistory> {
+        let tracked = self.processes.get(&pid)?;
+        let since = now - seconds;
+
+        Some(ProcessHistory {
+            pid,
+            name: tracked.name.clone(),
+            points: tracked
+                .points
+                .iter()
+                .filter(|p| p.timestamp >= since)
+                .cloned()
+                .collect(),
+            exited: tracked.last_seen < self.last_sample.unwrap_or(now),
+        })
+    }
+
+    /// Live processes whose memory has kept growing for at least `min_duration_seconds` at `min_mb_per_hour` or faster
+    pub fn growth_suspects(&self, min_duration_seconds: i64, min_mb_per_hour: f64) -> Vec<MemoryGrowth> {
+        let Some(last_sample) = self.last_sample else {
+            return Vec::new();
+        };
+
+        self.processes
+            .iter()
+            .filter(|(_, tracked)| tracked.last_seen == last_sample)
+            .filter_map(|(pid, tracked)| {
+                let (growth_mb, duration_seconds) = trailing_growth(&tracked.points)?;
+                let growth = MemoryGrowth {
+                    pid: *pid,
+                    name: tracked.name.clone(),
+                    growth_mb,
+                    duration_seconds,
+                };
+                (duration_seconds >= min_duration_seconds.max(1) && growth.mb_per_hour() >= min_mb_per_hour)
+                    .then_some(growth)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(pid: u32, name: &str, memory_mb: f64) -> ProcessSample<'_> {
+        ProcessSample {
+            pid,
+            name,
+            cpu_usage: 0.0,
+            memory_mb,
+        }
+    }
+
+    #[test]
+    fn test_records_only_significant_processes() {
+        let mut buffer = HistoryBuffer::default();
+        buffer.record(0, [sample(1, "chrome.exe", 500.0), sample(2, "tiny.exe", 5.0)]);
+
+        assert!(buffer.get(1, 60, 0).is_some());
+        assert!(buffer.get(2, 60, 0).is_none());
+
+        // Too soon after the previous sample
+        buffer.record(1, [sample(1, "chrome.exe", 510.0)]);
+        assert_eq!(buffer.get(1, 60, 1).unwrap().points.len(), 1);
+    }
+
+    #[test]
+    fn test_points_are_capped() {
+        let mut buffer = HistoryBuffer::default();
+        for i in 0..(MAX_POINTS as i64 + 10) {
+            buffer.record(i * SAMPLE_INTERVAL_SECONDS, [sample(1, "chrome.exe", 500.0)]);
+        }
+
+        let now = (MAX_POINTS as i64 + 9) * SAMPLE_INTERVAL_SECONDS;
+        assert_eq!(buffer.get(1, i64::MAX / 2, now).unwrap().points.len(), MAX_POINTS);
+        assert_eq!(buffer.get(1, 10, now).unwrap().points.len(), 3);
+    }
+
+    #[test]
+    fn test_exited_processes_expire_and_reused_pids_reset() {
+        let mut buffer = HistoryBuffer::default();
+        buffer.record(0, [sample(1, "chrome.exe", 500.0)]);
+
+        buffer.record(60, []);
+        assert!(buffer.get(1, 600, 60).unwrap().exited);
+
+        buffer.record(60 + EXITED_GRACE_SECONDS, []);
+        assert!(buffer.get(1, 600, 60 + EXITED_GRACE_SECONDS).is_none());
+
+        buffer.record(1000, [sample(2, "chrome.exe", 500.0)]);
+        buffer.record(1005, [sample(2, "game.exe", 500.0)]);
+        let history = buffer.get(2, 600, 1005).unwrap();
+        assert_eq!(history.name, "game.exe");
+        assert_eq!(history.points.len(), 1);
+    }
+
+    #[test]
+    fn test_growth_suspects() {
+        let mut buffer = HistoryBuffer::default();
+        // 1: +10 MB per minute for 30 minutes, 2: flat, 3: grew then dropped back 5 minutes ago
+        for minute in 0..=30i64 {
+            let dropped = if minute >= 25 { 200.0 } else { 200.0 + minute as f64 * 10.0 };
+            buffer.record(
+                minute * 60,
+                [
+                    sample(1, "chrome.exe", 200.0 + minute as f64 * 10.0),
+                    sample(2, "steam.exe", 300.0),
+                    sample(3, "slack.exe", dropped),
+                ],
+            );
+        }
+
+        let suspects = buffer.growth_suspects(20 * 60, 300.0);
+        assert_eq!(suspects.len(), 1);
+        assert_eq!(suspects[0].pid, 1);
+        assert_eq!(suspects[0].duration_seconds, 30 * 60);
+        assert!((suspects[0].mb_per_hour() - 600.0).abs() < 0.01);
+
+        assert!(buffer.growth_suspects(20 * 60, 700.0).is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+// Detailed single-process inspection
+// Every query is best-effort: elevated processes deny most of them, so each field fails on its own
+#![cfg_attr(not(windows), allow(dead_code))]
+use super::descriptions::get_full_description;
+use super::get_all_processes;
+use super::models::ProcessDetails;
+use super::process_tree;
+use super::system_tracker::SYSTEM_TRACKER;
+
+pub fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
+    let process = get_all_processes()
+        .into_iter()
+        .find(|p| p.pid == pid)
+        .ok_or_else(|| "Process not found".to_string())?;
+
+    let (command_line, start_time) = match SYSTEM_TRACKER.get_process_runtime(pid) {
+        Some((args, start)) => (format_command_line(&args), (start > 0).then_some(start as i64)),
+        None => (None, None),
+    };
+
+    let nodes = process_tree::snapshot_processes().unwrap_or_default();
+    let node = nodes.iter().find(|n| n.pid == pid);
+    let parent_name = process
+        .parent_pid
+        .and_then(|parent| nodes.iter().find(|n| n.pid == parent))
+        .map(|n| n.name.clone());
+
+    let (publisher, signed) = match process.exe_path.as_deref() {
+        Some(path) => (file_publisher(path), file_signed(path)),
+        None => (None, None),
+    };
+
+    Ok(ProcessDetails {
+        command_line,
+        start_time,
+        parent_name,
+        thread_count: node.map(|n| n.thread_count),
+        handle_count: handle_count(pid),
+        publisher,
+        signed,
+        known_description: get_full_description(&process.name).cloned(),
+        process,
+    })
+}
+
+/// Join arguments back into a command line, quoting any that contain spaces
+/// None when nothing was readable (access denied)
+fn format_command_line(args: &[String]) -> Option<String> {
+    if args.is_empty() {
+        return None;
+    }
+    let quoted: Vec<String> = args
+        .iter()
+        .map(|a| if a.contains(' ') { format!("\"{}\"", a) } else { a.clone() })
+        .collect();
+    Some(quoted.join(" "))
+}
+
+#[cfg(windows)]
+fn wide(s: &str) -> Vec<u16> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+
+    OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(windows)]
+fn handle_count(pid: u32) -> Option<u32> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut count = 0u32;
+        let result = GetProcessHandleCount(handle, &mut count);
+        CloseHandle(handle);
+        (result != 0).then_some(count)
+    }
+}
+
+/// CompanyName from the executable's version resource (first language listed)
+#[cfg(windows)]
+fn file_publisher(path: &str) -> Option<String> {
+    use std::ffi::c_void;
+    use std::ptr::null_mut;
+    use windows_sys::Win32::Storage::FileSystem::{GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW};
+
+    let path_wide = wide(path);
+    unsafe {
+        let size = GetFileVersionInfoSizeW(path_wide.as_ptr(), null_mut());
+        if size == 0 {
+            return None;
+        }
+
+        // u64 buffer keeps the resource aligned
+        let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+        let data = buffer.as_mut_ptr() as *mut c_void;
+        if GetFileVersionInfoW(path_wide.as_ptr(), 0, size, data) == 0 {
+            return None;
+        }
+
+        let mut value: *mut c_void = null_mut();
+        let mut len = 0u32;
+        if VerQueryValueW(data, wide("\\VarFileInfo\\Translation").as_ptr(), &mut value, &mut len) == 0 || len < 4 {
+            return None;
+        }
+        let translation = value as *const u16;
+        let key = format!(
+            "\\StringFileInfo\\{:04x}{:04x}\\CompanyName",
+            *translation,
+            *translation.add(1)
+        );
+
+        if VerQueryValueW(data, wide(&key).as_ptr(), &mut value, &mut len) == 0 || len == 0 {
+            return None;
+        }
+        let chars = std::slice::from_raw_parts(value as *const u16, len as usize);
+        let end = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+        let company = String::from_utf16_lossy(&chars[..end]).trim().to_string();
+        (!company.is_empty()).then_some(company)
+    }
+}
+
+/// Whether the file carries a valid Authenticode signature, embedded or through a system catalog
+/// (most Windows binaries are only catalog-signed)
+#[cfg(windows)]
+fn file_signed(path: &str) -> Option<bool> {
+    Some(embedded_signature_valid(path) || catalog_signature_valid(path))
+}
+
+/// Run WinVerifyTrust on `data`, releasing the state it allocates
+#[cfg(windows)]
+unsafe fn verify_trust(data: &mut windows_sys::Win32::Security::WinTrust::WINTRUST_DATA) -> bool {
+    use std::ffi::c_void;
+    use std::ptr::null_mut;
+    use windows_sys::Win32::Security::WinTrust::{
+        WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE,
+        WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+    };
+
+    data.cbStruct = std::mem::size_of_val(data) as u32;
+    data.dwUIChoice = WTD_UI_NONE;
+    data.fdwRevocationChecks = WTD_REVOKE_NONE;
+    data.dwStateAction = WTD_STATEACTION_VERIFY;
+
+    let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+    let status = WinVerifyTrust(null_mut(), &mut action, data as *mut _ as *mut c_void);
+
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(null_mut(), &mut action, data as *mut _ as *mut c_void);
+
+    status == 0
+}
+
+#[cfg(windows)]
+fn embedded_signature_valid(path: &str) -> bool {
+    use std::mem::{size_of, zeroed};
+    use windows_sys::Win32::Security::WinTrust::{WINTRUST_DATA, WINTRUST_FILE_INFO, WTD_CHOICE_FILE};
+
+    let path_wide = wide(path);
+    unsafe {
+        let mut file_info: WINTRUST_FILE_INFO = zeroed();
+        file_info.cbStruct = size_of::<WINTRUST_FILE_INFO>() as u32;
+        file_info.pcwszFilePath = path_wide.as_ptr();
+
+        let mut data: WINTRUST_DATA = zeroed();
+        data.dwUnionChoice = WTD_CHOICE_FILE;
+        data.Anonymous.pFile = &mut file_info;
+        verify_trust(&mut data)
+    }
+}
+
+/// Look the file's hash up in the system catalogs and verify the catalog that lists it
+#[cfg(windows)]
+fn catalog_signature_valid(path: &str) -> bool {
+    use std::mem::{size_of, zeroed};
+    use std::os::windows::io::AsRawHandle;
+    use std::ptr::{null, null_mut};
+    use windows_sys::Win32::Security::Cryptography::Catalog::{
+        CryptCATAdminAcquireContext2, CryptCATAdminCalcHashFromFileHandle2, CryptCATAdminEnumCatalogFromHash,
+        CryptCATAdminReleaseCatalogContext, CryptCATAdminReleaseContext, CryptCATCatalogInfoFromContext,
+        CATALOG_INFO,
+    };
+    use windows_sys::Win32::Security::WinTrust::{WINTRUST_CATALOG_INFO, WINTRUST_DATA, WTD_CHOICE_CATALOG};
+
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let path_wide = wide(path);
+
+    unsafe {
+        let mut admin = 0isize;
+        if CryptCATAdminAcquireContext2(&mut admin, null(), wide("SHA256").as_ptr(), null(), 0) == 0 {
+            return false;
+        }
+
+        let mut valid = false;
+        let mut hash = [0u8; 64];
+        let mut hash_len = hash.len() as u32;
+        if CryptCATAdminCalcHashFromFileHandle2(admin, file.as_raw_handle() as _, &mut hash_len, hash.as_mut_ptr(), 0)
+            != 0
+        {
+            let catalog = CryptCATAdminEnumCatalogFromHash(admin, hash.as_ptr(), hash_len, 0, null_mut());
+            if catalog != 0 {
+                let mut catalog_info: CATALOG_INFO = zeroed();
+                catalog_info.cbStruct = size_of::<CATALOG_INFO>() as u32;
+                if CryptCATCatalogInfoFromContext(catalog, &mut catalog_info, 0) != 0 {
+                    // Catalog members are tagged with their hash in upper-case hex
+                    let tag: String = hash[..hash_len as usize].iter().map(|b| format!("{:02X}", b)).collect();
+                    let tag_wide = wide(&tag);
+
+                    let mut member: WINTRUST_CATALOG_INFO = zeroed();
+                    member.cbStruct = size_of::<WINTRUST_CATALOG_INFO>() as u32;
+                    member.pcwszCatalogFilePath = catalog_info.wszCatalogFile.as_ptr();
+                    member.pcwszMemberTag = tag_wide.as_ptr();
+                    member.pcwszMemberFilePath = path_wide.as_ptr();
+                    member.pbCalculatedFileHash = hash.as_mut_ptr();
+                    member.cbCalculatedFileHash = hash_len;
+                    member.hCatAdmin = admin;
+
+                    let mut data: WINTRUST_DATA = zeroed();
+                    data.dwUnionChoice = WTD_CHOICE_CATALOG;
+                    data.Anonymous.pCatalog = &mut member;
+                    valid = verify_trust(&mut data);
+                }
+                CryptCATAdminReleaseCatalogContext(admin, catalog, 0);
+            }
+        }
+
+        CryptCATAdminReleaseContext(admin, 0);
+        valid
+    }
+}
+
+#[cfg(not(windows))]
+fn handle_count(_pid: u32) -> Option<u32> {
+    None
+}
+
+#[cfg(not(windows))]
+fn file_publisher(_path: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(windows))]
+fn file_signed(_path: &str) -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_command_line() {
+        assert_eq!(format_command_line(&[]), None);
+        assert_eq!(
+            format_command_line(&[
+                "C:\\Program Files\\App\\app.exe".to_string(),
+                "--sync".to_string()
+            ]),
+            Some("\"C:\\Program Files\\App\\app.exe\" --sync".to_string())
+        );
+    }
+}
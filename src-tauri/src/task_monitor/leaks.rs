@@ -0,0 +1,112 @@
+// Memory growth (leak) detection on top of the per-process history
+// Growth builds up over tens of minutes, so suspects are looked for on their own slow thread rather
+// than on every performance sample
+use std::collections::HashSet;
+use std::time::Duration;
+
+use log::{info, warn};
+use tauri::{AppHandle, Emitter};
+
+use super::history::MAX_HISTORY_SECONDS;
+use super::models::{LeakSuspect, ProcessInfo};
+use super::system_tracker::SYSTEM_TRACKER;
+use super::{get_all_processes, normalize_process_name};
+use crate::commands::settings::get_settings;
+use crate::file_manager::read_json_file;
+use crate::models::gaming::GameWhitelist;
+use crate::utils::get_game_whitelist_json_path;
+
+/// How often running processes are checked for sustained memory growth
+const LEAK_CHECK_INTERVAL: Duration = Duration::from_secs(120);
+
+/// e.g. "grew 1.2 GB in 40 min"
+pub fn describe_growth(growth_mb: f64, duration_seconds: i64) -> String {
+    let amount = if growth_mb >= 1024.0 {
+        format!("{:.1} GB", growth_mb / 1024.0)
+    } else {
+        format!("{:.0} MB", growth_mb)
+    };
+    format!("grew {} in {} min", amount, (duration_seconds + 30) / 60)
+}
+
+/// Killable processes with sustained memory growth, using the thresholds from settings
+/// Whitelisted games, protected and excluded processes are never flagged
+pub fn find_leak_suspects(processes: &[ProcessInfo]) -> Vec<LeakSuspect> {
+    let settings = get_settings().unwrap_or_default();
+    let min_duration_seconds = (i64::from(settings.leak_min_duration_minutes) * 60).min(MAX_HISTORY_SECONDS);
+    let growth = SYSTEM_TRACKER.get_memory_growth(min_duration_seconds, settings.leak_growth_mb_per_hour);
+    if growth.is_empty() {
+        return Vec::new();
+    }
+
+    let games: HashSet<String> = read_json_file::<GameWhitelist>(&get_game_whitelist_json_path())
+        .unwrap_or_else(|_| GameWhitelist::default_whitelist())
+        .games
+        .iter()
+        .map(|g| normalize_process_name(&g.process_name))
+        .collect();
+
+    growth
+        .into_iter()
+        .filter_map(|g| {
+            let process = processes.iter().find(|p| p.pid == g.pid && p.name == g.name)?;
+            if !process.can_kill || process.excluded || games.contains(&normalize_process_name(&process.name)) {
+                return None;
+            }
+            Some(LeakSuspect {
+                pid: g.pid,
+                name: process.name.clone(),
+                display_name: process.display_name.clone(),
+                memory_mb: process.memory_mb,
+                growth_mb: g.growth_mb,
+                duration_seconds: g.duration_seconds,
+                mb_per_hour: g.mb_per_hour(),
+                reason: describe_growth(g.growth_mb, g.duration_seconds),
+            })
+        })
+        .collect()
+}
+
+/// Emit `task_monitor:leak_suspect` for processes flagged since the last check
+/// `notified` carries the PIDs already reported; a PID is reported again if it stops growing and starts again
+fn emit_new_leak_suspects(app: &AppHandle, notified: &mut HashSet<u32>) {
+    if !get_settings().map(|s| s.leak_suspect_events).unwrap_or(true) {
+        notified.clear();
+        return;
+    }
+
+    let suspects = find_leak_suspects(&get_all_processes());
+    notified.retain(|pid| suspects.iter().any(|s| s.pid == *pid));
+
+    for suspect in suspects {
+        if !notified.insert(suspect.pid) {
+            continue;
+        }
+        info!("Possible memory leak: {} (PID {}) {}", suspect.name, suspect.pid, suspect.reason);
+        if let Err(e) = app.emit("task_monitor:leak_suspect", &suspect) {
+            warn!("Failed to emit leak suspect: {}", e);
+        }
+    }
+}
+
+/// Check for new leak suspects every LEAK_CHECK_INTERVAL for as long as the app runs
+pub fn start_leak_detector(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut notified = HashSet::new();
+        loop {
+            std::thread::sleep(LEAK_CHECK_INTERVAL);
+            emit_new_leak_suspects(&app, &mut notified);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_growth() {
+        assert_eq!(describe_growth(1228.8, 40 * 60), "grew 1.2 GB in 40 min");
+        assert_eq!(describe_growth(350.0, 25 * 60 + 10), "grew 350 MB in 25 min");
+    }
+}
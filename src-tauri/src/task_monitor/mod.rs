@@ -1,65 +1,315 @@
+pub mod affinity;
 pub mod categorizer;
 pub mod descriptions;
+pub mod graceful;
 pub mod gpu_tracker;
+pub mod history;
+pub mod inspect;
+pub mod leaks;
 pub mod models;
+pub mod network_tracker;
+pub mod overrides;
+pub mod priority;
+pub mod process_tree;
 pub mod profiles;
+pub mod protection;
 pub mod restore;
+pub mod services;
+pub mod startup;
+pub mod suspend;
 pub mod system_tracker;
 
 use categorizer::can_kill_process;
-use models::{KillResult, ProcessCategory, ProcessInfo, SystemSummary};
-use restore::KilledProcessInfo;
+use models::{
+    KillMethod, KillMode, KillRecommendation, KillResult, ProcessCategory, ProcessHistory, ProcessInfo,
+    ProcessKillOutcome, ProcessListPage, ProcessListQuery, ProcessPriority, ProcessSortBy, ProfilePreview,
+    ServiceState, SystemSummary,
+};
+use restore::{DeprioritizedProcessInfo, KilledProcessInfo, StoppedServiceInfo};
 use system_tracker::SYSTEM_TRACKER;
 
 use crate::commands::settings::get_settings;
 
+lazy_static::lazy_static! {
+    /// Most recent kill that wasn't added to the restore list, as (name, unix seconds, why), so undo can explain itself
+    static ref LAST_UNTRACKED_KILL: std::sync::Mutex<Option<(String, i64, &'static str)>> = std::sync::Mutex::new(None);
+}
+
+/// Lowercase and strip ".exe" so "OBS64.exe" and "obs64" match the same process
+pub fn normalize_process_name(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+}
+
 pub fn get_all_processes() -> Vec<ProcessInfo> {
     SYSTEM_TRACKER.get_all_processes()
 }
 
+/// Filtered, sorted slice of the process list from a single tracker snapshot
+pub fn query_processes(query: &ProcessListQuery) -> ProcessListPage {
+    filter_sort_page(get_all_processes(), query)
+}
+
+fn filter_sort_page(processes: Vec<ProcessInfo>, query: &ProcessListQuery) -> ProcessListPage {
+    let filter = query
+        .filter
+        .as_deref()
+        .map(|f| f.trim().to_lowercase())
+        .filter(|f| !f.is_empty());
+
+    let mut matching: Vec<ProcessInfo> = processes
+        .into_iter()
+        .filter(|p| query.category.as_ref().map_or(true, |c| &p.category == c))
+        .filter(|p| {
+            filter.as_deref().map_or(true, |f| {
+                p.name.to_lowercase().contains(f) || p.display_name.to_lowercase().contains(f)
+            })
+        })
+        .collect();
+
+    match query.sort_by {
+        Some(ProcessSortBy::Cpu) => matching.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+        Some(ProcessSortBy::Memory) => matching.sort_by(|a, b| b.memory_mb.total_cmp(&a.memory_mb)),
+        Some(ProcessSortBy::Gpu) => {
+            let gpu = |p: &ProcessInfo| p.gpu_percent.or(p.gpu_usage).unwrap_or(0.0);
+            matching.sort_by(|a, b| gpu(b).total_cmp(&gpu(a)))
+        }
+        Some(ProcessSortBy::Name) => {
+            matching.sort_by_cached_key(|p| p.display_name.to_lowercase());
+        }
+        None => {}
+    }
+
+    let total_count = matching.len();
+    let processes = matching
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    ProcessListPage { processes, total_count }
+}
+
+/// Processes with TCP traffic, busiest first
+pub fn get_top_network_processes(limit: usize) -> Result<Vec<ProcessInfo>, String> {
+    if network_tracker::NETWORK_TRACKER.get_rates().is_none() {
+        return Err(if cfg!(windows) {
+            "Per-process network usage needs Atlas to run as administrator".to_string()
+        } else {
+            "Per-process network usage is only supported on Windows".to_string()
+        });
+    }
+
+    let total = |p: &ProcessInfo| p.network_sent_bps.unwrap_or(0.0) + p.network_received_bps.unwrap_or(0.0);
+    let mut processes: Vec<ProcessInfo> = get_all_processes().into_iter().filter(|p| total(p) > 0.0).collect();
+    processes.sort_by(|a, b| total(b).total_cmp(&total(a)));
+    processes.truncate(limit);
+    Ok(processes)
+}
+
+/// Sample process usage every history sample interval, so per-process history keeps growing while
+/// nothing is polling the process list; skipped while something is
+pub fn start_history_sampler() {
+    std::thread::spawn(|| {
+        // Runs for the app's lifetime, so keep it out of the way of games
+        #[cfg(windows)]
+        {
+            use windows_sys::Win32::System::Threading::{
+                GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL,
+            };
+            unsafe {
+                SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_BELOW_NORMAL);
+            }
+        }
+
+        let interval = std::time::Duration::from_secs(history::SAMPLE_INTERVAL_SECONDS as u64);
+        loop {
+            SYSTEM_TRACKER.sample_history_if_stale(interval);
+            std::thread::sleep(interval);
+        }
+    });
+}
+
+/// (pid, name, start time) of running processes named any of `names` (case-insensitive)
+pub fn find_processes_by_name(names: &[String]) -> Vec<(u32, String, u64)> {
+    let names: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+    SYSTEM_TRACKER.find_processes_by_name(&names)
+}
+
+/// Start time (unix seconds) of `pid` if it is still running as `name`
+pub fn process_start_time(pid: u32, name: &str) -> Option<u64> {
+    find_processes_by_name(&[name.to_string()])
+        .into_iter()
+        .find(|(running, _, _)| *running == pid)
+        .map(|(_, _, started)| started)
+}
+
 pub fn get_system_summary() -> SystemSummary {
     SYSTEM_TRACKER.get_system_summary()
 }
 
+/// CPU/memory series for `pid` over the last `seconds` seconds
+pub fn get_process_history(pid: u32, seconds: u64) -> Result<ProcessHistory, String> {
+    SYSTEM_TRACKER
+        .get_process_history(pid, seconds)
+        .ok_or_else(|| format!("No history for PID {} (not tracked or below the tracking threshold)", pid))
+}
+
+pub fn get_top_processes(limit: usize) -> crate::models::performance::TopProcesses {
+    SYSTEM_TRACKER.get_top_processes(limit)
+}
+
 #[cfg(windows)]
-pub fn kill_process(pid: u32) -> Result<(), String> {
-    kill_process_internal(pid, true)
+pub fn kill_process_with(pid: u32, mode: KillMode) -> Result<ProcessKillOutcome, String> {
+    kill_process_internal(pid, true, mode)
 }
 
+/// Close a process without adding it to the restore list (for closing games, which mustn't be relaunched)
 #[cfg(windows)]
-fn kill_process_internal(pid: u32, track_for_restore: bool) -> Result<(), String> {
-    use windows_sys::Win32::Foundation::CloseHandle;
-    use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+pub fn close_process(pid: u32, mode: KillMode) -> Result<ProcessKillOutcome, String> {
+    kill_process_internal(pid, false, mode)
+}
 
-    let processes = get_all_processes();
-    let target = processes.iter().find(|p| p.pid == pid);
+#[cfg(windows)]
+fn kill_process_internal(pid: u32, track_for_restore: bool, mode: KillMode) -> Result<ProcessKillOutcome, String> {
+    let target = kill_target(&get_all_processes(), pid)?;
+    let (_, outcome) = close_targets(vec![target], track_for_restore, mode)
+        .pop()
+        .ok_or_else(|| "Process not found".to_string())?;
+    outcome
+}
 
-    let process_info = match target {
-        None => return Err("Process not found".to_string()),
-        Some(p) if !p.can_kill => {
-            return Err(format!("Cannot kill protected process: {}", p.name));
+/// The running process `pid`, unless it is protected
+fn kill_target(processes: &[ProcessInfo], pid: u32) -> Result<ProcessInfo, String> {
+    match processes.iter().find(|p| p.pid == pid) {
+        None => Err("Process not found".to_string()),
+        Some(p) if !p.can_kill => Err(format!("Cannot kill protected process: {}", p.name)),
+        Some(p) => Ok(p.clone()),
+    }
+}
+
+/// Close every target, returning each one's outcome by PID
+/// Processes closed gracefully are all asked to close at once and share one timeout; whatever is still
+/// running afterwards is terminated
+#[cfg(windows)]
+fn close_targets(
+    targets: Vec<ProcessInfo>,
+    track_for_restore: bool,
+    mode: KillMode,
+) -> Vec<(u32, Result<ProcessKillOutcome, String>)> {
+    if track_for_restore {
+        targets.iter().for_each(track_killed_process);
+    }
+
+    let graceful_pids: Vec<u32> = targets
+        .iter()
+        .filter(|p| match mode {
+            KillMode::Auto => p.category == ProcessCategory::UserApplication,
+            KillMode::Graceful => true,
+            KillMode::Force => false,
+        })
+        .map(|p| p.pid)
+        .collect();
+    let closed = if graceful_pids.is_empty() {
+        Vec::new()
+    } else {
+        let timeout_seconds = get_settings().unwrap_or_default().graceful_close_timeout_seconds;
+        graceful::close_all_gracefully(&graceful_pids, std::time::Duration::from_secs(timeout_seconds as u64))
+    };
+
+    targets
+        .into_iter()
+        .map(|process| {
+            let method = if closed.contains(&process.pid) {
+                Ok(KillMethod::Graceful)
+            } else {
+                terminate_pid(process.pid).map(|()| KillMethod::Forced)
+            };
+            let outcome = method.map(|method| ProcessKillOutcome {
+                pid: process.pid,
+                name: process.name,
+                method,
+            });
+            (process.pid, outcome)
+        })
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn close_targets(
+    targets: Vec<ProcessInfo>,
+    _track_for_restore: bool,
+    _mode: KillMode,
+) -> Vec<(u32, Result<ProcessKillOutcome, String>)> {
+    targets
+        .into_iter()
+        .map(|p| (p.pid, Err("Process killing is only supported on Windows".to_string())))
+        .collect()
+}
+
+/// Why a kill can't be added to the restore list, or None when it can
+#[cfg_attr(not(windows), allow(dead_code))]
+fn untracked_kill_reason(exe_path: Option<&str>, auto_restore_enabled: bool) -> Option<&'static str> {
+    if exe_path.is_none() {
+        Some("its executable path couldn't be read")
+    } else if !auto_restore_enabled {
+        Some("auto-restore is disabled")
+    } else {
+        None
+    }
+}
+
+/// Record a killed process so it can be restored when the gaming session ends
+#[cfg(windows)]
+fn track_killed_process(process_info: &ProcessInfo) {
+    let now = chrono::Utc::now().timestamp();
+    let auto_restore_enabled = get_settings().unwrap_or_default().auto_restore_enabled;
+    if let Some(reason) = untracked_kill_reason(process_info.exe_path.as_deref(), auto_restore_enabled) {
+        if let Ok(mut last) = LAST_UNTRACKED_KILL.lock() {
+            *last = Some((process_info.name.clone(), now, reason));
         }
-        Some(p) => p.clone(),
+        return;
+    }
+
+    let Some(exe_path) = process_info.exe_path.clone() else {
+        return;
     };
+    let killed_info = KilledProcessInfo {
+        working_dir: std::path::Path::new(&exe_path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string()),
+        exe_path,
+        name: process_info.name.clone(),
+        killed_at: now,
+        is_self_restoring: false,
+    };
+    let _ = restore::add_to_restore_list(killed_info);
+}
 
-    if track_for_restore {
-        if let Some(ref exe_path) = process_info.exe_path {
-            let settings = get_settings().unwrap_or_default();
-            if settings.auto_restore_enabled {
-                let killed_info = KilledProcessInfo {
-                    exe_path: exe_path.clone(),
-                    name: process_info.name.clone(),
-                    killed_at: chrono::Utc::now().timestamp(),
-                    is_self_restoring: false,
-                    working_dir: std::path::Path::new(exe_path)
-                        .parent()
-                        .map(|p| p.to_string_lossy().to_string()),
-                };
-                let _ = restore::add_to_restore_list(killed_info);
-            }
+/// Relaunch the most recently killed process from the restore list
+pub fn undo_last_kill() -> Result<KilledProcessInfo, String> {
+    let list = restore::load_restore_list()?;
+    let latest = restore::latest_restorable(&list).cloned();
+
+    let untracked = LAST_UNTRACKED_KILL.lock().ok().and_then(|last| last.clone());
+    if let Some((name, killed_at, reason)) = untracked {
+        if latest.as_ref().map_or(true, |p| killed_at > p.killed_at) {
+            return Err(format!(
+                "Can't undo killing {}: it was killed without restore tracking ({})",
+                name, reason
+            ));
         }
     }
 
+    let latest = latest.ok_or_else(|| "Nothing to undo - the restore list is empty".to_string())?;
+    restore::restore_single_process(&latest.exe_path)
+}
+
+#[cfg(windows)]
+fn terminate_pid(pid: u32) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
     unsafe {
         let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
         if handle.is_null() {
@@ -77,33 +327,147 @@ fn kill_process_internal(pid: u32, track_for_restore: bool) -> Result<(), String
     Ok(())
 }
 
-#[cfg(not(windows))]
-pub fn kill_process(_pid: u32) -> Result<(), String> {
-    Err("Process killing is only supported on Windows".to_string())
-}
+/// Kill a process and all of its descendants, children first
+/// Refuses to kill anything if the root or any descendant is protected; only the root is tracked for restore
+#[cfg(windows)]
+pub fn kill_process_tree(pid: u32) -> Result<KillResult, String> {
+    let processes = get_all_processes();
+    let root = match processes.iter().find(|p| p.pid == pid) {
+        None => return Err("Process not found".to_string()),
+        Some(p) if !p.can_kill => {
+            return Err(format!("Cannot kill protected process: {}", p.name));
+        }
+        Some(p) => p.clone(),
+    };
+
+    let nodes = process_tree::snapshot_processes()?;
+    let descendants = process_tree::descendants_depth_first(pid, &nodes);
+
+    // Check the whole tree before killing anything
+    for child_pid in &descendants {
+        let (name, can_kill) = match processes.iter().find(|p| p.pid == *child_pid) {
+            Some(p) => (p.name.clone(), p.can_kill),
+            // Spawned since the last refresh - categorize by name alone
+            None => match nodes.iter().find(|n| n.pid == *child_pid) {
+                Some(n) => {
+                    let category = categorizer::categorize_process(&n.name, None);
+                    (n.name.clone(), can_kill_process(&category) && !protection::is_user_protected(&n.name))
+                }
+                None => continue,
+            },
+        };
+        if !can_kill {
+            return Err(format!(
+                "Cannot kill process tree: child process {} (PID {}) is protected",
+                name, child_pid
+            ));
+        }
+    }
 
-pub fn kill_multiple_processes(pids: &[u32]) -> KillResult {
     let mut killed = 0;
     let mut failed = 0;
     let mut errors = Vec::new();
+    let mut outcomes = Vec::new();
 
-    for pid in pids {
-        match kill_process(*pid) {
-            Ok(()) => killed += 1,
+    for child_pid in descendants {
+        match terminate_pid(child_pid) {
+            Ok(()) => {
+                killed += 1;
+                let name = nodes
+                    .iter()
+                    .find(|n| n.pid == child_pid)
+                    .map(|n| n.name.clone())
+                    .unwrap_or_default();
+                outcomes.push(ProcessKillOutcome {
+                    pid: child_pid,
+                    name,
+                    method: KillMethod::Forced,
+                });
+            }
             Err(e) => {
                 failed += 1;
-                errors.push(format!("PID {}: {}", pid, e));
+                errors.push(format!("PID {}: {}", child_pid, e));
             }
         }
     }
 
-    KillResult {
+    track_killed_process(&root);
+    match terminate_pid(pid) {
+        Ok(()) => {
+            killed += 1;
+            outcomes.push(ProcessKillOutcome {
+                pid,
+                name: root.name.clone(),
+                method: KillMethod::Forced,
+            });
+        }
+        Err(e) => {
+            failed += 1;
+            errors.push(format!("PID {}: {}", pid, e));
+        }
+    }
+
+    Ok(KillResult {
         killed,
         failed,
         errors,
+        outcomes,
+        deprioritized: 0,
+        services_stopped: 0,
+    })
+}
+
+#[cfg(not(windows))]
+pub fn kill_process_tree(_pid: u32) -> Result<KillResult, String> {
+    Err("Process killing is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn kill_process_with(_pid: u32, _mode: KillMode) -> Result<ProcessKillOutcome, String> {
+    Err("Process killing is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn close_process(_pid: u32, _mode: KillMode) -> Result<ProcessKillOutcome, String> {
+    Err("Process killing is only supported on Windows".to_string())
+}
+
+pub fn kill_multiple_processes(pids: &[u32]) -> KillResult {
+    kill_pids(pids, KillMode::Auto)
+}
+
+/// Kill `pids` together, so graceful closes wait out one shared timeout rather than one per process
+fn kill_pids(pids: &[u32], mode: KillMode) -> KillResult {
+    let processes = get_all_processes();
+    let mut errors = Vec::new();
+    let mut targets = Vec::new();
+    for pid in pids {
+        match kill_target(&processes, *pid) {
+            Ok(target) => targets.push(target),
+            Err(e) => errors.push(format!("PID {}: {}", pid, e)),
+        }
+    }
+
+    let mut outcomes = Vec::new();
+    for (pid, outcome) in close_targets(targets, true, mode) {
+        match outcome {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(e) => errors.push(format!("PID {}: {}", pid, e)),
+        }
+    }
+
+    KillResult {
+        killed: outcomes.len(),
+        failed: errors.len(),
+        errors,
+        outcomes,
+        deprioritized: 0,
+        services_stopped: 0,
     }
 }
 
+/// Bulk-kill a built-in category
+/// Processes the user moved into a custom category or excluded from this one are left alone
 pub fn kill_by_category(category: &ProcessCategory) -> KillResult {
     if !can_kill_process(category) {
         return KillResult {
@@ -113,57 +477,369 @@ pub fn kill_by_category(category: &ProcessCategory) -> KillResult {
                 "Cannot kill processes in category: {:?}",
                 category
             )],
+            outcomes: Vec::new(),
+            deprioritized: 0,
+            services_stopped: 0,
         };
     }
 
     let processes = get_all_processes();
     let pids: Vec<u32> = processes
         .iter()
-        .filter(|p| &p.category == category && p.can_kill)
+        .filter(|p| &p.category == category && p.custom_category.is_none() && p.can_kill && !p.excluded)
         .map(|p| p.pid)
         .collect();
 
     kill_multiple_processes(&pids)
 }
 
-pub fn kill_by_names(names: &[String]) -> KillResult {
-    let processes = get_all_processes();
-    let names_lower: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+/// Bulk-kill a custom category, only if it was explicitly marked killable
+pub fn kill_by_custom_category(name: &str) -> Result<KillResult, String> {
+    let category = overrides::find_custom_category(name)
+        .ok_or_else(|| format!("Unknown category: {}", name))?;
+    if !category.killable {
+        return Err(format!("Category {} is not marked as killable", category.name));
+    }
 
-    let pids: Vec<u32> = processes
+    let pids: Vec<u32> = get_all_processes()
         .iter()
-        .filter(|p| {
-            let proc_name_lower = p.name.to_lowercase();
-            p.can_kill && names_lower.iter().any(|n| proc_name_lower.contains(n))
-        })
+        .filter(|p| p.custom_category.as_deref() == Some(category.name.as_str()) && p.can_kill && !p.excluded)
         .map(|p| p.pid)
         .collect();
 
-    kill_multiple_processes(&pids)
+    Ok(kill_multiple_processes(&pids))
 }
 
-pub fn execute_profile(profile_id: &str) -> Result<KillResult, String> {
+/// Kill killable processes matching `names`, optionally limited to `only_pids`
+/// Processes on their category's exclusion list are skipped unless `names` lists them by their exact name
+pub fn kill_by_names(names: &[String], mode: KillMode, only_pids: Option<&[u32]>) -> KillResult {
+    let pids: Vec<u32> = find_killable_by_names(&get_all_processes(), names)
+        .filter(|p| !skipped_as_excluded(p, names))
+        .map(|p| p.pid)
+        .filter(|pid| only_pids.map_or(true, |only| only.contains(pid)))
+        .collect();
+
+    kill_pids(&pids, mode)
+}
+
+/// Processes whose name contains any of `names` (case-insensitive), killable or not
+fn find_by_names<'a>(processes: &'a [ProcessInfo], names: &[String]) -> impl Iterator<Item = &'a ProcessInfo> {
+    let names_lower: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+
+    processes.iter().filter(move |p| {
+        let proc_name_lower = p.name.to_lowercase();
+        names_lower.iter().any(|n| proc_name_lower.contains(n))
+    })
+}
+
+/// Killable processes whose name contains any of `names` (case-insensitive)
+fn find_killable_by_names<'a>(
+    processes: &'a [ProcessInfo],
+    names: &[String],
+) -> impl Iterator<Item = &'a ProcessInfo> {
+    find_by_names(processes, names).filter(|p| p.can_kill)
+}
+
+/// Whether an excluded process is only matched by part of its name; naming it exactly overrides the exclusion
+fn skipped_as_excluded(process: &ProcessInfo, names: &[String]) -> bool {
+    let name = normalize_process_name(&process.name);
+    process.excluded && !names.iter().any(|n| normalize_process_name(n) == name)
+}
+
+/// Configured names that don't match any running process
+fn find_unmatched_names(processes: &[ProcessInfo], names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .filter(|name| find_by_names(processes, std::slice::from_ref(name)).next().is_none())
+        .cloned()
+        .collect()
+}
+
+/// Change a process's priority class, subject to the same protection rules as killing it
+/// Returns the previous priority when it could be read
+pub fn set_process_priority(pid: u32, priority: ProcessPriority) -> Result<Option<ProcessPriority>, String> {
+    let processes = get_all_processes();
+    match processes.iter().find(|p| p.pid == pid) {
+        None => Err("Process not found".to_string()),
+        Some(p) if !p.can_kill => Err(format!("Cannot change priority of protected process: {}", p.name)),
+        Some(p) => {
+            priority::set_priority(pid, priority)?;
+            Ok(p.priority)
+        }
+    }
+}
+
+/// Pin a process to the cores in `mask`, subject to the same protection rules as killing it
+pub fn set_process_affinity(pid: u32, mask: u64) -> Result<(), String> {
+    let processes = get_all_processes();
+    match processes.iter().find(|p| p.pid == pid) {
+        None => Err("Process not found".to_string()),
+        Some(p) if !p.can_kill => Err(format!("Cannot change affinity of protected process: {}", p.name)),
+        Some(_) => affinity::set_affinity(pid, mask),
+    }
+}
+
+/// Stop running services, recording them so the restore flow can start them again
+fn stop_services(names: &[String], errors: &mut Vec<String>) -> usize {
+    let mut count = 0;
+
+    for name in names {
+        let start_type = match services::get_service(name) {
+            Ok((ServiceState::Running, start_type)) => start_type,
+            Ok(_) => continue,
+            Err(e) => {
+                errors.push(format!("Service {}: {}", name, e));
+                continue;
+            }
+        };
+
+        match services::stop_service(name) {
+            Ok(()) => {
+                count += 1;
+                let _ = restore::add_stopped_service(StoppedServiceInfo {
+                    name: name.clone(),
+                    original_start_type: start_type,
+                    stopped_at: chrono::Utc::now().timestamp(),
+                });
+            }
+            Err(e) => errors.push(format!("Service {}: {}", name, e)),
+        }
+    }
+
+    count
+}
+
+/// Lower matching processes to Below Normal, recording their original priority for the restore flow
+fn deprioritize_by_names(names: &[String], errors: &mut Vec<String>) -> usize {
+    if names.is_empty() {
+        return 0;
+    }
+
+    let processes = get_all_processes();
+    let mut count = 0;
+
+    for process in find_killable_by_names(&processes, names) {
+        if process.priority == Some(ProcessPriority::BelowNormal) {
+            continue;
+        }
+        match priority::set_priority(process.pid, ProcessPriority::BelowNormal) {
+            Ok(()) => {
+                count += 1;
+                let _ = restore::add_deprioritized(DeprioritizedProcessInfo {
+                    pid: process.pid,
+                    name: process.name.clone(),
+                    original_priority: process.priority.unwrap_or(ProcessPriority::Normal),
+                    changed_at: chrono::Utc::now().timestamp(),
+                    started_at: process_start_time(process.pid, &process.name),
+                });
+            }
+            Err(e) => errors.push(format!("PID {}: {}", process.pid, e)),
+        }
+    }
+
+    count
+}
+
+/// Run a profile; `only_pids` limits the kills to processes the user kept selected in the preview
+pub fn execute_profile(profile_id: &str, only_pids: Option<&[u32]>) -> Result<KillResult, String> {
     let profiles = profiles::get_profiles()?;
     let profile = profiles
         .iter()
         .find(|p| p.id == profile_id)
         .ok_or_else(|| "Profile not found".to_string())?;
 
-    Ok(kill_by_names(&profile.processes_to_kill))
+    // Profile entries get the chance to close cleanly before being force-killed
+    let mut result = kill_by_names(&profile.processes_to_kill, KillMode::Graceful, only_pids);
+    result.deprioritized = deprioritize_by_names(&profile.processes_to_deprioritize, &mut result.errors);
+    result.services_stopped = stop_services(&profile.services_to_stop, &mut result.errors);
+    Ok(result)
 }
 
-pub fn get_kill_recommendations(min_memory_mb: f64) -> Vec<ProcessInfo> {
+/// What a profile would kill, without killing anything
+/// Protected and excluded matches are included (`can_kill = false` / `excluded`) so the UI can show why they'd be skipped;
+/// `excluded` is cleared for processes the profile names exactly, since those are killed anyway
+pub fn preview_profile(profile_id: &str) -> Result<ProfilePreview, String> {
+    let profiles = profiles::get_profiles()?;
+    let profile = profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| "Profile not found".to_string())?;
+
     let processes = get_all_processes();
+    let mut matched: Vec<ProcessInfo> = find_by_names(&processes, &profile.processes_to_kill)
+        .map(|p| ProcessInfo {
+            excluded: skipped_as_excluded(p, &profile.processes_to_kill),
+            ..p.clone()
+        })
+        .collect();
+    matched.sort_by(|a, b| b.memory_mb.partial_cmp(&a.memory_mb).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(ProfilePreview {
+        profile_id: profile.id.clone(),
+        profile_name: profile.name.clone(),
+        killable_memory_mb: matched.iter().filter(|p| p.can_kill && !p.excluded).map(|p| p.memory_mb).sum(),
+        unmatched_names: find_unmatched_names(&processes, &profile.processes_to_kill),
+        matched,
+    })
+}
+
+/// Background processes using this much GPU are recommended whatever their memory use
+const BACKGROUND_GPU_RECOMMEND_PERCENT: f32 = 10.0;
+
+/// Bloat/background processes above `min_memory_mb` or busy on the GPU, plus any process flagged for
+/// sustained memory growth
+pub fn get_kill_recommendations(min_memory_mb: f64) -> Vec<KillRecommendation> {
+    let processes = get_all_processes();
+    let leak_suspects = leaks::find_leak_suspects(&processes);
 
     processes
         .into_iter()
-        .filter(|p| {
-            p.can_kill
-                && p.memory_mb >= min_memory_mb
-                && matches!(
-                    p.category,
-                    ProcessCategory::MicrosoftBloat | ProcessCategory::BackgroundService
-                )
+        .filter(|p| !p.excluded)
+        .filter_map(|p| {
+            if let Some(suspect) = leak_suspects.iter().find(|s| s.pid == p.pid) {
+                return Some(KillRecommendation {
+                    reason: suspect.reason.clone(),
+                    process: p,
+                });
+            }
+
+            let label = match p.category {
+                ProcessCategory::MicrosoftBloat => "Microsoft bloatware",
+                ProcessCategory::BackgroundService => "Background service",
+                _ => return None,
+            };
+            if !p.can_kill || p.custom_category.is_some() {
+                return None;
+            }
+
+            let reason = match p.gpu_percent {
+                // Something rendering in the background is the bigger problem
+                Some(gpu) if gpu >= BACKGROUND_GPU_RECOMMEND_PERCENT => format!("{} using {:.0}% GPU", label, gpu),
+                _ if p.memory_mb >= min_memory_mb => format!("{} using {:.0} MB", label, p.memory_mb),
+                _ => return None,
+            };
+            Some(KillRecommendation { reason, process: p })
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_process_name() {
+        assert_eq!(normalize_process_name("OBS64.exe"), "obs64");
+        assert_eq!(normalize_process_name(" obs64 "), "obs64");
+        assert_eq!(normalize_process_name("Backup.Agent.EXE"), "backup.agent");
+    }
+
+    fn process(pid: u32, name: &str, can_kill: bool) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: name.to_string(),
+            display_name: name.to_string(),
+            exe_path: None,
+            cpu_usage: 0.0,
+            memory_mb: 100.0,
+            gpu_usage: None,
+            gpu_percent: None,
+            network_sent_bps: None,
+            network_received_bps: None,
+            category: ProcessCategory::UserApplication,
+            description: None,
+            can_kill,
+            parent_pid: None,
+            custom_category: None,
+            priority: None,
+            excluded: false,
+            suspended: false,
+        }
+    }
+
+    #[test]
+    fn test_filter_sort_page() {
+        let mut chrome = process(1, "chrome.exe", true);
+        chrome.memory_mb = 900.0;
+        chrome.cpu_usage = 2.0;
+        let mut steam = process(2, "steam.exe", true);
+        steam.memory_mb = 300.0;
+        steam.cpu_usage = 8.0;
+        let mut chrome_helper = process(3, "chrome_helper.exe", true);
+        chrome_helper.category = ProcessCategory::BackgroundService;
+        let processes = vec![chrome, steam, chrome_helper];
+
+        let page = filter_sort_page(
+            processes.clone(),
+            &ProcessListQuery {
+                sort_by: Some(ProcessSortBy::Memory),
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(page.total_count, 3);
+        assert_eq!(page.processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![1, 2]);
+
+        let page = filter_sort_page(
+            processes.clone(),
+            &ProcessListQuery {
+                filter: Some("CHROME".to_string()),
+                category: Some(ProcessCategory::UserApplication),
+                ..Default::default()
+            },
+        );
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.processes[0].pid, 1);
+
+        let page = filter_sort_page(
+            processes,
+            &ProcessListQuery {
+                sort_by: Some(ProcessSortBy::Cpu),
+                offset: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(page.total_count, 3);
+        assert_eq!(page.processes[0].pid, 1);
+    }
+
+    #[test]
+    fn test_profile_name_matching() {
+        let processes = vec![
+            process(1, "Discord.exe", true),
+            process(2, "OneDrive.exe", true),
+            process(3, "vgc.exe", false),
+        ];
+        let names = vec!["discord".to_string(), "VGC".to_string(), "spotify".to_string()];
+
+        let matched: Vec<u32> = find_by_names(&processes, &names).map(|p| p.pid).collect();
+        assert_eq!(matched, vec![1, 3]);
+
+        let killable: Vec<u32> = find_killable_by_names(&processes, &names).map(|p| p.pid).collect();
+        assert_eq!(killable, vec![1]);
+
+        assert_eq!(find_unmatched_names(&processes, &names), vec!["spotify".to_string()]);
+    }
+
+    #[test]
+    fn test_untracked_kill_reason() {
+        assert_eq!(untracked_kill_reason(Some("C:\\app.exe"), true), None);
+        assert_eq!(untracked_kill_reason(Some("C:\\app.exe"), false), Some("auto-restore is disabled"));
+        // A missing path is the reason even when auto-restore is off
+        assert_eq!(untracked_kill_reason(None, false), Some("its executable path couldn't be read"));
+        assert_eq!(untracked_kill_reason(None, true), Some("its executable path couldn't be read"));
+    }
+
+    #[test]
+    fn test_exact_profile_name_overrides_exclusion() {
+        let mut helper = process(1, "OneDriveHelper.exe", true);
+        helper.excluded = true;
+
+        assert!(skipped_as_excluded(&helper, &["onedrive".to_string()]));
+        assert!(!skipped_as_excluded(&helper, &["onedrivehelper".to_string()]));
+        assert!(!skipped_as_excluded(&helper, &["OneDriveHelper.exe".to_string()]));
+
+        helper.excluded = false;
+        assert!(!skipped_as_excluded(&helper, &["onedrive".to_string()]));
+    }
+}
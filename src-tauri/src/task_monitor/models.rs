@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::descriptions::ProcessDescription;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ProcessInfo {
@@ -9,10 +12,120 @@ pub struct ProcessInfo {
     pub cpu_usage: f32,
     pub memory_mb: f64,
     pub gpu_usage: Option<f32>,
+    /// Busiest GPU engine utilization from the Windows "GPU Engine" counters (any vendor) - None when unavailable
+    pub gpu_percent: Option<f32>,
+    /// TCP throughput - None when per-connection statistics are unavailable (needs elevation)
+    pub network_sent_bps: Option<f64>,
+    pub network_received_bps: Option<f64>,
     pub category: ProcessCategory,
     pub description: Option<String>,
     pub can_kill: bool,
     pub parent_pid: Option<u32>,
+    /// User-defined category label - such processes are excluded from built-in category bulk kills
+    pub custom_category: Option<String>,
+    /// Current priority class - None when it can't be read (or is Realtime)
+    pub priority: Option<ProcessPriority>,
+    /// On its category's exclusion list - skipped by bulk kills, profiles and recommendations
+    pub excluded: bool,
+    /// Suspended through Atlas (resumed by the restore flow)
+    pub suspended: bool,
+}
+
+/// Everything known about one process, for deciding whether to kill it
+/// Fields the process wouldn't give up (access denied when it runs elevated) are None
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessDetails {
+    #[serde(flatten)]
+    pub process: ProcessInfo,
+    pub command_line: Option<String>,
+    /// Unix seconds
+    pub start_time: Option<i64>,
+    pub parent_name: Option<String>,
+    pub thread_count: Option<u32>,
+    pub handle_count: Option<u32>,
+    /// CompanyName from the executable's version info
+    pub publisher: Option<String>,
+    /// Valid Authenticode signature, embedded or through a system catalog
+    pub signed: Option<bool>,
+    /// Built-in description with kill impact, for well-known processes
+    pub known_description: Option<ProcessDescription>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessHistoryPoint {
+    /// Unix seconds
+    pub timestamp: i64,
+    pub cpu_usage: f32,
+    pub memory_mb: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessHistory {
+    pub pid: u32,
+    pub name: String,
+    /// Oldest first
+    pub points: Vec<ProcessHistoryPoint>,
+    /// The process is gone but its history is still within the grace period
+    pub exited: bool,
+}
+
+/// Process whose memory has been growing steadily (payload of `task_monitor:leak_suspect`)
+#[derive(Debug, Clone, Serialize)]
+pub struct LeakSuspect {
+    pub pid: u32,
+    pub name: String,
+    pub display_name: String,
+    pub memory_mb: f64,
+    pub growth_mb: f64,
+    pub duration_seconds: i64,
+    pub mb_per_hour: f64,
+    /// e.g. "grew 1.2 GB in 40 min"
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KillRecommendation {
+    #[serde(flatten)]
+    pub process: ProcessInfo,
+    /// Why the process is recommended, e.g. "grew 1.2 GB in 40 min"
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProcessSortBy {
+    Cpu,
+    Memory,
+    Name,
+    Gpu,
+}
+
+/// Filter/sort/page options for the process list
+#[derive(Debug, Clone, Default)]
+pub struct ProcessListQuery {
+    /// Case-insensitive match against the process name and friendly name
+    pub filter: Option<String>,
+    pub category: Option<ProcessCategory>,
+    /// Cpu/Memory/Gpu sort highest first, Name alphabetically
+    pub sort_by: Option<ProcessSortBy>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessListPage {
+    pub processes: Vec<ProcessInfo>,
+    /// Matching processes before `limit`/`offset` were applied
+    pub total_count: usize,
+}
+
+/// Process priority classes that can be set from the task monitor
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProcessPriority {
+    Idle,
+    BelowNormal,
+    Normal,
+    AboveNormal,
+    High,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -43,6 +156,63 @@ impl ProcessCategory {
             ProcessCategory::Unknown => "Unknown",
         }
     }
+
+    /// Variant name as serialized ("MicrosoftBloat", ...)
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessCategory::AntiCheatProtected => "AntiCheatProtected",
+            ProcessCategory::SystemCritical => "SystemCritical",
+            ProcessCategory::SystemService => "SystemService",
+            ProcessCategory::MicrosoftBloat => "MicrosoftBloat",
+            ProcessCategory::SecuritySoftware => "SecuritySoftware",
+            ProcessCategory::UserApplication => "UserApplication",
+            ProcessCategory::BackgroundService => "BackgroundService",
+            ProcessCategory::DriverHardware => "DriverHardware",
+            ProcessCategory::Unknown => "Unknown",
+        }
+    }
+
+    /// Parse a variant name as serialized ("MicrosoftBloat", ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "AntiCheatProtected" => Some(ProcessCategory::AntiCheatProtected),
+            "SystemCritical" => Some(ProcessCategory::SystemCritical),
+            "SystemService" => Some(ProcessCategory::SystemService),
+            "MicrosoftBloat" => Some(ProcessCategory::MicrosoftBloat),
+            "SecuritySoftware" => Some(ProcessCategory::SecuritySoftware),
+            "UserApplication" => Some(ProcessCategory::UserApplication),
+            "BackgroundService" => Some(ProcessCategory::BackgroundService),
+            "DriverHardware" => Some(ProcessCategory::DriverHardware),
+            "Unknown" => Some(ProcessCategory::Unknown),
+            _ => None,
+        }
+    }
+}
+
+/// User override for how a process is categorized and labelled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryOverride {
+    /// Built-in category name (e.g. "UserApplication") or a custom category label
+    pub category: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCategory {
+    pub name: String,
+    /// Whether `kill_by_category` may bulk-kill this category
+    pub killable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProcessCategoryConfig {
+    /// Normalized process name -> override
+    pub overrides: HashMap<String, CategoryOverride>,
+    pub custom_categories: Vec<CustomCategory>,
+    /// Category name (built-in or custom) -> normalized process names never bulk-killed from it
+    #[serde(default)]
+    pub exclusions: HashMap<String, Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +221,12 @@ pub struct GamingProfile {
     pub name: String,
     pub processes_to_kill: Vec<String>,
     pub is_default: bool,
+    /// Processes lowered to Below Normal instead of killed (restored by the restore flow)
+    #[serde(default)]
+    pub processes_to_deprioritize: Vec<String>,
+    /// Windows services stopped when the profile runs (restarted by the restore flow)
+    #[serde(default)]
+    pub services_to_stop: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -58,11 +234,159 @@ pub struct GamingProfileList {
     pub profiles: Vec<GamingProfile>,
 }
 
+/// File format written by `export_gaming_profiles`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamingProfileExport {
+    pub schema_version: u32,
+    pub exported_at: i64,
+    pub profiles: Vec<GamingProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ProfileImportResult {
+    /// Profiles added as new entries
+    pub imported: usize,
+    /// Existing profiles overwritten (same id, overwrite enabled)
+    pub replaced: usize,
+    /// Imported profiles given a new id because theirs was already taken
+    pub renamed_ids: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessAffinity {
+    pub pid: u32,
+    /// Bit N set = the process may run on logical core N
+    pub mask: u64,
+    /// Every core available to the system
+    pub system_mask: u64,
+    pub core_count: usize,
+}
+
+/// Where a startup entry is registered
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StartupLocation {
+    RegistryUser,
+    RegistryMachine,
+    RegistryMachine32,
+    StartupFolderUser,
+    StartupFolderCommon,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StartupImpact {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupItem {
+    pub id: String,
+    /// Registry value or file name
+    pub name: String,
+    pub display_name: String,
+    pub command: String,
+    pub exe_path: Option<String>,
+    pub location: StartupLocation,
+    pub enabled: bool,
+    pub publisher: Option<String>,
+    /// Estimated from the executable size
+    pub impact: Option<StartupImpact>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ServiceState {
+    Running,
+    Stopped,
+    StartPending,
+    StopPending,
+    Paused,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ServiceStartType {
+    Automatic,
+    Manual,
+    Disabled,
+    /// Boot/system drivers
+    System,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceInfo {
+    pub name: String,
+    pub display_name: String,
+    pub state: ServiceState,
+    pub start_type: ServiceStartType,
+    /// False for services on the critical deny-list
+    pub can_stop: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServicesSummary {
+    pub running: usize,
+    pub stopped: usize,
+    pub services: Vec<ServiceInfo>,
+}
+
+/// Process names the user never wants killed
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProtectedProcessList {
+    pub processes: Vec<String>,
+}
+
+/// How a kill request should treat the target process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillMode {
+    /// Graceful for user applications, force for everything else
+    Auto,
+    /// Ask the process's windows to close first, force-kill after the timeout
+    Graceful,
+    /// TerminateProcess straight away
+    Force,
+}
+
+/// How a process actually ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KillMethod {
+    Graceful,
+    Forced,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessKillOutcome {
+    pub pid: u32,
+    pub name: String,
+    pub method: KillMethod,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct KillResult {
     pub killed: usize,
     pub failed: usize,
     pub errors: Vec<String>,
+    /// Per-process outcome for every successfully killed process
+    pub outcomes: Vec<ProcessKillOutcome>,
+    /// Processes lowered in priority by a profile
+    pub deprioritized: usize,
+    /// Services stopped by a profile
+    pub services_stopped: usize,
+}
+
+/// Dry run of a gaming profile
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfilePreview {
+    pub profile_id: String,
+    pub profile_name: String,
+    /// Every running process matching the kill list, largest first - check `can_kill` and `excluded`
+    pub matched: Vec<ProcessInfo>,
+    /// Configured names with no running process
+    pub unmatched_names: Vec<String>,
+    pub killable_memory_mb: f64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -0,0 +1,234 @@
+// Per-process network throughput from TCP connection statistics (ESTATS)
+// Only TCP over IPv4 is counted. Switching statistics on for a connection needs elevation,
+// so without it rates are reported as unavailable rather than as zero
+#![cfg_attr(not(windows), allow(dead_code))]
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Walking every connection is far more expensive than CPU/memory sampling, so readings are reused this long
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetworkRate {
+    pub sent_bytes_per_sec: f64,
+    pub received_bytes_per_sec: f64,
+}
+
+impl NetworkRate {
+    pub fn total(&self) -> f64 {
+        self.sent_bytes_per_sec + self.received_bytes_per_sec
+    }
+}
+
+/// Local address, local port, remote address, remote port
+type ConnectionKey = (u32, u32, u32, u32);
+
+/// Cumulative byte counters for one connection
+struct ConnectionSample {
+    key: ConnectionKey,
+    pid: u32,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+/// Turns cumulative per-connection counters into per-process rates
+#[derive(Default)]
+struct RateCalculator {
+    previous: HashMap<ConnectionKey, (u64, u64)>,
+}
+
+impl RateCalculator {
+    /// Connections seen for the first time only establish a baseline
+    fn update(&mut self, samples: &[ConnectionSample], elapsed_secs: f64) -> HashMap<u32, NetworkRate> {
+        let mut rates: HashMap<u32, NetworkRate> = HashMap::new();
+        let mut current = HashMap::with_capacity(samples.len());
+
+        for sample in samples {
+            let rate = rates.entry(sample.pid).or_default();
+            if let Some(&(prev_in, prev_out)) = self.previous.get(&sample.key) {
+                if elapsed_secs > 0.0 {
+                    rate.received_bytes_per_sec += sample.bytes_in.saturating_sub(prev_in) as f64 / elapsed_secs;
+                    rate.sent_bytes_per_sec += sample.bytes_out.saturating_sub(prev_out) as f64 / elapsed_secs;
+                }
+            }
+            current.insert(sample.key, (sample.bytes_in, sample.bytes_out));
+        }
+
+        self.previous = current;
+        rates
+    }
+}
+
+#[derive(Default)]
+struct TrackerState {
+    calculator: RateCalculator,
+    last_sample: Option<Instant>,
+    rates: Option<HashMap<u32, NetworkRate>>,
+}
+
+#[derive(Default)]
+pub struct NetworkTracker {
+    state: Mutex<TrackerState>,
+}
+
+impl NetworkTracker {
+    /// Per-PID rates, resampled at most every 5 seconds
+    /// PIDs without connections are absent; None when the statistics can't be read
+    pub fn get_rates(&self) -> Option<HashMap<u32, NetworkRate>> {
+        let mut state = self.state.lock().ok()?;
+
+        let due = state.last_sample.map_or(true, |t| t.elapsed() >= SAMPLE_INTERVAL);
+        if due {
+            let elapsed_secs = state.last_sample.map(|t| t.elapsed().as_secs_f64()).unwrap_or(0.0);
+            let rates = read_connections().map(|samples| state.calculator.update(&samples, elapsed_secs));
+            state.rates = rates;
+            state.last_sample = Some(Instant::now());
+        }
+
+        state.rates.clone()
+    }
+}
+
+#[cfg(windows)]
+fn read_connections() -> Option<Vec<ConnectionSample>> {
+    use std::mem::size_of;
+    use std::ptr::null_mut;
+    use windows_sys::Win32::NetworkManagement::IpHelper::{
+        GetExtendedTcpTable, GetPerTcpConnectionEStats, SetPerTcpConnectionEStats, TcpConnectionEstatsData,
+        MIB_TCPROW_LH, MIB_TCPROW_LH_0, MIB_TCPTABLE_OWNER_PID, MIB_TCP_STATE_ESTAB, TCP_ESTATS_DATA_ROD_v0,
+        TCP_ESTATS_DATA_RW_v0, TCP_TABLE_OWNER_PID_CONNECTIONS,
+    };
+
+    const AF_INET: u32 = 2;
+
+    unsafe {
+        let mut size = 0u32;
+        GetExtendedTcpTable(null_mut(), &mut size, 0, AF_INET, TCP_TABLE_OWNER_PID_CONNECTIONS, 0);
+        if size == 0 {
+            return None;
+        }
+
+        // u64 buffer keeps the table aligned
+        let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+        if GetExtendedTcpTable(
+            buffer.as_mut_ptr() as *mut _,
+            &mut size,
+            0,
+            AF_INET,
+            TCP_TABLE_OWNER_PID_CONNECTIONS,
+            0,
+        ) != 0
+        {
+            return None;
+        }
+
+        let table = &*(buffer.as_ptr() as *const MIB_TCPTABLE_OWNER_PID);
+        let rows = std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize);
+
+        let mut samples = Vec::with_capacity(rows.len());
+        let mut attempted = 0;
+        let mut enabled = 0;
+
+        for row in rows {
+            if row.dwState != MIB_TCP_STATE_ESTAB as u32 {
+                continue;
+            }
+            let tcp_row = MIB_TCPROW_LH {
+                Anonymous: MIB_TCPROW_LH_0 { dwState: row.dwState },
+                dwLocalAddr: row.dwLocalAddr,
+                dwLocalPort: row.dwLocalPort,
+                dwRemoteAddr: row.dwRemoteAddr,
+                dwRemotePort: row.dwRemotePort,
+            };
+
+            // Collection is switched on per connection and stays on for its lifetime
+            attempted += 1;
+            let rw = TCP_ESTATS_DATA_RW_v0 { EnableCollection: 1 };
+            if SetPerTcpConnectionEStats(
+                &tcp_row,
+                TcpConnectionEstatsData,
+                &rw as *const _ as *const u8,
+                0,
+                size_of::<TCP_ESTATS_DATA_RW_v0>() as u32,
+                0,
+            ) == 0
+            {
+                enabled += 1;
+            }
+
+            let mut rod: TCP_ESTATS_DATA_ROD_v0 = std::mem::zeroed();
+            if GetPerTcpConnectionEStats(
+                &tcp_row,
+                TcpConnectionEstatsData,
+                null_mut(),
+                0,
+                0,
+                null_mut(),
+                0,
+                0,
+                &mut rod as *mut _ as *mut u8,
+                0,
+                size_of::<TCP_ESTATS_DATA_ROD_v0>() as u32,
+            ) != 0
+            {
+                continue;
+            }
+
+            samples.push(ConnectionSample {
+                key: (row.dwLocalAddr, row.dwLocalPort, row.dwRemoteAddr, row.dwRemotePort),
+                pid: row.dwOwningPid,
+                bytes_in: rod.DataBytesIn,
+                bytes_out: rod.DataBytesOut,
+            });
+        }
+
+        // Not elevated: every counter would read zero, which is worse than reporting nothing
+        if attempted > 0 && enabled == 0 {
+            return None;
+        }
+
+        Some(samples)
+    }
+}
+
+#[cfg(not(windows))]
+fn read_connections() -> Option<Vec<ConnectionSample>> {
+    None
+}
+
+lazy_static::lazy_static! {
+    pub static ref NETWORK_TRACKER: NetworkTracker = NetworkTracker::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(key: u32, pid: u32, bytes_in: u64, bytes_out: u64) -> ConnectionSample {
+        ConnectionSample {
+            key: (key, 1, 2, 443),
+            pid,
+            bytes_in,
+            bytes_out,
+        }
+    }
+
+    #[test]
+    fn test_rate_calculator() {
+        let mut calculator = RateCalculator::default();
+
+        // First sighting only sets the baseline
+        let rates = calculator.update(&[sample(1, 100, 1_000, 500)], 0.0);
+        assert_eq!(rates[&100], NetworkRate::default());
+
+        // Two connections of the same process add up; a new connection contributes nothing yet
+        let rates = calculator.update(
+            &[sample(1, 100, 11_000, 2_500), sample(2, 100, 50_000, 50_000), sample(3, 200, 0, 0)],
+            5.0,
+        );
+        assert_eq!(rates[&100].received_bytes_per_sec, 2_000.0);
+        assert_eq!(rates[&100].sent_bytes_per_sec, 400.0);
+        assert_eq!(rates[&200].total(), 0.0);
+    }
+}
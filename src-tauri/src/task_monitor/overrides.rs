@@ -0,0 +1,205 @@
+// User category overrides and custom categories
+// Overrides win over the built-in categorizer rules; custom categories are never bulk-killed unless marked killable
+use std::sync::RwLock;
+
+use super::models::{CategoryOverride, CustomCategory, ProcessCategory, ProcessCategoryConfig};
+use super::normalize_process_name;
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::utils::get_process_categories_json_path;
+
+lazy_static::lazy_static! {
+    /// Loaded on first use so building the process list doesn't hit the disk
+    static ref CONFIG: RwLock<Option<ProcessCategoryConfig>> = RwLock::new(None);
+}
+
+/// What an override resolves to
+pub enum ResolvedCategory {
+    BuiltIn(ProcessCategory),
+    Custom(String),
+}
+
+pub fn get_config() -> Result<ProcessCategoryConfig, String> {
+    let path = get_process_categories_json_path();
+
+    if !path.exists() {
+        return Ok(ProcessCategoryConfig::default());
+    }
+
+    read_json_file(&path)
+}
+
+fn save_config(config: &ProcessCategoryConfig) -> Result<(), String> {
+    write_json_file(&get_process_categories_json_path(), config)?;
+
+    if let Ok(mut cache) = CONFIG.write() {
+        *cache = Some(config.clone());
+    }
+    Ok(())
+}
+
+fn with_cached<T>(f: impl FnOnce(&ProcessCategoryConfig) -> T) -> Option<T> {
+    let loaded = CONFIG.read().map(|cache| cache.is_some()).unwrap_or(false);
+    if !loaded {
+        let config = get_config().unwrap_or_default();
+        if let Ok(mut cache) = CONFIG.write() {
+            *cache = Some(config);
+        }
+    }
+
+    CONFIG.read().ok().and_then(|cache| cache.as_ref().map(f))
+}
+
+/// Override for a process name, if the user set one
+pub fn lookup(name: &str) -> Option<CategoryOverride> {
+    let key = normalize_process_name(name);
+    with_cached(|config| config.overrides.get(&key).cloned()).flatten()
+}
+
+/// Resolve an override's category string to a built-in or custom category
+pub fn resolve(category: &str) -> Option<ResolvedCategory> {
+    if let Some(builtin) = ProcessCategory::from_name(category) {
+        return Some(ResolvedCategory::BuiltIn(builtin));
+    }
+    with_cached(|config| {
+        config
+            .custom_categories
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(category))
+            .map(|c| ResolvedCategory::Custom(c.name.clone()))
+    })
+    .flatten()
+}
+
+/// Custom category definition by label
+pub fn find_custom_category(name: &str) -> Option<CustomCategory> {
+    with_cached(|config| {
+        config
+            .custom_categories
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .cloned()
+    })
+    .flatten()
+}
+
+/// Whether `name` is on the exclusion list of `category` (a built-in category name or custom label)
+pub fn is_excluded(category: &str, name: &str) -> bool {
+    let key = normalize_process_name(name);
+    with_cached(|config| {
+        config
+            .exclusions
+            .iter()
+            .any(|(c, names)| c.eq_ignore_ascii_case(category) && names.contains(&key))
+    })
+    .unwrap_or(false)
+}
+
+/// Canonical spelling of a built-in or custom category name
+fn canonical_category(config: &ProcessCategoryConfig, category: &str) -> Result<String, String> {
+    if let Some(builtin) = ProcessCategory::from_name(category) {
+        return Ok(builtin.name().to_string());
+    }
+    config
+        .custom_categories
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(category))
+        .map(|c| c.name.clone())
+        .ok_or_else(|| format!("Unknown category: {}", category))
+}
+
+pub fn get_exclusions(category: &str) -> Result<Vec<String>, String> {
+    let config = get_config()?;
+    let category = canonical_category(&config, category)?;
+    Ok(config.exclusions.get(&category).cloned().unwrap_or_default())
+}
+
+/// Replace a category's exclusion list; names are normalized and deduplicated
+pub fn set_exclusions(category: &str, names: &[String]) -> Result<(), String> {
+    let mut config = get_config()?;
+    let category = canonical_category(&config, category)?;
+
+    let mut normalized: Vec<String> = names
+        .iter()
+        .map(|n| normalize_process_name(n))
+        .filter(|n| !n.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+
+    if normalized.is_empty() {
+        config.exclusions.remove(&category);
+    } else {
+        config.exclusions.insert(category, normalized);
+    }
+    save_config(&config)
+}
+
+pub fn set_override(
+    name: &str,
+    category: &str,
+    display_name: Option<String>,
+    description: Option<String>,
+) -> Result<(), String> {
+    let key = normalize_process_name(name);
+    if key.is_empty() {
+        return Err("Process name cannot be empty".to_string());
+    }
+
+    // Custom categories may have been created since the cache was loaded
+    let mut config = get_config()?;
+    let category = canonical_category(&config, category)?;
+
+    config.overrides.insert(
+        key,
+        CategoryOverride {
+            category,
+            display_name: display_name.filter(|n| !n.trim().is_empty()),
+            description: description.filter(|d| !d.trim().is_empty()),
+        },
+    );
+    save_config(&config)
+}
+
+pub fn clear_override(name: &str) -> Result<(), String> {
+    let mut config = get_config()?;
+    if config.overrides.remove(&normalize_process_name(name)).is_none() {
+        return Err(format!("No category override for: {}", name));
+    }
+    save_config(&config)
+}
+
+/// Create or update a custom category
+pub fn save_custom_category(name: &str, killable: bool) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Category name cannot be empty".to_string());
+    }
+    if ProcessCategory::from_name(name).is_some() {
+        return Err(format!("{} is a built-in category", name));
+    }
+
+    let mut config = get_config()?;
+    match config.custom_categories.iter_mut().find(|c| c.name.eq_ignore_ascii_case(name)) {
+        Some(existing) => existing.killable = killable,
+        None => config.custom_categories.push(CustomCategory {
+            name: name.to_string(),
+            killable,
+        }),
+    }
+    save_config(&config)
+}
+
+/// Delete a custom category along with the overrides that point at it
+pub fn delete_custom_category(name: &str) -> Result<(), String> {
+    let mut config = get_config()?;
+    let initial_len = config.custom_categories.len();
+    config.custom_categories.retain(|c| !c.name.eq_ignore_ascii_case(name));
+
+    if config.custom_categories.len() == initial_len {
+        return Err(format!("Custom category not found: {}", name));
+    }
+
+    config.overrides.retain(|_, o| !o.category.eq_ignore_ascii_case(name));
+    config.exclusions.retain(|c, _| !c.eq_ignore_ascii_case(name));
+    save_config(&config)
+}
@@ -0,0 +1,80 @@
+// Process priority classes (Realtime is deliberately not offered)
+use super::models::ProcessPriority;
+
+#[cfg(windows)]
+use windows_sys::Win32::System::Threading::{
+    ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS,
+    NORMAL_PRIORITY_CLASS,
+};
+
+#[cfg(windows)]
+fn to_class(priority: ProcessPriority) -> u32 {
+    match priority {
+        ProcessPriority::Idle => IDLE_PRIORITY_CLASS,
+        ProcessPriority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+        ProcessPriority::Normal => NORMAL_PRIORITY_CLASS,
+        ProcessPriority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+        ProcessPriority::High => HIGH_PRIORITY_CLASS,
+    }
+}
+
+/// None for classes we don't expose (Realtime)
+#[cfg(windows)]
+fn from_class(class: u32) -> Option<ProcessPriority> {
+    match class {
+        IDLE_PRIORITY_CLASS => Some(ProcessPriority::Idle),
+        BELOW_NORMAL_PRIORITY_CLASS => Some(ProcessPriority::BelowNormal),
+        NORMAL_PRIORITY_CLASS => Some(ProcessPriority::Normal),
+        ABOVE_NORMAL_PRIORITY_CLASS => Some(ProcessPriority::AboveNormal),
+        HIGH_PRIORITY_CLASS => Some(ProcessPriority::High),
+        _ => None,
+    }
+}
+
+/// Current priority class, None when the process can't be opened
+#[cfg(windows)]
+pub fn get_priority(pid: u32) -> Option<ProcessPriority> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{GetPriorityClass, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let class = GetPriorityClass(handle);
+        CloseHandle(handle);
+        from_class(class)
+    }
+}
+
+#[cfg(windows)]
+pub fn set_priority(pid: u32, priority: ProcessPriority) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, SetPriorityClass, PROCESS_SET_INFORMATION};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err("Failed to open process (Access Denied or process no longer exists)".to_string());
+        }
+        let result = SetPriorityClass(handle, to_class(priority));
+        CloseHandle(handle);
+
+        if result == 0 {
+            return Err("Failed to set process priority".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn get_priority(_pid: u32) -> Option<ProcessPriority> {
+    None
+}
+
+#[cfg(not(windows))]
+pub fn set_priority(_pid: u32, _priority: ProcessPriority) -> Result<(), String> {
+    Err("Process priority is only supported on Windows".to_string())
+}
@@ -0,0 +1,167 @@
+// Parent/child process relationships for killing whole process trees
+use std::collections::{HashMap, HashSet};
+
+/// One entry of a process snapshot
+#[cfg_attr(not(windows), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub struct ProcessNode {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub name: String,
+    pub thread_count: u32,
+    /// Creation time as a FILETIME (100 ns ticks); None when the process couldn't be opened
+    pub created_at: Option<u64>,
+}
+
+/// Creation time of `pid` as a FILETIME
+#[cfg(windows)]
+fn process_creation_time(pid: u32) -> Option<u64> {
+    use windows_sys::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows_sys::Win32::System::Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let zero = FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 };
+        let (mut created, mut exited, mut kernel, mut user) = (zero, zero, zero, zero);
+        let ok = GetProcessTimes(handle, &mut created, &mut exited, &mut kernel, &mut user);
+        CloseHandle(handle);
+        (ok != 0).then(|| (u64::from(created.dwHighDateTime) << 32) | u64::from(created.dwLowDateTime))
+    }
+}
+
+/// Snapshot every running process with its parent PID (Toolhelp) and creation time
+#[cfg(windows)]
+pub fn snapshot_processes() -> Result<Vec<ProcessNode>, String> {
+    use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err("Failed to create process snapshot".to_string());
+        }
+
+        let mut nodes = Vec::new();
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+        if Process32FirstW(snapshot, &mut entry) != 0 {
+            loop {
+                let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                nodes.push(ProcessNode {
+                    pid: entry.th32ProcessID,
+                    parent_pid: entry.th32ParentProcessID,
+                    name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+                    thread_count: entry.cntThreads,
+                    created_at: process_creation_time(entry.th32ProcessID),
+                });
+                if Process32NextW(snapshot, &mut entry) == 0 {
+                    break;
+                }
+            }
+        }
+
+        CloseHandle(snapshot);
+        Ok(nodes)
+    }
+}
+
+#[cfg(not(windows))]
+#[allow(dead_code)]
+pub fn snapshot_processes() -> Result<Vec<ProcessNode>, String> {
+    Err("Process tree enumeration is only supported on Windows".to_string())
+}
+
+/// All descendants of `root`, deepest first, so children are always listed before their parent
+/// Windows reuses PIDs and never updates a stale parent PID: an orphan's parent PID can belong to a
+/// newer, unrelated process. A child created before its supposed parent is such an orphan and is
+/// left out; cycles are guarded against too
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn descendants_depth_first(root: u32, nodes: &[ProcessNode]) -> Vec<u32> {
+    let created: HashMap<u32, Option<u64>> = nodes.iter().map(|n| (n.pid, n.created_at)).collect();
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for node in nodes {
+        let parent_created = created.get(&node.parent_pid).copied().flatten();
+        let stale_parent = matches!((node.created_at, parent_created), (Some(child), Some(parent)) if child < parent);
+        if node.pid != node.parent_pid && !stale_parent {
+            children.entry(node.parent_pid).or_default().push(node.pid);
+        }
+    }
+
+    let mut order = Vec::new();
+    let mut visited = HashSet::from([root]);
+    visit(root, &children, &mut visited, &mut order);
+    order
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn visit(pid: u32, children: &HashMap<u32, Vec<u32>>, visited: &mut HashSet<u32>, order: &mut Vec<u32>) {
+    for &child in children.get(&pid).map(Vec::as_slice).unwrap_or_default() {
+        if visited.insert(child) {
+            visit(child, children, visited, order);
+            order.push(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(pid: u32, parent_pid: u32) -> ProcessNode {
+        ProcessNode {
+            pid,
+            parent_pid,
+            name: format!("{}.exe", pid),
+            thread_count: 1,
+            created_at: None,
+        }
+    }
+
+    fn created(mut node: ProcessNode, at: u64) -> ProcessNode {
+        node.created_at = Some(at);
+        node
+    }
+
+    #[test]
+    fn test_children_before_parents() {
+        // 1 -> 2 -> 4, 1 -> 3; 5 is unrelated
+        let nodes = vec![node(1, 0), node(2, 1), node(3, 1), node(4, 2), node(5, 0)];
+        let order = descendants_depth_first(1, &nodes);
+
+        assert_eq!(order.len(), 3);
+        assert!(!order.contains(&5));
+        let pos = |pid| order.iter().position(|&p| p == pid).unwrap();
+        assert!(pos(4) < pos(2));
+    }
+
+    #[test]
+    fn test_parent_pid_cycle_terminates() {
+        // A reused PID can point back at its own descendant
+        let nodes = vec![node(1, 2), node(2, 1)];
+        assert_eq!(descendants_depth_first(1, &nodes), vec![2]);
+    }
+
+    #[test]
+    fn test_stale_parent_pid_is_not_a_child() {
+        // 7's real parent exited and its PID 1 was reused by the root, started after 7
+        let nodes = vec![
+            created(node(1, 0), 500),
+            created(node(2, 1), 600),
+            created(node(7, 1), 100),
+            created(node(8, 7), 200),
+            node(9, 1),
+        ];
+        let order = descendants_depth_first(1, &nodes);
+
+        assert!(!order.contains(&7));
+        assert!(!order.contains(&8));
+        // Creation time unknown: the parent PID is taken at its word
+        assert!(order.contains(&2) && order.contains(&9));
+    }
+}
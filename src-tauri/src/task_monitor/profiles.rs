@@ -1,8 +1,13 @@
 use crate::file_manager::{read_json_file, write_json_file};
 use crate::utils::get_gaming_profiles_json_path;
-use super::models::{GamingProfile, GamingProfileList};
+use super::models::{GamingProfile, GamingProfileExport, GamingProfileList, ProfileImportResult};
+use std::collections::HashSet;
+use std::path::Path;
 use uuid::Uuid;
 
+/// Bumped whenever the export format changes incompatibly
+const PROFILE_EXPORT_SCHEMA_VERSION: u32 = 1;
+
 pub fn get_profiles() -> Result<Vec<GamingProfile>, String> {
     let path = get_gaming_profiles_json_path();
 
@@ -75,12 +80,134 @@ pub fn set_default_profile(id: &str) -> Result<(), String> {
     write_json_file(&path, &list)
 }
 
-#[allow(dead_code)]
 pub fn get_default_profile() -> Result<Option<GamingProfile>, String> {
     let profiles = get_profiles()?;
     Ok(profiles.into_iter().find(|p| p.is_default))
 }
 
+/// Write all profiles, or only those in `ids`, to `path`
+/// Returns the number of profiles exported
+pub fn export_profiles(path: &Path, ids: Option<&[String]>) -> Result<usize, String> {
+    let profiles = get_profiles()?;
+
+    let profiles = match ids {
+        Some(ids) => {
+            if let Some(missing) = ids.iter().find(|id| !profiles.iter().any(|p| &p.id == *id)) {
+                return Err(format!("Profile not found: {}", missing));
+            }
+            profiles.into_iter().filter(|p| ids.contains(&p.id)).collect()
+        }
+        None => profiles,
+    };
+    if profiles.is_empty() {
+        return Err("No profiles to export".to_string());
+    }
+
+    let export = GamingProfileExport {
+        schema_version: PROFILE_EXPORT_SCHEMA_VERSION,
+        exported_at: chrono::Utc::now().timestamp(),
+        profiles,
+    };
+    write_json_file(path, &export)?;
+    Ok(export.profiles.len())
+}
+
+/// Merge profiles from an export file into the saved list
+pub fn import_profiles(path: &Path, overwrite: bool) -> Result<ProfileImportResult, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let export = parse_export(&contents)?;
+
+    let (profiles, result) = merge_profiles(get_profiles()?, export.profiles, overwrite);
+    write_json_file(&get_gaming_profiles_json_path(), &GamingProfileList { profiles })?;
+    Ok(result)
+}
+
+/// Validate an export file, with errors that say what to fix
+fn parse_export(contents: &str) -> Result<GamingProfileExport, String> {
+    let value: serde_json::Value = serde_json::from_str(contents)
+        .map_err(|e| format!("Not a valid JSON file ({}). Choose a file created by Export Profiles", e))?;
+
+    let version = value
+        .get("schema_version")
+        .ok_or("Missing schema_version - this doesn't look like an exported gaming profile file")?
+        .as_u64()
+        .ok_or("schema_version must be a number")?;
+    if version == 0 || version > u64::from(PROFILE_EXPORT_SCHEMA_VERSION) {
+        return Err(format!(
+            "Unsupported profile export version {} (this version of Atlas reads version {}). Update Atlas and try again",
+            version, PROFILE_EXPORT_SCHEMA_VERSION
+        ));
+    }
+
+    let entries = value
+        .get("profiles")
+        .and_then(|p| p.as_array())
+        .ok_or("Missing profiles list in the export file")?;
+    if entries.is_empty() {
+        return Err("The export file contains no profiles".to_string());
+    }
+
+    let mut profiles = Vec::with_capacity(entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        let profile: GamingProfile = serde_json::from_value(entry.clone())
+            .map_err(|e| format!("Profile #{} is invalid: {}", i + 1, e))?;
+        if profile.name.trim().is_empty() {
+            return Err(format!("Profile #{} has an empty name", i + 1));
+        }
+        profiles.push(profile);
+    }
+
+    Ok(GamingProfileExport {
+        schema_version: version as u32,
+        exported_at: value.get("exported_at").and_then(|v| v.as_i64()).unwrap_or(0),
+        profiles,
+    })
+}
+
+/// Merge imported profiles into `existing`
+/// Colliding ids are replaced when `overwrite` is set, otherwise the import gets a new id.
+/// Imported profiles never take the default flag from an existing default; the flag carries over only when
+/// the import replaces the current default, or when there is no default yet.
+fn merge_profiles(
+    mut existing: Vec<GamingProfile>,
+    imported: Vec<GamingProfile>,
+    overwrite: bool,
+) -> (Vec<GamingProfile>, ProfileImportResult) {
+    let mut result = ProfileImportResult::default();
+    let mut taken: HashSet<String> = existing.iter().map(|p| p.id.clone()).collect();
+    let mut seen_in_import = HashSet::new();
+
+    for mut profile in imported {
+        let has_default = existing.iter().any(|p| p.is_default);
+
+        // Ids repeated within the file are treated like collisions too
+        let duplicate_in_file = !seen_in_import.insert(profile.id.clone());
+        let collides = taken.contains(&profile.id);
+
+        if collides && overwrite && !duplicate_in_file {
+            if let Some(current) = existing.iter_mut().find(|p| p.id == profile.id) {
+                // Only keep the import's default flag if it's replacing the default, or nothing is default
+                profile.is_default = current.is_default || (profile.is_default && !has_default);
+                *current = profile;
+                result.replaced += 1;
+                continue;
+            }
+        }
+
+        if collides || duplicate_in_file || profile.id.trim().is_empty() {
+            profile.id = Uuid::new_v4().to_string();
+            result.renamed_ids += 1;
+        }
+        profile.is_default = profile.is_default && !has_default;
+        taken.insert(profile.id.clone());
+        existing.push(profile);
+        result.imported += 1;
+    }
+
+    (existing, result)
+}
+
 fn get_default_profiles() -> Vec<GamingProfile> {
     vec![
         GamingProfile {
@@ -99,6 +226,8 @@ fn get_default_profiles() -> Vec<GamingProfile> {
                 "compattelrunner.exe".to_string(),
             ],
             is_default: true,
+            processes_to_deprioritize: Vec::new(),
+            services_to_stop: Vec::new(),
         },
         GamingProfile {
             id: Uuid::new_v4().to_string(),
@@ -109,6 +238,8 @@ fn get_default_profiles() -> Vec<GamingProfile> {
                 "compattelrunner.exe".to_string(),
             ],
             is_default: false,
+            processes_to_deprioritize: Vec::new(),
+            services_to_stop: Vec::new(),
         },
         GamingProfile {
             id: Uuid::new_v4().to_string(),
@@ -131,19 +262,106 @@ fn get_default_profiles() -> Vec<GamingProfile> {
                 "gamebar.exe".to_string(),
             ],
             is_default: false,
+            processes_to_deprioritize: Vec::new(),
+            services_to_stop: Vec::new(),
         },
     ]
 }
 
 pub fn initialize_profiles() -> Result<(), String> {
-    let path = get_gaming_profiles_json_path();
+    if !get_gaming_profiles_json_path().exists() {
+        write_default_profiles()?;
+    }
 
-    if !path.exists() {
-        let list = GamingProfileList {
-            profiles: get_default_profiles(),
+    Ok(())
+}
+
+/// Replace all gaming profiles with the built-in ones
+pub fn write_default_profiles() -> Result<(), String> {
+    let list = GamingProfileList {
+        profiles: get_default_profiles(),
+    };
+    write_json_file(&get_gaming_profiles_json_path(), &list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str, name: &str, is_default: bool) -> GamingProfile {
+        GamingProfile {
+            id: id.to_string(),
+            name: name.to_string(),
+            processes_to_kill: vec!["onedrive.exe".to_string()],
+            is_default,
+            processes_to_deprioritize: Vec::new(),
+            services_to_stop: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let export = GamingProfileExport {
+            schema_version: PROFILE_EXPORT_SCHEMA_VERSION,
+            exported_at: 1_700_000_000,
+            profiles: vec![profile("a", "Streaming", true), profile("b", "Light", false)],
         };
-        write_json_file(&path, &list)?;
+        let json = serde_json::to_string_pretty(&export).unwrap();
+
+        let parsed = parse_export(&json).unwrap();
+        assert_eq!(parsed.profiles.len(), 2);
+        assert_eq!(parsed.profiles[0].name, "Streaming");
+        assert_eq!(parsed.profiles[0].processes_to_kill, vec!["onedrive.exe".to_string()]);
+
+        // Importing into an empty list keeps ids and the default flag
+        let (merged, result) = merge_profiles(Vec::new(), parsed.profiles, false);
+        assert_eq!(result.imported, 2);
+        assert_eq!(result.renamed_ids, 0);
+        assert_eq!(merged[0].id, "a");
+        assert!(merged[0].is_default);
     }
 
-    Ok(())
+    #[test]
+    fn test_parse_export_rejects_malformed_files() {
+        assert!(parse_export("not json").unwrap_err().contains("Not a valid JSON file"));
+        assert!(parse_export(r#"{"profiles": []}"#).unwrap_err().contains("Missing schema_version"));
+        assert!(parse_export(r#"{"schema_version": 99, "profiles": []}"#)
+            .unwrap_err()
+            .contains("Update Atlas"));
+        assert!(parse_export(r#"{"schema_version": 1, "profiles": []}"#)
+            .unwrap_err()
+            .contains("no profiles"));
+        assert!(parse_export(r#"{"schema_version": 1, "profiles": [{"id": "a"}]}"#)
+            .unwrap_err()
+            .starts_with("Profile #1 is invalid"));
+    }
+
+    #[test]
+    fn test_merge_regenerates_colliding_ids() {
+        let existing = vec![profile("a", "Local", true)];
+        let imported = vec![profile("a", "Remote", true), profile("c", "Other", false)];
+
+        let (merged, result) = merge_profiles(existing, imported, false);
+        assert_eq!(result.imported, 2);
+        assert_eq!(result.renamed_ids, 1);
+        assert_eq!(merged.len(), 3);
+        assert_ne!(merged[1].id, "a");
+        // The local default stays the only default
+        assert_eq!(merged.iter().filter(|p| p.is_default).count(), 1);
+        assert!(merged[0].is_default);
+    }
+
+    #[test]
+    fn test_merge_overwrite_replaces_in_place() {
+        let existing = vec![profile("a", "Local", true), profile("b", "Light", false)];
+        let imported = vec![profile("a", "Remote", false), profile("b", "Remote Light", true)];
+
+        let (merged, result) = merge_profiles(existing, imported, true);
+        assert_eq!(result.replaced, 2);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].name, "Remote");
+        // Replacing the default keeps it default; the import can't steal the flag
+        assert!(merged[0].is_default);
+        assert!(!merged[1].is_default);
+    }
 }
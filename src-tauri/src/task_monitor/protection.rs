@@ -0,0 +1,86 @@
+// User-defined protected processes
+// These are never killed, on top of the categories the categorizer already protects
+use std::sync::RwLock;
+
+use super::models::ProtectedProcessList;
+use super::normalize_process_name as normalize;
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::utils::get_protected_processes_json_path;
+
+lazy_static::lazy_static! {
+    /// Normalized names, loaded on first use so `can_kill` checks don't hit the disk
+    static ref PROTECTED: RwLock<Option<Vec<String>>> = RwLock::new(None);
+}
+
+fn load_list() -> Result<ProtectedProcessList, String> {
+    let path = get_protected_processes_json_path();
+
+    if !path.exists() {
+        return Ok(ProtectedProcessList::default());
+    }
+
+    read_json_file(&path)
+}
+
+fn save_list(list: &ProtectedProcessList) -> Result<(), String> {
+    write_json_file(&get_protected_processes_json_path(), list)?;
+
+    if let Ok(mut cache) = PROTECTED.write() {
+        *cache = Some(list.processes.iter().map(|n| normalize(n)).collect());
+    }
+    Ok(())
+}
+
+pub fn get_protected_processes() -> Result<Vec<String>, String> {
+    Ok(load_list()?.processes)
+}
+
+pub fn add_protected_process(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Process name cannot be empty".to_string());
+    }
+
+    let mut list = load_list()?;
+    let normalized = normalize(name);
+    if list.processes.iter().any(|p| normalize(p) == normalized) {
+        return Ok(());
+    }
+
+    list.processes.push(name.to_string());
+    save_list(&list)
+}
+
+/// Remove a name from the user list
+/// Processes protected by their category stay protected regardless
+pub fn remove_protected_process(name: &str) -> Result<(), String> {
+    let mut list = load_list()?;
+    let normalized = normalize(name);
+    let initial_len = list.processes.len();
+    list.processes.retain(|p| normalize(p) != normalized);
+
+    if list.processes.len() == initial_len {
+        return Err(format!("Process is not in the protected list: {}", name));
+    }
+
+    save_list(&list)
+}
+
+/// Whether the user has protected this process name
+pub fn is_user_protected(name: &str) -> bool {
+    let loaded = PROTECTED.read().map(|cache| cache.is_some()).unwrap_or(false);
+    if !loaded {
+        let names = load_list()
+            .map(|list| list.processes.iter().map(|n| normalize(n)).collect())
+            .unwrap_or_default();
+        if let Ok(mut cache) = PROTECTED.write() {
+            *cache = Some(names);
+        }
+    }
+
+    let normalized = normalize(name);
+    PROTECTED
+        .read()
+        .map(|cache| cache.as_ref().is_some_and(|names| names.contains(&normalized)))
+        .unwrap_or(false)
+}
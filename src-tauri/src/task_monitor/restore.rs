@@ -1,3 +1,5 @@
+use super::models::{ProcessPriority, ServiceStartType};
+use super::{priority, services, suspend};
 use crate::file_manager::{read_json_file, write_json_file};
 use crate::utils::get_restore_list_json_path;
 use serde::{Deserialize, Serialize};
@@ -11,12 +13,37 @@ pub struct KilledProcessInfo {
     pub working_dir: Option<String>,
 }
 
+/// A process whose priority was lowered by a gaming profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprioritizedProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub original_priority: ProcessPriority,
+    pub changed_at: i64,
+    /// Process start time (unix seconds), so a PID reused by a new process isn't changed on restore
+    #[serde(default)]
+    pub started_at: Option<u64>,
+}
+
+/// A running service stopped by a gaming profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoppedServiceInfo {
+    pub name: String,
+    /// Start type when it was stopped - only ever read, never changed
+    pub original_start_type: ServiceStartType,
+    pub stopped_at: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RestoreList {
     pub session_id: Option<String>,
     pub processes: Vec<KilledProcessInfo>,
     pub created_at: i64,
     pub detected_respawns: Vec<String>,
+    #[serde(default)]
+    pub deprioritized: Vec<DeprioritizedProcessInfo>,
+    #[serde(default)]
+    pub stopped_services: Vec<StoppedServiceInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +58,21 @@ pub struct RestoreResult {
     pub skipped_self_restoring: usize,
     pub failed: usize,
     pub errors: Vec<RestoreError>,
+    pub priorities_restored: usize,
+    pub services_restored: usize,
+    pub processes_resumed: usize,
+}
+
+/// Per-entry outcome of restoring the processes killed during a gaming session
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct SessionRestoreResult {
+    pub restored: Vec<KilledProcessInfo>,
+    pub skipped_self_restoring: Vec<KilledProcessInfo>,
+    pub failed: Vec<RestoreError>,
+    pub priorities_restored: usize,
+    pub services_restored: usize,
+    /// Processes suspended during the session and resumed
+    pub processes_resumed: usize,
 }
 
 pub fn load_restore_list() -> Result<RestoreList, String> {
@@ -59,17 +101,114 @@ pub fn clear_restore_list() -> Result<(), String> {
     Ok(())
 }
 
+/// Save the list, or delete the file once nothing is left to restore
+fn save_or_clear(list: &RestoreList) -> Result<(), String> {
+    if list.processes.is_empty() && list.deprioritized.is_empty() && list.stopped_services.is_empty() {
+        clear_restore_list()
+    } else {
+        save_restore_list(list)
+    }
+}
+
+/// Add a killed process; killing the same executable again only refreshes its kill time
 pub fn add_to_restore_list(process: KilledProcessInfo) -> Result<(), String> {
     let mut list = load_restore_list()?;
 
-    if list.processes.iter().any(|p| p.exe_path == process.exe_path) {
+    if let Some(existing) = list.processes.iter_mut().find(|p| p.exe_path == process.exe_path) {
+        existing.killed_at = process.killed_at;
+    } else {
+        list.processes.push(process);
+    }
+    save_restore_list(&list)
+}
+
+/// Most recently killed entry that can be relaunched
+pub fn latest_restorable(list: &RestoreList) -> Option<&KilledProcessInfo> {
+    list.processes
+        .iter()
+        .filter(|p| !p.is_self_restoring)
+        .max_by_key(|p| p.killed_at)
+}
+
+/// Relaunch a single entry from the restore list and remove it from the list
+/// The entry stays on the list when the relaunch fails
+pub fn restore_single_process(exe_path: &str) -> Result<KilledProcessInfo, String> {
+    let mut list = load_restore_list()?;
+    let index = list
+        .processes
+        .iter()
+        .position(|p| p.exe_path.eq_ignore_ascii_case(exe_path))
+        .ok_or_else(|| format!("{} is not on the restore list", exe_path))?;
+
+    let process = list.processes[index].clone();
+    if !std::path::Path::new(&process.exe_path).exists() {
+        return Err(format!(
+            "Can't relaunch {}: {} no longer exists",
+            process.name, process.exe_path
+        ));
+    }
+
+    restore_process(&process)?;
+
+    list.processes.remove(index);
+    save_or_clear(&list)?;
+    Ok(process)
+}
+
+/// Remember a process's original priority; the first recorded priority wins if it is lowered twice
+pub fn add_deprioritized(process: DeprioritizedProcessInfo) -> Result<(), String> {
+    let mut list = load_restore_list()?;
+
+    if list.deprioritized.iter().any(|p| p.pid == process.pid) {
+        return Ok(());
+    }
+
+    list.deprioritized.push(process);
+    save_restore_list(&list)
+}
+
+/// Put lowered processes back to their original priority
+/// Processes that have exited since, and PIDs now used by another process, are silently skipped
+pub fn restore_priorities(entries: &[DeprioritizedProcessInfo]) -> usize {
+    entries
+        .iter()
+        .filter(|p| is_same_process(p, super::process_start_time(p.pid, &p.name)))
+        .filter(|p| priority::set_priority(p.pid, p.original_priority).is_ok())
+        .count()
+}
+
+/// Whether the process now running as the entry's PID and name (started at `running_since`) is the one
+/// that was lowered; entries saved before start times were recorded only match on PID and name
+fn is_same_process(entry: &DeprioritizedProcessInfo, running_since: Option<u64>) -> bool {
+    match (running_since, entry.started_at) {
+        (None, _) => false,
+        (Some(_), None) => true,
+        (Some(running), Some(recorded)) => running == recorded,
+    }
+}
+
+pub fn add_stopped_service(service: StoppedServiceInfo) -> Result<(), String> {
+    let mut list = load_restore_list()?;
+
+    if list.stopped_services.iter().any(|s| s.name.eq_ignore_ascii_case(&service.name)) {
         return Ok(());
     }
 
-    list.processes.push(process);
+    list.stopped_services.push(service);
     save_restore_list(&list)
 }
 
+/// Start stopped services again (services that were Disabled in the meantime are left alone)
+pub fn restore_services(entries: &[StoppedServiceInfo]) -> usize {
+    entries
+        .iter()
+        .filter(|s| {
+            services::get_service(&s.name).is_ok_and(|(_, start_type)| start_type != ServiceStartType::Disabled)
+        })
+        .filter(|s| services::start_service(&s.name).is_ok())
+        .count()
+}
+
 #[allow(dead_code)]
 pub fn mark_as_self_restoring(exe_path: &str) -> Result<(), String> {
     let mut list = load_restore_list()?;
@@ -177,6 +316,65 @@ pub fn restore_all_processes(restore_list: &RestoreList) -> RestoreResult {
     result
 }
 
+/// Restore entries killed (or deprioritized, stopped, suspended) at or after `since` (unix seconds)
+/// Restored and self-restoring entries are removed from the list; failed ones stay for a manual retry
+pub fn restore_killed_since(since: i64) -> Result<SessionRestoreResult, String> {
+    let mut list = load_restore_list()?;
+    let (session, older): (Vec<_>, Vec<_>) =
+        list.processes.into_iter().partition(|p| p.killed_at >= since);
+
+    let (mut result, kept) = restore_entries(session, restore_process);
+
+    let (lowered, older_lowered): (Vec<_>, Vec<_>) =
+        list.deprioritized.into_iter().partition(|p| p.changed_at >= since);
+    result.priorities_restored = restore_priorities(&lowered);
+
+    let (stopped, older_stopped): (Vec<_>, Vec<_>) =
+        list.stopped_services.into_iter().partition(|s| s.stopped_at >= since);
+    result.services_restored = restore_services(&stopped);
+    result.processes_resumed = suspend::resume_suspended_since(since);
+
+    list.processes = older;
+    list.processes.extend(kept);
+    list.deprioritized = older_lowered;
+    list.stopped_services = older_stopped;
+    save_or_clear(&list)?;
+
+    Ok(result)
+}
+
+/// Run `restore` over each entry, returning the outcome and the entries that should stay on the list
+fn restore_entries<F>(entries: Vec<KilledProcessInfo>, restore: F) -> (SessionRestoreResult, Vec<KilledProcessInfo>)
+where
+    F: Fn(&KilledProcessInfo) -> Result<(), String>,
+{
+    let mut result = SessionRestoreResult::default();
+    let mut kept = Vec::new();
+
+    for process in entries {
+        if process.is_self_restoring {
+            result.skipped_self_restoring.push(process);
+            continue;
+        }
+
+        match restore(&process) {
+            Ok(()) => {
+                result.restored.push(process);
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+            Err(e) => {
+                result.failed.push(RestoreError {
+                    exe_path: process.exe_path.clone(),
+                    error: e,
+                });
+                kept.push(process);
+            }
+        }
+    }
+
+    (result, kept)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,6 +407,8 @@ mod tests {
             }],
             created_at: 12345,
             detected_respawns: vec![],
+            deprioritized: vec![],
+            stopped_services: vec![],
         };
 
         let json = serde_json::to_string(&list).unwrap();
@@ -218,4 +418,74 @@ mod tests {
         assert_eq!(deserialized.processes.len(), 1);
         assert_eq!(deserialized.processes[0].name, "app.exe");
     }
+
+    #[test]
+    fn test_reused_pid_is_not_restored() {
+        let entry = DeprioritizedProcessInfo {
+            pid: 1234,
+            name: "discord.exe".to_string(),
+            original_priority: ProcessPriority::Normal,
+            changed_at: 0,
+            started_at: Some(1_000),
+        };
+        assert!(is_same_process(&entry, Some(1_000)));
+        // Same PID and name, but a process started later
+        assert!(!is_same_process(&entry, Some(2_000)));
+        // Exited, or the PID now belongs to something else
+        assert!(!is_same_process(&entry, None));
+        // Entries saved before start times were recorded fall back to PID and name
+        let legacy = DeprioritizedProcessInfo { started_at: None, ..entry };
+        assert!(is_same_process(&legacy, Some(2_000)));
+    }
+
+    #[test]
+    fn test_latest_restorable() {
+        let entry = |name: &str, killed_at: i64, is_self_restoring: bool| KilledProcessInfo {
+            exe_path: format!("C:\\test\\{}", name),
+            name: name.to_string(),
+            killed_at,
+            is_self_restoring,
+            working_dir: None,
+        };
+        let mut list = RestoreList {
+            processes: vec![
+                entry("old.exe", 100, false),
+                entry("recent.exe", 200, false),
+                entry("updater.exe", 300, true),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(latest_restorable(&list).unwrap().name, "recent.exe");
+
+        list.processes.retain(|p| p.is_self_restoring);
+        assert!(latest_restorable(&list).is_none());
+    }
+
+    #[test]
+    fn test_restore_entries_keeps_failures() {
+        let entry = |name: &str, is_self_restoring: bool| KilledProcessInfo {
+            exe_path: format!("C:\\test\\{}", name),
+            name: name.to_string(),
+            killed_at: 100,
+            is_self_restoring,
+            working_dir: None,
+        };
+        let entries = vec![entry("ok.exe", false), entry("broken.exe", false), entry("updater.exe", true)];
+
+        let (result, kept) = restore_entries(entries, |p| {
+            if p.name == "broken.exe" {
+                Err("Executable not found".to_string())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(result.restored.len(), 1);
+        assert_eq!(result.restored[0].name, "ok.exe");
+        assert_eq!(result.skipped_self_restoring.len(), 1);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].name, "broken.exe");
+    }
 }
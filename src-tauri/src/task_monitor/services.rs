@@ -0,0 +1,303 @@
+// Windows services (stop/start only - start types are never changed, so nothing ends up Disabled)
+#![cfg_attr(not(windows), allow(dead_code))]
+use super::models::{ServiceInfo, ServiceStartType, ServiceState, ServicesSummary};
+
+/// Services that are never stopped, whatever a profile says
+const CRITICAL_SERVICES: &[&str] = &[
+    // Core OS
+    "rpcss",
+    "rpceptmapper",
+    "dcomlaunch",
+    "lsm",
+    "samss",
+    "eventlog",
+    "plugplay",
+    "power",
+    "winmgmt",
+    "profsvc",
+    "schedule",
+    "usermanager",
+    "brokerinfrastructure",
+    "coremessagingregistrar",
+    "systemeventsbroker",
+    "gpsvc",
+    "cryptsvc",
+    // Networking
+    "dhcp",
+    "dnscache",
+    "nsi",
+    "bfe",
+    "mpssvc",
+    // Audio
+    "audiosrv",
+    "audioendpointbuilder",
+    // Security
+    "windefend",
+    "wscsvc",
+    // Anti-cheat
+    "vgc",
+    "vgk",
+    "easyanticheat",
+    "easyanticheat_eos",
+    "beservice",
+];
+
+pub fn is_critical_service(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    CRITICAL_SERVICES.contains(&lower.as_str())
+}
+
+#[cfg(windows)]
+mod win {
+    use super::*;
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr::{null, null_mut};
+    use windows_sys::Win32::System::Services::*;
+
+    pub fn wide(s: &str) -> Vec<u16> {
+        OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    pub unsafe fn from_wide(ptr: *const u16) -> String {
+        if ptr.is_null() {
+            return String::new();
+        }
+        let len = (0..).take_while(|&i| *ptr.add(i) != 0).count();
+        String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len))
+    }
+
+    /// Service handle that closes itself
+    pub struct Handle(pub SC_HANDLE);
+
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            unsafe {
+                CloseServiceHandle(self.0);
+            }
+        }
+    }
+
+    pub fn open_manager(access: u32) -> Result<Handle, String> {
+        let handle = unsafe { OpenSCManagerW(null(), null(), access) };
+        if handle.is_null() {
+            return Err("Failed to open the service control manager (Access Denied?)".to_string());
+        }
+        Ok(Handle(handle))
+    }
+
+    pub fn open_service(manager: &Handle, name: &str, access: u32) -> Result<Handle, String> {
+        let handle = unsafe { OpenServiceW(manager.0, wide(name).as_ptr(), access) };
+        if handle.is_null() {
+            return Err(format!("Failed to open service {} (Access Denied or not installed)", name));
+        }
+        Ok(Handle(handle))
+    }
+
+    pub fn state_from(raw: u32) -> ServiceState {
+        match raw {
+            SERVICE_RUNNING => ServiceState::Running,
+            SERVICE_STOPPED => ServiceState::Stopped,
+            SERVICE_START_PENDING => ServiceState::StartPending,
+            SERVICE_STOP_PENDING => ServiceState::StopPending,
+            SERVICE_PAUSED => ServiceState::Paused,
+            _ => ServiceState::Unknown,
+        }
+    }
+
+    pub fn start_type_from(raw: u32) -> ServiceStartType {
+        match raw {
+            SERVICE_AUTO_START => ServiceStartType::Automatic,
+            SERVICE_DEMAND_START => ServiceStartType::Manual,
+            SERVICE_DISABLED => ServiceStartType::Disabled,
+            SERVICE_BOOT_START | SERVICE_SYSTEM_START => ServiceStartType::System,
+            _ => ServiceStartType::Unknown,
+        }
+    }
+
+    pub fn query_start_type(service: &Handle) -> ServiceStartType {
+        unsafe {
+            let mut needed = 0u32;
+            QueryServiceConfigW(service.0, null_mut(), 0, &mut needed);
+            if needed == 0 {
+                return ServiceStartType::Unknown;
+            }
+            // u64 buffer keeps the config struct aligned
+            let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+            let config = buffer.as_mut_ptr() as *mut QUERY_SERVICE_CONFIGW;
+            if QueryServiceConfigW(service.0, config, needed, &mut needed) == 0 {
+                return ServiceStartType::Unknown;
+            }
+            start_type_from((*config).dwStartType)
+        }
+    }
+
+    pub fn query_state(service: &Handle) -> ServiceState {
+        unsafe {
+            let mut status: SERVICE_STATUS = std::mem::zeroed();
+            if QueryServiceStatus(service.0, &mut status) == 0 {
+                return ServiceState::Unknown;
+            }
+            state_from(status.dwCurrentState)
+        }
+    }
+}
+
+#[cfg(windows)]
+pub fn get_services_summary() -> Result<ServicesSummary, String> {
+    use std::ptr::{null, null_mut};
+    use win::*;
+    use windows_sys::Win32::System::Services::*;
+
+    let manager = open_manager(SC_MANAGER_CONNECT | SC_MANAGER_ENUMERATE_SERVICE)?;
+    let mut services = Vec::new();
+
+    unsafe {
+        let mut needed = 0u32;
+        let mut returned = 0u32;
+        let mut resume = 0u32;
+        EnumServicesStatusExW(
+            manager.0,
+            SC_ENUM_PROCESS_INFO,
+            SERVICE_WIN32,
+            SERVICE_STATE_ALL,
+            null_mut(),
+            0,
+            &mut needed,
+            &mut returned,
+            &mut resume,
+            null(),
+        );
+        if needed == 0 {
+            return Err("Failed to enumerate services".to_string());
+        }
+
+        let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+        resume = 0;
+        if EnumServicesStatusExW(
+            manager.0,
+            SC_ENUM_PROCESS_INFO,
+            SERVICE_WIN32,
+            SERVICE_STATE_ALL,
+            buffer.as_mut_ptr() as *mut u8,
+            (buffer.len() * 8) as u32,
+            &mut needed,
+            &mut returned,
+            &mut resume,
+            null(),
+        ) == 0
+        {
+            return Err("Failed to enumerate services".to_string());
+        }
+
+        let entries = std::slice::from_raw_parts(
+            buffer.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESSW,
+            returned as usize,
+        );
+        for entry in entries {
+            let name = from_wide(entry.lpServiceName);
+            let start_type = open_service(&manager, &name, SERVICE_QUERY_CONFIG)
+                .map(|service| query_start_type(&service))
+                .unwrap_or(ServiceStartType::Unknown);
+
+            services.push(ServiceInfo {
+                display_name: from_wide(entry.lpDisplayName),
+                state: state_from(entry.ServiceStatusProcess.dwCurrentState),
+                start_type,
+                can_stop: !is_critical_service(&name),
+                name,
+            });
+        }
+    }
+
+    services.sort_by(|a, b| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()));
+
+    Ok(ServicesSummary {
+        running: services.iter().filter(|s| s.state == ServiceState::Running).count(),
+        stopped: services.iter().filter(|s| s.state == ServiceState::Stopped).count(),
+        services,
+    })
+}
+
+/// Current state and start type of a single service
+#[cfg(windows)]
+pub fn get_service(name: &str) -> Result<(ServiceState, ServiceStartType), String> {
+    use win::*;
+    use windows_sys::Win32::System::Services::*;
+
+    let manager = open_manager(SC_MANAGER_CONNECT)?;
+    let service = open_service(&manager, name, SERVICE_QUERY_STATUS | SERVICE_QUERY_CONFIG)?;
+    Ok((query_state(&service), query_start_type(&service)))
+}
+
+#[cfg(windows)]
+pub fn stop_service(name: &str) -> Result<(), String> {
+    use win::*;
+    use windows_sys::Win32::System::Services::*;
+
+    if is_critical_service(name) {
+        return Err(format!("Cannot stop critical service: {}", name));
+    }
+
+    let manager = open_manager(SC_MANAGER_CONNECT)?;
+    let service = open_service(&manager, name, SERVICE_STOP | SERVICE_QUERY_STATUS)?;
+
+    unsafe {
+        let mut status: SERVICE_STATUS = std::mem::zeroed();
+        if ControlService(service.0, SERVICE_CONTROL_STOP, &mut status) == 0 {
+            return Err(format!("Failed to stop service {}", name));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn start_service(name: &str) -> Result<(), String> {
+    use win::*;
+    use windows_sys::Win32::System::Services::*;
+
+    let manager = open_manager(SC_MANAGER_CONNECT)?;
+    let service = open_service(&manager, name, SERVICE_START | SERVICE_QUERY_STATUS)?;
+
+    unsafe {
+        if StartServiceW(service.0, 0, std::ptr::null()) == 0 {
+            return Err(format!("Failed to start service {}", name));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn get_services_summary() -> Result<ServicesSummary, String> {
+    Err("Service control is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn get_service(_name: &str) -> Result<(ServiceState, ServiceStartType), String> {
+    Err("Service control is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn stop_service(_name: &str) -> Result<(), String> {
+    Err("Service control is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn start_service(_name: &str) -> Result<(), String> {
+    Err("Service control is only supported on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_critical_services_are_protected() {
+        assert!(is_critical_service("RpcSs"));
+        assert!(is_critical_service("vgc"));
+        assert!(!is_critical_service("Spooler"));
+        assert!(!is_critical_service("SysMain"));
+    }
+}
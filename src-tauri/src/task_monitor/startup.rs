@@ -0,0 +1,318 @@
+// Startup programs (Run registry keys and Startup folders)
+// Enabling/disabling goes through the same StartupApproved values Task Manager uses, so entries are never deleted
+#![cfg_attr(not(windows), allow(dead_code))]
+use super::descriptions::{get_friendly_name, get_process_description};
+use super::models::{StartupImpact, StartupItem, StartupLocation};
+
+#[cfg(windows)]
+use winreg::enums::*;
+#[cfg(windows)]
+use winreg::RegKey;
+
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+const RUN32_KEY: &str = "Software\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run";
+const APPROVED_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved";
+
+/// StartupApproved values are 12 bytes: a state byte (even = enabled, odd = disabled), padding, then a FILETIME
+const APPROVED_ENABLED: u8 = 0x02;
+const APPROVED_DISABLED: u8 = 0x03;
+
+impl StartupLocation {
+    fn id_prefix(&self) -> &'static str {
+        match self {
+            StartupLocation::RegistryUser => "hkcu_run",
+            StartupLocation::RegistryMachine => "hklm_run",
+            StartupLocation::RegistryMachine32 => "hklm_run32",
+            StartupLocation::StartupFolderUser => "user_folder",
+            StartupLocation::StartupFolderCommon => "common_folder",
+        }
+    }
+
+    /// StartupApproved subkey holding the enabled state for this location
+    fn approved_subkey(&self) -> &'static str {
+        match self {
+            StartupLocation::RegistryUser | StartupLocation::RegistryMachine => "Run",
+            StartupLocation::RegistryMachine32 => "Run32",
+            StartupLocation::StartupFolderUser | StartupLocation::StartupFolderCommon => "StartupFolder",
+        }
+    }
+
+    #[cfg(windows)]
+    fn approved_hive(&self) -> RegKey {
+        match self {
+            StartupLocation::RegistryUser | StartupLocation::StartupFolderUser => RegKey::predef(HKEY_CURRENT_USER),
+            _ => RegKey::predef(HKEY_LOCAL_MACHINE),
+        }
+    }
+}
+
+fn make_id(location: &StartupLocation, name: &str) -> String {
+    format!("{}:{}", location.id_prefix(), name)
+}
+
+/// Executable path from a Run command line (quoted or not, with or without arguments)
+fn parse_command_path(command: &str) -> Option<String> {
+    let command = command.trim();
+    if let Some(rest) = command.strip_prefix('"') {
+        return rest.split('"').next().filter(|p| !p.is_empty()).map(str::to_string);
+    }
+
+    // Unquoted paths can contain spaces; cut after the first ".exe". Searched on the original
+    // bytes, as lowercasing can change their length; an ASCII match always lies on a char boundary
+    let end = command.as_bytes().windows(4).position(|w| w.eq_ignore_ascii_case(b".exe"));
+    if let Some(end) = end {
+        return Some(command[..end + 4].to_string());
+    }
+    command.split_whitespace().next().map(str::to_string)
+}
+
+/// Whether a StartupApproved value marks the entry as enabled (missing value = enabled)
+fn is_approved(value: Option<&[u8]>) -> bool {
+    match value.and_then(|v| v.first()) {
+        Some(state) => state % 2 == 0,
+        None => true,
+    }
+}
+
+/// Rough boot impact from the size of the executable (Task Manager's measured impact isn't exposed)
+fn estimate_impact(file_size: u64) -> StartupImpact {
+    const MB: u64 = 1024 * 1024;
+    if file_size >= 50 * MB {
+        StartupImpact::High
+    } else if file_size >= 5 * MB {
+        StartupImpact::Medium
+    } else {
+        StartupImpact::Low
+    }
+}
+
+fn build_item(location: StartupLocation, name: String, command: String, enabled: bool) -> StartupItem {
+    let exe_path = parse_command_path(&command);
+    let exe_name = exe_path
+        .as_deref()
+        .and_then(|p| std::path::Path::new(p).file_name())
+        .map(|n| n.to_string_lossy().to_string());
+
+    // Known entries (from the process descriptions table) get their friendly name
+    let display_name = exe_name
+        .as_deref()
+        .map(get_friendly_name)
+        .filter(|friendly| Some(friendly.as_str()) != exe_name.as_deref())
+        .unwrap_or_else(|| name.clone());
+    let description = exe_name.as_deref().and_then(get_process_description);
+
+    let impact = exe_path
+        .as_deref()
+        .and_then(|p| std::fs::metadata(p).ok())
+        .map(|m| estimate_impact(m.len()));
+    let publisher = exe_path.as_deref().and_then(read_publisher);
+
+    StartupItem {
+        id: make_id(&location, &name),
+        name,
+        display_name,
+        command,
+        exe_path,
+        location,
+        enabled,
+        publisher,
+        impact,
+        description,
+    }
+}
+
+/// Enumerate startup entries (read-only)
+#[cfg(windows)]
+pub fn get_startup_items() -> Result<Vec<StartupItem>, String> {
+    let mut items = Vec::new();
+
+    let run_keys = [
+        (StartupLocation::RegistryUser, HKEY_CURRENT_USER, RUN_KEY),
+        (StartupLocation::RegistryMachine, HKEY_LOCAL_MACHINE, RUN_KEY),
+        (StartupLocation::RegistryMachine32, HKEY_LOCAL_MACHINE, RUN32_KEY),
+    ];
+    for (location, hive, path) in run_keys {
+        let Ok(key) = RegKey::predef(hive).open_subkey(path) else {
+            continue;
+        };
+        for (name, _) in key.enum_values().flatten() {
+            let Ok(command) = key.get_value::<String, _>(&name) else {
+                continue;
+            };
+            let enabled = is_approved(read_approved(&location, &name).as_deref());
+            items.push(build_item(location.clone(), name, command, enabled));
+        }
+    }
+
+    for (location, dir) in startup_folders() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.eq_ignore_ascii_case("desktop.ini") {
+                continue;
+            }
+            let command = entry.path().to_string_lossy().to_string();
+            let enabled = is_approved(read_approved(&location, &name).as_deref());
+            items.push(build_item(location.clone(), name, command, enabled));
+        }
+    }
+
+    Ok(items)
+}
+
+#[cfg(windows)]
+fn startup_folders() -> Vec<(StartupLocation, std::path::PathBuf)> {
+    let suffix = "Microsoft\\Windows\\Start Menu\\Programs\\Startup";
+    let mut folders = Vec::new();
+    if let Ok(appdata) = std::env::var("APPDATA") {
+        folders.push((StartupLocation::StartupFolderUser, std::path::Path::new(&appdata).join(suffix)));
+    }
+    if let Ok(programdata) = std::env::var("PROGRAMDATA") {
+        folders.push((StartupLocation::StartupFolderCommon, std::path::Path::new(&programdata).join(suffix)));
+    }
+    folders
+}
+
+#[cfg(windows)]
+fn read_approved(location: &StartupLocation, name: &str) -> Option<Vec<u8>> {
+    let key = location
+        .approved_hive()
+        .open_subkey(format!("{}\\{}", APPROVED_KEY, location.approved_subkey()))
+        .ok()?;
+    key.get_raw_value(name).ok().map(|v| v.bytes)
+}
+
+#[cfg(windows)]
+fn set_enabled(id: &str, enabled: bool) -> Result<(), String> {
+    let item = get_startup_items()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| format!("Startup item not found: {}", id))?;
+
+    let (key, _) = item
+        .location
+        .approved_hive()
+        .create_subkey(format!("{}\\{}", APPROVED_KEY, item.location.approved_subkey()))
+        .map_err(|e| format!("Failed to open StartupApproved key: {}", e))?;
+
+    let mut bytes = vec![0u8; 12];
+    bytes[0] = if enabled { APPROVED_ENABLED } else { APPROVED_DISABLED };
+    if !enabled {
+        // Task Manager stores when the entry was disabled
+        let filetime = (chrono::Utc::now().timestamp() + 11_644_473_600) as u64 * 10_000_000;
+        bytes[4..].copy_from_slice(&filetime.to_le_bytes());
+    }
+
+    key.set_raw_value(
+        &item.name,
+        &winreg::RegValue {
+            bytes,
+            vtype: REG_BINARY,
+        },
+    )
+    .map_err(|e| format!("Failed to update startup item: {}", e))
+}
+
+#[cfg(windows)]
+pub fn disable_startup_item(id: &str) -> Result<(), String> {
+    set_enabled(id, false)
+}
+
+#[cfg(windows)]
+pub fn enable_startup_item(id: &str) -> Result<(), String> {
+    set_enabled(id, true)
+}
+
+/// CompanyName from the executable's version resource
+#[cfg(windows)]
+fn read_publisher(exe_path: &str) -> Option<String> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::{GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW};
+
+    let wide = |s: &str| -> Vec<u16> { OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect() };
+    let path = wide(exe_path);
+
+    unsafe {
+        let size = GetFileVersionInfoSizeW(path.as_ptr(), std::ptr::null_mut());
+        if size == 0 {
+            return None;
+        }
+        let mut data = vec![0u8; size as usize];
+        if GetFileVersionInfoW(path.as_ptr(), 0, size, data.as_mut_ptr() as *mut _) == 0 {
+            return None;
+        }
+
+        // First language/codepage pair
+        let mut ptr: *mut std::ffi::c_void = std::ptr::null_mut();
+        let mut len = 0u32;
+        let query = wide("\\VarFileInfo\\Translation");
+        if VerQueryValueW(data.as_ptr() as *const _, query.as_ptr(), &mut ptr, &mut len) == 0 || len < 4 {
+            return None;
+        }
+        let lang = *(ptr as *const u16);
+        let codepage = *(ptr as *const u16).add(1);
+
+        let query = wide(&format!("\\StringFileInfo\\{:04x}{:04x}\\CompanyName", lang, codepage));
+        if VerQueryValueW(data.as_ptr() as *const _, query.as_ptr(), &mut ptr, &mut len) == 0 || len == 0 {
+            return None;
+        }
+        let chars = std::slice::from_raw_parts(ptr as *const u16, len as usize);
+        let end = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+        let name = String::from_utf16_lossy(&chars[..end]).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+#[cfg(not(windows))]
+fn read_publisher(_exe_path: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(windows))]
+pub fn get_startup_items() -> Result<Vec<StartupItem>, String> {
+    Err("Startup programs are only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn disable_startup_item(_id: &str) -> Result<(), String> {
+    Err("Startup programs are only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn enable_startup_item(_id: &str) -> Result<(), String> {
+    Err("Startup programs are only supported on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command_path() {
+        assert_eq!(
+            parse_command_path("\"C:\\Program Files\\App\\app.exe\" --minimized"),
+            Some("C:\\Program Files\\App\\app.exe".to_string())
+        );
+        assert_eq!(
+            parse_command_path("C:\\Program Files\\App\\app.exe /background"),
+            Some("C:\\Program Files\\App\\app.exe".to_string())
+        );
+        assert_eq!(parse_command_path("rundll32.dll,Entry"), Some("rundll32.dll,Entry".to_string()));
+        // "İ" is two bytes but lowercases to three
+        assert_eq!(
+            parse_command_path("C:\\İİİ\\Tool.EXE --tray"),
+            Some("C:\\İİİ\\Tool.EXE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_approved() {
+        assert!(is_approved(None));
+        assert!(is_approved(Some(&[0x02, 0, 0, 0])));
+        assert!(is_approved(Some(&[0x06, 0, 0, 0])));
+        assert!(!is_approved(Some(&[0x03, 0, 0, 0])));
+    }
+}
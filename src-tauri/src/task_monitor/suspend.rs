@@ -0,0 +1,238 @@
+// Suspending processes as a gentler alternative to killing them (e.g. OneDrive mid-upload)
+// Suspended PIDs are kept in suspended_processes.json as well, so processes still frozen when
+// Atlas exits or crashes are resumed on the next start; the end-of-session restore resumes them too
+#![cfg_attr(not(windows), allow(dead_code))]
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::categorizer::categorize_process;
+use super::get_all_processes;
+use super::models::{ProcessCategory, ProcessInfo};
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::utils::get_suspended_processes_json_path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SuspendedProcess {
+    name: String,
+    suspended_at: i64,
+}
+
+lazy_static::lazy_static! {
+    static ref SUSPENDED: Mutex<HashMap<u32, SuspendedProcess>> = Mutex::new(HashMap::new());
+}
+
+/// Write the suspended set, or delete the file once nothing is suspended
+fn persist(suspended: &HashMap<u32, SuspendedProcess>) {
+    let path = get_suspended_processes_json_path();
+    let result = if suspended.is_empty() {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    } else {
+        write_json_file(&path, suspended)
+    };
+    if let Err(e) = result {
+        warn!("Failed to save suspended process list: {}", e);
+    }
+}
+
+/// Resume processes a previous run left suspended, matched by PID and name
+/// Run once at startup
+pub fn resume_left_suspended() {
+    let path = get_suspended_processes_json_path();
+    if !path.exists() {
+        return;
+    }
+    let left: HashMap<u32, SuspendedProcess> = match read_json_file(&path) {
+        Ok(left) => left,
+        Err(e) => {
+            warn!("Failed to read suspended process list: {}", e);
+            return;
+        }
+    };
+
+    let processes = get_all_processes();
+    // Held until the file is rewritten, so a process suspended meanwhile isn't dropped from it
+    let Ok(suspended) = SUSPENDED.lock() else {
+        return;
+    };
+    let mut resumed = 0;
+    for (pid, entry) in &left {
+        // A PID reused by another program is left alone, as is one suspended again by this run
+        if suspended.contains_key(pid) || !processes.iter().any(|p| p.pid == *pid && p.name == entry.name) {
+            continue;
+        }
+        match set_suspended(*pid, false) {
+            Ok(()) => resumed += 1,
+            Err(e) => warn!("Failed to resume {} ({}) left suspended: {}", entry.name, pid, e),
+        }
+    }
+    info!("Resumed {} of {} processes left suspended by the last run", resumed, left.len());
+    persist(&suspended);
+}
+
+/// PID -> name of every process Atlas currently holds suspended
+/// Callers compare the name so a reused PID isn't reported as suspended
+pub fn suspended_processes() -> HashMap<u32, String> {
+    SUSPENDED
+        .lock()
+        .map(|suspended| suspended.iter().map(|(pid, p)| (*pid, p.name.clone())).collect())
+        .unwrap_or_default()
+}
+
+/// Same rules as killing, plus system, driver and audio processes, which hang the machine or the sound when frozen
+pub fn check_can_suspend(process: &ProcessInfo) -> Result<(), String> {
+    if !process.can_kill {
+        return Err(format!("Cannot suspend protected process: {}", process.name));
+    }
+
+    let builtin = categorize_process(&process.name, process.exe_path.as_deref());
+    let refused = [&process.category, &builtin]
+        .iter()
+        .any(|c| matches!(c, ProcessCategory::SystemCritical | ProcessCategory::DriverHardware));
+    if refused || process.name.to_lowercase().contains("audio") {
+        return Err(format!("Cannot suspend system, driver or audio process: {}", process.name));
+    }
+
+    Ok(())
+}
+
+pub fn suspend_process(pid: u32) -> Result<(), String> {
+    let process = get_all_processes()
+        .into_iter()
+        .find(|p| p.pid == pid)
+        .ok_or_else(|| "Process not found".to_string())?;
+    check_can_suspend(&process)?;
+
+    let mut suspended = SUSPENDED.lock().map_err(|_| "Suspended process list is unavailable".to_string())?;
+    // Suspending twice would need two resumes
+    if suspended.get(&pid).is_some_and(|p| p.name == process.name) {
+        return Ok(());
+    }
+
+    set_suspended(pid, true)?;
+    suspended.insert(
+        pid,
+        SuspendedProcess {
+            name: process.name,
+            suspended_at: chrono::Utc::now().timestamp(),
+        },
+    );
+    persist(&suspended);
+    Ok(())
+}
+
+/// Resume a process suspended through Atlas (processes suspended by anything else are left alone)
+pub fn resume_process(pid: u32) -> Result<(), String> {
+    // Listed before locking - building the process list reads the suspended set
+    let processes = get_all_processes();
+
+    let mut suspended = SUSPENDED.lock().map_err(|_| "Suspended process list is unavailable".to_string())?;
+    let entry = suspended
+        .get(&pid)
+        .ok_or_else(|| "Process was not suspended by Atlas".to_string())?;
+
+    let still_running = processes.iter().any(|p| p.pid == pid && p.name == entry.name);
+    if !still_running {
+        suspended.remove(&pid);
+        persist(&suspended);
+        return Err("Process no longer exists".to_string());
+    }
+
+    set_suspended(pid, false)?;
+    suspended.remove(&pid);
+    persist(&suspended);
+    Ok(())
+}
+
+/// Resume everything suspended at or after `since` (unix seconds), returning how many were resumed
+/// Entries for processes that have exited are dropped
+pub fn resume_suspended_since(since: i64) -> usize {
+    let pids: Vec<u32> = match SUSPENDED.lock() {
+        Ok(suspended) => suspended
+            .iter()
+            .filter(|(_, p)| p.suspended_at >= since)
+            .map(|(pid, _)| *pid)
+            .collect(),
+        Err(_) => return 0,
+    };
+
+    pids.into_iter().filter(|pid| resume_process(*pid).is_ok()).count()
+}
+
+#[cfg(windows)]
+fn set_suspended(pid: u32, suspend: bool) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, NTSTATUS};
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_SUSPEND_RESUME};
+
+    #[link(name = "ntdll")]
+    extern "system" {
+        fn NtSuspendProcess(process: HANDLE) -> NTSTATUS;
+        fn NtResumeProcess(process: HANDLE) -> NTSTATUS;
+    }
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_SUSPEND_RESUME, 0, pid);
+        if handle.is_null() {
+            return Err("Failed to open process (Access Denied or process no longer exists)".to_string());
+        }
+        let status = if suspend {
+            NtSuspendProcess(handle)
+        } else {
+            NtResumeProcess(handle)
+        };
+        CloseHandle(handle);
+
+        if status < 0 {
+            let action = if suspend { "suspend" } else { "resume" };
+            return Err(format!("Failed to {} process (NTSTATUS 0x{:08X})", action, status));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn set_suspended(_pid: u32, _suspend: bool) -> Result<(), String> {
+    Err("Suspending processes is only supported on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, category: ProcessCategory, can_kill: bool) -> ProcessInfo {
+        ProcessInfo {
+            pid: 1,
+            name: name.to_string(),
+            display_name: name.to_string(),
+            exe_path: None,
+            cpu_usage: 0.0,
+            memory_mb: 100.0,
+            gpu_usage: None,
+            gpu_percent: None,
+            network_sent_bps: None,
+            network_received_bps: None,
+            category,
+            description: None,
+            can_kill,
+            parent_pid: None,
+            custom_category: None,
+            priority: None,
+            excluded: false,
+            suspended: false,
+        }
+    }
+
+    #[test]
+    fn test_check_can_suspend() {
+        assert!(check_can_suspend(&process("OneDrive.exe", ProcessCategory::MicrosoftBloat, true)).is_ok());
+        assert!(check_can_suspend(&process("vgc.exe", ProcessCategory::AntiCheatProtected, false)).is_err());
+        // User overrides can make these killable, but freezing them is still refused
+        assert!(check_can_suspend(&process("audiodg.exe", ProcessCategory::UserApplication, true)).is_err());
+        assert!(check_can_suspend(&process("nvcontainer.exe", ProcessCategory::DriverHardware, true)).is_err());
+    }
+}
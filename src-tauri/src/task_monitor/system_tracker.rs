@@ -2,14 +2,22 @@ use std::sync::RwLock;
 use std::time::{Duration, Instant};
 use sysinfo::{ProcessRefreshKind, RefreshKind, System};
 
-use super::categorizer::{can_kill_process, categorize_process};
+use super::categorizer::{can_kill_process, categorize_process, is_always_protected};
 use super::descriptions::{get_friendly_name, get_process_description};
 use super::gpu_tracker::GPU_TRACKER;
-use super::models::{ProcessInfo, SystemSummary};
+use super::history::{HistoryBuffer, MemoryGrowth, ProcessSample};
+use super::models::{ProcessHistory, ProcessInfo, SystemSummary};
+use super::network_tracker::NETWORK_TRACKER;
+use super::overrides::{self, ResolvedCategory};
+use super::priority::get_priority;
+use super::protection::is_user_protected;
+use super::suspend;
+use crate::models::performance::{ProcessUsage, TopProcesses};
 
 pub struct SystemTracker {
     system: RwLock<System>,
     last_refresh: RwLock<Instant>,
+    history: RwLock<HistoryBuffer>,
 }
 
 impl SystemTracker {
@@ -24,10 +32,12 @@ impl SystemTracker {
         Self {
             system: RwLock::new(system),
             last_refresh: RwLock::new(Instant::now() - Duration::from_secs(10)),
+            history: RwLock::new(HistoryBuffer::default()),
         }
     }
 
-    fn refresh_if_needed(&self) {
+    /// Refresh processes (and record a history sample) unless that was done within the last second
+    pub fn refresh_if_needed(&self) {
         let should_refresh = {
             if let Ok(last) = self.last_refresh.read() {
                 last.elapsed() > Duration::from_millis(1000)
@@ -43,10 +53,80 @@ impl SystemTracker {
                 if let Ok(mut last) = self.last_refresh.write() {
                     *last = Instant::now();
                 }
+
+                self.record_history(&system);
             }
         }
     }
 
+    /// Record a history sample unless a refresh already did within `max_age`
+    /// Only processes are refreshed, which is much cheaper than the full refresh the process list does
+    pub fn sample_history_if_stale(&self, max_age: Duration) {
+        if self.last_refresh.read().is_ok_and(|last| last.elapsed() < max_age) {
+            return;
+        }
+
+        if let Ok(mut system) = self.system.write() {
+            system.refresh_processes_specifics(ProcessRefreshKind::new().with_memory().with_cpu());
+            self.record_history(&system);
+        }
+    }
+
+    fn record_history(&self, system: &System) {
+        let cpu_count = system.cpus().len().max(1) as f32;
+        let samples = system.processes().iter().map(|(pid, process)| ProcessSample {
+            pid: pid.as_u32(),
+            name: process.name(),
+            cpu_usage: process.cpu_usage() / cpu_count,
+            memory_mb: process.memory() as f64 / 1_048_576.0,
+        });
+
+        if let Ok(mut history) = self.history.write() {
+            history.record(chrono::Utc::now().timestamp(), samples);
+        }
+    }
+
+    pub fn get_process_history(&self, pid: u32, seconds: u64) -> Option<ProcessHistory> {
+        let seconds = seconds.min(i64::MAX as u64) as i64;
+        self.history
+            .read()
+            .ok()?
+            .get(pid, seconds, chrono::Utc::now().timestamp())
+    }
+
+    /// Processes whose memory has grown for at least `min_duration_seconds` at `min_mb_per_hour` or faster
+    pub fn get_memory_growth(&self, min_duration_seconds: i64, min_mb_per_hour: f64) -> Vec<MemoryGrowth> {
+        self.history
+            .read()
+            .map(|history| history.growth_suspects(min_duration_seconds, min_mb_per_hour))
+            .unwrap_or_default()
+    }
+
+    /// Command line arguments and start time (Unix seconds) - no arguments when access was denied
+    pub fn get_process_runtime(&self, pid: u32) -> Option<(Vec<String>, u64)> {
+        self.refresh_if_needed();
+
+        let system = self.system.read().ok()?;
+        let process = system.process(sysinfo::Pid::from_u32(pid))?;
+        Some((process.cmd().to_vec(), process.start_time()))
+    }
+
+    /// (pid, name, start time in Unix seconds) of processes with one of `names` (lowercase)
+    /// Only reads names, so it's cheap enough to poll
+    pub fn find_processes_by_name(&self, names: &[String]) -> Vec<(u32, String, u64)> {
+        self.refresh_if_needed();
+
+        let Ok(system) = self.system.read() else {
+            return Vec::new();
+        };
+        system
+            .processes()
+            .iter()
+            .filter(|(_, p)| names.contains(&p.name().to_lowercase()))
+            .map(|(pid, p)| (pid.as_u32(), p.name().to_string(), p.start_time()))
+            .collect()
+    }
+
     pub fn get_all_processes(&self) -> Vec<ProcessInfo> {
         self.refresh_if_needed();
 
@@ -56,6 +136,9 @@ impl SystemTracker {
         };
 
         let gpu_usage_map = GPU_TRACKER.get_all_gpu_usage();
+        let engine_usage_map = GPU_TRACKER.get_all_engine_usage();
+        let network_rates = NETWORK_TRACKER.get_rates();
+        let suspended = suspend::suspended_processes();
         let cpu_count = system.cpus().len() as f32;
 
         system
@@ -65,7 +148,23 @@ impl SystemTracker {
                 let pid_u32 = pid.as_u32();
                 let name = process.name().to_string();
                 let exe_path = process.exe().map(|p| p.to_string_lossy().to_string());
-                let category = categorize_process(&name, exe_path.as_deref());
+                let builtin_category = categorize_process(&name, exe_path.as_deref());
+                let category_override = overrides::lookup(&name);
+
+                // Overrides apply before the built-in rules, but can't unprotect critical/anti-cheat processes
+                let (category, custom_category) =
+                    match category_override.as_ref().and_then(|o| overrides::resolve(&o.category)) {
+                        Some(ResolvedCategory::BuiltIn(category)) => (category, None),
+                        Some(ResolvedCategory::Custom(label)) => (builtin_category.clone(), Some(label)),
+                        None => (builtin_category.clone(), None),
+                    };
+                let excluded = overrides::is_excluded(
+                    custom_category.as_deref().unwrap_or(category.name()),
+                    &name,
+                );
+                let can_kill = can_kill_process(&category)
+                    && !is_always_protected(&builtin_category)
+                    && !is_user_protected(&name);
 
                 let raw_cpu = process.cpu_usage();
                 let normalized_cpu = if cpu_count > 0.0 {
@@ -77,20 +176,76 @@ impl SystemTracker {
                 ProcessInfo {
                     pid: pid_u32,
                     name: name.clone(),
-                    display_name: get_friendly_name(&name),
+                    display_name: category_override
+                        .as_ref()
+                        .and_then(|o| o.display_name.clone())
+                        .unwrap_or_else(|| get_friendly_name(&name)),
                     exe_path,
                     cpu_usage: normalized_cpu,
                     memory_mb: process.memory() as f64 / 1_048_576.0,
                     gpu_usage: gpu_usage_map.get(&pid_u32).copied(),
-                    category: category.clone(),
-                    description: get_process_description(&name),
-                    can_kill: can_kill_process(&category),
+                    gpu_percent: engine_usage_map
+                        .as_ref()
+                        .map(|usage| usage.get(&pid_u32).copied().unwrap_or(0.0)),
+                    network_sent_bps: network_rates
+                        .as_ref()
+                        .map(|rates| rates.get(&pid_u32).map_or(0.0, |r| r.sent_bytes_per_sec)),
+                    network_received_bps: network_rates
+                        .as_ref()
+                        .map(|rates| rates.get(&pid_u32).map_or(0.0, |r| r.received_bytes_per_sec)),
+                    category,
+                    description: category_override
+                        .as_ref()
+                        .and_then(|o| o.description.clone())
+                        .or_else(|| get_process_description(&name)),
+                    can_kill,
                     parent_pid: process.parent().map(|p| p.as_u32()),
+                    priority: get_priority(pid_u32),
+                    custom_category,
+                    excluded,
+                    suspended: suspended.get(&pid_u32).is_some_and(|n| *n == name),
                 }
             })
             .collect()
     }
 
+    /// Top `limit` processes by CPU and by memory
+    /// Lighter than `get_all_processes` (no categorization or GPU lookup) and shares its refresh throttle
+    pub fn get_top_processes(&self, limit: usize) -> TopProcesses {
+        self.refresh_if_needed();
+
+        let system = match self.system.read() {
+            Ok(s) => s,
+            Err(_) => return TopProcesses::default(),
+        };
+
+        let cpu_count = system.cpus().len() as f32;
+        let usages: Vec<ProcessUsage> = system
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessUsage {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                cpu_percent: if cpu_count > 0.0 {
+                    process.cpu_usage() / cpu_count
+                } else {
+                    process.cpu_usage()
+                },
+                memory_mb: process.memory() as f64 / 1_048_576.0,
+            })
+            .collect();
+
+        let mut by_cpu = usages.clone();
+        by_cpu.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+        by_cpu.truncate(limit);
+
+        let mut by_memory = usages;
+        by_memory.sort_by(|a, b| b.memory_mb.total_cmp(&a.memory_mb));
+        by_memory.truncate(limit);
+
+        TopProcesses { by_cpu, by_memory }
+    }
+
     pub fn get_system_summary(&self) -> SystemSummary {
         self.refresh_if_needed();
 
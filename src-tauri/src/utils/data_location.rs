@@ -0,0 +1,98 @@
+// Data directory pointer
+// The data directory can be moved off the default location. data_location.json always stays in the
+// default directory and says where the data lives, so it is read once at startup before any other
+// path is resolved. A relocation only switches the pointer after the copy is verified; the old
+// directory is deleted on the next start, once nothing is running from it
+
+use log::{info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::paths::{get_app_data_dir, get_data_location_json_path, get_default_app_data_dir};
+use crate::file_manager::write_json_file;
+use crate::models::DataLocation;
+
+/// Read the pointer; a missing or unreadable file means the default location
+pub fn load_data_location() -> DataLocation {
+    fs::read_to_string(get_data_location_json_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_data_location(location: &DataLocation) -> Result<(), String> {
+    write_json_file(&get_data_location_json_path(), location)
+}
+
+/// Data directory to use for this run
+/// Falls back to the default one when the relocated directory is gone (e.g. an unplugged drive)
+pub fn resolve_app_data_dir() -> PathBuf {
+    match load_data_location().data_dir.map(PathBuf::from) {
+        Some(dir) if dir.is_dir() => dir,
+        _ => get_default_app_data_dir(),
+    }
+}
+
+/// Whether `path` is the pointer file or its temp file, which live in the default directory
+pub fn is_data_location_file(path: &Path) -> bool {
+    let pointer = get_data_location_json_path();
+    path == pointer || path == pointer.with_extension("tmp")
+}
+
+/// Delete the directory a previous relocation moved away from, in the background
+pub fn cleanup_previous_data_dir() {
+    let location = load_data_location();
+    let Some(old_dir) = location.pending_cleanup.map(PathBuf::from) else {
+        return;
+    };
+    if old_dir == get_app_data_dir() {
+        warn!("Pending cleanup points at the data directory in use, ignoring it");
+        clear_pending_cleanup();
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let result = if old_dir == get_default_app_data_dir() {
+            // The pointer lives here, so only everything else goes
+            remove_dir_contents_except_pointer(&old_dir)
+        } else if old_dir.exists() {
+            fs::remove_dir_all(&old_dir).map_err(|e| e.to_string())
+        } else {
+            Ok(())
+        };
+
+        match result {
+            Ok(()) => {
+                info!("Removed previous data directory {:?}", old_dir);
+                clear_pending_cleanup();
+            }
+            // Left pending, so it is retried on the next start
+            Err(e) => warn!("Failed to remove previous data directory {:?}: {}", old_dir, e),
+        }
+    });
+}
+
+fn remove_dir_contents_except_pointer(dir: &Path) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_data_location_file(&path) {
+            continue;
+        }
+        let removed = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        removed.map_err(|e| format!("{:?}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+fn clear_pending_cleanup() {
+    let mut location = load_data_location();
+    location.pending_cleanup = None;
+    if let Err(e) = save_data_location(&location) {
+        warn!("Failed to update data location: {}", e);
+    }
+}
@@ -1,4 +1,8 @@
 // Utility modules
+pub mod data_location;
 pub mod paths;
+pub mod settings_events;
+pub mod settings_validation;
+pub mod shell_template;
 
 pub use paths::*;
@@ -5,13 +5,21 @@ use std::sync::OnceLock;
 
 static APP_DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
+/// Where Atlas keeps its data unless it was relocated; also holds data_location.json
+pub fn get_default_app_data_dir() -> PathBuf {
+    let base_dir = dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."));
+    base_dir.join("Atlas")
+}
+
+pub fn get_data_location_json_path() -> PathBuf {
+    get_default_app_data_dir().join("data_location.json")
+}
+
+/// Data directory for this run, resolved once through data_location.json
 pub fn get_app_data_dir() -> PathBuf {
     APP_DATA_DIR
-        .get_or_init(|| {
-            let base_dir = dirs::data_dir()
-                .unwrap_or_else(|| PathBuf::from("."));
-            base_dir.join("Atlas")
-        })
+        .get_or_init(super::data_location::resolve_app_data_dir)
         .clone()
 }
 
@@ -55,14 +63,55 @@ pub fn get_ml_jobs_json_path() -> PathBuf {
     get_data_dir().join("ml_jobs.json")
 }
 
+/// Pre-multi-account store history, adopted by the first account added
 pub fn get_valorant_store_json_path() -> PathBuf {
     get_data_dir().join("valorant_store.json")
 }
 
+pub fn get_valorant_stores_dir() -> PathBuf {
+    get_data_dir().join("valorant_stores")
+}
+
+/// Store history of one Riot account
+pub fn get_account_valorant_store_json_path(account_id: &str) -> PathBuf {
+    let file_name: String = account_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    get_valorant_stores_dir().join(format!("{}.json", file_name))
+}
+
+pub fn get_riot_accounts_json_path() -> PathBuf {
+    get_data_dir().join("riot_accounts.json")
+}
+
+pub fn get_valorant_wishlist_json_path() -> PathBuf {
+    get_data_dir().join("valorant_wishlist.json")
+}
+
+pub fn get_valorant_notifier_json_path() -> PathBuf {
+    get_data_dir().join("valorant_notifier.json")
+}
+
+/// Skin catalog from valorant-api.com, keyed by game version
+pub fn get_valorant_content_json_path() -> PathBuf {
+    get_data_dir().join("valorant_content.json")
+}
+
+/// Downloaded images of skins seen in the store
+pub fn get_valorant_images_dir() -> PathBuf {
+    get_app_data_dir().join("valorant_images")
+}
+
 pub fn get_settings_json_path() -> PathBuf {
     get_data_dir().join("settings.json")
 }
 
+pub fn get_settings_profiles_json_path() -> PathBuf {
+    get_data_dir().join("settings_profiles.json")
+}
+
+/// Single-account cookies from before riot_accounts.json, migrated on first load
 pub fn get_auth_json_path() -> PathBuf {
     get_data_dir().join("auth.json")
 }
@@ -79,6 +128,29 @@ pub fn get_ssh_credentials_json_path() -> PathBuf {
     get_data_dir().join("ssh_credentials.json")
 }
 
+/// Private key created by `generate_ssh_keypair`; the public key sits next to it with a .pub extension
+pub fn get_generated_ssh_key_path() -> PathBuf {
+    get_data_dir().join("ssh").join("atlas_ed25519")
+}
+
+/// Host keys trusted for each server
+pub fn get_known_hosts_json_path() -> PathBuf {
+    get_data_dir().join("known_hosts.json")
+}
+
+pub fn get_server_metrics_dir() -> PathBuf {
+    get_data_dir().join("server_metrics")
+}
+
+/// Metrics history of one server, keyed by `ServerConfig::server_key`
+pub fn get_server_metrics_json_path(server_key: &str) -> PathBuf {
+    let file_name: String = server_key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect();
+    get_server_metrics_dir().join(format!("{}.json", file_name))
+}
+
 pub fn get_quick_actions_json_path() -> PathBuf {
     get_data_dir().join("quick_actions.json")
 }
@@ -103,6 +175,10 @@ pub fn get_bottleneck_thresholds_json_path() -> PathBuf {
     get_data_dir().join("bottleneck_thresholds.json")
 }
 
+pub fn get_performance_alerts_json_path() -> PathBuf {
+    get_data_dir().join("performance_alerts.json")
+}
+
 pub fn get_game_library_json_path() -> PathBuf {
     get_data_dir().join("game_library.json")
 }
@@ -111,6 +187,14 @@ pub fn get_game_scan_cache_json_path() -> PathBuf {
     get_data_dir().join("game_scan_cache.json")
 }
 
+pub fn get_launch_history_json_path() -> PathBuf {
+    get_data_dir().join("launch_history.json")
+}
+
+pub fn get_ignored_games_json_path() -> PathBuf {
+    get_data_dir().join("ignored_games.json")
+}
+
 pub fn get_music_dir() -> PathBuf {
     get_app_data_dir().join("music")
 }
@@ -131,6 +215,23 @@ pub fn get_last_run_version_path() -> PathBuf {
     get_data_dir().join("last_run_version.txt")
 }
 
+pub fn get_update_deferral_json_path() -> PathBuf {
+    get_data_dir().join("update_deferral.json")
+}
+
+pub fn get_update_rollback_json_path() -> PathBuf {
+    get_data_dir().join("update_rollback.json")
+}
+
+pub fn get_update_changelog_json_path() -> PathBuf {
+    get_data_dir().join("update_changelog.json")
+}
+
+/// Update channel of the last run, written next to last_run_version.txt
+pub fn get_last_run_channel_path() -> PathBuf {
+    get_data_dir().join("last_run_channel.txt")
+}
+
 pub fn get_gaming_profiles_json_path() -> PathBuf {
     get_data_dir().join("gaming_profiles.json")
 }
@@ -139,6 +240,19 @@ pub fn get_restore_list_json_path() -> PathBuf {
     get_data_dir().join("restore_list.json")
 }
 
+/// Processes Atlas holds suspended, resumed on the next start if Atlas exits first
+pub fn get_suspended_processes_json_path() -> PathBuf {
+    get_data_dir().join("suspended_processes.json")
+}
+
+pub fn get_protected_processes_json_path() -> PathBuf {
+    get_data_dir().join("protected_processes.json")
+}
+
+pub fn get_process_categories_json_path() -> PathBuf {
+    get_data_dir().join("process_categories.json")
+}
+
 pub fn get_feedback_sessions_json_path() -> PathBuf {
     get_data_dir().join("feedback_sessions.json")
 }
@@ -213,5 +327,6 @@ pub fn initialize_data_directories() -> Result<(), String> {
     }
 
     info!("Data directories initialized at: {:?}", get_app_data_dir());
+    super::data_location::cleanup_previous_data_dir();
     Ok(())
 }
@@ -0,0 +1,207 @@
+// Settings change notifications
+// `update_settings` diffs the settings before and after each write, emits `settings:changed` for
+// the UI and calls the backend subscribers registered here, so modules react without polling.
+// Subscribers run one change set at a time on a dispatch thread, so a slow one (reconnecting
+// Discord, say) doesn't hold up the command that saved the settings
+
+use lazy_static::lazy_static;
+use log::debug;
+use parking_lot::{Mutex, RwLock};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+use crate::models::{SettingChange, Settings, SettingsChangedEvent};
+
+type Subscriber = Arc<dyn Fn(&[SettingChange]) + Send + Sync>;
+
+/// Fields holding credentials: left out of exports unless asked for, and redacted in `settings:changed`
+pub const SENSITIVE_SETTINGS_FIELDS: &[&str] = &["steamgriddb_api_key", "valorant_credentials"];
+
+/// Stands in for a sensitive value in `settings:changed`
+const REDACTED: &str = "[redacted]";
+
+#[derive(Default)]
+pub struct SettingsSubscribers {
+    subscribers: RwLock<Vec<Subscriber>>,
+}
+
+impl SettingsSubscribers {
+    pub fn subscribe(&self, subscriber: impl Fn(&[SettingChange]) + Send + Sync + 'static) {
+        self.subscribers.write().push(Arc::new(subscriber));
+    }
+
+    /// Call every subscriber once with the whole change set; no-op when nothing changed
+    /// The list is copied first, so a subscriber may subscribe or save settings itself
+    pub fn notify(&self, changes: &[SettingChange]) {
+        if changes.is_empty() {
+            return;
+        }
+        let subscribers = self.subscribers.read().clone();
+        for subscriber in subscribers {
+            subscriber(changes);
+        }
+    }
+}
+
+lazy_static! {
+    static ref SUBSCRIBERS: SettingsSubscribers = SettingsSubscribers::default();
+    /// Change sets waiting for the dispatch thread, started with the first one
+    static ref DISPATCH: Mutex<Option<mpsc::Sender<Vec<SettingChange>>>> = Mutex::new(None);
+}
+
+/// Hand `changes` to the dispatch thread, which calls the subscribers in publish order
+fn dispatch(changes: Vec<SettingChange>) {
+    let mut dispatch = DISPATCH.lock();
+    let sender = dispatch.get_or_insert_with(|| {
+        let (sender, receiver) = mpsc::channel::<Vec<SettingChange>>();
+        thread::spawn(move || {
+            for changes in receiver {
+                SUBSCRIBERS.notify(&changes);
+            }
+        });
+        sender
+    });
+    let _ = sender.send(changes);
+}
+
+/// The change set with sensitive values replaced, as sent to the UI
+fn redacted(changes: &[SettingChange]) -> Vec<SettingChange> {
+    changes
+        .iter()
+        .map(|c| SettingChange {
+            key: c.key.clone(),
+            value: if SENSITIVE_SETTINGS_FIELDS.contains(&c.key.as_str()) && !c.value.is_null() {
+                serde_json::Value::String(REDACTED.to_string())
+            } else {
+                c.value.clone()
+            },
+        })
+        .collect()
+}
+
+/// Register a callback for settings changes; it runs on the settings dispatch thread
+pub fn subscribe(subscriber: impl Fn(&[SettingChange]) + Send + Sync + 'static) {
+    SUBSCRIBERS.subscribe(subscriber);
+}
+
+/// The new value of `key` if it is part of the change set
+pub fn changed_value<'a>(changes: &'a [SettingChange], key: &str) -> Option<&'a serde_json::Value> {
+    changes.iter().find(|c| c.key == key).map(|c| &c.value)
+}
+
+/// Top-level fields whose serialized value differs between `old` and `new`
+pub fn diff_settings(old: &Settings, new: &Settings) -> Vec<SettingChange> {
+    let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return vec![];
+    };
+
+    let mut changes: Vec<SettingChange> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, value)| SettingChange {
+            key: key.clone(),
+            value: value.clone(),
+        })
+        .collect();
+    changes.extend(old.keys().filter(|key| !new.contains_key(*key)).map(|key| SettingChange {
+        key: key.clone(),
+        value: serde_json::Value::Null,
+    }));
+    changes
+}
+
+/// Emit `settings:changed` and notify subscribers; nothing happens when nothing changed
+pub fn publish(app: &AppHandle, changes: Vec<SettingChange>) {
+    if changes.is_empty() {
+        return;
+    }
+    debug!(
+        "Settings changed: {:?}",
+        changes.iter().map(|c| c.key.as_str()).collect::<Vec<_>>()
+    );
+    let _ = app.emit("settings:changed", SettingsChangedEvent { changes: redacted(&changes) });
+    dispatch(changes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_flipping_a_boolean_notifies_once() {
+        let subscribers = SettingsSubscribers::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::new(RwLock::new(Vec::new()));
+        {
+            let calls = calls.clone();
+            let seen = seen.clone();
+            subscribers.subscribe(move |changes| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                seen.write().extend(changes.iter().cloned());
+            });
+        }
+
+        let old = Settings::default();
+        let new = Settings {
+            discord_rich_presence_enabled: !old.discord_rich_presence_enabled,
+            ..old.clone()
+        };
+        subscribers.notify(&diff_settings(&old, &new));
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            *seen.read(),
+            vec![SettingChange {
+                key: "discord_rich_presence_enabled".to_string(),
+                value: serde_json::Value::Bool(new.discord_rich_presence_enabled),
+            }]
+        );
+
+        // Saving without changes notifies nobody
+        subscribers.notify(&diff_settings(&new, &new));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_sensitive_values_are_redacted() {
+        let old = Settings::default();
+        let new = Settings {
+            steamgriddb_api_key: Some("secret".to_string()),
+            ..old.clone()
+        };
+        let changes = redacted(&diff_settings(&old, &new));
+        assert_eq!(changed_value(&changes, "steamgriddb_api_key"), Some(&serde_json::json!(REDACTED)));
+
+        // Clearing one is passed on as is
+        let changes = redacted(&diff_settings(&new, &old));
+        assert_eq!(changed_value(&changes, "steamgriddb_api_key"), Some(&serde_json::Value::Null));
+    }
+
+    #[test]
+    fn test_subscriber_may_subscribe_while_notified() {
+        let subscribers = Arc::new(SettingsSubscribers::default());
+        {
+            let inner = subscribers.clone();
+            subscribers.subscribe(move |_| inner.subscribe(|_| {}));
+        }
+        let changes = vec![SettingChange {
+            key: "close_to_tray".to_string(),
+            value: serde_json::Value::Bool(true),
+        }];
+        subscribers.notify(&changes);
+        assert_eq!(subscribers.subscribers.read().len(), 2);
+    }
+
+    #[test]
+    fn test_diff_settings_reports_removed_fields() {
+        let mut old = Settings::default();
+        old.extra.insert("from_newer_version".to_string(), serde_json::json!(1));
+        let changes = diff_settings(&old, &Settings::default());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changed_value(&changes, "from_newer_version"), Some(&serde_json::Value::Null));
+    }
+}
@@ -0,0 +1,367 @@
+// Settings value validation
+// `update_settings` checks every field of a partial update here before anything is written, so an
+// invalid update leaves settings.json untouched. Only the fields present in the update are checked,
+// which keeps an out-of-range value saved by an older version from blocking unrelated changes
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use crate::commands::downloads::validate_download_path;
+use crate::commands::settings::UpdateSettingsParams;
+use crate::models::{PresenceButton, SettingsFieldError};
+use crate::performance::collector::{MAX_POLL_INTERVAL_MS, MIN_POLL_INTERVAL_MS};
+
+/// Download qualities the yt-dlp worker understands
+pub const DOWNLOAD_QUALITIES: &[&str] = &["best", "1080p", "720p", "480p", "audio_only"];
+
+/// Release channels the updater has endpoints for
+pub const UPDATE_CHANNELS: &[&str] = &["stable", "beta"];
+
+/// Hours between background update checks
+pub const UPDATE_CHECK_INTERVAL_HOURS_RANGE: RangeInclusive<u32> = 1..=168;
+
+/// Simultaneous downloads
+pub const MAX_CONCURRENT_DOWNLOADS_RANGE: RangeInclusive<u32> = 1..=10;
+
+/// Simultaneous ML jobs
+pub const MAX_CONCURRENT_ML_JOBS_RANGE: RangeInclusive<u32> = 1..=5;
+
+/// Minutes of live performance history kept in memory
+pub const PERFORMANCE_HISTORY_MINUTES_RANGE: RangeInclusive<u32> = 1..=120;
+
+/// Fixed performance polling interval, the range the collector accepts; 0 selects automatic
+pub const PERFORMANCE_POLL_INTERVAL_MS_RANGE: RangeInclusive<u32> = MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS;
+
+/// Length cap of a performance capture
+pub const PERFORMANCE_CAPTURE_MAX_MINUTES_RANGE: RangeInclusive<u32> = 1..=720;
+
+/// Size cap of a performance capture
+pub const PERFORMANCE_CAPTURE_MAX_MB_RANGE: RangeInclusive<u32> = 10..=10_240;
+
+/// Delay before killed processes are restored after a game closes
+pub const AUTO_RESTORE_DELAY_SECONDS_RANGE: RangeInclusive<u32> = 0..=300;
+
+/// Wait for a process to close gracefully before it is killed
+pub const GRACEFUL_CLOSE_TIMEOUT_SECONDS_RANGE: RangeInclusive<u32> = 1..=60;
+
+/// Memory growth rate that flags a leak
+pub const LEAK_GROWTH_MB_PER_HOUR_RANGE: RangeInclusive<f64> = 1.0..=100_000.0;
+
+/// How long growth must last before it counts as a leak
+pub const LEAK_MIN_DURATION_MINUTES_RANGE: RangeInclusive<u32> = 1..=1440;
+
+/// Idle minutes before the presence shows AFK
+pub const DISCORD_PRESENCE_AFK_MINUTES_RANGE: RangeInclusive<u32> = 1..=120;
+
+/// What Discord shows while a game hidden from presence runs
+pub const PRESENCE_HIDDEN_GAME_DISPLAYS: &[&str] = &["generic", "idle"];
+
+/// Entries in the presence blocklist
+pub const MAX_PRESENCE_BLOCKLIST_ENTRIES: usize = 200;
+
+/// Longest presence template; the rendered line is cut to Discord's limit anyway
+pub const MAX_PRESENCE_TEMPLATE_CHARS: usize = 256;
+
+/// Discord shows at most this many presence buttons
+pub const MAX_PRESENCE_BUTTONS: usize = 2;
+
+/// Discord's limits on a presence button
+pub const MAX_PRESENCE_BUTTON_LABEL_CHARS: usize = 32;
+pub const MAX_PRESENCE_BUTTON_URL_CHARS: usize = 512;
+
+fn field_error(field: &str, message: impl Into<String>) -> SettingsFieldError {
+    SettingsFieldError {
+        field: field.to_string(),
+        message: message.into(),
+    }
+}
+
+fn check_range<T: PartialOrd + Display>(
+    errors: &mut Vec<SettingsFieldError>,
+    field: &str,
+    value: Option<T>,
+    range: &RangeInclusive<T>,
+) {
+    if let Some(value) = value {
+        if !range.contains(&value) {
+            errors.push(field_error(
+                field,
+                format!("Must be between {} and {} (got {})", range.start(), range.end(), value),
+            ));
+        }
+    }
+}
+
+/// Why Discord would reject a presence button, if it would
+/// Surrounding whitespace is ignored, as `update_settings` trims it
+pub fn presence_button_problem(button: &PresenceButton) -> Option<String> {
+    let (label, url) = (button.label.trim(), button.url.trim());
+    if label.is_empty() || label.chars().count() > MAX_PRESENCE_BUTTON_LABEL_CHARS {
+        return Some(format!("Label must be 1 to {} characters", MAX_PRESENCE_BUTTON_LABEL_CHARS));
+    }
+    if url.chars().count() > MAX_PRESENCE_BUTTON_URL_CHARS {
+        return Some(format!("URL must be at most {} characters", MAX_PRESENCE_BUTTON_URL_CHARS));
+    }
+    match url::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "https" && parsed.host().is_some() => None,
+        Ok(_) => Some("URL must be an https link".to_string()),
+        Err(e) => Some(format!("Not a valid URL: {}", e)),
+    }
+}
+
+/// Every problem with the fields set in `params`; empty when the update can be saved
+pub fn validate(params: &UpdateSettingsParams) -> Vec<SettingsFieldError> {
+    let mut errors = Vec::new();
+
+    if let Some(path) = &params.download_path {
+        let validation = validate_download_path(path.clone());
+        if !validation.valid {
+            errors.push(field_error("download_path", validation.message));
+        }
+    }
+    if let Some(quality) = &params.default_quality {
+        if !DOWNLOAD_QUALITIES.contains(&quality.as_str()) {
+            errors.push(field_error(
+                "default_quality",
+                format!("Must be one of {}", DOWNLOAD_QUALITIES.join(", ")),
+            ));
+        }
+    }
+    if let Some(channel) = &params.update_channel {
+        if !UPDATE_CHANNELS.contains(&channel.as_str()) {
+            errors.push(field_error(
+                "update_channel",
+                format!("Must be one of {}", UPDATE_CHANNELS.join(", ")),
+            ));
+        }
+    }
+    if let Some(url) = params.update_url_base.as_deref().filter(|u| !u.is_empty()) {
+        match url::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+            Ok(_) => errors.push(field_error("update_url_base", "Must be an http or https URL")),
+            Err(e) => errors.push(field_error("update_url_base", format!("Not a valid URL: {}", e))),
+        }
+    }
+    for (field, template) in [
+        ("discord_presence_details_template", &params.discord_presence_details_template),
+        ("discord_presence_state_template", &params.discord_presence_state_template),
+    ] {
+        if template.as_ref().is_some_and(|t| t.chars().count() > MAX_PRESENCE_TEMPLATE_CHARS) {
+            errors.push(field_error(
+                field,
+                format!("Must be at most {} characters", MAX_PRESENCE_TEMPLATE_CHARS),
+            ));
+        }
+    }
+    if let Some(display) = &params.presence_hidden_game_display {
+        if !PRESENCE_HIDDEN_GAME_DISPLAYS.contains(&display.as_str()) {
+            errors.push(field_error(
+                "presence_hidden_game_display",
+                format!("Must be one of {}", PRESENCE_HIDDEN_GAME_DISPLAYS.join(", ")),
+            ));
+        }
+    }
+    if params
+        .presence_blocklist
+        .as_ref()
+        .is_some_and(|games| games.len() > MAX_PRESENCE_BLOCKLIST_ENTRIES)
+    {
+        errors.push(field_error(
+            "presence_blocklist",
+            format!("At most {} games", MAX_PRESENCE_BLOCKLIST_ENTRIES),
+        ));
+    }
+    if let Some(buttons) = &params.discord_presence_buttons {
+        if buttons.len() > MAX_PRESENCE_BUTTONS {
+            errors.push(field_error(
+                "discord_presence_buttons",
+                format!("At most {} buttons", MAX_PRESENCE_BUTTONS),
+            ));
+        } else if let Some((i, problem)) = buttons
+            .iter()
+            .enumerate()
+            .find_map(|(i, b)| presence_button_problem(b).map(|p| (i, p)))
+        {
+            errors.push(field_error("discord_presence_buttons", format!("Button {}: {}", i + 1, problem)));
+        }
+    }
+
+    check_range(
+        &mut errors,
+        "discord_presence_afk_minutes",
+        params.discord_presence_afk_minutes,
+        &DISCORD_PRESENCE_AFK_MINUTES_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "max_concurrent_downloads",
+        params.max_concurrent_downloads,
+        &MAX_CONCURRENT_DOWNLOADS_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "max_concurrent_ml_jobs",
+        params.max_concurrent_ml_jobs,
+        &MAX_CONCURRENT_ML_JOBS_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "performance_history_minutes",
+        params.performance_history_minutes,
+        &PERFORMANCE_HISTORY_MINUTES_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "performance_poll_interval_ms",
+        params.performance_poll_interval_ms.filter(|&ms| ms != 0),
+        &PERFORMANCE_POLL_INTERVAL_MS_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "performance_capture_max_minutes",
+        params.performance_capture_max_minutes,
+        &PERFORMANCE_CAPTURE_MAX_MINUTES_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "performance_capture_max_mb",
+        params.performance_capture_max_mb,
+        &PERFORMANCE_CAPTURE_MAX_MB_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "auto_restore_delay_seconds",
+        params.auto_restore_delay_seconds,
+        &AUTO_RESTORE_DELAY_SECONDS_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "graceful_close_timeout_seconds",
+        params.graceful_close_timeout_seconds,
+        &GRACEFUL_CLOSE_TIMEOUT_SECONDS_RANGE,
+    );
+    // NaN fails the range check too
+    check_range(
+        &mut errors,
+        "leak_growth_mb_per_hour",
+        params.leak_growth_mb_per_hour,
+        &LEAK_GROWTH_MB_PER_HOUR_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "leak_min_duration_minutes",
+        params.leak_min_duration_minutes,
+        &LEAK_MIN_DURATION_MINUTES_RANGE,
+    );
+    check_range(
+        &mut errors,
+        "update_check_interval_hours",
+        params.update_check_interval_hours,
+        &UPDATE_CHECK_INTERVAL_HOURS_RANGE,
+    );
+
+    for (field, value) in [
+        ("partner_widget_position_x", params.partner_widget_position_x),
+        ("partner_widget_position_y", params.partner_widget_position_y),
+    ] {
+        if value.is_some_and(|v| !v.is_finite()) {
+            errors.push(field_error(field, "Must be a finite number"));
+        }
+    }
+
+    errors
+}
+
+/// One-line summary of validation errors, for callers that only carry a String
+pub fn describe(errors: &[SettingsFieldError]) -> String {
+    let details = errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    format!("Invalid settings: {}", details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(value: serde_json::Value) -> UpdateSettingsParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn fields(errors: &[SettingsFieldError]) -> Vec<&str> {
+        errors.iter().map(|e| e.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_update_has_no_errors() {
+        let update = params(serde_json::json!({
+            "default_quality": "720p",
+            "update_channel": "beta",
+            "max_concurrent_downloads": 10,
+            "performance_poll_interval_ms": 250,
+            "auto_restore_delay_seconds": 0,
+            "update_url_base": "https://updates.example.com/atlas",
+            "discord_presence_buttons": [
+                { "label": "My friend code", "url": "https://atlas.example.com/add/ABC123" },
+                { "label": "Download Atlas", "url": "https://github.com/KaiC5504/Atlas" },
+            ],
+        }));
+        assert!(validate(&update).is_empty());
+
+        // Clearing the URL is allowed
+        assert!(validate(&params(serde_json::json!({ "update_url_base": "" }))).is_empty());
+
+        // 0 is automatic polling; the slowest fixed interval is the collector's cap
+        assert!(validate(&params(serde_json::json!({ "performance_poll_interval_ms": 0 }))).is_empty());
+        assert!(validate(&params(serde_json::json!({ "performance_poll_interval_ms": 60_000 }))).is_empty());
+        let errors = validate(&params(serde_json::json!({ "performance_poll_interval_ms": 60_001 })));
+        assert_eq!(fields(&errors), vec!["performance_poll_interval_ms"]);
+    }
+
+    #[test]
+    fn test_each_invalid_field_is_reported() {
+        let update = params(serde_json::json!({
+            "default_quality": "8k",
+            "update_channel": "nightly",
+            "max_concurrent_downloads": 0,
+            "max_concurrent_ml_jobs": 50,
+            "performance_poll_interval_ms": 5,
+            "leak_growth_mb_per_hour": 0.0,
+            "update_url_base": "ftp://updates.example.com",
+        }));
+        assert_eq!(
+            fields(&validate(&update)),
+            vec![
+                "default_quality",
+                "update_channel",
+                "update_url_base",
+                "max_concurrent_downloads",
+                "max_concurrent_ml_jobs",
+                "performance_poll_interval_ms",
+                "leak_growth_mb_per_hour",
+            ]
+        );
+
+        let errors = validate(&params(serde_json::json!({
+            "discord_presence_buttons": [{ "label": "Download Atlas", "url": "http://example.com" }],
+        })));
+        assert_eq!(fields(&errors), vec!["discord_presence_buttons"]);
+        assert!(errors[0].message.contains("https"));
+
+        let template = "x".repeat(MAX_PRESENCE_TEMPLATE_CHARS + 1);
+        let errors = validate(&params(serde_json::json!({ "discord_presence_state_template": template })));
+        assert_eq!(fields(&errors), vec!["discord_presence_state_template"]);
+
+        let errors = validate(&params(serde_json::json!({ "discord_presence_afk_minutes": 0 })));
+        assert_eq!(fields(&errors), vec!["discord_presence_afk_minutes"]);
+
+        let errors = validate(&params(serde_json::json!({ "presence_hidden_game_display": "blank" })));
+        assert_eq!(fields(&errors), vec!["presence_hidden_game_display"]);
+
+        let errors = validate(&params(serde_json::json!({ "update_url_base": "not a url" })));
+        assert_eq!(fields(&errors), vec!["update_url_base"]);
+        assert!(describe(&errors).starts_with("Invalid settings: update_url_base: Not a valid URL"));
+    }
+}
@@ -0,0 +1,203 @@
+// Quick action command templates
+// A command may contain `{name}` placeholders that are filled in when the action runs. Every value
+// is single-quoted for the shell, so it always arrives as one literal argument whatever it contains.
+// A placeholder written inside quotes would end up outside that quoting, so such templates are
+// refused. `${VAR}` is shell syntax and `\{` a literal brace; neither is a placeholder
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+/// A placeholder at `start..end` in the template, braces included
+struct Placeholder<'a> {
+    name: &'a str,
+    start: usize,
+    end: usize,
+    quoted: bool,
+}
+
+fn is_name_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_'
+}
+
+fn is_name_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+/// Length of the `{name}` at the start of `rest`, if there is one
+fn placeholder_len(rest: &[u8]) -> Option<usize> {
+    if rest.len() < 3 || rest[0] != b'{' || !is_name_start(rest[1]) {
+        return None;
+    }
+    let name_len = rest[1..].iter().take_while(|c| is_name_char(**c)).count();
+    (rest.get(1 + name_len) == Some(&b'}')).then_some(name_len + 2)
+}
+
+fn scan(template: &str) -> Vec<Placeholder<'_>> {
+    let bytes = template.as_bytes();
+    let mut found = Vec::new();
+    let mut quote = Quote::None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let after_dollar = i > 0 && bytes[i - 1] == b'$';
+        if !after_dollar {
+            if let Some(len) = placeholder_len(&bytes[i..]) {
+                found.push(Placeholder {
+                    name: &template[i + 1..i + len - 1],
+                    start: i,
+                    end: i + len,
+                    quoted: quote != Quote::None,
+                });
+                i += len;
+                continue;
+            }
+        }
+
+        match (quote, bytes[i]) {
+            (Quote::Single, b'\'') => quote = Quote::None,
+            (Quote::Single, _) => {}
+            // Backslash escapes the next character outside single quotes
+            (_, b'\\') => i += 1,
+            (Quote::None, b'\'') => quote = Quote::Single,
+            (Quote::None, b'"') => quote = Quote::Double,
+            (Quote::Double, b'"') => quote = Quote::None,
+            _ => {}
+        }
+        i += 1;
+    }
+    found
+}
+
+/// Placeholder names in `template`, in order of first use
+pub fn placeholders(template: &str) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = Vec::new();
+    for placeholder in scan(template) {
+        if placeholder.quoted {
+            return Err(format!(
+                "Placeholder {{{}}} is inside quotes; leave it unquoted, values are quoted automatically",
+                placeholder.name
+            ));
+        }
+        if !names.iter().any(|n| n == placeholder.name) {
+            names.push(placeholder.name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// `value` as a single shell word, taken literally by sh, bash and zsh
+pub fn quote(value: &str) -> Result<String, String> {
+    if value.contains('\0') {
+        return Err("Values can't contain NUL characters".to_string());
+    }
+    Ok(format!("'{}'", value.replace('\'', r"'\''")))
+}
+
+/// `template` with every placeholder replaced by its quoted value from `values`
+pub fn render(template: &str, values: &HashMap<String, String>) -> Result<String, String> {
+    let missing: Vec<String> = placeholders(template)?
+        .into_iter()
+        .filter(|name| values.get(name).map_or(true, |v| v.trim().is_empty()))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Missing value for {}", missing.join(", ")));
+    }
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut last = 0;
+    for placeholder in scan(template) {
+        rendered.push_str(&template[last..placeholder.start]);
+        rendered.push_str(&quote(&values[placeholder.name])?);
+        last = placeholder.end;
+    }
+    rendered.push_str(&template[last..]);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_placeholders_in_order_without_duplicates() {
+        let names = placeholders("docker logs --tail {lines} {container} && docker restart {container}").unwrap();
+        assert_eq!(names, vec!["lines", "container"]);
+        assert!(placeholders("uptime").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shell_braces_are_not_placeholders() {
+        assert!(placeholders("echo ${HOME} \"${USER}\"").unwrap().is_empty());
+        assert!(placeholders("docker ps --format '{{.Names}}'").unwrap().is_empty());
+        assert!(placeholders("echo {a,b} {1..3} { echo hi; } \\{name}")
+            .unwrap()
+            .is_empty());
+        assert!(placeholders("awk '{print $1}'").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_quoted_placeholders_are_refused() {
+        assert!(placeholders("grep '{pattern}' /var/log/syslog").is_err());
+        assert!(placeholders("echo \"name: {name}\"").is_err());
+        // Quotes that have been closed again don't count
+        assert_eq!(placeholders("echo 'a' {name} \"b\"").unwrap(), vec!["name"]);
+        assert_eq!(placeholders("echo it\\'s {name}").unwrap(), vec!["name"]);
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("nginx").unwrap(), "'nginx'");
+        assert_eq!(quote("").unwrap(), "''");
+        assert_eq!(quote("it's").unwrap(), r"'it'\''s'");
+        assert!(quote("a\0b").is_err());
+    }
+
+    #[test]
+    fn test_injection_attempts_stay_one_argument() {
+        let cases = [
+            ("; rm -rf /", "pkill -f '; rm -rf /'"),
+            ("x && reboot", "pkill -f 'x && reboot'"),
+            ("$(reboot)", "pkill -f '$(reboot)'"),
+            ("`reboot`", "pkill -f '`reboot`'"),
+            ("a | sh", "pkill -f 'a | sh'"),
+            ("a\nreboot", "pkill -f 'a\nreboot'"),
+            ("'; reboot; echo '", r"pkill -f ''\''; reboot; echo '\'''"),
+            ("${IFS}reboot", "pkill -f '${IFS}reboot'"),
+        ];
+        for (value, expected) in cases {
+            let rendered = render("pkill -f {name}", &values(&[("name", value)])).unwrap();
+            assert_eq!(rendered, expected, "value {:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_render_replaces_every_occurrence() {
+        let rendered = render(
+            "docker logs --tail {lines} {container}; docker restart {container}",
+            &values(&[("lines", "50"), ("container", "web app")]),
+        )
+        .unwrap();
+        assert_eq!(rendered, "docker logs --tail '50' 'web app'; docker restart 'web app'");
+        assert_eq!(render("echo ${HOME}", &HashMap::new()).unwrap(), "echo ${HOME}");
+    }
+
+    #[test]
+    fn test_render_requires_every_value() {
+        let err = render("kill {signal} {pid}", &values(&[("signal", "-9")])).unwrap_err();
+        assert!(err.contains("pid"), "{}", err);
+        // Blank counts as missing, so `rm -rf /srv/{dir}` can't turn into `rm -rf /srv/''`
+        assert!(render("rm -rf /srv/{dir}", &values(&[("dir", "  ")])).is_err());
+        // Values for names the template doesn't use are ignored
+        assert_eq!(render("uptime", &values(&[("x", "1")])).unwrap(), "uptime");
+    }
+}
@@ -0,0 +1,396 @@
+// Valorant content catalog
+// The skin catalog from valorant-api.com is saved with the game version it was downloaded for and
+// only downloaded again after a game update. Images of skins seen in the store are downloaded on
+// first sight, so stores seen before still show names and images offline
+
+use chrono::Utc;
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::launcher::icon_extractor::download_image;
+use crate::models::{SkinMetadata, ValorantStore};
+use crate::utils::{get_valorant_content_json_path, get_valorant_images_dir};
+
+const API_BASE: &str = "https://valorant-api.com/v1";
+
+/// Minimum gap between game version checks
+const VERSION_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    /// valorant_content.json, loaded on first use
+    static ref CATALOG: Mutex<Option<ContentCatalog>> = Mutex::new(None);
+    static ref LAST_VERSION_CHECK: Mutex<Option<Instant>> = Mutex::new(None);
+}
+
+/// Saved catalog; the worker reads `skins` too, so field names are part of its input format
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ContentCatalog {
+    /// riotClientVersion the catalog was downloaded for
+    version: String,
+    fetched_at: String,
+    /// Lowercase skin, level and chroma UUID -> entry
+    skins: HashMap<String, CatalogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CatalogEntry {
+    skin_uuid: String,
+    name: String,
+    tier: Option<String>,
+    tier_color: Option<String>,
+    image_url: Option<String>,
+    video_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiVersion {
+    riot_client_version: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiSkin {
+    uuid: String,
+    display_name: String,
+    content_tier_uuid: Option<String>,
+    display_icon: Option<String>,
+    #[serde(default)]
+    levels: Vec<ApiSkinLevel>,
+    #[serde(default)]
+    chromas: Vec<ApiSkinChroma>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiSkinLevel {
+    uuid: String,
+    display_icon: Option<String>,
+    streamed_video: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiSkinChroma {
+    uuid: String,
+    display_name: String,
+    display_icon: Option<String>,
+    full_render: Option<String>,
+    streamed_video: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiContentTier {
+    uuid: String,
+    dev_name: String,
+    highlight_color: Option<String>,
+}
+
+fn get<T: for<'de> Deserialize<'de>>(path: &str) -> Result<T, String> {
+    ureq::get(&format!("{}/{}", API_BASE, path))
+        .timeout(REQUEST_TIMEOUT)
+        .call()
+        .map_err(|e| format!("valorant-api.com request failed: {}", e))?
+        .into_json::<ApiResponse<T>>()
+        .map(|r| r.data)
+        .map_err(|e| format!("Invalid valorant-api.com response: {}", e))
+}
+
+/// Single-spaced, without the "Level N" suffix the store shows on skin levels
+fn clean_name(name: &str) -> String {
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    if words.len() > 2
+        && words[words.len() - 2].eq_ignore_ascii_case("level")
+        && words[words.len() - 1].chars().all(|c| c.is_ascii_digit())
+    {
+        words.truncate(words.len() - 2);
+    }
+    words.join(" ")
+}
+
+fn build_catalog(version: String, skins: Vec<ApiSkin>, tiers: Vec<ApiContentTier>) -> ContentCatalog {
+    let tiers: HashMap<String, ApiContentTier> = tiers.into_iter().map(|t| (t.uuid.to_lowercase(), t)).collect();
+    let mut entries = HashMap::new();
+
+    for skin in skins {
+        let tier = skin
+            .content_tier_uuid
+            .as_ref()
+            .and_then(|uuid| tiers.get(&uuid.to_lowercase()));
+        let skin_uuid = skin.uuid.to_lowercase();
+        let name = clean_name(&skin.display_name);
+        let image_url = skin
+            .display_icon
+            .clone()
+            .or_else(|| skin.levels.iter().find_map(|l| l.display_icon.clone()))
+            .or_else(|| skin.chromas.iter().find_map(|c| c.full_render.clone()));
+        // The skin's own entry shows off its highest level
+        let video_url = skin.levels.iter().rev().find_map(|l| l.streamed_video.clone());
+
+        let entry = |name: String, image_url: Option<String>, video_url: Option<String>| CatalogEntry {
+            skin_uuid: skin_uuid.clone(),
+            name,
+            tier: tier.map(|t| t.dev_name.clone()),
+            tier_color: tier.and_then(|t| t.highlight_color.clone()),
+            image_url,
+            video_url,
+        };
+
+        for level in &skin.levels {
+            entries.insert(
+                level.uuid.to_lowercase(),
+                entry(
+                    name.clone(),
+                    level.display_icon.clone().or_else(|| image_url.clone()),
+                    level.streamed_video.clone(),
+                ),
+            );
+        }
+        for chroma in &skin.chromas {
+            entries.insert(
+                chroma.uuid.to_lowercase(),
+                entry(
+                    clean_name(&chroma.display_name),
+                    chroma
+                        .full_render
+                        .clone()
+                        .or_else(|| chroma.display_icon.clone())
+                        .or_else(|| image_url.clone()),
+                    chroma.streamed_video.clone(),
+                ),
+            );
+        }
+        entries.insert(skin_uuid.clone(), entry(name, image_url, video_url));
+    }
+
+    ContentCatalog {
+        version,
+        fetched_at: Utc::now().to_rfc3339(),
+        skins: entries,
+    }
+}
+
+/// Run `f` on the catalog, loading it from disk on first use; None when there is none yet
+fn with_catalog<R>(f: impl FnOnce(&ContentCatalog) -> R) -> Option<R> {
+    let mut catalog = CATALOG.lock();
+    if catalog.is_none() {
+        let path = get_valorant_content_json_path();
+        if path.exists() {
+            match read_json_file(&path) {
+                Ok(loaded) => *catalog = Some(loaded),
+                Err(e) => warn!("Failed to load Valorant content catalog: {}", e),
+            }
+        }
+    }
+    catalog.as_ref().map(f)
+}
+
+/// Download the catalog when there is none or the game has updated since
+/// The version is checked at most once per `VERSION_CHECK_INTERVAL`. Errors leave the saved catalog
+/// in place, so callers can log them and carry on
+pub fn refresh_if_needed() -> Result<(), String> {
+    let cached_version = with_catalog(|c| c.version.clone());
+    {
+        let mut last_check = LAST_VERSION_CHECK.lock();
+        if cached_version.is_some() && last_check.is_some_and(|t| t.elapsed() < VERSION_CHECK_INTERVAL) {
+            return Ok(());
+        }
+        *last_check = Some(Instant::now());
+    }
+
+    let version = get::<ApiVersion>("version")?.riot_client_version;
+    if cached_version.as_deref() == Some(version.as_str()) {
+        debug!("Valorant content catalog is up to date ({})", version);
+        return Ok(());
+    }
+
+    info!("Downloading Valorant content catalog for {}", version);
+    let skins: Vec<ApiSkin> = get("weapons/skins")?;
+    let tiers: Vec<ApiContentTier> = get("contenttiers")?;
+    let catalog = build_catalog(version, skins, tiers);
+
+    write_json_file(&get_valorant_content_json_path(), &catalog)?;
+    info!("Saved {} Valorant content entries", catalog.skins.len());
+    *CATALOG.lock() = Some(catalog);
+    Ok(())
+}
+
+/// The saved catalog file, for the store worker
+pub fn catalog_path() -> Option<String> {
+    let path = get_valorant_content_json_path();
+    path.exists().then(|| path.to_string_lossy().to_string())
+}
+
+fn local_image_path(uuid: &str) -> Option<PathBuf> {
+    // Only real UUIDs become file names
+    let uuid = uuid::Uuid::parse_str(uuid).ok()?;
+    Some(get_valorant_images_dir().join(format!("{}.png", uuid)))
+}
+
+/// Path of the downloaded image of a UUID; downloads it first when `download` is set
+fn local_image(uuid: &str, image_url: Option<&str>, download: bool) -> Option<String> {
+    let path = local_image_path(uuid)?;
+    if path.exists() {
+        return Some(path.to_string_lossy().to_string());
+    }
+    if !download {
+        return None;
+    }
+
+    let image_url = image_url?;
+    std::fs::create_dir_all(get_valorant_images_dir()).ok()?;
+    let saved = download_image(image_url, &path);
+    if saved.is_none() {
+        debug!("Failed to download Valorant image {}", image_url);
+    }
+    saved
+}
+
+/// Catalog entry of a skin, level or chroma UUID, with its local image when cached
+/// `download` fetches the image when it isn't cached yet
+pub fn skin_metadata(uuid: &str, download: bool) -> Option<SkinMetadata> {
+    let uuid = uuid.trim().to_lowercase();
+    let entry = with_catalog(|c| c.skins.get(&uuid).cloned()).flatten()?;
+    Some(SkinMetadata {
+        local_image: local_image(&uuid, entry.image_url.as_deref(), download),
+        uuid,
+        skin_uuid: entry.skin_uuid,
+        name: entry.name,
+        tier: entry.tier,
+        tier_color: entry.tier_color,
+        image_url: entry.image_url,
+        video_url: entry.video_url,
+    })
+}
+
+/// Names the worker falls back to when it couldn't resolve an offer
+fn is_placeholder_name(name: &str, uuid: &str) -> bool {
+    name == "Unknown Skin"
+        || name
+            .strip_prefix("Skin ")
+            .is_some_and(|prefix| !prefix.is_empty() && uuid.starts_with(prefix))
+}
+
+fn fill_item(
+    uuid: Option<&str>,
+    name: &mut String,
+    image_url: &mut Option<String>,
+    local: &mut Option<String>,
+    download: bool,
+) {
+    let Some(uuid) = uuid else {
+        return;
+    };
+    if let Some(metadata) = skin_metadata(uuid, false) {
+        if is_placeholder_name(name, uuid) {
+            *name = metadata.name;
+        }
+        if image_url.is_none() {
+            *image_url = metadata.image_url;
+        }
+    }
+    *local = local_image(uuid, image_url.as_deref(), download);
+}
+
+/// Fill in unresolved names, missing image URLs and local images of a store's skins from the cache
+/// With `download`, images not cached yet are downloaded
+pub fn apply_to_store(store: &mut ValorantStore, download: bool) {
+    for item in &mut store.items {
+        fill_item(
+            item.uuid.as_deref(),
+            &mut item.name,
+            &mut item.image_url,
+            &mut item.local_image,
+            download,
+        );
+    }
+    if let Some(night_market) = &mut store.night_market {
+        for item in &mut night_market.items {
+            fill_item(
+                item.uuid.as_deref(),
+                &mut item.name,
+                &mut item.image_url,
+                &mut item.local_image,
+                download,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(uuid: &str, video: Option<&str>) -> ApiSkinLevel {
+        ApiSkinLevel {
+            uuid: uuid.to_string(),
+            display_icon: None,
+            streamed_video: video.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_build_catalog() {
+        let skins = vec![ApiSkin {
+            uuid: "SKIN-1".to_string(),
+            display_name: "Reaver  Vandal".to_string(),
+            content_tier_uuid: Some("tier-1".to_string()),
+            display_icon: Some("https://example.com/skin.png".to_string()),
+            levels: vec![level("level-1", None), level("level-4", Some("https://example.com/l4.mp4"))],
+            chromas: vec![ApiSkinChroma {
+                uuid: "chroma-2".to_string(),
+                display_name: "Reaver Vandal Level 4\n(Variant 1 Red)".to_string(),
+                display_icon: None,
+                full_render: Some("https://example.com/red.png".to_string()),
+                streamed_video: None,
+            }],
+        }];
+        let tiers = vec![ApiContentTier {
+            uuid: "TIER-1".to_string(),
+            dev_name: "Premium".to_string(),
+            highlight_color: Some("d1548db3".to_string()),
+        }];
+
+        let catalog = build_catalog("release-09.00".to_string(), skins, tiers);
+        assert_eq!(catalog.skins.len(), 4);
+
+        let skin = &catalog.skins["skin-1"];
+        assert_eq!(skin.name, "Reaver Vandal");
+        assert_eq!(skin.tier.as_deref(), Some("Premium"));
+        assert_eq!(skin.video_url.as_deref(), Some("https://example.com/l4.mp4"));
+
+        let base_level = &catalog.skins["level-1"];
+        assert_eq!(base_level.skin_uuid, "skin-1");
+        assert_eq!(base_level.name, "Reaver Vandal");
+        assert_eq!(base_level.image_url.as_deref(), Some("https://example.com/skin.png"));
+        assert_eq!(base_level.video_url, None);
+
+        let chroma = &catalog.skins["chroma-2"];
+        assert_eq!(chroma.name, "Reaver Vandal Level 4 (Variant 1 Red)");
+        assert_eq!(chroma.image_url.as_deref(), Some("https://example.com/red.png"));
+    }
+
+    #[test]
+    fn test_is_placeholder_name() {
+        let uuid = "5ce9f1e4-4fd3-f3b5-e4a7-3a8e0a8b1a5b";
+        assert!(is_placeholder_name("Unknown Skin", uuid));
+        assert!(is_placeholder_name("Skin 5ce9f1e4", uuid));
+        assert!(!is_placeholder_name("Skin 00000000", uuid));
+        assert!(!is_placeholder_name("Reaver Vandal", uuid));
+        assert_eq!(clean_name("Reaver Vandal Level 4"), "Reaver Vandal");
+    }
+}
@@ -0,0 +1,3 @@
+pub mod content_cache;
+pub mod store_scheduler;
+pub mod store_stats;
@@ -0,0 +1,249 @@
+// Valorant store auto-refresh
+// Refreshes the active account's store when its saved expiry passes, emits
+// `valorant:store_updated` so every window picks it up, and notifies about the new rotation when
+// `valorant_store_notifications` is on. The next check is recomputed from each fetched expiry, so
+// it doesn't drift, and manual refreshes re-arm it through `rearm`. Waits are capped at
+// POLL_INTERVAL so a PC that was asleep at reset time catches up on wake. The last notified rotation
+// of each account is saved so restarts don't notify it again
+
+use chrono::{DateTime, Duration as ChronoDuration, DurationRound, Utc};
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::auth::load_riot_accounts;
+use crate::commands::valorant::{fetch_valorant_store, get_current_rotation_start, get_valorant_store};
+use crate::file_manager::{read_json_file, write_json_file};
+use crate::models::ValorantStore;
+use crate::utils::get_valorant_notifier_json_path;
+
+/// Longest single wait, so wall-clock jumps (sleep, clock changes) are noticed
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Wait before retrying after a failed check
+const RETRY_DELAY_MINUTES: i64 = 15;
+
+/// Minimum gap between checks, in case a fetched store is already past its expiry
+const MIN_CHECK_GAP_MINUTES: i64 = 1;
+
+lazy_static! {
+    static ref REARM: Mutex<Option<Sender<()>>> = Mutex::new(None);
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotifierState {
+    /// Account id -> last notified rotation key
+    #[serde(default)]
+    notified_rotations: HashMap<String, String>,
+}
+
+/// Start the scheduler thread
+pub fn start(app: AppHandle) {
+    let (sender, receiver) = mpsc::channel();
+    *REARM.lock() = Some(sender);
+
+    std::thread::spawn(move || {
+        // Check right away; a store that is still current just yields its expiry
+        let mut next_check = Utc::now();
+        loop {
+            let wait = (next_check - Utc::now()).to_std().unwrap_or(Duration::ZERO).min(POLL_INTERVAL);
+            match receiver.recv_timeout(wait) {
+                Ok(()) => {
+                    next_check = Utc::now();
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+            if Utc::now() < next_check {
+                continue;
+            }
+
+            next_check = match run_check(&app) {
+                Ok(at) => {
+                    debug!("Next Valorant store check at {}", at);
+                    at
+                }
+                Err(e) => {
+                    warn!("Scheduled Valorant store check failed: {}", e);
+                    Utc::now() + ChronoDuration::minutes(RETRY_DELAY_MINUTES)
+                }
+            };
+        }
+    });
+}
+
+/// Recompute the next check from the saved store, after a manual refresh or account change
+pub fn rearm() {
+    if let Some(sender) = REARM.lock().as_ref() {
+        let _ = sender.send(());
+    }
+}
+
+/// When the saved store goes stale; `now` when it already is
+fn next_reset(store: Option<&ValorantStore>, now: DateTime<Utc>, rotation_start: DateTime<Utc>) -> DateTime<Utc> {
+    let Some(store) = store else {
+        return now;
+    };
+    if let Some(expires_at) = store.expires_at() {
+        return expires_at;
+    }
+    // Stores saved before the API expiry was recorded fall back to the fixed daily reset
+    match DateTime::parse_from_rfc3339(&store.checked_at) {
+        Ok(checked_at) if checked_at.with_timezone(&Utc) >= rotation_start => {
+            rotation_start + ChronoDuration::days(1)
+        }
+        _ => now,
+    }
+}
+
+/// Identifies a rotation by its reset time
+/// The expiry is derived from a remaining-seconds countdown, so it is rounded to the hour to make
+/// two checks of the same rotation agree
+fn rotation_key(store: &ValorantStore) -> String {
+    match store.expires_at() {
+        Some(expires_at) => expires_at
+            .duration_round(ChronoDuration::hours(1))
+            .unwrap_or(expires_at)
+            .to_rfc3339(),
+        None => store.date.clone(),
+    }
+}
+
+fn emit_auth_required(app: &AppHandle, account_id: &str, reason: &str) {
+    info!("Valorant store refresh skipped for {}: {}", account_id, reason);
+    let _ = app.emit(
+        "valorant:auth_required",
+        serde_json::json!({ "account_id": account_id, "reason": reason }),
+    );
+}
+
+/// Refresh the active account's store if it has gone stale; returns when to check next
+/// Missing or expired credentials skip the refresh until the next rotation or a re-arm
+fn run_check(app: &AppHandle) -> Result<DateTime<Utc>, String> {
+    let next_rotation = get_current_rotation_start() + ChronoDuration::days(1);
+    let accounts = load_riot_accounts()?;
+    let Some(account) = accounts.resolve(None) else {
+        return Ok(next_rotation);
+    };
+    let account_id = account.id.clone();
+
+    let current = get_valorant_store(Some(account_id.clone()))?;
+    let due = next_reset(current.as_ref(), Utc::now(), get_current_rotation_start());
+    if Utc::now() < due {
+        return Ok(due);
+    }
+
+    if !account.cookies.is_complete() {
+        emit_auth_required(app, &account_id, "Riot login is incomplete");
+        return Ok(next_rotation);
+    }
+
+    debug!("Valorant store is stale, checking for the new rotation");
+    let store = tauri::async_runtime::block_on(fetch_valorant_store(app.clone(), None, Some(account_id.clone())))?;
+    if store.is_real_data == Some(false) {
+        emit_auth_required(app, &account_id, "Riot session has expired");
+        return Ok(next_rotation);
+    }
+    if store.items.is_empty() {
+        return Err("Store check returned no offers".to_string());
+    }
+
+    let _ = app.emit("valorant:store_updated", serde_json::json!({ "store": store }));
+
+    let notify = crate::commands::settings::get_settings()
+        .unwrap_or_default()
+        .valorant_store_notifications;
+    if notify {
+        notify_rotation(app, &account_id, &store)?;
+    }
+
+    let now = Utc::now();
+    let next = next_reset(Some(&store), now, get_current_rotation_start());
+    Ok(next.max(now + ChronoDuration::minutes(MIN_CHECK_GAP_MINUTES)))
+}
+
+/// Show the new rotation's offers, once per rotation and account
+fn notify_rotation(app: &AppHandle, account_id: &str, store: &ValorantStore) -> Result<(), String> {
+    let path = get_valorant_notifier_json_path();
+    let mut state: NotifierState = if path.exists() {
+        read_json_file(&path).unwrap_or_default()
+    } else {
+        NotifierState::default()
+    };
+    let key = rotation_key(store);
+    if state.notified_rotations.get(account_id) == Some(&key) {
+        return Ok(());
+    }
+
+    let body = store
+        .items
+        .iter()
+        .map(|item| format!("{} - {} VP", item.name, item.price))
+        .collect::<Vec<_>>()
+        .join("\n");
+    app.notification()
+        .builder()
+        .title("Valorant store has reset")
+        .body(body)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+    info!("Notified Valorant store rotation {}", key);
+
+    state.notified_rotations.insert(account_id.to_string(), key);
+    write_json_file(&path, &state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(checked_at: &str, expires_at: Option<&str>) -> ValorantStore {
+        ValorantStore {
+            date: "2024-01-15".to_string(),
+            items: vec![],
+            checked_at: checked_at.to_string(),
+            is_real_data: Some(true),
+            expires_at: expires_at.map(String::from),
+            night_market: None,
+            bundle: None,
+            other_bundles: vec![],
+            wishlist_hits: vec![],
+        }
+    }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_next_reset() {
+        let rotation_start = at("2024-01-15T00:00:00Z");
+        let now = at("2024-01-15T12:00:00Z");
+        assert_eq!(next_reset(None, now, rotation_start), now);
+
+        // API expiry wins over the fixed reset time
+        let fresh = store("2024-01-15T01:00:00Z", Some("2024-01-15T23:59:58Z"));
+        assert_eq!(next_reset(Some(&fresh), now, rotation_start), at("2024-01-15T23:59:58Z"));
+
+        // Without an expiry, the next fixed reset, or now when checked in an earlier rotation
+        let current = store("2024-01-15T01:00:00Z", None);
+        assert_eq!(next_reset(Some(&current), now, rotation_start), at("2024-01-16T00:00:00Z"));
+        let previous = store("2024-01-14T23:00:00Z", None);
+        assert_eq!(next_reset(Some(&previous), now, rotation_start), now);
+    }
+
+    #[test]
+    fn test_rotation_key_ignores_countdown_jitter() {
+        let a = store("2024-01-15T01:00:00Z", Some("2024-01-15T23:59:58Z"));
+        let b = store("2024-01-15T09:00:00Z", Some("2024-01-16T00:00:03Z"));
+        assert_eq!(rotation_key(&a), rotation_key(&b));
+        assert_eq!(rotation_key(&store("2024-01-15T01:00:00Z", None)), "2024-01-15");
+    }
+}
@@ -0,0 +1,300 @@
+// Valorant store history analytics
+// Pure computation over the saved rotations. Skins are matched by UUID when both sides have one,
+// otherwise by normalized name, since older history entries were saved without UUIDs
+
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{
+    SkinAppearance, SkinAppearanceStats, StoreStreaks, ValorantItem, ValorantStore, ValorantWishlistItem,
+};
+
+/// Rotations older than this lose their image URLs
+const FULL_DETAIL_DAYS: i64 = 90;
+
+/// Rotations older than this are dropped
+const MAX_HISTORY_DAYS: i64 = 730;
+
+/// Lowercase, single-spaced, without a trailing "Level N"
+pub fn normalize_skin_name(name: &str) -> String {
+    let mut words: Vec<String> = name.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if words.len() > 2
+        && words[words.len() - 2] == "level"
+        && words[words.len() - 1].chars().all(|c| c.is_ascii_digit())
+    {
+        words.truncate(words.len() - 2);
+    }
+    words.join(" ")
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Rotations with real offers, oldest first
+fn real_rotations(stores: &[ValorantStore]) -> Vec<&ValorantStore> {
+    let mut rotations: Vec<&ValorantStore> = stores
+        .iter()
+        .filter(|s| s.is_real_data != Some(false) && !s.items.is_empty() && parse_date(&s.date).is_some())
+        .collect();
+    rotations.sort_by(|a, b| a.date.cmp(&b.date));
+    rotations
+}
+
+fn item_matches(item: &ValorantItem, skin_uuid: Option<&str>, skin_name: &str) -> bool {
+    match (skin_uuid, item.uuid.as_deref()) {
+        (Some(wanted), Some(uuid)) => wanted.eq_ignore_ascii_case(uuid),
+        _ => normalize_skin_name(&item.name) == skin_name,
+    }
+}
+
+/// How often a skin (name or UUID) has been offered in the daily rotation
+pub fn skin_appearance_stats(stores: &[ValorantStore], skin: &str) -> SkinAppearanceStats {
+    let query = skin.trim();
+    let skin_uuid = uuid::Uuid::parse_str(query).ok().map(|u| u.to_string());
+    let mut skin_name = normalize_skin_name(query);
+
+    // A UUID query also matches entries saved without UUIDs, through the name it was offered under
+    if let Some(uuid) = &skin_uuid {
+        if let Some(item) = stores
+            .iter()
+            .flat_map(|s| s.items.iter())
+            .find(|i| i.uuid.as_deref().is_some_and(|u| u.eq_ignore_ascii_case(uuid)))
+        {
+            skin_name = normalize_skin_name(&item.name);
+        }
+    }
+
+    let mut display_name = None;
+    let mut appearances = Vec::new();
+    for store in real_rotations(stores) {
+        if let Some(item) = store.items.iter().find(|i| item_matches(i, skin_uuid.as_deref(), &skin_name)) {
+            display_name = Some(item.name.clone());
+            appearances.push(SkinAppearance {
+                date: store.date.clone(),
+                price: item.price,
+            });
+        }
+    }
+
+    let dates: Vec<NaiveDate> = appearances.iter().filter_map(|a| parse_date(&a.date)).collect();
+    let average_gap_days = (dates.len() >= 2).then(|| {
+        let span = (dates[dates.len() - 1] - dates[0]).num_days() as f64;
+        span / (dates.len() - 1) as f64
+    });
+
+    SkinAppearanceStats {
+        skin: display_name.unwrap_or_else(|| query.to_string()),
+        appearance_count: appearances.len() as u32,
+        first_seen: appearances.first().map(|a| a.date.clone()),
+        last_seen: appearances.last().map(|a| a.date.clone()),
+        average_gap_days,
+        appearances,
+    }
+}
+
+/// Fun stats over the whole history
+pub fn store_streaks(stores: &[ValorantStore], wishlist: &[ValorantWishlistItem], today: NaiveDate) -> StoreStreaks {
+    let rotations = real_rotations(stores);
+
+    let is_hit = |store: &ValorantStore| {
+        !store.wishlist_hits.is_empty()
+            || store
+                .items
+                .iter()
+                .any(|i| wishlist.iter().any(|w| w.matches(&i.name, i.uuid.as_deref())))
+    };
+    let hit_dates: Vec<NaiveDate> = rotations
+        .iter()
+        .filter(|s| is_hit(s))
+        .filter_map(|s| parse_date(&s.date))
+        .collect();
+
+    let mut counts: HashMap<String, (String, u32)> = HashMap::new();
+    for item in rotations.iter().flat_map(|s| s.items.iter()) {
+        let entry = counts
+            .entry(normalize_skin_name(&item.name))
+            .or_insert_with(|| (item.name.clone(), 0));
+        entry.1 += 1;
+    }
+    let most_frequent = counts
+        .values()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .cloned();
+
+    // Longest run of consecutive tracked rotations without a repeat of any earlier skin
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut fresh_streak = 0u32;
+    let mut longest_fresh_streak = 0u32;
+    for store in &rotations {
+        let names: Vec<String> = store.items.iter().map(|i| normalize_skin_name(&i.name)).collect();
+        if names.iter().any(|n| seen.contains(n)) {
+            fresh_streak = 0;
+        } else {
+            fresh_streak += 1;
+            longest_fresh_streak = longest_fresh_streak.max(fresh_streak);
+        }
+        seen.extend(names);
+    }
+
+    StoreStreaks {
+        rotations_tracked: rotations.len() as u32,
+        distinct_skins_seen: counts.len() as u32,
+        last_wishlist_hit: hit_dates.last().map(|d| d.format("%Y-%m-%d").to_string()),
+        days_since_wishlist_hit: hit_dates.last().map(|d| (today - *d).num_days()),
+        most_frequent_skin: most_frequent.as_ref().map(|(name, _)| name.clone()),
+        most_frequent_skin_count: most_frequent.map(|(_, count)| count).unwrap_or(0),
+        longest_fresh_streak,
+    }
+}
+
+/// Keep the history file bounded: drop old and failed rotations and strip images from older ones
+/// The most recent entry is always kept as-is, since it backs `get_valorant_store`
+pub fn compact_history(stores: &mut Vec<ValorantStore>, today: NaiveDate) {
+    let latest_date = stores.iter().map(|s| s.date.clone()).max();
+    let age_days = |store: &ValorantStore| parse_date(&store.date).map(|d| (today - d).num_days());
+
+    stores.retain(|store| {
+        if Some(&store.date) == latest_date.as_ref() {
+            return true;
+        }
+        store.is_real_data != Some(false) && age_days(store).is_some_and(|age| age <= MAX_HISTORY_DAYS)
+    });
+
+    for store in stores.iter_mut() {
+        if !age_days(store).is_some_and(|age| age > FULL_DETAIL_DAYS) {
+            continue;
+        }
+        for item in &mut store.items {
+            item.image_url = None;
+        }
+        if let Some(night_market) = &mut store.night_market {
+            for item in &mut night_market.items {
+                item.image_url = None;
+            }
+        }
+        for bundle in store.bundle.iter_mut().chain(store.other_bundles.iter_mut()) {
+            bundle.image_url = None;
+            for item in &mut bundle.items {
+                item.image_url = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, uuid: Option<&str>) -> ValorantItem {
+        ValorantItem {
+            name: name.to_string(),
+            price: 1775,
+            image_url: Some("https://example.com/skin.png".to_string()),
+            item_type: "skin".to_string(),
+            uuid: uuid.map(String::from),
+            local_image: None,
+        }
+    }
+
+    fn rotation(date: &str, items: Vec<ValorantItem>) -> ValorantStore {
+        ValorantStore {
+            date: date.to_string(),
+            items,
+            checked_at: format!("{}T01:00:00Z", date),
+            is_real_data: Some(true),
+            expires_at: None,
+            night_market: None,
+            bundle: None,
+            other_bundles: vec![],
+            wishlist_hits: vec![],
+        }
+    }
+
+    fn day(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    const REAVER_UUID: &str = "5ce9f1e4-4fd3-f3b5-e4a7-3a8e0a8b1a5b";
+
+    fn history() -> Vec<ValorantStore> {
+        vec![
+            rotation("2024-01-10", vec![item("Reaver Vandal Level 1", None), item("Prime Phantom", None)]),
+            rotation("2024-01-01", vec![item("Reaver Vandal", Some(REAVER_UUID))]),
+            rotation("2024-01-05", vec![item("Oni Phantom", None)]),
+            rotation("2024-01-07", vec![item("reaver  vandal", None)]),
+        ]
+    }
+
+    #[test]
+    fn test_normalize_skin_name() {
+        assert_eq!(normalize_skin_name("  Reaver   Vandal Level 4 "), "reaver vandal");
+        assert_eq!(normalize_skin_name("Level 4"), "level 4");
+        assert_eq!(normalize_skin_name("Prime 2.0 Karambit"), "prime 2.0 karambit");
+    }
+
+    #[test]
+    fn test_skin_appearance_stats() {
+        let stats = skin_appearance_stats(&history(), "reaver vandal");
+        assert_eq!(stats.appearance_count, 3);
+        assert_eq!(stats.first_seen.as_deref(), Some("2024-01-01"));
+        assert_eq!(stats.last_seen.as_deref(), Some("2024-01-10"));
+        assert_eq!(stats.average_gap_days, Some(4.5));
+
+        // A UUID query also finds entries that were saved without one
+        let by_uuid = skin_appearance_stats(&history(), &REAVER_UUID.to_uppercase());
+        assert_eq!(by_uuid.appearance_count, 3);
+
+        let never = skin_appearance_stats(&history(), "Glitchpop Odin");
+        assert_eq!(never.appearance_count, 0);
+        assert_eq!(never.average_gap_days, None);
+    }
+
+    #[test]
+    fn test_store_streaks() {
+        let mut stores = history();
+        stores.push(ValorantStore {
+            is_real_data: Some(false),
+            ..rotation("2024-01-12", vec![item("Oni Phantom", None)])
+        });
+        let wishlist = vec![ValorantWishlistItem {
+            id: "w1".to_string(),
+            skin_uuid: None,
+            name: Some("oni".to_string()),
+            added_at: "2024-01-01T00:00:00Z".to_string(),
+        }];
+
+        let streaks = store_streaks(&stores, &wishlist, day("2024-01-15"));
+        assert_eq!(streaks.rotations_tracked, 4);
+        assert_eq!(streaks.distinct_skins_seen, 3);
+        assert_eq!(streaks.last_wishlist_hit.as_deref(), Some("2024-01-05"));
+        assert_eq!(streaks.days_since_wishlist_hit, Some(10));
+        assert_eq!(streaks.most_frequent_skin_count, 3);
+        assert_eq!(streaks.longest_fresh_streak, 2);
+
+        assert_eq!(store_streaks(&stores, &[], day("2024-01-15")).days_since_wishlist_hit, None);
+    }
+
+    #[test]
+    fn test_compact_history() {
+        let mut stores = vec![
+            rotation("2021-06-01", vec![item("Ancient", None)]),
+            rotation("2023-10-01", vec![item("Old", None)]),
+            ValorantStore {
+                is_real_data: Some(false),
+                ..rotation("2024-01-09", vec![])
+            },
+            rotation("2024-01-10", vec![item("Recent", None)]),
+            ValorantStore {
+                is_real_data: Some(false),
+                ..rotation("2024-01-11", vec![])
+            },
+        ];
+        compact_history(&mut stores, day("2024-01-11"));
+
+        let dates: Vec<&str> = stores.iter().map(|s| s.date.as_str()).collect();
+        assert_eq!(dates, vec!["2023-10-01", "2024-01-10", "2024-01-11"]);
+        assert_eq!(stores[0].items[0].image_url, None);
+        assert!(stores[1].items[0].image_url.is_some());
+    }
+}
@@ -5,7 +5,10 @@ import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
 import { Sidebar } from './components/Sidebar';
 import { UpdateToast } from './components/UpdateToast';
+import { WhatsNewDialog } from './components/WhatsNewDialog';
+import { HostKeyDialog } from './components/server/HostKeyDialog';
 import { FloatingPartnerWidget } from './components/friends';
+import { PerformanceAlertToast } from './components/performance';
 import { DefaultRouteRedirect } from './components/DefaultRouteRedirect';
 import { useErrorLogger } from './hooks/useErrorLogger';
 import { Dashboard } from './views/Dashboard';
@@ -23,13 +26,23 @@ import FriendsPage from './views/Friends';
 import { Settings } from './views/Settings';
 import { useUpdater } from './hooks';
 import { NavigationSettingsProvider } from './contexts';
+import type { SettingsChangedEvent } from './types';
 import './App.css';
 
 function App() {
   // Capture frontend errors and forward to log file
   useErrorLogger();
 
-  const { state, checkForUpdate, downloadUpdate, installUpdate, dismissUpdate } = useUpdater();
+  const {
+    state,
+    checkForUpdate,
+    downloadUpdate,
+    cancelDownload,
+    installUpdate,
+    dismissUpdate,
+    skipUpdate,
+    snoozeUpdate,
+  } = useUpdater();
   const navigate = useNavigate();
   const location = useLocation();
 
@@ -46,6 +59,18 @@ function App() {
     return () => clearTimeout(timer);
   }, [checkForUpdate]);
 
+  // Check the new channel right away, so switching back to stable from a beta build offers the downgrade
+  useEffect(() => {
+    const unlisten = listen<SettingsChangedEvent>('settings:changed', (event) => {
+      if (event.payload.changes.some((change) => change.key === 'update_channel')) {
+        checkForUpdate(true);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [checkForUpdate]);
+
   // Listen for game launch navigation event
   useEffect(() => {
     const setupListener = async () => {
@@ -113,12 +138,24 @@ function App() {
         {/* Update toast notification */}
         <UpdateToast
           state={state}
-          onDownload={downloadUpdate}
+          onDownload={() => downloadUpdate()}
+          onCancelDownload={cancelDownload}
           onInstall={installUpdate}
           onDismiss={dismissUpdate}
-          onRetry={checkForUpdate}
+          onSkip={skipUpdate}
+          onSnooze={() => snoozeUpdate(24)}
+          onRetry={() => checkForUpdate(true)}
         />
 
+        {/* Performance threshold alerts */}
+        <PerformanceAlertToast />
+
+        {/* Release notes after an update */}
+        <WhatsNewDialog />
+
+        {/* SSH host key verification for any connection, including background ones */}
+        <HostKeyDialog />
+
         {/* Floating partner widget */}
         <FloatingPartnerWidget />
       </div>
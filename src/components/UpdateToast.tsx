@@ -4,16 +4,22 @@ import type { UpdateState } from '../types/updater';
 interface UpdateToastProps {
   state: UpdateState;
   onDownload: () => void;
+  onCancelDownload: () => void;
   onInstall: () => void;
   onDismiss: () => void;
+  onSkip: () => void;
+  onSnooze: () => void;
   onRetry: () => void;
 }
 
 export function UpdateToast({
   state,
   onDownload,
+  onCancelDownload,
   onInstall,
   onDismiss,
+  onSkip,
+  onSnooze,
   onRetry,
 }: UpdateToastProps) {
   if (state.status === 'idle') {
@@ -39,6 +45,11 @@ export function UpdateToast({
             <div className="flex items-center gap-2">
               <Download size={20} className="text-accent-primary" />
               <span className="text-text-primary font-medium">Update Available</span>
+              {state.info.channel === 'beta' && (
+                <span className="text-[10px] uppercase tracking-wide px-1.5 py-0.5 rounded bg-amber-500/20 text-amber-400">
+                  Beta
+                </span>
+              )}
             </div>
             <button
               onClick={onDismiss}
@@ -48,7 +59,9 @@ export function UpdateToast({
             </button>
           </div>
           <p className="text-text-secondary text-sm mb-2">
-            Version {state.info.version} is ready to download.
+            {state.info.downgrade
+              ? `Version ${state.info.version} is the latest on this channel, older than the installed build.`
+              : `Version ${state.info.version} is ready to download.`}
           </p>
           {state.info.body && (
             <p className="text-text-muted text-xs mb-3 line-clamp-2">
@@ -64,12 +77,18 @@ export function UpdateToast({
               Download Now
             </button>
             <button
-              onClick={onDismiss}
+              onClick={onSnooze}
               className="btn btn-secondary btn-sm"
             >
-              Later
+              Tomorrow
             </button>
           </div>
+          <button
+            onClick={onSkip}
+            className="text-text-muted hover:text-text-secondary text-xs mt-2 transition-colors"
+          >
+            Skip this version
+          </button>
         </div>
       </div>
     );
@@ -88,6 +107,13 @@ export function UpdateToast({
               </span>
               <span className="text-text-muted text-sm ml-2">{percent}%</span>
             </div>
+            <button
+              onClick={onCancelDownload}
+              className="text-text-muted hover:text-text-secondary transition-colors"
+              title="Cancel download"
+            >
+              <X size={18} />
+            </button>
           </div>
           {/* Progress bar */}
           <div className="h-2 bg-white/10 rounded-full overflow-hidden">
@@ -0,0 +1,84 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Sparkles, X } from 'lucide-react';
+import type { WhatsNew } from '../types/updater';
+
+// Shows the release notes of the version just installed, once
+export function WhatsNewDialog() {
+  const [whatsNew, setWhatsNew] = useState<WhatsNew | null>(null);
+
+  useEffect(() => {
+    // The notes are fetched in the background, so they may arrive before or after this mounts
+    const take = async () => {
+      try {
+        const result = await invoke<WhatsNew | null>('take_whats_new');
+        if (result) {
+          setWhatsNew(result);
+        }
+      } catch (err) {
+        console.error('Failed to load release notes:', err);
+      }
+    };
+
+    take();
+    const unlisten = listen('update:whats_new', () => {
+      take();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!whatsNew) {
+    return null;
+  }
+
+  const close = () => setWhatsNew(null);
+
+  return (
+    <div
+      className="fixed inset-0 z-[100] flex items-center justify-center p-4 bg-black/60 backdrop-blur-sm animate-fade-in"
+      onClick={close}
+    >
+      <div
+        className="glass-elevated rounded-xl w-full max-w-lg border border-white/20 shadow-2xl animate-scale-in"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="flex items-start gap-4 p-6 pb-4">
+          <div className="p-3 rounded-lg bg-accent/20 text-accent">
+            <Sparkles className="w-6 h-6" />
+          </div>
+          <div className="flex-1 min-w-0">
+            <h2 className="text-lg font-semibold text-primary">What's new in {whatsNew.version}</h2>
+            <p className="mt-1 text-xs text-muted">Updated from {whatsNew.previous_version}</p>
+          </div>
+          <button
+            onClick={close}
+            className="p-1.5 rounded-lg text-muted hover:text-primary hover:bg-white/5 transition-all"
+          >
+            <X className="w-4 h-4" />
+          </button>
+        </div>
+
+        <div className="px-6 pb-4 max-h-[50vh] overflow-y-auto">
+          {whatsNew.entry?.body ? (
+            <p className="text-sm text-secondary whitespace-pre-wrap">{whatsNew.entry.body}</p>
+          ) : (
+            <p className="text-sm text-muted">Release notes for this version aren't available.</p>
+          )}
+        </div>
+
+        <div className="flex items-center justify-end gap-3 p-6 pt-4 border-t border-white/10">
+          <button
+            onClick={close}
+            className="px-4 py-2 rounded-lg font-medium bg-accent hover:bg-accent/80 text-white transition-colors"
+          >
+            Got it
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
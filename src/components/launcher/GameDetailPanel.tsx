@@ -8,9 +8,16 @@ interface GameDetailPanelProps {
   onClose: () => void;
   onLaunch: (gameId: string) => void;
   onRemove: (gameId: string) => void;
+  onToggleHiddenFromPresence: (gameId: string, hidden: boolean) => void;
 }
 
-export function GameDetailPanel({ game, onClose, onLaunch, onRemove }: GameDetailPanelProps) {
+export function GameDetailPanel({
+  game,
+  onClose,
+  onLaunch,
+  onRemove,
+  onToggleHiddenFromPresence,
+}: GameDetailPanelProps) {
   const [iconSrc, setIconSrc] = useState<string | null>(null);
 
   useEffect(() => {
@@ -103,6 +110,17 @@ export function GameDetailPanel({ game, onClose, onLaunch, onRemove }: GameDetai
             </div>
           </div>
 
+          {/* Presence */}
+          <label className="flex items-center gap-2 text-sm text-secondary cursor-pointer">
+            <input
+              type="checkbox"
+              checked={game.hide_from_presence}
+              onChange={(e) => onToggleHiddenFromPresence(game.id, e.target.checked)}
+              className="w-4 h-4 rounded border-white/20 bg-white/5 text-accent focus:ring-accent"
+            />
+            Hide from Discord and friends presence
+          </label>
+
           {/* Added Date */}
           <p className="text-xs text-muted">
             Added to library: {formatDate(game.added_at)}
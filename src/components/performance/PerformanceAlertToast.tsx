@@ -0,0 +1,71 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { AlertTriangle, X } from 'lucide-react';
+import type { AlertMetric, PerformanceAlertEvent } from '../../types';
+
+const AUTO_HIDE_MS = 8000;
+
+const METRIC_LABELS: Record<AlertMetric, { label: string; unit: string }> = {
+  cpu_usage: { label: 'CPU usage', unit: '%' },
+  cpu_temp: { label: 'CPU temperature', unit: '°C' },
+  gpu_usage: { label: 'GPU usage', unit: '%' },
+  gpu_temp: { label: 'GPU temperature', unit: '°C' },
+  vram_usage: { label: 'VRAM usage', unit: '%' },
+  ram_usage: { label: 'RAM usage', unit: '%' },
+  ram_available_mb: { label: 'Available RAM', unit: ' MB' },
+};
+
+function describe(event: PerformanceAlertEvent): string {
+  const { label, unit } = METRIC_LABELS[event.metric];
+  return `${label} is ${event.value.toFixed(1)}${unit} (${event.comparator} ${event.threshold}${unit})`;
+}
+
+// Shows threshold alerts fired by the performance monitor on any page; alerts with
+// native notifications enabled are also sent to the OS by the backend
+export function PerformanceAlertToast() {
+  const [alert, setAlert] = useState<PerformanceAlertEvent | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<PerformanceAlertEvent>('performance:alert', (event) => {
+      setAlert(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!alert) {
+      return;
+    }
+    const timer = setTimeout(() => setAlert(null), AUTO_HIDE_MS);
+    return () => clearTimeout(timer);
+  }, [alert]);
+
+  if (!alert) {
+    return null;
+  }
+
+  return (
+    <div className="fixed top-4 right-4 z-50 animate-slide-up">
+      <div className="glass-elevated rounded-xl p-4 shadow-lg min-w-[300px] max-w-[400px]">
+        <div className="flex items-start justify-between gap-3">
+          <div className="flex items-start gap-2">
+            <AlertTriangle size={20} className="text-amber-400 shrink-0" />
+            <div>
+              <p className="text-text-primary font-medium">{alert.name}</p>
+              <p className="text-text-secondary text-sm">{describe(alert)}</p>
+            </div>
+          </div>
+          <button
+            onClick={() => setAlert(null)}
+            className="text-text-muted hover:text-text-secondary transition-colors"
+          >
+            <X size={18} />
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
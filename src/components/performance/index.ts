@@ -3,3 +3,4 @@ export { CpuChart } from './CpuChart';
 export { GpuChart } from './GpuChart';
 export { RamChart } from './RamChart';
 export { CoreBars } from './CoreBars';
+export { PerformanceAlertToast } from './PerformanceAlertToast';
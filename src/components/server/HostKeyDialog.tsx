@@ -0,0 +1,155 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { AlertTriangle, Key, X } from 'lucide-react';
+import type { HostKeyEvent } from '../../types';
+
+interface PendingHostKey extends HostKeyEvent {
+  changed: boolean;
+}
+
+// Asks to trust a server's host key whenever a connection is refused over it, including
+// background connections (saved credentials, metrics sampler, tunnels) that never show a login form
+export function HostKeyDialog() {
+  const [pending, setPending] = useState<PendingHostKey | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const show = (changed: boolean) => (event: { payload: HostKeyEvent }) => {
+      // Background callers keep retrying; don't reset a dialog already showing this key
+      setPending((current) =>
+        current && current.profile === event.payload.profile && current.fingerprint === event.payload.fingerprint
+          ? current
+          : { ...event.payload, changed }
+      );
+      setError(null);
+    };
+
+    const unlistenUnknown = listen<HostKeyEvent>('server:host_key_unknown', show(false));
+    const unlistenChanged = listen<HostKeyEvent>('server:host_key_changed', show(true));
+
+    return () => {
+      unlistenUnknown.then((fn) => fn());
+      unlistenChanged.then((fn) => fn());
+    };
+  }, []);
+
+  if (!pending) {
+    return null;
+  }
+
+  const answer = async (accept: boolean) => {
+    try {
+      setBusy(true);
+      setError(null);
+      // Trusting emits server:host_key_trusted, which the failed callers retry on
+      await invoke('verify_host_key', { profile: pending.profile, fingerprint: pending.fingerprint, accept });
+      setPending(null);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const clearTrusted = async () => {
+    try {
+      setBusy(true);
+      setError(null);
+      // The next connection presents the new key, which is then offered here as unknown
+      await invoke('clear_known_host', { profile: pending.profile });
+      setPending(null);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-[100] flex items-center justify-center p-4 bg-black/60 backdrop-blur-sm animate-fade-in">
+      <div className="glass-elevated rounded-xl w-full max-w-lg border border-white/20 shadow-2xl animate-scale-in">
+        <div className="flex items-start gap-4 p-6 pb-4">
+          <div className={`p-3 rounded-lg ${pending.changed ? 'bg-red-500/20 text-red-400' : 'bg-accent/20 text-accent'}`}>
+            {pending.changed ? <AlertTriangle className="w-6 h-6" /> : <Key className="w-6 h-6" />}
+          </div>
+          <div className="flex-1 min-w-0">
+            <h2 className="text-lg font-semibold text-primary">
+              {pending.changed ? 'Host key changed' : 'Verify host key'}
+            </h2>
+            <p className="mt-1 text-xs text-muted">
+              {pending.host}:{pending.port}
+            </p>
+          </div>
+          <button
+            onClick={() => (pending.changed ? setPending(null) : answer(false))}
+            disabled={busy}
+            className="p-1.5 rounded-lg text-muted hover:text-primary hover:bg-white/5 transition-all"
+          >
+            <X className="w-4 h-4" />
+          </button>
+        </div>
+
+        <div className="px-6 pb-4 space-y-3">
+          {pending.changed ? (
+            <p className="text-sm text-secondary">
+              The server now presents a different host key. This can mean the connection is being intercepted.
+              Only clear the trusted key if it was rotated on purpose.
+            </p>
+          ) : (
+            <p className="text-sm text-secondary">
+              Atlas hasn't connected to this server before. Only trust the key if it matches the server's own
+              (<code>ssh-keygen -lf /etc/ssh/ssh_host_*_key.pub</code>).
+            </p>
+          )}
+          <div className="p-3 rounded-lg bg-white/5 font-mono text-xs text-primary break-all">
+            {pending.key_type} {pending.fingerprint}
+          </div>
+          {pending.changed && pending.trusted_fingerprint && (
+            <p className="text-xs text-muted break-all">Trusted until now: {pending.trusted_fingerprint}</p>
+          )}
+          {error && <p className="text-xs text-red-400">{error}</p>}
+        </div>
+
+        <div className="flex items-center justify-end gap-3 p-6 pt-4 border-t border-white/10">
+          {pending.changed ? (
+            <>
+              <button
+                onClick={() => setPending(null)}
+                disabled={busy}
+                className="px-4 py-2 rounded-lg font-medium text-secondary hover:bg-white/5 transition-colors"
+              >
+                Keep refusing
+              </button>
+              <button
+                onClick={clearTrusted}
+                disabled={busy}
+                className="px-4 py-2 rounded-lg font-medium bg-red-500 hover:bg-red-500/80 text-white transition-colors"
+              >
+                Clear trusted key
+              </button>
+            </>
+          ) : (
+            <>
+              <button
+                onClick={() => answer(false)}
+                disabled={busy}
+                className="px-4 py-2 rounded-lg font-medium text-secondary hover:bg-white/5 transition-colors"
+              >
+                Reject
+              </button>
+              <button
+                onClick={() => answer(true)}
+                disabled={busy}
+                className="px-4 py-2 rounded-lg font-medium bg-accent hover:bg-accent/80 text-white transition-colors"
+              >
+                Trust key
+              </button>
+            </>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
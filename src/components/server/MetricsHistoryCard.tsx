@@ -0,0 +1,226 @@
+// Server metrics history - CPU, memory and disk trends from the background sampler
+import { useEffect, useMemo, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import {
+  LineChart,
+  Line,
+  XAxis,
+  YAxis,
+  CartesianGrid,
+  Tooltip,
+  ResponsiveContainer,
+  Legend,
+} from 'recharts';
+import { LineChart as LineChartIcon, AlertTriangle } from 'lucide-react';
+import type {
+  MetricsRange,
+  MetricsSampleEvent,
+  MetricsUnreachableEvent,
+  ServerConfig,
+  ServerMetricsHistory,
+} from '../../types';
+
+const RANGE_OPTIONS: { value: MetricsRange; label: string }[] = [
+  { value: '1h', label: '1h' },
+  { value: '6h', label: '6h' },
+  { value: '24h', label: '24h' },
+];
+
+const INTERVAL_OPTIONS: { value: number; label: string }[] = [
+  { value: 0, label: 'Off' },
+  { value: 60, label: 'Every minute' },
+  { value: 300, label: 'Every 5 minutes' },
+  { value: 900, label: 'Every 15 minutes' },
+];
+
+const RANGE_SECONDS: Record<MetricsRange, number> = { '1h': 3600, '6h': 6 * 3600, '24h': 24 * 3600 };
+
+interface MetricsHistoryCardProps {
+  config: ServerConfig;
+  onConfigChange: (config: ServerConfig) => void;
+}
+
+function percent(used: number, total: number): number {
+  return total > 0 ? (used / total) * 100 : 0;
+}
+
+function formatClock(timestamp: number): string {
+  return new Date(timestamp * 1000).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+}
+
+export function MetricsHistoryCard({ config, onConfigChange }: MetricsHistoryCardProps) {
+  const [range, setRange] = useState<MetricsRange>('24h');
+  const [history, setHistory] = useState<ServerMetricsHistory | null>(null);
+  const [unreachable, setUnreachable] = useState<MetricsUnreachableEvent | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<ServerMetricsHistory>('get_server_metrics_history', { range })
+      .then((h) => {
+        setHistory(h);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)));
+  }, [range, config.host, config.port]);
+
+  // New samples are appended as they arrive; the next fetch averages them like the rest
+  useEffect(() => {
+    let cancelled = false;
+    const unlisteners: (() => void)[] = [];
+
+    (async () => {
+      const sampleUnlisten = await listen<MetricsSampleEvent>('server:metrics_sample', (event) => {
+        setUnreachable(null);
+        setHistory((prev) => {
+          if (!prev || prev.server !== event.payload.server) return prev;
+          const since = event.payload.sample.timestamp - RANGE_SECONDS[prev.range];
+          return {
+            ...prev,
+            samples: [...prev.samples.filter((s) => s.timestamp >= since), event.payload.sample],
+          };
+        });
+      });
+      const unreachableUnlisten = await listen<MetricsUnreachableEvent>('server:metrics_unreachable', (event) =>
+        setUnreachable(event.payload)
+      );
+
+      if (cancelled) {
+        sampleUnlisten();
+        unreachableUnlisten();
+      } else {
+        unlisteners.push(sampleUnlisten, unreachableUnlisten);
+      }
+    })();
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((unlisten) => unlisten());
+    };
+  }, []);
+
+  async function updateConfig(update: Partial<ServerConfig>) {
+    try {
+      const updated = await invoke<ServerConfig>('update_server_config', { config: update });
+      onConfigChange(updated);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  const chartData = useMemo(
+    () =>
+      (history?.samples ?? []).map((s) => ({
+        timestamp: s.timestamp,
+        cpu: s.cpu_percent,
+        memory: percent(s.memory_used_bytes, s.memory_total_bytes),
+        disk: percent(s.disk_used_bytes, s.disk_total_bytes),
+      })),
+    [history]
+  );
+
+  const interval = config.metrics_interval_seconds ?? 0;
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-3">
+        <div className="flex items-center gap-2">
+          <LineChartIcon size={16} className="text-accent-primary" />
+          <h3 className="text-sm font-medium text-text-secondary">Metrics History</h3>
+        </div>
+        <div className="flex gap-1">
+          {RANGE_OPTIONS.map((option) => (
+            <button
+              key={option.value}
+              onClick={() => setRange(option.value)}
+              className={`px-2 py-0.5 rounded text-xs transition-colors ${
+                range === option.value ? 'bg-accent-primary/20 text-accent-primary' : 'text-text-muted hover:bg-white/10'
+              }`}
+            >
+              {option.label}
+            </button>
+          ))}
+        </div>
+      </div>
+
+      <select
+        value={INTERVAL_OPTIONS.some((o) => o.value === interval) ? interval : ''}
+        onChange={(e) => updateConfig({ metrics_interval_seconds: Number(e.target.value) })}
+        className="input w-full text-sm mb-3"
+      >
+        {!INTERVAL_OPTIONS.some((o) => o.value === interval) && <option value="">Every {interval}s</option>}
+        {INTERVAL_OPTIONS.map((option) => (
+          <option key={option.value} value={option.value}>
+            {option.label === 'Off' ? 'Sampling off' : `Sample ${option.label.toLowerCase()}`}
+          </option>
+        ))}
+      </select>
+
+      {interval > 0 && (
+        <label className="flex items-center gap-2 text-xs text-text-muted mb-3">
+          <input
+            type="checkbox"
+            checked={config.metrics_keep_session_open ?? false}
+            onChange={(e) => updateConfig({ metrics_keep_session_open: e.target.checked })}
+          />
+          Keep the SSH session open between samples
+        </label>
+      )}
+
+      {unreachable && (
+        <div className="flex items-start gap-2 text-xs text-yellow-400 mb-3">
+          <AlertTriangle size={14} className="shrink-0 mt-0.5" />
+          <span>
+            Server unreachable, retrying in {Math.round(unreachable.retry_in_seconds / 60)} min: {unreachable.message}
+          </span>
+        </div>
+      )}
+      {error && <p className="text-xs text-red-400 mb-3">{error}</p>}
+
+      {chartData.length < 2 ? (
+        <p className="text-xs text-text-muted">
+          {interval ? 'Not enough samples yet.' : 'Turn on sampling to record CPU, memory and disk use.'}
+        </p>
+      ) : (
+        <div className="h-40">
+          <ResponsiveContainer width="100%" height="100%">
+            <LineChart data={chartData} margin={{ top: 5, right: 5, left: -20, bottom: 0 }}>
+              <CartesianGrid strokeDasharray="3 3" stroke="rgba(255,255,255,0.1)" />
+              <XAxis
+                dataKey="timestamp"
+                type="number"
+                domain={['dataMin', 'dataMax']}
+                tickFormatter={formatClock}
+                tick={{ fill: 'rgba(255,255,255,0.5)', fontSize: 10 }}
+                stroke="rgba(255,255,255,0.3)"
+              />
+              <YAxis
+                domain={[0, 100]}
+                tickFormatter={(value) => `${value}%`}
+                tick={{ fill: 'rgba(255,255,255,0.5)', fontSize: 10 }}
+                stroke="rgba(255,255,255,0.3)"
+              />
+              <Tooltip
+                labelFormatter={(value) => formatClock(Number(value))}
+                formatter={(value) => `${Number(value).toFixed(1)}%`}
+                contentStyle={{ background: 'rgba(20,20,30,0.9)', border: 'none', fontSize: 12 }}
+              />
+              <Legend wrapperStyle={{ fontSize: 11 }} />
+              <Line type="monotone" dataKey="cpu" name="CPU" stroke="#06b6d4" dot={false} isAnimationActive={false} />
+              <Line
+                type="monotone"
+                dataKey="memory"
+                name="Memory"
+                stroke="#a855f7"
+                dot={false}
+                isAnimationActive={false}
+              />
+              <Line type="monotone" dataKey="disk" name="Disk" stroke="#22c55e" dot={false} isAnimationActive={false} />
+            </LineChart>
+          </ResponsiveContainer>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,192 @@
+// SSH tunnels - forward local ports to services reachable from the server
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Network, Plus, Trash2, Loader2, AlertTriangle } from 'lucide-react';
+import type { SshTunnel, TunnelConnectionEvent } from '../../types';
+
+// How often traffic counters are refreshed while tunnels are open
+const REFRESH_INTERVAL_MS = 5000;
+
+function formatBytes(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+function connectionMessage(event: TunnelConnectionEvent): string | null {
+  switch (event.state) {
+    case 'lost':
+      return 'SSH session lost, reconnecting...';
+    case 'reconnecting':
+      return event.retry_in ? `Reconnecting (attempt ${event.attempt ?? 1}, retry in ${event.retry_in}s)` : 'Reconnecting...';
+    case 'failed':
+      return event.message ?? 'Tunnels could not be reopened';
+    default:
+      return null;
+  }
+}
+
+export function TunnelsCard() {
+  const [tunnels, setTunnels] = useState<SshTunnel[]>([]);
+  const [localPort, setLocalPort] = useState('');
+  const [remoteHost, setRemoteHost] = useState('');
+  const [remotePort, setRemotePort] = useState('');
+  const [creating, setCreating] = useState(false);
+  const [status, setStatus] = useState<TunnelConnectionEvent | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setTunnels(await invoke<SshTunnel[]>('list_ssh_tunnels'));
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    if (tunnels.length === 0) return;
+    const timer = setInterval(refresh, REFRESH_INTERVAL_MS);
+    return () => clearInterval(timer);
+  }, [tunnels.length, refresh]);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+
+    listen<TunnelConnectionEvent>('server:tunnel_connection', (event) => {
+      // A reconnect clears the notice; a failure stays until dismissed
+      setStatus(event.payload.state === 'reconnected' ? null : event.payload);
+      refresh();
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  async function handleCreate(e: React.FormEvent) {
+    e.preventDefault();
+    setCreating(true);
+    setError(null);
+    try {
+      await invoke<SshTunnel>('create_ssh_tunnel', {
+        localPort: Number(localPort),
+        remoteHost: remoteHost.trim() || null,
+        remotePort: Number(remotePort),
+      });
+      setLocalPort('');
+      setRemoteHost('');
+      setRemotePort('');
+      setStatus(null);
+      await refresh();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setCreating(false);
+    }
+  }
+
+  async function handleClose(id: string) {
+    try {
+      await invoke('close_ssh_tunnel', { id });
+      await refresh();
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  const notice = status && connectionMessage(status);
+
+  return (
+    <div className="card">
+      <div className="flex items-center gap-2 mb-3">
+        <Network size={16} className="text-accent-primary" />
+        <h3 className="text-sm font-medium text-text-secondary">SSH Tunnels</h3>
+      </div>
+
+      {notice && (
+        <div
+          className={`flex items-start gap-2 text-xs mb-3 ${
+            status?.state === 'failed' ? 'text-red-400' : 'text-yellow-400'
+          }`}
+        >
+          <AlertTriangle size={14} className="shrink-0 mt-0.5" />
+          <span className="flex-1">{notice}</span>
+          {status?.state === 'failed' && (
+            <button onClick={() => setStatus(null)} className="text-text-muted hover:text-text-primary">
+              Dismiss
+            </button>
+          )}
+        </div>
+      )}
+      {error && <p className="text-xs text-red-400 mb-3">{error}</p>}
+
+      {tunnels.length > 0 && (
+        <div className="space-y-2 mb-3">
+          {tunnels.map((tunnel) => (
+            <div key={tunnel.id} className="flex items-center justify-between gap-2 text-xs">
+              <div className="min-w-0">
+                <div className="text-text-primary font-mono truncate">
+                  localhost:{tunnel.local_port} → {tunnel.remote_host}:{tunnel.remote_port}
+                </div>
+                <div className="text-text-muted">
+                  {tunnel.status} · {tunnel.active_connections} active · ↑ {formatBytes(tunnel.bytes_sent)} ↓{' '}
+                  {formatBytes(tunnel.bytes_received)}
+                </div>
+              </div>
+              <button
+                onClick={() => handleClose(tunnel.id)}
+                className="p-1 rounded text-text-muted hover:text-red-400 hover:bg-white/10"
+                title="Close tunnel"
+              >
+                <Trash2 size={14} />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <form onSubmit={handleCreate} className="flex items-center gap-2">
+        <input
+          type="number"
+          value={localPort}
+          onChange={(e) => setLocalPort(e.target.value)}
+          placeholder="Local port"
+          className="input w-24 text-sm"
+          min={1024}
+          max={65535}
+          required
+        />
+        <input
+          type="text"
+          value={remoteHost}
+          onChange={(e) => setRemoteHost(e.target.value)}
+          placeholder="localhost"
+          className="input flex-1 min-w-0 text-sm"
+        />
+        <input
+          type="number"
+          value={remotePort}
+          onChange={(e) => setRemotePort(e.target.value)}
+          placeholder="Remote port"
+          className="input w-24 text-sm"
+          min={1}
+          max={65535}
+          required
+        />
+        <button type="submit" disabled={creating} className="btn btn-primary p-2" title="Open tunnel">
+          {creating ? <Loader2 size={14} className="animate-spin" /> : <Plus size={14} />}
+        </button>
+      </form>
+    </div>
+  );
+}
@@ -43,8 +43,10 @@ function groupProcessesByName(processes: ProcessInfo[]): GroupedProcessInfo[] {
     for (const p of procs) {
       totalCpu += p.cpu_usage;
       totalMemory += p.memory_mb;
-      if (p.gpu_usage !== null) {
-        totalGpu = (totalGpu ?? 0) + p.gpu_usage;
+      // Engine counters cover every GPU vendor; NVML is the fallback
+      const gpu = p.gpu_percent ?? p.gpu_usage;
+      if (gpu !== null) {
+        totalGpu = (totalGpu ?? 0) + gpu;
       }
     }
 
@@ -1,5 +1,6 @@
 // Export all hooks
 export { useTauriEvent, useTauriEvents } from './useTauriEvent';
+export { useDataReset } from './useDataReset';
 export { usePerformanceData } from './usePerformanceData';
 export type { UsePerformanceDataReturn } from './usePerformanceData';
 export { useGamingData } from './useGamingData';
@@ -0,0 +1,17 @@
+// Hook for reloading data after the backend resets it
+import { useTauriEvent } from './useTauriEvent';
+import type { DataResetEvent } from '../types';
+
+/**
+ * Calls `onReset` when `reset_feature_data` (or `reset_settings`, for 'settings') resets the feature.
+ *
+ * @param feature - The feature whose data the caller shows
+ * @param onReset - Function that reloads it
+ */
+export function useDataReset(feature: DataResetEvent['feature'], onReset: () => void): void {
+  useTauriEvent<DataResetEvent>('data:reset', (payload) => {
+    if (payload.feature === feature) {
+      onReset();
+    }
+  });
+}
@@ -1,6 +1,7 @@
 import { useState, useCallback, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import { useDataReset } from './useDataReset';
 import type {
   LocalUserData,
   FriendWithDetails,
@@ -418,6 +419,11 @@ export function useFriends(): UseFriendsReturn {
     };
   }, []);
 
+  useDataReset('friends', () => {
+    loadLocalUser();
+    loadFriends();
+  });
+
   return {
     localUser,
     friends,
@@ -1,7 +1,15 @@
 import { useState, useCallback, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { GameLibrary, DetectedGame, AddGameRequest, LibraryGame } from '../types';
+import { useDataReset } from './useDataReset';
+import type {
+  GameLibrary,
+  DetectedGame,
+  AddGameRequest,
+  LibraryGame,
+  GameScanProgress,
+  GameScanComplete,
+} from '../types';
 
 const CACHE_TTL_MS = 30000;
 
@@ -9,21 +17,29 @@ export interface UseGameLauncherReturn {
   library: GameLibrary;
   isLoading: boolean;
   isScanning: boolean;
+  scanProgress: GameScanProgress | null;
   error: string | null;
+  /** Options the last launch couldn't apply, from launch_game */
+  launchWarnings: string[];
+  dismissLaunchWarnings: () => void;
   loadLibrary: () => Promise<void>;
   scanForGames: () => Promise<DetectedGame[]>;
+  cancelScan: () => Promise<void>;
   addDetectedGames: (games: DetectedGame[]) => Promise<void>;
   addManualGame: (request: AddGameRequest) => Promise<void>;
   removeGame: (gameId: string) => Promise<void>;
   launchGame: (gameId: string) => Promise<void>;
+  setHiddenFromPresence: (gameId: string, hidden: boolean) => Promise<void>;
   getGameById: (gameId: string) => LibraryGame | undefined;
 }
 
 export function useGameLauncher(): UseGameLauncherReturn {
-  const [library, setLibrary] = useState<GameLibrary>({ games: [] });
+  const [library, setLibrary] = useState<GameLibrary>({ games: [], collections: [] });
   const [isLoading, setIsLoading] = useState(false);
   const [isScanning, setIsScanning] = useState(false);
+  const [scanProgress, setScanProgress] = useState<GameScanProgress | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [launchWarnings, setLaunchWarnings] = useState<string[]>([]);
 
   const lastFetchRef = useRef<number>(0);
 
@@ -50,17 +66,40 @@ export function useGameLauncher(): UseGameLauncherReturn {
     lastFetchRef.current = 0;
   }, []);
 
+  // The scan runs in the background; resolves with its result once it completes (empty if cancelled)
   const scanForGames = useCallback(async (): Promise<DetectedGame[]> => {
     setIsScanning(true);
     setError(null);
+
+    let resolveScan: (games: DetectedGame[]) => void = () => {};
+    const scanDone = new Promise<DetectedGame[]>(resolve => {
+      resolveScan = resolve;
+    });
+    // Only one scan runs at a time, so any completion is ours (scan_for_games may join a running one)
+    const unlisteners = await Promise.all([
+      listen<GameScanProgress>('launcher:scan_progress', e => setScanProgress(e.payload)),
+      listen<GameScanComplete>('launcher:scan_complete', e => resolveScan(e.payload.games)),
+      listen<{ scan_id: string }>('launcher:scan_cancelled', () => resolveScan([])),
+    ]);
+
     try {
-      const games = await invoke<DetectedGame[]>('scan_for_games');
-      return games;
+      await invoke<string>('scan_for_games');
+      return await scanDone;
     } catch (e) {
       setError(e instanceof Error ? e.message : String(e));
       return [];
     } finally {
+      unlisteners.forEach(fn => fn());
       setIsScanning(false);
+      setScanProgress(null);
+    }
+  }, []);
+
+  const cancelScan = useCallback(async () => {
+    try {
+      await invoke('cancel_game_scan');
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
     }
   }, []);
 
@@ -99,8 +138,10 @@ export function useGameLauncher(): UseGameLauncherReturn {
 
   const launchGame = useCallback(async (gameId: string) => {
     setError(null);
+    setLaunchWarnings([]);
     try {
-      await invoke('launch_game', { gameId });
+      const warnings = await invoke<string[]>('launch_game', { gameId });
+      setLaunchWarnings(warnings);
       invalidateCache();
       await loadLibrary(true);
     } catch (e) {
@@ -108,6 +149,21 @@ export function useGameLauncher(): UseGameLauncherReturn {
     }
   }, [loadLibrary, invalidateCache]);
 
+  const setHiddenFromPresence = useCallback(async (gameId: string, hidden: boolean) => {
+    setError(null);
+    try {
+      const lib = await invoke<GameLibrary>('set_game_hidden_from_presence', { gameId, hidden });
+      setLibrary(lib);
+      invalidateCache();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  }, [invalidateCache]);
+
+  const dismissLaunchWarnings = useCallback(() => {
+    setLaunchWarnings([]);
+  }, []);
+
   const getGameById = useCallback((gameId: string): LibraryGame | undefined => {
     return library.games.find(g => g.id === gameId);
   }, [library]);
@@ -134,17 +190,24 @@ export function useGameLauncher(): UseGameLauncherReturn {
     };
   }, [loadLibrary, invalidateCache]);
 
+  useDataReset('game_library', () => loadLibrary(true));
+
   return {
     library,
     isLoading,
     isScanning,
+    scanProgress,
     error,
+    launchWarnings,
+    dismissLaunchWarnings,
     loadLibrary,
     scanForGames,
+    cancelScan,
     addDetectedGames,
     addManualGame,
     removeGame,
     launchGame,
+    setHiddenFromPresence,
     getGameById,
   };
 }
@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import { useDataReset } from './useDataReset';
 import {
   GameWhitelist,
   GameEntry,
@@ -23,6 +24,7 @@ interface UseGamingDataReturn {
   addGame: (game: GameEntry) => Promise<void>;
   removeGame: (processName: string) => Promise<void>;
   toggleGame: (processName: string, enabled: boolean) => Promise<void>;
+  setGameHiddenFromPresence: (processName: string, hidden: boolean) => Promise<void>;
 
   // Detection
   isDetecting: boolean;
@@ -111,6 +113,16 @@ export function useGamingData(): UseGamingDataReturn {
     }
   }, [loadWhitelist]);
 
+  const setGameHiddenFromPresence = useCallback(async (processName: string, hidden: boolean) => {
+    try {
+      await invoke('set_whitelist_game_hidden_from_presence', { processName, hidden });
+      await loadWhitelist();
+    } catch (e) {
+      setError(`Failed to update game: ${e}`);
+      throw e;
+    }
+  }, [loadWhitelist]);
+
   const checkDetectionStatus = useCallback(async () => {
     try {
       const running = await invoke<boolean>('is_gaming_detection_running');
@@ -302,6 +314,8 @@ export function useGamingData(): UseGamingDataReturn {
     checkActiveSession();
   }, [loadWhitelist, loadSessions, checkDetectionStatus, checkActiveSession]);
 
+  useDataReset('gaming_sessions', loadSessions);
+
   return {
     // Whitelist
     whitelist,
@@ -310,6 +324,7 @@ export function useGamingData(): UseGamingDataReturn {
     addGame,
     removeGame,
     toggleGame,
+    setGameHiddenFromPresence,
 
     // Detection
     isDetecting,
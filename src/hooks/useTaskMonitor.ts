@@ -1,9 +1,14 @@
 import { useState, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { useDataReset } from './useDataReset';
 import type {
   ProcessInfo,
   GamingProfile,
+  KillRecommendation,
   KillResult,
+  ProcessListPage,
+  ProfileImportResult,
+  ProfilePreview,
   SystemSummary,
 } from '../types/taskMonitor';
 
@@ -23,8 +28,11 @@ export interface UseTaskMonitorReturn {
   saveProfile: (profile: GamingProfile) => Promise<void>;
   deleteProfile: (id: string) => Promise<void>;
   setDefaultProfile: (id: string) => Promise<void>;
-  executeProfile: (id: string) => Promise<KillResult>;
-  getKillRecommendations: (minMemoryMb: number) => Promise<ProcessInfo[]>;
+  executeProfile: (id: string, onlyPids?: number[]) => Promise<KillResult>;
+  previewProfile: (profileId: string) => Promise<ProfilePreview>;
+  exportProfiles: (path: string, ids?: string[]) => Promise<number>;
+  importProfiles: (path: string, overwrite: boolean) => Promise<ProfileImportResult>;
+  getKillRecommendations: (minMemoryMb: number) => Promise<KillRecommendation[]>;
 }
 
 export function useTaskMonitor(): UseTaskMonitorReturn {
@@ -39,10 +47,10 @@ export function useTaskMonitor(): UseTaskMonitorReturn {
       setIsLoading(true);
       setError(null);
       const [processList, summary] = await Promise.all([
-        invoke<ProcessInfo[]>('get_process_list'),
+        invoke<ProcessListPage>('get_process_list'),
         invoke<SystemSummary>('get_system_summary'),
       ]);
-      setProcesses(processList);
+      setProcesses(processList.processes);
       setSystemSummary(summary);
     } catch (e) {
       setError(`Failed to load processes: ${e}`);
@@ -63,9 +71,9 @@ export function useTaskMonitor(): UseTaskMonitorReturn {
   }, []);
 
   const killProcess = useCallback(
-    async (pid: number) => {
+    async (pid: number, includeChildren = false, force = false) => {
       try {
-        await invoke('kill_single_process', { pid });
+        await invoke('kill_single_process', { pid, includeChildren, force });
         await refreshProcesses();
       } catch (e) {
         setError(`Failed to kill process: ${e}`);
@@ -143,9 +151,9 @@ export function useTaskMonitor(): UseTaskMonitorReturn {
   );
 
   const executeProfile = useCallback(
-    async (id: string): Promise<KillResult> => {
+    async (id: string, onlyPids?: number[]): Promise<KillResult> => {
       try {
-        const result = await invoke<KillResult>('execute_gaming_profile', { id });
+        const result = await invoke<KillResult>('execute_gaming_profile', { id, onlyPids });
         await refreshProcesses();
         return result;
       } catch (e) {
@@ -156,10 +164,42 @@ export function useTaskMonitor(): UseTaskMonitorReturn {
     [refreshProcesses]
   );
 
+  const previewProfile = useCallback(async (profileId: string): Promise<ProfilePreview> => {
+    try {
+      return await invoke<ProfilePreview>('preview_gaming_profile', { profileId });
+    } catch (e) {
+      setError(`Failed to preview profile: ${e}`);
+      throw e;
+    }
+  }, []);
+
+  const exportProfiles = useCallback(async (path: string, ids?: string[]): Promise<number> => {
+    try {
+      return await invoke<number>('export_gaming_profiles', { path, ids });
+    } catch (e) {
+      setError(`Failed to export profiles: ${e}`);
+      throw e;
+    }
+  }, []);
+
+  const importProfiles = useCallback(
+    async (path: string, overwrite: boolean): Promise<ProfileImportResult> => {
+      try {
+        const result = await invoke<ProfileImportResult>('import_gaming_profiles', { path, overwrite });
+        await refreshProfiles();
+        return result;
+      } catch (e) {
+        setError(`Failed to import profiles: ${e}`);
+        throw e;
+      }
+    },
+    [refreshProfiles]
+  );
+
   const getKillRecommendations = useCallback(
-    async (minMemoryMb: number): Promise<ProcessInfo[]> => {
+    async (minMemoryMb: number): Promise<KillRecommendation[]> => {
       try {
-        return await invoke<ProcessInfo[]>('get_kill_recommendations', { minMemoryMb });
+        return await invoke<KillRecommendation[]>('get_kill_recommendations', { minMemoryMb });
       } catch (e) {
         setError(`Failed to get recommendations: ${e}`);
         throw e;
@@ -168,6 +208,8 @@ export function useTaskMonitor(): UseTaskMonitorReturn {
     []
   );
 
+  useDataReset('task_monitor_profiles', refreshProfiles);
+
   return {
     processes,
     profiles,
@@ -183,6 +225,9 @@ export function useTaskMonitor(): UseTaskMonitorReturn {
     deleteProfile,
     setDefaultProfile,
     executeProfile,
+    previewProfile,
+    exportProfiles,
+    importProfiles,
     getKillRecommendations,
   };
 }
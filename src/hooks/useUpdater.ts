@@ -2,17 +2,48 @@ import { useState, useCallback, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import type {
+  ChangelogEntry,
   UpdateState,
   UpdateInfo,
+  UpdateDownloadEvent,
+  UpdateInstallError,
   UpdateProgressEvent,
 } from '../types/updater';
 
+// Error `download_update` rejects with after `cancel_update_download`
+const DOWNLOAD_CANCELLED = 'Update download cancelled';
+
+function describeInstallError(err: unknown): string {
+  if (typeof err === 'object' && err !== null && 'kind' in err) {
+    const error = err as UpdateInstallError;
+    switch (error.kind) {
+      case 'not_downloaded':
+        return 'No downloaded update available. Please download first.';
+      case 'length_mismatch':
+        return `The downloaded update is incomplete (${error.actual} of ${error.expected ?? '?'} bytes). Please download it again.`;
+      case 'signature_invalid':
+        return `The downloaded update failed verification: ${error.message}`;
+      case 'install_failed':
+        return `Failed to install update: ${error.message}`;
+    }
+  }
+  return err instanceof Error ? err.message : String(err);
+}
+
 export interface UseUpdaterReturn {
   state: UpdateState;
-  checkForUpdate: () => Promise<void>;
-  downloadUpdate: () => Promise<void>;
+  /** `manual` shows the update even when it was skipped or snoozed */
+  checkForUpdate: (manual?: boolean) => Promise<void>;
+  /** `allowDowngrade` accepts a release older than the installed one, e.g. stable after a beta;
+   * defaults to whether the offered update is a downgrade */
+  downloadUpdate: (allowDowngrade?: boolean) => Promise<void>;
+  cancelDownload: () => Promise<void>;
   installUpdate: () => Promise<void>;
   dismissUpdate: () => void;
+  skipUpdate: () => Promise<void>;
+  snoozeUpdate: (hours: number) => Promise<void>;
+  /** Release notes of `version` (the pending update when omitted) and the few releases before it */
+  getChangelog: (version?: string) => Promise<ChangelogEntry[]>;
   isUpdateAvailable: boolean;
   isDownloading: boolean;
   isReady: boolean;
@@ -31,7 +62,11 @@ export function useUpdater(): UseUpdaterReturn {
 
   useEffect(() => {
     const setupListeners = async () => {
-      const unlistenProgress = await listen<UpdateProgressEvent>('update:progress', (event) => {
+      const unlistenProgress = await listen<UpdateProgressEvent>('updater:download_progress', (event) => {
+        // Background downloads stay quiet until they are ready
+        if (event.payload.background) {
+          return;
+        }
         setState((prev) => ({
           ...prev,
           status: 'downloading',
@@ -43,7 +78,10 @@ export function useUpdater(): UseUpdaterReturn {
         }));
       });
 
-      const unlistenDownloaded = await listen('update:downloaded', () => {
+      const unlistenDownloaded = await listen<UpdateDownloadEvent>('updater:download_finished', (event) => {
+        if (event.payload.background) {
+          return;
+        }
         setState((prev) => ({
           ...prev,
           status: 'downloaded',
@@ -51,7 +89,10 @@ export function useUpdater(): UseUpdaterReturn {
         }));
       });
 
-      const unlistenDownloading = await listen('update:downloading', () => {
+      const unlistenDownloading = await listen<UpdateDownloadEvent>('updater:download_started', (event) => {
+        if (event.payload.background) {
+          return;
+        }
         setState((prev) => ({
           ...prev,
           status: 'downloading',
@@ -59,10 +100,20 @@ export function useUpdater(): UseUpdaterReturn {
         }));
       });
 
+      const unlistenReady = await listen<UpdateInfo>('update:ready', (event) => {
+        setState({
+          status: 'downloaded',
+          info: event.payload,
+          progress: null,
+          error: null,
+        });
+      });
+
       unlistenersRef.current = [
         unlistenProgress,
         unlistenDownloaded,
         unlistenDownloading,
+        unlistenReady,
       ];
     };
 
@@ -73,13 +124,16 @@ export function useUpdater(): UseUpdaterReturn {
     };
   }, []);
 
-  const checkForUpdate = useCallback(async () => {
+  const checkForUpdate = useCallback(async (manual?: boolean) => {
     setState((prev) => ({ ...prev, status: 'checking', error: null }));
 
     try {
       const result = await invoke<UpdateInfo | null>('check_for_update');
 
-      if (result) {
+      if (result && !manual && (result.skipped || result.snoozed_until)) {
+        // Deferred: keep the info so it can still be offered, but don't prompt
+        setState({ ...initialState, info: result });
+      } else if (result) {
         setState({
           status: 'available',
           info: result,
@@ -99,7 +153,7 @@ export function useUpdater(): UseUpdaterReturn {
     }
   }, []);
 
-  const downloadUpdate = useCallback(async () => {
+  const downloadUpdate = useCallback(async (allowDowngrade?: boolean) => {
     if (state.status !== 'available' && state.status !== 'error') {
       return;
     }
@@ -112,15 +166,35 @@ export function useUpdater(): UseUpdaterReturn {
     }));
 
     try {
-      await invoke('download_update');
+      await invoke('download_update', { allowDowngrade: allowDowngrade ?? state.info?.downgrade ?? null });
     } catch (err) {
+      if (String(err) === DOWNLOAD_CANCELLED) {
+        return;
+      }
       setState((prev) => ({
         ...prev,
         status: 'error',
         error: err instanceof Error ? err.message : String(err),
       }));
     }
-  }, [state.status]);
+  }, [state.status, state.info]);
+
+  const cancelDownload = useCallback(async () => {
+    try {
+      await invoke('cancel_update_download');
+      setState((prev) => ({
+        ...prev,
+        status: prev.info ? 'available' : 'idle',
+        progress: null,
+      }));
+    } catch (err) {
+      setState((prev) => ({
+        ...prev,
+        status: 'error',
+        error: err instanceof Error ? err.message : String(err),
+      }));
+    }
+  }, []);
 
   const installUpdate = useCallback(async () => {
     if (state.status !== 'downloaded') {
@@ -139,7 +213,7 @@ export function useUpdater(): UseUpdaterReturn {
       setState((prev) => ({
         ...prev,
         status: 'error',
-        error: err instanceof Error ? err.message : String(err),
+        error: describeInstallError(err),
       }));
     }
   }, [state.status]);
@@ -151,6 +225,33 @@ export function useUpdater(): UseUpdaterReturn {
     }));
   }, []);
 
+  const skipUpdate = useCallback(async () => {
+    if (!state.info) {
+      return;
+    }
+    try {
+      await invoke('skip_update_version', { version: state.info.version });
+      setState((prev) => ({ ...prev, status: 'idle' }));
+    } catch (err) {
+      console.error('Failed to skip update:', err);
+    }
+  }, [state.info]);
+
+  const snoozeUpdate = useCallback(async (hours: number) => {
+    try {
+      await invoke<string>('snooze_update', { hours });
+      setState((prev) => ({ ...prev, status: 'idle' }));
+    } catch (err) {
+      console.error('Failed to snooze update:', err);
+    }
+  }, []);
+
+  const getChangelog = useCallback(async (version?: string) => {
+    return invoke<ChangelogEntry[]>('get_update_changelog', {
+      version: version ?? state.info?.version ?? null,
+    });
+  }, [state.info]);
+
   const isUpdateAvailable = state.status === 'available';
   const isDownloading = state.status === 'downloading';
   const isReady = state.status === 'downloaded';
@@ -159,8 +260,12 @@ export function useUpdater(): UseUpdaterReturn {
     state,
     checkForUpdate,
     downloadUpdate,
+    cancelDownload,
     installUpdate,
     dismissUpdate,
+    skipUpdate,
+    snoozeUpdate,
+    getChangelog,
     isUpdateAvailable,
     isDownloading,
     isReady,
@@ -0,0 +1,60 @@
+// Discord Rich Presence types
+
+// A placeholder usable in the presence templates, from get_presence_template_placeholders
+export interface PresencePlaceholder {
+  placeholder: string;
+  description: string;
+  example: string;
+}
+
+// A link button under the presence; Discord shows at most two
+export interface PresenceButton {
+  label: string;
+  url: string;
+}
+
+// Activity sent to Discord, from preview_discord_presence
+export interface PresenceActivity {
+  // Discord application the activity is shown under
+  application_id: string;
+  details: string | null;
+  state: string | null;
+  start_timestamp: number | null;
+  large_image: string;
+  large_text: string;
+  buttons: PresenceButton[];
+}
+
+// Discord application a game's presence is shown under, instead of Atlas's
+export interface DiscordGameApp {
+  // Application (client) ID from the Discord developer portal
+  app_id?: string | null;
+  // Asset key of that application, or an https image URL
+  large_image?: string | null;
+}
+
+// Discord connection health, from is_discord_connected and the discord:connection_changed event
+export interface DiscordConnectionStatus {
+  connected: boolean;
+  // Presence is turned on, so a lost connection is retried
+  enabled: boolean;
+  last_error: string | null;
+  // Seconds until the next reconnect attempt; null when none is planned
+  retry_in_seconds: number | null;
+}
+
+// What Discord shows while a game hidden from presence runs: "Playing a game", or the idle presence
+export type PresenceHiddenGameDisplay = 'generic' | 'idle';
+
+// Where the shown presence comes from
+export type PresenceMode = 'custom' | 'gaming' | 'idle';
+
+// The presence Atlas shows, from get_current_presence, set_custom_presence and clear_custom_presence
+export interface CurrentPresence {
+  mode: PresenceMode;
+  activity: PresenceActivity;
+  // Unix seconds the custom presence ends at
+  custom_until: number | null;
+  // False while Discord isn't connected, so nothing is shown
+  shown: boolean;
+}
@@ -1,6 +1,6 @@
 // Game Launcher types
 
-export type GameSource = 'steam' | 'hoyo_play' | 'riot' | 'manual';
+export type GameSource = 'steam' | 'hoyo_play' | 'riot' | 'epic' | 'battle_net' | 'xbox' | 'ubisoft' | 'ea' | 'itch' | 'steam_shortcut' | 'manual';
 
 export interface DetectedGame {
   name: string;
@@ -20,15 +20,133 @@ export interface LibraryGame {
   source: GameSource;
   app_id: string | null;
   icon_path: string | null;
+  /** Set by set_game_icon - scans never replace a user-chosen icon */
+  icon_user_set: boolean;
   process_name: string;
   added_at: string;
   last_played: string | null;
   total_playtime_seconds: number;
+  /** Number of play sessions counted into total_playtime_seconds */
+  session_count: number;
   launch_args: string | null;  // Arguments to pass when launching (e.g., for Riot Client)
+  /** User-set arguments, appended after launch_args (ignored by store-brokered launches) */
+  custom_launch_args: string | null;
+  run_as_admin: boolean;
+  /** Steam games: launch through steam://rungameid (null = the steam_launch_via_protocol setting) */
+  launch_via_steam: boolean | null;
+  working_dir: string | null;
+  /** Environment variables for the game's process (direct, non-elevated launches only) */
+  env_vars: Record<string, string>;
+  /** Favorites are pinned to the top of every sort order */
+  favorite: boolean;
+  /** Position in the custom order (null = after the ordered games) */
+  sort_index: number | null;
+  /** SteamGridDB art downloaded by fetch_game_artwork */
+  artwork: GameArtwork;
+  pre_launch_command: GameHookCommand | null;
+  post_exit_command: GameHookCommand | null;
+  /** False once validate_game_library finds the game's files gone */
+  installed: boolean;
+  /** Ids of the user collections the game is in */
+  collection_ids: string[];
+  /** Keep the game's name out of the Discord and friends presence */
+  hide_from_presence: boolean;
+}
+
+export interface GameHookCommand {
+  command: string;
+  working_dir: string | null;
+  /** Seconds to wait (default 10); the command is left running past this, not killed */
+  timeout_seconds: number | null;
+  /** Pre-launch only: don't launch the game if the command fails */
+  required: boolean;
+}
+
+/** Payload of `launcher:hook_failed` */
+export interface HookFailedEvent {
+  game_id: string;
+  hook: 'pre_launch' | 'post_exit';
+  error: string;
+}
+
+/** Error launch_game returns when the UAC prompt of a run-as-admin game is declined */
+export const UAC_CANCELLED_ERROR = 'Launch cancelled: administrator permission was not granted';
+
+/** Prefix of the error launch_game returns for a game whose files are gone */
+export const GAME_UNINSTALLED_ERROR = 'Game appears uninstalled';
+
+/** Prefix of the error open_game_folder / reveal_game_executable return when the files are gone */
+export const GAME_PATH_MISSING_ERROR = 'Game files not found';
+
+export type ShortcutLocation = 'desktop' | 'start_menu';
+
+export type ArtworkKind = 'icon' | 'grid' | 'hero' | 'logo';
+
+export interface GameArtwork {
+  grid: string | null;
+  hero: string | null;
+  logo: string | null;
+}
+
+export type LibrarySortBy = 'name' | 'last_played' | 'playtime' | 'custom';
+
+export interface GamePlaytime {
+  game_id: string;
+  name: string;
+  total_seconds: number;
+  session_count: number;
+  last_played: string | null;
+  average_session_seconds: number;
+}
+
+export interface RecentlyPlayedGame extends LibraryGame {
+  last_launched_at: string;
+  /** Set once the game's process has exited */
+  last_exited_at: string | null;
+}
+
+export interface RunningGame {
+  game_id: string;
+  pid: number;
+  process_name: string;
+  /** Unix seconds */
+  started_at: number;
+}
+
+/** Payload of `launcher:scan_progress` */
+export interface GameScanProgress {
+  scan_id: string;
+  /** Detector or drive being scanned, e.g. "Steam" or "HoYoPlay (D:\)" */
+  phase: string;
+  games_found: number;
+}
+
+/** Payload of `launcher:scan_complete` - the games not yet in the library */
+export interface GameScanComplete {
+  scan_id: string;
+  games: DetectedGame[];
+}
+
+/** A detected game hidden from scans */
+export interface IgnoredGame {
+  /** The detected game's app_id, or its executable path when it has none */
+  key: string;
+  name: string | null;
+  ignored_at: string;
+}
+
+/** Built-in collection of recently added games, usable as get_game_library's collection_id */
+export const RECENTLY_ADDED_COLLECTION_ID = 'recently_added';
+
+export interface GameCollection {
+  id: string;
+  name: string;
+  created_at: string;
 }
 
 export interface GameLibrary {
   games: LibraryGame[];
+  collections: GameCollection[];
 }
 
 export interface AddGameRequest {
@@ -59,6 +177,20 @@ export function getSourceDisplayName(source: GameSource): string {
       return 'HoYoPlay';
     case 'riot':
       return 'Riot';
+    case 'epic':
+      return 'Epic Games';
+    case 'battle_net':
+      return 'Battle.net';
+    case 'xbox':
+      return 'Xbox';
+    case 'ubisoft':
+      return 'Ubisoft Connect';
+    case 'ea':
+      return 'EA app';
+    case 'itch':
+      return 'itch.io';
+    case 'steam_shortcut':
+      return 'Steam (non-Steam game)';
     case 'manual':
       return 'Manual';
     default:
@@ -1,4 +1,6 @@
 // Gaming Performance Analyzer types
+import type { CoreKind } from './performance';
+import type { DiscordGameApp } from './discord';
 
 export interface GameWhitelist {
   games: GameEntry[];
@@ -9,6 +11,12 @@ export interface GameEntry {
   process_name: string;
   icon?: string;
   enabled: boolean;
+  /** CPU cores to pin the game to when detected (bit N = logical core N) */
+  affinity_mask?: number | null;
+  /** Discord application and art for the presence; Atlas's when unset */
+  discord_app?: DiscordGameApp | null;
+  /** Keep the game's name out of the Discord and friends presence */
+  hide_from_presence?: boolean;
 }
 
 export interface GamingSession {
@@ -26,6 +34,8 @@ export type SessionStatus = 'active' | 'completed' | 'cancelled';
 export interface TopCoreInfo {
   core_index: number;
   usage_percent: number;
+  core_kind: CoreKind | null;
+  temperature_celsius: number | null;
 }
 
 export interface MetricsSnapshot {
@@ -38,6 +48,14 @@ export interface MetricsSnapshot {
   vram_percent: number | null;
   cpu_temp: number | null;
   gpu_temp: number | null;
+  gpu_power_watts: number | null;
+  gpu_power_limit_watts: number | null;
+  gpu_fan_percent: number | null;
+  cpu_clock_mhz: number | null;
+  gpu_core_clock_mhz: number | null;
+  gpu_memory_clock_mhz: number | null;
+  ram_available_mb: number | null;
+  commit_percent: number | null;
 }
 
 export interface BottleneckEvent {
@@ -62,11 +80,16 @@ export interface SessionSummary {
   cpu: MetricStats;
   top_core_1: MetricStats | null;
   top_core_2: MetricStats | null;
+  top_core: TopCoreInfo | null;
   gpu: MetricStats | null;
   ram: MetricStats;
   vram: MetricStats | null;
   cpu_temp: MetricStats | null;
   gpu_temp: MetricStats | null;
+  gpu_power: MetricStats | null;
+  cpu_clock: MetricStats | null;
+  gpu_core_clock: MetricStats | null;
+  gpu_memory_clock: MetricStats | null;
   total_bottleneck_seconds: number;
   dominant_bottleneck: BottleneckType;
   bottleneck_breakdown: BottleneckBreakdown[];
@@ -114,6 +137,7 @@ export interface BottleneckThresholds {
   gpu_low: number;
   ram_high: number;
   ram_available_min_mb: number;
+  commit_high: number;
   vram_high: number;
   cpu_thermal_limit: number;
   gpu_thermal_limit: number;
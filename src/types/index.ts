@@ -12,3 +12,4 @@ export * from './gameLauncher';
 export * from './playlistUploader';
 export * from './gacha';
 export * from './friends';
+export * from './discord';
@@ -2,6 +2,9 @@
 
 export type MLJobStatus = 'pending' | 'processing' | 'completed' | 'failed' | 'cancelled';
 
+// Queued jobs start highest priority first, then in submission order
+export type MLJobPriority = 'low' | 'normal' | 'high';
+
 export interface OutputFile {
   stem: string; // e.g., "vocals", "drums", "bass", "other"
   path: string;
@@ -13,18 +16,23 @@ export interface MLJob {
   model: string; // e.g., "htdemucs_ft"
   output_dir: string | null;
   status: MLJobStatus;
+  priority: MLJobPriority;
   progress: number; // 0-100
   stage: string | null; // e.g., "Processing segment 5/10"
   output_files: OutputFile[] | null;
   error: string | null;
   created_at: string;
+  started_at: string | null;
   completed_at: string | null;
+  queue_position: number | null; // 1-based, while pending
+  estimated_start: string | null; // null until a job has completed to estimate from
 }
 
 export interface SubmitMLJobParams {
   input_file: string;
   model: string;
   output_dir: string | null; // null = use default
+  priority?: MLJobPriority; // defaults to 'normal'
 }
 
 export interface SubmitMLJobResult {
@@ -35,6 +43,11 @@ export interface CancelMLJobParams {
   job_id: string;
 }
 
+export interface SetMLJobPriorityParams {
+  job_id: string;
+  priority: MLJobPriority;
+}
+
 export interface DeleteMLJobParams {
   job_id: string;
   delete_output: boolean;
@@ -3,8 +3,16 @@
 /** Container for all system performance metrics */
 export interface SystemMetrics {
   cpu: CpuMetrics;
+  /** Primary GPU (the pinned adapter, otherwise the busiest one) */
   gpu: GpuMetrics | null;
+  /** Every detected GPU, ordered by index */
+  gpus: GpuMetrics[];
   ram: RamMetrics;
+  network: NetworkMetrics;
+  disks: DiskMetrics[];
+  power: PowerStatus;
+  /** Heaviest processes by CPU and memory (only filled on request) */
+  top_processes: TopProcesses | null;
   timestamp: number;
 }
 
@@ -16,18 +24,36 @@ export interface CpuMetrics {
   per_core_usage: number[];
   /** CPU temperature in Celsius (if available) */
   temperature_celsius: number | null;
+  /** Per-core temperatures in Celsius, indexed like per_core_usage */
+  per_core_temperature: (number | null)[];
+  /** Core kind (P/E) per logical core - null on non-hybrid CPUs */
+  core_kinds: CoreKind[] | null;
   /** Current CPU frequency in MHz (if available) */
   frequency_mhz: number | null;
+  /** Average frequency across all cores in MHz (null when it can't be read) */
+  average_frequency_mhz: number | null;
+  /** Highest core frequency in MHz */
+  max_frequency_mhz: number | null;
   /** Number of CPU cores */
   core_count: number;
   /** CPU model name */
   name: string;
 }
 
-/** GPU performance metrics (NVIDIA only for now) */
+/** Core type on hybrid CPUs */
+export type CoreKind = 'performance' | 'efficiency';
+
+/** Backend that produced a set of GPU metrics */
+export type GpuProviderKind = 'nvml' | 'windows_counters';
+
+/** GPU performance metrics */
 export interface GpuMetrics {
+  /** Adapter index within SystemMetrics.gpus */
+  index: number;
   /** GPU model name */
   name: string;
+  /** Stable adapter id (NVML UUID, or PCI vendor/device/subsystem ids) that a preferred GPU is saved by */
+  adapter_id: string | null;
   /** GPU utilization percentage (0-100) */
   usage_percent: number;
   /** VRAM used in megabytes */
@@ -36,6 +62,18 @@ export interface GpuMetrics {
   memory_total_mb: number;
   /** GPU temperature in Celsius (if available) */
   temperature_celsius: number | null;
+  /** Board power draw in watts (if supported) */
+  power_draw_watts: number | null;
+  /** Enforced power limit in watts (if supported) */
+  power_limit_watts: number | null;
+  /** Average fan speed percentage (null for fanless or unsupported GPUs) */
+  fan_speed_percent: number | null;
+  /** Current graphics (core) clock in MHz */
+  core_clock_mhz: number | null;
+  /** Current memory clock in MHz */
+  memory_clock_mhz: number | null;
+  /** Backend the metrics were read from */
+  provider: GpuProviderKind;
 }
 
 /** RAM/Memory performance metrics */
@@ -48,6 +86,156 @@ export interface RamMetrics {
   available_bytes: number;
   /** Memory usage percentage (0-100) */
   usage_percent: number;
+  /** Total physical memory in megabytes */
+  total_mb: number;
+  /** Used physical memory in megabytes */
+  used_mb: number;
+  /** Available physical memory (free + reclaimable standby) in megabytes */
+  available_mb: number;
+  /** Committed memory in megabytes */
+  committed_mb: number | null;
+  /** Commit limit (physical memory + page file) in megabytes */
+  commit_limit_mb: number | null;
+  /** Standby cache size in megabytes */
+  standby_cache_mb: number | null;
+  /** Page file / swap in use in megabytes */
+  pagefile_used_mb: number | null;
+  /** Page file / swap size in megabytes */
+  pagefile_total_mb: number | null;
+}
+
+/** Network throughput metrics */
+export interface NetworkMetrics {
+  /** Total download rate across all interfaces (bytes/sec) */
+  download_bytes_per_sec: number;
+  /** Total upload rate across all interfaces (bytes/sec) */
+  upload_bytes_per_sec: number;
+  /** Per-interface breakdown (only included when requested) */
+  interfaces: NetworkInterfaceMetrics[] | null;
+}
+
+/** Throughput of a single network interface */
+export interface NetworkInterfaceMetrics {
+  /** Interface name/alias (e.g. "Ethernet", "Wi-Fi") */
+  name: string;
+  /** Download rate (bytes/sec) */
+  download_bytes_per_sec: number;
+  /** Upload rate (bytes/sec) */
+  upload_bytes_per_sec: number;
+}
+
+/** Capacity and activity of a single disk/volume */
+export interface DiskMetrics {
+  /** Device/volume name */
+  name: string;
+  /** Mount point (drive letter on Windows) */
+  mount_point: string;
+  /** File system (e.g. "NTFS") */
+  file_system: string;
+  /** Total capacity in bytes */
+  total_bytes: number;
+  /** Free space available to the user in bytes */
+  available_bytes: number;
+  /** Space usage percentage (0-100) */
+  usage_percent: number;
+  /** Whether this is a removable drive */
+  is_removable: boolean;
+  /** Read throughput in bytes/sec (null when unavailable) */
+  read_bytes_per_sec: number | null;
+  /** Write throughput in bytes/sec (null when unavailable) */
+  write_bytes_per_sec: number | null;
+}
+
+/** Battery and power plan status */
+export interface PowerStatus {
+  /** True on AC power, false on battery (null if unknown) */
+  on_ac_power: boolean | null;
+  /** Whether the system has a battery at all */
+  has_battery: boolean;
+  /** Battery charge percentage (0-100) */
+  battery_percent: number | null;
+  /** Whether the battery is currently charging */
+  is_charging: boolean;
+  /** Estimated battery time remaining in seconds (only while discharging) */
+  battery_seconds_remaining: number | null;
+  /** Active Windows power plan name (e.g. "Balanced", "High performance") */
+  power_plan: string | null;
+}
+
+/** Payload for the "performance:power_source_changed" event */
+export interface PowerSourceChangedEvent {
+  on_ac_power: boolean;
+  battery_percent: number | null;
+}
+
+/** Top resource-consuming processes */
+export interface TopProcesses {
+  /** Highest CPU usage first */
+  by_cpu: ProcessUsage[];
+  /** Highest memory usage first */
+  by_memory: ProcessUsage[];
+}
+
+/** Resource usage of a single process */
+export interface ProcessUsage {
+  pid: number;
+  name: string;
+  /** CPU usage normalized to total capacity (0-100) */
+  cpu_percent: number;
+  /** Resident memory in megabytes */
+  memory_mb: number;
+}
+
+/** Metric a performance alert watches */
+export type AlertMetric =
+  | 'cpu_usage'
+  | 'cpu_temp'
+  | 'gpu_usage'
+  | 'gpu_temp'
+  | 'vram_usage'
+  | 'ram_usage'
+  | 'ram_available_mb';
+
+/** How the metric is compared against the alert value */
+export type AlertComparator = 'above' | 'below';
+
+/** User-configurable threshold alert */
+export interface PerformanceAlert {
+  id: string;
+  name: string;
+  metric: AlertMetric;
+  comparator: AlertComparator;
+  value: number;
+  /** Minimum time between two firings of this alert */
+  cooldown_seconds: number;
+  enabled: boolean;
+  /** Whether to show a native notification (not just an in-app toast) */
+  notify: boolean;
+}
+
+/** Payload for the "performance:alert" event */
+export interface PerformanceAlertEvent {
+  alert_id: string;
+  name: string;
+  metric: AlertMetric;
+  comparator: AlertComparator;
+  threshold: number;
+  /** Value that triggered the alert */
+  value: number;
+  notify: boolean;
+  timestamp: number;
+}
+
+/** Why a performance capture ended */
+export type CaptureStopReason = 'manual' | 'duration_limit' | 'size_limit' | 'error' | 'monitoring_stopped';
+
+/** Result of a finished capture (also the "performance:capture_stopped" event payload) */
+export interface CaptureSummary {
+  path: string;
+  samples_written: number;
+  bytes_written: number;
+  duration_seconds: number;
+  stop_reason: CaptureStopReason;
 }
 
 /** Data point for time-series charts */
@@ -1,17 +1,35 @@
 // Server Monitoring types
 
+export type SSHAuthMethod = 'password' | 'key_file' | 'generated_key';
+
 export interface ServerConfig {
   host: string;
   port: number;
   username: string;
   domain?: string;
+  auth_method: SSHAuthMethod;
+  key_path?: string | null;
+  // Seconds between background metrics samples; null while the sampler is off
+  metrics_interval_seconds?: number | null;
+  // Seconds a pooled SSH session stays open unused; null uses the default
+  ssh_idle_timeout_seconds?: number | null;
+  // Keep the SSH session open between metrics samples instead of closing it after each
+  metrics_keep_session_open?: boolean;
 }
 
 export interface SSHCredentials {
-  password: string;
+  password?: string | null;
+  key_passphrase?: string | null;
   saved_at: string;
 }
 
+// Public half of the generated keypair, for the server's authorized_keys
+export interface SSHPublicKey {
+  public_key: string;
+  key_path: string;
+  fingerprint: string;
+}
+
 export type CommandStatus = 'running' | 'completed' | 'failed';
 
 export interface CommandResult {
@@ -26,6 +44,21 @@ export interface CommandResult {
 
 export type QuickActionCategory = 'login' | 'status' | 'service' | 'logs';
 
+export type SystemdServiceAction = 'start' | 'stop' | 'restart' | 'enable' | 'disable';
+
+// systemd action a quick action runs instead of a shell command
+export interface ServiceActionTemplate {
+  service: string;
+  action: SystemdServiceAction;
+}
+
+// `{name}` placeholder in a quick action command, filled in when it runs
+export interface QuickActionVariable {
+  name: string;
+  label?: string;
+  default?: string;
+}
+
 export interface QuickAction {
   id: string;
   label: string;
@@ -33,6 +66,18 @@ export interface QuickAction {
   category: QuickActionCategory;
   icon: string;
   description: string;
+  service_action?: ServiceActionTemplate;
+  variables: QuickActionVariable[];
+  requires_confirmation: boolean;
+}
+
+export interface SystemdService {
+  name: string;
+  description: string;
+  load_state: string;
+  active_state: string;
+  sub_state: string;
+  memory_bytes?: number | null;
 }
 
 export interface SystemStatus {
@@ -86,3 +131,153 @@ export interface UploadCompleteEvent {
   success: boolean;
   error?: string;
 }
+
+// SFTP browser types
+export interface SftpEntry {
+  name: string;
+  path: string;
+  size: number;
+  modified?: string | null;
+  permissions: string;
+  is_dir: boolean;
+  is_symlink: boolean;
+}
+
+export interface SftpListing {
+  path: string;
+  entries: SftpEntry[];
+}
+
+export interface SftpTransferResult {
+  transfer_id: string;
+  remote_path: string;
+  local_path: string;
+  size: number;
+  resumed_from: number;
+}
+
+// Error returned by the sftp_* commands
+export type SftpError =
+  | { kind: 'invalid_path'; path: string; message: string }
+  | { kind: 'path_traversal'; path: string }
+  | { kind: 'permission_denied'; path: string }
+  | { kind: 'not_found'; path: string }
+  | { kind: 'already_exists'; path: string }
+  | { kind: 'not_empty'; path: string }
+  | { kind: 'insufficient_space'; path: string; required: number; available: number }
+  | { kind: 'cancelled' }
+  | { kind: 'failed'; message: string };
+
+export interface TransferProgressEvent {
+  transfer_id: string;
+  direction: 'download' | 'upload';
+  remote_path: string;
+  percent: number;
+  stage: string;
+}
+
+// SSH tunnel types
+export interface SshTunnel {
+  id: string;
+  local_port: number;
+  remote_host: string;
+  remote_port: number;
+  created_at: string;
+  status: 'connected' | 'reconnecting' | 'disconnected';
+  bytes_sent: number;
+  bytes_received: number;
+  connections: number;
+  active_connections: number;
+}
+
+export type SshConnectionStatus = 'connected' | 'connecting' | 'reconnecting' | 'disconnected';
+
+export interface SshConnectionState {
+  profile: string;
+  state: SshConnectionStatus;
+  connected_since?: string | null;
+  active_requests: number;
+  idle_seconds: number;
+  idle_timeout_seconds: number;
+}
+
+export interface SshConnectionStateEvent {
+  profile: string;
+  state: SshConnectionStatus;
+  message?: string;
+}
+
+// Host key verification types
+export type HostKeyTrust = 'trusted' | 'unknown' | 'changed';
+
+export interface KnownHost {
+  key_type: string;
+  fingerprint: string;
+  trusted_at: string;
+}
+
+export interface SshConnectionTest {
+  success: boolean;
+  key_type?: string | null;
+  fingerprint?: string | null;
+  trust?: HostKeyTrust | null;
+  error?: string | null;
+}
+
+// Payload of server:host_key_unknown and server:host_key_changed
+export interface HostKeyEvent {
+  profile: string;
+  host: string;
+  port: number;
+  key_type: string;
+  fingerprint: string;
+  trusted_fingerprint?: string | null;
+}
+
+// Payload of server:host_key_trusted
+export interface HostKeyTrustedEvent {
+  profile: string;
+}
+
+export interface TunnelConnectionEvent {
+  state: 'lost' | 'reconnecting' | 'reconnected' | 'failed';
+  attempt?: number;
+  retry_in?: number;
+  message?: string;
+  // Tunnels dropped because they couldn't be reopened
+  closed?: string[];
+}
+
+// Server metrics history types
+export type MetricsRange = '1h' | '6h' | '24h';
+
+export interface ServerMetricsSample {
+  timestamp: number;
+  cpu_percent: number;
+  memory_used_bytes: number;
+  memory_total_bytes: number;
+  disk_used_bytes: number;
+  disk_total_bytes: number;
+  load_average: number;
+}
+
+export interface ServerMetricsHistory {
+  server: string;
+  range: MetricsRange;
+  interval_seconds?: number | null;
+  // Span each sample averages; 0 when samples are as recorded
+  bucket_seconds: number;
+  samples: ServerMetricsSample[];
+}
+
+export interface MetricsSampleEvent {
+  server: string;
+  sample: ServerMetricsSample;
+}
+
+export interface MetricsUnreachableEvent {
+  server: string;
+  message: string;
+  failures: number;
+  retry_in_seconds: number;
+}
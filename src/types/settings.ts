@@ -1,3 +1,6 @@
+import type { PresenceButton, PresenceHiddenGameDisplay } from './discord';
+import type { UpdateChannel } from './updater';
+
 export interface ValorantCredentials {
   username: string;
   region: string;
@@ -11,10 +14,13 @@ export interface ValorantCredentialsInput {
 }
 
 export interface Settings {
+  schema_version: number;
   download_path: string;
   default_quality: string;
   max_concurrent_downloads: number;
   max_concurrent_ml_jobs: number;
+  /** Start the ML job queue when Atlas launches; off leaves queued and interrupted jobs waiting */
+  resume_ml_jobs_on_startup: boolean;
   valorant_credentials: ValorantCredentials | null;
   atlas_project_path: string | null;
   remote_update_path: string | null;
@@ -23,6 +29,22 @@ export interface Settings {
   sidebar_order: string[] | null;
   hidden_sidebar_items: string[] | null;
   discord_rich_presence_enabled: boolean;
+  /** Template of the presence details line while gaming, e.g. "Playing {game}" */
+  discord_presence_details_template: string;
+  /** Template of the presence state line while gaming */
+  discord_presence_state_template: string;
+  /** Link buttons under the presence, at most two */
+  discord_presence_buttons: PresenceButton[];
+  /** Show "AFK in {game}" once idle during a gaming session */
+  discord_presence_afk_enabled: boolean;
+  /** Idle minutes before the presence shows AFK (1-120) */
+  discord_presence_afk_minutes: number;
+  /** Also set the friends presence to Away while idle */
+  friends_presence_afk_away: boolean;
+  /** Games (display or process names) kept out of the Discord and friends presence */
+  presence_blocklist: string[];
+  /** What Discord shows while a hidden game runs */
+  presence_hidden_game_display: PresenceHiddenGameDisplay;
   run_on_startup: boolean;
   close_to_tray: boolean;
   auto_restore_enabled: boolean;
@@ -38,6 +60,45 @@ export interface Settings {
   partner_widget_position_x: number | null;
   /** Y position of the floating partner widget */
   partner_widget_position_y: number | null;
+  /** How many minutes of performance history to keep in memory for graphs */
+  performance_history_minutes: number;
+  /** Performance monitor poll interval in ms (0 = automatic: faster while gaming, slower when idle) */
+  performance_poll_interval_ms: number;
+  /** Include removable drives (USB sticks, SD cards) in the performance monitor's disk metrics */
+  performance_include_removable_disks: boolean;
+  /** GPU (by GpuMetrics.adapter_id, or adapter name) to report as primary - null picks the busiest adapter */
+  preferred_gpu: string | null;
+  /** Performance captures stop automatically after this many minutes */
+  performance_capture_max_minutes: number;
+  /** Performance captures stop automatically once the file reaches this size (MB) */
+  performance_capture_max_mb: number;
+  /** Seconds to wait after a gaming session ends before auto-restoring killed processes */
+  auto_restore_delay_seconds: number;
+  /** Seconds to wait for a process to close after WM_CLOSE before force-killing it */
+  graceful_close_timeout_seconds: number;
+  /** Run the default gaming profile automatically when a whitelisted game starts */
+  auto_execute_default_profile: boolean;
+  /** Memory growth rate (MB/hour) at which a process is flagged as a possible leak */
+  leak_growth_mb_per_hour: number;
+  /** How long the growth must be sustained before flagging (capped at 30 minutes) */
+  leak_min_duration_minutes: number;
+  /** Emit `task_monitor:leak_suspect` when a new process is flagged */
+  leak_suspect_events: boolean;
+  /** Include games installed through the itch.io app when scanning for games */
+  itch_detection_enabled: boolean;
+  /** SteamGridDB API key used to fetch game artwork */
+  steamgriddb_api_key: string | null;
+  /** Launch Steam games through steam://rungameid unless the game overrides it */
+  steam_launch_via_protocol: boolean;
+  /** Check the Valorant store when it resets and show a notification with the new offers */
+  valorant_store_notifications: boolean;
+  settings_profile_auto_switch: boolean;
+  /** Release channel the updater checks */
+  update_channel: UpdateChannel;
+  /** Download updates in the background when found; installing still waits for the user */
+  auto_download_updates: boolean;
+  /** Hours between background update checks */
+  update_check_interval_hours: number;
 }
 
 export interface UpdateSettingsParams {
@@ -45,6 +106,7 @@ export interface UpdateSettingsParams {
   default_quality?: string;
   max_concurrent_downloads?: number;
   max_concurrent_ml_jobs?: number;
+  resume_ml_jobs_on_startup?: boolean;
   valorant_credentials?: ValorantCredentialsInput | null;
   atlas_project_path?: string;
   remote_update_path?: string;
@@ -53,6 +115,14 @@ export interface UpdateSettingsParams {
   sidebar_order?: string[];
   hidden_sidebar_items?: string[];
   discord_rich_presence_enabled?: boolean;
+  discord_presence_details_template?: string;
+  discord_presence_state_template?: string;
+  discord_presence_buttons?: PresenceButton[];
+  discord_presence_afk_enabled?: boolean;
+  discord_presence_afk_minutes?: number;
+  friends_presence_afk_away?: boolean;
+  presence_blocklist?: string[];
+  presence_hidden_game_display?: PresenceHiddenGameDisplay;
   run_on_startup?: boolean;
   close_to_tray?: boolean;
   auto_restore_enabled?: boolean;
@@ -62,4 +132,118 @@ export interface UpdateSettingsParams {
   partner_widget_enabled?: boolean;
   partner_widget_position_x?: number;
   partner_widget_position_y?: number;
+  performance_history_minutes?: number;
+  performance_poll_interval_ms?: number;
+  performance_include_removable_disks?: boolean;
+  preferred_gpu?: string;
+  performance_capture_max_minutes?: number;
+  performance_capture_max_mb?: number;
+  auto_restore_delay_seconds?: number;
+  graceful_close_timeout_seconds?: number;
+  auto_execute_default_profile?: boolean;
+  leak_growth_mb_per_hour?: number;
+  leak_min_duration_minutes?: number;
+  leak_suspect_events?: boolean;
+  itch_detection_enabled?: boolean;
+  steamgriddb_api_key?: string;
+  steam_launch_via_protocol?: boolean;
+  valorant_store_notifications?: boolean;
+  settings_profile_auto_switch?: boolean;
+  update_channel?: UpdateChannel;
+  auto_download_updates?: boolean;
+  update_check_interval_hours?: number;
+}
+
+export interface SkippedSettingsSection {
+  section: string;
+  reason: string;
+}
+
+// A field of an imported settings section that was left out
+export interface SkippedSettingsField {
+  field: string;
+  reason: string;
+}
+
+// Result of import_settings
+export interface SettingsImportReport {
+  applied: string[];
+  skipped: SkippedSettingsSection[];
+  /** Fields of an applied settings section that weren't imported */
+  skipped_fields: SkippedSettingsField[];
+}
+
+// A settings field rejected by validate_settings / update_settings
+export interface SettingsFieldError {
+  field: string;
+  message: string;
+}
+
+// Data directory location, from get_data_directory_info
+export interface DataMigration {
+  from: string;
+  to: string;
+  started_at: string;
+}
+
+export interface DataFolderSize {
+  name: string;
+  bytes: number;
+}
+
+export interface DataDirectoryInfo {
+  path: string;
+  is_default: boolean;
+  total_bytes: number;
+  folders: DataFolderSize[];
+  interrupted_migration: DataMigration | null;
+}
+
+// Payload of the data_directory:migration_progress event
+export interface DataMigrationProgress {
+  stage: 'copying' | 'verifying';
+  files_done: number;
+  files_total: number;
+  bytes_done: number;
+  bytes_total: number;
+}
+
+// Feature whose data reset_feature_data can reset
+export type DataFeature =
+  | 'downloads'
+  | 'gaming_sessions'
+  | 'game_library'
+  | 'task_monitor_profiles'
+  | 'valorant_store'
+  | 'friends';
+
+// Payload of the data:reset event
+export interface DataResetEvent {
+  feature: DataFeature | 'settings';
+}
+
+// Result of save_user_avatar
+export interface SavedAvatar {
+  path: string;
+  thumbnail_path: string;
+  width: number;
+  height: number;
+  bytes: number;
+}
+
+// Named snapshot of a subset of settings
+export interface SettingsProfile {
+  name: string;
+  values: Partial<Settings>;
+  saved_at: string;
+}
+
+// Payload of the settings:changed event
+export interface SettingChange {
+  key: string;
+  value: unknown;
+}
+
+export interface SettingsChangedEvent {
+  changes: SettingChange[];
 }
@@ -6,10 +6,99 @@ export interface ProcessInfo {
   cpu_usage: number;
   memory_mb: number;
   gpu_usage: number | null;
+  /** Busiest GPU engine utilization (any vendor) - null when the counters are unavailable */
+  gpu_percent: number | null;
+  /** TCP bytes per second; null when unavailable (not elevated or not Windows) */
+  network_sent_bps: number | null;
+  network_received_bps: number | null;
   category: ProcessCategory;
   description: string | null;
   can_kill: boolean;
   parent_pid: number | null;
+  /** Current priority class - null when it can't be read */
+  priority: ProcessPriority | null;
+  /** User-defined category label - excluded from built-in category bulk kills */
+  custom_category: string | null;
+  /** On its category's exclusion list - skipped by bulk kills, profiles and recommendations */
+  excluded: boolean;
+  /** Suspended through Atlas (resumed by the restore flow) */
+  suspended: boolean;
+}
+
+/** Built-in description of a well-known process */
+export interface ProcessDescription {
+  friendly_name: string;
+  description: string;
+  impact_if_killed: string;
+  respawns: boolean;
+  respawn_when: string | null;
+}
+
+/** Fields the process wouldn't give up (access denied when elevated) are null */
+export interface ProcessDetails extends ProcessInfo {
+  command_line: string | null;
+  /** Unix seconds */
+  start_time: number | null;
+  parent_name: string | null;
+  thread_count: number | null;
+  handle_count: number | null;
+  /** CompanyName from the executable's version info */
+  publisher: string | null;
+  /** Valid Authenticode signature, embedded or through a system catalog */
+  signed: boolean | null;
+  known_description: ProcessDescription | null;
+}
+
+export interface ProcessHistoryPoint {
+  /** Unix seconds */
+  timestamp: number;
+  cpu_usage: number;
+  memory_mb: number;
+}
+
+export interface ProcessHistory {
+  pid: number;
+  name: string;
+  /** Oldest first */
+  points: ProcessHistoryPoint[];
+  /** The process is gone but its history is still within the grace period */
+  exited: boolean;
+}
+
+export interface KillRecommendation extends ProcessInfo {
+  /** Why the process is recommended, e.g. "grew 1.2 GB in 40 min" */
+  reason: string;
+}
+
+/** Payload of `task_monitor:leak_suspect` */
+export interface LeakSuspect {
+  pid: number;
+  name: string;
+  display_name: string;
+  memory_mb: number;
+  growth_mb: number;
+  duration_seconds: number;
+  mb_per_hour: number;
+  /** e.g. "grew 1.2 GB in 40 min" */
+  reason: string;
+}
+
+export type ProcessSortBy = 'Cpu' | 'Memory' | 'Name' | 'Gpu';
+
+export interface ProcessListPage {
+  processes: ProcessInfo[];
+  /** Matching processes before limit/offset were applied */
+  total_count: number;
+}
+
+export type ProcessPriority = 'Idle' | 'BelowNormal' | 'Normal' | 'AboveNormal' | 'High';
+
+export interface ProcessAffinity {
+  pid: number;
+  /** Bit N set = the process may run on logical core N */
+  mask: number;
+  system_mask: number;
+  core_count: number;
 }
 
 export interface GroupedProcessInfo {
@@ -37,17 +126,102 @@ export type ProcessCategory =
   | 'DriverHardware'
   | 'Unknown';
 
+export interface CategoryOverride {
+  /** Built-in category name or a custom category label */
+  category: string;
+  display_name: string | null;
+  description: string | null;
+}
+
+export interface CustomCategory {
+  name: string;
+  /** Whether kill_by_category may bulk-kill this category */
+  killable: boolean;
+}
+
+export interface ProcessCategoryConfig {
+  /** Normalized process name -> override */
+  overrides: Record<string, CategoryOverride>;
+  custom_categories: CustomCategory[];
+  /** Category name -> normalized process names never bulk-killed from it */
+  exclusions: Record<string, string[]>;
+}
+
 export interface GamingProfile {
   id: string;
   name: string;
   processes_to_kill: string[];
   is_default: boolean;
+  /** Processes lowered to Below Normal instead of killed */
+  processes_to_deprioritize?: string[];
+  /** Windows services stopped when the profile runs */
+  services_to_stop?: string[];
+}
+
+export interface ProfileImportResult {
+  /** Profiles added as new entries */
+  imported: number;
+  /** Existing profiles overwritten (same id, overwrite enabled) */
+  replaced: number;
+  /** Imported profiles given a new id because theirs was already taken */
+  renamed_ids: number;
+}
+
+export type ServiceState = 'Running' | 'Stopped' | 'StartPending' | 'StopPending' | 'Paused' | 'Unknown';
+
+export type ServiceStartType = 'Automatic' | 'Manual' | 'Disabled' | 'System' | 'Unknown';
+
+export interface ServiceInfo {
+  name: string;
+  display_name: string;
+  state: ServiceState;
+  start_type: ServiceStartType;
+  /** False for services on the critical deny-list */
+  can_stop: boolean;
+}
+
+export interface ServicesSummary {
+  running: number;
+  stopped: number;
+  services: ServiceInfo[];
+}
+
+export type KillMethod = 'graceful' | 'forced';
+
+export interface ProcessKillOutcome {
+  pid: number;
+  name: string;
+  method: KillMethod;
 }
 
 export interface KillResult {
   killed: number;
   failed: number;
   errors: string[];
+  /** Per-process outcome for every successfully killed process */
+  outcomes: ProcessKillOutcome[];
+  /** Processes lowered in priority by a profile */
+  deprioritized: number;
+  /** Services stopped by a profile */
+  services_stopped: number;
+}
+
+/** Dry run of a gaming profile */
+export interface ProfilePreview {
+  profile_id: string;
+  profile_name: string;
+  /** Every running process matching the kill list, largest first - check `can_kill` and `excluded` */
+  matched: ProcessInfo[];
+  /** Configured names with no running process */
+  unmatched_names: string[];
+  killable_memory_mb: number;
+}
+
+/** Payload of `task_monitor:profile_auto_executed` */
+export interface ProfileAutoExecutedEvent {
+  profile_id: string;
+  profile_name: string;
+  result: KillResult;
 }
 
 export interface SystemSummary {
@@ -73,6 +247,30 @@ export const CATEGORY_CONFIG: Record<
   Unknown: { label: 'Unknown', color: 'text-gray-500', canKill: true },
 };
 
+export type StartupLocation =
+  | 'RegistryUser'
+  | 'RegistryMachine'
+  | 'RegistryMachine32'
+  | 'StartupFolderUser'
+  | 'StartupFolderCommon';
+
+export type StartupImpact = 'Low' | 'Medium' | 'High';
+
+export interface StartupItem {
+  id: string;
+  /** Registry value or file name */
+  name: string;
+  display_name: string;
+  command: string;
+  exe_path: string | null;
+  location: StartupLocation;
+  enabled: boolean;
+  publisher: string | null;
+  /** Estimated from the executable size */
+  impact: StartupImpact | null;
+  description: string | null;
+}
+
 export interface KilledProcessInfo {
   exe_path: string;
   name: string;
@@ -81,11 +279,26 @@ export interface KilledProcessInfo {
   working_dir: string | null;
 }
 
+export interface DeprioritizedProcessInfo {
+  pid: number;
+  name: string;
+  original_priority: ProcessPriority;
+  changed_at: number;
+}
+
+export interface StoppedServiceInfo {
+  name: string;
+  original_start_type: ServiceStartType;
+  stopped_at: number;
+}
+
 export interface RestoreList {
   session_id: string | null;
   processes: KilledProcessInfo[];
   created_at: number;
   detected_respawns: string[];
+  deprioritized: DeprioritizedProcessInfo[];
+  stopped_services: StoppedServiceInfo[];
 }
 
 export interface RestoreError {
@@ -98,4 +311,19 @@ export interface RestoreResult {
   skipped_self_restoring: number;
   failed: number;
   errors: RestoreError[];
+  priorities_restored: number;
+  services_restored: number;
+  /** Processes suspended through Atlas and resumed */
+  processes_resumed: number;
+}
+
+/** Payload of `task_monitor:processes_restored`, emitted after a gaming session's auto-restore */
+export interface SessionRestoreResult {
+  restored: KilledProcessInfo[];
+  skipped_self_restoring: KilledProcessInfo[];
+  failed: RestoreError[];
+  priorities_restored: number;
+  services_restored: number;
+  /** Processes suspended through Atlas and resumed */
+  processes_resumed: number;
 }
@@ -9,11 +9,18 @@ export type UpdateStatus =
   | 'installing'     // Installation in progress
   | 'error';         // Error occurred
 
+export type UpdateChannel = 'stable' | 'beta';
+
 export interface UpdateInfo {
   version: string;
   currentVersion: string;
   date?: string;
   body?: string;      // Changelog/release notes
+  channel: UpdateChannel;
+  /** Older than the installed version (the latest stable after leaving the beta channel) */
+  downgrade: boolean;
+  skipped: boolean;              // The user chose to skip this version
+  snoozed_until: string | null;  // Update prompts are snoozed until then
 }
 
 export interface UpdateProgress {
@@ -33,8 +40,23 @@ export interface UpdateProgressEvent {
   downloaded: number;
   total: number;
   percent: number;
+  background: boolean;  // Downloaded by the background checker
+}
+
+// Payload of `updater:download_started` and `updater:download_finished`
+export interface UpdateDownloadEvent {
+  version: string;
+  background: boolean;
+  bytes?: number;     // Only on download_finished
 }
 
+// Error returned by `install_update`
+export type UpdateInstallError =
+  | { kind: 'not_downloaded' }
+  | { kind: 'length_mismatch'; expected: number | null; actual: number }
+  | { kind: 'signature_invalid'; message: string }
+  | { kind: 'install_failed'; message: string };
+
 export interface UpdateAvailableEvent {
   version: string;
   current_version: string;
@@ -45,3 +67,25 @@ export interface UpdateAvailableEvent {
 export interface UpdateErrorEvent {
   message: string;
 }
+
+export interface ChangelogEntry {
+  version: string;
+  date: string | null;
+  body: string;       // Markdown
+  prerelease: boolean;
+}
+
+// Payload of `update:whats_new`, sent on the first run after an update
+export interface WhatsNew {
+  version: string;
+  previous_version: string;
+  entry: ChangelogEntry | null;
+}
+
+export interface RollbackInfo {
+  available: boolean;
+  version: string | null;
+  replaced_by: string | null;
+  saved_at: string | null;
+  expires_at: string | null;  // Set once the new version has started
+}
@@ -6,6 +6,50 @@ export interface ValorantItem {
   image_url: string | null;
   item_type: string; // e.g., "skin", "bundle"
   uuid?: string; // Item UUID
+  local_image?: string; // Downloaded copy of the image
+}
+
+export interface NightMarketItem {
+  name: string;
+  image_url: string | null;
+  item_type: string;
+  uuid?: string | null;
+  original_price: number; // VP
+  discount_percent: number;
+  final_price: number; // VP
+  local_image?: string;
+}
+
+export interface ValorantNightMarket {
+  expires_at: string; // ISO timestamp
+  items: NightMarketItem[];
+}
+
+export interface BundleItem {
+  name: string;
+  item_type: string; // "skin", "buddy", "spray", "player_card", "player_title", ...
+  uuid: string;
+  image_url: string | null;
+  amount: number;
+  base_price: number; // VP when bought on its own
+  discounted_price: number; // VP
+}
+
+export interface ValorantBundle {
+  uuid: string;
+  name: string;
+  image_url: string | null;
+  items: BundleItem[];
+  base_price: number; // Sum of the individual item prices
+  bundle_price: number; // VP for the whole bundle
+  duration_remaining_seconds: number; // As of the store check
+  expires_at: string; // ISO timestamp
+}
+
+export interface BundleHistoryEntry {
+  bundle: ValorantBundle;
+  first_seen: string; // Store date
+  last_seen: string; // Store date
 }
 
 export interface ValorantStore {
@@ -13,6 +57,51 @@ export interface ValorantStore {
   items: ValorantItem[];
   checked_at: string; // ISO timestamp
   is_real_data?: boolean; // Whether this is real API data or mock data
+  expires_at?: string | null; // ISO timestamp the daily offers rotate
+  night_market?: ValorantNightMarket | null; // Only while a Night Market is running
+  bundle?: ValorantBundle | null; // Featured bundle
+  other_bundles?: ValorantBundle[]; // Weeks with more than one featured bundle
+  wishlist_hits?: ValorantWishlistHit[]; // Wishlisted skins offered in this rotation
+}
+
+// Skin wishlist entry, matched by UUID or by name
+export interface ValorantWishlistItem {
+  id: string;
+  skin_uuid: string | null;
+  name: string | null;
+  added_at: string; // ISO timestamp
+}
+
+// Payload of the valorant:wishlist_hit event
+export interface ValorantWishlistHit {
+  wishlist_item_id: string;
+  name: string;
+  price: number; // VP
+  night_market: boolean;
+}
+
+export interface SkinAppearance {
+  date: string;
+  price: number; // VP
+}
+
+export interface SkinAppearanceStats {
+  skin: string;
+  appearance_count: number;
+  first_seen: string | null;
+  last_seen: string | null;
+  average_gap_days: number | null;
+  appearances: SkinAppearance[]; // Oldest first
+}
+
+export interface StoreStreaks {
+  rotations_tracked: number;
+  distinct_skins_seen: number;
+  last_wishlist_hit: string | null;
+  days_since_wishlist_hit: number | null;
+  most_frequent_skin: string | null;
+  most_frequent_skin_count: number;
+  longest_fresh_streak: number; // Rotations in a row offering only never-seen skins
 }
 
 export interface GetStoreHistoryParams {
@@ -29,7 +118,33 @@ export interface AuthStatus {
   expires_hint: string | null; // "3 weeks" or "1 week"
 }
 
+// A captured Riot account (cookies stay in the backend)
+export interface ValorantAccountInfo {
+  id: string; // PUUID
+  name: string | null; // Riot ID (name#tag), known after the first store check
+  region: string;
+  is_active: boolean;
+  has_full_cookies: boolean;
+  captured_at: string | null;
+}
+
 // Event payloads
 export interface ValorantUpdatedEvent {
   store: ValorantStore;
 }
+
+export interface ValorantAuthRequiredEvent {
+  account_id: string;
+  reason: string;
+}
+
+export interface SkinMetadata {
+  uuid: string;
+  skin_uuid: string;
+  name: string;
+  tier: string | null; // Content tier dev name, e.g. "Premium"
+  tier_color: string | null; // RRGGBBAA hex
+  image_url: string | null;
+  local_image: string | null;
+  video_url: string | null;
+}
@@ -1,6 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { useTauriEvent } from '../hooks';
+import { useDataReset, useTauriEvent } from '../hooks';
 import type { Download, DownloadProgressEvent, DownloadStatusEvent } from '../types';
 import {
   Download as DownloadIcon,
@@ -143,6 +143,11 @@ export function DownloadQueue() {
     );
   }, []);
 
+  // Handle files added from elsewhere, e.g. downloaded from the server
+  const handleAdded = useCallback((download: Download) => {
+    setDownloads((prev) => (prev.some((d) => d.id === download.id) ? prev : [...prev, download]));
+  }, []);
+
   // Handle download started
   const handleStarted = useCallback((event: DownloadStatusEvent) => {
     setDownloads((prev) =>
@@ -192,9 +197,11 @@ export function DownloadQueue() {
 
   // Listen for Tauri events
   useTauriEvent<DownloadProgressEvent>('download:progress', handleProgress);
+  useTauriEvent<Download>('download:added', handleAdded);
   useTauriEvent<DownloadStatusEvent>('download:started', handleStarted);
   useTauriEvent<DownloadStatusEvent>('download:completed', handleCompleted);
   useTauriEvent<DownloadStatusEvent>('download:failed', handleFailed);
+  useDataReset('downloads', fetchDownloads);
 
   async function handleSubmit(e: React.FormEvent) {
     e.preventDefault();
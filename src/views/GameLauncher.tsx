@@ -8,6 +8,8 @@ import {
   AlertCircle,
   Gamepad2,
   CheckCircle,
+  AlertTriangle,
+  X,
 } from 'lucide-react';
 import { useGameLauncher } from '../hooks/useGameLauncher';
 import { GameCard, GameDetailPanel, AddGameModal } from '../components/launcher';
@@ -20,12 +22,15 @@ export default function GameLauncher() {
     isLoading,
     isScanning,
     error,
+    launchWarnings,
+    dismissLaunchWarnings,
     loadLibrary,
     scanForGames,
     addDetectedGames,
     addManualGame,
     removeGame,
     launchGame,
+    setHiddenFromPresence,
   } = useGameLauncher();
 
   // Modal state
@@ -148,6 +153,25 @@ export default function GameLauncher() {
         </div>
       )}
 
+      {/* Options the last launch couldn't apply */}
+      {launchWarnings.length > 0 && (
+        <div className="mx-6 mt-4 p-4 rounded-lg bg-amber-500/20 border border-amber-500/30 flex items-start gap-3">
+          <AlertTriangle className="w-5 h-5 text-amber-400 flex-shrink-0" />
+          <div className="flex-1 space-y-1">
+            {launchWarnings.map((warning) => (
+              <p key={warning} className="text-sm text-amber-400">{warning}</p>
+            ))}
+          </div>
+          <button
+            onClick={dismissLaunchWarnings}
+            className="p-1 rounded-lg text-amber-400 hover:bg-white/10 transition-colors"
+            title="Dismiss"
+          >
+            <X className="w-4 h-4" />
+          </button>
+        </div>
+      )}
+
       {/* Content */}
       <div className="flex-1 overflow-auto p-6">
         {isLoading && library.games.length === 0 ? (
@@ -195,10 +219,11 @@ export default function GameLauncher() {
       {/* Game Detail Panel */}
       {selectedGame && (
         <GameDetailPanel
-          game={selectedGame}
+          game={library.games.find(g => g.id === selectedGame.id) ?? selectedGame}
           onClose={() => setSelectedGame(null)}
           onLaunch={handleLaunchGame}
           onRemove={handleRemoveGame}
+          onToggleHiddenFromPresence={setHiddenFromPresence}
         />
       )}
 
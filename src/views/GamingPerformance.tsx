@@ -30,6 +30,7 @@ export default function GamingPerformance() {
     addGame,
     removeGame,
     toggleGame,
+    setGameHiddenFromPresence,
     isDetecting,
     startDetection,
     stopDetection,
@@ -336,6 +337,7 @@ export default function GamingPerformance() {
                   key={game.process_name}
                   game={game}
                   onToggle={(enabled) => toggleGame(game.process_name, enabled)}
+                  onToggleHidden={(hidden) => setGameHiddenFromPresence(game.process_name, hidden)}
                   onDelete={() => removeGame(game.process_name)}
                 />
               ))}
@@ -451,10 +453,12 @@ function BottleneckBadge({ type }: { type: BottleneckType }) {
 function GameListItem({
   game,
   onToggle,
+  onToggleHidden,
   onDelete,
 }: {
   game: GameEntry;
   onToggle: (enabled: boolean) => void;
+  onToggleHidden: (hidden: boolean) => void;
   onDelete: () => void;
 }) {
   return (
@@ -471,12 +475,26 @@ function GameListItem({
           <p className="text-xs text-muted">{game.process_name}</p>
         </div>
       </div>
-      <button
-        onClick={onDelete}
-        className="p-2 rounded-lg text-muted hover:text-red-400 hover:bg-red-500/20 transition-all"
-      >
-        <Trash2 className="w-4 h-4" />
-      </button>
+      <div className="flex items-center gap-3">
+        <label
+          className="flex items-center gap-2 text-xs text-muted cursor-pointer"
+          title="Keep this game's name out of the Discord and friends presence"
+        >
+          <input
+            type="checkbox"
+            checked={game.hide_from_presence ?? false}
+            onChange={(e) => onToggleHidden(e.target.checked)}
+            className="w-4 h-4 rounded border-white/20 bg-white/5 text-accent focus:ring-accent"
+          />
+          Hide from presence
+        </label>
+        <button
+          onClick={onDelete}
+          className="p-2 rounded-lg text-muted hover:text-red-400 hover:bg-red-500/20 transition-all"
+        >
+          <Trash2 className="w-4 h-4" />
+        </button>
+      </div>
     </div>
   );
 }
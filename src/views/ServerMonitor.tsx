@@ -4,6 +4,12 @@ import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import type {
   ServerConfig,
+  SSHAuthMethod,
+  SSHPublicKey,
+  SshConnectionTest,
+  HostKeyTrustedEvent,
+  SystemdService,
+  SystemdServiceAction,
   QuickAction,
   CommandResult,
   SSHOutputEvent,
@@ -41,7 +47,18 @@ import {
   CheckCircle,
   Circle,
   Play,
+  Copy,
+  Square,
+  XCircle,
 } from 'lucide-react';
+import { MetricsHistoryCard } from '../components/server/MetricsHistoryCard';
+import { TunnelsCard } from '../components/server/TunnelsCard';
+
+const AUTH_METHOD_OPTIONS: { value: SSHAuthMethod; label: string }[] = [
+  { value: 'password', label: 'Password' },
+  { value: 'key_file', label: 'Key File' },
+  { value: 'generated_key', label: 'Atlas Key' },
+];
 
 // Icon mapping for quick actions
 const iconMap: Record<string, React.ComponentType<{ size?: number; className?: string }>> = {
@@ -56,6 +73,7 @@ const iconMap: Record<string, React.ComponentType<{ size?: number; className?: s
   FileText,
   AlertTriangle,
   ScrollText,
+  XCircle,
 };
 
 export function ServerMonitor() {
@@ -72,12 +90,28 @@ export function ServerMonitor() {
   const [savePassword, setSavePassword] = useState(true);
   const [authLoading, setAuthLoading] = useState(false);
   const [authError, setAuthError] = useState<string | null>(null);
+  const [keyPath, setKeyPath] = useState('');
+  const [generatedKey, setGeneratedKey] = useState<SSHPublicKey | null>(null);
+  const [keyLoading, setKeyLoading] = useState(false);
+  const authMethod: SSHAuthMethod = serverConfig?.auth_method ?? 'password';
+  const usesKey = authMethod !== 'password';
+
+  // systemd services state
+  const [services, setServices] = useState<SystemdService[] | null>(null);
+  const [serviceFilter, setServiceFilter] = useState('');
+  const [servicesLoading, setServicesLoading] = useState(false);
+
+  // Quick action waiting for its variable values
+  const [pendingAction, setPendingAction] = useState<QuickAction | null>(null);
+  const [actionVars, setActionVars] = useState<Record<string, string>>({});
 
   // Terminal state
   const [terminalLines, setTerminalLines] = useState<TerminalLine[]>([]);
   const [currentCommand, setCurrentCommand] = useState('');
   const [isExecuting, setIsExecuting] = useState(false);
   const terminalRef = useRef<HTMLDivElement>(null);
+  // Call to repeat once the host key dialog trusts the server's key
+  const retryAfterTrustRef = useRef<(() => void) | null>(null);
 
   // System status
   const [systemStatus, setSystemStatus] = useState<SystemStatus | null>(null);
@@ -157,6 +191,7 @@ export function ServerMonitor() {
     let unlistenOutput: (() => void) | null = null;
     let unlistenComplete: (() => void) | null = null;
     let unlistenUploadProgress: (() => void) | null = null;
+    let unlistenHostKeyTrusted: (() => void) | null = null;
 
     const setupListeners = async () => {
       const outputUnlisten = await listen<SSHOutputEvent>('ssh:output', (event) => {
@@ -184,15 +219,24 @@ export function ServerMonitor() {
         setReleaseProgress(percent);
       });
 
+      const hostKeyTrustedUnlisten = await listen<HostKeyTrustedEvent>('server:host_key_trusted', () => {
+        if (cancelled) return;
+        const retry = retryAfterTrustRef.current;
+        retryAfterTrustRef.current = null;
+        retry?.();
+      });
+
       // If cleanup already ran while we were awaiting, unlisten immediately
       if (cancelled) {
         outputUnlisten();
         completeUnlisten();
         uploadProgressUnlisten();
+        hostKeyTrustedUnlisten();
       } else {
         unlistenOutput = outputUnlisten;
         unlistenComplete = completeUnlisten;
         unlistenUploadProgress = uploadProgressUnlisten;
+        unlistenHostKeyTrusted = hostKeyTrustedUnlisten;
       }
     };
 
@@ -203,6 +247,7 @@ export function ServerMonitor() {
       unlistenOutput?.();
       unlistenComplete?.();
       unlistenUploadProgress?.();
+      unlistenHostKeyTrusted?.();
     };
   }, []);
 
@@ -226,6 +271,7 @@ export function ServerMonitor() {
       ]);
 
       setServerConfig(config);
+      setKeyPath(config.key_path ?? '');
       setQuickActions(actions);
       setHasCredentials(hasCreds);
       setAtlasProjectPath(settings.atlas_project_path);
@@ -245,8 +291,30 @@ export function ServerMonitor() {
     ]);
   }
 
+  async function updateAuthConfig(update: { auth_method?: SSHAuthMethod; key_path?: string }) {
+    try {
+      setAuthError(null);
+      const config = await invoke<ServerConfig>('update_server_config', { config: update });
+      setServerConfig(config);
+    } catch (err) {
+      setAuthError(String(err));
+    }
+  }
+
+  async function handleGenerateKey() {
+    try {
+      setKeyLoading(true);
+      setAuthError(null);
+      setGeneratedKey(await invoke<SSHPublicKey>('generate_ssh_keypair'));
+    } catch (err) {
+      setAuthError(String(err));
+    } finally {
+      setKeyLoading(false);
+    }
+  }
+
   async function handleLogin() {
-    if (!password) {
+    if (!usesKey && !password) {
       setAuthError('Please enter a password');
       return;
     }
@@ -255,11 +323,23 @@ export function ServerMonitor() {
       setAuthLoading(true);
       setAuthError(null);
 
-      // Test connection
-      const success = await invoke<boolean>('test_ssh_connection', { password });
-
-      if (success) {
-        if (savePassword) {
+      // Test connection; for key auth the password field holds the optional key passphrase
+      const test = await invoke<SshConnectionTest>('test_ssh_connection', { password: password || undefined });
+
+      // The host key dialog asks about the key; trusting it logs in again
+      if (test.trust === 'unknown') {
+        retryAfterTrustRef.current = () => handleLogin();
+        setAuthError('Verify the server\'s host key to continue');
+      } else if (test.trust === 'changed') {
+        setAuthError('Host key changed; connection refused');
+      } else if (test.success) {
+        // Key auth is saved either way so the key is used from now on; only the passphrase is optional
+        if (usesKey) {
+          await invoke('save_ssh_credentials', {
+            keyPassphrase: savePassword && password ? password : undefined,
+          });
+          setHasCredentials(true);
+        } else if (savePassword) {
           await invoke('save_ssh_credentials', { password });
           setHasCredentials(true);
         }
@@ -268,7 +348,7 @@ export function ServerMonitor() {
         // Fetch system status after login
         fetchSystemStatus(password);
       } else {
-        setAuthError('Connection failed. Check your password.');
+        setAuthError(usesKey ? 'Connection failed. Check the SSH key.' : 'Connection failed. Check your password.');
       }
     } catch (err) {
       setAuthError(String(err));
@@ -298,7 +378,8 @@ export function ServerMonitor() {
       });
       setSystemStatus(status);
     } catch (err) {
-      // Silently fail - status is optional
+      // Silently fail - status is optional; a refused host key is retried once it is trusted
+      retryAfterTrustRef.current = () => fetchSystemStatus(pwd);
       console.error('Failed to fetch system status:', err);
     } finally {
       setStatusLoading(false);
@@ -335,7 +416,85 @@ export function ServerMonitor() {
   }
 
   function handleQuickAction(action: QuickAction) {
-    executeCommand(action.command);
+    if (action.variables.length > 0) {
+      setPendingAction(action);
+      setActionVars(Object.fromEntries(action.variables.map((v) => [v.name, v.default ?? ''])));
+      return;
+    }
+    runQuickAction(action, {});
+  }
+
+  function runQuickAction(action: QuickAction, vars: Record<string, string>) {
+    if (action.requires_confirmation && !window.confirm(`Run "${action.label}" on the server?`)) return;
+    setPendingAction(null);
+    if (action.service_action) {
+      runServiceAction(action.service_action.service, action.service_action.action);
+    } else {
+      executeQuickAction(action, vars);
+    }
+  }
+
+  async function executeQuickAction(action: QuickAction, vars: Record<string, string>) {
+    if (isExecuting) return;
+
+    try {
+      setIsExecuting(true);
+      // The server receives the values quoted; this line is only for display
+      const shown = action.command.replace(/\{(\w+)\}/g, (match, name) => vars[name] ?? match);
+      addTerminalLine(`$ ${shown}`, false, true);
+
+      await invoke<CommandResult>('execute_quick_action', {
+        id: action.id,
+        vars,
+        password: hasCredentials ? undefined : password || undefined,
+      });
+    } catch (err) {
+      addTerminalLine(`Error: ${err}`, true);
+      setIsExecuting(false);
+    }
+  }
+
+  async function fetchServices() {
+    try {
+      setServicesLoading(true);
+      const list = await invoke<SystemdService[]>('get_systemd_services', {
+        filter: serviceFilter || undefined,
+        password: hasCredentials ? undefined : password || undefined,
+      });
+      setServices(list);
+    } catch (err) {
+      addTerminalLine(`Error: ${err}`, true);
+    } finally {
+      setServicesLoading(false);
+    }
+  }
+
+  async function runServiceAction(service: string, action: SystemdServiceAction) {
+    try {
+      setIsExecuting(true);
+      addTerminalLine(`$ systemctl ${action} ${service}`, false, true);
+      const result = await invoke<CommandResult>('systemd_service_action', {
+        name: service,
+        action,
+        password: hasCredentials ? undefined : password || undefined,
+      });
+      if (result.output.trim()) addTerminalLine(result.output, false);
+      if (result.error) addTerminalLine(result.error, true);
+      if (result.exit_code !== 0) {
+        addTerminalLine(`Process exited with code ${result.exit_code ?? -1}`, true);
+      }
+      if (services) fetchServices();
+    } catch (err) {
+      addTerminalLine(`Error: ${err}`, true);
+    } finally {
+      setIsExecuting(false);
+    }
+  }
+
+  function formatMemory(bytes?: number | null) {
+    if (bytes == null) return '';
+    if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+    return `${Math.round(bytes / 1024 ** 2)} MB`;
   }
 
   function clearTerminal() {
@@ -582,13 +741,74 @@ export function ServerMonitor() {
                   <h2 className="card-title mb-0">SSH Login</h2>
                 </div>
                 <div className="space-y-3">
+                  <div className="flex gap-1">
+                    {AUTH_METHOD_OPTIONS.map((option) => (
+                      <button
+                        key={option.value}
+                        type="button"
+                        onClick={() => updateAuthConfig({ auth_method: option.value })}
+                        disabled={authLoading}
+                        className={`btn btn-sm flex-1 ${authMethod === option.value ? 'btn-primary' : 'btn-secondary'}`}
+                      >
+                        {option.label}
+                      </button>
+                    ))}
+                  </div>
+                  {authMethod === 'key_file' && (
+                    <input
+                      type="text"
+                      value={keyPath}
+                      onChange={(e) => setKeyPath(e.target.value)}
+                      onBlur={() => keyPath !== (serverConfig?.key_path ?? '') && updateAuthConfig({ key_path: keyPath })}
+                      placeholder="Path to private key, e.g. ~/.ssh/id_ed25519"
+                      className="input"
+                      disabled={authLoading}
+                    />
+                  )}
+                  {authMethod === 'generated_key' && (
+                    <div className="space-y-2">
+                      <button
+                        type="button"
+                        onClick={handleGenerateKey}
+                        disabled={keyLoading || authLoading}
+                        className="btn btn-sm btn-secondary w-full"
+                      >
+                        {keyLoading ? <Loader2 size={14} className="animate-spin" /> : <Key size={14} />}
+                        {generatedKey ? 'Show Public Key' : 'Generate / Show Key'}
+                      </button>
+                      {generatedKey && (
+                        <div className="space-y-1">
+                          <div className="flex items-center justify-between text-xs text-text-muted">
+                            <span className="truncate">{generatedKey.fingerprint}</span>
+                            <button
+                              type="button"
+                              onClick={() => navigator.clipboard.writeText(generatedKey.public_key)}
+                              className="p-1 hover:text-text-primary"
+                              title="Copy public key"
+                            >
+                              <Copy size={14} />
+                            </button>
+                          </div>
+                          <textarea
+                            readOnly
+                            value={generatedKey.public_key}
+                            rows={3}
+                            className="input font-mono text-xs resize-none"
+                          />
+                          <p className="text-xs text-text-muted">
+                            Add this line to ~/.ssh/authorized_keys on the server, then connect.
+                          </p>
+                        </div>
+                      )}
+                    </div>
+                  )}
                   <div className="relative">
                     <input
                       type={showPassword ? 'text' : 'password'}
                       value={password}
                       onChange={(e) => setPassword(e.target.value)}
                       onKeyDown={(e) => e.key === 'Enter' && handleLogin()}
-                      placeholder="Enter SSH password"
+                      placeholder={usesKey ? 'Key passphrase (if any)' : 'Enter SSH password'}
                       className="input pr-10"
                       disabled={authLoading}
                     />
@@ -607,7 +827,7 @@ export function ServerMonitor() {
                       onChange={(e) => setSavePassword(e.target.checked)}
                       className="rounded"
                     />
-                    Save password
+                    {usesKey ? 'Save passphrase' : 'Save password'}
                   </label>
                   {authError && (
                     <div className="text-sm text-red-400 flex items-center gap-1">
@@ -617,7 +837,7 @@ export function ServerMonitor() {
                   )}
                   <button
                     onClick={handleLogin}
-                    disabled={authLoading || !password}
+                    disabled={authLoading || (!usesKey && !password)}
                     className="btn btn-primary w-full"
                   >
                     {authLoading ? (
@@ -867,6 +1087,103 @@ export function ServerMonitor() {
               </div>
             )}
 
+            {/* Metrics History */}
+            {hasCredentials && serverConfig && (
+              <MetricsHistoryCard config={serverConfig} onConfigChange={setServerConfig} />
+            )}
+
+            {/* SSH Tunnels */}
+            {hasCredentials && <TunnelsCard />}
+
+            {/* systemd Services */}
+            {hasCredentials && (
+              <div className="card">
+                <div className="flex items-center justify-between mb-3">
+                  <div className="flex items-center gap-2">
+                    <Server size={16} className="text-accent-primary" />
+                    <h3 className="text-sm font-medium text-text-secondary">systemd Services</h3>
+                  </div>
+                  <button
+                    onClick={fetchServices}
+                    disabled={servicesLoading}
+                    className="btn btn-sm btn-ghost"
+                    title="Load services"
+                  >
+                    <RefreshCw size={14} className={servicesLoading ? 'animate-spin' : ''} />
+                  </button>
+                </div>
+                <input
+                  type="text"
+                  value={serviceFilter}
+                  onChange={(e) => setServiceFilter(e.target.value)}
+                  onKeyDown={(e) => e.key === 'Enter' && fetchServices()}
+                  placeholder="Filter, e.g. jellyfin"
+                  className="input text-sm mb-2"
+                />
+                {services && (
+                  <div className="space-y-1 max-h-64 overflow-y-auto">
+                    {services.length === 0 && (
+                      <p className="text-xs text-text-muted">No matching services</p>
+                    )}
+                    {services.map((service) => (
+                      <div
+                        key={service.name}
+                        className="flex items-center gap-2 px-2 py-1.5 rounded-lg glass-subtle"
+                        title={service.description}
+                      >
+                        <Circle
+                          size={8}
+                          className={
+                            service.active_state === 'active'
+                              ? 'fill-green-400 text-green-400'
+                              : service.active_state === 'failed'
+                                ? 'fill-red-400 text-red-400'
+                                : 'text-text-muted'
+                          }
+                        />
+                        <div className="flex-1 min-w-0">
+                          <div className="text-sm text-text-primary truncate">
+                            {service.name.replace(/\.service$/, '')}
+                          </div>
+                          <div className="text-xs text-text-muted">
+                            {service.active_state} ({service.sub_state})
+                            {service.memory_bytes != null && ` · ${formatMemory(service.memory_bytes)}`}
+                          </div>
+                        </div>
+                        <button
+                          onClick={() => runServiceAction(service.name, 'restart')}
+                          disabled={isExecuting}
+                          className="p-1 text-text-muted hover:text-text-primary disabled:opacity-50"
+                          title="Restart"
+                        >
+                          <RotateCcw size={12} />
+                        </button>
+                        {service.active_state === 'active' ? (
+                          <button
+                            onClick={() => runServiceAction(service.name, 'stop')}
+                            disabled={isExecuting}
+                            className="p-1 text-text-muted hover:text-red-400 disabled:opacity-50"
+                            title="Stop"
+                          >
+                            <Square size={12} />
+                          </button>
+                        ) : (
+                          <button
+                            onClick={() => runServiceAction(service.name, 'start')}
+                            disabled={isExecuting}
+                            className="p-1 text-text-muted hover:text-green-400 disabled:opacity-50"
+                            title="Start"
+                          >
+                            <Play size={12} />
+                          </button>
+                        )}
+                      </div>
+                    ))}
+                  </div>
+                )}
+              </div>
+            )}
+
             {/* Quick Actions */}
             {categories.map((cat) => {
               const actions = getActionsByCategory(cat.id);
@@ -883,16 +1200,55 @@ export function ServerMonitor() {
                     {actions.map((action) => {
                       const ActionIcon = iconMap[action.icon] || Terminal;
                       return (
-                        <button
-                          key={action.id}
-                          onClick={() => handleQuickAction(action)}
-                          disabled={isExecuting || (!hasCredentials && !password)}
-                          className="w-full flex items-center gap-2 px-3 py-2 rounded-lg glass-subtle hover:bg-white/10 transition-colors text-left disabled:opacity-50 disabled:cursor-not-allowed"
-                          title={action.description}
-                        >
-                          <ActionIcon size={14} className="text-text-muted" />
-                          <span className="text-sm text-text-primary">{action.label}</span>
-                        </button>
+                        <div key={action.id}>
+                          <button
+                            onClick={() => handleQuickAction(action)}
+                            disabled={isExecuting || (!hasCredentials && !password)}
+                            className="w-full flex items-center gap-2 px-3 py-2 rounded-lg glass-subtle hover:bg-white/10 transition-colors text-left disabled:opacity-50 disabled:cursor-not-allowed"
+                            title={action.description}
+                          >
+                            <ActionIcon size={14} className="text-text-muted" />
+                            <span className="text-sm text-text-primary">{action.label}</span>
+                          </button>
+                          {pendingAction?.id === action.id && (
+                            <form
+                              onSubmit={(e) => {
+                                e.preventDefault();
+                                runQuickAction(action, actionVars);
+                              }}
+                              className="mt-2 space-y-2 px-1"
+                            >
+                              {action.variables.map((variable) => (
+                                <input
+                                  key={variable.name}
+                                  type="text"
+                                  value={actionVars[variable.name] ?? ''}
+                                  onChange={(e) =>
+                                    setActionVars((prev) => ({ ...prev, [variable.name]: e.target.value }))
+                                  }
+                                  placeholder={variable.label ?? variable.name}
+                                  className="input w-full text-sm"
+                                />
+                              ))}
+                              <div className="flex gap-2">
+                                <button
+                                  type="submit"
+                                  disabled={action.variables.some((v) => !actionVars[v.name]?.trim())}
+                                  className="btn btn-primary text-sm flex-1"
+                                >
+                                  Run
+                                </button>
+                                <button
+                                  type="button"
+                                  onClick={() => setPendingAction(null)}
+                                  className="btn btn-secondary text-sm"
+                                >
+                                  Cancel
+                                </button>
+                              </div>
+                            </form>
+                          )}
+                        </div>
                       );
                     })}
                   </div>
@@ -1,6 +1,26 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { Settings as SettingsType, UpdateSettingsParams } from '../types';
+import { listen } from '@tauri-apps/api/event';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import type {
+  DataDirectoryInfo,
+  DataFeature,
+  CurrentPresence,
+  DataMigrationProgress,
+  DiscordConnectionStatus,
+  PresenceActivity,
+  PresenceHiddenGameDisplay,
+  PresenceButton,
+  PresencePlaceholder,
+  RollbackInfo,
+  Settings as SettingsType,
+  SettingsImportReport,
+  SavedAvatar,
+  SettingsFieldError,
+  SettingsProfile,
+  UpdateChannel,
+  UpdateSettingsParams,
+} from '../types';
 import type { GachaAccount, GachaGame } from '../types/gacha';
 import { getGameDisplayName } from '../types/gacha';
 import {
@@ -26,11 +46,19 @@ import {
   Camera,
   X,
   Heart,
+  Upload,
+  Archive,
+  Layers,
+  Trash2,
+  HardDrive,
+  RotateCcw,
+  RefreshCw,
 } from 'lucide-react';
 import { DraggableNavList } from '../components/DraggableNavList';
 import { CustomSelect } from '../components/ui/CustomSelect';
 import { ImageCropModal } from '../components/ui/ImageCropModal';
 import { useNavigationSettingsContext } from '../contexts';
+import { useDataReset } from '../hooks';
 
 const QUALITY_OPTIONS = [
   { value: 'best', label: 'Best Quality' },
@@ -40,6 +68,28 @@ const QUALITY_OPTIONS = [
   { value: 'audio_only', label: 'Audio Only' },
 ];
 
+function formatBytes(bytes: number): string {
+  if (bytes === 0) return '0 B';
+  const BYTES_PER_UNIT = 1024;
+  const sizes = ['B', 'KB', 'MB', 'GB', 'TB'];
+  const unitIndex = Math.floor(Math.log(bytes) / Math.log(BYTES_PER_UNIT));
+  return parseFloat((bytes / Math.pow(BYTES_PER_UNIT, unitIndex)).toFixed(1)) + ' ' + sizes[unitIndex];
+}
+
+const UPDATE_CHANNEL_OPTIONS: { value: UpdateChannel; label: string }[] = [
+  { value: 'stable', label: 'Stable' },
+  { value: 'beta', label: 'Beta' },
+];
+
+const DATA_FEATURE_OPTIONS: { value: DataFeature; label: string }[] = [
+  { value: 'downloads', label: 'Download History' },
+  { value: 'gaming_sessions', label: 'Gaming Sessions' },
+  { value: 'game_library', label: 'Game Library' },
+  { value: 'task_monitor_profiles', label: 'Task Monitor Profiles' },
+  { value: 'valorant_store', label: 'Valorant Store History' },
+  { value: 'friends', label: 'Friends Data' },
+];
+
 interface DownloadPathValidation {
   valid: boolean;
   resolved_path: string;
@@ -54,12 +104,14 @@ export function Settings() {
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [message, setMessage] = useState<{ type: 'success' | 'error'; text: string } | null>(null);
+  const [fieldErrors, setFieldErrors] = useState<Record<string, string>>({});
 
   // Form state
   const [downloadPath, setDownloadPath] = useState('');
   const [defaultQuality, setDefaultQuality] = useState('best');
   const [maxConcurrentDownloads, setMaxConcurrentDownloads] = useState(3);
   const [maxConcurrentMLJobs, setMaxConcurrentMLJobs] = useState(1);
+  const [resumeMLJobsOnStartup, setResumeMLJobsOnStartup] = useState(false);
   const [atlasProjectPath, setAtlasProjectPath] = useState('');
   const [remoteUpdatePath, setRemoteUpdatePath] = useState('');
   const [updateUrlBase, setUpdateUrlBase] = useState('');
@@ -72,10 +124,26 @@ export function Settings() {
   const [discordEnabled, setDiscordEnabled] = useState(false);
   const [discordConnected, setDiscordConnected] = useState(false);
   const [discordConnecting, setDiscordConnecting] = useState(false);
+  const [discordStatus, setDiscordStatus] = useState<DiscordConnectionStatus | null>(null);
+  const [presenceDetailsTemplate, setPresenceDetailsTemplate] = useState('');
+  const [presenceStateTemplate, setPresenceStateTemplate] = useState('');
+  const [presencePlaceholders, setPresencePlaceholders] = useState<PresencePlaceholder[]>([]);
+  const [presenceButtons, setPresenceButtons] = useState<PresenceButton[]>([]);
+  const [presencePreview, setPresencePreview] = useState<PresenceActivity | null>(null);
+  const [presenceAfkEnabled, setPresenceAfkEnabled] = useState(true);
+  const [presenceAfkMinutes, setPresenceAfkMinutes] = useState(10);
+  const [friendsAfkAway, setFriendsAfkAway] = useState(false);
+  const [currentPresence, setCurrentPresence] = useState<CurrentPresence | null>(null);
+  const [customDetails, setCustomDetails] = useState('');
+  const [customState, setCustomState] = useState('');
+  const [customMinutes, setCustomMinutes] = useState(60);
+  const [presenceBlocklist, setPresenceBlocklist] = useState('');
+  const [presenceHiddenGameDisplay, setPresenceHiddenGameDisplay] = useState<PresenceHiddenGameDisplay>('generic');
 
   // Startup & Tray state
   const [runOnStartup, setRunOnStartup] = useState(false);
   const [closeToTray, setCloseToTray] = useState(false);
+  const [valorantStoreNotifications, setValorantStoreNotifications] = useState(false);
 
   // Task Monitor state
   const [autoRestoreEnabled, setAutoRestoreEnabled] = useState(false);
@@ -95,6 +163,30 @@ export function Settings() {
   const [cropImageSrc, setCropImageSrc] = useState<string | null>(null);
   const [showCropModal, setShowCropModal] = useState(false);
 
+  // Backup state
+  const [backupIncludeSensitive, setBackupIncludeSensitive] = useState(false);
+  const [backupBusy, setBackupBusy] = useState(false);
+
+  // Data directory state
+  const [dataDirectory, setDataDirectory] = useState<DataDirectoryInfo | null>(null);
+  const [migrationProgress, setMigrationProgress] = useState<DataMigrationProgress | null>(null);
+  const [migrating, setMigrating] = useState(false);
+
+  // Reset state
+  const [resetFeature, setResetFeature] = useState<DataFeature>('downloads');
+  const [resetting, setResetting] = useState(false);
+
+  // Profiles state
+  const [profiles, setProfiles] = useState<SettingsProfile[]>([]);
+  const [profileName, setProfileName] = useState('');
+  const [profileAutoSwitch, setProfileAutoSwitch] = useState(false);
+  const [updateChannel, setUpdateChannel] = useState<UpdateChannel>('stable');
+  const [autoDownloadUpdates, setAutoDownloadUpdates] = useState(false);
+  const [rollbackInfo, setRollbackInfo] = useState<RollbackInfo | null>(null);
+  const [rollingBack, setRollingBack] = useState(false);
+  const [updateCheckIntervalHours, setUpdateCheckIntervalHours] = useState(6);
+  const [profileBusy, setProfileBusy] = useState(false);
+
   const {
     developerModeEnabled,
     orderedItems,
@@ -106,6 +198,31 @@ export function Settings() {
 
   useEffect(() => {
     fetchSettings();
+    invoke<DataDirectoryInfo>('get_data_directory_info')
+      .then(setDataDirectory)
+      .catch((err) => console.warn('Failed to read data directory:', err));
+  }, []);
+
+  useDataReset('settings', fetchSettings);
+
+  // The watchdog reconnects after Discord restarts
+  useEffect(() => {
+    const unlisten = listen<DiscordConnectionStatus>('discord:connection_changed', (event) => {
+      setDiscordStatus(event.payload);
+      setDiscordConnected(event.payload.connected);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<DataMigrationProgress>('data_directory:migration_progress', (event) => {
+      setMigrationProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   // Validate download path with debounce
@@ -145,24 +262,48 @@ export function Settings() {
       setDefaultQuality(result.default_quality);
       setMaxConcurrentDownloads(result.max_concurrent_downloads);
       setMaxConcurrentMLJobs(result.max_concurrent_ml_jobs);
+      setResumeMLJobsOnStartup(result.resume_ml_jobs_on_startup);
       setAtlasProjectPath(result.atlas_project_path || '');
       setRemoteUpdatePath(result.remote_update_path || '');
       setUpdateUrlBase(result.update_url_base || '');
       setDiscordEnabled(result.discord_rich_presence_enabled);
+      setPresenceDetailsTemplate(result.discord_presence_details_template);
+      setPresenceStateTemplate(result.discord_presence_state_template);
+      setPresenceButtons(result.discord_presence_buttons);
+      setPresenceAfkEnabled(result.discord_presence_afk_enabled);
+      setPresenceAfkMinutes(result.discord_presence_afk_minutes);
+      setFriendsAfkAway(result.friends_presence_afk_away);
+      setPresenceBlocklist(result.presence_blocklist.join('\n'));
+      setPresenceHiddenGameDisplay(result.presence_hidden_game_display);
       setRunOnStartup(result.run_on_startup);
       setCloseToTray(result.close_to_tray);
+      setValorantStoreNotifications(result.valorant_store_notifications);
       setAutoRestoreEnabled(result.auto_restore_enabled);
       setSelectedGachaAccounts(result.selected_gacha_accounts || {});
       setUserDisplayName(result.user_display_name || '');
       setPartnerWidgetEnabled(result.partner_widget_enabled);
+      setProfileAutoSwitch(result.settings_profile_auto_switch);
+      setUpdateChannel(result.update_channel);
+      setAutoDownloadUpdates(result.auto_download_updates);
+      setUpdateCheckIntervalHours(result.update_check_interval_hours);
 
-      // Load avatar as base64 (bypasses asset protocol issues)
-      const avatarBase64 = await invoke<string | null>('get_user_avatar_base64');
+      // Load the avatar thumbnail as base64 (bypasses asset protocol issues)
+      const avatarBase64 = await invoke<string | null>('get_user_avatar_thumbnail_base64');
       setUserAvatarBase64(avatarBase64);
 
       // Check Discord connection status
-      const connected = await invoke<boolean>('is_discord_connected');
-      setDiscordConnected(connected);
+      const status = await invoke<DiscordConnectionStatus>('is_discord_connected');
+      setDiscordStatus(status);
+      setDiscordConnected(status.connected);
+      setPresencePlaceholders(await invoke<PresencePlaceholder[]>('get_presence_template_placeholders'));
+      setCurrentPresence(await invoke<CurrentPresence>('get_current_presence'));
+
+      // Load the update rollback slot
+      setRollbackInfo(await invoke<RollbackInfo>('get_rollback_info'));
+
+      // Load settings profiles
+      const savedProfiles = await invoke<SettingsProfile[]>('list_settings_profiles');
+      setProfiles(savedProfiles);
 
       // Load gacha accounts
       const accounts = await invoke<GachaAccount[]>('get_gacha_accounts');
@@ -184,11 +325,20 @@ export function Settings() {
         default_quality: defaultQuality,
         max_concurrent_downloads: maxConcurrentDownloads,
         max_concurrent_ml_jobs: maxConcurrentMLJobs,
+        resume_ml_jobs_on_startup: resumeMLJobsOnStartup,
         atlas_project_path: atlasProjectPath,
         remote_update_path: remoteUpdatePath,
         update_url_base: updateUrlBase,
         discord_rich_presence_enabled: discordEnabled,
       };
+
+      const errors = await invoke<SettingsFieldError[]>('validate_settings', { settings: params });
+      setFieldErrors(Object.fromEntries(errors.map((e) => [e.field, e.message])));
+      if (errors.length > 0) {
+        setMessage({ type: 'error', text: 'Some settings are invalid, nothing was saved' });
+        return;
+      }
+
       await invoke('update_settings', { settings: params });
       setMessage({ type: 'success', text: 'Settings saved successfully!' });
       fetchSettings();
@@ -234,6 +384,123 @@ export function Settings() {
     });
   }
 
+  // Applied on the next presence update, without reconnecting
+  async function handlePresenceTemplateBlur(
+    field: 'discord_presence_details_template' | 'discord_presence_state_template',
+    value: string
+  ) {
+    try {
+      const saved = await invoke<SettingsType>('update_settings', { settings: { [field]: value } });
+      setPresenceDetailsTemplate(saved.discord_presence_details_template);
+      setPresenceStateTemplate(saved.discord_presence_state_template);
+      setFieldErrors((prev) => {
+        const next = { ...prev };
+        delete next[field];
+        return next;
+      });
+    } catch (err) {
+      setFieldErrors((prev) => ({ ...prev, [field]: String(err) }));
+    }
+  }
+
+  function handlePresenceButtonChange(index: number, field: keyof PresenceButton, value: string) {
+    setPresenceButtons((prev) => {
+      const next = [...prev];
+      while (next.length <= index) next.push({ label: '', url: '' });
+      next[index] = { ...next[index], [field]: value };
+      return next;
+    });
+  }
+
+  async function handleSavePresenceButtons() {
+    // Rows left blank are dropped
+    const buttons = presenceButtons.filter((b) => b.label.trim() || b.url.trim());
+    try {
+      const saved = await invoke<SettingsType>('update_settings', { settings: { discord_presence_buttons: buttons } });
+      setPresenceButtons(saved.discord_presence_buttons);
+      setFieldErrors((prev) => {
+        const next = { ...prev };
+        delete next.discord_presence_buttons;
+        return next;
+      });
+      setMessage({ type: 'success', text: 'Presence buttons saved' });
+    } catch (err) {
+      setFieldErrors((prev) => ({ ...prev, discord_presence_buttons: String(err) }));
+    }
+  }
+
+  async function handlePreviewPresence() {
+    try {
+      setPresencePreview(await invoke<PresenceActivity>('preview_discord_presence'));
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to preview presence: ${err}` });
+    }
+  }
+
+  // Picked up by a running gaming session within a few seconds
+  async function handlePresenceAfkChange(
+    settings: Pick<UpdateSettingsParams, 'discord_presence_afk_enabled' | 'discord_presence_afk_minutes' | 'friends_presence_afk_away'>
+  ) {
+    try {
+      const saved = await invoke<SettingsType>('update_settings', { settings });
+      setPresenceAfkEnabled(saved.discord_presence_afk_enabled);
+      setPresenceAfkMinutes(saved.discord_presence_afk_minutes);
+      setFriendsAfkAway(saved.friends_presence_afk_away);
+      setFieldErrors((prev) => {
+        const next = { ...prev };
+        delete next.discord_presence_afk_minutes;
+        return next;
+      });
+    } catch (err) {
+      setFieldErrors((prev) => ({ ...prev, discord_presence_afk_minutes: String(err) }));
+    }
+  }
+
+  // One game per line; blank lines are dropped when saving
+  async function handlePresencePrivacyChange(
+    settings: Pick<UpdateSettingsParams, 'presence_blocklist' | 'presence_hidden_game_display'>
+  ) {
+    try {
+      const saved = await invoke<SettingsType>('update_settings', { settings });
+      setPresenceBlocklist(saved.presence_blocklist.join('\n'));
+      setPresenceHiddenGameDisplay(saved.presence_hidden_game_display);
+      setFieldErrors((prev) => {
+        const next = { ...prev };
+        delete next.presence_blocklist;
+        return next;
+      });
+    } catch (err) {
+      setFieldErrors((prev) => ({ ...prev, presence_blocklist: String(err) }));
+    }
+  }
+
+  async function handleSetCustomPresence() {
+    try {
+      setCurrentPresence(
+        await invoke<CurrentPresence>('set_custom_presence', {
+          details: customDetails || null,
+          state: customState || null,
+          durationMinutes: customMinutes,
+        })
+      );
+      setFieldErrors((prev) => {
+        const next = { ...prev };
+        delete next.custom_presence;
+        return next;
+      });
+    } catch (err) {
+      setFieldErrors((prev) => ({ ...prev, custom_presence: String(err) }));
+    }
+  }
+
+  async function handleClearCustomPresence() {
+    try {
+      setCurrentPresence(await invoke<CurrentPresence>('clear_custom_presence'));
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to clear custom presence: ${err}` });
+    }
+  }
+
   async function handleRunOnStartupToggle() {
     const newEnabled = !runOnStartup;
     setRunOnStartup(newEnabled);
@@ -256,6 +523,12 @@ export function Settings() {
     await invoke('update_settings', { settings: { close_to_tray: newEnabled } });
   }
 
+  async function handleValorantStoreNotificationsToggle() {
+    const newEnabled = !valorantStoreNotifications;
+    setValorantStoreNotifications(newEnabled);
+    await invoke('update_settings', { settings: { valorant_store_notifications: newEnabled } });
+  }
+
   async function handleAutoRestoreToggle() {
     const newEnabled = !autoRestoreEnabled;
     setAutoRestoreEnabled(newEnabled);
@@ -325,14 +598,11 @@ export function Settings() {
       // Extract base64 data (remove data URL prefix)
       const base64Data = croppedBase64.split(',')[1];
 
-      // Save to backend (cropped images are always JPEG)
-      await invoke<string>('save_user_avatar', {
-        imageData: base64Data,
-        fileExtension: 'jpg',
-      });
+      // Save to backend, which stores a resized PNG and a thumbnail
+      await invoke<SavedAvatar>('save_user_avatar', { imageData: base64Data });
 
-      // Load the saved avatar as base64
-      const avatarBase64 = await invoke<string | null>('get_user_avatar_base64');
+      // Load the saved avatar's thumbnail as base64
+      const avatarBase64 = await invoke<string | null>('get_user_avatar_thumbnail_base64');
       setUserAvatarBase64(avatarBase64);
       setAvatarPreview(null); // Clear preview, use saved base64
 
@@ -397,6 +667,198 @@ export function Settings() {
     await invoke('update_settings', { settings: { partner_widget_enabled: newEnabled } });
   }
 
+  async function handleExportSettings() {
+    const path = await save({
+      defaultPath: `atlas_settings_${new Date().toISOString().slice(0, 10)}.json`,
+      filters: [{ name: 'JSON', extensions: ['json'] }],
+    });
+    if (!path) return;
+
+    setBackupBusy(true);
+    try {
+      const sections = await invoke<string[]>('export_settings', {
+        path,
+        includeSensitive: backupIncludeSensitive,
+      });
+      setMessage({ type: 'success', text: `Exported ${sections.join(', ')}` });
+    } catch (err) {
+      setMessage({ type: 'error', text: `Export failed: ${err}` });
+    } finally {
+      setBackupBusy(false);
+    }
+  }
+
+  async function handleImportSettings() {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: 'JSON', extensions: ['json'] }],
+    });
+    if (!path || Array.isArray(path)) return;
+
+    setBackupBusy(true);
+    try {
+      const report = await invoke<SettingsImportReport>('import_settings', {
+        path,
+        includeSensitive: backupIncludeSensitive,
+      });
+      const skipped = [
+        ...report.skipped.map((s) => `${s.section} (${s.reason})`),
+        ...report.skipped_fields.map((f) => `setting ${f.field} (${f.reason})`),
+      ].join(', ');
+      setMessage({
+        type: skipped ? 'error' : 'success',
+        text: `Imported ${report.applied.join(', ') || 'nothing'}${skipped ? `. Skipped ${skipped}` : ''}`,
+      });
+      fetchSettings();
+    } catch (err) {
+      setMessage({ type: 'error', text: `Import failed: ${err}` });
+    } finally {
+      setBackupBusy(false);
+    }
+  }
+
+  async function handleMoveDataDirectory(resumePath?: string) {
+    let target = resumePath;
+    if (!target) {
+      const picked = await open({ directory: true, multiple: false });
+      if (!picked || Array.isArray(picked)) return;
+      target = picked;
+    }
+    if (!window.confirm(`Move all Atlas data to ${target}? Atlas restarts when the move is done.`)) return;
+
+    setMigrating(true);
+    setMigrationProgress(null);
+    try {
+      // Atlas restarts from the new folder on success, so this only returns on failure
+      await invoke('migrate_data_directory', { newPath: target });
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to move data: ${err}` });
+      setDataDirectory(await invoke<DataDirectoryInfo>('get_data_directory_info'));
+    } finally {
+      setMigrating(false);
+    }
+  }
+
+  async function handleResetSettings() {
+    if (!window.confirm('Reset all settings to their defaults? A backup of the current settings is kept.')) return;
+
+    setResetting(true);
+    try {
+      await invoke<SettingsType>('reset_settings');
+      setMessage({ type: 'success', text: 'Settings reset to defaults' });
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to reset settings: ${err}` });
+    } finally {
+      setResetting(false);
+    }
+  }
+
+  async function handleResetFeature() {
+    const label = DATA_FEATURE_OPTIONS.find((o) => o.value === resetFeature)?.label ?? resetFeature;
+    if (!window.confirm(`Reset ${label}? This cannot be undone.`)) return;
+
+    setResetting(true);
+    try {
+      await invoke('reset_feature_data', { feature: resetFeature });
+      setMessage({ type: 'success', text: `${label} reset` });
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to reset ${label}: ${err}` });
+    } finally {
+      setResetting(false);
+    }
+  }
+
+  async function handleRollback() {
+    if (!rollbackInfo?.version) return;
+    if (!window.confirm(`Reinstall version ${rollbackInfo.version}? Atlas closes while it installs.`)) return;
+
+    setRollingBack(true);
+    try {
+      await invoke('rollback_update');
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to roll back: ${err}` });
+      setRollingBack(false);
+    }
+  }
+
+  async function handleSaveProfile() {
+    const name = profileName.trim();
+    if (!name) return;
+
+    setProfileBusy(true);
+    try {
+      await invoke<SettingsProfile>('save_settings_profile', { name });
+      setProfiles(await invoke<SettingsProfile[]>('list_settings_profiles'));
+      setProfileName('');
+      setMessage({ type: 'success', text: `Saved profile ${name}` });
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to save profile: ${err}` });
+    } finally {
+      setProfileBusy(false);
+    }
+  }
+
+  async function handleApplyProfile(name: string) {
+    setProfileBusy(true);
+    try {
+      await invoke<SettingsType>('apply_settings_profile', { name });
+      setMessage({ type: 'success', text: `Applied profile ${name}` });
+      fetchSettings();
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to apply profile: ${err}` });
+    } finally {
+      setProfileBusy(false);
+    }
+  }
+
+  async function handleDeleteProfile(name: string) {
+    setProfileBusy(true);
+    try {
+      await invoke('delete_settings_profile', { name });
+      setProfiles((prev) => prev.filter((p) => p.name !== name));
+    } catch (err) {
+      setMessage({ type: 'error', text: `Failed to delete profile: ${err}` });
+    } finally {
+      setProfileBusy(false);
+    }
+  }
+
+  async function handleProfileAutoSwitchToggle() {
+    const newEnabled = !profileAutoSwitch;
+    setProfileAutoSwitch(newEnabled);
+    await invoke('update_settings', { settings: { settings_profile_auto_switch: newEnabled } });
+  }
+
+  async function handleUpdateChannelChange(channel: UpdateChannel) {
+    const previous = updateChannel;
+    setUpdateChannel(channel);
+    try {
+      await invoke('update_settings', { settings: { update_channel: channel } });
+    } catch (err) {
+      setUpdateChannel(previous);
+      setMessage({ type: 'error', text: `Failed to change update channel: ${err}` });
+    }
+  }
+
+  async function handleAutoDownloadUpdatesToggle() {
+    const newEnabled = !autoDownloadUpdates;
+    setAutoDownloadUpdates(newEnabled);
+    await invoke('update_settings', { settings: { auto_download_updates: newEnabled } });
+  }
+
+  async function handleUpdateCheckIntervalBlur() {
+    try {
+      await invoke('update_settings', { settings: { update_check_interval_hours: updateCheckIntervalHours } });
+      setFieldErrors((prev) => {
+        const next = { ...prev };
+        delete next.update_check_interval_hours;
+        return next;
+      });
+    } catch (err) {
+      setFieldErrors((prev) => ({ ...prev, update_check_interval_hours: String(err) }));
+    }
+  }
+
   // Group gacha accounts by game
   const gachaAccountsByGame = gachaAccounts.reduce((acc, account) => {
     if (!acc[account.game]) {
@@ -676,6 +1138,9 @@ export function Settings() {
                       disabled={saving}
                       className="input"
                     />
+                    {fieldErrors.max_concurrent_downloads && (
+                      <p className="text-xs text-red-400 mt-1">{fieldErrors.max_concurrent_downloads}</p>
+                    )}
                   </div>
                 </div>
               </div>
@@ -705,8 +1170,23 @@ export function Settings() {
                   disabled={saving}
                   className="input max-w-xs"
                 />
+                {fieldErrors.max_concurrent_ml_jobs && (
+                  <p className="text-xs text-red-400 mt-1">{fieldErrors.max_concurrent_ml_jobs}</p>
+                )}
                 <p className="text-xs text-text-muted mt-1">
-                  ML jobs are resource-intensive. Keep this low for stability.
+                  ML jobs are resource-intensive. Keep this low for stability; further jobs wait in the queue.
+                </p>
+                <label className="flex items-center gap-2 text-sm text-text-secondary mt-4">
+                  <input
+                    type="checkbox"
+                    checked={resumeMLJobsOnStartup}
+                    onChange={(e) => setResumeMLJobsOnStartup(e.target.checked)}
+                    disabled={saving}
+                  />
+                  Resume queued jobs when Atlas starts
+                </label>
+                <p className="text-xs text-text-muted mt-1">
+                  Jobs interrupted by closing Atlas go back in the queue. When off, the queue waits until the next job is submitted.
                 </p>
               </div>
             </div>
@@ -731,6 +1211,32 @@ export function Settings() {
                 </div>
               )}
             </div>
+
+            {/* Store Reset Notifications Toggle */}
+            <div className="flex items-center justify-between mt-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary">
+                  Store Reset Notifications
+                </label>
+                <p className="text-xs text-text-muted mt-0.5">
+                  Check the store when it resets and notify with the new skins
+                </p>
+              </div>
+              <button
+                type="button"
+                onClick={handleValorantStoreNotificationsToggle}
+                disabled={saving}
+                className={`
+                  p-1 rounded-lg transition-colors
+                  ${valorantStoreNotifications
+                    ? 'text-red-400 hover:text-red-300'
+                    : 'text-text-muted hover:text-text-secondary'
+                  }
+                `}
+              >
+                {valorantStoreNotifications ? <ToggleRight size={32} /> : <ToggleLeft size={32} />}
+              </button>
+            </div>
           </div>
 
           {/* Discord Integration */}
@@ -757,6 +1263,11 @@ export function Settings() {
                 {discordEnabled && discordConnected && (
                   <span className="text-xs text-green-400">Connected</span>
                 )}
+                {discordEnabled && !discordConnected && discordStatus?.retry_in_seconds != null && (
+                  <span className="text-xs text-yellow-400" title={discordStatus.last_error ?? undefined}>
+                    Discord not reachable, retrying in {discordStatus.retry_in_seconds}s
+                  </span>
+                )}
                 <button
                   type="button"
                   onClick={handleDiscordToggle}
@@ -773,6 +1284,235 @@ export function Settings() {
                 </button>
               </div>
             </div>
+
+            {/* Presence text templates */}
+            <div className="mt-4 grid grid-cols-1 md:grid-cols-2 gap-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary mb-2">
+                  Details Line
+                </label>
+                <input
+                  type="text"
+                  value={presenceDetailsTemplate}
+                  onChange={(e) => setPresenceDetailsTemplate(e.target.value)}
+                  onBlur={() => handlePresenceTemplateBlur('discord_presence_details_template', presenceDetailsTemplate)}
+                  placeholder="Playing {game}"
+                  className="input w-full"
+                />
+                {fieldErrors.discord_presence_details_template && (
+                  <p className="text-xs text-red-400 mt-1">{fieldErrors.discord_presence_details_template}</p>
+                )}
+              </div>
+              <div>
+                <label className="block text-sm font-medium text-text-secondary mb-2">
+                  State Line
+                </label>
+                <input
+                  type="text"
+                  value={presenceStateTemplate}
+                  onChange={(e) => setPresenceStateTemplate(e.target.value)}
+                  onBlur={() => handlePresenceTemplateBlur('discord_presence_state_template', presenceStateTemplate)}
+                  placeholder="{bottleneck}"
+                  className="input w-full"
+                />
+                {fieldErrors.discord_presence_state_template && (
+                  <p className="text-xs text-red-400 mt-1">{fieldErrors.discord_presence_state_template}</p>
+                )}
+              </div>
+            </div>
+            {presencePlaceholders.length > 0 && (
+              <p className="text-xs text-text-muted mt-2">
+                Shown while gaming. Available:{' '}
+                {presencePlaceholders.map((p, i) => (
+                  <span key={p.placeholder} title={`${p.description} (e.g. ${p.example})`}>
+                    {i > 0 && ', '}
+                    <code>{p.placeholder}</code>
+                  </span>
+                ))}
+              </p>
+            )}
+
+            {/* AFK while idle in a game */}
+            <div className="mt-4 flex flex-wrap items-center gap-4">
+              <label className="flex items-center gap-2 text-sm text-text-secondary">
+                <input
+                  type="checkbox"
+                  checked={presenceAfkEnabled}
+                  onChange={(e) => handlePresenceAfkChange({ discord_presence_afk_enabled: e.target.checked })}
+                />
+                Show "AFK in {'{game}'}" after
+              </label>
+              <input
+                type="number"
+                min={1}
+                max={120}
+                value={presenceAfkMinutes}
+                onChange={(e) => setPresenceAfkMinutes(Number(e.target.value))}
+                onBlur={() => handlePresenceAfkChange({ discord_presence_afk_minutes: presenceAfkMinutes })}
+                disabled={!presenceAfkEnabled && !friendsAfkAway}
+                className="input w-20"
+              />
+              <span className="text-sm text-text-secondary">idle minutes</span>
+              <label className="flex items-center gap-2 text-sm text-text-secondary">
+                <input
+                  type="checkbox"
+                  checked={friendsAfkAway}
+                  onChange={(e) => handlePresenceAfkChange({ friends_presence_afk_away: e.target.checked })}
+                />
+                Also set friends status to Away
+              </label>
+            </div>
+            {fieldErrors.discord_presence_afk_minutes && (
+              <p className="text-xs text-red-400 mt-1">{fieldErrors.discord_presence_afk_minutes}</p>
+            )}
+            <p className="text-xs text-text-muted mt-1">
+              Idle means the game barely using the CPU and GPU with no keyboard or mouse input, so controller play never counts as idle
+            </p>
+
+            {/* Games kept out of the presence */}
+            <div className="mt-4 grid grid-cols-1 md:grid-cols-2 gap-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary mb-2">
+                  Hidden Games
+                </label>
+                <textarea
+                  value={presenceBlocklist}
+                  onChange={(e) => setPresenceBlocklist(e.target.value)}
+                  onBlur={() => handlePresencePrivacyChange({ presence_blocklist: presenceBlocklist.split('\n') })}
+                  placeholder={'One game or process name per line'}
+                  rows={3}
+                  className="input w-full"
+                />
+                {fieldErrors.presence_blocklist && (
+                  <p className="text-xs text-red-400 mt-1">{fieldErrors.presence_blocklist}</p>
+                )}
+              </div>
+              <div>
+                <label className="block text-sm font-medium text-text-secondary mb-2">
+                  While a Hidden Game Runs
+                </label>
+                <select
+                  value={presenceHiddenGameDisplay}
+                  onChange={(e) =>
+                    handlePresencePrivacyChange({
+                      presence_hidden_game_display: e.target.value as PresenceHiddenGameDisplay,
+                    })
+                  }
+                  className="input w-full"
+                >
+                  <option value="generic">Show "Playing a game"</option>
+                  <option value="idle">Keep the idle presence</option>
+                </select>
+                <p className="text-xs text-text-muted mt-1">
+                  The game's name is left out of the friends presence too
+                </p>
+              </div>
+            </div>
+
+            {/* Custom status, over the automatic presence for a while */}
+            <div className="mt-4">
+              <label className="block text-sm font-medium text-text-secondary mb-2">
+                Custom Status
+              </label>
+              <div className="flex flex-wrap gap-2">
+                <input
+                  type="text"
+                  value={customDetails}
+                  onChange={(e) => setCustomDetails(e.target.value)}
+                  placeholder="Details, e.g. Streaming tonight at 8"
+                  maxLength={128}
+                  className="input flex-1"
+                />
+                <input
+                  type="text"
+                  value={customState}
+                  onChange={(e) => setCustomState(e.target.value)}
+                  placeholder="State"
+                  maxLength={128}
+                  className="input flex-1"
+                />
+                <input
+                  type="number"
+                  min={1}
+                  max={1440}
+                  value={customMinutes}
+                  onChange={(e) => setCustomMinutes(Number(e.target.value))}
+                  className="input w-24"
+                  title="Minutes"
+                />
+                <button type="button" onClick={handleSetCustomPresence} className="btn btn-secondary">
+                  Set
+                </button>
+                {currentPresence?.mode === 'custom' && (
+                  <button type="button" onClick={handleClearCustomPresence} className="btn btn-secondary">
+                    Clear
+                  </button>
+                )}
+              </div>
+              {fieldErrors.custom_presence && (
+                <p className="text-xs text-red-400 mt-1">{fieldErrors.custom_presence}</p>
+              )}
+              {currentPresence?.mode === 'custom' && currentPresence.custom_until !== null && (
+                <p className="text-xs text-text-muted mt-1">
+                  Shown until {new Date(currentPresence.custom_until * 1000).toLocaleTimeString()}, then back to
+                  the automatic presence
+                </p>
+              )}
+            </div>
+
+            {/* Presence buttons */}
+            <div className="mt-4">
+              <label className="block text-sm font-medium text-text-secondary mb-2">
+                Buttons
+              </label>
+              {[0, 1].map((index) => (
+                <div key={index} className="flex gap-2 mb-2">
+                  <input
+                    type="text"
+                    value={presenceButtons[index]?.label ?? ''}
+                    onChange={(e) => handlePresenceButtonChange(index, 'label', e.target.value)}
+                    placeholder={index === 0 ? 'My friend code' : 'Download Atlas'}
+                    maxLength={32}
+                    className="input w-48"
+                  />
+                  <input
+                    type="text"
+                    value={presenceButtons[index]?.url ?? ''}
+                    onChange={(e) => handlePresenceButtonChange(index, 'url', e.target.value)}
+                    placeholder="https://"
+                    className="input flex-1"
+                  />
+                </div>
+              ))}
+              {fieldErrors.discord_presence_buttons && (
+                <p className="text-xs text-red-400 mb-2">{fieldErrors.discord_presence_buttons}</p>
+              )}
+              <div className="flex gap-2">
+                <button type="button" onClick={handleSavePresenceButtons} className="btn btn-secondary">
+                  Save Buttons
+                </button>
+                <button type="button" onClick={handlePreviewPresence} className="btn btn-secondary">
+                  Preview
+                </button>
+              </div>
+            </div>
+
+            {presencePreview && (
+              <div className="mt-4 p-3 rounded-lg bg-white/5 text-sm">
+                <p className="text-text-primary">{presencePreview.details ?? <em className="text-text-muted">no details line</em>}</p>
+                <p className="text-text-secondary">{presencePreview.state ?? <em className="text-text-muted">no state line</em>}</p>
+                {presencePreview.start_timestamp !== null && (
+                  <p className="text-xs text-text-muted">
+                    Elapsed since {new Date(presencePreview.start_timestamp * 1000).toLocaleTimeString()}
+                  </p>
+                )}
+                {presencePreview.buttons.map((b) => (
+                  <p key={b.label} className="text-xs text-text-muted">
+                    [{b.label}] → {b.url}
+                  </p>
+                ))}
+              </div>
+            )}
           </div>
 
           {/* Startup & Tray Behavior */}
@@ -835,6 +1575,98 @@ export function Settings() {
             </div>
           </div>
 
+          {/* Updates */}
+          <div className="card">
+            <div className="flex items-center gap-2 mb-4">
+              <RefreshCw size={18} className="text-cyan-400" />
+              <h2 className="card-title mb-0">Updates</h2>
+            </div>
+
+            <label className="block text-sm font-medium text-text-secondary mb-2">
+              Release Channel
+            </label>
+            <CustomSelect
+              value={updateChannel}
+              onChange={handleUpdateChannelChange}
+              disabled={saving}
+              options={UPDATE_CHANNEL_OPTIONS}
+            />
+            <p className="text-xs text-text-muted mt-1">
+              Beta builds get new features first but may be less stable. After switching back to Stable, the
+              next update check offers the latest stable release even though it is older than the installed beta.
+            </p>
+
+            {/* Auto-download Toggle */}
+            <div className="flex items-center justify-between mt-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary">
+                  Download Updates Automatically
+                </label>
+                <p className="text-xs text-text-muted mt-0.5">
+                  Download new versions in the background and notify when ready. Nothing is installed until you choose to.
+                </p>
+              </div>
+              <button
+                type="button"
+                onClick={handleAutoDownloadUpdatesToggle}
+                disabled={saving}
+                className={`
+                  p-1 rounded-lg transition-colors
+                  ${autoDownloadUpdates
+                    ? 'text-cyan-400 hover:text-cyan-300'
+                    : 'text-text-muted hover:text-text-secondary'
+                  }
+                `}
+              >
+                {autoDownloadUpdates ? <ToggleRight size={32} /> : <ToggleLeft size={32} />}
+              </button>
+            </div>
+
+            {autoDownloadUpdates && (
+              <div className="mt-4">
+                <label className="block text-sm font-medium text-text-secondary mb-2">
+                  Check Every (hours)
+                </label>
+                <input
+                  type="number"
+                  min="1"
+                  max="168"
+                  value={updateCheckIntervalHours || ''}
+                  onChange={(e) => setUpdateCheckIntervalHours(parseInt(e.target.value) || 0)}
+                  onBlur={handleUpdateCheckIntervalBlur}
+                  className="input w-32"
+                />
+                {fieldErrors.update_check_interval_hours && (
+                  <p className="text-xs text-red-400 mt-1">{fieldErrors.update_check_interval_hours}</p>
+                )}
+              </div>
+            )}
+
+            {rollbackInfo?.available && (
+              <div className="flex items-center justify-between mt-4">
+                <div>
+                  <label className="block text-sm font-medium text-text-secondary">
+                    Roll Back to {rollbackInfo.version}
+                  </label>
+                  <p className="text-xs text-text-muted mt-0.5">
+                    Reinstall the version {rollbackInfo.replaced_by} replaced.
+                    {rollbackInfo.expires_at &&
+                      ` Available until ${new Date(rollbackInfo.expires_at).toLocaleDateString()}.`}
+                  </p>
+                </div>
+                <button
+                  type="button"
+                  onClick={handleRollback}
+                  disabled={rollingBack}
+                  className="btn btn-secondary"
+                >
+                  <RotateCcw size={16} />
+                  {rollingBack ? 'Rolling Back...' : 'Roll Back'}
+                </button>
+              </div>
+            )}
+          </div>
+
           {/* Task Monitor */}
           <div className="card">
             <div className="flex items-center gap-2 mb-4">
@@ -996,6 +1828,9 @@ export function Settings() {
                     className="input"
                     placeholder="https://updates.example.com/atlas"
                   />
+                  {fieldErrors.update_url_base && (
+                    <p className="text-xs text-red-400 mt-1">{fieldErrors.update_url_base}</p>
+                  )}
                   <p className="text-xs text-text-muted mt-1">
                     Public URL base for update files. Used for update.json verification.
                   </p>
@@ -1004,6 +1839,272 @@ export function Settings() {
             )}
           </div>
 
+          {/* Profiles */}
+          <div className="card">
+            <div className="flex items-center gap-2 mb-4">
+              <Layers size={18} className="text-sky-400" />
+              <h2 className="card-title mb-0">Profiles</h2>
+            </div>
+
+            <div className="flex items-center justify-between mb-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary">
+                  Switch During Games
+                </label>
+                <p className="text-xs text-text-muted mt-0.5">
+                  Apply the "Gaming" profile when a game starts and switch back when it closes
+                </p>
+              </div>
+              <button
+                type="button"
+                onClick={handleProfileAutoSwitchToggle}
+                className={`
+                  p-1 rounded-lg transition-colors
+                  ${profileAutoSwitch
+                    ? 'text-sky-400 hover:text-sky-300'
+                    : 'text-text-muted hover:text-text-secondary'
+                  }
+                `}
+              >
+                {profileAutoSwitch ? <ToggleRight size={32} /> : <ToggleLeft size={32} />}
+              </button>
+            </div>
+
+            {profiles.length > 0 && (
+              <div className="space-y-2 mb-4">
+                {profiles.map((profile) => (
+                  <div
+                    key={profile.name}
+                    className="flex items-center justify-between p-2 rounded-lg bg-white/5"
+                  >
+                    <div>
+                      <p className="text-sm text-text-primary">{profile.name}</p>
+                      <p className="text-xs text-text-muted">
+                        Saved {new Date(profile.saved_at).toLocaleString()}
+                      </p>
+                    </div>
+                    <div className="flex items-center gap-2">
+                      <button
+                        type="button"
+                        onClick={() => handleApplyProfile(profile.name)}
+                        disabled={profileBusy}
+                        className="btn btn-secondary"
+                      >
+                        Apply
+                      </button>
+                      <button
+                        type="button"
+                        onClick={() => handleDeleteProfile(profile.name)}
+                        disabled={profileBusy}
+                        className="p-2 rounded-lg text-text-muted hover:text-red-400 transition-colors"
+                        title="Delete profile"
+                      >
+                        <Trash2 size={16} />
+                      </button>
+                    </div>
+                  </div>
+                ))}
+              </div>
+            )}
+
+            <div className="flex items-center gap-3">
+              <input
+                type="text"
+                value={profileName}
+                onChange={(e) => setProfileName(e.target.value)}
+                placeholder="Profile name, e.g. Gaming"
+                className="input flex-1"
+                maxLength={32}
+                disabled={profileBusy}
+              />
+              <button
+                type="button"
+                onClick={handleSaveProfile}
+                disabled={profileBusy || !profileName.trim()}
+                className="btn btn-secondary"
+              >
+                <Save size={16} />
+                Save Current
+              </button>
+            </div>
+          </div>
+
+          {/* Backup */}
+          <div className="card">
+            <div className="flex items-center gap-2 mb-4">
+              <Archive size={18} className="text-emerald-400" />
+              <h2 className="card-title mb-0">Backup</h2>
+            </div>
+
+            <div className="flex items-center justify-between mb-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary">
+                  Include Credentials
+                </label>
+                <p className="text-xs text-text-muted mt-0.5">
+                  Also export and import the SSH password, Riot logins and API keys
+                </p>
+              </div>
+              <button
+                type="button"
+                onClick={() => setBackupIncludeSensitive(!backupIncludeSensitive)}
+                disabled={backupBusy}
+                className={`
+                  p-1 rounded-lg transition-colors
+                  ${backupIncludeSensitive
+                    ? 'text-emerald-400 hover:text-emerald-300'
+                    : 'text-text-muted hover:text-text-secondary'
+                  }
+                `}
+              >
+                {backupIncludeSensitive ? <ToggleRight size={32} /> : <ToggleLeft size={32} />}
+              </button>
+            </div>
+
+            <div className="flex items-center gap-3">
+              <button
+                type="button"
+                onClick={handleExportSettings}
+                disabled={backupBusy}
+                className="btn btn-secondary"
+              >
+                <Download size={16} />
+                Export Settings
+              </button>
+              <button
+                type="button"
+                onClick={handleImportSettings}
+                disabled={backupBusy}
+                className="btn btn-secondary"
+              >
+                <Upload size={16} />
+                Import Settings
+              </button>
+              {backupBusy && <Loader2 size={16} className="animate-spin text-text-muted" />}
+            </div>
+          </div>
+
+          {/* Data Location */}
+          {dataDirectory && (
+            <div className="card">
+              <div className="flex items-center gap-2 mb-4">
+                <HardDrive size={18} className="text-amber-400" />
+                <h2 className="card-title mb-0">Data Location</h2>
+              </div>
+
+              <div className="flex items-center justify-between mb-3">
+                <div className="min-w-0">
+                  <p className="text-sm text-text-primary truncate">{dataDirectory.path}</p>
+                  <p className="text-xs text-text-muted mt-0.5">
+                    {formatBytes(dataDirectory.total_bytes)}
+                    {dataDirectory.is_default ? ' · default location' : ''}
+                  </p>
+                </div>
+                <button
+                  type="button"
+                  onClick={() => handleMoveDataDirectory()}
+                  disabled={migrating}
+                  className="btn btn-secondary"
+                >
+                  <FolderOpen size={16} />
+                  Move...
+                </button>
+              </div>
+
+              {dataDirectory.folders.length > 0 && (
+                <div className="grid grid-cols-2 gap-x-4 gap-y-1 mb-3">
+                  {dataDirectory.folders.slice(0, 8).map((folder) => (
+                    <div key={folder.name} className="flex justify-between text-xs">
+                      <span className="text-text-secondary">{folder.name}</span>
+                      <span className="text-text-muted">{formatBytes(folder.bytes)}</span>
+                    </div>
+                  ))}
+                </div>
+              )}
+
+              {dataDirectory.interrupted_migration && !migrating && (
+                <div className="flex items-center justify-between p-2 rounded-lg bg-amber-500/10 text-xs text-amber-400">
+                  <span>A move to {dataDirectory.interrupted_migration.to} was interrupted</span>
+                  <button
+                    type="button"
+                    onClick={() => handleMoveDataDirectory(dataDirectory.interrupted_migration?.to)}
+                    className="btn btn-secondary"
+                  >
+                    Resume
+                  </button>
+                </div>
+              )}
+
+              {migrating && (
+                <div>
+                  <div className="h-2 rounded-full bg-white/10 overflow-hidden">
+                    <div
+                      className="h-full bg-amber-400 transition-all"
+                      style={{
+                        width: `${migrationProgress && migrationProgress.files_total > 0
+                          ? (migrationProgress.files_done / migrationProgress.files_total) * 100
+                          : 0}%`,
+                      }}
+                    />
+                  </div>
+                  <p className="text-xs text-text-muted mt-1">
+                    {migrationProgress
+                      ? `${migrationProgress.stage === 'copying' ? 'Copying' : 'Verifying'} ` +
+                        `${migrationProgress.files_done} / ${migrationProgress.files_total} files ` +
+                        `(${formatBytes(migrationProgress.bytes_done)} / ${formatBytes(migrationProgress.bytes_total)})`
+                      : 'Preparing...'}
+                  </p>
+                </div>
+              )}
+            </div>
+          )}
+
+          {/* Reset */}
+          <div className="card">
+            <div className="flex items-center gap-2 mb-4">
+              <RotateCcw size={18} className="text-red-400" />
+              <h2 className="card-title mb-0">Reset</h2>
+            </div>
+
+            <div className="flex items-center justify-between mb-4">
+              <div>
+                <label className="block text-sm font-medium text-text-secondary">Settings</label>
+                <p className="text-xs text-text-muted mt-0.5">
+                  Restore every setting to its default; the old settings file is backed up
+                </p>
+              </div>
+              <button
+                type="button"
+                onClick={handleResetSettings}
+                disabled={resetting}
+                className="btn btn-secondary"
+              >
+                Reset Settings
+              </button>
+            </div>
+
+            <div className="flex items-center gap-3">
+              <div className="flex-1">
+                <CustomSelect
+                  value={resetFeature}
+                  onChange={setResetFeature}
+                  disabled={resetting}
+                  options={DATA_FEATURE_OPTIONS}
+                />
+              </div>
+              <button
+                type="button"
+                onClick={handleResetFeature}
+                disabled={resetting}
+                className="btn btn-secondary"
+              >
+                <Trash2 size={16} />
+                Reset Data
+              </button>
+              {resetting && <Loader2 size={16} className="animate-spin text-text-muted" />}
+            </div>
+          </div>
+
           {/* Save Button */}
           <div className="flex items-center gap-4">
             <button
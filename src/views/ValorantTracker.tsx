@@ -2,7 +2,16 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { ValorantStore, AuthStatus } from '../types';
+import { useDataReset } from '../hooks';
+import type {
+  ValorantStore,
+  AuthStatus,
+  ValorantUpdatedEvent,
+  ValorantAuthRequiredEvent,
+  ValorantWishlistItem,
+  StoreStreaks,
+  ValorantAccountInfo,
+} from '../types';
 import {
   Gamepad2,
   RefreshCw,
@@ -18,28 +27,74 @@ import {
   X,
   History,
   Sparkles,
+  Moon,
+  Heart,
+  Plus,
+  Trash2,
+  Package,
 } from 'lucide-react';
 
+// Item image, preferring the downloaded copy so past stores render offline
+function SkinImage({
+  imageUrl,
+  localImage,
+  alt,
+  className,
+}: {
+  imageUrl: string | null;
+  localImage?: string;
+  alt: string;
+  className: string;
+}) {
+  const [localSrc, setLocalSrc] = useState<string | null>(null);
+
+  useEffect(() => {
+    setLocalSrc(null);
+    if (localImage) {
+      invoke<string>('get_icon_base64', { iconPath: localImage })
+        .then(setLocalSrc)
+        .catch(() => setLocalSrc(null));
+    }
+  }, [localImage]);
+
+  const src = localSrc ?? imageUrl;
+  if (!src) {
+    return (
+      <div className={`${className} bg-white/5 flex items-center justify-center`}>
+        <Sparkles size={24} className="text-text-muted" />
+      </div>
+    );
+  }
+  return <img src={src} alt={alt} className={`${className} object-contain`} />;
+}
+
 export function ValorantTracker() {
   const [currentStore, setCurrentStore] = useState<ValorantStore | null>(null);
   const [history, setHistory] = useState<ValorantStore[]>([]);
   const [loading, setLoading] = useState(true);
   const [checking, setChecking] = useState(false);
   const [error, setError] = useState<string | null>(null);
-  const [autoRefreshing, setAutoRefreshing] = useState(false);
+  const [wishlist, setWishlist] = useState<ValorantWishlistItem[]>([]);
+  const [wishlistInput, setWishlistInput] = useState('');
+  const [streaks, setStreaks] = useState<StoreStreaks | null>(null);
 
   // Auth state
   const [authStatus, setAuthStatus] = useState<AuthStatus | null>(null);
   const [isLoggingIn, setIsLoggingIn] = useState(false);
+  const [accounts, setAccounts] = useState<ValorantAccountInfo[]>([]);
+
+  useDataReset('valorant_store', () => {
+    setCurrentStore(null);
+    fetchStoreData();
+    fetchHistory();
+  });
 
   useEffect(() => {
     // Load initial data
     fetchStoreData();
     fetchHistory();
     fetchAuthStatus();
-
-    // Check if we should auto-refresh (once per store rotation)
-    checkAutoRefresh();
+    fetchWishlist();
 
     const unlistenSuccess = listen('riot-auth-success', () => {
       setIsLoggingIn(false);
@@ -52,9 +107,22 @@ export function ValorantTracker() {
       setError(`Authentication failed: ${event.payload}`);
     });
 
+    // The backend refreshes the store at each reset
+    const unlistenUpdated = listen<ValorantUpdatedEvent>('valorant:store_updated', (event) => {
+      setCurrentStore(event.payload.store);
+      fetchHistory();
+    });
+
+    const unlistenAuthRequired = listen<ValorantAuthRequiredEvent>('valorant:auth_required', (event) => {
+      setError(`${event.payload.reason}. Please login again to refresh the store.`);
+      fetchAuthStatus();
+    });
+
     return () => {
       unlistenSuccess.then(fn => fn());
       unlistenError.then(fn => fn());
+      unlistenUpdated.then(fn => fn());
+      unlistenAuthRequired.then(fn => fn());
     };
   }, []);
 
@@ -62,37 +130,12 @@ export function ValorantTracker() {
     try {
       const status = await invoke<AuthStatus>('get_auth_status');
       setAuthStatus(status);
+      setAccounts(await invoke<ValorantAccountInfo[]>('list_valorant_accounts'));
     } catch (err) {
       console.error('Failed to fetch auth status:', err);
     }
   }
 
-  async function checkAutoRefresh() {
-    try {
-      const shouldRefresh = await invoke<boolean>('should_auto_refresh_store');
-      if (shouldRefresh) {
-        console.log('Auto-refreshing store (new rotation detected)');
-        setAutoRefreshing(true);
-        // Wait for auth status to load first
-        const status = await invoke<AuthStatus>('get_auth_status');
-        if (status?.is_authenticated) {
-          await invoke<ValorantStore>('check_valorant_store', {
-            region: status.region || 'ap'
-          }).then(result => {
-            setCurrentStore(result);
-            fetchHistory();
-          }).catch(err => {
-            console.error('Auto-refresh failed:', err);
-          });
-        }
-        setAutoRefreshing(false);
-      }
-    } catch (err) {
-      console.error('Failed to check auto-refresh:', err);
-      setAutoRefreshing(false);
-    }
-  }
-
   async function fetchStoreData() {
     try {
       setLoading(true);
@@ -110,11 +153,41 @@ export function ValorantTracker() {
     try {
       const result = await invoke<ValorantStore[]>('get_store_history', { limit: 10 });
       setHistory(result);
+      setStreaks(await invoke<StoreStreaks>('get_store_streaks'));
     } catch (err) {
       console.error('Failed to fetch history:', err);
     }
   }
 
+  async function fetchWishlist() {
+    try {
+      const result = await invoke<ValorantWishlistItem[]>('get_valorant_wishlist');
+      setWishlist(result);
+    } catch (err) {
+      console.error('Failed to fetch wishlist:', err);
+    }
+  }
+
+  async function handleAddWishlistItem() {
+    if (!wishlistInput.trim()) return;
+    try {
+      await invoke('add_valorant_wishlist_item', { skinNameOrUuid: wishlistInput });
+      setWishlistInput('');
+      fetchWishlist();
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleRemoveWishlistItem(id: string) {
+    try {
+      await invoke('remove_valorant_wishlist_item', { id });
+      fetchWishlist();
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
   async function handleCheckStore() {
     try {
       setChecking(true);
@@ -131,11 +204,11 @@ export function ValorantTracker() {
     }
   }
 
-  async function handleLogin() {
+  async function handleLogin(addAccount = false) {
     try {
       setIsLoggingIn(true);
       setError(null);
-      await invoke('open_auth_window');
+      await invoke('open_auth_window', { addAccount });
     } catch (err) {
       setError(String(err));
       setIsLoggingIn(false);
@@ -160,6 +233,19 @@ export function ValorantTracker() {
       await invoke('logout');
       setAuthStatus(null);
       fetchAuthStatus();
+      fetchStoreData();
+      fetchHistory();
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleSwitchAccount(id: string) {
+    try {
+      await invoke('set_active_valorant_account', { id });
+      fetchAuthStatus();
+      fetchStoreData();
+      fetchHistory();
     } catch (err) {
       setError(String(err));
     }
@@ -222,6 +308,9 @@ export function ValorantTracker() {
                       {authStatus.has_full_cookies ? 'Full Auth' : 'Partial'}
                     </span>
                   </h3>
+                  {authStatus.username && (
+                    <p className="text-sm text-text-muted">{authStatus.username}</p>
+                  )}
                 </div>
               </>
             ) : (
@@ -236,14 +325,37 @@ export function ValorantTracker() {
               </>
             )}
           </div>
-          <div>
+          <div className="flex items-center gap-2">
+            {accounts.length > 1 && (
+              <select
+                value={accounts.find((a) => a.is_active)?.id ?? ''}
+                onChange={(e) => handleSwitchAccount(e.target.value)}
+                className="input py-1 text-sm"
+              >
+                {accounts.map((account) => (
+                  <option key={account.id} value={account.id}>
+                    {account.name ?? `Account ${account.id.slice(0, 8)}`}
+                  </option>
+                ))}
+              </select>
+            )}
             {authStatus?.is_authenticated ? (
-              <button onClick={handleLogout} className="btn btn-ghost btn-sm">
-                <LogOut size={16} />
-                Logout
-              </button>
+              <>
+                <button
+                  onClick={() => handleLogin(true)}
+                  disabled={isLoggingIn}
+                  className="btn btn-ghost btn-sm"
+                  title="Add another Riot account"
+                >
+                  <Plus size={16} />
+                </button>
+                <button onClick={handleLogout} className="btn btn-ghost btn-sm">
+                  <LogOut size={16} />
+                  Logout
+                </button>
+              </>
             ) : (
-              <button onClick={handleLogin} disabled={isLoggingIn} className="btn btn-primary btn-sm">
+              <button onClick={() => handleLogin()} disabled={isLoggingIn} className="btn btn-primary btn-sm">
                 {isLoggingIn ? (
                   <>
                     <Loader2 size={16} className="animate-spin" />
@@ -301,18 +413,13 @@ export function ValorantTracker() {
           )}
         </div>
 
-        {(loading || autoRefreshing) && (
+        {loading && (
           <div className="card flex items-center justify-center py-12">
-            <div className="flex flex-col items-center gap-2">
-              <Loader2 size={32} className="animate-spin text-red-400" />
-              {autoRefreshing && (
-                <span className="text-sm text-text-muted">Auto-refreshing store...</span>
-              )}
-            </div>
+            <Loader2 size={32} className="animate-spin text-red-400" />
           </div>
         )}
 
-        {!loading && !autoRefreshing && !currentStore && (
+        {!loading && !currentStore && (
           <div className="card empty-state">
             <ShoppingBag className="empty-state-icon" />
             <h3 className="empty-state-title">No store data</h3>
@@ -357,17 +464,12 @@ export function ValorantTracker() {
                     style={{ animationDelay: `${idx * 50}ms` }}
                   >
                     <div className="flex gap-4">
-                      {item.image_url ? (
-                        <img
-                          src={item.image_url}
-                          alt={item.name}
-                          className="w-20 h-16 object-contain rounded"
-                        />
-                      ) : (
-                        <div className="w-20 h-16 rounded bg-white/5 flex items-center justify-center">
-                          <Sparkles size={24} className="text-text-muted" />
-                        </div>
-                      )}
+                      <SkinImage
+                        imageUrl={item.image_url}
+                        localImage={item.local_image}
+                        alt={item.name}
+                        className="w-20 h-16 rounded"
+                      />
                       <div className="flex-1 min-w-0">
                         <h4 className="font-medium text-white truncate mb-1">{item.name}</h4>
                         <p className="text-lg font-bold text-accent-primary">
@@ -383,11 +485,156 @@ export function ValorantTracker() {
         )}
       </div>
 
+      {/* Night Market */}
+      {currentStore?.night_market && currentStore.night_market.items.length > 0 && (
+        <div className="mb-6">
+          <div className="flex items-center gap-2 mb-4">
+            <Moon size={20} className="text-text-secondary" />
+            <h2 className="text-lg font-semibold text-white">Night Market</h2>
+            <span className="text-sm text-text-muted">
+              Ends {new Date(currentStore.night_market.expires_at).toLocaleString()}
+            </span>
+          </div>
+
+          <div className="card">
+            <div className="grid grid-cols-3 gap-4">
+              {currentStore.night_market.items.map((item, idx) => (
+                <div key={idx} className="glass rounded-lg p-4">
+                  <SkinImage
+                    imageUrl={item.image_url}
+                    localImage={item.local_image}
+                    alt={item.name}
+                    className="w-full h-16 rounded mb-2"
+                  />
+                  <h4 className="font-medium text-white truncate mb-1">{item.name}</h4>
+                  <div className="flex items-baseline gap-2">
+                    <span className="text-lg font-bold text-accent-primary">
+                      {item.final_price.toLocaleString()} VP
+                    </span>
+                    <span className="text-sm text-text-muted line-through">
+                      {item.original_price.toLocaleString()}
+                    </span>
+                    <span className="badge badge-success">-{item.discount_percent}%</span>
+                  </div>
+                </div>
+              ))}
+            </div>
+          </div>
+        </div>
+      )}
+
+      {/* Featured Bundles */}
+      {currentStore?.bundle && (
+        <div className="mb-6">
+          <div className="flex items-center gap-2 mb-4">
+            <Package size={20} className="text-text-secondary" />
+            <h2 className="text-lg font-semibold text-white">Featured Bundle</h2>
+          </div>
+
+          <div className="space-y-4">
+            {[currentStore.bundle, ...(currentStore.other_bundles ?? [])].map((bundle) => (
+              <div key={bundle.uuid} className="card">
+                <div className="flex items-center justify-between mb-4 pb-4 border-b border-white/10">
+                  <div>
+                    <h3 className="font-semibold text-white">{bundle.name}</h3>
+                    <span className="text-sm text-text-muted">
+                      Ends {new Date(bundle.expires_at).toLocaleString()}
+                    </span>
+                  </div>
+                  <div className="text-right">
+                    <p className="text-lg font-bold text-accent-primary">
+                      {bundle.bundle_price.toLocaleString()} VP
+                    </p>
+                    {bundle.base_price > bundle.bundle_price && (
+                      <p className="text-sm text-text-muted line-through">
+                        {bundle.base_price.toLocaleString()} VP
+                      </p>
+                    )}
+                  </div>
+                </div>
+                <div className="grid grid-cols-2 gap-2">
+                  {bundle.items.map((item) => (
+                    <div
+                      key={item.uuid}
+                      className="flex items-center justify-between p-2 rounded-lg glass-subtle"
+                    >
+                      <span className="text-sm text-white truncate">{item.name}</span>
+                      <span className="text-sm text-text-muted">
+                        {item.base_price.toLocaleString()} VP
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
+      {/* Wishlist */}
+      <div className="mb-6">
+        <div className="flex items-center gap-2 mb-4">
+          <Heart size={20} className="text-text-secondary" />
+          <h2 className="text-lg font-semibold text-white">Wishlist</h2>
+        </div>
+
+        <div className="card">
+          <div className="flex gap-2 mb-4">
+            <input
+              type="text"
+              value={wishlistInput}
+              onChange={(e) => setWishlistInput(e.target.value)}
+              onKeyDown={(e) => e.key === 'Enter' && handleAddWishlistItem()}
+              placeholder="Skin name or UUID"
+              className="input flex-1"
+            />
+            <button
+              onClick={handleAddWishlistItem}
+              disabled={!wishlistInput.trim()}
+              className="btn btn-secondary flex items-center gap-2"
+            >
+              <Plus size={16} />
+              Add
+            </button>
+          </div>
+
+          {wishlist.length === 0 ? (
+            <p className="text-sm text-text-muted">
+              You'll be notified when a wishlisted skin shows up in your store
+            </p>
+          ) : (
+            <div className="space-y-2">
+              {wishlist.map((item) => (
+                <div
+                  key={item.id}
+                  className="flex items-center justify-between p-3 rounded-lg glass-subtle"
+                >
+                  <span className="text-white truncate">{item.name ?? item.skin_uuid}</span>
+                  <button
+                    onClick={() => handleRemoveWishlistItem(item.id)}
+                    className="text-text-muted hover:text-red-400 transition-colors"
+                  >
+                    <Trash2 size={16} />
+                  </button>
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      </div>
+
       {/* Store History */}
       <div>
         <div className="flex items-center gap-2 mb-4">
           <History size={20} className="text-text-secondary" />
           <h2 className="text-lg font-semibold text-white">Store History</h2>
+          {streaks && streaks.rotations_tracked > 0 && (
+            <span className="ml-auto text-sm text-text-muted">
+              {streaks.rotations_tracked} rotations, {streaks.distinct_skins_seen} skins
+              {streaks.days_since_wishlist_hit !== null &&
+                ` · ${streaks.days_since_wishlist_hit} days since a wishlist skin`}
+            </span>
+          )}
         </div>
 
         {history.length === 0 ? (
@@ -409,6 +656,14 @@ export function ValorantTracker() {
                   </div>
                   <div className="flex items-center gap-3">
                     <span className="text-sm text-text-muted">{store.items.length} items</span>
+                    {store.wishlist_hits && store.wishlist_hits.length > 0 && (
+                      <span
+                        className="badge badge-success text-xs"
+                        title={store.wishlist_hits.map((hit) => hit.name).join(', ')}
+                      >
+                        wishlist
+                      </span>
+                    )}
                     {store.is_real_data === false && (
                       <span className="badge badge-warning text-xs">mock</span>
                     )}